@@ -3,13 +3,19 @@
 
 allocator::assign_global!();
 
-use clap::{crate_version, ArgGroup, Parser, ValueEnum};
+use clap::{crate_version, ArgGroup, Args, Parser, Subcommand, ValueEnum};
 use log::info;
 use miette::{Context, IntoDiagnostic, Report};
+use qsc::cache::Cache;
+use qsc::generate_html::generate_html_docs;
 use qsc::hir::PackageId;
+use qsc::linter::{has_errors, lints_to_json, lints_to_sarif, run_lints, LintConfig, LintLevel};
 use qsc::packages::BuildableProgram;
-use qsc::{compile::compile, PassContext};
-use qsc_codegen::qir::fir_to_qir;
+use qsc::target::{read_target_file, TargetDescription};
+use qsc::watch::{Input, WatchedProgram};
+use qsc::{compile::compile, incremental, PassContext};
+use qsc::{fir, lower_hir_to_fir};
+use qsc_codegen::qir::{fir_to_rir, rir_to_qir};
 use qsc_data_structures::{language_features::LanguageFeatures, target::TargetCapabilityFlags};
 use qsc_frontend::{
     compile::{PackageStore, SourceContents, SourceMap, SourceName},
@@ -19,16 +25,21 @@ use qsc_hir::hir::Package;
 use qsc_partial_eval::ProgramEntry;
 use qsc_passes::PackageType;
 use qsc_project::{FileSystem, StdFs};
+use qsc_rca::{report::PackageReport, Analyzer};
+use qsc_rir::{passes::check_target, rir::Program};
+use serde::Serialize;
 use std::sync::Arc;
 use std::{
-    concat, fs,
+    concat,
+    fmt::Display,
+    fs,
     io::{self, Read},
     path::{Path, PathBuf},
     process::ExitCode,
     string::String,
 };
 
-#[derive(clap::ValueEnum, Clone, Debug, Default, PartialEq)]
+#[derive(clap::ValueEnum, Clone, Copy, Debug, Default, PartialEq)]
 pub enum Profile {
     /// This is the default profile, which allows all operations.
     #[default]
@@ -54,6 +65,9 @@ impl From<Profile> for qsc::target::Profile {
 #[command(version = concat!(crate_version!(), " (", env!("QSHARP_GIT_HASH"), ")"), arg_required_else_help(false))]
 #[clap(group(ArgGroup::new("input").args(["entry", "sources"]).required(false).multiple(true)))]
 struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+
     /// Disable automatic inclusion of the standard library.
     #[arg(long)]
     nostdlib: bool,
@@ -78,6 +92,11 @@ struct Cli {
     #[arg(short, long)]
     profile: Option<Profile>,
 
+    /// Target capability file that describes the capabilities, the intrinsic gates and the
+    /// limits of the hardware to generate code for, instead of a QIR profile.
+    #[arg(long, value_name = "FILE", conflicts_with = "profile")]
+    target: Option<PathBuf>,
+
     /// Q# source files to compile, or `-` to read from stdin.
     #[arg()]
     sources: Vec<PathBuf>,
@@ -89,31 +108,165 @@ struct Cli {
     /// Language features to compile with
     #[arg(short, long)]
     features: Vec<String>,
+
+    /// Load the core and standard libraries and project dependencies from compiled packages
+    /// in the given directory, compiling and saving them there when they are missing.
+    #[arg(long, value_name = "DIR")]
+    cache_dir: Option<PathBuf>,
+
+    /// Watch the source files for changes, and compile them again and regenerate the emitted
+    /// outputs whenever they change.
+    #[arg(long)]
+    watch: bool,
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
 enum Emit {
+    /// The abstract syntax tree, as `ast.txt` and `ast.json`.
+    Ast,
+    /// The high-level intermediate representation, as `hir.txt`.
     Hir,
+    /// The flattened intermediate representation, as `fir.txt` and `fir.json`.
+    Fir,
+    /// The compute properties of each callable from runtime capabilities analysis, as `rca.txt`
+    /// and `rca.json`.
+    Rca,
+    /// The runtime intermediate representation that QIR is generated from, as `rir.txt` and
+    /// `rir.json`.
+    Rir,
+    /// QIR for the target profile, as `qir.ll`.
     Qir,
 }
 
+impl Emit {
+    /// Whether the output is generated for a target, which needs an executable package.
+    fn is_codegen(self) -> bool {
+        matches!(self, Emit::Rir | Emit::Qir)
+    }
+
+    /// Whether the output is lowered from the HIR, which needs a package without errors.
+    fn is_lowered(self) -> bool {
+        !matches!(self, Emit::Ast | Emit::Hir)
+    }
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Run the linter on a program or project and report the lints at their configured levels.
+    /// Exits with a failure code if any lint is reported as an error.
+    Lint(LintArgs),
+    /// Generate a static HTML documentation site for a program or project, its
+    /// dependencies and the standard library.
+    Doc(DocArgs),
+}
+
+#[derive(Debug, Args)]
+struct LintArgs {
+    /// Output format for the lints.
+    #[arg(long, value_enum, default_value_t = LintFormat::Text)]
+    format: LintFormat,
+
+    /// Disable automatic inclusion of the standard library.
+    #[arg(long)]
+    nostdlib: bool,
+
+    /// Q# source files to lint, or `-` to read from stdin.
+    #[arg()]
+    sources: Vec<PathBuf>,
+
+    /// Path to a Q# manifest for a project. Lint levels are read from its `lints` section.
+    #[arg(short, long, conflicts_with = "sources")]
+    qsharp_json: Option<PathBuf>,
+
+    /// Language features to compile with
+    #[arg(short, long)]
+    features: Vec<String>,
+}
+
+#[derive(Debug, Args)]
+struct DocArgs {
+    /// Directory to write the site to.
+    #[arg(long = "outdir", value_name = "DIR", default_value = "doc")]
+    out_dir: PathBuf,
+
+    /// Disable automatic inclusion of the standard library.
+    #[arg(long)]
+    nostdlib: bool,
+
+    /// Q# source files to document, or `-` to read from stdin.
+    #[arg()]
+    sources: Vec<PathBuf>,
+
+    /// Path to a Q# manifest for a project. Its dependencies are documented as well.
+    #[arg(short, long)]
+    qsharp_json: Option<PathBuf>,
+
+    /// Language features to compile with
+    #[arg(short, long)]
+    features: Vec<String>,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+enum LintFormat {
+    /// Human-readable diagnostics.
+    Text,
+    /// A JSON array of lints.
+    Json,
+    /// A SARIF 2.1.0 log.
+    Sarif,
+}
+
 #[allow(clippy::too_many_lines)]
 fn main() -> miette::Result<ExitCode> {
     env_logger::init();
     let cli = Cli::parse();
-    let profile: qsc::target::Profile = cli.profile.unwrap_or_default().into();
-    let capabilities = profile.into();
-    let package_type = if cli.emit.contains(&Emit::Qir) {
+    match cli.command {
+        Some(Command::Lint(args)) => return lint(args),
+        Some(Command::Doc(args)) => return doc(args),
+        None => {}
+    }
+    let package_type = if cli.emit.iter().any(|emit| emit.is_codegen()) {
         PackageType::Exe
     } else {
         PackageType::Lib
     };
     let mut features = LanguageFeatures::from_iter(cli.features);
+    let cache = cli.cache_dir.map(Cache::new);
+    let out_dir = cli.out_dir.as_deref().unwrap_or(".".as_ref());
+
+    if cli.watch {
+        let input = match (cli.qsharp_json, cli.sources) {
+            (Some(qsharp_json), _) => {
+                let Some(dir) = qsharp_json.parent() else {
+                    eprintln!("{} must have a parent directory", qsharp_json.display());
+                    return Ok(ExitCode::FAILURE);
+                };
+                Input::Project(dir.to_path_buf())
+            }
+            (None, paths) if paths.iter().any(|path| path.as_os_str() == "-") => {
+                eprintln!("standard input can't be watched for changes");
+                return Ok(ExitCode::FAILURE);
+            }
+            (None, paths) => Input::Files {
+                paths,
+                entry: cli.entry.map(Into::into),
+                nostdlib: cli.nostdlib,
+            },
+        };
+        let program =
+            WatchedProgram::new(input, Some(TargetCapabilityFlags::all()), features, cache);
+        let target_file = cli.target.as_deref();
+        watch(program, &cli.emit, out_dir, package_type, target_file, cli.profile);
+    }
 
-    let (mut store, dependencies, source_map) = if let Some(qsharp_json) = cli.qsharp_json {
+    let (mut store, dependencies, source_map, project_target) = if let Some(qsharp_json) =
+        cli.qsharp_json
+    {
         if let Some(dir) = qsharp_json.parent() {
-            match load_project(dir, &mut features) {
-                Ok(items) => items,
+            match load_project(dir, &mut features, cache.as_ref()) {
+                Ok((store, dependencies, source_map, _, target)) => {
+                    (store, dependencies, source_map, target)
+                }
                 Err(exit_code) => return Ok(exit_code),
             }
         } else {
@@ -127,20 +280,34 @@ fn main() -> miette::Result<ExitCode> {
             .map(read_source)
             .collect::<miette::Result<Vec<_>>>()?;
 
-        let mut store = PackageStore::new(qsc::compile::core());
-        let dependencies = if cli.nostdlib {
-            vec![]
-        } else {
-            let std_id = store.insert(qsc::compile::std(&store, TargetCapabilityFlags::all()));
-            vec![(std_id, None)]
+        let (store, dependencies) = match (&cache, cli.nostdlib) {
+            (Some(cache), true) => (PackageStore::new(cache.core()), vec![]),
+            (Some(cache), false) => {
+                let (std_id, store) = cache.package_store_with_stdlib(TargetCapabilityFlags::all());
+                (store, vec![(std_id, None)])
+            }
+            (None, true) => (PackageStore::new(qsc::compile::core()), vec![]),
+            (None, false) => {
+                let (std_id, store) =
+                    qsc::compile::package_store_with_stdlib(TargetCapabilityFlags::all());
+                (store, vec![(std_id, None)])
+            }
         };
         (
             store,
             dependencies,
             SourceMap::new(sources, cli.entry.clone().map(std::convert::Into::into)),
+            None,
         )
     };
 
+    let target = resolve_target(cli.target.as_deref(), cli.profile, project_target)?;
+    let capabilities = target.capabilities;
+    if let Err(message) = check_emits(&cli.emit, package_type, capabilities) {
+        eprintln!("{message}");
+        return Ok(ExitCode::FAILURE);
+    }
+
     let (unit, errors) = compile(
         &store,
         &dependencies,
@@ -150,44 +317,321 @@ fn main() -> miette::Result<ExitCode> {
         features,
     );
     let package_id = store.insert(unit);
-    let unit = store.get(package_id).expect("package should be in store");
+    let compiled = errors.is_empty();
+    let emitted = emit(&cli.emit, out_dir, &store, package_id, &target, compiled);
 
-    let out_dir = cli.out_dir.as_ref().map_or(".".as_ref(), PathBuf::as_path);
-    for emit in &cli.emit {
-        match emit {
-            Emit::Hir => emit_hir(&unit.package, out_dir)?,
-            Emit::Qir => {
-                if package_type != PackageType::Exe {
-                    eprintln!("QIR generation is only supported for executable packages");
-                    return Ok(ExitCode::FAILURE);
+    for error in errors {
+        eprintln!("{:?}", Report::new(error));
+    }
+    if !compiled {
+        let skipped: Vec<_> = cli
+            .emit
+            .iter()
+            .filter(|emit| emit.is_lowered())
+            .map(|emit| format!("{emit:?}").to_uppercase())
+            .collect();
+        if !skipped.is_empty() {
+            eprintln!(
+                "{} not emitted because the program has errors",
+                skipped.join(", ")
+            );
+        }
+    }
+
+    match emitted {
+        Ok(()) if compiled => Ok(ExitCode::SUCCESS),
+        Ok(()) => Ok(ExitCode::FAILURE),
+        Err(reports) => {
+            print_reports(reports);
+            Ok(ExitCode::FAILURE)
+        }
+    }
+}
+
+/// Checks that the requested outputs can be emitted for the package type and target.
+fn check_emits(
+    emits: &[Emit],
+    package_type: PackageType,
+    capabilities: TargetCapabilityFlags,
+) -> Result<(), String> {
+    for emit in emits.iter().filter(|emit| emit.is_codegen()) {
+        let name = format!("{emit:?}").to_uppercase();
+        if package_type != PackageType::Exe {
+            return Err(format!(
+                "{name} generation is only supported for executable packages"
+            ));
+        }
+        if capabilities == TargetCapabilityFlags::all() {
+            return Err(format!(
+                "{name} generation is not supported for unrestricted profile"
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Writes the requested outputs for the compiled package. Only the AST and HIR are written when
+/// the package has errors, since the later forms are lowered from a valid HIR. An output that
+/// fails doesn't stop the others, so that the analysis can still be inspected when the program
+/// can't be generated for the target.
+fn emit(
+    emits: &[Emit],
+    out_dir: &Path,
+    store: &PackageStore,
+    package_id: PackageId,
+    target: &TargetDescription,
+    compiled: bool,
+) -> Result<(), Vec<Report>> {
+    let unit = store.get(package_id).expect("package should be in store");
+    let mut reports = Vec::new();
+    // The FIR and RIR are shared by the outputs that need them, so they are generated only once.
+    let mut fir = None;
+    let mut rir = None;
+    for emit in emits {
+        let result = match emit {
+            Emit::Ast => emit_output(out_dir, "ast", &unit.ast.package),
+            Emit::Hir => emit_hir(&unit.package, out_dir),
+            // The caller reports the outputs skipped because of errors.
+            _ if !compiled => Ok(()),
+            Emit::Fir => {
+                let (fir_store, fir_package_id) =
+                    fir.get_or_insert_with(|| lower_hir_to_fir(store, package_id));
+                emit_output(out_dir, "fir", fir_store.get(*fir_package_id))
+            }
+            Emit::Rca => {
+                let (fir_store, fir_package_id) =
+                    fir.get_or_insert_with(|| lower_hir_to_fir(store, package_id));
+                let compute_properties = Analyzer::init(fir_store).analyze_all();
+                let report = PackageReport::new(fir_store, &compute_properties, *fir_package_id);
+                emit_output(out_dir, "rca", &report)
+            }
+            Emit::Rir | Emit::Qir => {
+                // The errors of generating the RIR are reported once, for the first output
+                // that needs it.
+                let program = rir.get_or_insert_with(|| {
+                    let (fir_store, fir_package_id) =
+                        fir.get_or_insert_with(|| lower_hir_to_fir(store, package_id));
+                    generate_rir(store, package_id, fir_store, *fir_package_id, target)
+                        .map_err(|errors| reports.extend(errors))
+                        .ok()
+                });
+                match program {
+                    Some(program) if *emit == Emit::Rir => emit_output(out_dir, "rir", program),
+                    Some(program) => {
+                        emit_file(&out_dir.join("qir.ll"), "QIR", &rir_to_qir(program))
+                    }
+                    None => Ok(()),
                 }
-                if capabilities == TargetCapabilityFlags::all() {
-                    eprintln!("QIR generation is not supported for unrestricted profile");
-                    return Ok(ExitCode::FAILURE);
+            }
+        };
+        reports.extend(result.err());
+    }
+
+    if reports.is_empty() {
+        Ok(())
+    } else {
+        Err(reports)
+    }
+}
+
+/// Compiles the program and emits the requested outputs, then does it again whenever its files
+/// change. Only the program itself is compiled again, unless its dependencies changed.
+fn watch(
+    mut program: WatchedProgram,
+    emits: &[Emit],
+    out_dir: &Path,
+    package_type: PackageType,
+    target_file: Option<&Path>,
+    profile: Option<Profile>,
+) -> ! {
+    // The target is resolved again along with the dependencies, since the manifest can name a
+    // different one. Until a valid target is found, nothing is compiled.
+    let mut compiler = None;
+    loop {
+        match program.load() {
+            Ok((sources, dependencies)) => {
+                if let Some(dependencies) = dependencies {
+                    compiler = resolve_target(target_file, profile, dependencies.target)
+                        .map_err(|report| eprintln!("{report:?}"))
+                        .and_then(|target| {
+                            check_emits(emits, package_type, target.capabilities)
+                                .map_err(|message| eprintln!("{message}"))?;
+                            let compiler = incremental::Compiler::new(
+                                SourceMap::default(),
+                                PackageType::Lib,
+                                target.capabilities,
+                                dependencies.language_features,
+                                dependencies.store,
+                                &dependencies.dependencies,
+                            )
+                            .expect("empty sources should compile");
+                            Ok((compiler, target))
+                        })
+                        .ok();
                 }
-                if errors.is_empty() {
-                    if let Err(reports) = emit_qir(out_dir, &store, package_id, capabilities) {
-                        for report in reports {
-                            eprintln!("{report:?}");
-                        }
-                        return Ok(ExitCode::FAILURE);
+                if let Some((compiler, target)) = compiler.as_mut() {
+                    let result = compiler
+                        .update_sources(sources, package_type)
+                        .map_err(|errors| errors.into_iter().map(Report::new).collect())
+                        .and_then(|()| {
+                            emit(
+                                emits,
+                                out_dir,
+                                compiler.package_store(),
+                                compiler.source_package_id(),
+                                target,
+                                true,
+                            )
+                        });
+                    match result {
+                        Ok(()) => eprintln!("Compiled successfully."),
+                        Err(reports) => print_reports(reports),
                     }
                 }
             }
+            Err(errors) => print_reports(errors.into_iter().map(Report::new).collect()),
+        }
+
+        eprintln!("Watching for changes...");
+        let changed = program.wait();
+        eprintln!();
+        for path in changed {
+            info!("Changed: {}", path.display());
         }
     }
+}
 
-    if errors.is_empty() {
-        Ok(ExitCode::SUCCESS)
-    } else {
+fn print_reports(reports: Vec<Report>) {
+    for report in reports {
+        eprintln!("{report:?}");
+    }
+}
+
+fn lint(args: LintArgs) -> miette::Result<ExitCode> {
+    let mut features = LanguageFeatures::from_iter(args.features);
+
+    let (mut store, dependencies, source_map, lint_config) =
+        if let Some(qsharp_json) = args.qsharp_json {
+            if let Some(dir) = qsharp_json.parent() {
+                match load_project(dir, &mut features, None) {
+                    Ok((store, dependencies, source_map, lint_config, _)) => {
+                        (store, dependencies, source_map, lint_config)
+                    }
+                    Err(exit_code) => return Ok(exit_code),
+                }
+            } else {
+                eprintln!("{} must have a parent directory", qsharp_json.display());
+                return Ok(ExitCode::FAILURE);
+            }
+        } else {
+            let sources = args
+                .sources
+                .iter()
+                .map(read_source)
+                .collect::<miette::Result<Vec<_>>>()?;
+
+            let mut store = PackageStore::new(qsc::compile::core());
+            let dependencies = if args.nostdlib {
+                vec![]
+            } else {
+                let std_id = store.insert(qsc::compile::std(&store, TargetCapabilityFlags::all()));
+                vec![(std_id, None)]
+            };
+            (store, dependencies, SourceMap::new(sources, None), vec![])
+        };
+
+    let (unit, errors) = compile(
+        &store,
+        &dependencies,
+        source_map,
+        PackageType::Lib,
+        TargetCapabilityFlags::all(),
+        features,
+    );
+
+    // Lints are only meaningful for programs that compile.
+    if !errors.is_empty() {
         for error in errors {
             eprintln!("{:?}", Report::new(error));
         }
+        return Ok(ExitCode::FAILURE);
+    }
+
+    let package_id = store.insert(unit);
+    let unit = store.get(package_id).expect("package should be in store");
+    let lints = run_lints(&store, unit, Some(&lint_config));
+
+    match args.format {
+        LintFormat::Text => {
+            for lint in lints.iter().filter(|lint| lint.level != LintLevel::Allow) {
+                eprintln!(
+                    "{:?}",
+                    Report::new(WithSource::from_map(&unit.sources, lint.clone()))
+                );
+            }
+        }
+        LintFormat::Json => println!("{}", lints_to_json(&lints, &unit.sources)),
+        LintFormat::Sarif => println!("{}", lints_to_sarif(&lints, &unit.sources)),
+    }
 
+    if has_errors(&lints) {
         Ok(ExitCode::FAILURE)
+    } else {
+        Ok(ExitCode::SUCCESS)
     }
 }
 
+fn doc(args: DocArgs) -> miette::Result<ExitCode> {
+    let mut features = LanguageFeatures::from_iter(args.features);
+
+    let (store, dependencies, source_map) = if let Some(qsharp_json) = args.qsharp_json {
+        if let Some(dir) = qsharp_json.parent() {
+            match load_project(dir, &mut features, None) {
+                Ok((store, dependencies, source_map, _, _)) => (store, dependencies, source_map),
+                Err(exit_code) => return Ok(exit_code),
+            }
+        } else {
+            eprintln!("{} must have a parent directory", qsharp_json.display());
+            return Ok(ExitCode::FAILURE);
+        }
+    } else {
+        let sources = args
+            .sources
+            .iter()
+            .map(read_source)
+            .collect::<miette::Result<Vec<_>>>()?;
+
+        let mut store = PackageStore::new(qsc::compile::core());
+        let dependencies = if args.nostdlib {
+            vec![]
+        } else {
+            let std_id = store.insert(qsc::compile::std(&store, TargetCapabilityFlags::all()));
+            vec![(std_id, None)]
+        };
+        (store, dependencies, SourceMap::new(sources, None))
+    };
+
+    let files = generate_html_docs(
+        Some((store, &dependencies, source_map)),
+        Some(TargetCapabilityFlags::all()),
+        Some(features),
+    );
+    for (path, contents) in files {
+        let path = args.out_dir.join(&*path);
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)
+                .into_diagnostic()
+                .with_context(|| format!("could not create directory `{}`", dir.display()))?;
+        }
+        fs::write(&path, &*contents)
+            .into_diagnostic()
+            .with_context(|| format!("could not write documentation file `{}`", path.display()))?;
+    }
+    info!("Wrote documentation site to: {}", args.out_dir.display());
+
+    Ok(ExitCode::SUCCESS)
+}
+
 fn read_source(path: impl AsRef<Path>) -> miette::Result<(SourceName, SourceContents)> {
     let path = path.as_ref();
     if path.as_os_str() == "-" {
@@ -208,23 +652,39 @@ fn read_source(path: impl AsRef<Path>) -> miette::Result<(SourceName, SourceCont
 }
 
 fn emit_hir(package: &Package, dir: impl AsRef<Path>) -> miette::Result<()> {
-    let path = dir.as_ref().join("hir.txt");
+    emit_file(&dir.as_ref().join("hir.txt"), "HIR", &package.to_string())
+}
+
+/// Writes an output both as pretty text, to `<name>.txt`, and as JSON, to `<name>.json`.
+fn emit_output(dir: &Path, name: &str, output: &(impl Display + Serialize)) -> miette::Result<()> {
+    let kind = name.to_uppercase();
+    emit_file(&dir.join(format!("{name}.txt")), &kind, &output.to_string())?;
+    let json = serde_json::to_string_pretty(output)
+        .into_diagnostic()
+        .with_context(|| format!("could not serialize {kind}"))?;
+    emit_file(&dir.join(format!("{name}.json")), &kind, &json)
+}
+
+fn emit_file(path: &Path, kind: &str, contents: &str) -> miette::Result<()> {
     info!(
-        "Writing HIR output file to: {}",
+        "Writing {kind} output file to: {}",
         path.to_str().unwrap_or_default()
     );
-    fs::write(&path, package.to_string())
+    fs::write(path, contents)
         .into_diagnostic()
-        .with_context(|| format!("could not emit HIR file `{}`", path.display()))
+        .with_context(|| format!("could not emit {kind} file `{}`", path.display()))
 }
 
-fn emit_qir(
-    out_dir: &Path,
+/// Generates the RIR for the package, after checking that it only uses the capabilities of the
+/// target, and checks that the generated program stays within the limits of the target.
+fn generate_rir(
     store: &PackageStore,
     package_id: PackageId,
-    capabilities: TargetCapabilityFlags,
-) -> Result<(), Vec<Report>> {
-    let (fir_store, fir_package_id) = qsc_passes::lower_hir_to_fir(store, package_id);
+    fir_store: &fir::PackageStore,
+    fir_package_id: fir::PackageId,
+    target: &TargetDescription,
+) -> Result<Program, Vec<Report>> {
+    let capabilities = target.capabilities;
     let package = fir_store.get(fir_package_id);
     let entry = ProgramEntry {
         exec_graph: package.entry_exec_graph.clone(),
@@ -237,27 +697,12 @@ fn emit_qir(
             .into(),
     };
 
-    let results = PassContext::run_fir_passes_on_fir(&fir_store, fir_package_id, capabilities);
-    if results.is_err() {
-        let errors = results.expect_err("should have errors");
-        let errors = errors.into_iter().map(Report::new).collect();
-        return Err(errors);
-    }
-    let compute_properties = results.expect("should have compute properties");
-
-    match fir_to_qir(&fir_store, capabilities, Some(compute_properties), &entry) {
-        Ok(qir) => {
-            let path = out_dir.join("qir.ll");
-            info!(
-                "Writing QIR output file to: {}",
-                path.to_str().unwrap_or_default()
-            );
-            fs::write(&path, qir)
-                .into_diagnostic()
-                .with_context(|| format!("could not emit QIR file `{}`", path.display()))
-                .map_err(|err| vec![err])
-        }
-        Err(error) => {
+    let compute_properties =
+        PassContext::run_fir_passes_on_fir(fir_store, fir_package_id, capabilities)
+            .map_err(|errors| errors.into_iter().map(Report::new).collect::<Vec<_>>())?;
+
+    let program =
+        fir_to_rir(fir_store, capabilities, Some(compute_properties), &entry).map_err(|error| {
             let source_package = match error.span() {
                 Some(span) => span.package,
                 None => package_id,
@@ -265,22 +710,52 @@ fn emit_qir(
             let unit = store
                 .get(source_package)
                 .expect("package should be in store");
-            Err(vec![Report::new(WithSource::from_map(
-                &unit.sources,
-                error,
-            ))])
-        }
+            vec![Report::new(WithSource::from_map(&unit.sources, error))]
+        })?;
+
+    let errors = check_target(&program, target);
+    if errors.is_empty() {
+        Ok(program)
+    } else {
+        Err(errors.into_iter().map(Report::new).collect())
     }
 }
 
+/// Resolves the target to compile for: the capability file given on the command line, or else the
+/// given profile, or else the target named by the project manifest, or else the default profile.
+fn resolve_target(
+    target_file: Option<&Path>,
+    profile: Option<Profile>,
+    project_target: Option<TargetDescription>,
+) -> miette::Result<TargetDescription> {
+    if let Some(path) = target_file {
+        return read_target_file(path);
+    }
+    if let (None, Some(target)) = (profile, project_target) {
+        return Ok(target);
+    }
+    let profile: qsc::target::Profile = profile.unwrap_or_default().into();
+    Ok(TargetCapabilityFlags::from(profile).into())
+}
+
 /// Loads a project from the given directory and returns the package store, the list of
-/// dependencies, and the source map.
+/// dependencies, the source map, and the lint configuration and target from the manifest.
 /// Pre-populates the package store with all of the compiled dependencies.
 #[allow(clippy::type_complexity)]
 fn load_project(
     dir: impl AsRef<Path>,
     features: &mut LanguageFeatures,
-) -> Result<(PackageStore, Vec<(PackageId, Option<Arc<str>>)>, SourceMap), ExitCode> {
+    cache: Option<&Cache>,
+) -> Result<
+    (
+        PackageStore,
+        Vec<(PackageId, Option<Arc<str>>)>,
+        SourceMap,
+        Vec<LintConfig>,
+        Option<TargetDescription>,
+    ),
+    ExitCode,
+> {
     let fs = StdFs;
     let project = match fs.load_project(dir.as_ref(), None) {
         Ok(project) => project,
@@ -300,8 +775,14 @@ fn load_project(
     }
 
     // This builds all the dependencies
-    let buildable_program =
-        BuildableProgram::new(TargetCapabilityFlags::all(), project.package_graph_sources);
+    let buildable_program = match cache {
+        Some(cache) => BuildableProgram::with_cache(
+            TargetCapabilityFlags::all(),
+            project.package_graph_sources,
+            cache,
+        ),
+        None => BuildableProgram::new(TargetCapabilityFlags::all(), project.package_graph_sources),
+    };
 
     if !buildable_program.dependency_errors.is_empty() {
         for e in buildable_program.dependency_errors {
@@ -321,5 +802,11 @@ fn load_project(
 
     features.merge(LanguageFeatures::from_iter(user_code.language_features));
 
-    Ok((store, user_code_dependencies, source_map))
+    Ok((
+        store,
+        user_code_dependencies,
+        source_map,
+        project.lints,
+        project.target,
+    ))
 }
@@ -0,0 +1,384 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+//! Caches compiled packages on disk, so that the core and standard libraries and the
+//! dependencies of a project can be loaded instead of compiled again.
+//!
+//! A cache file starts with a magic number and the version of its format, followed by two
+//! sections, each prefixed with its length: a header recording the version of the compiler
+//! that wrote the file and the [`Key`] of the compilation, and the HIR package, source map and
+//! ID assigner of the compile unit. A file is only loaded when its format version, compiler
+//! version and key all match, so a stale or foreign file is compiled again and overwritten.
+//!
+//! The cache is used by the `qsc` and `qsi` command line tools when given a cache directory.
+//! Lowering to FIR isn't cached, since it is fast compared to compiling and the interpreter
+//! lowers packages with or without debug statements.
+
+mod encoding;
+#[cfg(test)]
+mod tests;
+
+use crate::compile::{self, Dependencies};
+use log::{info, warn};
+use miette::Diagnostic;
+use qsc_data_structures::{language_features::LanguageFeatures, target::TargetCapabilityFlags};
+use qsc_frontend::compile::{
+    preprocess::TrackedName, AstPackage, CompileUnit, PackageStore, SourceMap,
+};
+use qsc_hir::{assigner::Assigner, hir};
+use qsc_passes::PackageType;
+use rustc_hash::FxHasher;
+use serde::{Deserialize, Serialize};
+use std::{
+    fs,
+    hash::{Hash, Hasher},
+    io::{self, Write},
+    path::PathBuf,
+};
+use thiserror::Error;
+
+/// The version of the cache file format. It changes whenever the layout of the HIR does.
+pub const FORMAT_VERSION: u32 = 2;
+
+const MAGIC: &[u8; 4] = b"QSPK";
+
+const EXTENSION: &str = "qspkg";
+
+#[derive(Debug, Diagnostic, Error)]
+pub enum Error {
+    #[error("could not access package cache file `{0}`")]
+    #[diagnostic(code("Qsc.Cache.Io"))]
+    Io(String, #[source] io::Error),
+
+    #[error("`{0}` is not a package cache file")]
+    #[diagnostic(code("Qsc.Cache.NotACacheFile"))]
+    NotACacheFile(String),
+
+    #[error("package cache file `{0}` has format version {1}, expected {FORMAT_VERSION}")]
+    #[diagnostic(code("Qsc.Cache.FormatVersion"))]
+    FormatVersion(String, u32),
+
+    #[error("package cache file `{0}` was written by compiler version {1}")]
+    #[diagnostic(code("Qsc.Cache.CompilerVersion"))]
+    CompilerVersion(String, String),
+
+    #[error("package cache file `{0}` is for a different compilation")]
+    #[diagnostic(code("Qsc.Cache.KeyMismatch"))]
+    KeyMismatch(String),
+
+    #[error("package cache file `{0}` is corrupt: {1}")]
+    #[diagnostic(code("Qsc.Cache.Corrupt"))]
+    Corrupt(String, String),
+}
+
+/// Identifies a compilation by hashing everything its result depends on: the compiler
+/// version, the target capabilities, the language features, the sources, the dependencies,
+/// and the sources of every package already in the store. The store matters because the
+/// HIR refers to items in other packages by package ID.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Key(u64);
+
+impl Key {
+    #[must_use]
+    pub fn new(
+        store: Option<&PackageStore>,
+        dependencies: &Dependencies,
+        sources: &SourceMap,
+        capabilities: TargetCapabilityFlags,
+        language_features: LanguageFeatures,
+    ) -> Self {
+        let mut hasher = FxHasher::default();
+        compiler_version().hash(&mut hasher);
+        capabilities.bits().hash(&mut hasher);
+        language_features.bits().hash(&mut hasher);
+        for (id, unit) in store.into_iter().flatten() {
+            usize::from(id).hash(&mut hasher);
+            hash_sources(&unit.sources, &mut hasher);
+        }
+        for (id, alias) in dependencies {
+            usize::from(*id).hash(&mut hasher);
+            alias.hash(&mut hasher);
+        }
+        hash_sources(sources, &mut hasher);
+        Self(hasher.finish())
+    }
+}
+
+fn hash_sources(sources: &SourceMap, hasher: &mut impl Hasher) {
+    for source in sources.iter() {
+        source.name.hash(hasher);
+        source.contents.hash(hasher);
+    }
+}
+
+fn compiler_version() -> String {
+    format!(
+        "{} ({})",
+        env!("CARGO_PKG_VERSION"),
+        env!("QSHARP_GIT_HASH").trim()
+    )
+}
+
+#[derive(Serialize, Deserialize)]
+struct Header {
+    compiler_version: String,
+    key: u64,
+}
+
+#[derive(Serialize)]
+struct UnitRef<'a> {
+    package: &'a hir::Package,
+    assigner: &'a Assigner,
+    sources: &'a SourceMap,
+    dropped_names: &'a [TrackedName],
+}
+
+#[derive(Deserialize)]
+struct Unit {
+    package: hir::Package,
+    assigner: Assigner,
+    sources: SourceMap,
+    dropped_names: Vec<TrackedName>,
+}
+
+/// A directory of package cache files, named after the keys of their compilations.
+#[derive(Clone, Debug)]
+pub struct Cache {
+    dir: PathBuf,
+}
+
+impl Cache {
+    #[must_use]
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    #[must_use]
+    pub fn path(&self, key: Key) -> PathBuf {
+        self.dir.join(format!("{:016x}.{EXTENSION}", key.0))
+    }
+
+    /// Loads the package compiled with the given key. The unit has no AST or errors, since
+    /// only packages that compiled without errors are cached, and compiling against a package
+    /// only needs its HIR.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there is no cache file for the key, or if the file can't be read,
+    /// is corrupt, or was written by a different version of the compiler or file format.
+    pub fn load(&self, key: Key) -> Result<CompileUnit, Error> {
+        let (name, bytes) = self.read(key)?;
+        let mut sections = Sections::new(&name, &bytes[MAGIC.len() + 4..]);
+        sections.next()?;
+        let unit: Unit = decode(&name, sections.next()?)?;
+        Ok(CompileUnit {
+            package: unit.package,
+            ast: AstPackage::default(),
+            assigner: unit.assigner,
+            sources: unit.sources,
+            errors: Vec::new(),
+            dropped_names: unit.dropped_names,
+        })
+    }
+
+    /// Reads the cache file for a key and checks its header, returning the name of the file
+    /// and its contents.
+    fn read(&self, key: Key) -> Result<(String, Vec<u8>), Error> {
+        let path = self.path(key);
+        let name = path.display().to_string();
+        let bytes = fs::read(&path).map_err(|e| Error::Io(name.clone(), e))?;
+
+        let (magic, rest) = bytes
+            .split_first_chunk::<4>()
+            .ok_or_else(|| Error::NotACacheFile(name.clone()))?;
+        let version = rest
+            .first_chunk::<4>()
+            .filter(|_| magic == MAGIC)
+            .ok_or_else(|| Error::NotACacheFile(name.clone()))?;
+        let version = u32::from_le_bytes(*version);
+        if version != FORMAT_VERSION {
+            return Err(Error::FormatVersion(name, version));
+        }
+
+        let header: Header = decode(&name, Sections::new(&name, &rest[4..]).next()?)?;
+        if header.compiler_version != compiler_version() {
+            return Err(Error::CompilerVersion(name, header.compiler_version));
+        }
+        if header.key != key.0 {
+            return Err(Error::KeyMismatch(name));
+        }
+        Ok((name, bytes))
+    }
+
+    /// Saves a package that compiled without errors under the given key. The file is written
+    /// next to its final path and then renamed, so that a concurrent load never sees a partly
+    /// written file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the cache directory or file can't be written.
+    pub fn save(&self, key: Key, unit: &CompileUnit) -> Result<(), Error> {
+        let path = self.path(key);
+        let temp = path.with_extension(format!("{EXTENSION}.{}", std::process::id()));
+        let name = temp.display().to_string();
+
+        let header = Header {
+            compiler_version: compiler_version(),
+            key: key.0,
+        };
+        let unit = UnitRef {
+            package: &unit.package,
+            assigner: &unit.assigner,
+            sources: &unit.sources,
+            dropped_names: &unit.dropped_names,
+        };
+        let mut bytes = MAGIC.to_vec();
+        bytes.extend_from_slice(&FORMAT_VERSION.to_le_bytes());
+        for section in [encoding::to_bytes(&header), encoding::to_bytes(&unit)] {
+            let section = section.map_err(|e| Error::Corrupt(name.clone(), e.to_string()))?;
+            bytes.extend_from_slice(&(section.len() as u64).to_le_bytes());
+            bytes.extend_from_slice(&section);
+        }
+
+        fs::create_dir_all(&self.dir).map_err(|e| Error::Io(self.dir.display().to_string(), e))?;
+        fs::File::create(&temp)
+            .and_then(|mut file| {
+                file.write_all(&bytes)?;
+                file.sync_all()
+            })
+            .map_err(|e| Error::Io(name, e))?;
+        fs::rename(&temp, &path).map_err(|e| Error::Io(path.display().to_string(), e))
+    }
+
+    /// Loads the package with the given key, or compiles it and saves it when it isn't
+    /// cached. A package that fails to compile isn't saved.
+    fn load_or_compile(
+        &self,
+        key: Key,
+        compile: impl FnOnce() -> (CompileUnit, Vec<compile::Error>),
+    ) -> (CompileUnit, Vec<compile::Error>) {
+        match self.load(key) {
+            Ok(unit) => {
+                info!("Loaded package from {}", self.path(key).display());
+                return (unit, Vec::new());
+            }
+            Err(Error::Io(_, e)) if e.kind() == io::ErrorKind::NotFound => {}
+            Err(e) => warn!("{e}, compiling the package instead"),
+        }
+
+        let (unit, errors) = compile();
+        if errors.is_empty() {
+            if let Err(e) = self.save(key, &unit) {
+                warn!("could not cache package: {e}");
+            }
+        }
+        (unit, errors)
+    }
+
+    /// Compiles the core library, or loads it from the cache.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the core library compiles with errors.
+    #[must_use]
+    pub fn core(&self) -> CompileUnit {
+        let sources = qsc_frontend::compile::core_sources();
+        let key = Key::new(
+            None,
+            &[],
+            &sources,
+            TargetCapabilityFlags::all(),
+            LanguageFeatures::default(),
+        );
+        self.load_or_compile(key, || (compile::core(), Vec::new()))
+            .0
+    }
+
+    /// Compiles the standard library, or loads it from the cache.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the standard library compiles with errors.
+    #[must_use]
+    pub fn std(&self, store: &PackageStore, capabilities: TargetCapabilityFlags) -> CompileUnit {
+        let sources = qsc_frontend::compile::std_sources();
+        let key = Key::new(
+            Some(store),
+            &[],
+            &sources,
+            capabilities,
+            LanguageFeatures::default(),
+        );
+        self.load_or_compile(key, || (compile::std(store, capabilities), Vec::new()))
+            .0
+    }
+
+    /// Creates a package store with the core and standard libraries, loading them from the
+    /// cache when possible.
+    #[must_use]
+    pub fn package_store_with_stdlib(
+        &self,
+        capabilities: TargetCapabilityFlags,
+    ) -> (hir::PackageId, PackageStore) {
+        let mut store = PackageStore::new(self.core());
+        let std_id = store.insert(self.std(&store, capabilities));
+        (std_id, store)
+    }
+
+    /// Compiles a library package, such as a dependency of a project, or loads it from the cache.
+    #[must_use]
+    pub fn compile(
+        &self,
+        store: &PackageStore,
+        dependencies: &Dependencies,
+        sources: SourceMap,
+        capabilities: TargetCapabilityFlags,
+        language_features: LanguageFeatures,
+    ) -> (CompileUnit, Vec<compile::Error>) {
+        let key = Key::new(
+            Some(store),
+            dependencies,
+            &sources,
+            capabilities,
+            language_features,
+        );
+        self.load_or_compile(key, || {
+            compile::compile(
+                store,
+                dependencies,
+                sources,
+                PackageType::Lib,
+                capabilities,
+                language_features,
+            )
+        })
+    }
+}
+
+/// The length-prefixed sections of a cache file, after its magic number and format version.
+struct Sections<'a> {
+    name: &'a str,
+    bytes: &'a [u8],
+}
+
+impl<'a> Sections<'a> {
+    fn new(name: &'a str, bytes: &'a [u8]) -> Self {
+        Self { name, bytes }
+    }
+
+    fn next(&mut self) -> Result<&'a [u8], Error> {
+        let truncated = || Error::Corrupt(self.name.to_string(), "file is truncated".to_string());
+        let (len, rest) = self.bytes.split_first_chunk::<8>().ok_or_else(truncated)?;
+        let len = usize::try_from(u64::from_le_bytes(*len)).map_err(|_| truncated())?;
+        if len > rest.len() {
+            return Err(truncated());
+        }
+        let (section, rest) = rest.split_at(len);
+        self.bytes = rest;
+        Ok(section)
+    }
+}
+
+fn decode<'a, T: Deserialize<'a>>(name: &str, section: &'a [u8]) -> Result<T, Error> {
+    encoding::from_bytes(section).map_err(|e| Error::Corrupt(name.to_string(), e.to_string()))
+}
@@ -0,0 +1,244 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use super::{Cache, Error, Key, FORMAT_VERSION, MAGIC};
+use crate::{compile, interpret::Interpreter};
+use expect_test::expect;
+use qsc_data_structures::{language_features::LanguageFeatures, target::TargetCapabilityFlags};
+use qsc_eval::output::CursorReceiver;
+use qsc_frontend::compile::{PackageStore, SourceMap};
+use qsc_passes::PackageType;
+use std::{fs, path::PathBuf, sync::Arc};
+
+/// A cache in a fresh temporary directory, removed when the test ends.
+struct TempCache {
+    dir: PathBuf,
+    cache: Cache,
+}
+
+impl TempCache {
+    fn new(name: &str) -> Self {
+        let dir = std::env::temp_dir().join(format!("qsc-cache-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let cache = Cache::new(&dir);
+        Self { dir, cache }
+    }
+
+    fn files(&self) -> usize {
+        fs::read_dir(&self.dir).map_or(0, Iterator::count)
+    }
+}
+
+impl Drop for TempCache {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.dir);
+    }
+}
+
+const SHAPES: &str = "namespace Shapes {
+    function Square() : Int { 4 }
+    export Square;
+}";
+
+fn shapes() -> SourceMap {
+    SourceMap::new([("Shapes.qs".into(), SHAPES.into())], None)
+}
+
+#[test]
+fn cached_std_matches_compiled_std() {
+    let temp = TempCache::new("std");
+    let store = PackageStore::new(compile::core());
+    let compiled = compile::std(&store, TargetCapabilityFlags::all());
+
+    let saved = temp.cache.std(&store, TargetCapabilityFlags::all());
+    assert_eq!(temp.files(), 1);
+    let key = Key::new(
+        Some(&store),
+        &[],
+        &qsc_frontend::compile::std_sources(),
+        TargetCapabilityFlags::all(),
+        LanguageFeatures::default(),
+    );
+    let cached = temp.cache.load(key).expect("std should be cached");
+
+    assert_eq!(cached.package.to_string(), compiled.package.to_string());
+    assert_eq!(cached.package.to_string(), saved.package.to_string());
+    assert_eq!(
+        cached.sources.iter().count(),
+        compiled.sources.iter().count()
+    );
+}
+
+#[test]
+fn cached_packages_can_be_compiled_against_and_run() {
+    let temp = TempCache::new("run");
+    for _ in 0..2 {
+        let (std_id, mut store) = temp
+            .cache
+            .package_store_with_stdlib(TargetCapabilityFlags::all());
+        let (dependency, errors) = temp.cache.compile(
+            &store,
+            &[(std_id, None)],
+            shapes(),
+            TargetCapabilityFlags::all(),
+            LanguageFeatures::default(),
+        );
+        assert!(errors.is_empty(), "{errors:?}");
+        let dependency_id = store.insert(dependency);
+
+        let mut interpreter = Interpreter::new(
+            SourceMap::new(
+                [(
+                    "Main.qs".into(),
+                    "namespace Main {
+                        @EntryPoint()
+                        operation Main() : Int { Shapes.Shapes.Square() + Length([1, 2]) }
+                    }"
+                    .into(),
+                )],
+                None,
+            ),
+            PackageType::Exe,
+            TargetCapabilityFlags::all(),
+            LanguageFeatures::default(),
+            store,
+            &[(std_id, None), (dependency_id, Some(Arc::from("Shapes")))],
+        )
+        .expect("interpreter creation should succeed");
+        let mut cursor = std::io::Cursor::new(Vec::<u8>::new());
+        let mut receiver = CursorReceiver::new(&mut cursor);
+        let value = interpreter
+            .eval_entry(&mut receiver)
+            .expect("entry should run");
+        expect!["6"].assert_eq(&value.to_string());
+    }
+    // Core, std and the dependency, each written once.
+    assert_eq!(temp.files(), 3);
+}
+
+#[test]
+fn packages_with_errors_are_not_cached() {
+    let temp = TempCache::new("errors");
+    let (std_id, store) = compile::package_store_with_stdlib(TargetCapabilityFlags::all());
+    let (_, errors) = temp.cache.compile(
+        &store,
+        &[(std_id, None)],
+        SourceMap::new(
+            [(
+                "Bad.qs".into(),
+                "namespace Bad { function F() : Int { true } }".into(),
+            )],
+            None,
+        ),
+        TargetCapabilityFlags::all(),
+        LanguageFeatures::default(),
+    );
+    assert!(!errors.is_empty());
+    assert_eq!(temp.files(), 0);
+}
+
+#[test]
+fn key_depends_on_sources_capabilities_dependencies_and_store() {
+    let (std_id, store) = compile::package_store_with_stdlib(TargetCapabilityFlags::all());
+    let key = |store, dependencies: &[_], sources: &SourceMap, capabilities| {
+        Key::new(
+            store,
+            dependencies,
+            sources,
+            capabilities,
+            LanguageFeatures::default(),
+        )
+    };
+    let all = TargetCapabilityFlags::all();
+    let base = key(Some(&store), &[(std_id, None)], &shapes(), all);
+    assert_eq!(base, key(Some(&store), &[(std_id, None)], &shapes(), all));
+
+    let edited = SourceMap::new(
+        [("Shapes.qs".into(), SHAPES.replace('4', "5").into())],
+        None,
+    );
+    assert_ne!(base, key(Some(&store), &[(std_id, None)], &edited, all));
+    assert_ne!(
+        base,
+        key(
+            Some(&store),
+            &[(std_id, None)],
+            &shapes(),
+            TargetCapabilityFlags::empty()
+        )
+    );
+    assert_ne!(
+        base,
+        key(
+            Some(&store),
+            &[(std_id, Some("Std".into()))],
+            &shapes(),
+            all
+        )
+    );
+    assert_ne!(base, key(None, &[(std_id, None)], &shapes(), all));
+}
+
+#[test]
+fn stale_and_foreign_files_are_rejected() {
+    let temp = TempCache::new("stale");
+    let (std_id, store) = compile::package_store_with_stdlib(TargetCapabilityFlags::all());
+    let key = |sources: &SourceMap| {
+        Key::new(
+            Some(&store),
+            &[(std_id, None)],
+            sources,
+            TargetCapabilityFlags::all(),
+            LanguageFeatures::default(),
+        )
+    };
+    let (unit, _) = temp.cache.compile(
+        &store,
+        &[(std_id, None)],
+        shapes(),
+        TargetCapabilityFlags::all(),
+        LanguageFeatures::default(),
+    );
+    let key = key(&shapes());
+    let other = Key(key.0.wrapping_add(1));
+    let path = temp.cache.path(other);
+
+    assert!(matches!(temp.cache.load(other), Err(Error::Io(..))));
+
+    fs::copy(temp.cache.path(key), &path).expect("file should copy");
+    assert!(matches!(
+        temp.cache.load(other),
+        Err(Error::KeyMismatch(..))
+    ));
+
+    let mut future = MAGIC.to_vec();
+    future.extend_from_slice(&(FORMAT_VERSION + 1).to_le_bytes());
+    fs::write(&path, future).expect("file should be written");
+    assert!(matches!(
+        temp.cache.load(other),
+        Err(Error::FormatVersion(..))
+    ));
+
+    fs::write(&path, "not a package").expect("file should be written");
+    assert!(matches!(
+        temp.cache.load(other),
+        Err(Error::NotACacheFile(..))
+    ));
+
+    let mut truncated = fs::read(temp.cache.path(key)).expect("file should be read");
+    truncated.truncate(truncated.len() / 2);
+    fs::write(temp.cache.path(key), truncated).expect("file should be written");
+    assert!(matches!(temp.cache.load(key), Err(Error::Corrupt(..))));
+
+    // A corrupt file for a key is compiled again and replaced.
+    let (recompiled, errors) = temp.cache.compile(
+        &store,
+        &[(std_id, None)],
+        shapes(),
+        TargetCapabilityFlags::all(),
+        LanguageFeatures::default(),
+    );
+    assert!(errors.is_empty());
+    assert_eq!(recompiled.package.to_string(), unit.package.to_string());
+    assert!(temp.cache.load(key).is_ok());
+}
@@ -4,8 +4,11 @@
 #[cfg(test)]
 mod tests;
 
-use qsc_codegen::qir::fir_to_qir;
-use qsc_data_structures::{language_features::LanguageFeatures, target::TargetCapabilityFlags};
+use qsc_codegen::qir::{fir_to_rir, rir_to_qir};
+use qsc_data_structures::{
+    language_features::LanguageFeatures,
+    target::{TargetCapabilityFlags, TargetDescription},
+};
 use qsc_frontend::{
     compile::{Dependencies, PackageStore, SourceMap},
     error::WithSource,
@@ -15,13 +18,16 @@ use qsc_passes::{PackageType, PassContext};
 
 use crate::interpret::Error;
 
+/// Generates QIR for the entry point of the sources, and checks the program against the gates
+/// and limits of the target.
 pub fn get_qir(
     sources: SourceMap,
     language_features: LanguageFeatures,
-    capabilities: TargetCapabilityFlags,
+    target: &TargetDescription,
     mut package_store: PackageStore,
     dependencies: &Dependencies,
 ) -> Result<String, Vec<Error>> {
+    let capabilities = target.capabilities;
     if capabilities == TargetCapabilityFlags::all() {
         return Err(vec![Error::UnsupportedRuntimeCapabilities]);
     }
@@ -67,17 +73,24 @@ pub fn get_qir(
             },
         )?;
 
-    fir_to_qir(&fir_store, capabilities, Some(compute_properties), &entry).map_err(|e| {
-        let source_package_id = match e.span() {
-            Some(span) => span.package,
-            None => package_id,
-        };
-        let source_package = package_store
-            .get(source_package_id)
-            .expect("package should be in store");
-        vec![Error::PartialEvaluation(WithSource::from_map(
-            &source_package.sources,
-            e,
-        ))]
-    })
+    let program =
+        fir_to_rir(&fir_store, capabilities, Some(compute_properties), &entry).map_err(|e| {
+            let source_package_id = match e.span() {
+                Some(span) => span.package,
+                None => package_id,
+            };
+            let source_package = package_store
+                .get(source_package_id)
+                .expect("package should be in store");
+            vec![Error::PartialEvaluation(WithSource::from_map(
+                &source_package.sources,
+                e,
+            ))]
+        })?;
+    let errors = qsc_rir::passes::check_target(&program, target);
+    if errors.is_empty() {
+        Ok(rir_to_qir(&program))
+    } else {
+        Err(errors.into_iter().map(Error::Target).collect())
+    }
 }
@@ -2,7 +2,10 @@
 // Licensed under the MIT License.
 
 use expect_test::expect;
-use qsc_data_structures::{language_features::LanguageFeatures, target::TargetCapabilityFlags};
+use qsc_data_structures::{
+    language_features::LanguageFeatures,
+    target::{TargetCapabilityFlags, TargetDescription},
+};
 use qsc_frontend::compile::SourceMap;
 
 use crate::codegen::get_qir;
@@ -56,7 +59,45 @@ fn code_with_errors_returns_errors() {
             ],
         )
     "#]]
-    .assert_debug_eq(&get_qir(sources, language_features, capabilities, store, &[(std_id, None)]));
+    .assert_debug_eq(&get_qir(sources, language_features, &capabilities.into(), store, &[(std_id, None)]));
+}
+
+#[test]
+fn program_outside_target_limits_returns_errors() {
+    let source = "namespace Test {
+            @EntryPoint()
+            operation Main() : Result[] {
+                use qs = Qubit[2];
+                CZ(qs[0], qs[1]);
+                MResetEachZ(qs)
+            }
+        }";
+    let sources = SourceMap::new([("test.qs".into(), source.into())], None);
+    let language_features = LanguageFeatures::default();
+    let target = TargetDescription {
+        gates: Some(vec!["h".into(), "cx".into()]),
+        max_qubits: Some(1),
+        ..TargetCapabilityFlags::empty().into()
+    };
+    let (std_id, store) = crate::compile::package_store_with_stdlib(target.capabilities);
+
+    let errors = get_qir(
+        sources,
+        language_features,
+        &target,
+        store,
+        &[(std_id, None)],
+    )
+    .expect_err("program should be outside the target limits");
+    let messages = errors.iter().map(ToString::to_string).collect::<Vec<_>>();
+    expect![[r#"
+        [
+            "the target does not support the `cz` gate",
+            "the target does not support the `m` gate",
+            "the program uses 2 qubits, but the target only has 1",
+        ]
+    "#]]
+    .assert_debug_eq(&messages);
 }
 
 mod base_profile {
@@ -91,7 +132,7 @@ mod base_profile {
         let qir = get_qir(
             sources,
             language_features,
-            capabilities,
+            &capabilities.into(),
             store,
             &[(std_id, None)],
         )
@@ -148,7 +189,7 @@ mod base_profile {
         let qir = get_qir(
             sources,
             language_features,
-            capabilities,
+            &capabilities.into(),
             store,
             &[(std_id, None)],
         )
@@ -208,7 +249,7 @@ mod base_profile {
         let qir = get_qir(
             sources,
             language_features,
-            capabilities,
+            &capabilities.into(),
             store,
             &[(std_id, None)],
         )
@@ -284,7 +325,7 @@ mod adaptive_profile {
         let qir = get_qir(
             sources,
             language_features,
-            capabilities,
+            &capabilities.into(),
             store,
             &[(std_id, None)],
         )
@@ -348,7 +389,7 @@ mod adaptive_profile {
         let qir = get_qir(
             sources,
             language_features,
-            capabilities,
+            &capabilities.into(),
             store,
             &[(std_id, None)],
         )
@@ -415,7 +456,7 @@ mod adaptive_profile {
         let qir = get_qir(
             sources,
             language_features,
-            capabilities,
+            &capabilities.into(),
             store,
             &[(std_id, None)],
         )
@@ -501,7 +542,7 @@ mod adaptive_ri_profile {
         let qir = get_qir(
             sources,
             language_features,
-            capabilities,
+            &capabilities.into(),
             store,
             &[(std_id, None)],
         )
@@ -567,7 +608,7 @@ mod adaptive_ri_profile {
         let qir = get_qir(
             sources,
             language_features,
-            capabilities,
+            &capabilities.into(),
             store,
             &[(std_id, None)],
         )
@@ -636,7 +677,7 @@ mod adaptive_ri_profile {
         let qir = get_qir(
             sources,
             language_features,
-            capabilities,
+            &capabilities.into(),
             store,
             &[(std_id, None)],
         )
@@ -706,7 +747,7 @@ mod adaptive_ri_profile {
         let qir = get_qir(
             sources,
             language_features,
-            capabilities,
+            &capabilities.into(),
             store,
             &[(std_id, None)],
         )
@@ -14,6 +14,7 @@ use qsc_frontend::{
 };
 use qsc_hir::hir::PackageId;
 use qsc_passes::{PackageType, PassContext};
+use std::sync::Arc;
 
 /// An incremental Q# compiler.
 pub struct Compiler {
@@ -27,6 +28,12 @@ pub struct Compiler {
     passes: PassContext,
     /// The frontend incremental compiler.
     frontend: qsc_frontend::incremental::Compiler,
+    /// The dependencies of the source package.
+    dependencies: Vec<(PackageId, Option<Arc<str>>)>,
+    /// The target capabilities used for compilation.
+    capabilities: TargetCapabilityFlags,
+    /// The language features used for compilation.
+    language_features: LanguageFeatures,
 }
 
 /// An incremental compiler error.
@@ -59,14 +66,12 @@ impl Compiler {
         // make the user code fully public, so increments on top of this can access them
         unit.expose();
 
-        let mut dependencies = dependencies.iter().map(Clone::clone).collect::<Vec<_>>();
-
+        let dependencies = dependencies.iter().map(Clone::clone).collect::<Vec<_>>();
         let source_package_id = store.insert(unit);
-        dependencies.push((source_package_id, None));
-
-        let frontend = qsc_frontend::incremental::Compiler::new(
+        let frontend = new_frontend(
             &store,
-            &dependencies[..],
+            &dependencies,
+            source_package_id,
             capabilities,
             language_features,
         );
@@ -77,6 +82,9 @@ impl Compiler {
             source_package_id,
             frontend,
             passes: PassContext::default(),
+            dependencies,
+            capabilities,
+            language_features,
         })
     }
 
@@ -88,6 +96,14 @@ impl Compiler {
     ) -> Result<Self, Errors> {
         let frontend =
             qsc_frontend::incremental::Compiler::new(&store, &[], capabilities, language_features);
+        // The aliases of the dependencies are not known here, so the other packages in the store
+        // are used as the dependencies when the source package is compiled again.
+        let dependencies = store
+            .iter()
+            .map(|(id, _)| id)
+            .filter(|&id| id != PackageId::CORE && id != source_package_id)
+            .map(|id| (id, None))
+            .collect();
         let store = store.open();
 
         Ok(Self {
@@ -95,6 +111,9 @@ impl Compiler {
             source_package_id,
             frontend,
             passes: PassContext::default(),
+            dependencies,
+            capabilities,
+            language_features,
         })
     }
 
@@ -255,6 +274,43 @@ impl Compiler {
         Ok(increment)
     }
 
+    /// Compiles the source package again from new sources, against the same dependencies, and
+    /// replaces it in the store. Fragments compiled on top of the old source package are
+    /// discarded. This is much faster than creating a new compiler, since the dependencies are
+    /// not compiled again.
+    /// # Errors
+    /// If compiling the sources fails, compiler errors are returned and the current compilation
+    /// is left unchanged.
+    pub fn update_sources(
+        &mut self,
+        sources: SourceMap,
+        package_type: PackageType,
+    ) -> Result<(), Errors> {
+        let (mut unit, errors) = compile(
+            self.store.package_store(),
+            &self.dependencies,
+            sources,
+            package_type,
+            self.capabilities,
+            self.language_features,
+        );
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+
+        unit.expose();
+        self.store.replace(self.source_package_id, unit);
+        self.frontend = new_frontend(
+            self.store.package_store(),
+            &self.dependencies,
+            self.source_package_id,
+            self.capabilities,
+            self.language_features,
+        );
+        self.passes = PassContext::default();
+        Ok(())
+    }
+
     /// Updates the current compilation with the AST and HIR packages,
     /// and any associated context, returned from a previous incremental compilation.
     /// Entry expressions are ignored.
@@ -291,6 +347,20 @@ impl Compiler {
     }
 }
 
+/// Creates a frontend compiler for fragments that can refer to the dependencies and the source
+/// package.
+fn new_frontend(
+    store: &PackageStore,
+    dependencies: &Dependencies,
+    source_package_id: PackageId,
+    capabilities: TargetCapabilityFlags,
+    language_features: LanguageFeatures,
+) -> qsc_frontend::incremental::Compiler {
+    let mut dependencies = dependencies.to_vec();
+    dependencies.push((source_package_id, None));
+    qsc_frontend::incremental::Compiler::new(store, &dependencies, capabilities, language_features)
+}
+
 fn into_errors_with_source<T>(errors: Vec<T>, sources: &SourceMap) -> Errors
 where
     compile::ErrorKind: From<T>,
@@ -6,11 +6,16 @@ mod circuit_tests;
 mod debug;
 #[cfg(test)]
 mod debugger_tests;
+mod host;
+#[cfg(test)]
+mod host_tests;
 #[cfg(test)]
 mod package_tests;
 #[cfg(test)]
 mod tests;
+mod watch;
 
+pub use host::{HostFn, HostValue};
 pub use qsc_eval::{
     debug::Frame,
     output::{self, GenericReceiver},
@@ -31,6 +36,7 @@ use crate::{
     location::Location,
 };
 use debug::format_call_stack;
+use host::{is_host_type, HostBackend, HostIntrinsics};
 use miette::Diagnostic;
 use num_bigint::BigUint;
 use num_complex::Complex;
@@ -38,22 +44,24 @@ use qsc_circuit::{
     operations::entry_expr_for_qubit_operation, Builder as CircuitBuilder, Circuit,
     Config as CircuitConfig,
 };
-use qsc_codegen::qir::fir_to_qir;
+use qsc_codegen::qir::{fir_to_rir, rir_to_qir};
 use qsc_data_structures::{
     functors::FunctorApp,
     language_features::LanguageFeatures,
     line_column::{Encoding, Range},
     span::Span,
-    target::TargetCapabilityFlags,
+    target::{TargetCapabilityFlags, TargetDescription},
 };
+use qsc_doc_gen::doctests::{extract_doctests, Doctest};
 use qsc_eval::{
     backend::{Backend, Chain as BackendChain, SparseSim},
     output::Receiver,
+    state::views as state_views,
     val, Env, State, VariableInfo,
 };
-use qsc_fir::fir::{self, ExecGraph, Global, PackageStoreLookup};
+use qsc_fir::fir::{self, ExecGraph, Global, PackageLookup, PackageStoreLookup};
 use qsc_fir::{
-    fir::{Block, BlockId, Expr, ExprId, Package, PackageId, Pat, PatId, Stmt, StmtId},
+    fir::{Block, BlockId, Expr, ExprId, LocalVarId, Package, PackageId, Pat, PatId, Stmt, StmtId},
     visit::{self, Visitor},
 };
 use qsc_frontend::{
@@ -61,8 +69,10 @@ use qsc_frontend::{
     error::WithSource,
     incremental::Increment,
 };
+use qsc_hir::ty::Ty;
 use qsc_passes::{PackageType, PassContext};
-use rustc_hash::FxHashSet;
+use rustc_hash::{FxHashMap, FxHashSet};
+use std::{any::Any, fmt::Write, iter, rc::Rc};
 use thiserror::Error;
 
 impl Error {
@@ -99,9 +109,48 @@ pub enum Error {
     #[diagnostic(code("Qsc.Interpret.NotAnOperation"))]
     #[diagnostic(help("provide the name of a callable or a lambda expression"))]
     NotAnOperation,
+    #[error("expression does not evaluate to a callable or type")]
+    #[diagnostic(code("Qsc.Interpret.NotAnItem"))]
+    #[diagnostic(help("provide the name of a callable or type"))]
+    NotAnItem,
+    #[error("`{0}` is not an intrinsic function declared in this session")]
+    #[diagnostic(code("Qsc.Interpret.NotAHostIntrinsic"))]
+    #[diagnostic(help(
+        "declare a function without type parameters whose body is `body intrinsic;`"
+    ))]
+    NotAHostIntrinsic(String),
+    #[error("`{0}` has type `{1}`, which host functions do not support")]
+    #[diagnostic(code("Qsc.Interpret.UnsupportedHostType"))]
+    #[diagnostic(help(
+        "host functions take and return Bool, Int, BigInt, Double, String, Pauli and Result values, and arrays and tuples of them"
+    ))]
+    UnsupportedHostType(String, String),
+    #[error("host function for `{0}` has type `{2}`, but `{0}` is declared with type `{1}`")]
+    #[diagnostic(code("Qsc.Interpret.HostTypeMismatch"))]
+    HostTypeMismatch(String, String, String),
     #[error("partial evaluation error")]
     #[diagnostic(transparent)]
     PartialEvaluation(#[from] WithSource<qsc_partial_eval::Error>),
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    Target(#[from] qsc_rir::passes::TargetError),
+    #[error("stack frame {0} not found")]
+    #[diagnostic(code("Qsc.Interpret.FrameNotFound"))]
+    FrameNotFound(usize),
+    #[error("the type of variable `{0}` cannot be determined")]
+    #[diagnostic(code("Qsc.Interpret.UnknownVariableType"))]
+    #[diagnostic(help(
+        "only variables whose type can be determined from their value can be used in the debugger"
+    ))]
+    UnknownVariableType(String),
+    #[error("expression has quantum side effects")]
+    #[diagnostic(code("Qsc.Interpret.QuantumSideEffects"))]
+    #[diagnostic(help("allow quantum side effects to call operations or allocate qubits"))]
+    QuantumSideEffects,
+    #[error("no earlier program state is available")]
+    #[diagnostic(code("Qsc.Interpret.NoCheckpoint"))]
+    #[diagnostic(help("set a checkpoint interval before starting execution to step backwards"))]
+    NoCheckpoint,
 }
 
 /// A Q# interpreter.
@@ -110,6 +159,8 @@ pub struct Interpreter {
     compiler: Compiler,
     /// The target capabilities used for compilation.
     capabilities: TargetCapabilityFlags,
+    /// The target that generated QIR is checked against, for its gates and limits.
+    target: TargetDescription,
     /// The number of lines that have so far been compiled.
     /// This field is used to generate a unique label
     /// for each line evaluated with `eval_fragments`.
@@ -137,6 +188,8 @@ pub struct Interpreter {
     classical_seed: Option<u64>,
     /// The evaluator environment.
     env: Env,
+    /// The host functions that implement intrinsic functions.
+    host_intrinsics: HostIntrinsics,
 }
 
 pub type InterpretResult = std::result::Result<Value, Vec<Error>>;
@@ -217,34 +270,19 @@ impl Interpreter {
         let package_id = compiler.package_id();
 
         let package = map_hir_package_to_fir(package_id);
-        if capabilities != TargetCapabilityFlags::all() {
-            let _ = PassContext::run_fir_passes_on_fir(
-                &fir_store,
-                map_hir_package_to_fir(source_package_id),
-                capabilities,
-            )
-            .map_err(|caps_errors| {
-                let source_package = compiler
-                    .package_store()
-                    .get(source_package_id)
-                    .expect("package should exist in the package store");
-
-                caps_errors
-                    .into_iter()
-                    .map(|error| Error::Pass(WithSource::from_map(&source_package.sources, error)))
-                    .collect::<Vec<_>>()
-            })?;
-        }
+        check_source_capabilities(&compiler, &fir_store, capabilities)?;
 
         Ok(Self {
             compiler,
             lines: 0,
             capabilities,
+            target: capabilities.into(),
             fir_store,
             lowerer: qsc_lowerer::Lowerer::new().with_debug(dbg),
             expr_graph: None,
             env: Env::default(),
             sim: sim_circuit_backend(),
+            host_intrinsics: HostIntrinsics::default(),
             quantum_seed: None,
             classical_seed: None,
             package,
@@ -275,11 +313,13 @@ impl Interpreter {
             compiler,
             lines: 0,
             capabilities,
+            target: capabilities.into(),
             fir_store,
             lowerer: qsc_lowerer::Lowerer::new(),
             expr_graph: None,
             env: Env::default(),
             sim: sim_circuit_backend(),
+            host_intrinsics: HostIntrinsics::default(),
             quantum_seed: None,
             classical_seed: None,
             package: map_hir_package_to_fir(package_id),
@@ -296,6 +336,59 @@ impl Interpreter {
         self.classical_seed = seed;
     }
 
+    /// Sets the intrinsic gates and the qubit and measurement limits of the target that the
+    /// program generated by [`Interpreter::qirgen`] is checked against. The capabilities used
+    /// for compilation are kept.
+    pub fn set_target_limits(&mut self, target: &TargetDescription) {
+        self.target = TargetDescription {
+            capabilities: self.capabilities,
+            ..target.clone()
+        };
+    }
+
+    /// Compiles the source package again from new sources, reusing the compiled dependencies,
+    /// and resets the session: lines evaluated so far, their variables, and the simulator state
+    /// are discarded. Registered host functions are kept.
+    /// # Errors
+    /// If compiling the sources fails, compiler errors are returned and the interpreter is left
+    /// unchanged. If the new sources use capabilities that the target doesn't support, the
+    /// interpreter is updated and the capability errors are returned.
+    pub fn update_sources(
+        &mut self,
+        sources: SourceMap,
+        package_type: PackageType,
+    ) -> std::result::Result<(), Vec<Error>> {
+        self.compiler
+            .update_sources(sources, package_type)
+            .map_err(into_errors)?;
+
+        let dbg = self.lowerer.is_debug();
+        for id in [
+            self.compiler.source_package_id(),
+            self.compiler.package_id(),
+        ] {
+            let unit = self
+                .compiler
+                .package_store()
+                .get(id)
+                .expect("package should exist in the package store");
+            let pkg = qsc_lowerer::Lowerer::new()
+                .with_debug(dbg)
+                .lower_package(&unit.package, &self.fir_store);
+            self.fir_store.insert(map_hir_package_to_fir(id), pkg);
+        }
+
+        self.lowerer = qsc_lowerer::Lowerer::new().with_debug(dbg);
+        self.lines = 0;
+        self.expr_graph = None;
+        self.env = Env::default();
+        self.sim = sim_circuit_backend();
+        if self.quantum_seed.is_some() {
+            self.sim.set_seed(self.quantum_seed);
+        }
+        check_source_capabilities(&self.compiler, &self.fir_store, self.capabilities)
+    }
+
     pub fn check_source_lints(&self) -> Vec<Lint> {
         if let Some(compile_unit) = self
             .compiler
@@ -317,6 +410,34 @@ impl Interpreter {
         }
     }
 
+    /// Returns the code examples in the doc comments of the source package.
+    #[must_use]
+    pub fn doctests(&self) -> Vec<Doctest> {
+        self.compiler
+            .package_store()
+            .get(self.compiler.source_package_id())
+            .map(|unit| extract_doctests(&unit.package, &unit.sources))
+            .unwrap_or_default()
+    }
+
+    /// Runs a code example from a doc comment, after opening the namespace of the documented item.
+    /// Examples should each be run in a new interpreter so that they can't depend on
+    /// each other, or on qubits left allocated by an earlier example.
+    /// # Errors
+    /// If the example fails to compile, or there is a runtime error when running it, an error is returned.
+    pub fn run_doctest(
+        &mut self,
+        receiver: &mut impl Receiver,
+        doctest: &Doctest,
+    ) -> std::result::Result<(), Vec<Error>> {
+        if let Some(namespace) = &doctest.namespace {
+            self.eval_fragments(receiver, &format!("open {namespace};"))?;
+        }
+        // Errors in the example are reported against a source named after the documented item.
+        self.eval_labeled_fragments(receiver, &doctest.name, &doctest.code)
+            .map(|_| ())
+    }
+
     /// Executes the entry expression until the end of execution.
     /// # Errors
     /// Returns a vector of errors if evaluating the entry point fails.
@@ -333,6 +454,7 @@ impl Interpreter {
             self.compiler.package_store(),
             &self.fir_store,
             &mut Env::default(),
+            &self.host_intrinsics,
             &mut self.sim,
             receiver,
         )
@@ -357,6 +479,7 @@ impl Interpreter {
             self.compiler.package_store(),
             &self.fir_store,
             &mut Env::default(),
+            &self.host_intrinsics,
             sim,
             receiver,
         )
@@ -380,10 +503,18 @@ impl Interpreter {
         fragments: &str,
     ) -> InterpretResult {
         let label = self.next_line_label();
+        self.eval_labeled_fragments(receiver, &label, fragments)
+    }
 
+    fn eval_labeled_fragments(
+        &mut self,
+        receiver: &mut impl Receiver,
+        label: &str,
+        fragments: &str,
+    ) -> InterpretResult {
         let mut increment = self
             .compiler
-            .compile_fragments_fail_fast(&label, fragments)
+            .compile_fragments_fail_fast(label, fragments)
             .map_err(into_errors)?;
         // Clear the entry expression, as we are evaluating fragments and a fragment with a `@EntryPoint` attribute
         // should not change what gets executed.
@@ -437,6 +568,7 @@ impl Interpreter {
             self.compiler.package_store(),
             &self.fir_store,
             &mut self.env,
+            &self.host_intrinsics,
             &mut self.sim,
             receiver,
         )
@@ -452,6 +584,171 @@ impl Interpreter {
         self.run_with_sim(&mut SparseSim::new(), receiver, expr)
     }
 
+    /// Evaluates fragments read from the named source, such as a file, instead of an
+    /// interactive line.
+    /// # Errors
+    /// If the parsing of the fragments fails, an error is returned.
+    /// If the compilation of the fragments fails, an error is returned.
+    /// If there is a runtime error when interpreting the fragments, an error is returned.
+    pub fn eval_source(
+        &mut self,
+        receiver: &mut impl Receiver,
+        name: &str,
+        fragments: &str,
+    ) -> InterpretResult {
+        self.eval_labeled_fragments(receiver, name, fragments)
+    }
+
+    /// Compiles the given expression without evaluating it and returns its type.
+    /// # Errors
+    /// If the compilation of the expression fails, an error is returned.
+    pub fn type_of(&mut self, expr: &str) -> std::result::Result<Ty, Vec<Error>> {
+        let increment = self
+            .compiler
+            .compile_entry_expr(expr)
+            .map_err(into_errors)?;
+        let ty = increment
+            .hir
+            .entry
+            .as_ref()
+            .expect("increment should have an entry expression")
+            .ty
+            .clone();
+        // The entry expression itself is dropped, but any items it defined, such as
+        // lambdas, are kept so that the compiler state stays consistent.
+        self.compiler.update(increment);
+        Ok(ty)
+    }
+
+    /// Evaluates the name of a callable or type and returns its doc comment, which is empty if
+    /// the item is not documented.
+    /// # Errors
+    /// If the name does not evaluate to a callable or type, an error is returned.
+    pub fn doc(&mut self, name: &str) -> std::result::Result<Rc<str>, Vec<Error>> {
+        let (_, item) = self.eval_item(name)?;
+        Ok(item.doc.clone())
+    }
+
+    /// Returns the input and output types of the intrinsic function with the given name, which
+    /// a host function can implement.
+    /// # Errors
+    /// If the name is not an intrinsic function declared in this session, or if host functions
+    /// do not support its types, an error is returned.
+    pub fn intrinsic_signature(&mut self, name: &str) -> std::result::Result<(Ty, Ty), Vec<Error>> {
+        self.host_intrinsic(name)
+            .map(|(_, input, output)| (input, output))
+    }
+
+    /// Registers a host function as the implementation of the intrinsic function with the given
+    /// name, which must be declared in this session with `body intrinsic;`. The input and output
+    /// types of the host function must match the declaration, and later calls to the function
+    /// are routed to it.
+    /// # Errors
+    /// If the name is not an intrinsic function declared in this session, or if the types of
+    /// the host function do not match its declaration, an error is returned.
+    pub fn register_intrinsic(
+        &mut self,
+        name: &str,
+        input: &Ty,
+        output: Ty,
+        handler: Rc<HostFn>,
+    ) -> std::result::Result<(), Vec<Error>> {
+        let (intrinsic, declared_input, declared_output) = self.host_intrinsic(name)?;
+        if *input != declared_input || output != declared_output {
+            return Err(vec![Error::HostTypeMismatch(
+                name.to_string(),
+                format!(
+                    "{} -> {}",
+                    declared_input.display(),
+                    declared_output.display()
+                ),
+                format!("{} -> {}", input.display(), output.display()),
+            )]);
+        }
+        self.host_intrinsics.insert(intrinsic, output, handler);
+        Ok(())
+    }
+
+    /// Registers a Rust function as the implementation of the intrinsic function with the given
+    /// name, converting the argument and return value of each call. See
+    /// [`Interpreter::register_intrinsic`].
+    /// # Errors
+    /// If the name is not an intrinsic function declared in this session, or if the types of
+    /// the function do not match its declaration, an error is returned.
+    pub fn register_function<I: HostValue, O: HostValue>(
+        &mut self,
+        name: &str,
+        function: impl Fn(I) -> std::result::Result<O, String> + 'static,
+    ) -> std::result::Result<(), Vec<Error>> {
+        self.register_intrinsic(
+            name,
+            &I::ty(),
+            O::ty(),
+            Rc::new(move |arg| function(I::from_value(arg)).map(HostValue::into_value)),
+        )
+    }
+
+    /// Evaluates the name of an intrinsic function that a host function can implement, and
+    /// returns its declared name, input type and output type.
+    fn host_intrinsic(&mut self, name: &str) -> std::result::Result<(Rc<str>, Ty, Ty), Vec<Error>> {
+        let session_packages = [self.package, self.source_package];
+        let (package, item) = self.eval_item(name)?;
+        let decl = match &item.kind {
+            crate::hir::ItemKind::Callable(decl)
+                if decl.kind == crate::hir::CallableKind::Function
+                    && decl.generics.is_empty()
+                    && matches!(
+                        decl.body.body,
+                        crate::hir::SpecBody::Gen(crate::hir::SpecGen::Intrinsic)
+                    )
+                    && session_packages.contains(&package) =>
+            {
+                decl
+            }
+            _ => return Err(vec![Error::NotAHostIntrinsic(name.to_string())]),
+        };
+        for ty in [&decl.input.ty, &decl.output] {
+            if !is_host_type(ty) {
+                return Err(vec![Error::UnsupportedHostType(
+                    name.to_string(),
+                    ty.display(),
+                )]);
+            }
+        }
+        Ok((
+            decl.name.name.clone(),
+            decl.input.ty.clone(),
+            decl.output.clone(),
+        ))
+    }
+
+    /// Evaluates the name of a callable or type and returns the package and HIR item it refers
+    /// to.
+    fn eval_item(
+        &mut self,
+        name: &str,
+    ) -> std::result::Result<(PackageId, &crate::hir::Item), Vec<Error>> {
+        let mut sink = std::io::sink();
+        let mut out = GenericReceiver::new(&mut sink);
+        let store_item_id = match self.eval_fragments(&mut out, name)? {
+            Value::Closure(b) => b.id,
+            Value::Global(item_id, _) => item_id,
+            _ => return Err(vec![Error::NotAnItem]),
+        };
+        let package = map_fir_package_to_hir(store_item_id.package);
+        let local_item_id = crate::hir::LocalItemId::from(usize::from(store_item_id.item));
+        let item = self
+            .compiler
+            .package_store()
+            .get(package)
+            .expect("package should exist in the package store")
+            .package
+            .items
+            .get(local_item_id)
+            .expect("item should exist in the package");
+        Ok((store_item_id.package, item))
+    }
+
     /// Gets the current quantum state of the simulator.
     pub fn get_quantum_state(&mut self) -> (Vec<(BigUint, Complex<f64>)>, usize) {
         self.sim.capture_quantum_state()
@@ -463,7 +760,8 @@ impl Interpreter {
     }
 
     /// Performs QIR codegen using the given entry expression on a new instance of the environment
-    /// and simulator but using the current compilation.
+    /// and simulator but using the current compilation. The generated program is checked against
+    /// the target limits, if any were set.
     pub fn qirgen(&mut self, expr: &str) -> std::result::Result<String, Vec<Error>> {
         if self.capabilities == TargetCapabilityFlags::all() {
             return Err(vec![Error::UnsupportedRuntimeCapabilities]);
@@ -490,7 +788,7 @@ impl Interpreter {
                 .into(),
         };
         // Generate QIR
-        fir_to_qir(
+        let program = fir_to_rir(
             &self.fir_store,
             self.capabilities,
             Some(compute_properties),
@@ -510,7 +808,13 @@ impl Interpreter {
                 &source_package.sources,
                 e,
             ))]
-        })
+        })?;
+        let errors = qsc_rir::passes::check_target(&program, &self.target);
+        if errors.is_empty() {
+            Ok(rir_to_qir(&program))
+        } else {
+            Err(errors.into_iter().map(Error::Target).collect())
+        }
     }
 
     /// Generates a circuit representation for the program.
@@ -587,6 +891,7 @@ impl Interpreter {
             self.compiler.package_store(),
             &self.fir_store,
             &mut Env::default(),
+            &self.host_intrinsics,
             sim,
             receiver,
         ))
@@ -620,6 +925,7 @@ impl Interpreter {
             self.compiler.package_store(),
             &self.fir_store,
             &mut Env::default(),
+            &self.host_intrinsics,
             sim,
             &mut out,
         )
@@ -782,6 +1088,34 @@ pub struct Debugger {
     position_encoding: Encoding,
     /// The current state of the evaluator.
     state: State,
+    /// The language features used to parse breakpoint conditions and log messages.
+    language_features: LanguageFeatures,
+    /// The number of times each breakpoint has been hit, for evaluating hit conditions.
+    /// Going back in time restores the counts saved with the checkpoint replayed from.
+    hits: FxHashMap<StmtId, u32>,
+    /// The number of statements execution has stopped at so far.
+    steps: usize,
+    /// The number of statements between checkpoints, or zero if checkpoints are disabled.
+    checkpoint_interval: usize,
+    /// Saved program states to travel back to, ordered by step.
+    checkpoints: Vec<Checkpoint>,
+}
+
+/// The default number of statements between checkpoints.
+const DEFAULT_CHECKPOINT_INTERVAL: usize = 100;
+
+/// The number of checkpoints above which older checkpoints are thinned out.
+const MAX_CHECKPOINTS: usize = 64;
+
+/// A saved state of the program being debugged.
+struct Checkpoint {
+    /// The step at which the state was saved.
+    step: usize,
+    state: State,
+    env: Env,
+    sim: Box<dyn Any>,
+    /// The breakpoint hit counts at the saved step.
+    hits: FxHashMap<StmtId, u32>,
 }
 
 impl Debugger {
@@ -804,11 +1138,30 @@ impl Debugger {
         let source_package_id = interpreter.source_package;
         let unit = interpreter.fir_store.get(source_package_id);
         let entry_exec_graph = unit.entry_exec_graph.clone();
-        Ok(Self {
+        let mut debugger = Self {
             interpreter,
             position_encoding,
             state: State::new(source_package_id, entry_exec_graph, None),
-        })
+            language_features,
+            hits: FxHashMap::default(),
+            steps: 0,
+            checkpoint_interval: DEFAULT_CHECKPOINT_INTERVAL,
+            checkpoints: Vec::new(),
+        };
+        debugger.save_checkpoint();
+        Ok(debugger)
+    }
+
+    /// Sets the number of statements between the checkpoints that stepping backwards
+    /// resumes from. Smaller intervals make stepping backwards faster at the cost of memory.
+    /// An interval of zero disables checkpoints, and with them stepping backwards.
+    pub fn set_checkpoint_interval(&mut self, interval: usize) {
+        self.checkpoint_interval = interval;
+        if interval == 0 {
+            self.checkpoints.clear();
+        } else if self.checkpoints.is_empty() {
+            self.save_checkpoint();
+        }
     }
 
     /// Resumes execution with specified `StepAction`.
@@ -819,15 +1172,127 @@ impl Debugger {
         receiver: &mut impl Receiver,
         breakpoints: &[StmtId],
         step: StepAction,
+    ) -> std::result::Result<StepResult, Vec<Error>> {
+        let breakpoints: Vec<Breakpoint> = breakpoints.iter().copied().map(Into::into).collect();
+        self.eval_step_with_breakpoints(receiver, &breakpoints, step)
+    }
+
+    /// Resumes execution with specified `StepAction`, stopping only at the breakpoints
+    /// whose conditions are met. Logpoints write their message to the receiver
+    /// and never stop execution.
+    /// # Errors
+    /// Returns a vector of errors if evaluating the entry point fails.
+    pub fn eval_step_with_breakpoints(
+        &mut self,
+        receiver: &mut impl Receiver,
+        breakpoints: &[Breakpoint],
+        step: StepAction,
+    ) -> std::result::Result<StepResult, Vec<Error>> {
+        let ids: Vec<StmtId> = breakpoints.iter().map(|bp| bp.id).collect();
+        let step_frame = self.state.call_depth();
+        // Execution advances one statement at a time so that every statement is counted
+        // and checkpoints can be taken between them.
+        loop {
+            let result = self.resume(receiver, &ids)?;
+            match result {
+                StepResult::BreakpointHit(id) => {
+                    // The hit is counted before the step so that a checkpoint saved for
+                    // this statement includes it.
+                    let is_stop = breakpoints
+                        .iter()
+                        .find(|bp| bp.id == id)
+                        .map_or(true, |breakpoint| self.should_stop(receiver, breakpoint));
+                    self.record_step();
+                    if is_stop {
+                        return Ok(result);
+                    }
+                }
+                StepResult::Return(_) => return Ok(result),
+                _ => self.record_step(),
+            }
+            if let Some(result) = self.state.check_for_step(step, step_frame) {
+                return Ok(result);
+            }
+        }
+    }
+
+    /// Moves execution back to the previous statement of the current callable or its callers,
+    /// the reverse of stepping over the previous statement.
+    /// # Errors
+    /// Returns a vector of errors if no checkpoint precedes the current statement.
+    pub fn step_back(&mut self) -> std::result::Result<StepResult, Vec<Error>> {
+        let depth = self.state.call_depth();
+        self.run_back(&[], |debugger, _| debugger.state.call_depth() <= depth)?;
+        Ok(StepResult::Next)
+    }
+
+    /// Runs execution backwards until the most recent earlier statement at which one of the
+    /// given breakpoints would have stopped, or else to the earliest statement that can be
+    /// reached. Breakpoint conditions are checked, while hit conditions and logpoints are ignored.
+    /// # Errors
+    /// Returns a vector of errors if no checkpoint precedes the current statement.
+    pub fn reverse_continue(
+        &mut self,
+        breakpoints: &[Breakpoint],
+    ) -> std::result::Result<StepResult, Vec<Error>> {
+        let ids: Vec<StmtId> = breakpoints.iter().map(|bp| bp.id).collect();
+        let hit = self.run_back(&ids, |debugger, result| {
+            let StepResult::BreakpointHit(id) = result else {
+                return false;
+            };
+            breakpoints
+                .iter()
+                .any(|bp| bp.id == *id && bp.log_message.is_none() && debugger.condition_met(bp))
+        })?;
+        Ok(hit.unwrap_or(StepResult::Next))
+    }
+
+    /// Moves execution back to the most recent earlier statement for which `is_stop` returns
+    /// true, and returns the step result for it. If there is no such statement, moves to the
+    /// earliest statement that can be reached and returns `None`.
+    fn run_back(
+        &mut self,
+        breakpoints: &[StmtId],
+        mut is_stop: impl FnMut(&mut Self, &StepResult) -> bool,
+    ) -> std::result::Result<Option<StepResult>, Vec<Error>> {
+        if self.steps <= 1 {
+            return Ok(None);
+        }
+        // Search the history one checkpoint at a time, starting with the latest.
+        let mut end = self.steps;
+        while end > 1 {
+            let start = self.restore_checkpoint(end - 2)?;
+            if let Some((step, result)) = self.replay(end - 1, breakpoints, &mut is_stop)?.pop() {
+                self.restore_checkpoint(step)?;
+                self.replay(step, &[], |_, _| false)?;
+                return Ok(Some(result));
+            }
+            if self.checkpoints.len() == 1 {
+                break;
+            }
+            end = start + 1;
+        }
+        let earliest = self.checkpoints[0].step.max(1);
+        self.restore_checkpoint(earliest)?;
+        self.replay(earliest, &[], |_, _| false)?;
+        Ok(None)
+    }
+
+    /// Resumes execution until the next statement.
+    fn resume(
+        &mut self,
+        receiver: &mut impl Receiver,
+        breakpoints: &[StmtId],
     ) -> std::result::Result<StepResult, Vec<Error>> {
         self.state
-            .eval(
+            .resume(
                 &self.interpreter.fir_store,
                 &mut self.interpreter.env,
-                &mut self.interpreter.sim,
+                &mut HostBackend::new(&mut self.interpreter.sim, &self.interpreter.host_intrinsics),
                 receiver,
                 breakpoints,
-                step,
+                StepAction::In,
+                0,
             )
             .map_err(|(error, call_stack)| {
                 eval_error(
@@ -839,6 +1304,129 @@ impl Debugger {
             })
     }
 
+    /// Counts the statement execution stopped at, saving a checkpoint if one is due.
+    fn record_step(&mut self) {
+        self.steps += 1;
+        if self.checkpoint_interval > 0 && self.steps % self.checkpoint_interval == 0 {
+            self.save_checkpoint();
+        }
+    }
+
+    fn save_checkpoint(&mut self) {
+        let Some(sim) = self.interpreter.sim.checkpoint() else {
+            return;
+        };
+        self.checkpoints.push(Checkpoint {
+            step: self.steps,
+            state: self.state.clone(),
+            env: self.interpreter.env.clone(),
+            sim,
+            hits: self.hits.clone(),
+        });
+        if self.checkpoints.len() > MAX_CHECKPOINTS {
+            // Keep memory bounded by dropping every other checkpoint and saving half as often.
+            let mut index = 0;
+            self.checkpoints.retain(|_| {
+                index += 1;
+                index % 2 == 1
+            });
+            self.checkpoint_interval *= 2;
+        }
+    }
+
+    /// Restores the latest checkpoint at or before the given step and returns its step.
+    /// Later checkpoints are discarded, since running forward again recreates them.
+    fn restore_checkpoint(&mut self, step: usize) -> std::result::Result<usize, Vec<Error>> {
+        let index = self
+            .checkpoints
+            .partition_point(|checkpoint| checkpoint.step <= step)
+            .checked_sub(1)
+            .ok_or_else(|| vec![Error::NoCheckpoint])?;
+        self.checkpoints.truncate(index + 1);
+        let checkpoint = &self.checkpoints[index];
+        self.state = checkpoint.state.clone();
+        self.interpreter.env = checkpoint.env.clone();
+        self.interpreter.sim.restore(checkpoint.sim.as_ref());
+        self.hits = checkpoint.hits.clone();
+        self.steps = checkpoint.step;
+        Ok(self.steps)
+    }
+
+    /// Runs forward to the given step without writing any output, returning the steps
+    /// for which `is_stop` returns true.
+    fn replay(
+        &mut self,
+        target: usize,
+        breakpoints: &[StmtId],
+        mut is_stop: impl FnMut(&mut Self, &StepResult) -> bool,
+    ) -> std::result::Result<Vec<(usize, StepResult)>, Vec<Error>> {
+        let mut sink = std::io::sink();
+        let mut receiver = GenericReceiver::new(&mut sink);
+        let mut stops = Vec::new();
+        while self.steps < target {
+            let result = self.resume(&mut receiver, breakpoints)?;
+            if let StepResult::Return(_) = result {
+                break;
+            }
+            self.record_step();
+            if is_stop(self, &result) {
+                stops.push((self.steps, result));
+            }
+        }
+        Ok(stops)
+    }
+
+    /// Returns true unless the breakpoint's condition evaluates to false.
+    /// A condition that can't be evaluated stops execution so that the problem is visible.
+    fn condition_met(&mut self, breakpoint: &Breakpoint) -> bool {
+        let Some(condition) = &breakpoint.condition else {
+            return true;
+        };
+        !matches!(
+            self.evaluate_in_paused_frame(condition),
+            Ok(Value::Bool(false))
+        )
+    }
+
+    /// Evaluates an expression of a breakpoint in the frame execution is paused in.
+    /// Only functions can be called, and any output they write is discarded.
+    fn evaluate_in_paused_frame(&mut self, expr: &str) -> std::result::Result<Value, Vec<Error>> {
+        let frame_id = self.state.get_stack_frames().len().saturating_sub(1);
+        let mut sink = std::io::sink();
+        let mut receiver = GenericReceiver::new(&mut sink);
+        self.evaluate(&mut receiver, expr, frame_id, false)
+    }
+
+    fn should_stop(&mut self, receiver: &mut impl Receiver, breakpoint: &Breakpoint) -> bool {
+        if !self.condition_met(breakpoint) {
+            return false;
+        }
+        let hits = self.hits.entry(breakpoint.id).or_default();
+        *hits += 1;
+        if let Some(hit_condition) = &breakpoint.hit_condition {
+            if watch::hit_condition_met(hit_condition, *hits) == Some(false) {
+                return false;
+            }
+        }
+        if let Some(log_message) = &breakpoint.log_message {
+            let message = watch::interpolate(log_message, |expr| {
+                self.evaluate_in_paused_frame(expr).map_err(|errors| {
+                    // Report the innermost cause, since the outer errors only name the
+                    // compilation phase that failed.
+                    errors.first().map_or_else(String::new, |error| {
+                        iter::successors(Some(error as &dyn std::error::Error), |e| e.source())
+                            .last()
+                            .map(ToString::to_string)
+                            .unwrap_or_default()
+                    })
+                })
+            });
+            // A logpoint only stops execution if its message can't be written.
+            return receiver.message(&message).is_err();
+        }
+        true
+    }
+
     #[must_use]
     pub fn get_stack_frames(&self) -> Vec<StackFrame> {
         let frames = self.state.get_stack_frames();
@@ -875,6 +1463,34 @@ impl Debugger {
         self.interpreter.sim.capture_quantum_state()
     }
 
+    /// Returns the probability, reduced density matrix and Bloch vector of each allocated qubit.
+    pub fn qubit_views(&mut self) -> Vec<state_views::QubitView> {
+        let (state, qubit_count) = self.capture_quantum_state();
+        state_views::qubit_views(&state, qubit_count)
+    }
+
+    /// Returns the entanglement entropy, in bits, of the given subset of allocated qubits.
+    /// # Errors
+    /// Returns an error if a qubit is out of range or listed more than once.
+    pub fn entanglement_entropy(
+        &mut self,
+        qubits: &[usize],
+    ) -> std::result::Result<f64, state_views::Error> {
+        let (state, qubit_count) = self.capture_quantum_state();
+        state_views::entanglement_entropy(&state, qubit_count, qubits)
+    }
+
+    /// Returns the expectation value of a Pauli string over all allocated qubits.
+    /// # Errors
+    /// Returns an error if the Pauli string is invalid or doesn't match the number of qubits.
+    pub fn pauli_expectation(
+        &mut self,
+        paulis: &str,
+    ) -> std::result::Result<f64, state_views::Error> {
+        let (state, qubit_count) = self.capture_quantum_state();
+        state_views::pauli_expectation(&state, qubit_count, paulis)
+    }
+
     pub fn circuit(&self) -> Circuit {
         self.interpreter.get_circuit()
     }
@@ -905,6 +1521,65 @@ impl Debugger {
         }
     }
 
+    /// Returns the locations at which to break when entering the callables with the given name
+    /// in the source package. The name may be qualified with its namespace.
+    #[must_use]
+    pub fn get_function_breakpoints(&self, name: &str) -> Vec<FunctionBreakpoint> {
+        let package = self
+            .interpreter
+            .fir_store
+            .get(self.interpreter.source_package);
+        let (namespace, name) = match name.rsplit_once('.') {
+            Some((namespace, name)) => (Some(namespace), name),
+            None => (None, name),
+        };
+        let mut breakpoints = Vec::new();
+        for item in package.items.values() {
+            let fir::ItemKind::Callable(decl) = &item.kind else {
+                continue;
+            };
+            if decl.name.name.as_ref() != name {
+                continue;
+            }
+            if let Some(namespace) = namespace {
+                let parent = item.parent.map(|parent| &package.get_item(parent).kind);
+                if !matches!(parent, Some(fir::ItemKind::Namespace(ns, _)) if ns.name.as_ref() == namespace)
+                {
+                    continue;
+                }
+            }
+            let specs = match &decl.implementation {
+                fir::CallableImpl::Intrinsic => Vec::new(),
+                fir::CallableImpl::Spec(spec) => [&spec.adj, &spec.ctl, &spec.ctl_adj]
+                    .into_iter()
+                    .flatten()
+                    .chain(std::iter::once(&spec.body))
+                    .collect(),
+                fir::CallableImpl::SimulatableIntrinsic(spec) => vec![spec],
+            };
+            for spec in specs {
+                let block = package.get_block(spec.block);
+                let Some(stmt) = block.stmts.first().map(|id| package.get_stmt(*id)) else {
+                    continue;
+                };
+                if stmt.span != Span::default() {
+                    breakpoints.push(FunctionBreakpoint {
+                        id: stmt.id.into(),
+                        location: Location::from(
+                            stmt.span,
+                            map_fir_package_to_hir(self.interpreter.source_package),
+                            self.interpreter.compiler.package_store(),
+                            self.position_encoding,
+                        ),
+                    });
+                }
+            }
+        }
+        breakpoints.sort_by_key(|bp| bp.id);
+        breakpoints.dedup_by_key(|bp| bp.id);
+        breakpoints
+    }
+
     #[must_use]
     pub fn get_locals(&self) -> Vec<VariableInfo> {
         self.interpreter
@@ -915,6 +1590,181 @@ impl Debugger {
             .collect()
     }
 
+    /// Returns the variables in the given stack frame, where frame ids are
+    /// indices into the frames returned by [`Debugger::get_stack_frames`].
+    #[must_use]
+    pub fn get_locals_in_frame(&self, frame_id: usize) -> Vec<VariableInfo> {
+        self.interpreter
+            .env
+            .get_variables_in_frame(frame_id + 1)
+            .into_iter()
+            .filter(|v| !v.name.starts_with('@'))
+            .collect()
+    }
+
+    /// Evaluates a Q# expression in the scope of the given stack frame, where frame ids are
+    /// indices into the frames returned by [`Debugger::get_stack_frames`].
+    ///
+    /// The expression is compiled as a lambda over the frame's variables, with the namespace
+    /// of the frame's callable open, and runs without changing where execution is paused.
+    /// Assignments to mutable variables of the frame are written back to it. Unless
+    /// `allow_quantum` is set, expressions that call operations or allocate qubits are
+    /// rejected instead of being run.
+    /// # Errors
+    /// Returns a vector of errors if the expression fails to compile or to evaluate.
+    pub fn evaluate(
+        &mut self,
+        receiver: &mut impl Receiver,
+        expr: &str,
+        frame_id: usize,
+        allow_quantum: bool,
+    ) -> std::result::Result<Value, Vec<Error>> {
+        let frames = self.state.get_stack_frames();
+        let frame = frames
+            .get(frame_id)
+            .ok_or(vec![Error::FrameNotFound(frame_id)])?;
+        let (mutable, namespace) = self.frame_scope(frame);
+        let locals = self.get_locals_in_frame(frame_id);
+
+        // Only the variables that the expression uses become parameters of the lambda,
+        // since each one needs a type that can be written in Q#.
+        let mut used: Vec<&VariableInfo> = Vec::new();
+        for name in watch::free_names(expr, self.language_features) {
+            if let Some(local) = locals.iter().rev().find(|local| local.name == name) {
+                used.push(local);
+            }
+        }
+        let mut params = Vec::new();
+        let mut bindings = String::new();
+        let mut outputs = Vec::new();
+        for (i, local) in used.iter().enumerate() {
+            let ty = watch::type_of(&local.value)
+                .ok_or_else(|| vec![Error::UnknownVariableType(local.name.to_string())])?;
+            params.push(format!("__arg{i}"));
+            if mutable.contains(&local.id) {
+                let _ = write!(bindings, "mutable {} : {ty} = __arg{i}; ", local.name);
+                outputs.push(local);
+            } else {
+                let _ = write!(bindings, "let {} : {ty} = __arg{i}; ", local.name);
+            }
+        }
+        let arrow = if allow_quantum { "=>" } else { "->" };
+        let output_names = outputs.iter().fold(String::new(), |mut names, local| {
+            let _ = write!(names, "{}, ", local.name);
+            names
+        });
+        // Assignments are emitted as statements, since they have no value to bind.
+        let body = if watch::is_assignment(expr, self.language_features) {
+            format!("{expr}; let __result = ();")
+        } else {
+            format!("let __result = {{ {expr} }};")
+        };
+        let mut source = format!(
+            "({}) {arrow} {{ {bindings}{body} (__result, ({output_names})) }}",
+            params.join(", ")
+        );
+        if let Some(namespace) = namespace {
+            source = format!("open {namespace}; {source}");
+        }
+
+        let lambda = self.declare_lambda(receiver, &source)?;
+
+        let mut args: Vec<Value> = used.iter().map(|local| local.value.clone()).collect();
+        let arg = if args.len() == 1 {
+            args.remove(0)
+        } else {
+            Value::Tuple(args.into())
+        };
+        let result = qsc_eval::invoke(
+            self.interpreter.package,
+            self.interpreter.classical_seed,
+            &self.interpreter.fir_store,
+            &mut Env::default(),
+            &mut HostBackend::new(&mut self.interpreter.sim, &self.interpreter.host_intrinsics),
+            receiver,
+            lambda,
+            arg,
+        )
+        .map_err(|(error, call_stack)| {
+            eval_error(
+                self.interpreter.compiler.package_store(),
+                &self.interpreter.fir_store,
+                call_stack,
+                error,
+            )
+        })?;
+
+        let (result, updated) = match result {
+            Value::Tuple(items) if items.len() == 2 => (items[0].clone(), items[1].clone()),
+            _ => panic!("evaluated expression should return a result and the updated variables"),
+        };
+        for (local, value) in outputs.iter().zip(updated.unwrap_tuple().iter()) {
+            if *value != local.value {
+                self.interpreter.env.update_variable_in_frame(
+                    frame_id + 1,
+                    local.id,
+                    value.clone(),
+                );
+            }
+        }
+        Ok(result)
+    }
+
+    /// Compiles and evaluates the declaration of a lambda, returning the lambda value.
+    fn declare_lambda(
+        &mut self,
+        receiver: &mut impl Receiver,
+        source: &str,
+    ) -> std::result::Result<Value, Vec<Error>> {
+        let label = self.interpreter.next_line_label();
+        let increment = self
+            .interpreter
+            .compiler
+            .compile_fragments_fail_fast(&label, source)
+            .map_err(|errors| {
+                let errors = into_errors(errors);
+                let has_quantum_effects = errors.iter().any(|error| {
+                    error.code().is_some_and(|code| {
+                        let code = code.to_string();
+                        code == "Qsc.CallableLimits.OpCall"
+                            || code == "Qsc.CallableLimits.QubitAlloc"
+                    })
+                });
+                if has_quantum_effects {
+                    vec![Error::QuantumSideEffects]
+                } else {
+                    errors
+                }
+            })?;
+        // The lambda is declared in an environment of its own so that the scopes of the
+        // paused program are left untouched.
+        let env = std::mem::take(&mut self.interpreter.env);
+        let lambda = self.interpreter.eval_increment(receiver, increment);
+        self.interpreter.env = env;
+        lambda
+    }
+
+    /// Returns the mutable variables of the frame's callable and the name of the namespace
+    /// the callable is declared in.
+    fn frame_scope(&self, frame: &Frame) -> (FxHashSet<LocalVarId>, Option<Rc<str>>) {
+        let package = self.interpreter.fir_store.get(frame.id.package);
+        let Some(item) = package.items.get(frame.id.item) else {
+            return (FxHashSet::default(), None);
+        };
+        let mut mutable = MutableLocals {
+            package,
+            ids: FxHashSet::default(),
+        };
+        if let fir::ItemKind::Callable(decl) = &item.kind {
+            mutable.visit_callable_decl(decl);
+        }
+        let namespace = match item.parent.map(|parent| &package.get_item(parent).kind) {
+            Some(fir::ItemKind::Namespace(namespace, _)) => Some(namespace.name.clone()),
+            _ => None,
+        };
+        (mutable.ids, namespace)
+    }
+
     fn source_package(&self) -> &CompileUnit {
         self.interpreter
             .compiler
@@ -933,6 +1783,7 @@ fn eval(
     package_store: &PackageStore,
     fir_store: &fir::PackageStore,
     env: &mut Env,
+    host_intrinsics: &HostIntrinsics,
     sim: &mut impl Backend<ResultType = impl Into<val::Result>>,
     receiver: &mut impl Receiver,
 ) -> InterpretResult {
@@ -942,7 +1793,7 @@ fn eval(
         exec_graph,
         fir_store,
         env,
-        sim,
+        &mut HostBackend::new(sim, host_intrinsics),
         receiver,
     )
     .map_err(|(error, call_stack)| eval_error(package_store, fir_store, call_stack, error))
@@ -966,6 +1817,40 @@ pub struct BreakpointSpan {
     pub range: Range,
 }
 
+/// A breakpoint at a statement, as returned by [`Debugger::get_breakpoints`]
+/// or [`Debugger::get_function_breakpoints`].
+#[derive(Clone, Debug, Default)]
+pub struct Breakpoint {
+    /// The id of the statement to break at.
+    pub id: StmtId,
+    /// A Q# boolean expression over the locals of the paused frame.
+    /// Execution only stops when it evaluates to `true`.
+    pub condition: Option<String>,
+    /// The number of hits after which to stop, such as `3`, `>= 3` or `% 2`.
+    pub hit_condition: Option<String>,
+    /// A message to log instead of stopping. Expressions in braces, such as `{x}`,
+    /// are replaced with their values.
+    pub log_message: Option<String>,
+}
+
+impl From<StmtId> for Breakpoint {
+    fn from(id: StmtId) -> Self {
+        Self {
+            id,
+            ..Default::default()
+        }
+    }
+}
+
+/// The location of a breakpoint on entry to a callable.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FunctionBreakpoint {
+    /// The id of the first statement of the callable.
+    pub id: u32,
+    /// The source location of the statement.
+    pub location: Location,
+}
+
 struct BreakpointCollector<'a> {
     statements: FxHashSet<BreakpointSpan>,
     sources: &'a SourceMap,
@@ -1071,9 +1956,86 @@ fn eval_error(
     vec![error::from_eval(error, package_store, stack_trace).into()]
 }
 
+/// Checks that the source package only uses the target capabilities, if the target is restricted.
+fn check_source_capabilities(
+    compiler: &Compiler,
+    fir_store: &fir::PackageStore,
+    capabilities: TargetCapabilityFlags,
+) -> std::result::Result<(), Vec<Error>> {
+    if capabilities == TargetCapabilityFlags::all() {
+        return Ok(());
+    }
+
+    let source_package_id = compiler.source_package_id();
+    PassContext::run_fir_passes_on_fir(
+        fir_store,
+        map_hir_package_to_fir(source_package_id),
+        capabilities,
+    )
+    .map(|_| ())
+    .map_err(|caps_errors| {
+        let source_package = compiler
+            .package_store()
+            .get(source_package_id)
+            .expect("package should exist in the package store");
+
+        caps_errors
+            .into_iter()
+            .map(|error| Error::Pass(WithSource::from_map(&source_package.sources, error)))
+            .collect()
+    })
+}
+
 fn into_errors(errors: Vec<crate::compile::Error>) -> Vec<Error> {
     errors
         .into_iter()
         .map(|error| Error::Compile(error.into_with_source()))
         .collect::<Vec<_>>()
 }
+
+/// Collects the variables of a callable that are declared as mutable.
+struct MutableLocals<'a> {
+    package: &'a Package,
+    ids: FxHashSet<LocalVarId>,
+}
+
+impl MutableLocals<'_> {
+    fn add_bindings(&mut self, pat: PatId) {
+        match &self.package.get_pat(pat).kind {
+            fir::PatKind::Bind(ident) => {
+                self.ids.insert(ident.id);
+            }
+            fir::PatKind::Tuple(items) => {
+                for item in items {
+                    self.add_bindings(*item);
+                }
+            }
+            fir::PatKind::Discard => {}
+        }
+    }
+}
+
+impl<'a> Visitor<'a> for MutableLocals<'a> {
+    fn visit_stmt(&mut self, stmt: StmtId) {
+        if let fir::StmtKind::Local(fir::Mutability::Mutable, pat, _) = self.get_stmt(stmt).kind {
+            self.add_bindings(pat);
+        }
+        visit::walk_stmt(self, stmt);
+    }
+
+    fn get_block(&self, id: BlockId) -> &'a Block {
+        self.package.get_block(id)
+    }
+
+    fn get_expr(&self, id: ExprId) -> &'a Expr {
+        self.package.get_expr(id)
+    }
+
+    fn get_pat(&self, id: PatId) -> &'a Pat {
+        self.package.get_pat(id)
+    }
+
+    fn get_stmt(&self, id: StmtId) -> &'a Stmt {
+        self.package.get_stmt(id)
+    }
+}
@@ -236,4 +236,581 @@ mod given_debugger {
             Ok(())
         }
     }
+
+    static LOOP_SOURCE: &str = r#"
+        namespace Test {
+            @EntryPoint()
+            operation Main() : Int {
+                mutable total = 0;
+                for i in 0..4 {
+                    set total += i;
+                }
+                Add(total, 1)
+            }
+            function Add(a : Int, b : Int) : Int {
+                a + b
+            }
+        }"#;
+
+    #[cfg(test)]
+    mod breakpoints {
+        use super::*;
+        use crate::interpret::Breakpoint;
+        use qsc_data_structures::target::TargetCapabilityFlags;
+
+        fn loop_debugger() -> Debugger {
+            let sources = SourceMap::new([("test".into(), LOOP_SOURCE.into())], None);
+            let (std_id, store) =
+                crate::compile::package_store_with_stdlib(TargetCapabilityFlags::all());
+            Debugger::new(
+                sources,
+                TargetCapabilityFlags::all(),
+                Encoding::Utf8,
+                LanguageFeatures::default(),
+                store,
+                &[(std_id, None)],
+            )
+            .expect("debugger should be created")
+        }
+
+        fn breakpoint_at_line(debugger: &Debugger, line: u32) -> Breakpoint {
+            let span = debugger
+                .get_breakpoints("test")
+                .into_iter()
+                .find(|bp| bp.range.start.line == line)
+                .expect("line should have a breakpoint");
+            StmtId::from(span.id).into()
+        }
+
+        fn continue_to(
+            debugger: &mut Debugger,
+            breakpoints: &[Breakpoint],
+        ) -> (Result<StepResult, Vec<crate::interpret::Error>>, String) {
+            let mut cursor = Cursor::new(Vec::<u8>::new());
+            let mut receiver = CursorReceiver::new(&mut cursor);
+            (
+                debugger.eval_step_with_breakpoints(
+                    &mut receiver,
+                    breakpoints,
+                    StepAction::Continue,
+                ),
+                receiver.dump(),
+            )
+        }
+
+        fn local(debugger: &Debugger, name: &str) -> String {
+            debugger
+                .get_locals()
+                .into_iter()
+                .find(|local| local.name.as_ref() == name)
+                .map(|local| local.value.to_string())
+                .expect("local should exist")
+        }
+
+        fn expect_hit(debugger: &mut Debugger, breakpoints: &[Breakpoint]) {
+            match continue_to(debugger, breakpoints).0 {
+                Ok(StepResult::BreakpointHit(_)) => (),
+                Ok(v) => panic!("Expected BP, got {v:?}"),
+                Err(e) => panic!("Expected BP, got {e:?}"),
+            }
+        }
+
+        fn expect_finish(debugger: &mut Debugger, breakpoints: &[Breakpoint]) -> String {
+            match continue_to(debugger, breakpoints) {
+                (Ok(StepResult::Return(value)), output) => {
+                    assert_eq!(value.to_string(), "11");
+                    output
+                }
+                (Ok(v), _) => panic!("Expected Return, got {v:?}"),
+                (Err(e), _) => panic!("Expected Return, got {e:?}"),
+            }
+        }
+
+        #[test]
+        fn conditional_breakpoint_stops_when_condition_is_true() {
+            let mut debugger = loop_debugger();
+            let breakpoints = [Breakpoint {
+                condition: Some("i == 3".into()),
+                ..breakpoint_at_line(&debugger, 6)
+            }];
+            expect_hit(&mut debugger, &breakpoints);
+            assert_eq!(local(&debugger, "i"), "3");
+            assert_eq!(local(&debugger, "total"), "3");
+            expect_finish(&mut debugger, &breakpoints);
+        }
+
+        #[test]
+        fn conditional_breakpoint_can_call_functions() {
+            let mut debugger = loop_debugger();
+            let breakpoints = [Breakpoint {
+                condition: Some("Add(i, 1) == 4".into()),
+                ..breakpoint_at_line(&debugger, 6)
+            }];
+            expect_hit(&mut debugger, &breakpoints);
+            assert_eq!(local(&debugger, "i"), "3");
+            expect_finish(&mut debugger, &breakpoints);
+        }
+
+        #[test]
+        fn conditional_breakpoint_with_invalid_condition_stops() {
+            let mut debugger = loop_debugger();
+            let breakpoints = [Breakpoint {
+                condition: Some("j == 3".into()),
+                ..breakpoint_at_line(&debugger, 6)
+            }];
+            expect_hit(&mut debugger, &breakpoints);
+            assert_eq!(local(&debugger, "i"), "0");
+        }
+
+        #[test]
+        fn hit_count_breakpoint_stops_after_hits() {
+            let mut debugger = loop_debugger();
+            let breakpoints = [Breakpoint {
+                hit_condition: Some(">= 4".into()),
+                ..breakpoint_at_line(&debugger, 6)
+            }];
+            expect_hit(&mut debugger, &breakpoints);
+            assert_eq!(local(&debugger, "i"), "3");
+            expect_hit(&mut debugger, &breakpoints);
+            assert_eq!(local(&debugger, "i"), "4");
+            expect_finish(&mut debugger, &breakpoints);
+        }
+
+        #[test]
+        fn logpoint_logs_without_stopping() {
+            let mut debugger = loop_debugger();
+            let breakpoints = [Breakpoint {
+                condition: Some("i % 2 == 0".into()),
+                log_message: Some("i = {i}, total = {total}".into()),
+                ..breakpoint_at_line(&debugger, 6)
+            }];
+            let output = expect_finish(&mut debugger, &breakpoints);
+            expect_test::expect![[r#"
+                i = 0, total = 0
+                i = 2, total = 1
+                i = 4, total = 6"#]]
+            .assert_eq(&output);
+        }
+
+        #[test]
+        fn logpoint_can_call_functions() {
+            let mut debugger = loop_debugger();
+            let breakpoints = [Breakpoint {
+                condition: Some("i > 2".into()),
+                log_message: Some("sum = {Add(i, total)}, bad = {j}".into()),
+                ..breakpoint_at_line(&debugger, 6)
+            }];
+            let output = expect_finish(&mut debugger, &breakpoints);
+            expect_test::expect![[r#"
+                sum = 6, bad = <`j` not found>
+                sum = 10, bad = <`j` not found>"#]]
+            .assert_eq(&output);
+        }
+
+        #[test]
+        fn step_continues_past_breakpoint_that_does_not_stop() {
+            let mut debugger = loop_debugger();
+            let breakpoints = [
+                breakpoint_at_line(&debugger, 4),
+                Breakpoint {
+                    condition: Some("false".into()),
+                    ..breakpoint_at_line(&debugger, 6)
+                },
+            ];
+            expect_hit(&mut debugger, &breakpoints);
+            let mut cursor = Cursor::new(Vec::<u8>::new());
+            let mut receiver = CursorReceiver::new(&mut cursor);
+            let current_line = |debugger: &Debugger| {
+                let frames = debugger.get_stack_frames();
+                frames
+                    .last()
+                    .expect("should have a frame")
+                    .location
+                    .range
+                    .start
+                    .line
+            };
+            // Step over the statements generated for the loop header.
+            let mut line = 4;
+            while line < 6 {
+                let result = debugger.eval_step_with_breakpoints(
+                    &mut receiver,
+                    &breakpoints,
+                    StepAction::Next,
+                );
+                assert!(matches!(result, Ok(StepResult::Next)), "{result:?}");
+                line = current_line(&debugger);
+            }
+            // The step ends at the loop body even though its breakpoint doesn't stop.
+            assert_eq!(line, 6);
+            assert_eq!(local(&debugger, "i"), "0");
+        }
+
+        #[test]
+        fn function_breakpoint_stops_on_entry() {
+            let mut debugger = loop_debugger();
+            assert!(debugger.get_function_breakpoints("Other.Add").is_empty());
+            let function_breakpoints = debugger.get_function_breakpoints("Test.Add");
+            assert_eq!(
+                function_breakpoints,
+                debugger.get_function_breakpoints("Add")
+            );
+            let [bp] = function_breakpoints.as_slice() else {
+                panic!("expected one function breakpoint, got {function_breakpoints:?}");
+            };
+            assert_eq!(bp.location.source.as_ref(), "test");
+            assert_eq!(bp.location.range.start.line, 11);
+            let breakpoints = [StmtId::from(bp.id).into()];
+            expect_hit(&mut debugger, &breakpoints);
+            assert_eq!(local(&debugger, "a"), "10");
+            expect_finish(&mut debugger, &breakpoints);
+        }
+    }
+
+    static EVALUATE_SOURCE: &str = r#"
+        namespace Test {
+            @EntryPoint()
+            operation Main() : Int {
+                use q = Qubit();
+                mutable count = 2;
+                let scale = Scale(count);
+                Reset(q);
+                count + scale
+            }
+            function Scale(factor : Int) : Int {
+                let doubled = factor * 2;
+                doubled
+            }
+        }"#;
+
+    #[cfg(test)]
+    mod evaluate {
+        use super::*;
+        use qsc_data_structures::target::TargetCapabilityFlags;
+
+        /// Returns a debugger paused at the last statement of `Scale`.
+        fn paused_debugger() -> Debugger {
+            let sources = SourceMap::new([("test".into(), EVALUATE_SOURCE.into())], None);
+            let (std_id, store) =
+                crate::compile::package_store_with_stdlib(TargetCapabilityFlags::all());
+            let mut debugger = Debugger::new(
+                sources,
+                TargetCapabilityFlags::all(),
+                Encoding::Utf8,
+                LanguageFeatures::default(),
+                store,
+                &[(std_id, None)],
+            )
+            .expect("debugger should be created");
+            let bp = debugger
+                .get_breakpoints("test")
+                .into_iter()
+                .find(|bp| bp.range.start.line == 12)
+                .expect("line should have a breakpoint");
+            expect_bp(&mut debugger, &[bp.id.into()], bp.id.into());
+            debugger
+        }
+
+        fn evaluate(debugger: &mut Debugger, expr: &str, frame_id: usize, quantum: bool) -> String {
+            let mut cursor = Cursor::new(Vec::<u8>::new());
+            let mut receiver = CursorReceiver::new(&mut cursor);
+            match debugger.evaluate(&mut receiver, expr, frame_id, quantum) {
+                Ok(value) => value.to_string(),
+                Err(errors) => errors
+                    .iter()
+                    .map(|e| format!("error: {e}"))
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+            }
+        }
+
+        fn run_to_end(debugger: &mut Debugger) -> String {
+            match step(debugger, &[], StepAction::Continue).0 {
+                Ok(StepResult::Return(value)) => value.to_string(),
+                result => panic!("Expected Return, got {result:?}"),
+            }
+        }
+
+        fn locals(debugger: &Debugger, frame_id: usize) -> String {
+            debugger
+                .get_locals_in_frame(frame_id)
+                .iter()
+                .map(|local| format!("{} = {}", local.name, local.value))
+                .collect::<Vec<_>>()
+                .join(", ")
+        }
+
+        #[test]
+        fn locals_in_each_frame() {
+            let debugger = paused_debugger();
+            assert_eq!(debugger.get_stack_frames().len(), 2);
+            assert_eq!(locals(&debugger, 0), "q = Qubit0, count = 2");
+            assert_eq!(locals(&debugger, 1), "factor = 2, doubled = 4");
+            assert_eq!(locals(&debugger, 2), "");
+        }
+
+        #[test]
+        fn expression_in_top_frame() {
+            let mut debugger = paused_debugger();
+            assert_eq!(evaluate(&mut debugger, "doubled + factor", 1, false), "6");
+        }
+
+        #[test]
+        fn expression_in_caller_frame() {
+            let mut debugger = paused_debugger();
+            assert_eq!(evaluate(&mut debugger, "count * 10", 0, false), "20");
+        }
+
+        #[test]
+        fn expression_calling_function_in_frame_namespace() {
+            let mut debugger = paused_debugger();
+            assert_eq!(
+                evaluate(&mut debugger, "Scale(count) + Length([1, 2])", 0, false),
+                "6"
+            );
+        }
+
+        #[test]
+        fn assignment_updates_mutable_variable() {
+            let mut debugger = paused_debugger();
+            assert_eq!(evaluate(&mut debugger, "set count += 3", 0, false), "()");
+            assert_eq!(locals(&debugger, 0), "q = Qubit0, count = 5");
+            assert_eq!(run_to_end(&mut debugger), "9");
+        }
+
+        #[test]
+        fn assignment_to_immutable_variable_is_error() {
+            let mut debugger = paused_debugger();
+            let result = evaluate(&mut debugger, "set doubled = 3", 1, false);
+            assert!(result.starts_with("error"), "{result}");
+            assert_eq!(locals(&debugger, 1), "factor = 2, doubled = 4");
+        }
+
+        #[test]
+        fn quantum_side_effects_are_rejected() {
+            let mut debugger = paused_debugger();
+            assert_eq!(
+                evaluate(&mut debugger, "M(q)", 0, false),
+                "error: expression has quantum side effects"
+            );
+        }
+
+        #[test]
+        fn quantum_side_effects_are_allowed_when_requested() {
+            let mut debugger = paused_debugger();
+            assert_eq!(evaluate(&mut debugger, "M(q) == Zero", 0, true), "true");
+            assert_eq!(run_to_end(&mut debugger), "6");
+        }
+
+        #[test]
+        fn unknown_frame_is_error() {
+            let mut debugger = paused_debugger();
+            assert_eq!(
+                evaluate(&mut debugger, "1", 2, false),
+                "error: stack frame 2 not found"
+            );
+        }
+    }
+
+    static FLIP_SOURCE: &str = r#"
+        namespace Test {
+            @EntryPoint()
+            operation Main() : Result {
+                use q = Qubit();
+                mutable flips = 0;
+                for i in 0..39 {
+                    X(q);
+                    set flips += 1;
+                }
+                M(q)
+            }
+        }"#;
+
+    #[cfg(test)]
+    mod time_travel {
+        use super::*;
+        use crate::interpret::Breakpoint;
+        use qsc_data_structures::target::TargetCapabilityFlags;
+
+        fn flip_debugger(checkpoint_interval: usize) -> Debugger {
+            let sources = SourceMap::new([("test".into(), FLIP_SOURCE.into())], None);
+            let (std_id, store) =
+                crate::compile::package_store_with_stdlib(TargetCapabilityFlags::all());
+            let mut debugger = Debugger::new(
+                sources,
+                TargetCapabilityFlags::all(),
+                Encoding::Utf8,
+                LanguageFeatures::default(),
+                store,
+                &[(std_id, None)],
+            )
+            .expect("debugger should be created");
+            debugger.set_checkpoint_interval(checkpoint_interval);
+            debugger
+        }
+
+        fn breakpoint_at_line(debugger: &Debugger, line: u32, condition: &str) -> Breakpoint {
+            let span = debugger
+                .get_breakpoints("test")
+                .into_iter()
+                .find(|bp| bp.range.start.line == line)
+                .expect("line should have a breakpoint");
+            Breakpoint {
+                id: StmtId::from(span.id),
+                condition: Some(condition.to_string()).filter(|c| !c.is_empty()),
+                ..Breakpoint::default()
+            }
+        }
+
+        fn continue_to(debugger: &mut Debugger, breakpoints: &[Breakpoint]) -> StepResult {
+            let mut cursor = Cursor::new(Vec::<u8>::new());
+            let mut receiver = CursorReceiver::new(&mut cursor);
+            debugger
+                .eval_step_with_breakpoints(&mut receiver, breakpoints, StepAction::Continue)
+                .expect("execution should succeed")
+        }
+
+        /// Describes where execution is paused and the state of the program there.
+        fn position(debugger: &mut Debugger) -> String {
+            let line = debugger
+                .get_stack_frames()
+                .last()
+                .map(|frame| frame.location.range.start.line.to_string())
+                .unwrap_or_default();
+            let locals = debugger
+                .get_locals()
+                .iter()
+                .map(|local| format!("{} = {}", local.name, local.value))
+                .collect::<Vec<_>>()
+                .join(", ");
+            let (state, _) = debugger.capture_quantum_state();
+            let state = state
+                .iter()
+                .map(|(id, _)| format!("|{id}>"))
+                .collect::<Vec<_>>()
+                .join(" + ");
+            let gates = debugger.circuit().operations.len();
+            format!("line {line}: {locals}; state {state}; {gates} gates")
+        }
+
+        #[test]
+        fn step_back_restores_variables_and_quantum_state() {
+            for interval in [1, 3, 100] {
+                let mut debugger = flip_debugger(interval);
+                let bp = breakpoint_at_line(&debugger, 8, "flips == 2");
+                continue_to(&mut debugger, std::slice::from_ref(&bp));
+                assert_eq!(
+                    position(&mut debugger),
+                    "line 8: q = Qubit0, flips = 2, i = 2; state |1>; 3 gates"
+                );
+                debugger.step_back().expect("step back should succeed");
+                assert_eq!(
+                    position(&mut debugger),
+                    "line 7: q = Qubit0, flips = 2, i = 2; state |0>; 2 gates"
+                );
+                debugger.step_back().expect("step back should succeed");
+                assert_eq!(
+                    position(&mut debugger),
+                    "line 6: q = Qubit0, flips = 2; state |0>; 2 gates"
+                );
+            }
+        }
+
+        #[test]
+        fn execution_after_step_back_matches_original() {
+            // The first interval saves enough checkpoints for older ones to be thinned out.
+            for interval in [1, 3] {
+                let mut debugger = flip_debugger(interval);
+                let bp = breakpoint_at_line(&debugger, 8, "flips == 30");
+                continue_to(&mut debugger, std::slice::from_ref(&bp));
+                let before = position(&mut debugger);
+                debugger.step_back().expect("step back should succeed");
+                debugger.step_back().expect("step back should succeed");
+                continue_to(&mut debugger, std::slice::from_ref(&bp));
+                assert_eq!(position(&mut debugger), before);
+                match continue_to(&mut debugger, &[]) {
+                    StepResult::Return(value) => assert_eq!(value.to_string(), "Zero"),
+                    result => panic!("Expected Return, got {result:?}"),
+                }
+            }
+        }
+
+        #[test]
+        fn reverse_continue_stops_at_previous_breakpoint() {
+            for interval in [1, 4, 100] {
+                let mut debugger = flip_debugger(interval);
+                let bp = breakpoint_at_line(&debugger, 8, "");
+                for _ in 0..3 {
+                    continue_to(&mut debugger, std::slice::from_ref(&bp));
+                }
+                let mut positions = Vec::new();
+                for _ in 0..3 {
+                    let result = debugger
+                        .reverse_continue(std::slice::from_ref(&bp))
+                        .expect("reverse continue should succeed");
+                    positions.push(format!("{result:?} at {}", position(&mut debugger)));
+                }
+                assert_eq!(
+                    positions,
+                    [
+                        format!(
+                            "BreakpointHit({:?}) at line 8: q = Qubit0, flips = 1, i = 1; state |0>; 2 gates",
+                            bp.id
+                        ),
+                        format!(
+                            "BreakpointHit({:?}) at line 8: q = Qubit0, flips = 0, i = 0; state |1>; 1 gates",
+                            bp.id
+                        ),
+                        "Next at line 4: ; state |0>; 0 gates".to_string(),
+                    ]
+                );
+            }
+        }
+
+        #[test]
+        fn reverse_continue_checks_conditions() {
+            let mut debugger = flip_debugger(5);
+            let conditional = breakpoint_at_line(&debugger, 8, "flips % 10 == 5");
+            let bp = breakpoint_at_line(&debugger, 8, "flips == 37");
+            continue_to(&mut debugger, &[bp]);
+            debugger
+                .reverse_continue(&[conditional])
+                .expect("reverse continue should succeed");
+            assert_eq!(
+                position(&mut debugger),
+                "line 8: q = Qubit0, flips = 35, i = 35; state |0>; 36 gates"
+            );
+        }
+
+        #[test]
+        fn step_back_restores_hit_counts() {
+            let mut debugger = flip_debugger(1);
+            let bp = Breakpoint {
+                hit_condition: Some("== 3".into()),
+                ..breakpoint_at_line(&debugger, 8, "")
+            };
+            continue_to(&mut debugger, std::slice::from_ref(&bp));
+            let before = position(&mut debugger);
+            debugger.step_back().expect("step back should succeed");
+            debugger.step_back().expect("step back should succeed");
+            continue_to(&mut debugger, std::slice::from_ref(&bp));
+            assert_eq!(position(&mut debugger), before);
+        }
+
+        #[test]
+        fn step_back_without_checkpoints_is_error() {
+            let mut debugger = flip_debugger(0);
+            let bp = breakpoint_at_line(&debugger, 8, "");
+            continue_to(&mut debugger, &[bp]);
+            let errors = debugger.step_back().expect_err("step back should fail");
+            assert_eq!(
+                errors[0].to_string(),
+                "no earlier program state is available"
+            );
+            assert_eq!(
+                position(&mut debugger),
+                "line 8: q = Qubit0, flips = 0, i = 0; state |1>; 1 gates"
+            );
+        }
+    }
 }
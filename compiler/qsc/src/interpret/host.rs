@@ -0,0 +1,352 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+//! Host functions that implement intrinsic Q# functions. A host function is registered for a
+//! function declared with `body intrinsic;`, and the evaluator routes calls to it through the
+//! backend's custom intrinsics.
+
+use num_bigint::BigInt;
+use num_complex::Complex;
+use qsc_eval::{backend::Backend, val::Value};
+use qsc_fir::fir::Pauli;
+use qsc_hir::ty::{Prim, Ty};
+use rustc_hash::FxHashMap;
+use std::{any::Any, rc::Rc};
+
+/// The implementation of a host function, which takes the argument of the Q# call and returns
+/// its result or an error message.
+pub type HostFn = dyn Fn(Value) -> Result<Value, String>;
+
+/// A Rust type that can be passed to and returned from a host function, along with the Q# type
+/// it corresponds to.
+pub trait HostValue: Sized {
+    /// The Q# type of the values.
+    fn ty() -> Ty;
+
+    /// Converts a value of the Q# type.
+    fn from_value(value: Value) -> Self;
+
+    fn into_value(self) -> Value;
+}
+
+impl HostValue for () {
+    fn ty() -> Ty {
+        Ty::UNIT
+    }
+
+    fn from_value(_: Value) -> Self {}
+
+    fn into_value(self) -> Value {
+        Value::unit()
+    }
+}
+
+macro_rules! impl_host_value {
+    ($ty:ty, $prim:ident, $unwrap:ident, $variant:ident) => {
+        impl HostValue for $ty {
+            fn ty() -> Ty {
+                Ty::Prim(Prim::$prim)
+            }
+
+            fn from_value(value: Value) -> Self {
+                value.$unwrap().into()
+            }
+
+            fn into_value(self) -> Value {
+                Value::$variant(self.into())
+            }
+        }
+    };
+}
+
+impl_host_value!(bool, Bool, unwrap_bool, Bool);
+impl_host_value!(i64, Int, unwrap_int, Int);
+impl_host_value!(BigInt, BigInt, unwrap_big_int, BigInt);
+impl_host_value!(f64, Double, unwrap_double, Double);
+impl_host_value!(Pauli, Pauli, unwrap_pauli, Pauli);
+
+impl HostValue for String {
+    fn ty() -> Ty {
+        Ty::Prim(Prim::String)
+    }
+
+    fn from_value(value: Value) -> Self {
+        value.unwrap_string().to_string()
+    }
+
+    fn into_value(self) -> Value {
+        Value::String(self.into())
+    }
+}
+
+impl HostValue for qsc_eval::val::Result {
+    fn ty() -> Ty {
+        Ty::Prim(Prim::Result)
+    }
+
+    fn from_value(value: Value) -> Self {
+        Self::Val(value.unwrap_result())
+    }
+
+    fn into_value(self) -> Value {
+        Value::Result(self)
+    }
+}
+
+impl<T: HostValue> HostValue for Vec<T> {
+    fn ty() -> Ty {
+        Ty::Array(Box::new(T::ty()))
+    }
+
+    fn from_value(value: Value) -> Self {
+        value
+            .unwrap_array()
+            .iter()
+            .map(|item| T::from_value(item.clone()))
+            .collect()
+    }
+
+    fn into_value(self) -> Value {
+        Value::Array(Rc::new(self.into_iter().map(T::into_value).collect()))
+    }
+}
+
+macro_rules! impl_host_value_tuple {
+    ($($item:ident),+) => {
+        impl<$($item: HostValue),+> HostValue for ($($item,)+) {
+            fn ty() -> Ty {
+                Ty::Tuple(vec![$($item::ty()),+])
+            }
+
+            #[allow(non_snake_case)]
+            fn from_value(value: Value) -> Self {
+                let items = value.unwrap_tuple();
+                let mut items = items.iter().cloned();
+                $(let $item = $item::from_value(items.next().expect("tuple should have an item"));)+
+                ($($item,)+)
+            }
+
+            #[allow(non_snake_case)]
+            fn into_value(self) -> Value {
+                let ($($item,)+) = self;
+                Value::Tuple(vec![$($item.into_value()),+].into())
+            }
+        }
+    };
+}
+
+impl_host_value_tuple!(A, B);
+impl_host_value_tuple!(A, B, C);
+impl_host_value_tuple!(A, B, C, D);
+
+/// Returns whether a value has the given Q# type.
+fn has_type(value: &Value, ty: &Ty) -> bool {
+    match (value, ty) {
+        (Value::Array(items), Ty::Array(item)) => items.iter().all(|value| has_type(value, item)),
+        (Value::Tuple(items), Ty::Tuple(tys)) => {
+            items.len() == tys.len() && items.iter().zip(tys).all(|(value, ty)| has_type(value, ty))
+        }
+        (Value::BigInt(_), Ty::Prim(Prim::BigInt))
+        | (Value::Bool(_), Ty::Prim(Prim::Bool))
+        | (Value::Double(_), Ty::Prim(Prim::Double))
+        | (Value::Int(_), Ty::Prim(Prim::Int))
+        | (Value::Pauli(_), Ty::Prim(Prim::Pauli))
+        | (Value::Result(_), Ty::Prim(Prim::Result))
+        | (Value::String(_), Ty::Prim(Prim::String)) => true,
+        _ => false,
+    }
+}
+
+/// Returns whether values of the Q# type can be passed to and returned from host functions.
+pub(super) fn is_host_type(ty: &Ty) -> bool {
+    match ty {
+        Ty::Array(item) => is_host_type(item),
+        Ty::Tuple(items) => items.iter().all(is_host_type),
+        Ty::Prim(prim) => matches!(
+            prim,
+            Prim::BigInt
+                | Prim::Bool
+                | Prim::Double
+                | Prim::Int
+                | Prim::Pauli
+                | Prim::Result
+                | Prim::String
+        ),
+        _ => false,
+    }
+}
+
+struct HostIntrinsic {
+    output: Ty,
+    handler: Rc<HostFn>,
+}
+
+/// The host functions registered with an interpreter, by the name of the intrinsic they
+/// implement.
+#[derive(Default)]
+pub(super) struct HostIntrinsics(FxHashMap<Rc<str>, HostIntrinsic>);
+
+impl HostIntrinsics {
+    pub(super) fn insert(&mut self, name: Rc<str>, output: Ty, handler: Rc<HostFn>) {
+        self.0.insert(name, HostIntrinsic { output, handler });
+    }
+
+    fn call(&self, name: &str, arg: Value) -> Option<Result<Value, String>> {
+        let intrinsic = self.0.get(name)?;
+        Some((intrinsic.handler)(arg).and_then(|value| {
+            if has_type(&value, &intrinsic.output) {
+                Ok(value)
+            } else {
+                Err(format!(
+                    "host function returned a value of type {}, expected `{}`",
+                    value.type_name(),
+                    intrinsic.output.display()
+                ))
+            }
+        }))
+    }
+}
+
+/// A backend that calls the registered host functions for custom intrinsics, and passes
+/// everything else on to the backend it wraps.
+pub(super) struct HostBackend<'a, B> {
+    backend: &'a mut B,
+    intrinsics: &'a HostIntrinsics,
+}
+
+impl<'a, B: Backend> HostBackend<'a, B> {
+    pub(super) fn new(backend: &'a mut B, intrinsics: &'a HostIntrinsics) -> Self {
+        Self {
+            backend,
+            intrinsics,
+        }
+    }
+}
+
+impl<B: Backend> Backend for HostBackend<'_, B> {
+    type ResultType = B::ResultType;
+
+    fn ccx(&mut self, ctl0: usize, ctl1: usize, q: usize) {
+        self.backend.ccx(ctl0, ctl1, q);
+    }
+
+    fn cx(&mut self, ctl: usize, q: usize) {
+        self.backend.cx(ctl, q);
+    }
+
+    fn cy(&mut self, ctl: usize, q: usize) {
+        self.backend.cy(ctl, q);
+    }
+
+    fn cz(&mut self, ctl: usize, q: usize) {
+        self.backend.cz(ctl, q);
+    }
+
+    fn h(&mut self, q: usize) {
+        self.backend.h(q);
+    }
+
+    fn m(&mut self, q: usize) -> Self::ResultType {
+        self.backend.m(q)
+    }
+
+    fn mresetz(&mut self, q: usize) -> Self::ResultType {
+        self.backend.mresetz(q)
+    }
+
+    fn reset(&mut self, q: usize) {
+        self.backend.reset(q);
+    }
+
+    fn rx(&mut self, theta: f64, q: usize) {
+        self.backend.rx(theta, q);
+    }
+
+    fn rxx(&mut self, theta: f64, q0: usize, q1: usize) {
+        self.backend.rxx(theta, q0, q1);
+    }
+
+    fn ry(&mut self, theta: f64, q: usize) {
+        self.backend.ry(theta, q);
+    }
+
+    fn ryy(&mut self, theta: f64, q0: usize, q1: usize) {
+        self.backend.ryy(theta, q0, q1);
+    }
+
+    fn rz(&mut self, theta: f64, q: usize) {
+        self.backend.rz(theta, q);
+    }
+
+    fn rzz(&mut self, theta: f64, q0: usize, q1: usize) {
+        self.backend.rzz(theta, q0, q1);
+    }
+
+    fn sadj(&mut self, q: usize) {
+        self.backend.sadj(q);
+    }
+
+    fn s(&mut self, q: usize) {
+        self.backend.s(q);
+    }
+
+    fn swap(&mut self, q0: usize, q1: usize) {
+        self.backend.swap(q0, q1);
+    }
+
+    fn tadj(&mut self, q: usize) {
+        self.backend.tadj(q);
+    }
+
+    fn t(&mut self, q: usize) {
+        self.backend.t(q);
+    }
+
+    fn x(&mut self, q: usize) {
+        self.backend.x(q);
+    }
+
+    fn y(&mut self, q: usize) {
+        self.backend.y(q);
+    }
+
+    fn z(&mut self, q: usize) {
+        self.backend.z(q);
+    }
+
+    fn qubit_allocate(&mut self) -> usize {
+        self.backend.qubit_allocate()
+    }
+
+    fn qubit_release(&mut self, q: usize) {
+        self.backend.qubit_release(q);
+    }
+
+    fn capture_quantum_state(&mut self) -> (Vec<(num_bigint::BigUint, Complex<f64>)>, usize) {
+        self.backend.capture_quantum_state()
+    }
+
+    fn qubit_is_zero(&mut self, q: usize) -> bool {
+        self.backend.qubit_is_zero(q)
+    }
+
+    fn custom_intrinsic(&mut self, name: &str, arg: Value) -> Option<Result<Value, String>> {
+        if self.intrinsics.0.contains_key(name) {
+            self.intrinsics.call(name, arg)
+        } else {
+            self.backend.custom_intrinsic(name, arg)
+        }
+    }
+
+    fn set_seed(&mut self, seed: Option<u64>) {
+        self.backend.set_seed(seed);
+    }
+
+    fn checkpoint(&self) -> Option<Box<dyn Any>> {
+        self.backend.checkpoint()
+    }
+
+    fn restore(&mut self, checkpoint: &dyn Any) {
+        self.backend.restore(checkpoint);
+    }
+}
@@ -0,0 +1,218 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use super::{Error, Interpreter, Value};
+use expect_test::{expect, Expect};
+use qsc_data_structures::{language_features::LanguageFeatures, target::TargetCapabilityFlags};
+use qsc_eval::output::GenericReceiver;
+use qsc_frontend::compile::SourceMap;
+use qsc_hir::ty::{Prim, Ty};
+use qsc_passes::PackageType;
+use std::{fmt::Write, rc::Rc};
+
+const SOURCE: &str = r"
+    namespace Test {
+        function Add(a : Int, b : Int) : Int {
+            body intrinsic;
+        }
+
+        function Greet(name : String) : String {
+            body intrinsic;
+        }
+
+        function Sum(xs : Double[]) : (Double, Int) {
+            body intrinsic;
+        }
+
+        function Apply(f : Int -> Int) : Int {
+            body intrinsic;
+        }
+
+        operation Prepare(n : Int) : Unit {
+            body intrinsic;
+        }
+
+        function Implemented() : Unit {}
+    }";
+
+fn interpreter() -> Interpreter {
+    let sources = SourceMap::new([("test.qs".into(), SOURCE.into())], None);
+    let (std_id, store) = crate::compile::package_store_with_stdlib(TargetCapabilityFlags::all());
+    Interpreter::new(
+        sources,
+        PackageType::Lib,
+        TargetCapabilityFlags::all(),
+        LanguageFeatures::default(),
+        store,
+        &[(std_id, None)],
+    )
+    .expect("interpreter creation should succeed")
+}
+
+fn eval(interpreter: &mut Interpreter, line: &str) -> String {
+    let mut cursor = Vec::new();
+    let mut receiver = GenericReceiver::new(&mut cursor);
+    match interpreter.eval_fragments(&mut receiver, line) {
+        Ok(value) => value.to_string(),
+        Err(errors) => errors
+            .iter()
+            .map(|error| match std::error::Error::source(error) {
+                Some(source) => format!("{error}: {source}"),
+                None => error.to_string(),
+            })
+            .collect(),
+    }
+}
+
+fn check_errors(result: Result<(), Vec<Error>>, expect: &Expect) {
+    let errors = result.expect_err("registration should fail");
+    expect.assert_eq(&errors.iter().fold(String::new(), |mut out, error| {
+        let _ = writeln!(out, "{error}");
+        out
+    }));
+}
+
+#[test]
+fn registered_functions_are_called_with_converted_values() {
+    let mut interpreter = interpreter();
+    interpreter
+        .register_function("Test.Add", |(a, b): (i64, i64)| Ok(a + b))
+        .expect("registration should succeed");
+    interpreter
+        .register_function("Test.Greet", |name: String| Ok(format!("Hello, {name}!")))
+        .expect("registration should succeed");
+    interpreter
+        .register_function("Test.Sum", |xs: Vec<f64>| {
+            Ok((
+                xs.iter().sum::<f64>(),
+                i64::try_from(xs.len()).unwrap_or(i64::MAX),
+            ))
+        })
+        .expect("registration should succeed");
+
+    assert_eq!(eval(&mut interpreter, "Test.Add(2, 3)"), "5");
+    assert_eq!(eval(&mut interpreter, r#"Test.Greet("Q#")"#), "Hello, Q#!");
+    assert_eq!(eval(&mut interpreter, "Test.Sum([0.5, 1.5])"), "(2.0, 2)");
+}
+
+#[test]
+fn registered_functions_are_called_in_new_runs() {
+    let mut interpreter = interpreter();
+    interpreter
+        .register_function("Test.Add", |(a, b): (i64, i64)| Ok(a * 10 + b))
+        .expect("registration should succeed");
+    let mut cursor = Vec::new();
+    let result = interpreter
+        .run(
+            &mut GenericReceiver::new(&mut cursor),
+            Some("Test.Add(4, 2)"),
+        )
+        .expect("run should compile");
+    assert_eq!(result.expect("run should succeed"), Value::Int(42));
+}
+
+#[test]
+fn functions_declared_in_fragments_can_be_registered() {
+    let mut interpreter = interpreter();
+    eval(
+        &mut interpreter,
+        "function Twice(x : Double) : Double { body intrinsic; }",
+    );
+    interpreter
+        .register_function("Twice", |x: f64| Ok(2.0 * x))
+        .expect("registration should succeed");
+    assert_eq!(eval(&mut interpreter, "Twice(1.5)"), "3.0");
+}
+
+#[test]
+fn unregistered_intrinsic_is_unknown() {
+    expect!["runtime error: unknown intrinsic `Add`"]
+        .assert_eq(&eval(&mut interpreter(), "Test.Add(2, 3)"));
+}
+
+#[test]
+fn types_are_checked_at_registration() {
+    let mut interpreter = interpreter();
+    check_errors(
+        interpreter.register_function("Test.Add", |(a, _): (i64, f64)| Ok(a)),
+        &expect![[r"
+            host function for `Test.Add` has type `(Int, Double) -> Int`, but `Test.Add` is declared with type `(Int, Int) -> Int`
+        "]],
+    );
+    check_errors(
+        interpreter.register_function("Test.Greet", |name: String| Ok(name.len() > 1)),
+        &expect![[r"
+            host function for `Test.Greet` has type `String -> Bool`, but `Test.Greet` is declared with type `String -> String`
+        "]],
+    );
+    check_errors(
+        interpreter.register_function("Test.Apply", |x: i64| Ok(x)),
+        &expect![[r"
+            `Test.Apply` has type `(Int -> Int)`, which host functions do not support
+        "]],
+    );
+}
+
+#[test]
+fn only_intrinsic_functions_declared_in_the_session_can_be_registered() {
+    let mut interpreter = interpreter();
+    for name in [
+        "Test.Prepare",
+        "Test.Implemented",
+        "Microsoft.Quantum.Math.AbsI",
+        "Microsoft.Quantum.Convert.IntAsDouble",
+    ] {
+        let errors = interpreter
+            .register_function(name, |x: i64| Ok(x))
+            .expect_err("registration should fail");
+        assert!(
+            matches!(errors.as_slice(), [Error::NotAHostIntrinsic(n)] if n == name),
+            "{errors:?}"
+        );
+    }
+    check_errors(
+        interpreter.register_function("42", |x: i64| Ok(x)),
+        &expect![[r"
+            expression does not evaluate to a callable or type
+        "]],
+    );
+}
+
+#[test]
+fn host_function_errors_are_runtime_errors() {
+    let mut interpreter = interpreter();
+    interpreter
+        .register_function("Test.Add", |(a, b): (i64, i64)| {
+            a.checked_add(b).ok_or_else(|| "overflow".to_string())
+        })
+        .expect("registration should succeed");
+    expect!["runtime error: intrinsic callable `Add` failed: overflow"]
+        .assert_eq(&eval(&mut interpreter, "Test.Add(0x7FFFFFFFFFFFFFFF, 1)"));
+}
+
+#[test]
+fn return_values_of_untyped_handlers_are_checked() {
+    let mut interpreter = interpreter();
+    interpreter
+        .register_intrinsic(
+            "Test.Greet",
+            &Ty::Prim(Prim::String),
+            Ty::Prim(Prim::String),
+            Rc::new(|_| Ok(Value::Int(7))),
+        )
+        .expect("registration should succeed");
+    expect!["runtime error: intrinsic callable `Greet` failed: host function returned a value of type Int, expected `String`"]
+        .assert_eq(&eval(&mut interpreter, r#"Test.Greet("Q#")"#));
+}
+
+#[test]
+fn intrinsic_signature_is_the_declared_types() {
+    let mut interpreter = interpreter();
+    let (input, output) = interpreter
+        .intrinsic_signature("Test.Sum")
+        .expect("signature should be found");
+    assert_eq!(
+        (input.display(), output.display()),
+        ("Double[]".to_string(), "(Double, Int)".to_string())
+    );
+}
@@ -7,7 +7,10 @@ mod given_interpreter {
     use crate::interpret::{Error, InterpretResult, Interpreter};
     use expect_test::Expect;
     use miette::Diagnostic;
-    use qsc_data_structures::{language_features::LanguageFeatures, target::TargetCapabilityFlags};
+    use qsc_data_structures::{
+        language_features::LanguageFeatures,
+        target::{TargetCapabilityFlags, TargetDescription},
+    };
     use qsc_eval::{output::CursorReceiver, val::Value};
     use qsc_frontend::compile::SourceMap;
     use qsc_passes::PackageType;
@@ -113,6 +116,116 @@ mod given_interpreter {
             is_only_value(&result, &output, &Value::Int(7));
         }
 
+        #[test]
+        fn type_of_expression_is_inferred_without_evaluating_it() {
+            let mut interpreter = get_interpreter();
+            line(&mut interpreter, "let x = [1, 2];")
+                .0
+                .expect("line should succeed");
+            let types = [
+                "x",
+                "(x, 1.0)",
+                "Microsoft.Quantum.Math.AbsI",
+                "q => H(q)",
+                "Message(\"_\")",
+            ]
+            .map(|expr| {
+                interpreter
+                    .type_of(expr)
+                    .expect("expression should compile")
+                    .display()
+            })
+            .join("\n");
+            expect![[r#"
+                Int[]
+                (Int[], Double)
+                (Int -> Int)
+                (Qubit => Unit)
+                Unit"#]]
+            .assert_eq(&types);
+            let (result, output) = line(&mut interpreter, "x");
+            is_only_value(
+                &result,
+                &output,
+                &Value::Array(vec![Value::Int(1), Value::Int(2)].into()),
+            );
+        }
+
+        #[test]
+        fn type_of_invalid_expression_is_error() {
+            let mut interpreter = get_interpreter();
+            let errors = interpreter
+                .type_of("1 + true")
+                .expect_err("expression should not compile");
+            is_error(
+                &errors,
+                &expect![[r#"
+                    type error: expected Int, found Bool
+                       [<entry>] [true]
+                "#]],
+            );
+        }
+
+        #[test]
+        fn doc_of_callable_and_type() {
+            let mut interpreter = get_interpreter();
+            line(
+                &mut interpreter,
+                indoc! {"
+                    /// Adds one.
+                    function AddOne(x : Int) : Int { x + 1 }
+                    newtype Pair = (Int, Int);
+                "},
+            )
+            .0
+            .expect("line should succeed");
+            let docs = ["AddOne", "Pair", "Microsoft.Quantum.Math.PI"]
+                .map(|name| {
+                    interpreter
+                        .doc(name)
+                        .expect("name should be an item")
+                        .lines()
+                        .next()
+                        .unwrap_or_default()
+                        .to_string()
+                })
+                .join("\n");
+            expect![[r#"
+                Adds one.
+
+                # Summary"#]]
+            .assert_eq(&docs);
+        }
+
+        #[test]
+        fn doc_of_value_is_error() {
+            let mut interpreter = get_interpreter();
+            let errors = interpreter.doc("1 + 2").expect_err("value is not an item");
+            is_error(
+                &errors,
+                &expect![[r#"
+                    expression does not evaluate to a callable or type
+                "#]],
+            );
+        }
+
+        #[test]
+        fn eval_source_labels_errors_with_source_name() {
+            let mut interpreter = get_interpreter();
+            let mut cursor = Cursor::new(Vec::<u8>::new());
+            let mut receiver = CursorReceiver::new(&mut cursor);
+            let result =
+                interpreter.eval_source(&mut receiver, "Ops.qs", "function F() : Int { true }");
+            is_only_error(
+                &result,
+                &receiver.dump(),
+                &expect![[r#"
+                    type error: expected Int, found Bool
+                       [Ops.qs] [true]
+                "#]],
+            );
+        }
+
         #[test]
         fn let_bindings_can_be_shadowed() {
             let mut interpreter = get_interpreter();
@@ -532,11 +645,16 @@ mod given_interpreter {
                 &result,
                 &output,
                 &expect![[r#"
-                cannot use a dynamic integer value
-                   [line_0] [set x = 2]
-                cannot use a dynamic integer value
-                   [line_0] [x]
-            "#]],
+                    cannot use a dynamic integer value
+                       [line_0] [set x = 2]
+                      this condition depends on a measurement result [line_0] [MResetZ(q) == One]
+                      `MResetZ` returns a measurement result here [line_0] [MResetZ(q)]
+                    cannot use a dynamic integer value
+                       [line_0] [x]
+                      `x` is assigned a dynamic value here [line_0] [set x = 2]
+                      this condition depends on a measurement result [line_0] [MResetZ(q) == One]
+                      `MResetZ` returns a measurement result here [line_0] [MResetZ(q)]
+                "#]],
             );
             // do something innocuous
             let (result, output) = line(&mut interpreter, indoc! {r#"Foo()"#});
@@ -565,11 +683,16 @@ mod given_interpreter {
                 &result,
                 &output,
                 &expect![[r#"
-                cannot use a dynamic integer value
-                   [line_0] [set x = 2]
-                cannot use a dynamic integer value
-                   [line_0] [x]
-            "#]],
+                    cannot use a dynamic integer value
+                       [line_0] [set x = 2]
+                      this condition depends on a measurement result [line_0] [MResetZ(q) == One]
+                      `MResetZ` returns a measurement result here [line_0] [MResetZ(q)]
+                    cannot use a dynamic integer value
+                       [line_0] [x]
+                      `x` is assigned a dynamic value here [line_0] [set x = 2]
+                      this condition depends on a measurement result [line_0] [MResetZ(q) == One]
+                      `MResetZ` returns a measurement result here [line_0] [MResetZ(q)]
+                "#]],
             );
             let res = interpreter.qirgen("{Foo();}");
             expect![[r#"
@@ -618,11 +741,16 @@ mod given_interpreter {
                 &result,
                 &output,
                 &expect![[r#"
-                cannot use a dynamic integer value
-                   [line_0] [set x = 2]
-                cannot use a dynamic integer value
-                   [line_0] [x]
-            "#]],
+                    cannot use a dynamic integer value
+                       [line_0] [set x = 2]
+                      this condition depends on a measurement result [line_0] [MResetZ(q) == One]
+                      `MResetZ` returns a measurement result here [line_0] [MResetZ(q)]
+                    cannot use a dynamic integer value
+                       [line_0] [x]
+                      `x` is assigned a dynamic value here [line_0] [set x = 2]
+                      this condition depends on a measurement result [line_0] [MResetZ(q) == One]
+                      `MResetZ` returns a measurement result here [line_0] [MResetZ(q)]
+                "#]],
             );
             // do something innocuous
             let (result, output) = line(
@@ -743,6 +871,43 @@ mod given_interpreter {
             "#]].assert_eq(&res);
         }
 
+        #[test]
+        fn base_qirgen_checks_target_limits() {
+            let mut interpreter = get_interpreter_with_capabilities(TargetCapabilityFlags::empty());
+            interpreter.set_target_limits(&TargetDescription {
+                gates: Some(vec!["h".into(), "m".into()]),
+                max_qubits: Some(1),
+                ..TargetDescription::default()
+            });
+            let (result, output) = line(
+                &mut interpreter,
+                indoc! {"operation Foo() : Result { use q = Qubit(); let r = M(q); Reset(q); return r; } "},
+            );
+            is_only_value(&result, &output, &Value::unit());
+            let errors = interpreter.qirgen("Foo()").expect_err("expected error");
+            is_error(&errors, &expect![[r#"
+                the target does not support the `cz` gate
+                the program uses 2 qubits, but the target only has 1
+            "#]]);
+        }
+
+        #[test]
+        fn adaptive_qirgen_branching_on_measurement_checks_only_gates() {
+            let mut interpreter = get_interpreter_with_capabilities(
+                TargetCapabilityFlags::Adaptive | TargetCapabilityFlags::QubitReset,
+            );
+            interpreter.set_target_limits(&TargetDescription {
+                gates: Some(vec!["x".into(), "m".into(), "reset".into()]),
+                ..TargetDescription::default()
+            });
+            let (result, output) = line(
+                &mut interpreter,
+                indoc! {"operation Foo() : Result { use q = Qubit(); let r = M(q); if r == One { X(q); } Reset(q); return r; } "},
+            );
+            is_only_value(&result, &output, &Value::unit());
+            interpreter.qirgen("Foo()").expect("expected success");
+        }
+
         #[test]
         fn adaptive_qirgen() {
             let mut interpreter = get_interpreter_with_capabilities(
@@ -906,6 +1071,8 @@ mod given_interpreter {
                 &expect![[r#"
                     cannot use a dynamic integer value
                        [<entry>] [set x = 2]
+                      this condition depends on a measurement result [<entry>] [M(q) == One]
+                      `M` returns a measurement result here [<entry>] [M(q)]
                 "#]],
             );
         }
@@ -1421,12 +1588,27 @@ mod given_interpreter {
                     &expect![[r#"
                         cannot use a dynamic double value
                            [<entry>] [A.Test()]
+                          `Test` returns a dynamic value here [<entry>] [A.Test()]
+                          `@generated_ident_37` is bound to a dynamic value here [test] [x]
+                          `x` is assigned a dynamic value here [test] [set x = 2.0]
+                          this condition depends on a measurement result [test] [MResetZ(q) == One]
+                          `MResetZ` returns a measurement result here [test] [MResetZ(q)]
                         cannot use a double value as an output
                            [<entry>] [A.Test()]
+                          `Test` returns a dynamic value here [<entry>] [A.Test()]
+                          `@generated_ident_37` is bound to a dynamic value here [test] [x]
+                          `x` is assigned a dynamic value here [test] [set x = 2.0]
+                          this condition depends on a measurement result [test] [MResetZ(q) == One]
+                          `MResetZ` returns a measurement result here [test] [MResetZ(q)]
                         cannot use a dynamic double value
                            [test] [set x = 2.0]
+                          this condition depends on a measurement result [test] [MResetZ(q) == One]
+                          `MResetZ` returns a measurement result here [test] [MResetZ(q)]
                         cannot use a dynamic double value
                            [test] [x]
+                          `x` is assigned a dynamic value here [test] [set x = 2.0]
+                          this condition depends on a measurement result [test] [MResetZ(q) == One]
+                          `MResetZ` returns a measurement result here [test] [MResetZ(q)]
                     "#]],
                 ),
             }
@@ -1895,5 +2077,213 @@ mod given_interpreter {
                 entry: None,
             }
         }
+
+        fn doctest_interpreter(source: &str) -> Interpreter {
+            let sources = SourceMap::new([("test".into(), source.into())], None);
+            let (std_id, store) =
+                crate::compile::package_store_with_stdlib(TargetCapabilityFlags::all());
+            Interpreter::new(
+                sources,
+                PackageType::Lib,
+                TargetCapabilityFlags::all(),
+                LanguageFeatures::default(),
+                store,
+                &[(std_id, None)],
+            )
+            .expect("interpreter should be created")
+        }
+
+        fn run_doctests(source: &str) -> String {
+            let doctests = doctest_interpreter(source).doctests();
+            let mut results = String::new();
+            for doctest in &doctests {
+                // Each example runs in a new interpreter.
+                let mut interpreter = doctest_interpreter(source);
+                let mut cursor = Cursor::new(Vec::<u8>::new());
+                let mut receiver = CursorReceiver::new(&mut cursor);
+                let result = interpreter.run_doctest(&mut receiver, doctest);
+                let line = doctest.position.line + 1;
+                match result {
+                    Ok(()) => writeln!(results, "{} (line {line}): ok", doctest.name),
+                    Err(errors) => writeln!(
+                        results,
+                        "{} (line {line}): {}",
+                        doctest.name,
+                        errors
+                            .iter()
+                            .map(ToString::to_string)
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    ),
+                }
+                .expect("writing should succeed");
+                let output = receiver.dump();
+                if !output.is_empty() {
+                    writeln!(results, "{output}").expect("writing should succeed");
+                }
+            }
+            results
+        }
+
+        #[test]
+        fn doctests_run_with_item_namespace_open() {
+            let source = indoc! { r#"
+            namespace Test {
+                /// # Example
+                /// ```qsharp
+                /// Message($"{Double(2)}");
+                /// ```
+                function Double(x : Int) : Int { 2 * x }
+            }"#};
+
+            expect![[r#"
+                Test.Double (line 3): ok
+                4
+            "#]]
+            .assert_eq(&run_doctests(source));
+        }
+
+        #[test]
+        fn doctests_report_compile_and_runtime_errors() {
+            let source = indoc! { r#"
+            namespace Test {
+                /// ```qsharp
+                /// let x = Half(1.0);
+                /// ```
+                /// ```qsharp
+                /// fail "rotted";
+                /// ```
+                function Half(x : Int) : Int { x / 2 }
+            }"#};
+
+            expect![[r#"
+                Test.Half (line 2): type error
+                Test.Half (line 5): runtime error
+            "#]]
+            .assert_eq(&run_doctests(source));
+        }
+
+        #[test]
+        fn doctests_do_not_share_state() {
+            let source = indoc! { r#"
+            namespace Test {
+                /// ```qsharp
+                /// use q = Qubit();
+                /// X(q);
+                /// let shared = 1;
+                /// ```
+                /// ```qsharp
+                /// use q = Qubit();
+                /// if M(q) == One { fail "qubit was reused"; }
+                /// Message($"{shared}");
+                /// ```
+                operation Foo() : Unit {}
+            }"#};
+
+            expect![[r#"
+                Test.Foo (line 2): ok
+                Test.Foo (line 7): name error
+            "#]]
+            .assert_eq(&run_doctests(source));
+        }
+
+        #[test]
+        fn ignored_doctests_are_extracted_but_marked() {
+            let source = indoc! { r#"
+            namespace Test {
+                /// ```qsharp,ignore
+                /// NotYetImplemented();
+                /// ```
+                function Foo() : Unit {}
+            }"#};
+
+            let doctests = doctest_interpreter(source).doctests();
+            assert_eq!(doctests.len(), 1);
+            assert!(doctests[0].ignore);
+        }
+
+        fn exe_interpreter(message: &str) -> Interpreter {
+            let (std_id, store) =
+                crate::compile::package_store_with_stdlib(TargetCapabilityFlags::all());
+            Interpreter::new(
+                exe_sources(message),
+                PackageType::Exe,
+                TargetCapabilityFlags::all(),
+                LanguageFeatures::default(),
+                store,
+                &[(std_id, None)],
+            )
+            .expect("interpreter should be created")
+        }
+
+        fn exe_sources(message: &str) -> SourceMap {
+            let source = format!(
+                "namespace Test {{ @EntryPoint() operation Main() : Unit {{ Message(\"{message}\"); }} }}"
+            );
+            SourceMap::new([("test".into(), source.into())], None)
+        }
+
+        #[test]
+        fn update_sources_recompiles_the_entry_point() {
+            let mut interpreter = exe_interpreter("before");
+            let (result, output) = entry(&mut interpreter);
+            is_unit_with_output_eval_entry(&result, &output, "before");
+
+            interpreter
+                .update_sources(exe_sources("after"), PackageType::Exe)
+                .expect("sources should compile");
+            let (result, output) = entry(&mut interpreter);
+            is_unit_with_output_eval_entry(&result, &output, "after");
+        }
+
+        #[test]
+        fn update_sources_discards_evaluated_lines() {
+            let mut interpreter = exe_interpreter("before");
+            let (result, output) = line(&mut interpreter, "let x = 1;");
+            is_only_value(&result, &output, &Value::unit());
+
+            let source = "namespace Test { function Two() : Int { 2 } }";
+            interpreter
+                .update_sources(
+                    SourceMap::new([("test".into(), source.into())], None),
+                    PackageType::Lib,
+                )
+                .expect("sources should compile");
+            let (result, output) = line(&mut interpreter, "Test.Two()");
+            is_only_value(&result, &output, &Value::Int(2));
+            let (result, output) = line(&mut interpreter, "x");
+            is_only_error(
+                &result,
+                &output,
+                &expect![[r#"
+                    name error: `x` not found
+                       [line_1] [x]
+                "#]],
+            );
+        }
+
+        #[test]
+        fn update_sources_with_errors_keeps_the_previous_sources() {
+            let mut interpreter = exe_interpreter("before");
+            let source = "namespace Test { @EntryPoint() operation Main() : Unit { Foo(); } }";
+            let errors = interpreter
+                .update_sources(
+                    SourceMap::new([("test".into(), source.into())], None),
+                    PackageType::Exe,
+                )
+                .expect_err("sources should not compile");
+            is_error(
+                &errors,
+                &expect![[r#"
+                    name error: `Foo` not found
+                       [test] [Foo]
+                    type error: insufficient type information to infer type
+                       [test] [Foo()]
+                "#]],
+            );
+
+            let (result, output) = entry(&mut interpreter);
+            is_unit_with_output_eval_entry(&result, &output, "before");
+        }
     }
 }
@@ -0,0 +1,119 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+//! Helpers for evaluating debugger expressions, such as breakpoint conditions, logpoint
+//! messages and watch expressions, which [`super::Debugger::evaluate`] compiles over the
+//! locals of a paused frame.
+
+#[cfg(test)]
+mod tests;
+
+use qsc_ast::{
+    ast::{ExprKind, Path},
+    visit::{self, Visitor},
+};
+use qsc_data_structures::language_features::LanguageFeatures;
+use qsc_eval::val::Value;
+use std::{fmt::Write, rc::Rc};
+
+/// Replaces each `{expr}` in the given message with the value of the expression, as given by
+/// `eval`. Expressions that fail to evaluate are replaced with a description of the error.
+pub fn interpolate(message: &str, mut eval: impl FnMut(&str) -> Result<Value, String>) -> String {
+    let mut output = String::new();
+    let mut rest = message;
+    while let Some(start) = rest.find('{') {
+        let Some(len) = rest[start..].find('}') else {
+            break;
+        };
+        output.push_str(&rest[..start]);
+        match eval(&rest[start + 1..start + len]) {
+            Ok(value) => output.push_str(&value.to_string()),
+            Err(error) => {
+                let _ = write!(output, "<{error}>");
+            }
+        }
+        rest = &rest[start + len + 1..];
+    }
+    output.push_str(rest);
+    output
+}
+
+/// Returns whether a breakpoint with the given hit condition should stop after the given number
+/// of hits. The condition is either a number of hits, optionally preceded by a comparison
+/// operator (`==`, `>`, `>=`, `<`, `<=`), or `%` followed by a number to stop on every multiple.
+/// Returns `None` if the condition is not valid.
+pub fn hit_condition_met(condition: &str, hits: u32) -> Option<bool> {
+    let condition = condition.trim();
+    let (op, count) = ["==", ">=", "<=", ">", "<", "%", "="]
+        .into_iter()
+        .find_map(|op| condition.strip_prefix(op).map(|count| (op, count)))
+        .unwrap_or(("==", condition));
+    let count = count.trim().parse::<u32>().ok()?;
+    Some(match op {
+        ">=" => hits >= count,
+        "<=" => hits <= count,
+        ">" => hits > count,
+        "<" => hits < count,
+        "%" => count != 0 && hits % count == 0,
+        _ => hits == count,
+    })
+}
+
+/// Returns the unqualified names used by the given expression, in order of first use.
+/// Returns no names if the expression can't be parsed.
+pub fn free_names(source: &str, language_features: LanguageFeatures) -> Vec<Rc<str>> {
+    struct NameCollector(Vec<Rc<str>>);
+
+    impl Visitor<'_> for NameCollector {
+        fn visit_path(&mut self, path: &Path) {
+            if path.segments.is_none() && !self.0.contains(&path.name.name) {
+                self.0.push(path.name.name.clone());
+            }
+        }
+    }
+
+    let (expr, errors) = qsc_parse::expr(source, language_features);
+    if !errors.is_empty() {
+        return Vec::new();
+    }
+    let mut collector = NameCollector(Vec::new());
+    visit::walk_expr(&mut collector, &expr);
+    collector.0
+}
+
+/// Returns true if the given expression is an assignment, which evaluates to `Unit`.
+pub fn is_assignment(source: &str, language_features: LanguageFeatures) -> bool {
+    let (expr, errors) = qsc_parse::expr(source, language_features);
+    errors.is_empty()
+        && matches!(
+            *expr.kind,
+            ExprKind::Assign(..) | ExprKind::AssignOp(..) | ExprKind::AssignUpdate(..)
+        )
+}
+
+/// Returns the Q# type of the given value, if it can be determined from the value alone.
+/// Callables and empty arrays have no such type.
+pub fn type_of(value: &Value) -> Option<String> {
+    Some(match value {
+        Value::Array(items) => format!("{}[]", type_of(items.first()?)?),
+        Value::BigInt(_) => "BigInt".to_string(),
+        Value::Bool(_) => "Bool".to_string(),
+        Value::Double(_) => "Double".to_string(),
+        Value::Int(_) => "Int".to_string(),
+        Value::Pauli(_) => "Pauli".to_string(),
+        Value::Qubit(_) => "Qubit".to_string(),
+        Value::Range(_) => "Range".to_string(),
+        Value::Result(_) => "Result".to_string(),
+        Value::String(_) => "String".to_string(),
+        Value::Tuple(items) if items.is_empty() => "Unit".to_string(),
+        Value::Tuple(items) => {
+            let items = items.iter().map(type_of).collect::<Option<Vec<_>>>()?;
+            if items.len() == 1 {
+                format!("({},)", items[0])
+            } else {
+                format!("({})", items.join(", "))
+            }
+        }
+        Value::Closure(_) | Value::Global(..) | Value::Var(_) => return None,
+    })
+}
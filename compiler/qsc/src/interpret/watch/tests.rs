@@ -0,0 +1,56 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+#![allow(clippy::needless_raw_string_hashes)]
+
+use super::{hit_condition_met, interpolate};
+use expect_test::expect;
+use qsc_eval::val::Value;
+
+#[test]
+fn interpolate_message() {
+    let message = interpolate(
+        "n = {n}, next = {n + 1}, bad = {m}, {unclosed",
+        |expr| match expr {
+            "n" => Ok(Value::Int(4)),
+            "n + 1" => Ok(Value::Int(5)),
+            _ => Err(format!("`{expr}` not found")),
+        },
+    );
+    expect!["n = 4, next = 5, bad = <`m` not found>, {unclosed"].assert_eq(&message);
+}
+
+#[test]
+fn hit_conditions() {
+    let results: Vec<_> = ["3", "== 3", ">= 2", "> 3", "< 4", "<= 2", "% 3", "x"]
+        .into_iter()
+        .map(|condition| hit_condition_met(condition, 3))
+        .collect();
+    expect![[r#"
+        [
+            Some(
+                true,
+            ),
+            Some(
+                true,
+            ),
+            Some(
+                true,
+            ),
+            Some(
+                false,
+            ),
+            Some(
+                true,
+            ),
+            Some(
+                false,
+            ),
+            Some(
+                true,
+            ),
+            None,
+        ]
+    "#]]
+    .assert_debug_eq(&results);
+}
@@ -1,18 +1,23 @@
 // Copyright (c) Microsoft Corporation.
 // Licensed under the MIT License.
 
+pub mod cache;
 pub mod codegen;
 pub mod compile;
 pub mod error;
 pub mod incremental;
 pub mod interpret;
+pub mod linter;
 pub mod location;
 pub mod packages;
 pub mod target;
+pub mod watch;
 
 pub use qsc_formatter::formatter;
 
-pub use qsc_frontend::compile::{CompileUnit, PackageStore, SourceContents, SourceMap, SourceName};
+pub use qsc_frontend::compile::{
+    CompileUnit, PackageStore, Source, SourceContents, SourceMap, SourceName,
+};
 
 pub mod resolve {
     pub use qsc_frontend::resolve::{path_as_field_accessor, Local, LocalKind, Locals, Res};
@@ -38,11 +43,16 @@ pub mod project {
 }
 
 pub use qsc_data_structures::{
-    language_features::LanguageFeatures, namespaces::*, span::Span, target::TargetCapabilityFlags,
+    functors::FunctorApp, language_features::LanguageFeatures, namespaces::*, span::Span,
+    target::TargetCapabilityFlags,
 };
 
 pub use qsc_passes::{lower_hir_to_fir, PackageType, PassContext};
 
+pub mod passes {
+    pub use qsc_passes::Error;
+}
+
 pub mod line_column {
     pub use qsc_data_structures::line_column::{Encoding, Position, Range};
 }
@@ -52,11 +62,14 @@ pub use qsc_eval::{
     state::{fmt_basis_state_label, fmt_complex, format_state_id, get_latex, get_phase},
 };
 
-pub mod linter {
-    pub use qsc_linter::{run_lints, LintConfig, LintKind, LintLevel};
+pub mod state_views {
+    pub use qsc_eval::state::views::{
+        bloch_vector, entanglement_entropy, pauli_expectation, qubit_views, reduced_density_matrix,
+        Error, QubitView,
+    };
 }
 
-pub use qsc_doc_gen::{display, generate_docs};
+pub use qsc_doc_gen::{display, doctests, generate_docs, generate_html};
 
 pub mod circuit {
     pub use qsc_circuit::{operations::*, Circuit, Operation};
@@ -0,0 +1,124 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+#[cfg(test)]
+mod tests;
+
+pub use qsc_linter::{run_lints, Lint, LintConfig, LintKind, LintLevel};
+
+use qsc_data_structures::line_column::{Encoding, Range};
+use qsc_frontend::compile::SourceMap;
+use serde_json::{json, Value};
+
+/// Returns `true` if any of the lints is reported at the error level.
+#[must_use]
+pub fn has_errors(lints: &[Lint]) -> bool {
+    lints
+        .iter()
+        .any(|lint| matches!(lint.level, LintLevel::Error | LintLevel::ForceError))
+}
+
+/// Formats the lints as a JSON array. Lines and columns are 1-based,
+/// and columns are counted in UTF-16 code units.
+#[must_use]
+pub fn lints_to_json(lints: &[Lint], sources: &SourceMap) -> String {
+    let lints: Vec<Value> = reported(lints)
+        .map(|lint| {
+            let (source, range) = resolve_range(lint, sources);
+            json!({
+                "lint": lint.kind,
+                "level": lint.level.to_string(),
+                "message": lint.message,
+                "help": lint.help,
+                "source": source,
+                "range": {
+                    "start": { "line": range.start.line + 1, "column": range.start.column + 1 },
+                    "end": { "line": range.end.line + 1, "column": range.end.column + 1 },
+                },
+            })
+        })
+        .collect();
+
+    serde_json::to_string_pretty(&lints).expect("lints should serialize to JSON")
+}
+
+/// Formats the lints as a SARIF 2.1.0 log, with one rule per reported lint kind.
+#[must_use]
+pub fn lints_to_sarif(lints: &[Lint], sources: &SourceMap) -> String {
+    let mut rules: Vec<Value> = Vec::new();
+    let mut rule_ids: Vec<LintKind> = Vec::new();
+    let mut results: Vec<Value> = Vec::new();
+
+    for lint in reported(lints) {
+        let rule_index = rule_ids
+            .iter()
+            .position(|kind| *kind == lint.kind)
+            .unwrap_or_else(|| {
+                rule_ids.push(lint.kind);
+                rules.push(json!({
+                    "id": lint.kind,
+                    "shortDescription": { "text": lint.message },
+                    "help": { "text": lint.help },
+                }));
+                rule_ids.len() - 1
+            });
+
+        let (source, range) = resolve_range(lint, sources);
+        results.push(json!({
+            "ruleId": lint.kind,
+            "ruleIndex": rule_index,
+            "level": lint.level.to_string(),
+            "message": { "text": lint.message },
+            "locations": [{
+                "physicalLocation": {
+                    "artifactLocation": { "uri": source },
+                    "region": {
+                        "startLine": range.start.line + 1,
+                        "startColumn": range.start.column + 1,
+                        "endLine": range.end.line + 1,
+                        "endColumn": range.end.column + 1,
+                    },
+                },
+            }],
+        }));
+    }
+
+    let log = json!({
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "qsc",
+                    "informationUri": "https://github.com/microsoft/qsharp",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "rules": rules,
+                },
+            },
+            "columnKind": "utf16CodeUnits",
+            "results": results,
+        }],
+    });
+
+    serde_json::to_string_pretty(&log).expect("SARIF log should serialize to JSON")
+}
+
+/// The lints that are not disabled. Their level, `warning` or `error`, is also
+/// a valid SARIF result level.
+fn reported(lints: &[Lint]) -> impl Iterator<Item = &Lint> {
+    lints
+        .iter()
+        .filter(|lint| !matches!(lint.level, LintLevel::Allow))
+}
+
+fn resolve_range(lint: &Lint, sources: &SourceMap) -> (String, Range) {
+    let source = sources
+        .find_by_offset(lint.span.lo)
+        .expect("source should exist for lint span");
+    let range = Range::from_span(
+        Encoding::Utf16,
+        &source.contents,
+        &(lint.span - source.offset),
+    );
+    (source.name.to_string(), range)
+}
@@ -0,0 +1,226 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use super::{has_errors, lints_to_json, lints_to_sarif, run_lints, Lint, LintConfig};
+use crate::compile;
+use expect_test::{expect, Expect};
+use indoc::indoc;
+use qsc_data_structures::{language_features::LanguageFeatures, target::TargetCapabilityFlags};
+use qsc_frontend::compile::{PackageStore, SourceMap};
+use qsc_passes::PackageType;
+
+const SOURCE: &str = indoc! {"
+    namespace Test {
+        function Foo() : Int {
+            let x = 4;;;
+            x / 0
+        }
+    }
+"};
+
+/// Compiles and lints the source, then passes the lints and the user sources to `f`.
+fn with_lints<T>(
+    source: &str,
+    config: &[LintConfig],
+    f: impl FnOnce(&[Lint], &SourceMap) -> T,
+) -> T {
+    let mut store = PackageStore::new(compile::core());
+    let std_id = store.insert(compile::std(&store, TargetCapabilityFlags::all()));
+    let sources = SourceMap::new([("test.qs".into(), source.into())], None);
+    let (unit, errors) = compile::compile(
+        &store,
+        &[(std_id, None)],
+        sources,
+        PackageType::Lib,
+        TargetCapabilityFlags::all(),
+        LanguageFeatures::default(),
+    );
+    assert!(errors.is_empty(), "compilation failed: {errors:?}");
+    let package_id = store.insert(unit);
+    let unit = store.get(package_id).expect("package should be in store");
+    f(&run_lints(&store, unit, Some(config)), &unit.sources)
+}
+
+fn check_json(source: &str, config: &[LintConfig], expect: &Expect) {
+    let json = with_lints(source, config, lints_to_json);
+    expect.assert_eq(&json);
+}
+
+#[test]
+fn json_output() {
+    check_json(
+        SOURCE,
+        &[],
+        &expect![[r#"
+        [
+          {
+            "help": "remove the redundant semicolons",
+            "level": "warning",
+            "lint": "redundantSemicolons",
+            "message": "redundant semicolons",
+            "range": {
+              "end": {
+                "column": 21,
+                "line": 3
+              },
+              "start": {
+                "column": 19,
+                "line": 3
+              }
+            },
+            "source": "test.qs"
+          },
+          {
+            "help": "division by zero will fail at runtime",
+            "level": "error",
+            "lint": "divisionByZero",
+            "message": "attempt to divide by zero",
+            "range": {
+              "end": {
+                "column": 14,
+                "line": 4
+              },
+              "start": {
+                "column": 9,
+                "line": 4
+              }
+            },
+            "source": "test.qs"
+          }
+        ]"#]],
+    );
+}
+
+#[test]
+fn json_output_respects_config() {
+    let config: Vec<LintConfig> = serde_json::from_str(
+        r#"[{ "lint": "divisionByZero", "level": "allow" }, { "lint": "redundantSemicolons", "level": "error" }]"#,
+    )
+    .expect("config should parse");
+    check_json(
+        SOURCE,
+        &config,
+        &expect![[r#"
+        [
+          {
+            "help": "remove the redundant semicolons",
+            "level": "error",
+            "lint": "redundantSemicolons",
+            "message": "redundant semicolons",
+            "range": {
+              "end": {
+                "column": 21,
+                "line": 3
+              },
+              "start": {
+                "column": 19,
+                "line": 3
+              }
+            },
+            "source": "test.qs"
+          }
+        ]"#]],
+    );
+}
+
+#[test]
+fn sarif_output() {
+    let sarif =
+        with_lints(SOURCE, &[], lints_to_sarif).replace(env!("CARGO_PKG_VERSION"), "<version>");
+    expect![[r#"
+        {
+          "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+          "runs": [
+            {
+              "columnKind": "utf16CodeUnits",
+              "results": [
+                {
+                  "level": "warning",
+                  "locations": [
+                    {
+                      "physicalLocation": {
+                        "artifactLocation": {
+                          "uri": "test.qs"
+                        },
+                        "region": {
+                          "endColumn": 21,
+                          "endLine": 3,
+                          "startColumn": 19,
+                          "startLine": 3
+                        }
+                      }
+                    }
+                  ],
+                  "message": {
+                    "text": "redundant semicolons"
+                  },
+                  "ruleId": "redundantSemicolons",
+                  "ruleIndex": 0
+                },
+                {
+                  "level": "error",
+                  "locations": [
+                    {
+                      "physicalLocation": {
+                        "artifactLocation": {
+                          "uri": "test.qs"
+                        },
+                        "region": {
+                          "endColumn": 14,
+                          "endLine": 4,
+                          "startColumn": 9,
+                          "startLine": 4
+                        }
+                      }
+                    }
+                  ],
+                  "message": {
+                    "text": "attempt to divide by zero"
+                  },
+                  "ruleId": "divisionByZero",
+                  "ruleIndex": 1
+                }
+              ],
+              "tool": {
+                "driver": {
+                  "informationUri": "https://github.com/microsoft/qsharp",
+                  "name": "qsc",
+                  "rules": [
+                    {
+                      "help": {
+                        "text": "remove the redundant semicolons"
+                      },
+                      "id": "redundantSemicolons",
+                      "shortDescription": {
+                        "text": "redundant semicolons"
+                      }
+                    },
+                    {
+                      "help": {
+                        "text": "division by zero will fail at runtime"
+                      },
+                      "id": "divisionByZero",
+                      "shortDescription": {
+                        "text": "attempt to divide by zero"
+                      }
+                    }
+                  ],
+                  "version": "<version>"
+                }
+              }
+            }
+          ],
+          "version": "2.1.0"
+        }"#]]
+    .assert_eq(&sarif);
+}
+
+#[test]
+fn errors_are_detected() {
+    assert!(with_lints(SOURCE, &[], |lints, _| has_errors(lints)));
+
+    let config: Vec<LintConfig> =
+        serde_json::from_str(r#"[{ "lint": "divisionByZero", "level": "warn" }]"#)
+            .expect("config should parse");
+    assert!(!with_lints(SOURCE, &config, |lints, _| has_errors(lints)));
+}
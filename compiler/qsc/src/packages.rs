@@ -2,6 +2,7 @@
 // Licensed under the MIT License.
 
 use crate::{
+    cache::Cache,
     compile::{self, ErrorKind},
     hir::PackageId,
     PackageStore, TargetCapabilityFlags,
@@ -34,6 +35,17 @@ impl BuildableProgram {
     ) -> Self {
         prepare_package_store(capabilities, package_graph_sources)
     }
+
+    /// Like [`BuildableProgram::new`], but loads the standard library and the dependencies from
+    /// the given cache when they are in it, and adds them to it when they aren't.
+    #[must_use]
+    pub fn with_cache(
+        capabilities: TargetCapabilityFlags,
+        package_graph_sources: PackageGraphSources,
+        cache: &Cache,
+    ) -> Self {
+        prepare_package_store_with_cache(capabilities, package_graph_sources, Some(cache))
+    }
 }
 
 /// Given a program config, prepare the package store by compiling all dependencies in the correct order and inserting them.
@@ -42,7 +54,18 @@ pub fn prepare_package_store(
     capabilities: TargetCapabilityFlags,
     package_graph_sources: PackageGraphSources,
 ) -> BuildableProgram {
-    let (std_id, mut package_store) = crate::compile::package_store_with_stdlib(capabilities);
+    prepare_package_store_with_cache(capabilities, package_graph_sources, None)
+}
+
+fn prepare_package_store_with_cache(
+    capabilities: TargetCapabilityFlags,
+    package_graph_sources: PackageGraphSources,
+    cache: Option<&Cache>,
+) -> BuildableProgram {
+    let (std_id, mut package_store) = match cache {
+        Some(cache) => cache.package_store_with_stdlib(capabilities),
+        None => crate::compile::package_store_with_stdlib(capabilities),
+    };
 
     let mut canonical_package_identifier_to_package_id_mapping = FxHashMap::default();
 
@@ -80,14 +103,24 @@ pub fn prepare_package_store(
             .map(|(alias, b)| (*b, Some(alias.clone())))
             .chain(std::iter::once((std_id, None)))
             .collect::<Vec<_>>();
-        let (compile_unit, mut this_errors) = compile::compile(
-            &package_store,
-            &dependencies[..],
-            source_map,
-            PackageType::Lib,
-            capabilities,
-            LanguageFeatures::from_iter(package_to_compile.language_features),
-        );
+        let language_features = LanguageFeatures::from_iter(package_to_compile.language_features);
+        let (compile_unit, mut this_errors) = match cache {
+            Some(cache) => cache.compile(
+                &package_store,
+                &dependencies[..],
+                source_map,
+                capabilities,
+                language_features,
+            ),
+            None => compile::compile(
+                &package_store,
+                &dependencies[..],
+                source_map,
+                PackageType::Lib,
+                capabilities,
+                language_features,
+            ),
+        };
 
         let package_id = package_store.insert(compile_unit);
         if !this_errors.is_empty() {
@@ -38,6 +38,8 @@ fn mock_program() -> Project {
             )]),
         },
         lints: vec![],
+        format: qsc_project::FormatterConfig::default(),
+        target: None,
         errors: vec![],
         path: "project/qsharp.json".into(),
         name: "project".into(),
@@ -1,9 +1,11 @@
 // Copyright (c) Microsoft Corporation.
 // Licensed under the MIT License.
 
-use std::str::FromStr;
+use std::{fs, path::Path, str::FromStr};
 
+use miette::{Context, IntoDiagnostic};
 use qsc_data_structures::target::TargetCapabilityFlags;
+pub use qsc_data_structures::target::TargetDescription;
 
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub enum Profile {
@@ -45,3 +47,13 @@ impl FromStr for Profile {
         }
     }
 }
+
+/// Reads the description of a target from a capability file.
+pub fn read_target_file(path: &Path) -> miette::Result<TargetDescription> {
+    let contents = fs::read_to_string(path)
+        .into_diagnostic()
+        .with_context(|| format!("could not read target file `{}`", path.display()))?;
+    serde_json::from_str(&contents)
+        .into_diagnostic()
+        .with_context(|| format!("could not parse target file `{}`", path.display()))
+}
@@ -0,0 +1,219 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+//! Loading a program again when its files change, for the `--watch` modes of the command-line
+//! tools. The sources of the program are read again on every change, but its dependencies are
+//! only compiled again when they changed, so that the program itself can be compiled
+//! incrementally against them.
+
+#[cfg(test)]
+mod tests;
+
+use crate::{
+    cache::Cache,
+    compile,
+    hir::PackageId,
+    packages::BuildableProgram,
+    project::{FileSystem, PackageGraphSources},
+    PackageStore, SourceMap,
+};
+use miette::Diagnostic;
+use qsc_data_structures::{
+    language_features::LanguageFeatures,
+    target::{TargetCapabilityFlags, TargetDescription},
+};
+use qsc_project::{StdFs, Watcher, MANIFEST_FILE_NAME};
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use thiserror::Error;
+
+#[derive(Debug, Diagnostic, Error)]
+pub enum Error {
+    #[error("could not read source file `{0}`")]
+    #[diagnostic(code("Qsc.Watch.Io"))]
+    Io(String, #[source] io::Error),
+
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    Project(qsc_project::Error),
+
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    Compile(compile::Error),
+}
+
+/// The files that make up a watched program.
+pub enum Input {
+    /// A project, given by the directory that contains its manifest.
+    Project(PathBuf),
+    /// Source files, which depend on the standard library unless `nostdlib` is set.
+    Files {
+        paths: Vec<PathBuf>,
+        entry: Option<Arc<str>>,
+        nostdlib: bool,
+    },
+}
+
+/// The compiled dependencies of a program.
+pub struct Dependencies {
+    pub store: PackageStore,
+    pub dependencies: Vec<(PackageId, Option<Arc<str>>)>,
+    /// The language features of the program, including the ones enabled by its manifest.
+    pub language_features: LanguageFeatures,
+    /// The target named by the manifest of the project, if any.
+    pub target: Option<TargetDescription>,
+}
+
+/// A program whose files are watched for changes.
+pub struct WatchedProgram {
+    input: Input,
+    capabilities: Option<TargetCapabilityFlags>,
+    language_features: LanguageFeatures,
+    cache: Option<Cache>,
+    watcher: Watcher,
+    /// The package graph the dependencies were last compiled from, without the sources of the
+    /// program itself, and the target named by the manifest.
+    built: Option<(PackageGraphSources, Option<TargetDescription>)>,
+}
+
+impl WatchedProgram {
+    /// Creates a watched program. The dependencies are compiled for the given capabilities, or
+    /// else for those of the target named by the project manifest, or else for all capabilities,
+    /// and loaded from the cache, if there is one.
+    #[must_use]
+    pub fn new(
+        input: Input,
+        capabilities: Option<TargetCapabilityFlags>,
+        language_features: LanguageFeatures,
+        cache: Option<Cache>,
+    ) -> Self {
+        // Until a project is loaded its files are unknown, so a change to its manifest or
+        // sources directory starts the next attempt.
+        let watcher = Watcher::new(match &input {
+            Input::Project(dir) => vec![dir.join(MANIFEST_FILE_NAME), dir.join("src")],
+            Input::Files { paths, .. } => paths.clone(),
+        });
+        Self {
+            input,
+            capabilities,
+            language_features,
+            cache,
+            watcher,
+            built: None,
+        }
+    }
+
+    /// Reads the sources of the program, and watches the files they were resolved from. The
+    /// dependencies are returned when they were compiled again, which happens on the first load
+    /// and whenever they changed since the previous load.
+    /// # Errors
+    /// If the project can't be loaded, a source file can't be read, or a dependency fails to
+    /// compile, the errors are returned.
+    pub fn load(&mut self) -> Result<(SourceMap, Option<Dependencies>), Vec<Error>> {
+        let (graph, target, entry, paths) = match &self.input {
+            Input::Project(dir) => {
+                let (graph, target, paths) = load_project(dir)?;
+                (graph, target, None, paths)
+            }
+            Input::Files { paths, entry, .. } => {
+                let sources = paths
+                    .iter()
+                    .map(|path| read_source(path))
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(|error| vec![error])?;
+                let graph = PackageGraphSources::with_no_dependencies(
+                    sources,
+                    LanguageFeatures::default(),
+                    None,
+                );
+                (graph, None, entry.clone(), paths.clone())
+            }
+        };
+        self.watcher.watch(paths);
+
+        let mut dependency_graph = graph.clone();
+        let sources = std::mem::take(&mut dependency_graph.root.sources);
+        let built = (dependency_graph, target);
+        let dependencies = if self.built.as_ref() == Some(&built) {
+            None
+        } else {
+            let dependencies = self.build_dependencies(graph, built.1.clone())?;
+            self.built = Some(built);
+            Some(dependencies)
+        };
+        Ok((SourceMap::new(sources, entry), dependencies))
+    }
+
+    /// Blocks until some of the files of the program change, and returns them.
+    pub fn wait(&mut self) -> Vec<PathBuf> {
+        self.watcher.wait()
+    }
+
+    fn build_dependencies(
+        &self,
+        graph: PackageGraphSources,
+        target: Option<TargetDescription>,
+    ) -> Result<Dependencies, Vec<Error>> {
+        let mut language_features = self.language_features;
+        language_features.merge(graph.root.language_features);
+        if let Input::Files { nostdlib: true, .. } = self.input {
+            let core = match &self.cache {
+                Some(cache) => cache.core(),
+                None => compile::core(),
+            };
+            return Ok(Dependencies {
+                store: PackageStore::new(core),
+                dependencies: Vec::new(),
+                language_features,
+                target,
+            });
+        }
+
+        let capabilities = self
+            .capabilities
+            .or(target.as_ref().map(|target| target.capabilities))
+            .unwrap_or_else(TargetCapabilityFlags::all);
+        let program = match &self.cache {
+            Some(cache) => BuildableProgram::with_cache(capabilities, graph, cache),
+            None => BuildableProgram::new(capabilities, graph),
+        };
+        if !program.dependency_errors.is_empty() {
+            return Err(program
+                .dependency_errors
+                .into_iter()
+                .map(Error::Compile)
+                .collect());
+        }
+        Ok(Dependencies {
+            store: program.store,
+            dependencies: program.user_code_dependencies,
+            language_features,
+            target,
+        })
+    }
+}
+
+/// Loads the project in the directory, and returns its package graph, the target named by its
+/// manifest and the paths to watch.
+#[allow(clippy::type_complexity)]
+fn load_project(
+    dir: &Path,
+) -> Result<(PackageGraphSources, Option<TargetDescription>, Vec<PathBuf>), Vec<Error>> {
+    let project = StdFs
+        .load_project(dir, None)
+        .map_err(|errors| errors.into_iter().map(Error::Project).collect::<Vec<_>>())?;
+    if !project.errors.is_empty() {
+        return Err(project.errors.into_iter().map(Error::Project).collect());
+    }
+    let paths = project.watched_paths();
+    Ok((project.package_graph_sources, project.target, paths))
+}
+
+fn read_source(path: &Path) -> Result<(Arc<str>, Arc<str>), Error> {
+    let contents =
+        fs::read_to_string(path).map_err(|error| Error::Io(path.display().to_string(), error))?;
+    Ok((path.to_string_lossy().into(), contents.into()))
+}
@@ -0,0 +1,150 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use super::{Input, WatchedProgram};
+use crate::{incremental::Compiler, PackageType, SourceMap};
+use qsc_data_structures::{language_features::LanguageFeatures, target::TargetCapabilityFlags};
+use std::{fs, path::PathBuf};
+
+/// Creates an empty directory for a test, named after the test.
+fn test_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("qsc-watched-{name}-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(dir.join("src")).expect("directory should be created");
+    dir
+}
+
+fn write(path: PathBuf, contents: &str) {
+    fs::write(path, contents).expect("file should be written");
+}
+
+fn contents(sources: &SourceMap) -> Vec<&str> {
+    sources.iter().map(|source| &*source.contents).collect()
+}
+
+#[test]
+fn dependencies_are_only_compiled_again_when_they_change() {
+    let dir = test_dir("project");
+    write(dir.join("qsharp.json"), "{}");
+    write(
+        dir.join("src").join("Main.qs"),
+        "namespace Main { function Answer() : Int { 1 } }",
+    );
+    let mut program = WatchedProgram::new(
+        Input::Project(dir.clone()),
+        None,
+        LanguageFeatures::default(),
+        None,
+    );
+
+    let (_, dependencies) = program.load().expect("program should load");
+    assert!(dependencies.is_some(), "first load should compile them");
+
+    write(
+        dir.join("src").join("Main.qs"),
+        "namespace Main { function Two() : Int { 2 } }",
+    );
+    let (sources, dependencies) = program.load().expect("program should load");
+    assert!(
+        dependencies.is_none(),
+        "sources changed, dependencies didn't"
+    );
+    assert_eq!(
+        contents(&sources),
+        ["namespace Main { function Two() : Int { 2 } }"]
+    );
+
+    write(
+        dir.join("qsharp.json"),
+        r#"{ "languageFeatures": ["v2-preview-syntax"] }"#,
+    );
+    let (_, dependencies) = program.load().expect("program should load");
+    let dependencies = dependencies.expect("manifest changed");
+    assert_eq!(
+        dependencies.language_features,
+        LanguageFeatures::from_iter(["v2-preview-syntax"])
+    );
+    fs::remove_dir_all(&dir).expect("directory should be removed");
+}
+
+#[test]
+fn dependencies_carry_the_target_named_by_the_manifest() {
+    let dir = test_dir("target");
+    write(dir.join("qsharp.json"), "{}");
+    write(
+        dir.join("src").join("Main.qs"),
+        "namespace Main { function Answer() : Int { 1 } }",
+    );
+    let mut program = WatchedProgram::new(
+        Input::Project(dir.clone()),
+        None,
+        LanguageFeatures::default(),
+        None,
+    );
+
+    let (_, dependencies) = program.load().expect("program should load");
+    let dependencies = dependencies.expect("first load should compile them");
+    assert_eq!(dependencies.target, None);
+
+    write(
+        dir.join("target.json"),
+        r#"{ "capabilities": ["Adaptive"], "maxQubits": 4 }"#,
+    );
+    write(dir.join("qsharp.json"), r#"{ "target": "target.json" }"#);
+    let (_, dependencies) = program.load().expect("program should load");
+    let target = dependencies
+        .expect("target changed")
+        .target
+        .expect("manifest names a target");
+    assert_eq!(target.capabilities, TargetCapabilityFlags::Adaptive);
+    assert_eq!(target.max_qubits, Some(4));
+    fs::remove_dir_all(&dir).expect("directory should be removed");
+}
+
+#[test]
+fn files_are_read_again_and_compiled_incrementally() {
+    let dir = test_dir("files");
+    let path = dir.join("src").join("Main.qs");
+    write(path.clone(), "function Answer() : Int { 1 }");
+    let mut program = WatchedProgram::new(
+        Input::Files {
+            paths: vec![path.clone()],
+            entry: Some("Main.Answer()".into()),
+            nostdlib: true,
+        },
+        None,
+        LanguageFeatures::default(),
+        None,
+    );
+
+    let (sources, dependencies) = program.load().expect("program should load");
+    let dependencies = dependencies.expect("first load should compile them");
+    let mut compiler = Compiler::new(
+        SourceMap::default(),
+        PackageType::Lib,
+        TargetCapabilityFlags::all(),
+        dependencies.language_features,
+        dependencies.store,
+        &dependencies.dependencies,
+    )
+    .expect("empty sources should compile");
+    // An executable needs an entry point, so this compiles only with the entry expression.
+    compiler
+        .update_sources(sources, PackageType::Exe)
+        .expect("sources should compile");
+
+    write(path.clone(), "function Answer() : Int { Two() }");
+    let (sources, dependencies) = program.load().expect("program should load");
+    assert!(dependencies.is_none());
+    let errors = compiler
+        .update_sources(sources, PackageType::Exe)
+        .expect_err("sources should not compile");
+    assert!(!errors.is_empty());
+
+    fs::remove_file(&path).expect("file should be removed");
+    assert!(program.load().is_err(), "missing file should be an error");
+    write(path, "function Answer() : Int { 1 }");
+    let (sources, _) = program.load().expect("program should load");
+    assert_eq!(contents(&sources), ["function Answer() : Int { 1 }"]);
+    fs::remove_dir_all(&dir).expect("directory should be removed");
+}
@@ -8,6 +8,7 @@
 use indenter::{indented, Format, Indented};
 use num_bigint::BigInt;
 use qsc_data_structures::span::{Span, WithSpan};
+use serde::Serialize;
 use std::{
     cmp::Ordering,
     fmt::{self, Display, Formatter, Write},
@@ -31,7 +32,7 @@ fn set_indentation<'a, 'b>(
 /// This could be assigned or unassigned. If unassigned, the value will be `u32::MAX`.
 /// Assignment happens after symbol resolution. Use [`NodeId::is_default`] to check if the node
 /// has been assigned yet.
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, Serialize)]
 pub struct NodeId(u32);
 
 impl NodeId {
@@ -111,7 +112,7 @@ impl Hash for NodeId {
 }
 
 /// The root node of an AST.
-#[derive(Clone, Debug, Default, PartialEq)]
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
 pub struct Package {
     /// The node ID.
     pub id: NodeId,
@@ -137,7 +138,7 @@ impl Display for Package {
 }
 
 /// A node that can exist at the top level of a package.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize)]
 pub enum TopLevelNode {
     /// A namespace
     Namespace(Namespace),
@@ -155,7 +156,7 @@ impl Display for TopLevelNode {
 }
 
 /// A namespace.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize)]
 pub struct Namespace {
     /// The node ID.
     pub id: NodeId,
@@ -205,7 +206,7 @@ impl Display for Namespace {
 }
 
 /// An item.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize)]
 pub struct Item {
     /// The ID.
     pub id: NodeId,
@@ -254,7 +255,7 @@ impl Display for Item {
 }
 
 /// An item kind.
-#[derive(Clone, Debug, Default, PartialEq)]
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
 pub enum ItemKind {
     /// A `function` or `operation` declaration.
     Callable(Box<CallableDecl>),
@@ -290,7 +291,7 @@ impl Display for ItemKind {
 }
 
 /// An attribute.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize)]
 pub struct Attr {
     /// The node ID.
     pub id: NodeId,
@@ -313,7 +314,7 @@ impl Display for Attr {
 }
 
 /// A type definition.
-#[derive(Clone, Debug, PartialEq, Default)]
+#[derive(Clone, Debug, PartialEq, Default, Serialize)]
 pub struct TyDef {
     /// The node ID.
     pub id: NodeId,
@@ -352,7 +353,7 @@ impl WithSpan for TyDef {
 }
 
 /// A type definition kind.
-#[derive(Clone, Debug, PartialEq, Default)]
+#[derive(Clone, Debug, PartialEq, Default, Serialize)]
 pub enum TyDefKind {
     /// A field definition with an optional name but required type.
     Field(Option<Box<Ident>>, Box<Ty>),
@@ -400,7 +401,7 @@ impl Display for TyDefKind {
 }
 
 /// A struct definition.
-#[derive(Clone, Debug, PartialEq, Default)]
+#[derive(Clone, Debug, PartialEq, Default, Serialize)]
 pub struct StructDecl {
     /// The node ID.
     pub id: NodeId,
@@ -435,7 +436,7 @@ impl WithSpan for StructDecl {
 }
 
 /// A struct field definition.
-#[derive(Clone, Debug, PartialEq, Default)]
+#[derive(Clone, Debug, PartialEq, Default, Serialize)]
 pub struct FieldDef {
     /// The node ID.
     pub id: NodeId,
@@ -464,7 +465,7 @@ impl WithSpan for FieldDef {
 }
 
 /// A callable declaration header.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize)]
 pub struct CallableDecl {
     /// The node ID.
     pub id: NodeId,
@@ -515,7 +516,7 @@ impl Display for CallableDecl {
 }
 
 /// The body of a callable.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize)]
 pub enum CallableBody {
     /// A block for the callable's body specialization.
     Block(Box<Block>),
@@ -541,7 +542,7 @@ impl Display for CallableBody {
 }
 
 /// A specialization declaration.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize)]
 pub struct SpecDecl {
     /// The node ID.
     pub id: NodeId,
@@ -564,7 +565,7 @@ impl Display for SpecDecl {
 }
 
 /// The body of a specialization.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize)]
 pub enum SpecBody {
     /// The strategy to use to automatically generate the specialization.
     Gen(SpecGen),
@@ -589,7 +590,7 @@ impl Display for SpecBody {
 }
 
 /// An expression that describes a set of functors.
-#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq, Serialize)]
 pub struct FunctorExpr {
     /// The node ID.
     pub id: NodeId,
@@ -606,7 +607,7 @@ impl Display for FunctorExpr {
 }
 
 /// A functor expression kind.
-#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq, Serialize)]
 pub enum FunctorExprKind {
     /// A binary operation.
     BinOp(SetOp, Box<FunctorExpr>, Box<FunctorExpr>),
@@ -627,7 +628,7 @@ impl Display for FunctorExprKind {
 }
 
 /// A type.
-#[derive(Clone, Debug, Eq, Hash, PartialEq, Default)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq, Default, Serialize)]
 pub struct Ty {
     /// The node ID.
     pub id: NodeId,
@@ -650,7 +651,7 @@ impl WithSpan for Ty {
 }
 
 /// A type kind.
-#[derive(Clone, Debug, Eq, Hash, PartialEq, Default)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq, Default, Serialize)]
 pub enum TyKind {
     /// An array type.
     Array(Box<Ty>),
@@ -709,7 +710,7 @@ impl Display for TyKind {
 }
 
 /// A sequenced block of statements.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize)]
 pub struct Block {
     /// The node ID.
     pub id: NodeId,
@@ -736,7 +737,7 @@ impl Display for Block {
 }
 
 /// A statement.
-#[derive(Clone, Debug, Default, PartialEq)]
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
 pub struct Stmt {
     /// The node ID.
     pub id: NodeId,
@@ -753,7 +754,7 @@ impl Display for Stmt {
 }
 
 /// A statement kind.
-#[derive(Clone, Debug, Default, PartialEq)]
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
 pub enum StmtKind {
     /// An empty statement.
     Empty,
@@ -802,7 +803,7 @@ impl Display for StmtKind {
 }
 
 /// An expression.
-#[derive(Clone, Debug, Default, PartialEq)]
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
 pub struct Expr {
     /// The node ID.
     pub id: NodeId,
@@ -825,7 +826,7 @@ impl WithSpan for Expr {
 }
 
 /// An expression kind.
-#[derive(Clone, Debug, Default, PartialEq)]
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
 pub enum ExprKind {
     /// An array: `[a, b, c]`.
     Array(Box<[Box<Expr>]>),
@@ -1199,7 +1200,7 @@ fn display_while(mut indent: Indented<Formatter>, cond: &Expr, block: &Block) ->
 }
 
 /// A field assignment in a struct constructor expression.
-#[derive(Clone, Debug, Default, PartialEq)]
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
 pub struct FieldAssign {
     /// The node ID.
     pub id: NodeId,
@@ -1228,7 +1229,7 @@ impl Display for FieldAssign {
 }
 
 /// An interpolated string component.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize)]
 pub enum StringComponent {
     /// An expression.
     Expr(Box<Expr>),
@@ -1237,7 +1238,7 @@ pub enum StringComponent {
 }
 
 /// A pattern.
-#[derive(Clone, Debug, Eq, Hash, PartialEq, Default)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq, Default, Serialize)]
 pub struct Pat {
     /// The node ID.
     pub id: NodeId,
@@ -1260,7 +1261,7 @@ impl WithSpan for Pat {
 }
 
 /// A pattern kind.
-#[derive(Clone, Debug, Eq, Hash, PartialEq, Default)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq, Default, Serialize)]
 pub enum PatKind {
     /// A binding with an optional type annotation.
     Bind(Box<Ident>, Option<Box<Ty>>),
@@ -1321,7 +1322,7 @@ impl Display for PatKind {
 }
 
 /// A qubit initializer.
-#[derive(Clone, Debug, PartialEq, Default)]
+#[derive(Clone, Debug, PartialEq, Default, Serialize)]
 pub struct QubitInit {
     /// The node ID.
     pub id: NodeId,
@@ -1344,7 +1345,7 @@ impl WithSpan for QubitInit {
 }
 
 /// A qubit initializer kind.
-#[derive(Clone, Debug, PartialEq, Default)]
+#[derive(Clone, Debug, PartialEq, Default, Serialize)]
 pub enum QubitInitKind {
     /// An array of qubits: `Qubit[a]`.
     Array(Box<Expr>),
@@ -1392,7 +1393,7 @@ impl Display for QubitInitKind {
 }
 
 /// A path to a declaration.
-#[derive(Clone, Debug, Eq, Hash, PartialEq, Default)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq, Default, Serialize)]
 pub struct Path {
     /// The node ID.
     pub id: NodeId,
@@ -1468,7 +1469,7 @@ impl WithSpan for Path {
 }
 
 /// An identifier.
-#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq, Serialize)]
 pub struct Ident {
     /// The node ID.
     pub id: NodeId,
@@ -1481,7 +1482,7 @@ pub struct Ident {
 /// A [`Idents`] represents a sequence of idents. It provides a helpful abstraction
 /// that is more powerful than a simple `Vec<Ident>`, and is primarily used to represent
 /// dot-separated paths.
-#[derive(Clone, Debug, Eq, Hash, PartialEq, Default)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq, Default, Serialize)]
 pub struct Idents(pub Box<[Ident]>);
 
 impl From<Idents> for Vec<Rc<str>> {
@@ -1637,7 +1638,7 @@ impl Display for Ident {
 }
 
 /// A callable kind.
-#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize)]
 pub enum CallableKind {
     /// A function.
     Function,
@@ -1646,7 +1647,7 @@ pub enum CallableKind {
 }
 
 /// The mutability of a binding.
-#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize)]
 pub enum Mutability {
     /// An immutable binding.
     Immutable,
@@ -1655,7 +1656,7 @@ pub enum Mutability {
 }
 
 /// The source of an allocated qubit.
-#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize)]
 pub enum QubitSource {
     /// A qubit initialized to the zero state.
     Fresh,
@@ -1665,7 +1666,7 @@ pub enum QubitSource {
 }
 
 /// A literal.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize)]
 pub enum Lit {
     /// A big integer literal.
     BigInt(Box<BigInt>),
@@ -1699,7 +1700,7 @@ impl Display for Lit {
 }
 
 /// A measurement result.
-#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize)]
 pub enum Result {
     /// The zero eigenvalue.
     Zero,
@@ -1708,7 +1709,7 @@ pub enum Result {
 }
 
 /// A Pauli operator.
-#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize)]
 pub enum Pauli {
     /// The Pauli I operator.
     I,
@@ -1721,7 +1722,7 @@ pub enum Pauli {
 }
 
 /// A functor that may be applied to an operation.
-#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize)]
 pub enum Functor {
     /// The adjoint functor.
     Adj,
@@ -1730,7 +1731,7 @@ pub enum Functor {
 }
 
 /// A specialization that may be implemented for an operation.
-#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize)]
 pub enum Spec {
     /// The default specialization.
     Body,
@@ -1754,7 +1755,7 @@ impl Display for Spec {
 }
 
 /// A strategy for generating a specialization.
-#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize)]
 pub enum SpecGen {
     /// Choose a strategy automatically.
     Auto,
@@ -1770,7 +1771,7 @@ pub enum SpecGen {
 }
 
 /// A unary operator.
-#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize)]
 pub enum UnOp {
     /// A functor application.
     Functor(Functor),
@@ -1797,7 +1798,7 @@ impl Display for UnOp {
 }
 
 /// A binary operator.
-#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize)]
 pub enum BinOp {
     /// Addition: `+`.
     Add,
@@ -1840,7 +1841,7 @@ pub enum BinOp {
 }
 
 /// A ternary operator.
-#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize)]
 pub enum TernOp {
     /// Conditional: `a ? b | c`.
     Cond,
@@ -1849,7 +1850,7 @@ pub enum TernOp {
 }
 
 /// A set operator.
-#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize)]
 pub enum SetOp {
     /// The set union.
     Union,
@@ -1857,7 +1858,7 @@ pub enum SetOp {
     Intersect,
 }
 
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
 /// Represents an export declaration.
 pub struct ImportOrExportDecl {
     /// The span.
@@ -1910,7 +1911,7 @@ impl ImportOrExportDecl {
 }
 
 /// An individual item within an [`ExportDecl`]. This can be a path or a path with an alias.
-#[derive(Clone, Debug, Eq, PartialEq, Default)]
+#[derive(Clone, Debug, Eq, PartialEq, Default, Serialize)]
 pub struct ImportOrExportItem {
     /// The path to the item being exported.
     pub path: Path,
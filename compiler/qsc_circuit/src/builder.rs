@@ -10,9 +10,10 @@ use num_complex::Complex;
 use qsc_codegen::remapper::{HardwareId, Remapper};
 use qsc_data_structures::index_map::IndexMap;
 use qsc_eval::{backend::Backend, val::Value};
-use std::{fmt::Write, mem::take, rc::Rc};
+use std::{any::Any, fmt::Write, mem::take, rc::Rc};
 
 /// Backend implementation that builds a circuit representation.
+#[derive(Clone)]
 pub struct Builder {
     circuit: Circuit,
     config: Config,
@@ -214,6 +215,17 @@ impl Backend for Builder {
             _ => Some(Ok(Value::unit())),
         }
     }
+
+    fn checkpoint(&self) -> Option<Box<dyn Any>> {
+        Some(Box::new(self.clone()))
+    }
+
+    fn restore(&mut self, checkpoint: &dyn Any) {
+        *self = checkpoint
+            .downcast_ref::<Self>()
+            .expect("checkpoint should be a circuit builder state")
+            .clone();
+    }
 }
 
 impl Builder {
@@ -43,9 +43,26 @@ pub fn fir_to_qir(
     compute_properties: Option<PackageStoreComputeProperties>,
     entry: &ProgramEntry,
 ) -> Result<String, qsc_partial_eval::Error> {
+    let program = fir_to_rir(fir_store, capabilities, compute_properties, entry)?;
+    Ok(rir_to_qir(&program))
+}
+
+/// Partially evaluates the program into the RIR that QIR is generated from.
+pub fn fir_to_rir(
+    fir_store: &qsc_fir::fir::PackageStore,
+    capabilities: TargetCapabilityFlags,
+    compute_properties: Option<PackageStoreComputeProperties>,
+    entry: &ProgramEntry,
+) -> Result<rir::Program, qsc_partial_eval::Error> {
     let mut program = get_rir_from_compilation(fir_store, compute_properties, entry, capabilities)?;
     check_and_transform(&mut program);
-    Ok(ToQir::<String>::to_qir(&program, &program))
+    Ok(program)
+}
+
+/// Generates QIR from a program in RIR.
+#[must_use]
+pub fn rir_to_qir(program: &rir::Program) -> String {
+    ToQir::<String>::to_qir(program, program)
 }
 
 fn get_rir_from_compilation(
@@ -13,7 +13,7 @@ use qsc_data_structures::index_map::IndexMap;
 /// Note that even though qubit reset & reuse is disallowed,
 /// qubit ids are still reused for new allocations.
 /// Measurements are tracked and deferred.
-#[derive(Default)]
+#[derive(Clone, Default)]
 pub struct Remapper {
     next_meas_id: usize,
     next_qubit_id: usize,
@@ -1,6 +1,7 @@
 // Copyright (c) Microsoft Corporation.
 // Licensed under the MIT License.
 
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::{
     fmt::{self, Debug, Formatter},
     iter::Enumerate,
@@ -169,6 +170,22 @@ impl<K, V> Default for IndexMap<K, V> {
     }
 }
 
+// Serialized as the vector of slots, so that keys keep their indices.
+impl<K, V: Serialize> Serialize for IndexMap<K, V> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.values.serialize(serializer)
+    }
+}
+
+impl<'de, K, V: Deserialize<'de>> Deserialize<'de> for IndexMap<K, V> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(Self {
+            _keys: PhantomData,
+            values: Vec::deserialize(deserializer)?,
+        })
+    }
+}
+
 impl<K: From<usize>, V> IntoIterator for IndexMap<K, V> {
     type Item = (K, V);
 
@@ -5,6 +5,7 @@
 mod tests;
 
 use rustc_hash::{FxHashMap, FxHashSet};
+use serde::{de::Error as _, Deserialize, Deserializer, Serialize, Serializer};
 use std::{cell::RefCell, collections::BTreeMap, fmt::Display, iter::Peekable, ops::Deref, rc::Rc};
 
 pub const PRELUDE: [[&str; 3]; 4] = [
@@ -15,7 +16,9 @@ pub const PRELUDE: [[&str; 3]; 4] = [
 ];
 
 /// An ID that corresponds to a namespace in the global scope.
-#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Default, PartialOrd, Ord)]
+#[derive(
+    Clone, Copy, Debug, Eq, Hash, PartialEq, Default, PartialOrd, Ord, Serialize, Deserialize,
+)]
 pub struct NamespaceId(usize);
 impl NamespaceId {
     /// Create a new namespace ID.
@@ -287,6 +290,77 @@ impl Default for NamespaceTreeRoot {
     }
 }
 
+/// The serialized form of a [`NamespaceTreeRoot`]: the ID assigner, and each node of the tree with
+/// the names and IDs of its children, root first. A node reachable by several paths is stored once,
+/// so that it is shared again after deserialization.
+#[derive(Serialize, Deserialize)]
+struct SerializedNamespaceTree {
+    assigner: usize,
+    nodes: Vec<SerializedNamespaceNode>,
+}
+
+type SerializedNamespaceNode = (NamespaceId, Vec<(Rc<str>, NamespaceId)>);
+
+impl Serialize for NamespaceTreeRoot {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut nodes = Vec::new();
+        let mut seen = FxHashSet::default();
+        let mut stack = vec![self.tree.clone()];
+        while let Some(cell) = stack.pop() {
+            let node = cell.borrow();
+            if !seen.insert(node.id) {
+                continue;
+            }
+            let mut children = node
+                .children
+                .iter()
+                .map(|(name, child)| (name.clone(), child.borrow().id))
+                .collect::<Vec<_>>();
+            children.sort();
+            stack.extend(node.children.values().cloned());
+            nodes.push((node.id, children));
+        }
+        SerializedNamespaceTree {
+            assigner: self.assigner,
+            nodes,
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for NamespaceTreeRoot {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let serialized = SerializedNamespaceTree::deserialize(deserializer)?;
+        let cells = serialized
+            .nodes
+            .iter()
+            .map(|(id, _)| {
+                let node = NamespaceTreeNode::new(*id, FxHashMap::default());
+                (*id, Rc::new(RefCell::new(node)))
+            })
+            .collect::<FxHashMap<_, _>>();
+        for (id, children) in &serialized.nodes {
+            let mut node = cells[id].borrow_mut();
+            for (name, child) in children {
+                let child = cells
+                    .get(child)
+                    .ok_or_else(|| D::Error::custom(format!("{child} is not in the tree")))?;
+                node.children.insert(name.clone(), child.clone());
+            }
+        }
+        let tree = serialized
+            .nodes
+            .first()
+            .map(|(id, _)| cells[id].clone())
+            .ok_or_else(|| D::Error::custom("namespace tree has no root"))?;
+        Ok(Self {
+            assigner: serialized.assigner,
+            tree,
+            memo: RefCell::new(FxHashMap::default()),
+        })
+    }
+}
+
 /// A node in the namespace tree. Each node has a unique ID and a map of children.
 /// Supports interior mutability of children for inserting new nodes.
 #[derive(Clone)]
@@ -1,7 +1,11 @@
 // Copyright (c) Microsoft Corporation.
 // Licensed under the MIT License.
 
+#[cfg(test)]
+mod tests;
+
 use bitflags::bitflags;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 
 bitflags! {
     #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -38,3 +42,53 @@ impl Default for TargetCapabilityFlags {
         TargetCapabilityFlags::empty()
     }
 }
+
+// Serialized as the names of the set flags, so that they are readable in emitted outputs.
+impl Serialize for TargetCapabilityFlags {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter_names().map(|(name, _)| name))
+    }
+}
+
+// Deserialized from the names of the flags, which can also be `Base` or `Unrestricted` for the
+// capabilities of those profiles.
+impl<'de> Deserialize<'de> for TargetCapabilityFlags {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let names = Vec::<String>::deserialize(deserializer)?;
+        names.iter().try_fold(Self::empty(), |flags, name| {
+            name.parse::<Self>()
+                .map(|flag| flags | flag)
+                .map_err(|()| de::Error::custom(format!("unknown target capability `{name}`")))
+        })
+    }
+}
+
+/// A description of a target, from a capability file, which refines the capabilities of a QIR
+/// profile with the limits of the hardware.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct TargetDescription {
+    /// The capabilities of the target.
+    #[serde(default)]
+    pub capabilities: TargetCapabilityFlags,
+    /// The intrinsic gates the target supports, named as in QIR without the `__quantum__qis__`
+    /// prefix and the `__body` suffix, such as `h`, `rz`, `cx` or `mresetz`. All gates are
+    /// supported when this is `None`.
+    #[serde(default)]
+    pub gates: Option<Vec<String>>,
+    /// The number of qubits the target has.
+    #[serde(default)]
+    pub max_qubits: Option<u32>,
+    /// The number of measurements the target can do in a program.
+    #[serde(default)]
+    pub max_measurements: Option<u32>,
+}
+
+impl From<TargetCapabilityFlags> for TargetDescription {
+    fn from(capabilities: TargetCapabilityFlags) -> Self {
+        Self {
+            capabilities,
+            ..Self::default()
+        }
+    }
+}
@@ -0,0 +1,90 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+#![allow(clippy::needless_raw_string_hashes)]
+
+use expect_test::expect;
+
+use super::*;
+
+#[test]
+fn capabilities_serialize_as_flag_names() {
+    let capabilities = TargetCapabilityFlags::Adaptive | TargetCapabilityFlags::QubitReset;
+    let json = serde_json::to_string(&capabilities).expect("capabilities should serialize");
+    expect![[r#"["Adaptive","QubitReset"]"#]].assert_eq(&json);
+}
+
+#[test]
+fn capabilities_deserialize_from_flag_names() {
+    let capabilities: TargetCapabilityFlags =
+        serde_json::from_str(r#"["Adaptive", "IntegerComputations"]"#)
+            .expect("capabilities should deserialize");
+    assert_eq!(
+        capabilities,
+        TargetCapabilityFlags::Adaptive | TargetCapabilityFlags::IntegerComputations
+    );
+}
+
+#[test]
+fn capabilities_deserialize_from_profile_names() {
+    let base: TargetCapabilityFlags =
+        serde_json::from_str(r#"["Base"]"#).expect("capabilities should deserialize");
+    assert_eq!(base, TargetCapabilityFlags::empty());
+    let unrestricted: TargetCapabilityFlags =
+        serde_json::from_str(r#"["Unrestricted"]"#).expect("capabilities should deserialize");
+    assert_eq!(unrestricted, TargetCapabilityFlags::all());
+}
+
+#[test]
+fn unknown_capability_is_error() {
+    let err = serde_json::from_str::<TargetCapabilityFlags>(r#"["Adaptive", "Teleportation"]"#)
+        .expect_err("unknown capability should fail");
+    expect!["unknown target capability `Teleportation`"].assert_eq(&err.to_string());
+}
+
+#[test]
+fn target_description_deserializes_limits() {
+    let target: TargetDescription = serde_json::from_str(
+        r#"{
+            "capabilities": ["Adaptive", "QubitReset"],
+            "gates": ["h", "cx", "mresetz"],
+            "maxQubits": 20,
+            "maxMeasurements": 100
+        }"#,
+    )
+    .expect("target description should deserialize");
+    expect![[r#"
+        TargetDescription {
+            capabilities: TargetCapabilityFlags(
+                Adaptive | QubitReset,
+            ),
+            gates: Some(
+                [
+                    "h",
+                    "cx",
+                    "mresetz",
+                ],
+            ),
+            max_qubits: Some(
+                20,
+            ),
+            max_measurements: Some(
+                100,
+            ),
+        }
+    "#]]
+    .assert_debug_eq(&target);
+}
+
+#[test]
+fn target_description_defaults_to_base_without_limits() {
+    let target: TargetDescription =
+        serde_json::from_str("{}").expect("target description should deserialize");
+    assert_eq!(target, TargetDescription::default());
+}
+
+#[test]
+fn target_description_rejects_unknown_fields() {
+    serde_json::from_str::<TargetDescription>(r#"{ "qubits": 5 }"#)
+        .expect_err("unknown field should fail");
+}
@@ -0,0 +1,130 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+#[cfg(test)]
+mod tests;
+
+use qsc_data_structures::line_column::{Encoding, Position};
+use qsc_frontend::compile::SourceMap;
+use qsc_hir::hir::{ItemKind, Package};
+use std::rc::Rc;
+use std::sync::Arc;
+
+/// A code example taken from a fenced `qsharp` block in the doc comment of an item.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Doctest {
+    /// The fully qualified name of the documented item.
+    pub name: String,
+    /// The namespace of the documented item, which is opened before the example runs
+    /// so that the example can refer to the item and its neighbours by name.
+    pub namespace: Option<Rc<str>>,
+    /// The code of the example, without the doc comment markers.
+    pub code: String,
+    /// The name of the source file containing the doc comment.
+    pub source: Arc<str>,
+    /// The position of the opening fence of the example in the source file.
+    /// The column is a UTF-8 byte offset.
+    pub position: Position,
+    /// Whether the fence is marked `ignore`, as in ```` ```qsharp,ignore ````,
+    /// so the example is only documentation and shouldn't be run.
+    pub ignore: bool,
+}
+
+/// Extracts the code examples from the doc comments of the callables and types of a package.
+/// Examples are returned in source order.
+#[must_use]
+pub fn extract_doctests(package: &Package, sources: &SourceMap) -> Vec<Doctest> {
+    let mut doctests = Vec::new();
+    for (_, item) in &package.items {
+        let name = match &item.kind {
+            ItemKind::Callable(decl) => &decl.name.name,
+            ItemKind::Ty(ident, _) => &ident.name,
+            // Namespaces have no examples, and exports share the doc comment of the exported item.
+            ItemKind::Namespace(..) | ItemKind::Export(..) => continue,
+        };
+        if !item.doc.contains("```") {
+            continue;
+        }
+        let Some(source) = sources.find_by_offset(item.span.lo) else {
+            continue;
+        };
+        let namespace = item
+            .parent
+            .and_then(|parent| match &package.items.get(parent)?.kind {
+                ItemKind::Namespace(namespace, _) => Some(namespace.name()),
+                _ => None,
+            });
+        let name = match &namespace {
+            Some(namespace) => format!("{namespace}.{name}"),
+            None => name.to_string(),
+        };
+        // An item's span starts at its doc comment, so the doc comment lines can be
+        // read back from the source to find where each example is.
+        let offset = (item.span.lo - source.offset) as usize;
+        for (fence_offset, code, ignore) in fenced_blocks(&source.contents[offset..]) {
+            doctests.push(Doctest {
+                name: name.clone(),
+                namespace: namespace.clone(),
+                code,
+                source: source.name.clone(),
+                position: Position::from_utf8_byte_offset(
+                    Encoding::Utf8,
+                    &source.contents,
+                    u32::try_from(offset + fence_offset).expect("offset should fit in u32"),
+                ),
+                ignore,
+            });
+        }
+    }
+    doctests.sort_by(|a, b| {
+        a.source
+            .cmp(&b.source)
+            .then(a.position.line.cmp(&b.position.line))
+    });
+    doctests
+}
+
+/// Finds the fenced `qsharp` blocks in the doc comment at the start of the given text.
+/// Returns the offset of each opening fence, the code in the block, and whether the
+/// block is marked `ignore`.
+fn fenced_blocks(text: &str) -> Vec<(usize, String, bool)> {
+    let mut blocks = Vec::new();
+    // The fence offset, code and `ignore` marker of the open `qsharp` block, if any, and
+    // whether a block in another language is open.
+    let mut open: Option<(usize, String, bool)> = None;
+    let mut in_other_block = false;
+    let mut line_offset = 0;
+    for line in text.split_inclusive('\n') {
+        let offset = line_offset;
+        line_offset += line.len();
+        let Some(content) = line.trim_start().strip_prefix("///") else {
+            break;
+        };
+        let content = content.trim_end_matches(['\r', '\n']);
+        let content = content.strip_prefix(' ').unwrap_or(content);
+        let trimmed = content.trim_start();
+
+        if let Some(info) = trimmed.strip_prefix("```") {
+            if let Some(block) = open.take() {
+                blocks.push(block);
+            } else if in_other_block {
+                in_other_block = false;
+            } else {
+                let mut attributes = info
+                    .split(|c: char| c == ',' || c.is_whitespace())
+                    .filter(|attribute| !attribute.is_empty());
+                if attributes.next() == Some("qsharp") {
+                    let ignore = attributes.any(|attribute| attribute == "ignore");
+                    let fence_offset = offset + line.find("```").expect("line should have a fence");
+                    open = Some((fence_offset, String::new(), ignore));
+                } else {
+                    in_other_block = true;
+                }
+            }
+        } else if let Some((_, code, _)) = &mut open {
+            code.push_str(content);
+            code.push('\n');
+        }
+    }
+    blocks
+}
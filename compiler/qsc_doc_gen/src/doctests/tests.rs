@@ -0,0 +1,210 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+#![allow(clippy::needless_raw_string_hashes)]
+
+use super::extract_doctests;
+use expect_test::{expect, Expect};
+use qsc_data_structures::{language_features::LanguageFeatures, target::TargetCapabilityFlags};
+use qsc_frontend::compile::{self, compile, PackageStore, SourceMap};
+
+fn check(sources: &[(&str, &str)], expect: &Expect) {
+    let store = PackageStore::new(compile::core());
+    let sources = SourceMap::new(
+        sources
+            .iter()
+            .map(|(name, contents)| ((*name).into(), (*contents).into())),
+        None,
+    );
+    let unit = compile(
+        &store,
+        &[],
+        sources,
+        TargetCapabilityFlags::all(),
+        LanguageFeatures::default(),
+    );
+    assert!(unit.errors.is_empty(), "{:?}", unit.errors);
+
+    let doctests = extract_doctests(&unit.package, &unit.sources)
+        .iter()
+        .map(|doctest| {
+            format!(
+                "{} at {}:{}:{}{}\n{}",
+                doctest.name,
+                doctest.source,
+                doctest.position.line + 1,
+                doctest.position.column + 1,
+                if doctest.ignore { " (ignored)" } else { "" },
+                doctest.code
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("---\n");
+    expect.assert_eq(&doctests);
+}
+
+#[test]
+fn examples_of_callables_and_types() {
+    check(
+        &[(
+            "A.qs",
+            r#"namespace A {
+    /// # Summary
+    /// Adds one.
+    ///
+    /// # Example
+    /// ```qsharp
+    /// let two = AddOne(1);
+    /// Message($"{two}");
+    /// ```
+    function AddOne(x : Int) : Int { x + 1 }
+
+    /// A pair.
+    /// ```qsharp
+    /// let pair = Pair(1, 2);
+    /// ```
+    newtype Pair = (Int, Int);
+}"#,
+        )],
+        &expect![[r#"
+            A.AddOne at A.qs:6:9
+            let two = AddOne(1);
+            Message($"{two}");
+            ---
+            A.Pair at A.qs:13:9
+            let pair = Pair(1, 2);
+        "#]],
+    );
+}
+
+#[test]
+fn only_qsharp_blocks_are_extracted() {
+    check(
+        &[(
+            "A.qs",
+            r#"namespace A {
+    /// Not a doctest:
+    /// ```python
+    /// qsharp.eval("Foo()")
+    /// ```
+    /// Nor is this:
+    /// ```
+    /// Foo()
+    /// ```
+    /// ```qsharp
+    /// Foo();
+    /// ```
+    operation Foo() : Unit {}
+}"#,
+        )],
+        &expect![[r#"
+            A.Foo at A.qs:10:9
+            Foo();
+        "#]],
+    );
+}
+
+#[test]
+fn ignored_examples_are_marked() {
+    check(
+        &[(
+            "A.qs",
+            r#"namespace A {
+    /// ```qsharp, ignore
+    /// Foo(NotDefined);
+    /// ```
+    /// ```qsharp
+    /// Foo();
+    /// ```
+    @Config(Unrestricted)
+    operation Foo() : Unit {}
+}"#,
+        )],
+        &expect![[r#"
+            A.Foo at A.qs:2:9 (ignored)
+            Foo(NotDefined);
+            ---
+            A.Foo at A.qs:5:9
+            Foo();
+        "#]],
+    );
+}
+
+#[test]
+fn indentation_inside_examples_is_kept() {
+    check(
+        &[(
+            "A.qs",
+            r#"namespace A {
+    ///   ```qsharp
+    ///   for i in 0..2 {
+    ///       Message($"{i}");
+    ///   }
+    ///   ```
+    function Foo() : Unit {}
+}"#,
+        )],
+        &expect![[r#"
+            A.Foo at A.qs:2:11
+              for i in 0..2 {
+                  Message($"{i}");
+              }
+        "#]],
+    );
+}
+
+#[test]
+fn examples_are_ordered_by_source_and_line() {
+    check(
+        &[
+            (
+                "B.qs",
+                r#"namespace B {
+    /// ```qsharp
+    /// B.Second();
+    /// ```
+    function Second() : Unit {}
+
+    /// ```qsharp
+    /// B.First();
+    /// ```
+    function First() : Unit {}
+}"#,
+            ),
+            (
+                "A.qs",
+                r#"namespace A {
+    /// ```qsharp
+    /// A.Only();
+    /// ```
+    function Only() : Unit {}
+}"#,
+            ),
+        ],
+        &expect![[r#"
+            A.Only at A.qs:2:9
+            A.Only();
+            ---
+            B.Second at B.qs:2:9
+            B.Second();
+            ---
+            B.First at B.qs:7:9
+            B.First();
+        "#]],
+    );
+}
+
+#[test]
+fn items_without_examples_have_no_doctests() {
+    check(
+        &[(
+            "A.qs",
+            r#"namespace A {
+    /// Just a summary with `code`.
+    function Foo() : Unit {}
+    function Bar() : Unit {}
+}"#,
+        )],
+        &expect![""],
+    );
+}
@@ -24,11 +24,11 @@ type FilesWithMetadata = Vec<(Arc<str>, Arc<Metadata>, Arc<str>)>;
 
 /// Represents an immutable compilation state.
 #[derive(Debug)]
-struct Compilation {
+pub(crate) struct Compilation {
     /// Package store, containing the current package and all its dependencies.
-    package_store: PackageStore,
+    pub(crate) package_store: PackageStore,
     /// Current package id when provided.
-    current_package_id: Option<PackageId>,
+    pub(crate) current_package_id: Option<PackageId>,
     /// Aliases for packages.
     dependencies: FxHashMap<PackageId, Arc<str>>,
 }
@@ -81,6 +81,27 @@ impl Compilation {
             dependencies: package_aliases,
         }
     }
+
+    /// Returns the kind of the given package, or `None` if the package is not
+    /// documented because user code can't access it.
+    pub(crate) fn package_kind(&self, package_id: PackageId) -> Option<PackageKind> {
+        if package_id == PackageId::CORE {
+            // Core package is always included in the compilation.
+            Some(PackageKind::Core)
+        } else if package_id == 1.into() {
+            // Standard package is currently always included, but this isn't enforced by the compiler.
+            Some(PackageKind::StandardLibrary)
+        } else if self.current_package_id == Some(package_id) {
+            // This package could be user code if current package is specified.
+            Some(PackageKind::UserCode)
+        } else {
+            // A direct dependency of the user code has an alias. Other packages are
+            // indirect dependencies, which user code can't access.
+            self.dependencies
+                .get(&package_id)
+                .map(|alias| PackageKind::AliasedPackage(alias.to_string()))
+        }
+    }
 }
 
 impl Lookup for Compilation {
@@ -169,23 +190,9 @@ pub fn generate_docs(
 
     for (package_id, unit) in &compilation.package_store {
         let is_current_package = compilation.current_package_id == Some(package_id);
-        let package_kind;
-        if package_id == PackageId::CORE {
-            // Core package is always included in the compilation.
-            package_kind = PackageKind::Core;
-        } else if package_id == 1.into() {
-            // Standard package is currently always included, but this isn't enforced by the compiler.
-            package_kind = PackageKind::StandardLibrary;
-        } else if is_current_package {
-            // This package could be user code if current package is specified.
-            package_kind = PackageKind::UserCode;
-        } else if let Some(alias) = compilation.dependencies.get(&package_id) {
-            // This is a direct dependency of the user code.
-            package_kind = PackageKind::AliasedPackage(alias.to_string());
-        } else {
-            // This is not a package user can access (an indirect dependency).
+        let Some(package_kind) = compilation.package_kind(package_id) else {
             continue;
-        }
+        };
 
         let package = &unit.package;
         for (_, item) in &package.items {
@@ -261,7 +268,7 @@ fn generate_doc_for_item<'a>(
     Some((ns.clone(), line))
 }
 
-fn get_namespace(package: &Package, item: &Item) -> Option<Rc<str>> {
+pub(crate) fn get_namespace(package: &Package, item: &Item) -> Option<Rc<str>> {
     match item.parent {
         Some(local_id) => {
             let parent = package
@@ -343,7 +350,7 @@ impl Metadata {
 }
 
 #[derive(PartialOrd, Ord, Eq, PartialEq, Clone)]
-enum PackageKind {
+pub(crate) enum PackageKind {
     UserCode,
     AliasedPackage(String),
     StandardLibrary,
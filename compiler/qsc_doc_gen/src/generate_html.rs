@@ -0,0 +1,584 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+//! Generates a self-contained static HTML documentation site: a page for each item, an index page
+//! for each namespace, a page for each source file, and a JSON index for client-side search.
+//! Types in signatures and fully qualified names in doc comments link to the pages of their items,
+//! across all the packages the site documents.
+
+mod markdown;
+#[cfg(test)]
+mod tests;
+
+use crate::display::{increase_header_level, parse_doc_for_summary, Lookup};
+use crate::generate_docs::{get_namespace, Compilation, PackageKind};
+use markdown::escape;
+use qsc_data_structures::language_features::LanguageFeatures;
+use qsc_data_structures::line_column::{Encoding, Position};
+use qsc_data_structures::target::TargetCapabilityFlags;
+use qsc_frontend::compile::{longest_common_prefix, Dependencies, PackageStore, Source, SourceMap};
+use qsc_hir::hir::{
+    CallableDecl, CallableKind, Item, ItemKind, LocalItemId, PackageId, Pat, PatKind, Res,
+    Visibility,
+};
+use qsc_hir::ty::{FunctorSet, FunctorSetValue, GenericParam, Ty, Udt, UdtDef, UdtDefKind};
+use rustc_hash::FxHashMap;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::rc::Rc;
+use std::sync::Arc;
+
+/// The files of a documentation site: the path of each file relative to the site root, and its contents.
+pub type SiteFiles = Vec<(Arc<str>, Arc<str>)>;
+
+const STYLE: &str = include_str!("generate_html/style.css");
+const SEARCH: &str = include_str!("generate_html/search.js");
+
+/// Generates a static HTML documentation site for the standard library
+/// and additional sources (if specified.)
+#[must_use]
+pub fn generate_html_docs(
+    additional_sources: Option<(PackageStore, &Dependencies, SourceMap)>,
+    capabilities: Option<TargetCapabilityFlags>,
+    language_features: Option<LanguageFeatures>,
+) -> SiteFiles {
+    let compilation = Compilation::new(additional_sources, capabilities, language_features);
+    Site::new(&compilation).render()
+}
+
+struct Site<'a> {
+    compilation: &'a Compilation,
+    /// The documented packages, user code first and the core library last.
+    packages: Vec<DocPackage<'a>>,
+    /// The page of each documented item.
+    pages: FxHashMap<(PackageId, LocalItemId), String>,
+    /// The page of each documented item by fully qualified name, for links in doc comments.
+    pages_by_name: FxHashMap<String, String>,
+}
+
+struct DocPackage<'a> {
+    id: PackageId,
+    kind: PackageKind,
+    /// The directory of the pages of the package.
+    dir: String,
+    /// Each source file of the package and the path of its page.
+    sources: Vec<(&'a Source, String)>,
+    /// The documented items of each namespace, sorted by name.
+    namespaces: BTreeMap<Rc<str>, Vec<DocItem<'a>>>,
+}
+
+struct DocItem<'a> {
+    item: &'a Item,
+    name: Rc<str>,
+    page: String,
+}
+
+impl<'a> Site<'a> {
+    fn new(compilation: &'a Compilation) -> Self {
+        let mut packages = Vec::new();
+        for (id, unit) in &compilation.package_store {
+            let Some(kind) = compilation.package_kind(id) else {
+                continue;
+            };
+            let dir = match &kind {
+                PackageKind::UserCode => "project".to_string(),
+                PackageKind::AliasedPackage(alias) => {
+                    format!("dependencies/{}", path_segment(alias))
+                }
+                PackageKind::StandardLibrary => "std".to_string(),
+                PackageKind::Core => "core".to_string(),
+            };
+            let sources = source_pages(&dir, &unit.sources);
+
+            let mut namespaces: BTreeMap<Rc<str>, Vec<DocItem>> = BTreeMap::new();
+            for (_, item) in &unit.package.items {
+                if kind != PackageKind::UserCode && item.visibility == Visibility::Internal {
+                    continue;
+                }
+                let Some(name) = item_name(item) else {
+                    continue;
+                };
+                let Some(namespace) = get_namespace(&unit.package, item) else {
+                    continue;
+                };
+                let page = format!("{dir}/{namespace}/{name}.html");
+                namespaces
+                    .entry(namespace)
+                    .or_default()
+                    .push(DocItem { item, name, page });
+            }
+            for items in namespaces.values_mut() {
+                items.sort_by(|a, b| a.name.cmp(&b.name));
+            }
+
+            packages.push(DocPackage {
+                id,
+                kind,
+                dir,
+                sources,
+                namespaces,
+            });
+        }
+        packages.sort_by(|a, b| a.kind.cmp(&b.kind));
+
+        // Names are claimed in package order, so that a name in doc comments refers
+        // to the item in user code over one with the same name in a dependency.
+        let mut pages = FxHashMap::default();
+        let mut pages_by_name = FxHashMap::default();
+        for package in &packages {
+            for (namespace, items) in &package.namespaces {
+                for item in items {
+                    pages.insert((package.id, item.item.id), item.page.clone());
+                    pages_by_name
+                        .entry(fully_qualified_name(package, namespace, &item.name))
+                        .or_insert_with(|| item.page.clone());
+                    pages_by_name
+                        .entry(format!("{namespace}.{}", item.name))
+                        .or_insert_with(|| item.page.clone());
+                }
+            }
+        }
+
+        Self {
+            compilation,
+            packages,
+            pages,
+            pages_by_name,
+        }
+    }
+
+    fn render(&self) -> SiteFiles {
+        let mut files = vec![
+            file("style.css", STYLE.to_string()),
+            file("search.js", SEARCH.to_string()),
+            file("index.html", self.index_page()),
+            file("search-index.json", self.search_index()),
+        ];
+        for package in &self.packages {
+            for (namespace, items) in &package.namespaces {
+                files.push(file(
+                    &format!("{}/{namespace}/index.html", package.dir),
+                    self.namespace_page(package, namespace, items),
+                ));
+                for item in items {
+                    files.push(file(&item.page, self.item_page(package, namespace, item)));
+                }
+            }
+            for (source, page) in &package.sources {
+                files.push(file(page, source_page(package, source, page)));
+            }
+        }
+        files
+    }
+
+    fn index_page(&self) -> String {
+        let mut body = String::from("<h1>Documentation</h1>\n");
+        for package in &self.packages {
+            let _ = writeln!(
+                body,
+                "<h2>{}</h2>\n<ul>",
+                escape(&package_title(&package.kind))
+            );
+            for namespace in package.namespaces.keys() {
+                let _ = writeln!(
+                    body,
+                    "<li><a href=\"{}/{namespace}/index.html\">{namespace}</a></li>",
+                    package.dir
+                );
+            }
+            body.push_str("</ul>\n");
+        }
+        page("Documentation", "", &body)
+    }
+
+    fn namespace_page(&self, package: &DocPackage, namespace: &str, items: &[DocItem]) -> String {
+        let root = root_of(&format!("{}/{namespace}/index.html", package.dir));
+        let mut body = format!(
+            "<nav class=\"breadcrumbs\"><a href=\"{root}index.html\">{}</a></nav>\n\
+             <h1>{namespace} namespace</h1>\n<table class=\"items\">\n",
+            escape(&package_title(&package.kind))
+        );
+        for item in items {
+            let summary = parse_doc_for_summary(&item.item.doc);
+            let _ = writeln!(
+                body,
+                "<tr><td><a href=\"{name}.html\">{name}</a></td><td class=\"kind\">{kind}</td><td>{summary}</td></tr>",
+                name = item.name,
+                kind = item_kind(item.item),
+                summary = markdown::inline_to_html(&summary, &self.linker(&root)),
+            );
+        }
+        body.push_str("</table>\n");
+        page(&format!("{namespace} namespace"), &root, &body)
+    }
+
+    fn item_page(&self, package: &DocPackage, namespace: &str, item: &DocItem) -> String {
+        let root = root_of(&item.page);
+        let link = self.linker(&root);
+        let fqn = fully_qualified_name(package, namespace, &item.name);
+        let mut body = format!(
+            "<nav class=\"breadcrumbs\"><a href=\"{root}index.html\">{}</a> / <a href=\"index.html\">{namespace}</a></nav>\n\
+             <h1>{} <span class=\"kind\">{}</span></h1>\n\
+             <p class=\"fqn\">Fully qualified name: <code>{fqn}</code></p>\n",
+            escape(&package_title(&package.kind)),
+            item.name,
+            item_kind(item.item),
+        );
+
+        let signature = Signature {
+            site: self,
+            package: package.id,
+            root: &root,
+        };
+        match &item.item.kind {
+            ItemKind::Callable(decl) => {
+                let _ = writeln!(
+                    body,
+                    "<pre class=\"signature\"><code>{}</code></pre>",
+                    signature.callable(decl)
+                );
+            }
+            ItemKind::Ty(_, udt) => {
+                let _ = writeln!(
+                    body,
+                    "<pre class=\"signature\"><code>{}</code></pre>",
+                    signature.udt(udt)
+                );
+            }
+            ItemKind::Export(_, item_id) => {
+                let (exported, _, exported_id) = self.compilation.resolve_item(package.id, item_id);
+                let name = item_name(exported).unwrap_or_default();
+                let exported = exported_id
+                    .package
+                    .and_then(|id| self.pages.get(&(id, exported_id.item)))
+                    .map_or_else(
+                        || escape(&name),
+                        |page| format!("<a href=\"{root}{page}\">{name}</a>"),
+                    );
+                let _ = writeln!(body, "<p>Exports {exported}.</p>");
+            }
+            ItemKind::Namespace(..) => {}
+        }
+
+        if let Some((line, source_page)) = declaration_line(package, item.item) {
+            let _ = writeln!(
+                body,
+                "<p class=\"source-link\"><a href=\"{root}{source_page}#L{line}\">Source</a></p>"
+            );
+        }
+        body.push_str(&markdown::to_html(
+            &increase_header_level(&item.item.doc),
+            &link,
+        ));
+        page(&fqn, &root, &body)
+    }
+
+    fn search_index(&self) -> String {
+        let mut entries = Vec::new();
+        for package in &self.packages {
+            for (namespace, items) in &package.namespaces {
+                for item in items {
+                    let summary = parse_doc_for_summary(&item.item.doc)
+                        .replace("\r\n", " ")
+                        .replace('\n', " ");
+                    entries.push(serde_json::json!({
+                        "name": item.name.as_ref(),
+                        "fqn": fully_qualified_name(package, namespace, &item.name),
+                        "kind": item_kind(item.item),
+                        "package": package_title(&package.kind),
+                        "summary": summary,
+                        "url": item.page,
+                    }));
+                }
+            }
+        }
+        serde_json::Value::Array(entries).to_string()
+    }
+
+    /// Returns a function that finds the URL of the page of a fully qualified item name,
+    /// relative to a page with the given root.
+    fn linker<'b>(&'b self, root: &'b str) -> impl Fn(&str) -> Option<String> + 'b {
+        move |name| {
+            self.pages_by_name
+                .get(name)
+                .map(|page| format!("{root}{page}"))
+        }
+    }
+}
+
+/// Renders signatures as HTML, linking each user-defined type to the page of its item.
+struct Signature<'a> {
+    site: &'a Site<'a>,
+    /// The package the signature is declared in, which item references are relative to.
+    package: PackageId,
+    root: &'a str,
+}
+
+impl Signature<'_> {
+    fn callable(&self, decl: &CallableDecl) -> String {
+        let kind = match decl.kind {
+            CallableKind::Function => "function",
+            CallableKind::Operation => "operation",
+        };
+        let type_params = decl
+            .generics
+            .iter()
+            .filter_map(|generic| match generic {
+                GenericParam::Ty(name) => Some(name.name.to_string()),
+                GenericParam::Functor(_) => None,
+            })
+            .collect::<Vec<_>>();
+        let type_params = if type_params.is_empty() {
+            String::new()
+        } else {
+            escape(&format!("<{}>", type_params.join(", ")))
+        };
+        let input = self.pat(&decl.input);
+        let input = if matches!(decl.input.kind, PatKind::Tuple(_)) {
+            input
+        } else {
+            format!("({input})")
+        };
+        format!(
+            "{kind} {}{type_params}{input} : {}{}",
+            decl.name.name,
+            self.ty(&decl.output),
+            functors(decl.functors)
+        )
+    }
+
+    fn pat(&self, pat: &Pat) -> String {
+        match &pat.kind {
+            PatKind::Bind(name) => format!("{} : {}", name.name, self.ty(&pat.ty)),
+            PatKind::Discard => format!("_ : {}", self.ty(&pat.ty)),
+            PatKind::Tuple(items) => format!(
+                "({})",
+                items
+                    .iter()
+                    .map(|item| self.pat(item))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            PatKind::Err => "?".to_string(),
+        }
+    }
+
+    fn udt(&self, udt: &Udt) -> String {
+        match &udt.definition.kind {
+            UdtDefKind::Tuple(fields) if udt.is_struct() => format!(
+                "struct {} {{ {} }}",
+                udt.name,
+                fields
+                    .iter()
+                    .map(|field| self.udt_def(field))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            _ => format!("newtype {} = {}", udt.name, self.udt_def(&udt.definition)),
+        }
+    }
+
+    fn udt_def(&self, def: &UdtDef) -> String {
+        match &def.kind {
+            UdtDefKind::Field(field) => match &field.name {
+                Some(name) => format!("{name} : {}", self.ty(&field.ty)),
+                None => self.ty(&field.ty),
+            },
+            UdtDefKind::Tuple(defs) => {
+                tuple(&defs.iter().map(|def| self.udt_def(def)).collect::<Vec<_>>())
+            }
+        }
+    }
+
+    fn ty(&self, ty: &Ty) -> String {
+        match ty {
+            Ty::Array(item) => format!("{}[]", self.ty(item)),
+            Ty::Arrow(arrow) => {
+                let symbol = match arrow.kind {
+                    CallableKind::Function => "-&gt;",
+                    CallableKind::Operation => "=&gt;",
+                };
+                let functors = match arrow.functors {
+                    FunctorSet::Value(value) | FunctorSet::Param(_, value) => functors(value),
+                    FunctorSet::Infer(_) => format!(" is {}", arrow.functors),
+                };
+                format!(
+                    "({} {symbol} {}{functors})",
+                    self.ty(&arrow.input),
+                    self.ty(&arrow.output)
+                )
+            }
+            Ty::Udt(name, Res::Item(item_id)) => {
+                let package = item_id.package.unwrap_or(self.package);
+                match self.site.pages.get(&(package, item_id.item)) {
+                    Some(page) => format!("<a href=\"{}{page}\">{name}</a>", self.root),
+                    None => name.to_string(),
+                }
+            }
+            Ty::Tuple(items) => tuple(&items.iter().map(|item| self.ty(item)).collect::<Vec<_>>()),
+            _ => escape(&ty.display()),
+        }
+    }
+}
+
+fn functors(functors: FunctorSetValue) -> String {
+    match functors {
+        FunctorSetValue::Empty => String::new(),
+        _ => format!(" is {functors}"),
+    }
+}
+
+fn tuple(items: &[String]) -> String {
+    match items {
+        [] => "Unit".to_string(),
+        [item] => format!("({item},)"),
+        _ => format!("({})", items.join(", ")),
+    }
+}
+
+/// Returns the name user code refers to an item by, which for a dependency starts with its alias.
+fn fully_qualified_name(package: &DocPackage, namespace: &str, name: &str) -> String {
+    match &package.kind {
+        PackageKind::AliasedPackage(alias) => format!("{alias}.{namespace}.{name}"),
+        _ => format!("{namespace}.{name}"),
+    }
+}
+
+fn item_name(item: &Item) -> Option<Rc<str>> {
+    match &item.kind {
+        ItemKind::Callable(decl) => Some(decl.name.name.clone()),
+        ItemKind::Ty(ident, _) => Some(ident.name.clone()),
+        ItemKind::Export(name, _) => Some(name.name.clone()),
+        ItemKind::Namespace(..) => None,
+    }
+}
+
+fn item_kind(item: &Item) -> &'static str {
+    match &item.kind {
+        ItemKind::Callable(decl) => match decl.kind {
+            CallableKind::Function => "function",
+            CallableKind::Operation => "operation",
+        },
+        ItemKind::Ty(..) => "user-defined type",
+        ItemKind::Export(..) => "export",
+        ItemKind::Namespace(..) => "namespace",
+    }
+}
+
+fn package_title(kind: &PackageKind) -> String {
+    match kind {
+        PackageKind::UserCode => "Project".to_string(),
+        PackageKind::AliasedPackage(alias) => alias.clone(),
+        PackageKind::StandardLibrary => "Standard library".to_string(),
+        PackageKind::Core => "Core library".to_string(),
+    }
+}
+
+/// Returns the one-based line of the declaration of an item, and the page of its source file.
+fn declaration_line<'a>(package: &'a DocPackage, item: &Item) -> Option<(u32, &'a str)> {
+    let offset = match &item.kind {
+        ItemKind::Callable(decl) => decl.name.span.lo,
+        ItemKind::Ty(ident, _) => ident.span.lo,
+        ItemKind::Export(name, _) => name.span.lo,
+        ItemKind::Namespace(..) => return None,
+    };
+    let (source, page) = package
+        .sources
+        .iter()
+        .rev()
+        .find(|(source, _)| source.offset <= offset)?;
+    let position =
+        Position::from_utf8_byte_offset(Encoding::Utf8, &source.contents, offset - source.offset);
+    Some((position.line + 1, page))
+}
+
+/// Returns each source file of a package and the path of its page. Pages are placed under
+/// `src/`, at the path of the source relative to the directory common to all of the sources.
+fn source_pages<'a>(dir: &str, sources: &'a SourceMap) -> Vec<(&'a Source, String)> {
+    let names = sources
+        .iter()
+        .map(|source| source.name.as_ref())
+        .collect::<Vec<_>>();
+    let prefix = longest_common_prefix(&names).len();
+    sources
+        .iter()
+        .map(|source| {
+            let path = source.name[prefix..]
+                .split(['/', '\\'])
+                .filter(|segment| !matches!(*segment, "" | "." | ".."))
+                .map(path_segment)
+                .collect::<Vec<_>>()
+                .join("/");
+            (source, format!("src/{dir}/{path}.html"))
+        })
+        .collect()
+}
+
+fn source_page(package: &DocPackage, source: &Source, page_path: &str) -> String {
+    let root = root_of(page_path);
+    let name = &source.name;
+    let mut body = format!(
+        "<nav class=\"breadcrumbs\"><a href=\"{root}index.html\">{}</a></nav>\n<h1>{}</h1>\n<pre class=\"source\"><code>",
+        escape(&package_title(&package.kind)),
+        escape(name),
+    );
+    for (index, line) in source.contents.lines().enumerate() {
+        let number = index + 1;
+        let _ = writeln!(
+            body,
+            "<span id=\"L{number}\"><a class=\"line-number\" href=\"#L{number}\">{number}</a>{}</span>",
+            escape(line)
+        );
+    }
+    body.push_str("</code></pre>\n");
+    page(name, &root, &body)
+}
+
+/// Replaces the characters of a path segment that aren't safe in URLs.
+fn path_segment(segment: &str) -> String {
+    segment
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+/// Returns the relative path from the directory of a page to the site root.
+fn root_of(page_path: &str) -> String {
+    "../".repeat(page_path.matches('/').count())
+}
+
+fn file(path: &str, contents: String) -> (Arc<str>, Arc<str>) {
+    (path.into(), contents.into())
+}
+
+fn page(title: &str, root: &str, body: &str) -> String {
+    let title = escape(title);
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<meta name="viewport" content="width=device-width, initial-scale=1">
+<title>{title}</title>
+<link rel="stylesheet" href="{root}style.css">
+</head>
+<body data-root="{root}">
+<header>
+<a class="home" href="{root}index.html">Q# documentation</a>
+<input id="search" type="search" placeholder="Search" autocomplete="off">
+<ul id="search-results"></ul>
+</header>
+<main>
+{body}</main>
+<script src="{root}search.js"></script>
+</body>
+</html>
+"#
+    )
+}
@@ -0,0 +1,243 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+//! A small Markdown renderer for doc comments. It covers what Q# doc comments use:
+//! headings, paragraphs, lists, fenced code blocks, code spans, links and emphasis.
+//! Fully qualified item names, in text or in code spans, are linked to their pages.
+
+use std::fmt::Write;
+
+/// Renders Markdown to HTML. `link` returns the URL of the page for a fully qualified item name.
+pub(super) fn to_html(markdown: &str, link: &dyn Fn(&str) -> Option<String>) -> String {
+    let mut blocks = Blocks {
+        html: String::new(),
+        paragraph: Vec::new(),
+        list: None,
+        link,
+    };
+    let mut after_blank = false;
+    let mut lines = markdown.lines();
+    while let Some(line) = lines.next() {
+        let trimmed = line.trim();
+        if let Some(info) = trimmed.strip_prefix("```") {
+            blocks.flush();
+            let code = lines
+                .by_ref()
+                .take_while(|code| !code.trim().starts_with("```"))
+                .collect::<Vec<_>>();
+            blocks.code(info, &code);
+        } else if let Some((level, text)) = heading(trimmed) {
+            blocks.flush();
+            let text = inline_to_html(text, link);
+            let _ = writeln!(blocks.html, "<h{level}>{text}</h{level}>");
+        } else if let Some((tag, text)) = list_item(trimmed) {
+            blocks.flush_paragraph();
+            match &mut blocks.list {
+                Some((open, items)) if *open == tag => items.push(vec![text]),
+                _ => {
+                    blocks.flush_list();
+                    blocks.list = Some((tag, vec![vec![text]]));
+                }
+            }
+        } else if trimmed.is_empty() {
+            // Lists continue past blank lines, so that loosely spaced items stay one list.
+            blocks.flush_paragraph();
+            after_blank = true;
+            continue;
+        } else {
+            let is_continuation = !after_blank || line.starts_with(char::is_whitespace);
+            match &mut blocks.list {
+                Some((_, items)) if is_continuation => items
+                    .last_mut()
+                    .expect("list should have an item")
+                    .push(trimmed),
+                _ => {
+                    blocks.flush_list();
+                    blocks.paragraph.push(trimmed);
+                }
+            }
+        }
+        after_blank = false;
+    }
+    blocks.flush();
+    blocks.html
+}
+
+/// The rendered HTML of a Markdown document, and the paragraph or list being read.
+struct Blocks<'a> {
+    html: String,
+    paragraph: Vec<&'a str>,
+    /// The tag of the open list, and the lines of each of its items.
+    list: Option<(&'static str, Vec<Vec<&'a str>>)>,
+    link: &'a dyn Fn(&str) -> Option<String>,
+}
+
+impl Blocks<'_> {
+    fn flush(&mut self) {
+        self.flush_paragraph();
+        self.flush_list();
+    }
+
+    fn flush_paragraph(&mut self) {
+        if !self.paragraph.is_empty() {
+            let text = inline_to_html(&self.paragraph.join("\n"), self.link);
+            let _ = writeln!(self.html, "<p>{text}</p>");
+            self.paragraph.clear();
+        }
+    }
+
+    fn flush_list(&mut self) {
+        if let Some((tag, items)) = self.list.take() {
+            let _ = writeln!(self.html, "<{tag}>");
+            for item in items {
+                let text = inline_to_html(&item.join("\n"), self.link);
+                let _ = writeln!(self.html, "<li>{text}</li>");
+            }
+            let _ = writeln!(self.html, "</{tag}>");
+        }
+    }
+
+    fn code(&mut self, info: &str, code: &[&str]) {
+        let language = info.split([',', ' ']).next().unwrap_or_default();
+        if language.is_empty() {
+            self.html += "<pre><code>";
+        } else {
+            let _ = write!(
+                self.html,
+                "<pre><code class=\"language-{}\">",
+                escape(language)
+            );
+        }
+        for line in code {
+            self.html += &escape(line);
+            self.html.push('\n');
+        }
+        self.html += "</code></pre>\n";
+    }
+}
+
+fn heading(line: &str) -> Option<(usize, &str)> {
+    let level = line.chars().take_while(|c| *c == '#').count();
+    let text = line[level..].strip_prefix(' ')?;
+    (1..=6).contains(&level).then_some((level, text.trim()))
+}
+
+/// Returns the list tag and the text of a list item line.
+fn list_item(line: &str) -> Option<(&'static str, &str)> {
+    if let Some(text) = line.strip_prefix("- ").or_else(|| line.strip_prefix("* ")) {
+        return Some(("ul", text));
+    }
+    let digits = line.chars().take_while(char::is_ascii_digit).count();
+    let text = line[digits..].strip_prefix(". ")?;
+    (digits > 0).then_some(("ol", text))
+}
+
+/// Renders the inline Markdown of a single block to HTML.
+pub(super) fn inline_to_html(text: &str, link: &dyn Fn(&str) -> Option<String>) -> String {
+    let mut html = String::new();
+    let mut rest = text;
+    let mut emphasis = [false, false];
+    while !rest.is_empty() {
+        if let Some(after) = rest.strip_prefix('`') {
+            if let Some(end) = after.find('`') {
+                let code = &after[..end];
+                let code_html = format!("<code>{}</code>", escape(code));
+                match link(code) {
+                    Some(url) => {
+                        let _ = write!(html, "<a href=\"{}\">{code_html}</a>", escape(&url));
+                    }
+                    None => html.push_str(&code_html),
+                }
+                rest = &after[end + 1..];
+                continue;
+            }
+        } else if let Some(after) = rest.strip_prefix('[') {
+            if let Some((label, url, remaining)) = inline_link(after) {
+                let _ = write!(
+                    html,
+                    "<a href=\"{}\">{}</a>",
+                    escape(url),
+                    inline_to_html(label, link)
+                );
+                rest = remaining;
+                continue;
+            }
+        } else if let Some(after) = rest.strip_prefix("**") {
+            html.push_str(if emphasis[0] { "</strong>" } else { "<strong>" });
+            emphasis[0] = !emphasis[0];
+            rest = after;
+            continue;
+        } else if let Some(after) = rest.strip_prefix('*') {
+            if emphasis[1] || after.starts_with(|c: char| !c.is_whitespace()) {
+                html.push_str(if emphasis[1] { "</em>" } else { "<em>" });
+                emphasis[1] = !emphasis[1];
+                rest = after;
+                continue;
+            }
+        } else if rest.starts_with(|c: char| c.is_ascii_alphabetic()) {
+            let len = rest
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_' || c == '.'))
+                .unwrap_or(rest.len());
+            let word = rest[..len].trim_end_matches('.');
+            match link(word) {
+                Some(url) => {
+                    let _ = write!(html, "<a href=\"{}\">{}</a>", escape(&url), escape(word));
+                }
+                None => html.push_str(&escape(word)),
+            }
+            rest = &rest[word.len()..];
+            continue;
+        }
+        let c = rest.chars().next().expect("text should not be empty");
+        html.push_str(&escape(&c.to_string()));
+        rest = &rest[c.len_utf8()..];
+    }
+    // Close any emphasis left open by unbalanced markers.
+    if emphasis[1] {
+        html.push_str("</em>");
+    }
+    if emphasis[0] {
+        html.push_str("</strong>");
+    }
+    html
+}
+
+/// Parses the rest of an inline link after its `[`, returning the label, the URL and
+/// the text after the link.
+fn inline_link(text: &str) -> Option<(&str, &str, &str)> {
+    let label_end = text.find("](")?;
+    let after_label = &text[label_end + 2..];
+    // URLs can contain balanced parentheses, as in Wikipedia links.
+    let mut depth = 0;
+    for (index, c) in after_label.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' if depth == 0 => {
+                return Some((
+                    &text[..label_end],
+                    &after_label[..index],
+                    &after_label[index + 1..],
+                ));
+            }
+            ')' => depth -= 1,
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Escapes text for use in HTML content and attribute values.
+pub(super) fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
@@ -0,0 +1,55 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+// Client-side search over the items listed in search-index.json.
+(function () {
+  const root = document.body.dataset.root;
+  const input = document.getElementById("search");
+  const results = document.getElementById("search-results");
+  let index = null;
+
+  async function loadIndex() {
+    if (index === null) {
+      const response = await fetch(root + "search-index.json");
+      index = await response.json();
+    }
+    return index;
+  }
+
+  // Exact name matches come first, then names starting with the query, then the rest.
+  function rank(item, query) {
+    const name = item.name.toLowerCase();
+    if (name === query) return 0;
+    if (name.startsWith(query)) return 1;
+    return 2;
+  }
+
+  input.addEventListener("input", async () => {
+    const query = input.value.trim().toLowerCase();
+    const items = query === "" ? [] : await loadIndex();
+    if (input.value.trim().toLowerCase() !== query) {
+      // A newer search has started.
+      return;
+    }
+    const matches = items
+      .filter((item) => item.fqn.toLowerCase().includes(query))
+      .sort((a, b) => rank(a, query) - rank(b, query) || a.fqn.localeCompare(b.fqn))
+      .slice(0, 20);
+    results.replaceChildren(
+      ...matches.map((item) => {
+        const link = document.createElement("a");
+        link.href = root + item.url;
+        link.textContent = item.fqn;
+        const kind = document.createElement("span");
+        kind.className = "kind";
+        kind.textContent = " " + item.kind;
+        const summary = document.createElement("span");
+        summary.className = "summary";
+        summary.textContent = item.summary;
+        const result = document.createElement("li");
+        result.append(link, kind, summary);
+        return result;
+      }),
+    );
+  });
+})();
@@ -0,0 +1,246 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+#![allow(clippy::needless_raw_string_hashes)]
+
+use super::{generate_html_docs, markdown};
+use expect_test::expect;
+use qsc_data_structures::{language_features::LanguageFeatures, target::TargetCapabilityFlags};
+use qsc_frontend::compile::{self, compile, PackageStore, SourceMap};
+use rustc_hash::FxHashMap;
+use std::sync::Arc;
+
+const PROJECT: &str = r#"namespace Geometry {
+    /// # Summary
+    /// A point in the plane.
+    newtype Point = (X : Double, Y : Double);
+
+    /// # Summary
+    /// Moves a point by a complex offset.
+    ///
+    /// # See Also
+    /// - Geometry.Origin
+    /// - Microsoft.Quantum.Math.Complex
+    function Shift(point : Point, offset : Microsoft.Quantum.Math.Complex) : Point {
+        Point(point.X + offset.Real, point.Y + offset.Imag)
+    }
+
+    /// # Summary
+    /// The origin, as a `Geometry.Point`.
+    function Origin() : Point {
+        Point(0.0, 0.0)
+    }
+
+    /// # Summary
+    /// Applies an operation to each shape of a register.
+    operation ApplyToShapes<'T>(op : (Qubit => Unit is Adj), shapes : Shapes.Shape[]) : Unit {}
+}"#;
+
+const DEPENDENCY: &str = r#"namespace Shapes {
+    /// # Summary
+    /// A shape with the given number of sides.
+    newtype Shape = (Sides : Int);
+    export Shape;
+}"#;
+
+/// Generates the site for a project with a dependency that has the alias `Shapes`.
+fn site() -> FxHashMap<String, String> {
+    let mut store = PackageStore::new(compile::core());
+    let std_id = store.insert(compile::std(&store, TargetCapabilityFlags::all()));
+    let dependency = compile(
+        &store,
+        &[(std_id, None)],
+        SourceMap::new([("shapes/src/Shapes.qs".into(), DEPENDENCY.into())], None),
+        TargetCapabilityFlags::all(),
+        LanguageFeatures::default(),
+    );
+    assert!(dependency.errors.is_empty(), "{:?}", dependency.errors);
+    let dependency_id = store.insert(dependency);
+
+    // The alias puts the dependency's namespace under `Shapes`.
+    let project = PROJECT.replace("Shapes.Shape[]", "Shapes.Shapes.Shape[]");
+    let dependencies = [(std_id, None), (dependency_id, Some(Arc::from("Shapes")))];
+    generate_html_docs(
+        Some((
+            store,
+            &dependencies,
+            SourceMap::new(
+                [("/home/me/project/src/Geometry.qs".into(), project.into())],
+                None,
+            ),
+        )),
+        None,
+        None,
+    )
+    .into_iter()
+    .map(|(path, contents)| (path.to_string(), contents.to_string()))
+    .collect()
+}
+
+fn lines_with(contents: &str, pattern: &str) -> String {
+    contents
+        .lines()
+        .filter(|line| line.contains(pattern))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[test]
+fn signature_types_link_to_their_pages() {
+    let site = site();
+    let signatures = ["Shift", "Origin", "ApplyToShapes"]
+        .iter()
+        .map(|name| lines_with(&site[&format!("project/Geometry/{name}.html")], "signature"))
+        .collect::<Vec<_>>()
+        .join("\n");
+    expect![[r#"
+        <pre class="signature"><code>function Shift(point : <a href="../../project/Geometry/Point.html">Point</a>, offset : <a href="../../std/Microsoft.Quantum.Math/Complex.html">Complex</a>) : <a href="../../project/Geometry/Point.html">Point</a></code></pre>
+        <pre class="signature"><code>function Origin() : <a href="../../project/Geometry/Point.html">Point</a></code></pre>
+        <pre class="signature"><code>operation ApplyToShapes&lt;&#39;T&gt;(op : (Qubit =&gt; Unit is Adj), shapes : <a href="../../dependencies/Shapes/Shapes/Shape.html">Shape</a>[]) : Unit</code></pre>"#]]
+    .assert_eq(&signatures);
+}
+
+#[test]
+fn udt_signatures_link_field_types() {
+    let site = site();
+    expect![[r#"<pre class="signature"><code>struct Complex { Real : Double, Imag : Double }</code></pre>"#]]
+        .assert_eq(&lines_with(
+            &site["std/Microsoft.Quantum.Math/Complex.html"],
+            "signature",
+        ));
+    expect![[
+        r#"<pre class="signature"><code>struct Point { X : Double, Y : Double }</code></pre>"#
+    ]]
+    .assert_eq(&lines_with(
+        &site["project/Geometry/Point.html"],
+        "signature",
+    ));
+}
+
+#[test]
+fn doc_comments_link_fully_qualified_names() {
+    let site = site();
+    let shift = &site["project/Geometry/Shift.html"];
+    expect![[r#"
+        <h2>Summary</h2>
+        <p>Moves a point by a complex offset.</p>
+        <h2>See Also</h2>
+        <ul>
+        <li><a href="../../project/Geometry/Origin.html">Geometry.Origin</a></li>
+        <li><a href="../../std/Microsoft.Quantum.Math/Complex.html">Microsoft.Quantum.Math.Complex</a></li>
+        </ul>"#]]
+    .assert_eq(shift[shift.find("<h2>").expect("page should have headings")..shift.find("</main>").expect("page should have a main element")].trim_end());
+}
+
+#[test]
+fn namespace_pages_list_items_with_summaries() {
+    let site = site();
+    expect![[r#"
+        <tr><td><a href="ApplyToShapes.html">ApplyToShapes</a></td><td class="kind">operation</td><td>Applies an operation to each shape of a register.</td></tr>
+        <tr><td><a href="Origin.html">Origin</a></td><td class="kind">function</td><td>The origin, as a <a href="../../project/Geometry/Point.html"><code>Geometry.Point</code></a>.</td></tr>
+        <tr><td><a href="Point.html">Point</a></td><td class="kind">user-defined type</td><td>A point in the plane.</td></tr>
+        <tr><td><a href="Shift.html">Shift</a></td><td class="kind">function</td><td>Moves a point by a complex offset.</td></tr>"#]]
+    .assert_eq(&lines_with(&site["project/Geometry/index.html"], "<tr>"));
+}
+
+#[test]
+fn index_page_lists_namespaces_of_each_package() {
+    let site = site();
+    let index = &site["index.html"];
+    expect![[r#"
+        <h2>Project</h2>
+        <h2>Shapes</h2>
+        <h2>Standard library</h2>
+        <h2>Core library</h2>"#]]
+    .assert_eq(&lines_with(index, "<h2>"));
+    expect![[r#"
+        <li><a href="project/Geometry/index.html">Geometry</a></li>
+        <li><a href="dependencies/Shapes/Shapes/index.html">Shapes</a></li>"#]]
+    .assert_eq(&format!(
+        "{}\n{}",
+        lines_with(index, "index.html\">Geometry<"),
+        lines_with(index, "index.html\">Shapes<")
+    ));
+}
+
+#[test]
+fn items_link_to_their_source_lines() {
+    let site = site();
+    expect![[
+        r#"<p class="source-link"><a href="../../src/project/Geometry.qs.html#L12">Source</a></p>"#
+    ]]
+    .assert_eq(&lines_with(
+        &site["project/Geometry/Shift.html"],
+        "source-link",
+    ));
+    let source = &site["src/project/Geometry.qs.html"];
+    expect![[r##"<span id="L12"><a class="line-number" href="#L12">12</a>    function Shift(point : Point, offset : Microsoft.Quantum.Math.Complex) : Point {</span>"##]]
+        .assert_eq(&lines_with(source, "id=\"L12\""));
+    assert!(site.contains_key("src/dependencies/Shapes/Shapes.qs.html"));
+}
+
+#[test]
+fn search_index_has_an_entry_for_each_item() {
+    let site = site();
+    let index: serde_json::Value =
+        serde_json::from_str(&site["search-index.json"]).expect("search index should be JSON");
+    let entries = index.as_array().expect("search index should be an array");
+    let project_entries = entries
+        .iter()
+        .filter(|entry| entry["package"] == "Project" || entry["package"] == "Shapes")
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join("\n");
+    expect![[r#"
+        {"fqn":"Geometry.ApplyToShapes","kind":"operation","name":"ApplyToShapes","package":"Project","summary":"Applies an operation to each shape of a register.","url":"project/Geometry/ApplyToShapes.html"}
+        {"fqn":"Geometry.Origin","kind":"function","name":"Origin","package":"Project","summary":"The origin, as a `Geometry.Point`.","url":"project/Geometry/Origin.html"}
+        {"fqn":"Geometry.Point","kind":"user-defined type","name":"Point","package":"Project","summary":"A point in the plane.","url":"project/Geometry/Point.html"}
+        {"fqn":"Geometry.Shift","kind":"function","name":"Shift","package":"Project","summary":"Moves a point by a complex offset.","url":"project/Geometry/Shift.html"}
+        {"fqn":"Shapes.Shapes.Shape","kind":"user-defined type","name":"Shape","package":"Shapes","summary":"A shape with the given number of sides.","url":"dependencies/Shapes/Shapes/Shape.html"}"#]]
+    .assert_eq(&project_entries);
+    assert!(entries
+        .iter()
+        .any(|entry| entry["fqn"] == "Microsoft.Quantum.Core.Length"));
+}
+
+#[test]
+fn markdown_blocks() {
+    let html = markdown::to_html(
+        r#"# Summary
+Some *emphasis*, **strong** text
+and `code` & <tags>.
+
+1. [First](https://example.com/a_(b))
+
+2. Second item,
+   continued.
+
+Paragraph after the list.
+```qsharp
+let x = a < b;
+```"#,
+        &|_| None,
+    );
+    expect![[r#"
+        <h1>Summary</h1>
+        <p>Some <em>emphasis</em>, <strong>strong</strong> text
+        and <code>code</code> &amp; &lt;tags&gt;.</p>
+        <ol>
+        <li><a href="https://example.com/a_(b)">First</a></li>
+        <li>Second item,
+        continued.</li>
+        </ol>
+        <p>Paragraph after the list.</p>
+        <pre><code class="language-qsharp">let x = a &lt; b;
+        </code></pre>
+    "#]]
+    .assert_eq(&html);
+}
+
+#[test]
+fn markdown_leaves_lone_asterisks_alone() {
+    let html = markdown::inline_to_html("2 * 3 = 6 and a.b.", &|name| {
+        (name == "a.b").then(|| "a/b.html".to_string())
+    });
+    expect![[r#"2 * 3 = 6 and <a href="a/b.html">a.b</a>."#]].assert_eq(&html);
+}
@@ -2,4 +2,6 @@
 // Licensed under the MIT License.
 
 pub mod display;
+pub mod doctests;
 pub mod generate_docs;
+pub mod generate_html;
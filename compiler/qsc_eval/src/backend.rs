@@ -5,6 +5,7 @@ use num_bigint::BigUint;
 use num_complex::Complex;
 use quantum_sparse_sim::QuantumSim;
 use rand::RngCore;
+use std::any::Any;
 
 use crate::val::Value;
 
@@ -97,6 +98,17 @@ pub trait Backend {
     }
 
     fn set_seed(&mut self, _seed: Option<u64>) {}
+
+    /// Captures the current state of the backend so that it can be restored later.
+    /// Returns `None` if the backend doesn't support checkpoints.
+    fn checkpoint(&self) -> Option<Box<dyn Any>> {
+        None
+    }
+
+    /// Restores a state captured by [`Backend::checkpoint`].
+    fn restore(&mut self, _checkpoint: &dyn Any) {
+        unimplemented!("restore operation");
+    }
 }
 
 /// Default backend used when targeting sparse simulation.
@@ -301,6 +313,17 @@ impl Backend for SparseSim {
             None => self.sim.set_rng_seed(rand::thread_rng().next_u64()),
         }
     }
+
+    fn checkpoint(&self) -> Option<Box<dyn Any>> {
+        Some(Box::new(self.sim.clone()))
+    }
+
+    fn restore(&mut self, checkpoint: &dyn Any) {
+        self.sim = checkpoint
+            .downcast_ref::<QuantumSim>()
+            .expect("checkpoint should be a simulator state")
+            .clone();
+    }
 }
 
 /// Simple struct that chains two backends together so that the chained
@@ -479,4 +502,19 @@ where
         self.chained.set_seed(seed);
         self.main.set_seed(seed);
     }
+
+    fn checkpoint(&self) -> Option<Box<dyn Any>> {
+        // Both backends must support checkpoints for the chain to support them.
+        let checkpoint: (Box<dyn Any>, Box<dyn Any>) =
+            (self.main.checkpoint()?, self.chained.checkpoint()?);
+        Some(Box::new(checkpoint))
+    }
+
+    fn restore(&mut self, checkpoint: &dyn Any) {
+        let (main, chained) = checkpoint
+            .downcast_ref::<(Box<dyn Any>, Box<dyn Any>)>()
+            .expect("checkpoint should be a chain state");
+        self.main.restore(main.as_ref());
+        self.chained.restore(chained.as_ref());
+    }
 }
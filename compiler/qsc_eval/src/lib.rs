@@ -264,6 +264,43 @@ pub fn eval(
     Ok(value)
 }
 
+/// Calls the given callable value with the given argument, evaluating it to completion.
+/// # Errors
+/// Returns the first error encountered during execution.
+/// # Panics
+/// On internal error where no result is returned.
+#[allow(clippy::too_many_arguments)]
+pub fn invoke(
+    package: PackageId,
+    seed: Option<u64>,
+    globals: &impl PackageStoreLookup,
+    env: &mut Env,
+    sim: &mut impl Backend<ResultType = impl Into<val::Result>>,
+    receiver: &mut impl Receiver,
+    callable: Value,
+    arg: Value,
+) -> Result<Value, (Error, Vec<Frame>)> {
+    let mut state = State::new(package, Vec::new().into(), seed);
+    state.set_val_register(callable);
+    state.push_val();
+    state.set_val_register(arg);
+    state
+        .eval_call(
+            env,
+            sim,
+            globals,
+            Span::default(),
+            Span::default(),
+            receiver,
+        )
+        .map_err(|e| (e, state.get_stack_frames()))?;
+    let res = state.eval(globals, env, sim, receiver, &[], StepAction::Continue)?;
+    let StepResult::Return(value) = res else {
+        panic!("invoke should always return a value");
+    };
+    Ok(value)
+}
+
 /// The type of step action to take during evaluation
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 pub enum StepAction {
@@ -309,6 +346,7 @@ pub struct Variable {
 
 #[derive(Debug, Clone)]
 pub struct VariableInfo {
+    pub id: LocalVarId,
     pub value: Value,
     pub name: Rc<str>,
     pub type_name: String,
@@ -345,6 +383,7 @@ impl Range {
     }
 }
 
+#[derive(Clone)]
 pub struct Env(Vec<Scope>);
 
 impl Default for Env {
@@ -429,7 +468,8 @@ impl Env {
             .into_iter()
             .map(|bindings| {
                 bindings
-                    .map(|(_, var)| VariableInfo {
+                    .map(|(id, var)| VariableInfo {
+                        id,
                         name: var.name.clone(),
                         type_name: var.value.type_name().to_string(),
                         value: var.value.clone(),
@@ -453,9 +493,32 @@ impl Env {
             .expect("local variable is not present");
         variable.value = value;
     }
+
+    /// Updates the innermost binding of the variable in the given frame, which need not be
+    /// the top frame. Returns `false` if the frame has no such variable.
+    pub fn update_variable_in_frame(
+        &mut self,
+        frame_id: usize,
+        local_var_id: LocalVarId,
+        value: Value,
+    ) -> bool {
+        match self
+            .0
+            .iter_mut()
+            .rev()
+            .filter(|scope| scope.frame_id == frame_id)
+            .find_map(|scope| scope.bindings.get_mut(local_var_id))
+        {
+            Some(variable) => {
+                variable.value = value;
+                true
+            }
+            None => false,
+        }
+    }
 }
 
-#[derive(Default)]
+#[derive(Clone, Default)]
 struct Scope {
     bindings: IndexMap<LocalVarId, Variable>,
     frame_id: usize,
@@ -463,6 +526,7 @@ struct Scope {
 
 type CallableCountKey = (StoreItemId, bool, bool);
 
+#[derive(Clone)]
 pub struct State {
     exec_graph_stack: Vec<ExecGraph>,
     idx: u32,
@@ -585,6 +649,27 @@ impl State {
         step: StepAction,
     ) -> Result<StepResult, (Error, Vec<Frame>)> {
         let current_frame = self.call_stack.len();
+        self.resume(globals, env, sim, out, breakpoints, step, current_frame)
+    }
+
+    /// Continues evaluation like [`State::eval`], except that `Next` and `Out` steps are
+    /// relative to the given call depth rather than the current one. This allows a step
+    /// that was interrupted by a breakpoint to be continued from where it left off.
+    /// # Errors
+    /// Returns the first error encountered during execution.
+    /// # Panics
+    /// When returning a value in the middle of execution.
+    #[allow(clippy::too_many_arguments)]
+    pub fn resume(
+        &mut self,
+        globals: &impl PackageStoreLookup,
+        env: &mut Env,
+        sim: &mut impl Backend<ResultType = impl Into<val::Result>>,
+        out: &mut impl Receiver,
+        breakpoints: &[StmtId],
+        step: StepAction,
+        current_frame: usize,
+    ) -> Result<StepResult, (Error, Vec<Frame>)> {
         while !self.exec_graph_stack.is_empty() {
             let exec_graph = self
                 .exec_graph_stack
@@ -691,29 +776,39 @@ impl State {
         step: StepAction,
         current_frame: usize,
     ) -> Option<StepResult> {
-        Some(
-            if let Some(bp) = breakpoints
-                .iter()
-                .find(|&bp| *bp == stmt && self.package == self.source_package)
-            {
-                StepResult::BreakpointHit(*bp)
-            } else {
-                if self.current_span == Span::default() {
-                    // if there is no span, we are in generated code, so we should skip
-                    return None;
-                }
-                // no breakpoint, but we may stop here
-                if step == StepAction::In {
-                    StepResult::StepIn
-                } else if step == StepAction::Next && current_frame >= self.call_stack.len() {
-                    StepResult::Next
-                } else if step == StepAction::Out && current_frame > self.call_stack.len() {
-                    StepResult::StepOut
-                } else {
-                    return None;
-                }
-            },
-        )
+        if let Some(bp) = breakpoints
+            .iter()
+            .find(|&bp| *bp == stmt && self.package == self.source_package)
+        {
+            Some(StepResult::BreakpointHit(*bp))
+        } else {
+            self.check_for_step(step, current_frame)
+        }
+    }
+
+    /// Returns the step result if the given step action, started at the given call depth,
+    /// should stop at the current statement.
+    #[must_use]
+    pub fn check_for_step(&self, step: StepAction, current_frame: usize) -> Option<StepResult> {
+        if self.current_span == Span::default() {
+            // if there is no span, we are in generated code, so we should skip
+            return None;
+        }
+        if step == StepAction::In {
+            Some(StepResult::StepIn)
+        } else if step == StepAction::Next && current_frame >= self.call_stack.len() {
+            Some(StepResult::Next)
+        } else if step == StepAction::Out && current_frame > self.call_stack.len() {
+            Some(StepResult::StepOut)
+        } else {
+            None
+        }
+    }
+
+    /// The number of callables currently on the call stack.
+    #[must_use]
+    pub fn call_depth(&self) -> usize {
+        self.call_stack.len()
     }
 
     pub fn get_result(&mut self) -> Value {
@@ -2046,7 +2141,7 @@ fn make_counting_key(id: StoreItemId, functor: FunctorApp) -> CallableCountKey {
     (id, functor.adjoint, functor.controlled > 0)
 }
 
-#[derive(Default)]
+#[derive(Clone, Default)]
 struct QubitCounter {
     seen: FxHashSet<usize>,
     count: i64,
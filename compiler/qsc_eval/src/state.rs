@@ -4,6 +4,8 @@
 #[cfg(test)]
 mod tests;
 
+pub mod views;
+
 use num_bigint::BigUint;
 use num_complex::{Complex, Complex64};
 use std::fmt::Write;
@@ -0,0 +1,281 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+//! Views derived from a sparse state vector, such as per-qubit probabilities, reduced density
+//! matrices, Bloch vectors, entanglement entropy and Pauli expectation values.
+//!
+//! Qubits are numbered in the order of allocation, which matches the order in which they appear
+//! in basis state labels: qubit 0 is the leftmost, most significant bit of a basis state id.
+
+#[cfg(test)]
+mod tests;
+
+use miette::Diagnostic;
+use num_bigint::BigUint;
+use num_complex::Complex64;
+use rustc_hash::FxHashMap;
+use thiserror::Error;
+
+#[derive(Clone, Debug, Diagnostic, Error, PartialEq)]
+pub enum Error {
+    #[error("qubit {0} is out of range for a state of {1} qubits")]
+    #[diagnostic(code("Qsc.StateView.QubitOutOfRange"))]
+    QubitOutOfRange(usize, usize),
+
+    #[error("qubit {0} is listed more than once")]
+    #[diagnostic(code("Qsc.StateView.DuplicateQubit"))]
+    DuplicateQubit(usize),
+
+    #[error("invalid Pauli string `{0}`")]
+    #[diagnostic(code("Qsc.StateView.InvalidPauliString"))]
+    #[diagnostic(help("provide one of I, X, Y or Z for each of the {1} qubits"))]
+    InvalidPauliString(String, usize),
+}
+
+/// The views of a single qubit of a state.
+#[derive(Clone, Debug, PartialEq)]
+pub struct QubitView {
+    /// The probability of measuring the qubit in the Z basis and getting `One`.
+    pub probability_one: f64,
+    /// The density matrix of the qubit with all other qubits traced out.
+    pub density_matrix: [[Complex64; 2]; 2],
+    /// The Bloch vector of the qubit, as its x, y and z components.
+    pub bloch_vector: [f64; 3],
+}
+
+/// Returns the views of each qubit of the state, in allocation order.
+#[must_use]
+pub fn qubit_views(state: &[(BigUint, Complex64)], qubit_count: usize) -> Vec<QubitView> {
+    (0..qubit_count)
+        .map(|qubit| {
+            let density_matrix = single_qubit_density_matrix(state, qubit_count, qubit);
+            QubitView {
+                probability_one: density_matrix[1][1].re,
+                density_matrix,
+                bloch_vector: bloch_vector_of(&density_matrix),
+            }
+        })
+        .collect()
+}
+
+/// Returns the density matrix of the given qubit, with all other qubits traced out.
+/// # Errors
+/// Returns an error if the qubit is out of range.
+pub fn reduced_density_matrix(
+    state: &[(BigUint, Complex64)],
+    qubit_count: usize,
+    qubit: usize,
+) -> Result<[[Complex64; 2]; 2], Error> {
+    check_qubits(&[qubit], qubit_count)?;
+    Ok(single_qubit_density_matrix(state, qubit_count, qubit))
+}
+
+/// Returns the Bloch vector of the given qubit, as its x, y and z components.
+/// The vector is shorter than one when the qubit is entangled with others.
+/// # Errors
+/// Returns an error if the qubit is out of range.
+pub fn bloch_vector(
+    state: &[(BigUint, Complex64)],
+    qubit_count: usize,
+    qubit: usize,
+) -> Result<[f64; 3], Error> {
+    let density_matrix = reduced_density_matrix(state, qubit_count, qubit)?;
+    Ok(bloch_vector_of(&density_matrix))
+}
+
+/// Returns the von Neumann entropy, in bits, of the given subset of qubits.
+/// For a pure state this measures the entanglement between the subset and the other qubits.
+/// # Errors
+/// Returns an error if a qubit is out of range or listed more than once.
+pub fn entanglement_entropy(
+    state: &[(BigUint, Complex64)],
+    qubit_count: usize,
+    qubits: &[usize],
+) -> Result<f64, Error> {
+    check_qubits(qubits, qubit_count)?;
+    // Both sides of a pure state have the same entropy, so use the smaller one.
+    let subset: Vec<usize> = if qubits.len() * 2 <= qubit_count {
+        qubits.to_vec()
+    } else {
+        (0..qubit_count).filter(|q| !qubits.contains(q)).collect()
+    };
+    if subset.is_empty() {
+        return Ok(0.0);
+    }
+
+    // Group the amplitudes by the bits of the other qubits, which are traced out.
+    let mut groups: FxHashMap<Vec<bool>, Vec<(usize, Complex64)>> = FxHashMap::default();
+    for (id, amplitude) in state {
+        let mut index = 0;
+        for &qubit in &subset {
+            index = index << 1 | usize::from(qubit_bit(id, qubit_count, qubit));
+        }
+        let rest = (0..qubit_count)
+            .filter(|q| !subset.contains(q))
+            .map(|q| qubit_bit(id, qubit_count, q))
+            .collect();
+        groups.entry(rest).or_default().push((index, *amplitude));
+    }
+    let dim = 1 << subset.len();
+    let mut density = vec![vec![Complex64::default(); dim]; dim];
+    for amplitudes in groups.values() {
+        for (row, a) in amplitudes {
+            for (col, b) in amplitudes {
+                density[*row][*col] += a * b.conj();
+            }
+        }
+    }
+
+    // A Hermitian matrix H = A + iB has the same eigenvalues as the real symmetric matrix
+    // [[A, -B], [B, A]], each of them appearing twice.
+    let mut embedded = vec![vec![0.0; 2 * dim]; 2 * dim];
+    for (row, values) in density.iter().enumerate() {
+        for (col, value) in values.iter().enumerate() {
+            embedded[row][col] = value.re;
+            embedded[row + dim][col + dim] = value.re;
+            embedded[row][col + dim] = -value.im;
+            embedded[row + dim][col] = value.im;
+        }
+    }
+    let entropy: f64 = symmetric_eigenvalues(embedded)
+        .into_iter()
+        .filter(|&eigenvalue| eigenvalue > 1e-12)
+        .map(|eigenvalue| -eigenvalue * eigenvalue.log2())
+        .sum();
+    Ok(entropy / 2.0)
+}
+
+/// Returns the expectation value of the given Pauli string, which has one of `I`, `X`, `Y`
+/// or `Z` for each qubit in allocation order.
+/// # Errors
+/// Returns an error if the Pauli string is invalid or doesn't match the number of qubits.
+pub fn pauli_expectation(
+    state: &[(BigUint, Complex64)],
+    qubit_count: usize,
+    paulis: &str,
+) -> Result<f64, Error> {
+    let invalid = || Error::InvalidPauliString(paulis.to_string(), qubit_count);
+    let paulis: Vec<char> = paulis.chars().map(|c| c.to_ascii_uppercase()).collect();
+    if paulis.len() != qubit_count || paulis.iter().any(|c| !"IXYZ".contains(*c)) {
+        return Err(invalid());
+    }
+
+    // Each Pauli string maps a basis state |x⟩ to c(x)|x ⊕ m⟩, where the flipped bits m
+    // come from the X and Y operators.
+    let amplitudes: FxHashMap<&BigUint, &Complex64> = state
+        .iter()
+        .map(|(id, amplitude)| (id, amplitude))
+        .collect();
+    let mut flips = BigUint::default();
+    for (qubit, pauli) in paulis.iter().enumerate() {
+        if matches!(pauli, 'X' | 'Y') {
+            flips.set_bit(bit_position(qubit_count, qubit), true);
+        }
+    }
+    let mut expectation = Complex64::default();
+    for (id, amplitude) in state {
+        let Some(flipped) = amplitudes.get(&(id ^ &flips)) else {
+            continue;
+        };
+        let mut coefficient = Complex64::new(1.0, 0.0);
+        for (qubit, pauli) in paulis.iter().enumerate() {
+            let one = qubit_bit(id, qubit_count, qubit);
+            coefficient *= match (pauli, one) {
+                ('Y', false) => Complex64::i(),
+                ('Y', true) => -Complex64::i(),
+                ('Z', true) => Complex64::new(-1.0, 0.0),
+                _ => Complex64::new(1.0, 0.0),
+            };
+        }
+        expectation += flipped.conj() * coefficient * amplitude;
+    }
+    Ok(expectation.re)
+}
+
+fn check_qubits(qubits: &[usize], qubit_count: usize) -> Result<(), Error> {
+    for (i, &qubit) in qubits.iter().enumerate() {
+        if qubit >= qubit_count {
+            return Err(Error::QubitOutOfRange(qubit, qubit_count));
+        }
+        if qubits[..i].contains(&qubit) {
+            return Err(Error::DuplicateQubit(qubit));
+        }
+    }
+    Ok(())
+}
+
+fn bit_position(qubit_count: usize, qubit: usize) -> u64 {
+    (qubit_count - 1 - qubit) as u64
+}
+
+fn qubit_bit(id: &BigUint, qubit_count: usize, qubit: usize) -> bool {
+    id.bit(bit_position(qubit_count, qubit))
+}
+
+fn single_qubit_density_matrix(
+    state: &[(BigUint, Complex64)],
+    qubit_count: usize,
+    qubit: usize,
+) -> [[Complex64; 2]; 2] {
+    let position = bit_position(qubit_count, qubit);
+    // Pair up the amplitudes that differ only in the bit of the qubit.
+    let mut pairs: FxHashMap<BigUint, [Complex64; 2]> = FxHashMap::default();
+    for (id, amplitude) in state {
+        let mut rest = id.clone();
+        rest.set_bit(position, false);
+        pairs.entry(rest).or_default()[usize::from(id.bit(position))] = *amplitude;
+    }
+    let mut density = [[Complex64::default(); 2]; 2];
+    for [zero, one] in pairs.values() {
+        density[0][0] += zero * zero.conj();
+        density[0][1] += zero * one.conj();
+        density[1][0] += one * zero.conj();
+        density[1][1] += one * one.conj();
+    }
+    density
+}
+
+fn bloch_vector_of(density_matrix: &[[Complex64; 2]; 2]) -> [f64; 3] {
+    // ρ = (I + xX + yY + zZ) / 2, so ρ₀₁ = (x - iy) / 2 and z = ρ₀₀ - ρ₁₁.
+    [
+        2.0 * density_matrix[0][1].re,
+        0.0 - 2.0 * density_matrix[0][1].im,
+        density_matrix[0][0].re - density_matrix[1][1].re,
+    ]
+}
+
+/// Returns the eigenvalues of a real symmetric matrix, using the cyclic Jacobi method.
+fn symmetric_eigenvalues(mut matrix: Vec<Vec<f64>>) -> Vec<f64> {
+    let n = matrix.len();
+    for _ in 0..100 {
+        let off_diagonal: f64 = (0..n)
+            .flat_map(|p| (0..n).filter(move |&q| q != p).map(move |q| (p, q)))
+            .map(|(p, q)| matrix[p][q] * matrix[p][q])
+            .sum();
+        if off_diagonal < 1e-24 {
+            break;
+        }
+        for p in 0..n {
+            for q in p + 1..n {
+                if matrix[p][q].abs() < 1e-300 {
+                    continue;
+                }
+                // Rotate rows and columns p and q so that the element at (p, q) becomes zero.
+                let theta = (matrix[q][q] - matrix[p][p]) / (2.0 * matrix[p][q]);
+                let t = theta.signum() / (theta.abs() + theta.hypot(1.0));
+                let c = 1.0 / t.hypot(1.0);
+                let s = t * c;
+                for row in &mut matrix {
+                    let (kp, kq) = (row[p], row[q]);
+                    row[p] = c * kp - s * kq;
+                    row[q] = s * kp + c * kq;
+                }
+                let (upper, lower) = matrix.split_at_mut(q);
+                for (pk, qk) in upper[p].iter_mut().zip(lower[0].iter_mut()) {
+                    (*pk, *qk) = (c * *pk - s * *qk, s * *pk + c * *qk);
+                }
+            }
+        }
+    }
+    (0..n).map(|i| matrix[i][i]).collect()
+}
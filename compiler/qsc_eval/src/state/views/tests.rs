@@ -0,0 +1,212 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+#![allow(clippy::needless_raw_string_hashes)]
+
+use super::{
+    bloch_vector, entanglement_entropy, pauli_expectation, qubit_views, reduced_density_matrix,
+};
+use expect_test::{expect, Expect};
+use num_bigint::BigUint;
+use num_complex::Complex64;
+use std::f64::consts::{FRAC_1_SQRT_2, PI};
+
+fn state(amplitudes: &[(u32, Complex64)]) -> Vec<(BigUint, Complex64)> {
+    amplitudes
+        .iter()
+        .map(|(id, amplitude)| (BigUint::from(*id), *amplitude))
+        .collect()
+}
+
+fn real(value: f64) -> Complex64 {
+    Complex64::new(value, 0.0)
+}
+
+fn bell() -> Vec<(BigUint, Complex64)> {
+    state(&[(0b00, real(FRAC_1_SQRT_2)), (0b11, real(FRAC_1_SQRT_2))])
+}
+
+fn fmt(values: &[f64]) -> String {
+    values
+        .iter()
+        .map(|value| format!("{:.4}", value + 0.0))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn check_views(state: &[(BigUint, Complex64)], qubit_count: usize, expect: &Expect) {
+    let views = qubit_views(state, qubit_count)
+        .iter()
+        .enumerate()
+        .map(|(qubit, view)| {
+            format!(
+                "q{qubit}: P(One) = {}, Bloch = ({})",
+                fmt(&[view.probability_one]),
+                fmt(&view.bloch_vector)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    expect.assert_eq(&views);
+}
+
+#[test]
+fn views_of_basis_state() {
+    check_views(
+        &state(&[(0b01, real(1.0))]),
+        2,
+        &expect![[r#"
+            q0: P(One) = 0.0000, Bloch = (0.0000, 0.0000, 1.0000)
+            q1: P(One) = 1.0000, Bloch = (0.0000, 0.0000, -1.0000)"#]],
+    );
+}
+
+#[test]
+fn views_of_superpositions() {
+    // |+⟩ ⊗ |+i⟩
+    let half = real(0.5);
+    let half_i = Complex64::new(0.0, 0.5);
+    check_views(
+        &state(&[(0b00, half), (0b01, half_i), (0b10, half), (0b11, half_i)]),
+        2,
+        &expect![[r#"
+            q0: P(One) = 0.5000, Bloch = (1.0000, 0.0000, 0.0000)
+            q1: P(One) = 0.5000, Bloch = (0.0000, 1.0000, 0.0000)"#]],
+    );
+}
+
+#[test]
+fn views_of_entangled_qubits_are_mixed() {
+    check_views(
+        &bell(),
+        2,
+        &expect![[r#"
+            q0: P(One) = 0.5000, Bloch = (0.0000, 0.0000, 0.0000)
+            q1: P(One) = 0.5000, Bloch = (0.0000, 0.0000, 0.0000)"#]],
+    );
+}
+
+#[test]
+fn density_matrix_of_bell_state_qubit() {
+    let matrix = reduced_density_matrix(&bell(), 2, 1).expect("qubit should be in range");
+    expect![[r#"
+        [
+            [
+                Complex {
+                    re: 0.5000000000000001,
+                    im: 0.0,
+                },
+                Complex {
+                    re: 0.0,
+                    im: 0.0,
+                },
+            ],
+            [
+                Complex {
+                    re: 0.0,
+                    im: 0.0,
+                },
+                Complex {
+                    re: 0.5000000000000001,
+                    im: 0.0,
+                },
+            ],
+        ]
+    "#]]
+    .assert_debug_eq(&matrix);
+}
+
+#[test]
+fn bloch_vector_of_rotated_qubit() {
+    // Ry(π/3)|0⟩
+    let angle = PI / 6.0;
+    let state = state(&[(0, real(angle.cos())), (1, real(angle.sin()))]);
+    let vector = bloch_vector(&state, 1, 0).expect("qubit should be in range");
+    assert_eq!(fmt(&vector), "0.8660, 0.0000, 0.5000");
+}
+
+#[test]
+fn entropy_of_product_state_is_zero() {
+    // |+⟩ ⊗ |0⟩
+    let state = state(&[(0b00, real(FRAC_1_SQRT_2)), (0b10, real(FRAC_1_SQRT_2))]);
+    let entropy = entanglement_entropy(&state, 2, &[0]).expect("qubits should be valid");
+    assert_eq!(fmt(&[entropy]), "0.0000");
+}
+
+#[test]
+fn entropy_of_bell_state() {
+    let state = bell();
+    let entropies = [&[0][..], &[1], &[0, 1], &[]]
+        .iter()
+        .map(|qubits| entanglement_entropy(&state, 2, qubits).expect("qubits should be valid"))
+        .collect::<Vec<_>>();
+    assert_eq!(fmt(&entropies), "1.0000, 1.0000, 0.0000, 0.0000");
+}
+
+#[test]
+fn entropy_of_partially_entangled_state() {
+    let angle = PI / 8.0;
+    let (c, s) = (angle.cos(), angle.sin());
+    let state = state(&[(0b00, real(c)), (0b11, Complex64::new(0.0, s))]);
+    let entropy = entanglement_entropy(&state, 2, &[1]).expect("qubits should be valid");
+    let (p, q) = (c * c, s * s);
+    let expected = -p * p.log2() - q * q.log2();
+    assert!((entropy - expected).abs() < 1e-9, "{entropy} != {expected}");
+}
+
+#[test]
+fn entropy_of_ghz_state_subsets() {
+    let state = state(&[(0b000, real(FRAC_1_SQRT_2)), (0b111, real(FRAC_1_SQRT_2))]);
+    let entropies = [&[0][..], &[2, 0], &[0, 1, 2]]
+        .iter()
+        .map(|qubits| entanglement_entropy(&state, 3, qubits).expect("qubits should be valid"))
+        .collect::<Vec<_>>();
+    assert_eq!(fmt(&entropies), "1.0000, 1.0000, 0.0000");
+}
+
+#[test]
+fn pauli_expectations_of_bell_state() {
+    let state = bell();
+    let expectations = ["ZZ", "XX", "YY", "XI", "iz", "ZI"]
+        .iter()
+        .map(|paulis| pauli_expectation(&state, 2, paulis).expect("Pauli string should be valid"))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        fmt(&expectations),
+        "1.0000, 1.0000, -1.0000, 0.0000, 0.0000, 0.0000"
+    );
+}
+
+#[test]
+fn pauli_expectation_of_y_eigenstate() {
+    // |−i⟩ has Y expectation -1.
+    let state = state(&[
+        (0, real(FRAC_1_SQRT_2)),
+        (1, Complex64::new(0.0, -FRAC_1_SQRT_2)),
+    ]);
+    let expectation = pauli_expectation(&state, 1, "Y").expect("Pauli string should be valid");
+    assert_eq!(fmt(&[expectation]), "-1.0000");
+}
+
+#[test]
+fn invalid_arguments_are_errors() {
+    let state = bell();
+    let errors = [
+        reduced_density_matrix(&state, 2, 2).map(|_| ()),
+        entanglement_entropy(&state, 2, &[1, 1]).map(|_| ()),
+        pauli_expectation(&state, 2, "XQ").map(|_| ()),
+        pauli_expectation(&state, 2, "X").map(|_| ()),
+    ]
+    .into_iter()
+    .map(|result| result.expect_err("arguments should be invalid").to_string())
+    .collect::<Vec<_>>();
+    expect![[r#"
+        [
+            "qubit 2 is out of range for a state of 2 qubits",
+            "qubit 1 is listed more than once",
+            "invalid Pauli string `XQ`",
+            "invalid Pauli string `X`",
+        ]
+    "#]]
+    .assert_debug_eq(&errors);
+}
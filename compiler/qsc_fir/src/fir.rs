@@ -15,6 +15,7 @@ use qsc_data_structures::{
     index_map::{IndexMap, Iter},
     span::Span,
 };
+use serde::Serialize;
 use std::{
     cmp::Ordering,
     fmt::{self, Debug, Display, Formatter, Write},
@@ -38,7 +39,7 @@ fn set_indentation<'a, 'b>(
 }
 
 /// A unique identifier for an FIR node.
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, Serialize)]
 pub struct NodeId(u32);
 
 impl NodeId {
@@ -115,7 +116,7 @@ impl Hash for NodeId {
 macro_rules! fir_id {
     ($id:ident) => {
         /// A unique identifier for an FIR node.
-        #[derive(Debug, Clone, Copy)]
+        #[derive(Debug, Clone, Copy, Serialize)]
         pub struct $id(pub u32);
 
         impl $id {
@@ -213,7 +214,7 @@ fir_id!(StmtId);
 fir_id!(LocalVarId);
 
 /// A unique identifier for a package within a package store.
-#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize)]
 pub struct PackageId(usize);
 
 impl PackageId {
@@ -246,7 +247,7 @@ impl From<usize> for PackageId {
 }
 
 /// A unique identifier for an item within a package.
-#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize)]
 pub struct LocalItemId(usize);
 
 impl LocalItemId {
@@ -276,7 +277,7 @@ impl From<LocalItemId> for usize {
 }
 
 /// A unique identifier for an item within a package store.
-#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize)]
 pub struct ItemId {
     /// The package ID or `None` for the local package.
     pub package: Option<PackageId>,
@@ -295,7 +296,7 @@ impl Display for ItemId {
 
 /// A resolution. This connects a usage of a name with the declaration of that name by uniquely
 /// identifying the node that declared it.
-#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize)]
 pub enum Res {
     /// An invalid resolution.
     Err,
@@ -324,7 +325,7 @@ pub enum Global<'a> {
 }
 
 /// A unique identifier for an item within a package store.
-#[derive(Clone, Copy, Debug, PartialEq, Hash, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Hash, Eq, Serialize)]
 pub struct StoreItemId {
     /// The package ID.
     pub package: PackageId,
@@ -348,7 +349,7 @@ impl From<(PackageId, LocalItemId)> for StoreItemId {
 }
 
 /// A unique identifier for a block within a package store.
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
 pub struct StoreBlockId {
     /// The package ID.
     pub package: PackageId,
@@ -372,7 +373,7 @@ impl From<(PackageId, BlockId)> for StoreBlockId {
 }
 
 /// A unique identifier for an expression within a package store.
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
 pub struct StoreExprId {
     /// The package ID.
     pub package: PackageId,
@@ -396,7 +397,7 @@ impl From<(PackageId, ExprId)> for StoreExprId {
 }
 
 /// A unique identifier for a pattern within a package store.
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
 pub struct StorePatId {
     /// The package ID.
     pub package: PackageId,
@@ -420,7 +421,7 @@ impl From<(PackageId, PatId)> for StorePatId {
 }
 
 /// A unique identifier for a statement within a package store.
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
 pub struct StoreStmtId {
     /// The package ID.
     pub package: PackageId,
@@ -555,7 +556,7 @@ pub trait PackageLookup {
 /// within the containing node. Node ids are used to identify nodes within
 /// the package and require mapping from the HIR node id to the new FIR node id.
 /// `PackageId`s and `LocalItemId`s are 1:1 from the HIR and are not remapped.
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 pub struct Package {
     /// The items in the package.
     pub items: IndexMap<LocalItemId, Item>,
@@ -651,7 +652,7 @@ impl PackageLookup for Package {
 }
 
 /// An item.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize)]
 pub struct Item {
     /// The ID.
     pub id: LocalItemId,
@@ -700,7 +701,7 @@ impl Display for Item {
 }
 
 /// An item kind.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize)]
 pub enum ItemKind {
     /// A `function` or `operation` declaration.
     Callable(CallableDecl),
@@ -736,7 +737,7 @@ impl Display for ItemKind {
 }
 
 /// A callable declaration header.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize)]
 pub struct CallableDecl {
     /// The node ID.
     pub id: NodeId,
@@ -801,7 +802,7 @@ impl Display for CallableDecl {
 }
 
 /// A callable implementations.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize)]
 pub enum CallableImpl {
     /// An intrinsic callable implementation.
     Intrinsic,
@@ -835,7 +836,7 @@ impl Display for CallableImpl {
 }
 
 /// A specialized implementation.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize)]
 pub struct SpecImpl {
     /// The body implementation.
     pub body: SpecDecl,
@@ -870,7 +871,7 @@ impl Display for SpecImpl {
 }
 
 /// A specialization declaration.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize)]
 pub struct SpecDecl {
     /// The node ID.
     pub id: NodeId,
@@ -897,7 +898,7 @@ impl Display for SpecDecl {
 /// An execution graph represented by a reference counted vector of nodes.
 pub type ExecGraph = Rc<[ExecGraphNode]>;
 
-#[derive(Copy, Clone, Debug, PartialEq)]
+#[derive(Copy, Clone, Debug, PartialEq, Serialize)]
 /// A node within the control flow graph.
 pub enum ExecGraphNode {
     /// A binding of a value to a variable.
@@ -930,7 +931,7 @@ pub enum ExecGraphNode {
 }
 
 /// A sequenced block of statements.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize)]
 pub struct Block {
     /// The node ID.
     pub id: BlockId,
@@ -963,7 +964,7 @@ impl Display for Block {
 }
 
 /// A statement.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize)]
 pub struct Stmt {
     /// The stmt ID.
     pub id: StmtId,
@@ -982,7 +983,7 @@ impl Display for Stmt {
 }
 
 /// A statement kind.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize)]
 pub enum StmtKind {
     /// An expression without a trailing semicolon.
     Expr(ExprId),
@@ -1013,7 +1014,7 @@ impl Display for StmtKind {
 }
 
 /// An expression.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize)]
 pub struct Expr {
     /// The expr ID.
     pub id: ExprId,
@@ -1038,7 +1039,7 @@ impl Display for Expr {
 }
 
 /// An expression kind.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize)]
 pub enum ExprKind {
     /// An array: `[a, b, c]`.
     Array(Vec<ExprId>),
@@ -1408,7 +1409,7 @@ fn display_while(mut indent: Indented<Formatter>, cond: ExprId, block: BlockId)
 }
 
 /// A field assignment in a struct constructor expression.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize)]
 pub struct FieldAssign {
     /// The node ID.
     pub id: NodeId,
@@ -1431,7 +1432,7 @@ impl Display for FieldAssign {
 }
 
 /// A string component.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize)]
 pub enum StringComponent {
     /// An expression.
     Expr(ExprId),
@@ -1440,7 +1441,7 @@ pub enum StringComponent {
 }
 
 /// A pattern.
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
 pub struct Pat {
     /// The node ID.
     pub id: PatId,
@@ -1463,7 +1464,7 @@ impl Display for Pat {
 }
 
 /// A pattern kind.
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
 pub enum PatKind {
     /// A binding.
     Bind(Ident),
@@ -1498,7 +1499,7 @@ impl Display for PatKind {
 }
 
 /// An identifier.
-#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq, Serialize)]
 pub struct Ident {
     /// The node ID.
     pub id: LocalVarId,
@@ -1515,14 +1516,14 @@ impl Display for Ident {
 }
 
 /// An attribute.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize)]
 pub enum Attr {
     /// Indicates that a callable is an entry point to a program.
     EntryPoint,
 }
 
 /// A field.
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
 pub enum Field {
     /// A field path.
     Path(FieldPath),
@@ -1543,14 +1544,14 @@ impl Display for Field {
 }
 
 /// A path to a field in a tuple or user-defined type.
-#[derive(Clone, Debug, Default, Eq, Ord, PartialEq, PartialOrd)]
+#[derive(Clone, Debug, Default, Eq, Ord, PartialEq, PartialOrd, Serialize)]
 pub struct FieldPath {
     /// The tuple item indices to follow in order from top to bottom.
     pub indices: Vec<usize>,
 }
 
 /// A primitive field for a built-in type.
-#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize)]
 pub enum PrimField {
     /// The start of a range.
     Start,
@@ -1574,7 +1575,7 @@ impl FromStr for PrimField {
 }
 
 /// The visibility of a declaration.
-#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize)]
 pub enum Visibility {
     /// Visible everywhere.
     Public,
@@ -1583,7 +1584,7 @@ pub enum Visibility {
 }
 
 /// A callable kind.
-#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize)]
 pub enum CallableKind {
     /// A function.
     Function,
@@ -1601,7 +1602,7 @@ impl Display for CallableKind {
 }
 
 /// The mutability of a binding.
-#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize)]
 pub enum Mutability {
     /// An immutable binding.
     Immutable,
@@ -1610,7 +1611,7 @@ pub enum Mutability {
 }
 
 /// The source of an allocated qubit.
-#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize)]
 pub enum QubitSource {
     /// A qubit initialized to the zero state.
     Fresh,
@@ -1620,7 +1621,7 @@ pub enum QubitSource {
 }
 
 /// A literal.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize)]
 pub enum Lit {
     /// A big integer literal.
     BigInt(BigInt),
@@ -1651,7 +1652,7 @@ impl Display for Lit {
 }
 
 /// A measurement result.
-#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize)]
 pub enum Result {
     /// The zero eigenvalue.
     Zero,
@@ -1660,7 +1661,7 @@ pub enum Result {
 }
 
 /// A Pauli operator.
-#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize)]
 pub enum Pauli {
     /// The Pauli I operator.
     I,
@@ -1673,7 +1674,7 @@ pub enum Pauli {
 }
 
 /// A functor that may be applied to an operation.
-#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize)]
 pub enum Functor {
     /// The adjoint functor.
     Adj,
@@ -1691,7 +1692,7 @@ impl Display for Functor {
 }
 
 /// A unary operator.
-#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize)]
 pub enum UnOp {
     /// A functor application.
     Functor(Functor),
@@ -1718,7 +1719,7 @@ impl Display for UnOp {
 }
 
 /// A binary operator.
-#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize)]
 pub enum BinOp {
     /// Addition: `+`.
     Add,
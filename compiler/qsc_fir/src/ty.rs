@@ -4,6 +4,7 @@
 use indenter::{indented, Indented};
 use qsc_data_structures::span::Span;
 use rustc_hash::FxHashMap;
+use serde::Serialize;
 
 use crate::fir::{CallableKind, FieldPath, Functor, ItemId, Res};
 use std::{
@@ -24,7 +25,7 @@ fn set_indentation<'a, 'b>(
 }
 
 /// A type.
-#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize)]
 pub enum Ty {
     /// An array type.
     Array(Box<Ty>),
@@ -188,7 +189,7 @@ impl Display for GenericParam {
 }
 
 /// The kind of a generic parameter.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize)]
 pub enum GenericParam {
     /// A type parameter.
     Ty,
@@ -197,7 +198,7 @@ pub enum GenericParam {
 }
 
 /// A generic parameter ID.
-#[derive(Clone, Copy, Default, Debug, Eq, Hash, PartialEq)]
+#[derive(Clone, Copy, Default, Debug, Eq, Hash, PartialEq, Serialize)]
 pub struct ParamId(u32);
 
 impl ParamId {
@@ -225,7 +226,7 @@ impl Display for ParamId {
 }
 
 /// An argument to a generic parameter.
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
 pub enum GenericArg {
     /// A type argument.
     Ty(Ty),
@@ -243,7 +244,7 @@ impl Display for GenericArg {
 }
 
 /// An arrow type: `->` for a function or `=>` for an operation.
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
 pub struct Arrow {
     /// Whether the callable is a function or an operation.
     pub kind: CallableKind,
@@ -271,7 +272,7 @@ impl Display for Arrow {
 }
 
 /// A primitive type.
-#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize)]
 pub enum Prim {
     /// The big integer type.
     BigInt,
@@ -300,7 +301,7 @@ pub enum Prim {
 }
 
 /// A set of functors.
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize)]
 pub enum FunctorSet {
     /// An evaluated set.
     Value(FunctorSetValue),
@@ -336,7 +337,7 @@ impl Display for FunctorSet {
 }
 
 /// The value of a functor set.
-#[derive(Clone, Copy, Debug, Default, Hash, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Default, Hash, Eq, PartialEq, Serialize)]
 pub enum FunctorSetValue {
     /// The empty set.
     #[default]
@@ -402,7 +403,7 @@ impl Display for FunctorSetValue {
 }
 
 /// A user-defined type.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize)]
 pub struct Udt {
     /// The span.
     pub span: Span,
@@ -508,7 +509,7 @@ impl Display for Udt {
 }
 
 /// A UDT type definition.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize)]
 pub struct UdtDef {
     /// The span.
     pub span: Span,
@@ -523,7 +524,7 @@ impl Display for UdtDef {
 }
 
 /// A UDT type definition kind.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize)]
 pub enum UdtDefKind {
     /// A field definition with an optional name but required type.
     Field(UdtField),
@@ -557,7 +558,7 @@ impl Display for UdtDefKind {
 }
 
 /// A user-defined type.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize)]
 pub struct UdtField {
     /// The span of the field name.
     pub name_span: Option<Span>,
@@ -580,7 +581,7 @@ impl Display for UdtField {
 }
 
 /// A placeholder type variable used during type inference.
-#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq, Serialize)]
 pub struct InferTyId(usize);
 
 impl InferTyId {
@@ -610,7 +611,7 @@ impl From<InferTyId> for usize {
 }
 
 /// A placeholder functor variable used during type inference.
-#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize)]
 pub struct InferFunctorId(usize);
 
 impl InferFunctorId {
@@ -2,7 +2,11 @@
 // Licensed under the MIT License.
 
 use clap::{crate_version, Parser};
-use qsc_formatter::formatter::{calculate_format_edits, format_str};
+use qsc_formatter::formatter::{
+    calculate_format_edits_with_config, format_str_with_config, FormatterConfig, TrailingComma,
+};
+use rustc_hash::FxHashMap;
+use serde::Deserialize;
 use std::{
     env,
     fmt::Display,
@@ -24,6 +28,39 @@ struct Cli {
     /// Overwrite the file contents with the formatted contents.
     #[arg(short, long, default_value("false"))]
     write: bool,
+
+    /// Wrap lists that don't fit in the given line width.
+    /// Overrides the `format` section of the nearest `qsharp.json`.
+    #[arg(long)]
+    max_line_width: Option<usize>,
+
+    /// The number of spaces for each level of indentation.
+    /// Overrides the `format` section of the nearest `qsharp.json`.
+    #[arg(long)]
+    indent_width: Option<usize>,
+
+    /// How trailing commas are handled: `preserve`, `multiline` or `never`.
+    /// Overrides the `format` section of the nearest `qsharp.json`.
+    #[arg(long, value_parser = parse_trailing_comma)]
+    trailing_comma: Option<TrailingComma>,
+}
+
+/// The part of a `qsharp.json` manifest that configures the formatter.
+#[derive(Deserialize)]
+struct Manifest {
+    #[serde(default)]
+    format: FormatterConfig,
+}
+
+fn parse_trailing_comma(value: &str) -> Result<TrailingComma, String> {
+    match value {
+        "preserve" => Ok(TrailingComma::Preserve),
+        "multiline" => Ok(TrailingComma::Multiline),
+        "never" => Ok(TrailingComma::Never),
+        _ => Err(format!(
+            "expected `preserve`, `multiline` or `never`, found `{value}`"
+        )),
+    }
 }
 
 struct FileWalker {
@@ -33,6 +70,11 @@ struct FileWalker {
     file_count: i32,
     changed_files: Vec<String>,
     skipped_files: Vec<String>,
+    max_line_width: Option<usize>,
+    indent_width: Option<usize>,
+    trailing_comma: Option<TrailingComma>,
+    /// The formatter configuration of each directory, from the nearest `qsharp.json`.
+    configs: FxHashMap<PathBuf, FormatterConfig>,
 }
 
 impl FileWalker {
@@ -45,9 +87,37 @@ impl FileWalker {
             file_count: 0,
             changed_files: vec![],
             skipped_files: vec![],
+            max_line_width: cli.max_line_width,
+            indent_width: cli.indent_width,
+            trailing_comma: cli.trailing_comma,
+            configs: FxHashMap::default(),
         }
     }
 
+    /// Returns the formatter configuration for the file, from the `format` section of
+    /// the nearest `qsharp.json` with any command line options applied on top.
+    fn config_for(&mut self, path: &Path) -> FormatterConfig {
+        let dir = path.parent().unwrap_or(Path::new(".")).to_path_buf();
+        let mut config = match self.configs.get(&dir) {
+            Some(config) => *config,
+            None => {
+                let config = load_manifest_config(&dir);
+                self.configs.insert(dir, config);
+                config
+            }
+        };
+        if let Some(max_line_width) = self.max_line_width {
+            config.max_line_width = Some(max_line_width);
+        }
+        if let Some(indent_width) = self.indent_width {
+            config.indent_width = indent_width;
+        }
+        if let Some(trailing_comma) = self.trailing_comma {
+            config.trailing_comma = trailing_comma;
+        }
+        config
+    }
+
     fn format_from_roots(&mut self) {
         let temp = self.roots.clone();
         for root in temp {
@@ -97,8 +167,9 @@ impl FileWalker {
                 return; // don't count file in file_count
             }
         };
+        let config = self.config_for(path);
         if self.is_write {
-            let formatted = format_str(&file_as_string);
+            let formatted = format_str_with_config(&file_as_string, &config);
             if file_as_string != formatted {
                 match std::fs::write(path, formatted) {
                     Ok(_) => {
@@ -111,7 +182,7 @@ impl FileWalker {
                     }
                 }
             }
-        } else if !calculate_format_edits(&file_as_string).is_empty() {
+        } else if !calculate_format_edits_with_config(&file_as_string, &config).is_empty() {
             self.changed_files.push(path.display().to_string());
         }
         self.file_count += 1;
@@ -253,6 +324,34 @@ fn main() -> Result<(), String> {
     }
 }
 
+/// Reads the `format` section of the `qsharp.json` in the directory or its nearest ancestor.
+fn load_manifest_config(dir: &Path) -> FormatterConfig {
+    use OutputFormatting::*;
+
+    let Some(manifest_path) = dir
+        .ancestors()
+        .map(|dir| dir.join("qsharp.json"))
+        .find(|path| path.is_file())
+    else {
+        return FormatterConfig::default();
+    };
+    let manifest = std::fs::read_to_string(&manifest_path)
+        .map_err(|e| e.to_string())
+        .and_then(|contents| {
+            serde_json::from_str::<Manifest>(&contents).map_err(|e| e.to_string())
+        });
+    match manifest {
+        Ok(manifest) => manifest.format,
+        Err(e) => {
+            println!(
+                "\t{Skip}Could not read format settings from {}: {e}{Reset}",
+                manifest_path.display()
+            );
+            FormatterConfig::default()
+        }
+    }
+}
+
 fn is_path_qs(path: &Path) -> bool {
     if path.is_file() {
         if let Some(ex) = path.extension() {
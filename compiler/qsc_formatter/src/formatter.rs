@@ -10,6 +10,12 @@ use qsc_frontend::{
         Delim, InterpolatedEnding, InterpolatedStart,
     },
 };
+use rustc_hash::FxHashSet;
+use serde::{Deserialize, Serialize};
+
+use layout::Groups;
+
+mod layout;
 
 #[cfg(test)]
 mod tests;
@@ -19,7 +25,13 @@ mod tests;
 /// Applies formatting rules to the give code str and returns
 /// the formatted string.
 pub fn format_str(code: &str) -> String {
-    let mut edits = calculate_format_edits(code);
+    format_str_with_config(code, &FormatterConfig::default())
+}
+
+/// Applies formatting rules to the given code str, using the given
+/// configuration, and returns the formatted string.
+pub fn format_str_with_config(code: &str, config: &FormatterConfig) -> String {
+    let mut edits = calculate_format_edits_with_config(code, config);
     edits.sort_by_key(|edit| edit.span.hi); // sort edits by their span's hi value from lowest to highest
     edits.reverse(); // sort from highest to lowest so that that as edits are applied they don't invalidate later applications of edits
     let mut new_code = String::from(code);
@@ -35,11 +47,46 @@ pub fn format_str(code: &str) -> String {
 /// Applies formatting rules to the given code str, generating edits where
 /// the source code needs to be changed to comply with the format rules.
 pub fn calculate_format_edits(code: &str) -> Vec<TextEdit> {
-    let tokens = concrete::ConcreteTokenIterator::new(code);
+    calculate_format_edits_with_config(code, &FormatterConfig::default())
+}
+
+/// Applies formatting rules to the given code str, using the given configuration,
+/// generating edits where the source code needs to be changed to comply with the format rules.
+pub fn calculate_format_edits_with_config(code: &str, config: &FormatterConfig) -> Vec<TextEdit> {
+    let tokens = concrete::ConcreteTokenIterator::new(code).collect::<Vec<_>>();
+    let groups = Groups::new(&tokens);
+
+    // Lists that don't fit on their line are broken one at a time, outermost first,
+    // until every line fits or there is nothing left to break.
+    let mut breaks = FxHashSet::default();
+    loop {
+        let edits = apply_format_rules(code, &tokens, config, &groups, &breaks);
+        let Some(max_line_width) = config.max_line_width else {
+            return edits;
+        };
+        let new_breaks =
+            groups.breaks_for_long_lines(code, &tokens, &edits, &breaks, max_line_width);
+        if new_breaks.is_empty() {
+            return edits;
+        }
+        breaks.extend(new_breaks);
+    }
+}
+
+fn apply_format_rules(
+    code: &str,
+    tokens: &[ConcreteToken],
+    config: &FormatterConfig,
+    groups: &Groups,
+    breaks: &FxHashSet<u32>,
+) -> Vec<TextEdit> {
     let mut edits = vec![];
 
     let mut formatter = Formatter {
         code,
+        config,
+        groups,
+        breaks,
         indent_level: 0,
         delim_newlines_stack: vec![],
         type_param_state: TypeParameterListState::NoState,
@@ -109,6 +156,44 @@ impl TextEdit {
     }
 }
 
+/// Formatter settings, typically read from the `format` section of a `qsharp.json` manifest.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct FormatterConfig {
+    /// The maximum width of a line, in characters. Argument lists, tuple and array literals,
+    /// and callable parameter lists that don't fit are wrapped to one item per line.
+    /// When unset, lines are never wrapped.
+    pub max_line_width: Option<usize>,
+    /// The number of spaces for each level of indentation.
+    pub indent_width: usize,
+    /// How trailing commas in comma-separated lists are handled.
+    pub trailing_comma: TrailingComma,
+}
+
+impl Default for FormatterConfig {
+    fn default() -> Self {
+        Self {
+            max_line_width: None,
+            indent_width: 4,
+            trailing_comma: TrailingComma::default(),
+        }
+    }
+}
+
+/// The trailing-comma policy for comma-separated lists. Single-item tuples
+/// always keep their trailing comma, since it is what makes them tuples.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum TrailingComma {
+    /// Trailing commas are left as they are.
+    #[default]
+    Preserve,
+    /// Lists with one item per line end with a trailing comma, and lists on a single line don't.
+    Multiline,
+    /// Trailing commas are removed.
+    Never,
+}
+
 // Private types
 
 /// This is to keep track of whether the formatter is currently
@@ -220,6 +305,10 @@ impl Delimiter {
 
 struct Formatter<'a> {
     code: &'a str,
+    config: &'a FormatterConfig,
+    groups: &'a Groups,
+    /// The offsets of the open delimiters of lists that must be broken to one item per line.
+    breaks: &'a FxHashSet<u32>,
     indent_level: usize,
     delim_newlines_stack: Vec<NewlineContext>,
     type_param_state: TypeParameterListState,
@@ -263,10 +352,12 @@ impl<'a> Formatter<'a> {
             self.import_export_state,
         );
 
+        let is_forced_break =
+            matches!(left_delim_state, Delimiter::Open) && self.breaks.contains(&left.span.lo);
         let newline_context = self.update_indent_level(
             left_delim_state,
             right_delim_state,
-            are_newlines_in_spaces,
+            are_newlines_in_spaces || is_forced_break,
             does_right_required_newline,
             matches!(right.kind, Comment),
         );
@@ -275,10 +366,22 @@ impl<'a> Formatter<'a> {
             (Comment | Syntax(DocComment), _) => {
                 // remove whitespace at the ends of comments
                 effect_trim_comment(left, &mut edits, self.code);
-                effect_correct_indentation(left, whitespace, right, &mut edits, self.indent_level);
+                effect_correct_indentation(
+                    left,
+                    whitespace,
+                    right,
+                    &mut edits,
+                    self.indent(self.indent_level),
+                );
             }
             (_, Comment) if matches!(left_delim_state, Delimiter::Open) => {
-                effect_correct_indentation(left, whitespace, right, &mut edits, self.indent_level);
+                effect_correct_indentation(
+                    left,
+                    whitespace,
+                    right,
+                    &mut edits,
+                    self.indent(self.indent_level),
+                );
             }
             (_, Comment) => {
                 if are_newlines_in_spaces {
@@ -287,7 +390,7 @@ impl<'a> Formatter<'a> {
                         whitespace,
                         right,
                         &mut edits,
-                        self.indent_level,
+                        self.indent(self.indent_level),
                     );
                 }
                 // else do nothing, preserving the user's spaces before the comment
@@ -318,7 +421,7 @@ impl<'a> Formatter<'a> {
                         whitespace,
                         right,
                         &mut edits,
-                        self.indent_level,
+                        self.indent(self.indent_level),
                     );
                 }
                 (_, Semi) => {
@@ -346,7 +449,7 @@ impl<'a> Formatter<'a> {
                         whitespace,
                         right,
                         &mut edits,
-                        self.indent_level,
+                        self.indent(self.indent_level),
                     );
                 }
                 (Comma, _) if matches!(newline_context, NewlineContext::Newlines) => {
@@ -355,7 +458,7 @@ impl<'a> Formatter<'a> {
                         whitespace,
                         right,
                         &mut edits,
-                        self.indent_level,
+                        self.indent(self.indent_level),
                     );
                 }
                 (Comma, _) => {
@@ -370,7 +473,7 @@ impl<'a> Formatter<'a> {
                         whitespace,
                         right,
                         &mut edits,
-                        self.indent_level,
+                        self.indent(self.indent_level),
                     );
                 }
                 (Open(Delim::Bracket | Delim::Paren), _)
@@ -404,7 +507,7 @@ impl<'a> Formatter<'a> {
                         whitespace,
                         right,
                         &mut edits,
-                        self.indent_level,
+                        self.indent(self.indent_level),
                     );
                 }
                 (_, Keyword(Keyword::Until))
@@ -430,7 +533,7 @@ impl<'a> Formatter<'a> {
                         whitespace,
                         right,
                         &mut edits,
-                        self.indent_level,
+                        self.indent(self.indent_level),
                     );
                 }
                 (String(StringToken::Interpolated(_, InterpolatedEnding::LBrace)), _)
@@ -502,7 +605,7 @@ impl<'a> Formatter<'a> {
                         whitespace,
                         right,
                         &mut edits,
-                        self.indent_level + 1,
+                        self.indent(self.indent_level + 1),
                     );
                 }
                 (_, _) if is_bin_op(cooked_right) => {
@@ -512,9 +615,59 @@ impl<'a> Formatter<'a> {
             },
             _ => {}
         }
+        self.apply_trailing_comma_policy(left, whitespace, right, newline_context, &mut edits);
         edits
     }
 
+    /// Returns the number of spaces of indentation for the given indent level.
+    fn indent(&self, level: usize) -> usize {
+        level * self.config.indent_width
+    }
+
+    /// Adds or removes the trailing comma of the list closed by the right token, combining
+    /// the change with any edit of the whitespace before the closing delimiter.
+    fn apply_trailing_comma_policy(
+        &self,
+        left: &ConcreteToken,
+        whitespace: &str,
+        right: &ConcreteToken,
+        newline_context: NewlineContext,
+        edits: &mut Vec<TextEdit>,
+    ) {
+        let Some(group) = self.groups.closed_by(right.span.lo) else {
+            return;
+        };
+        if !group.allows_trailing_comma() || matches!(left.kind, ConcreteTokenKind::Comment) {
+            return;
+        }
+        let is_multiline = matches!(newline_context, NewlineContext::Newlines);
+        let wants_trailing_comma = match self.config.trailing_comma {
+            TrailingComma::Preserve => return,
+            TrailingComma::Multiline => is_multiline,
+            TrailingComma::Never => false,
+        };
+        if wants_trailing_comma == group.has_trailing_comma {
+            return;
+        }
+
+        // Take over the edit of the whitespace between the two tokens, if there is one.
+        let gap = edits
+            .iter()
+            .position(|edit| edit.span.lo == left.span.hi && edit.span.hi == right.span.lo)
+            .map_or_else(|| whitespace.to_string(), |i| edits.remove(i).new_text);
+        if wants_trailing_comma {
+            edits.push(TextEdit::new(
+                &format!(",{gap}"),
+                left.span.hi,
+                right.span.lo,
+            ));
+        } else {
+            // The left token is the trailing comma.
+            let gap = if is_multiline { gap.as_str() } else { "" };
+            edits.push(TextEdit::new(gap, left.span.lo, right.span.lo));
+        }
+    }
+
     fn update_spec_decl_state(&mut self, left_kind: &ConcreteTokenKind) {
         use qsc_frontend::keyword::Keyword;
         use ConcreteTokenKind::*;
@@ -668,10 +821,6 @@ impl<'a> Formatter<'a> {
 
 // Helper Functions
 
-fn make_indent_string(level: usize) -> String {
-    "    ".repeat(level)
-}
-
 fn get_token_contents<'a>(code: &'a str, token: &ConcreteToken) -> &'a str {
     &code[token.span.lo as usize..token.span.hi as usize]
 }
@@ -868,7 +1017,7 @@ fn effect_correct_indentation(
     whitespace: &str,
     right: &ConcreteToken,
     edits: &mut Vec<TextEdit>,
-    indent_level: usize,
+    indent: usize,
 ) {
     let mut count_newlines = whitespace.chars().filter(|c| *c == '\n').count();
 
@@ -882,7 +1031,7 @@ fn effect_correct_indentation(
     } else {
         "\n".repeat(count_newlines)
     };
-    new_whitespace.push_str(&make_indent_string(indent_level));
+    new_whitespace.push_str(&" ".repeat(indent));
     if whitespace != new_whitespace {
         edits.push(TextEdit::new(
             new_whitespace.as_str(),
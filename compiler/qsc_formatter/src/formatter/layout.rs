@@ -0,0 +1,291 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+//! Line-width-aware layout. The rule engine decides the spacing between each pair
+//! of tokens, so wrapping a list only requires telling it which lists to break:
+//! a broken list gets a newline after its open delimiter, which puts each of its
+//! items on its own line just like a list the user already split over several lines.
+//! After each pass of the rule engine, the resulting lines are measured and, for each
+//! line that is too long, the outermost list on it that can still be broken is chosen.
+
+use super::TextEdit;
+use qsc_frontend::lex::{
+    concrete::{ConcreteToken, ConcreteTokenKind},
+    cooked::{StringToken, TokenKind},
+    Delim, InterpolatedEnding, InterpolatedStart,
+};
+use rustc_hash::{FxHashMap, FxHashSet};
+
+/// A pair of matching delimiters and the tokens between them.
+pub(super) struct Group {
+    /// The index of the open delimiter token.
+    open: usize,
+    /// The index of the close delimiter token, if the group is closed.
+    close: Option<usize>,
+    /// The innermost group containing this one.
+    parent: Option<usize>,
+    delim: Delim,
+    /// The number of commas directly inside the group, including any trailing comma.
+    commas: usize,
+    pub(super) has_trailing_comma: bool,
+    /// Whether the last item is the `...` of a specialization argument list.
+    ends_with_ellipsis: bool,
+    /// Whether there is an `=` directly inside the group, as in `[0, size = 3]`.
+    has_eq: bool,
+    /// Whether the group is an argument of an interpolated string.
+    in_interpolation: bool,
+}
+
+impl Group {
+    /// Whether the group is a parenthesized or bracketed list of at least two items.
+    fn is_list(&self) -> bool {
+        matches!(self.delim, Delim::Paren | Delim::Bracket)
+            && self.commas > usize::from(self.has_trailing_comma)
+    }
+
+    /// Whether a trailing comma can be added to or removed from the group without
+    /// changing the meaning of the code.
+    pub(super) fn allows_trailing_comma(&self) -> bool {
+        self.is_list() && !self.ends_with_ellipsis && !self.has_eq && !self.in_interpolation
+    }
+
+    fn can_break(&self) -> bool {
+        self.is_list() && !self.in_interpolation
+    }
+}
+
+pub(super) struct Groups {
+    groups: Vec<Group>,
+    /// The group closed by the close delimiter at each offset.
+    by_close: FxHashMap<u32, usize>,
+}
+
+impl Groups {
+    pub(super) fn new(tokens: &[ConcreteToken]) -> Self {
+        let mut groups: Vec<Group> = Vec::new();
+        let mut stack: Vec<usize> = Vec::new();
+        let mut by_close = FxHashMap::default();
+        let mut interpolation_depth = 0usize;
+        let mut previous: [Option<usize>; 2] = [None, None];
+
+        for (index, token) in tokens.iter().enumerate() {
+            let ConcreteTokenKind::Syntax(kind) = &token.kind else {
+                continue;
+            };
+            match kind {
+                TokenKind::Open(delim) => {
+                    groups.push(Group {
+                        open: index,
+                        close: None,
+                        parent: stack.last().copied(),
+                        delim: *delim,
+                        commas: 0,
+                        has_trailing_comma: false,
+                        ends_with_ellipsis: false,
+                        has_eq: false,
+                        in_interpolation: interpolation_depth > 0,
+                    });
+                    stack.push(groups.len() - 1);
+                }
+                TokenKind::Close(delim) => {
+                    if let Some(&top) = stack.last() {
+                        if groups[top].delim == *delim {
+                            stack.pop();
+                            let is_kind = |i: Option<usize>, expected: &TokenKind| {
+                                i.is_some_and(|i| {
+                                    matches!(&tokens[i].kind, ConcreteTokenKind::Syntax(k) if k == expected)
+                                })
+                            };
+                            let group = &mut groups[top];
+                            group.close = Some(index);
+                            group.has_trailing_comma = previous[1] != Some(group.open)
+                                && is_kind(previous[1], &TokenKind::Comma);
+                            let last_item = if group.has_trailing_comma {
+                                previous[0]
+                            } else {
+                                previous[1]
+                            };
+                            group.ends_with_ellipsis = is_kind(last_item, &TokenKind::DotDotDot);
+                            by_close.insert(token.span.lo, top);
+                        }
+                    }
+                }
+                TokenKind::Comma => {
+                    if let Some(&top) = stack.last() {
+                        groups[top].commas += 1;
+                    }
+                }
+                TokenKind::Eq => {
+                    if let Some(&top) = stack.last() {
+                        groups[top].has_eq = true;
+                    }
+                }
+                TokenKind::String(StringToken::Interpolated(start, ending)) => {
+                    match (start, ending) {
+                        (InterpolatedStart::DollarQuote, InterpolatedEnding::LBrace) => {
+                            interpolation_depth += 1;
+                        }
+                        (InterpolatedStart::RBrace, InterpolatedEnding::Quote) => {
+                            interpolation_depth = interpolation_depth.saturating_sub(1);
+                        }
+                        _ => {}
+                    }
+                }
+                _ => {}
+            }
+            previous = [previous[1], Some(index)];
+        }
+
+        Self { groups, by_close }
+    }
+
+    /// Returns the group closed by the close delimiter at the given offset.
+    pub(super) fn closed_by(&self, offset: u32) -> Option<&Group> {
+        self.by_close.get(&offset).map(|&i| &self.groups[i])
+    }
+
+    /// Measures the code after applying the edits and returns the offsets of the open
+    /// delimiters of the lists to break next, at most one for each line that is too long.
+    pub(super) fn breaks_for_long_lines(
+        &self,
+        code: &str,
+        tokens: &[ConcreteToken],
+        edits: &[TextEdit],
+        breaks: &FxHashSet<u32>,
+        max_line_width: usize,
+    ) -> Vec<u32> {
+        let layout = Layout::new(code, edits);
+
+        // The lists that could be broken, grouped by the long line they start on.
+        let mut candidates: FxHashMap<usize, Vec<(usize, usize)>> = FxHashMap::default();
+        for (index, group) in self.groups.iter().enumerate() {
+            let open = &tokens[group.open];
+            if group.close.is_none() || !group.can_break() || breaks.contains(&open.span.lo) {
+                continue;
+            }
+            let line = layout.line(open.span.lo);
+            if layout.width(line) <= max_line_width {
+                continue;
+            }
+            // Lists whose first item is already on the next line are broken.
+            let first = tokens[group.open + 1..]
+                .iter()
+                .find(|token| !matches!(token.kind, ConcreteTokenKind::WhiteSpace));
+            if first.map_or(true, |token| {
+                matches!(token.kind, ConcreteTokenKind::Comment)
+                    || layout.line(token.span.lo) != line
+            }) {
+                continue;
+            }
+            let depth = self
+                .ancestors(index)
+                .filter(|&ancestor| layout.line(tokens[self.groups[ancestor].open].span.lo) == line)
+                .count();
+            candidates.entry(line).or_default().push((depth, index));
+        }
+
+        let mut new_breaks = Vec::new();
+        for (line, candidates) in candidates {
+            let min_depth = candidates
+                .iter()
+                .map(|(depth, _)| *depth)
+                .min()
+                .unwrap_or_default();
+            let outermost = candidates
+                .iter()
+                .filter(|(depth, _)| *depth == min_depth)
+                .map(|(_, index)| &self.groups[*index])
+                .collect::<Vec<_>>();
+            // Prefer the first list that runs past the limit, otherwise the last one,
+            // which moves the most code off the line.
+            let overflows = |group: &&Group| {
+                let close = tokens[group.close.expect("group should be closed")].span.lo;
+                layout.line(close) != line || layout.column(close) + 1 > max_line_width
+            };
+            if let Some(group) = outermost
+                .iter()
+                .find(|group| overflows(group))
+                .or(outermost.last())
+            {
+                new_breaks.push(tokens[group.open].span.lo);
+            }
+        }
+        new_breaks
+    }
+
+    fn ancestors(&self, index: usize) -> impl Iterator<Item = usize> + '_ {
+        std::iter::successors(self.groups[index].parent, |&i| self.groups[i].parent)
+    }
+}
+
+/// The lines of the formatted code, with a mapping from offsets in the original code.
+struct Layout {
+    formatted: String,
+    /// The end offset of each edit in the original code, sorted, with the total change
+    /// in length of all edits up to and including it.
+    shifts: Vec<(u32, isize)>,
+    line_starts: Vec<usize>,
+}
+
+impl Layout {
+    fn new(code: &str, edits: &[TextEdit]) -> Self {
+        let mut sorted = edits.iter().collect::<Vec<_>>();
+        sorted.sort_by_key(|edit| (edit.span.lo, edit.span.hi));
+
+        let mut formatted = String::with_capacity(code.len());
+        let mut shifts = Vec::with_capacity(sorted.len());
+        let mut copied = 0;
+        let mut shift = 0isize;
+        for edit in sorted {
+            let (lo, hi) = (edit.span.lo as usize, edit.span.hi as usize);
+            formatted.push_str(&code[copied..lo]);
+            formatted.push_str(&edit.new_text);
+            copied = hi;
+            shift += edit.new_text.len() as isize - (hi - lo) as isize;
+            shifts.push((edit.span.hi, shift));
+        }
+        formatted.push_str(&code[copied..]);
+
+        let line_starts = std::iter::once(0)
+            .chain(formatted.match_indices('\n').map(|(i, _)| i + 1))
+            .collect();
+        Self {
+            formatted,
+            shifts,
+            line_starts,
+        }
+    }
+
+    /// Maps an offset of the start of a token in the original code to the formatted code.
+    fn offset(&self, offset: u32) -> usize {
+        let applied = self.shifts.partition_point(|(hi, _)| *hi <= offset);
+        let shift = applied.checked_sub(1).map_or(0, |i| self.shifts[i].1);
+        (offset as isize + shift) as usize
+    }
+
+    fn line(&self, offset: u32) -> usize {
+        self.line_starts
+            .partition_point(|start| *start <= self.offset(offset))
+            - 1
+    }
+
+    /// The column, in characters, of the token starting at the given original offset.
+    fn column(&self, offset: u32) -> usize {
+        let offset = self.offset(offset);
+        let start = self.line_starts[self.line_starts.partition_point(|s| *s <= offset) - 1];
+        self.formatted[start..offset].chars().count()
+    }
+
+    /// The width, in characters, of the given line.
+    fn width(&self, line: usize) -> usize {
+        let start = self.line_starts[line];
+        let end = self
+            .line_starts
+            .get(line + 1)
+            .map_or(self.formatted.len(), |next| next - 1);
+        self.formatted[start..end]
+            .trim_end_matches('\r')
+            .chars()
+            .count()
+    }
+}
@@ -1,6 +1,7 @@
 // Copyright (c) Microsoft Corporation.
 // Licensed under the MIT License.
 
+use super::{FormatterConfig, TrailingComma};
 use expect_test::{expect, Expect};
 use indoc::indoc;
 
@@ -14,6 +15,23 @@ fn check_edits(input: &str, expect: &Expect) {
     expect.assert_debug_eq(&actual);
 }
 
+fn check_with_config(input: &str, config: &FormatterConfig, expect: &Expect) {
+    let actual = super::format_str_with_config(input, config);
+    expect.assert_eq(&actual);
+    // Formatting is idempotent
+    assert!(
+        super::calculate_format_edits_with_config(&actual, config).is_empty(),
+        "formatting the output again should not produce edits"
+    );
+}
+
+fn width(max_line_width: usize) -> FormatterConfig {
+    FormatterConfig {
+        max_line_width: Some(max_line_width),
+        ..FormatterConfig::default()
+    }
+}
+
 // Removing trailing whitespace from lines
 
 #[test]
@@ -1382,3 +1400,374 @@ namespace Microsoft.Quantum.Arrays {
     "#]],
     );
 }
+
+// Wrapping lists that don't fit in the configured line width
+
+#[test]
+fn lines_are_not_wrapped_without_max_line_width() {
+    let input = "operation Foo() : Unit { let x = Bar(first, second, third, fourth, fifth, sixth, seventh, eighth); }";
+    assert_eq!(
+        super::format_str(input),
+        super::format_str_with_config(input, &FormatterConfig::default())
+    );
+    check(
+        input,
+        &expect![[r#"
+            operation Foo() : Unit {
+                let x = Bar(first, second, third, fourth, fifth, sixth, seventh, eighth);
+            }"#]],
+    );
+}
+
+#[test]
+fn call_arguments_wrap_to_fit_width() {
+    check_with_config(
+        indoc! {"
+            operation Foo() : Unit {
+                let x = Bar(first, second, third, fourth);
+                let y = Bar(a, b);
+            }
+        "},
+        &width(40),
+        &expect![[r#"
+            operation Foo() : Unit {
+                let x = Bar(
+                    first,
+                    second,
+                    third,
+                    fourth
+                );
+                let y = Bar(a, b);
+            }
+        "#]],
+    );
+}
+
+#[test]
+fn array_and_tuple_literals_wrap_to_fit_width() {
+    check_with_config(
+        indoc! {"
+            function Foo() : Unit {
+                let arr = [1000, 2000, 3000, 4000, 5000];
+                let tup = (\"first\", \"second\", \"third\");
+            }
+        "},
+        &width(40),
+        &expect![[r#"
+            function Foo() : Unit {
+                let arr = [
+                    1000,
+                    2000,
+                    3000,
+                    4000,
+                    5000
+                ];
+                let tup = (
+                    "first",
+                    "second",
+                    "third"
+                );
+            }
+        "#]],
+    );
+}
+
+#[test]
+fn callable_signatures_wrap_to_fit_width() {
+    check_with_config(
+        "operation ApplyRotation(angle : Double, target : Qubit, control : Qubit) : Unit is Adj + Ctl {}",
+        &width(60),
+        &expect![[r#"
+            operation ApplyRotation(
+                angle : Double,
+                target : Qubit,
+                control : Qubit
+            ) : Unit is Adj + Ctl {}"#]],
+    );
+}
+
+#[test]
+fn outermost_list_wraps_first() {
+    check_with_config(
+        indoc! {"
+            function Foo() : Unit {
+                Outer(Inner(first, second, third), Other(fourth, fifth, sixth), seventh);
+            }
+        "},
+        &width(50),
+        &expect![[r#"
+            function Foo() : Unit {
+                Outer(
+                    Inner(first, second, third),
+                    Other(fourth, fifth, sixth),
+                    seventh
+                );
+            }
+        "#]],
+    );
+}
+
+#[test]
+fn nested_lists_wrap_until_they_fit() {
+    check_with_config(
+        indoc! {"
+            function Foo() : Unit {
+                Outer(Inner(first, second, third, fourth), fifth);
+            }
+        "},
+        &width(30),
+        &expect![[r#"
+            function Foo() : Unit {
+                Outer(
+                    Inner(
+                        first,
+                        second,
+                        third,
+                        fourth
+                    ),
+                    fifth
+                );
+            }
+        "#]],
+    );
+}
+
+#[test]
+fn overflowing_list_wraps_instead_of_earlier_list() {
+    check_with_config(
+        indoc! {"
+            function Foo() : Unit {
+                let x = Short(a, b) + Long(first, second, third);
+            }
+        "},
+        &width(50),
+        &expect![[r#"
+            function Foo() : Unit {
+                let x = Short(a, b) + Long(
+                    first,
+                    second,
+                    third
+                );
+            }
+        "#]],
+    );
+}
+
+#[test]
+fn single_item_groups_and_interpolated_strings_are_not_wrapped() {
+    check_with_config(
+        indoc! {"
+            function Foo() : Unit {
+                let x = VeryLongFunctionName(AnotherVeryLongArgumentName);
+                Message($\"{Format(first, second, third)}\");
+            }
+        "},
+        &width(30),
+        &expect![[r#"
+            function Foo() : Unit {
+                let x = VeryLongFunctionName(AnotherVeryLongArgumentName);
+                Message($"{Format(first, second, third)}");
+            }
+        "#]],
+    );
+}
+
+#[test]
+fn lines_within_width_keep_user_line_breaks() {
+    check_with_config(
+        indoc! {"
+            function Foo() : Unit {
+                let x = Bar(
+                    a, b
+                );
+            }
+        "},
+        &width(100),
+        &expect![[r#"
+            function Foo() : Unit {
+                let x = Bar(
+                    a,
+                    b
+                );
+            }
+        "#]],
+    );
+}
+
+#[test]
+fn wrapping_produces_minimal_edits() {
+    let edits = super::calculate_format_edits_with_config(
+        indoc! {"
+            function Foo() : Unit {
+                Bar(first, second, third);
+                Bar(x, y);
+            }
+        "},
+        &width(25),
+    );
+    expect![[r#"
+        [
+            TextEdit {
+                new_text: "\n        ",
+                span: Span {
+                    lo: 32,
+                    hi: 32,
+                },
+            },
+            TextEdit {
+                new_text: "\n        ",
+                span: Span {
+                    lo: 38,
+                    hi: 39,
+                },
+            },
+            TextEdit {
+                new_text: "\n        ",
+                span: Span {
+                    lo: 46,
+                    hi: 47,
+                },
+            },
+            TextEdit {
+                new_text: "\n    ",
+                span: Span {
+                    lo: 52,
+                    hi: 52,
+                },
+            },
+        ]
+    "#]]
+    .assert_debug_eq(&edits);
+}
+
+// Configurable indent width
+
+#[test]
+fn indent_width_is_configurable() {
+    check_with_config(
+        indoc! {"
+            operation Foo() : Unit {
+                if true {
+                    let x = Bar(a, b);
+                }
+            }
+        "},
+        &FormatterConfig {
+            indent_width: 2,
+            max_line_width: Some(20),
+            ..FormatterConfig::default()
+        },
+        &expect![[r#"
+            operation Foo() : Unit {
+              if true {
+                let x = Bar(
+                  a,
+                  b
+                );
+              }
+            }
+        "#]],
+    );
+}
+
+// Trailing-comma policy
+
+#[test]
+fn trailing_comma_multiline_adds_to_wrapped_lists() {
+    check_with_config(
+        indoc! {"
+            function Foo() : Unit {
+                let x = Bar(first, second, third);
+                let y = [1, 2,];
+            }
+        "},
+        &FormatterConfig {
+            max_line_width: Some(30),
+            trailing_comma: TrailingComma::Multiline,
+            ..FormatterConfig::default()
+        },
+        &expect![[r#"
+            function Foo() : Unit {
+                let x = Bar(
+                    first,
+                    second,
+                    third,
+                );
+                let y = [1, 2];
+            }
+        "#]],
+    );
+}
+
+#[test]
+fn trailing_comma_never_removes_trailing_commas() {
+    check_with_config(
+        indoc! {"
+            function Foo() : Unit {
+                let x = Bar(
+                    first,
+                    second,
+                );
+                let y = [1, 2,];
+            }
+        "},
+        &FormatterConfig {
+            trailing_comma: TrailingComma::Never,
+            ..FormatterConfig::default()
+        },
+        &expect![[r#"
+            function Foo() : Unit {
+                let x = Bar(
+                    first,
+                    second
+                );
+                let y = [1, 2];
+            }
+        "#]],
+    );
+}
+
+#[test]
+fn trailing_comma_policy_keeps_meaningful_commas() {
+    check_with_config(
+        indoc! {"
+            operation Foo() : Unit {
+                let single = (1,);
+                let repeated = [
+                    0,
+                    size = 3
+                ];
+                controlled (cs, ...) {}
+            }
+        "},
+        &FormatterConfig {
+            trailing_comma: TrailingComma::Multiline,
+            ..FormatterConfig::default()
+        },
+        &expect![[r#"
+            operation Foo() : Unit {
+                let single = (1, );
+                let repeated = [
+                    0,
+                    size = 3
+                ];
+                controlled (cs, ...) {}
+            }
+        "#]],
+    );
+}
+
+#[test]
+fn config_deserializes_from_manifest_format_section() {
+    let config: FormatterConfig =
+        serde_json::from_str(r#"{ "maxLineWidth": 80, "trailingComma": "multiline" }"#)
+            .expect("config should deserialize");
+    assert_eq!(
+        config,
+        FormatterConfig {
+            max_line_width: Some(80),
+            indent_width: 4,
+            trailing_comma: TrailingComma::Multiline,
+        }
+    );
+}
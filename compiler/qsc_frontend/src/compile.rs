@@ -35,6 +35,7 @@ use qsc_hir::{
     validate::Validator as HirValidator,
     visit::Visitor as _,
 };
+use serde::{Deserialize, Serialize};
 use std::{fmt::Debug, sync::Arc};
 use thiserror::Error;
 
@@ -64,7 +65,7 @@ pub struct AstPackage {
     pub locals: Locals,
 }
 
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Serialize, Deserialize)]
 pub struct SourceMap {
     sources: Vec<Source>,
     /// The common prefix of the sources
@@ -172,7 +173,7 @@ impl SourceMap {
     }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Source {
     pub name: SourceName,
     pub contents: SourceContents,
@@ -316,6 +317,14 @@ impl OpenPackageStore {
         )
     }
 
+    /// Replaces a package that is not the open package with a newly compiled unit.
+    /// The open package is cleared, since its contents may refer to the replaced package.
+    pub fn replace(&mut self, id: PackageId, unit: CompileUnit) {
+        assert_ne!(id, self.open, "open package should not be replaced");
+        self.store.units.insert(id, unit);
+        self.store.units.insert(self.open, CompileUnit::default());
+    }
+
     /// Consumes the `OpenPackageStore` and returns a `PackageStore`
     /// along with the id of the formerly open package.
     #[must_use]
@@ -430,6 +439,26 @@ pub fn compile_ast(
     }
 }
 
+/// The sources of the core library.
+#[must_use]
+pub fn core_sources() -> SourceMap {
+    let core: Vec<(SourceName, SourceContents)> = library::CORE_LIB
+        .iter()
+        .map(|(name, contents)| ((*name).into(), (*contents).into()))
+        .collect();
+    SourceMap::new(core, None)
+}
+
+/// The sources of the standard library.
+#[must_use]
+pub fn std_sources() -> SourceMap {
+    let std: Vec<(SourceName, SourceContents)> = library::STD_LIB
+        .iter()
+        .map(|(name, contents)| ((*name).into(), (*contents).into()))
+        .collect();
+    SourceMap::new(std, None)
+}
+
 /// Compiles the core library.
 ///
 /// # Panics
@@ -443,16 +472,10 @@ pub fn core() -> CompileUnit {
         next_id: PackageId::CORE,
     };
 
-    let core: Vec<(SourceName, SourceContents)> = library::CORE_LIB
-        .iter()
-        .map(|(name, contents)| ((*name).into(), (*contents).into()))
-        .collect();
-    let sources = SourceMap::new(core, None);
-
     let mut unit = compile(
         &store,
         &[],
-        sources,
+        core_sources(),
         TargetCapabilityFlags::empty(),
         LanguageFeatures::default(),
     );
@@ -467,16 +490,10 @@ pub fn core() -> CompileUnit {
 /// Panics if the standard library does not compile without errors.
 #[must_use]
 pub fn std(store: &PackageStore, capabilities: TargetCapabilityFlags) -> CompileUnit {
-    let std: Vec<(SourceName, SourceContents)> = library::STD_LIB
-        .iter()
-        .map(|(name, contents)| ((*name).into(), (*contents).into()))
-        .collect();
-    let sources = SourceMap::new(std, None);
-
     let mut unit = compile(
         store,
         &[(PackageId::CORE, None)],
-        sources,
+        std_sources(),
         capabilities,
         LanguageFeatures::default(),
     );
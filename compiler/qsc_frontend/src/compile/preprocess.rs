@@ -7,6 +7,7 @@ use qsc_ast::{
     mut_visit::MutVisitor,
 };
 use qsc_hir::hir;
+use serde::{Deserialize, Serialize};
 use std::rc::Rc;
 
 use super::TargetCapabilityFlags;
@@ -14,7 +15,7 @@ use super::TargetCapabilityFlags;
 #[cfg(test)]
 mod tests;
 
-#[derive(PartialEq, Hash, Clone, Debug)]
+#[derive(PartialEq, Hash, Clone, Debug, Serialize, Deserialize)]
 pub struct TrackedName {
     pub name: Rc<str>,
     pub namespace: Rc<str>,
@@ -24,7 +24,7 @@ use thiserror::Error;
 #[derive(Clone, Debug, Diagnostic, Error)]
 pub(super) enum Error {
     #[error("unknown attribute {0}")]
-    #[diagnostic(help("supported attributes are: EntryPoint, Config"))]
+    #[diagnostic(help("supported attributes are: EntryPoint, Config, Allow"))]
     #[diagnostic(code("Qsc.LowerAst.UnknownAttr"))]
     UnknownAttr(String, #[label] Span),
     #[error("invalid attribute arguments: expected {0}")]
@@ -350,6 +350,16 @@ impl With<'_> {
                     None
                 }
             },
+            Ok(hir::Attr::Allow) => {
+                // The lint names are only consumed by the linter, which reads them from the AST.
+                if !is_lint_names_arg(&attr.arg) {
+                    self.lowerer.errors.push(Error::InvalidAttrArgs(
+                        "lint names as string literals".to_string(),
+                        attr.arg.span,
+                    ));
+                }
+                None
+            }
             Err(()) => {
                 self.lowerer.errors.push(Error::UnknownAttr(
                     attr.name.name.to_string(),
@@ -1000,3 +1010,14 @@ fn is_partial_app(arg: &ast::Expr) -> bool {
         _ => false,
     }
 }
+
+/// Checks that the argument is a string literal or a tuple of string literals, as in
+/// `@Allow("needlessParens")` or `@Allow("needlessParens", "divisionByZero")`.
+fn is_lint_names_arg(arg: &ast::Expr) -> bool {
+    let is_string = |expr: &ast::Expr| matches!(expr.kind.as_ref(), ast::ExprKind::Lit(lit) if matches!(lit.as_ref(), ast::Lit::String(_)));
+    match arg.kind.as_ref() {
+        ast::ExprKind::Paren(inner) => is_string(inner),
+        ast::ExprKind::Tuple(items) => !items.is_empty() && items.iter().all(|i| is_string(i)),
+        _ => false,
+    }
+}
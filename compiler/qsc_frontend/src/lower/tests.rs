@@ -131,6 +131,49 @@ fn test_target_profile_attr_wrong_args() {
     );
 }
 
+#[test]
+fn test_allow_attr_allowed() {
+    check_errors(
+        indoc! {r#"
+            namespace input {
+                @Allow("needlessParens")
+                @Allow("needlessOperation", "divisionByZero")
+                operation Foo() : Unit {
+                    body ... {}
+                }
+            }
+        "#},
+        &expect![[r#"
+            []
+        "#]],
+    );
+}
+
+#[test]
+fn test_allow_attr_wrong_args() {
+    check_errors(
+        indoc! {"
+            namespace input {
+                @Allow(needlessParens)
+                operation Foo() : Unit {
+                    body ... {}
+                }
+            }
+        "},
+        &expect![[r#"
+            [
+                InvalidAttrArgs(
+                    "lint names as string literals",
+                    Span {
+                        lo: 28,
+                        hi: 44,
+                    },
+                ),
+            ]
+        "#]],
+    );
+}
+
 #[test]
 fn test_unknown_attr() {
     check_errors(
@@ -5,12 +5,13 @@ use crate::{
     hir::{Block, CallableDecl, Expr, Ident, LocalItemId, NodeId, Pat, QubitInit, SpecDecl, Stmt},
     mut_visit::{self, MutVisitor},
 };
+use serde::{Deserialize, Serialize};
 
 /// The [Assigner] tracks the current state of IDs being handed out within a pass of the resolver.
 /// It is used when visiting a package to assign IDs to all elements. Identifiers are resolved and
 /// replaced with canonical IDs in this process. The AST gets all IDs resolved after the symbol resolution
 /// run.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct Assigner {
     next_node: NodeId,
     next_item: LocalItemId,
@@ -8,6 +8,7 @@ use crate::ty::{Arrow, FunctorSet, FunctorSetValue, GenericArg, GenericParam, Sc
 use indenter::{indented, Indented};
 use num_bigint::BigInt;
 use qsc_data_structures::{index_map::IndexMap, span::Span};
+use serde::{Deserialize, Serialize};
 use std::{
     cmp::Ordering,
     fmt::{self, Debug, Display, Formatter, Write},
@@ -30,7 +31,7 @@ fn set_indentation<'a, 'b>(
 }
 
 /// A unique identifier for an HIR node.
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
 pub struct NodeId(u32);
 
 impl NodeId {
@@ -110,7 +111,7 @@ impl Hash for NodeId {
 }
 
 /// A unique identifier for a package within a package store.
-#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize, Deserialize)]
 pub struct PackageId(usize);
 
 impl PackageId {
@@ -143,7 +144,9 @@ impl From<usize> for PackageId {
 }
 
 /// A unique identifier for an item within a package.
-#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[derive(
+    Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize, Deserialize,
+)]
 pub struct LocalItemId(usize);
 
 impl LocalItemId {
@@ -173,7 +176,7 @@ impl From<LocalItemId> for usize {
 }
 
 /// A unique identifier for an item within a package store.
-#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize, Deserialize)]
 pub struct ItemId {
     /// The package ID or `None` for the local package.
     pub package: Option<PackageId>,
@@ -191,7 +194,7 @@ impl Display for ItemId {
 }
 
 /// The status of an item.
-#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize, Deserialize)]
 pub enum ItemStatus {
     /// The item is defined normally.
     Available,
@@ -214,7 +217,7 @@ impl ItemStatus {
 
 /// A resolution. This connects a usage of a name with the declaration of that name by uniquely
 /// identifying the node that declared it.
-#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
 pub enum Res {
     /// An invalid resolution.
     Err,
@@ -249,7 +252,7 @@ impl Display for Res {
 }
 
 /// The root node of the HIR.
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct Package {
     /// The items in the package.
     pub items: IndexMap<LocalItemId, Item>,
@@ -280,7 +283,7 @@ impl Display for Package {
 }
 
 /// An item.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Item {
     /// The ID.
     pub id: LocalItemId,
@@ -329,7 +332,7 @@ impl Display for Item {
 }
 
 /// An item kind.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub enum ItemKind {
     /// A `function` or `operation` declaration.
     Callable(CallableDecl),
@@ -365,7 +368,7 @@ impl Display for ItemKind {
 }
 
 /// A callable declaration header.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct CallableDecl {
     /// The node ID.
     pub id: NodeId,
@@ -448,7 +451,7 @@ impl Display for CallableDecl {
 }
 
 /// A specialization declaration.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct SpecDecl {
     /// The node ID.
     pub id: NodeId,
@@ -465,7 +468,7 @@ impl Display for SpecDecl {
 }
 
 /// The body of a specialization.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub enum SpecBody {
     /// The strategy to use to automatically generate the specialization.
     Gen(SpecGen),
@@ -492,7 +495,7 @@ impl Display for SpecBody {
 }
 
 /// A sequenced block of statements.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Block {
     /// The node ID.
     pub id: NodeId,
@@ -525,7 +528,7 @@ impl Display for Block {
 }
 
 /// A statement.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Stmt {
     /// The node ID.
     pub id: NodeId,
@@ -542,7 +545,7 @@ impl Display for Stmt {
 }
 
 /// A statement kind.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub enum StmtKind {
     /// An expression without a trailing semicolon.
     Expr(Expr),
@@ -584,7 +587,7 @@ impl Display for StmtKind {
 }
 
 /// An expression.
-#[derive(Clone, Debug, Default, PartialEq)]
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
 pub struct Expr {
     /// The node ID.
     pub id: NodeId,
@@ -607,7 +610,7 @@ impl Display for Expr {
 }
 
 /// An expression kind.
-#[derive(Clone, Debug, Default, PartialEq)]
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
 pub enum ExprKind {
     /// An array: `[a, b, c]`.
     Array(Vec<Expr>),
@@ -1031,7 +1034,7 @@ fn display_while(mut indent: Indented<Formatter>, cond: &Expr, block: &Block) ->
 }
 
 /// A field assignment in a struct constructor expression.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct FieldAssign {
     /// The node ID.
     pub id: NodeId,
@@ -1054,7 +1057,7 @@ impl Display for FieldAssign {
 }
 
 /// A string component.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub enum StringComponent {
     /// An expression.
     Expr(Expr),
@@ -1063,7 +1066,7 @@ pub enum StringComponent {
 }
 
 /// A pattern.
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub struct Pat {
     /// The node ID.
     pub id: NodeId,
@@ -1086,7 +1089,7 @@ impl Display for Pat {
 }
 
 /// A pattern kind.
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub enum PatKind {
     /// A binding.
     Bind(Ident),
@@ -1124,7 +1127,7 @@ impl Display for PatKind {
 }
 
 /// A qubit initializer.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct QubitInit {
     /// The node ID.
     pub id: NodeId,
@@ -1147,7 +1150,7 @@ impl Display for QubitInit {
 }
 
 /// A qubit initializer kind.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub enum QubitInitKind {
     /// An array of qubits: `Qubit[a]`.
     Array(Box<Expr>),
@@ -1189,7 +1192,7 @@ impl Display for QubitInitKind {
 /// A [`Idents`] represents a sequence of idents. It provides a helpful abstraction
 /// that is more powerful than a simple `Vec<Ident>`, and is primarily used to represent
 /// dot-separated paths.
-#[derive(Clone, Debug, Eq, Hash, PartialEq, Default)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq, Default, Serialize, Deserialize)]
 pub struct Idents(pub Box<[Ident]>);
 
 impl<'a> IntoIterator for &'a Idents {
@@ -1323,7 +1326,7 @@ impl Idents {
     }
 }
 /// An identifier.
-#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
 pub struct Ident {
     /// The node ID.
     pub id: NodeId,
@@ -1340,7 +1343,7 @@ impl Display for Ident {
 }
 
 /// An attribute.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub enum Attr {
     /// Provide pre-processing information about when an item should be included in compilation.
     Config,
@@ -1351,6 +1354,8 @@ pub enum Attr {
     /// Indicates that an item should be treated as an intrinsic callable for QIR code generation
     /// and any implementation should be ignored.
     SimulatableIntrinsic,
+    /// Suppresses the named lints within an item.
+    Allow,
 }
 
 impl FromStr for Attr {
@@ -1362,13 +1367,14 @@ impl FromStr for Attr {
             "EntryPoint" => Ok(Self::EntryPoint),
             "Unimplemented" => Ok(Self::Unimplemented),
             "SimulatableIntrinsic" => Ok(Self::SimulatableIntrinsic),
+            "Allow" => Ok(Self::Allow),
             _ => Err(()),
         }
     }
 }
 
 /// A field.
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub enum Field {
     /// A field path.
     Path(FieldPath),
@@ -1389,14 +1395,14 @@ impl Display for Field {
 }
 
 /// A path to a field in a tuple or user-defined type.
-#[derive(Clone, Debug, Default, Eq, Ord, PartialEq, PartialOrd)]
+#[derive(Clone, Debug, Default, Eq, Ord, PartialEq, PartialOrd, Serialize, Deserialize)]
 pub struct FieldPath {
     /// The tuple item indices to follow in order from top to bottom.
     pub indices: Vec<usize>,
 }
 
 /// A primitive field for a built-in type.
-#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize, Deserialize)]
 pub enum PrimField {
     /// The start of a range.
     Start,
@@ -1420,7 +1426,7 @@ impl FromStr for PrimField {
 }
 
 /// The visibility of a declaration.
-#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
 pub enum Visibility {
     /// Visible everywhere.
     Public,
@@ -1429,7 +1435,7 @@ pub enum Visibility {
 }
 
 /// A callable kind.
-#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
 pub enum CallableKind {
     /// A function.
     Function,
@@ -1447,7 +1453,7 @@ impl Display for CallableKind {
 }
 
 /// The mutability of a binding.
-#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
 pub enum Mutability {
     /// An immutable binding.
     Immutable,
@@ -1456,7 +1462,7 @@ pub enum Mutability {
 }
 
 /// The source of an allocated qubit.
-#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
 pub enum QubitSource {
     /// A qubit initialized to the zero state.
     Fresh,
@@ -1466,7 +1472,7 @@ pub enum QubitSource {
 }
 
 /// A literal.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub enum Lit {
     /// A big integer literal.
     BigInt(BigInt),
@@ -1497,7 +1503,7 @@ impl Display for Lit {
 }
 
 /// A measurement result.
-#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
 pub enum Result {
     /// The zero eigenvalue.
     Zero,
@@ -1506,7 +1512,7 @@ pub enum Result {
 }
 
 /// A Pauli operator.
-#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
 pub enum Pauli {
     /// The Pauli I operator.
     I,
@@ -1519,7 +1525,7 @@ pub enum Pauli {
 }
 
 /// A functor that may be applied to an operation.
-#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
 pub enum Functor {
     /// The adjoint functor.
     Adj,
@@ -1537,7 +1543,7 @@ impl Display for Functor {
 }
 
 /// A strategy for generating a specialization.
-#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
 pub enum SpecGen {
     /// Choose a strategy automatically.
     Auto,
@@ -1553,7 +1559,7 @@ pub enum SpecGen {
 }
 
 /// A unary operator.
-#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
 pub enum UnOp {
     /// A functor application.
     Functor(Functor),
@@ -1580,7 +1586,7 @@ impl Display for UnOp {
 }
 
 /// A binary operator.
-#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
 pub enum BinOp {
     /// Addition: `+`.
     Add,
@@ -1622,7 +1628,7 @@ pub enum BinOp {
     XorB,
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 /// Represents an export declaration.
 pub struct ExportDecl {
     /// The span.
@@ -4,6 +4,7 @@
 use indenter::{indented, Indented};
 use qsc_data_structures::span::Span;
 use rustc_hash::FxHashMap;
+use serde::{Deserialize, Serialize};
 
 use crate::hir::{CallableKind, FieldPath, Functor, ItemId, PackageId, Res};
 use std::{
@@ -24,7 +25,7 @@ fn set_indentation<'a, 'b>(
 }
 
 /// A type.
-#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
 pub enum Ty {
     /// An array type.
     Array(Box<Ty>),
@@ -266,7 +267,7 @@ impl Display for GenericParam {
 }
 
 /// The kind of a generic parameter.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub enum GenericParam {
     /// A type parameter.
     Ty(TypeParamName),
@@ -275,7 +276,7 @@ pub enum GenericParam {
 }
 
 /// The name of a generic type parameter.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct TypeParamName {
     /// The span.
     pub span: Span,
@@ -290,7 +291,7 @@ impl Display for TypeParamName {
 }
 
 /// A generic parameter ID.
-#[derive(Clone, Copy, Default, Debug, Eq, Hash, PartialEq)]
+#[derive(Clone, Copy, Default, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
 pub struct ParamId(u32);
 
 impl ParamId {
@@ -324,7 +325,7 @@ impl Display for ParamId {
 }
 
 /// An argument to a generic parameter.
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub enum GenericArg {
     /// A type argument.
     Ty(Ty),
@@ -342,7 +343,7 @@ impl Display for GenericArg {
 }
 
 /// An arrow type: `->` for a function or `=>` for an operation.
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub struct Arrow {
     /// Whether the callable is a function or an operation.
     pub kind: CallableKind,
@@ -382,7 +383,7 @@ impl Display for Arrow {
 }
 
 /// A primitive type.
-#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
 pub enum Prim {
     /// The big integer type.
     BigInt,
@@ -411,7 +412,7 @@ pub enum Prim {
 }
 
 /// A set of functors.
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub enum FunctorSet {
     /// An evaluated set.
     Value(FunctorSetValue),
@@ -447,7 +448,7 @@ impl Display for FunctorSet {
 }
 
 /// The value of a functor set.
-#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
 pub enum FunctorSetValue {
     /// The empty set.
     #[default]
@@ -524,7 +525,7 @@ impl Display for FunctorSetValue {
 }
 
 /// The item for a user-defined type.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Udt {
     /// The span.
     pub span: Span,
@@ -674,7 +675,7 @@ impl Display for Udt {
 }
 
 /// A UDT type definition.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct UdtDef {
     /// The span.
     pub span: Span,
@@ -689,7 +690,7 @@ impl Display for UdtDef {
 }
 
 /// A UDT type definition kind.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub enum UdtDefKind {
     /// A field definition with an optional name but required type.
     Field(UdtField),
@@ -723,7 +724,7 @@ impl Display for UdtDefKind {
 }
 
 /// A user-defined type.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct UdtField {
     /// The span of the field name.
     pub name_span: Option<Span>,
@@ -746,7 +747,7 @@ impl Display for UdtField {
 }
 
 /// A placeholder type variable used during type inference.
-#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq, Serialize, Deserialize)]
 pub struct InferTyId(usize);
 
 impl InferTyId {
@@ -776,7 +777,9 @@ impl From<InferTyId> for usize {
 }
 
 /// A placeholder functor variable used during type inference.
-#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[derive(
+    Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize, Deserialize,
+)]
 pub struct InferFunctorId(usize);
 
 impl InferFunctorId {
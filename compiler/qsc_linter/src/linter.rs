@@ -3,8 +3,9 @@
 
 pub(crate) mod ast;
 pub(crate) mod hir;
+pub(crate) mod suppression;
 
-use self::{ast::run_ast_lints, hir::run_hir_lints};
+use self::{ast::run_ast_lints, hir::run_hir_lints, suppression::apply_suppressions};
 use crate::lints::{ast::AstLint, hir::HirLint};
 use miette::{Diagnostic, LabeledSpan};
 use qsc_data_structures::span::Span;
@@ -32,7 +33,7 @@ pub fn run_lints(
     let mut lints = Vec::new();
     lints.append(&mut ast_lints);
     lints.append(&mut hir_lints);
-    lints
+    apply_suppressions(lints, compile_unit, config)
 }
 
 #[derive(Clone, Copy)]
@@ -73,6 +74,32 @@ impl Compilation<'_> {
         source.contents[lo..hi].to_string()
     }
 
+    /// Returns the span to delete in order to remove the code in `span`. If nothing else
+    /// is written on the lines it occupies, the span is extended to cover those lines entirely,
+    /// including their indentation and the trailing line break.
+    pub fn deletion_span(&self, span: Span) -> Span {
+        let source = self
+            .compile_unit
+            .sources
+            .find_by_offset(span.lo)
+            .expect("source should exist");
+
+        let lo = (span.lo - source.offset) as usize;
+        let hi = (span.hi - source.offset) as usize;
+        let before = &source.contents[..lo];
+        let before = &before[before.rfind('\n').map_or(0, |i| i + 1)..];
+        let after = &source.contents[hi..];
+        let after = after.find('\n').map_or(after, |i| &after[..=i]);
+        if before.trim().is_empty() && after.trim().is_empty() {
+            Span {
+                lo: span.lo - u32::try_from(before.len()).expect("offset should fit in u32"),
+                hi: span.hi + u32::try_from(after.len()).expect("offset should fit in u32"),
+            }
+        } else {
+            span
+        }
+    }
+
     /// Returns the indentation at the given offset.
     pub fn indentation_at_offset(&self, offset: u32) -> u32 {
         let source = self
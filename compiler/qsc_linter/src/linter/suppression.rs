@@ -0,0 +1,272 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+//! Inline lint suppression.
+//!
+//! Lints can be suppressed for a single item with the `@Allow("lintName")` attribute,
+//! or for the statement or item on the line following a `// qsharp-lint-ignore lintName` comment.
+//! A comment placed at the end of a line of code suppresses lints on that line instead.
+//! Several lints can be listed at once, separated by commas or whitespace.
+//!
+//! Suppressions that don't match any reported lint are reported through the
+//! [`UnusedSuppression`] lint.
+
+use crate::{
+    lints::ast::{AstLint, UnusedSuppression},
+    Lint, LintConfig, LintKind,
+};
+use qsc_ast::{
+    ast::{Attr, ExprKind, Item, Lit, Package, Stmt, TopLevelNode},
+    visit::{self, Visitor},
+};
+use qsc_data_structures::span::Span;
+use qsc_frontend::compile::CompileUnit;
+use qsc_hir::hir;
+use qsc_parse::lex::raw::{CommentKind, Lexer, TokenKind};
+use serde::{
+    de::{value::StrDeserializer, IntoDeserializer},
+    Deserialize,
+};
+use std::str::FromStr;
+
+/// The marker that introduces a suppression comment.
+const IGNORE_COMMENT: &str = "qsharp-lint-ignore";
+
+struct Suppression {
+    /// The suppressed lint, or `None` if the name doesn't match any lint.
+    kind: Option<LintKind>,
+    /// The span of the attribute or comment declaring the suppression.
+    decl_span: Span,
+    /// The range of code in which lints of `kind` are suppressed.
+    scope: Span,
+    used: bool,
+}
+
+/// Removes the lints covered by a suppression attribute or comment, and reports
+/// the suppressions that did not cover any lint.
+pub(crate) fn apply_suppressions(
+    lints: Vec<Lint>,
+    compile_unit: &CompileUnit,
+    config: Option<&[LintConfig]>,
+) -> Vec<Lint> {
+    let mut collector = SuppressionCollector::default();
+    collector.visit_package_nodes(&compile_unit.ast.package);
+    collector.collect_comments(compile_unit);
+
+    let mut suppressions = collector.suppressions;
+    if suppressions.is_empty() {
+        return lints;
+    }
+
+    let mut lints: Vec<Lint> = lints
+        .into_iter()
+        .filter(|lint| {
+            let mut suppressed = false;
+            for suppression in &mut suppressions {
+                if suppression.kind == Some(lint.kind)
+                    && suppression.scope.lo <= lint.span.lo
+                    && lint.span.hi <= suppression.scope.hi
+                {
+                    suppression.used = true;
+                    suppressed = true;
+                }
+            }
+            !suppressed
+        })
+        .collect();
+
+    let unused_kind = LintKind::Ast(AstLint::UnusedSuppression);
+    let unused_suppression = config
+        .unwrap_or(&[])
+        .iter()
+        .rev()
+        .find(|lint_config| lint_config.kind == unused_kind)
+        .map_or_else(UnusedSuppression::default, |lint_config| {
+            lint_config.level.into()
+        });
+
+    for suppression in suppressions.iter().filter(|s| !s.used) {
+        // A single declaration can name several lints, but is reported at most once.
+        if lints
+            .iter()
+            .any(|lint| lint.kind == unused_kind && lint.span == suppression.decl_span)
+        {
+            continue;
+        }
+        // Only offer to remove the declaration if none of the lints it names are in use.
+        let declaration_unused = suppressions
+            .iter()
+            .filter(|s| s.decl_span == suppression.decl_span)
+            .all(|s| !s.used);
+        let code_action_edits = if declaration_unused {
+            vec![(String::new(), suppression.decl_span)]
+        } else {
+            vec![]
+        };
+        lints.push(unused_suppression.lint(suppression.decl_span, code_action_edits));
+    }
+
+    lints
+}
+
+/// Parses a lint name as written in `qsharp.json`, e.g. `needlessParens`.
+fn parse_lint_kind(name: &str) -> Option<LintKind> {
+    let deserializer: StrDeserializer<'_, serde::de::value::Error> = name.into_deserializer();
+    LintKind::deserialize(deserializer).ok()
+}
+
+#[derive(Default)]
+struct SuppressionCollector {
+    suppressions: Vec<Suppression>,
+    /// Spans of the statements and items that a suppression comment can apply to.
+    targets: Vec<Span>,
+}
+
+impl SuppressionCollector {
+    fn visit_package_nodes(&mut self, package: &Package) {
+        for node in &package.nodes {
+            match node {
+                TopLevelNode::Namespace(namespace) => self.visit_namespace(namespace),
+                TopLevelNode::Stmt(stmt) => self.visit_stmt(stmt),
+            }
+        }
+    }
+
+    fn push_attr(&mut self, attr: &Attr, scope: Span) {
+        if hir::Attr::from_str(attr.name.name.as_ref()) != Ok(hir::Attr::Allow) {
+            return;
+        }
+        let names = match attr.arg.kind.as_ref() {
+            ExprKind::Paren(inner) => vec![inner.as_ref()],
+            ExprKind::Tuple(items) => items.iter().map(AsRef::as_ref).collect(),
+            _ => vec![],
+        };
+        for name in names {
+            if let ExprKind::Lit(lit) = name.kind.as_ref() {
+                if let Lit::String(name) = lit.as_ref() {
+                    self.suppressions.push(Suppression {
+                        kind: parse_lint_kind(name),
+                        decl_span: attr.span,
+                        scope,
+                        used: false,
+                    });
+                }
+            }
+        }
+    }
+
+    /// Finds the `// qsharp-lint-ignore` comments in the sources of the compile unit.
+    /// The sources are lexed so that `//` inside a string literal isn't taken for a comment.
+    fn collect_comments(&mut self, compile_unit: &CompileUnit) {
+        for source in compile_unit.sources.iter() {
+            let contents = source.contents.as_ref();
+            let mut tokens = Lexer::new(contents).peekable();
+            while let Some(token) = tokens.next() {
+                if token.kind != TokenKind::Comment(CommentKind::Normal) {
+                    continue;
+                }
+                let lo = token.offset as usize;
+                let hi = tokens
+                    .peek()
+                    .map_or(contents.len(), |next| next.offset as usize);
+                let comment = contents[lo..hi].trim_end();
+                let Some(names) = parse_ignore_comment(comment) else {
+                    continue;
+                };
+                let line_start = contents[..lo].rfind('\n').map_or(0, |index| index + 1);
+                let decl_span = Span {
+                    lo: source.offset + token.offset,
+                    hi: source.offset
+                        + u32::try_from(lo + comment.len()).expect("offset should fit in u32"),
+                };
+                let scope = if contents[line_start..lo].trim().is_empty() {
+                    let limit = next_code_line_end(contents, lo + comment.len());
+                    self.next_target(
+                        decl_span.hi,
+                        source.offset + u32::try_from(limit).expect("offset should fit in u32"),
+                    )
+                } else {
+                    Span {
+                        lo: source.offset
+                            + u32::try_from(line_start).expect("offset should fit in u32"),
+                        hi: decl_span.lo,
+                    }
+                };
+                for name in names {
+                    self.suppressions.push(Suppression {
+                        kind: parse_lint_kind(name),
+                        decl_span,
+                        scope,
+                        used: false,
+                    });
+                }
+            }
+        }
+    }
+
+    /// Returns the span of the outermost statement or item that starts between `offset` and
+    /// `limit`, or an empty span if there is none.
+    fn next_target(&self, offset: u32, limit: u32) -> Span {
+        self.targets
+            .iter()
+            .filter(|span| offset <= span.lo && span.lo < limit)
+            .min_by_key(|span| (span.lo, u32::MAX - span.hi))
+            .copied()
+            .unwrap_or(Span {
+                lo: offset,
+                hi: offset,
+            })
+    }
+}
+
+/// Returns the offset of the end of the first line after `offset` that isn't only a comment.
+/// A comment on a line of its own applies to the code starting on that line, so it doesn't
+/// reach past blank lines or the end of a block.
+fn next_code_line_end(contents: &str, offset: usize) -> usize {
+    let mut line_start = contents[offset..]
+        .find('\n')
+        .map_or(contents.len(), |index| offset + index + 1);
+    while line_start < contents.len() {
+        let line_end = contents[line_start..]
+            .find('\n')
+            .map_or(contents.len(), |index| line_start + index);
+        if !contents[line_start..line_end].trim_start().starts_with("//") {
+            return line_end;
+        }
+        line_start = line_end + 1;
+    }
+    contents.len()
+}
+
+/// Returns the lint names suppressed by a comment of the form
+/// `// qsharp-lint-ignore <lint names>`, or `None` for any other comment.
+fn parse_ignore_comment(comment: &str) -> Option<Vec<&str>> {
+    let names = comment
+        .strip_prefix("//")?
+        .trim_start()
+        .strip_prefix(IGNORE_COMMENT)?;
+    if !names.is_empty() && !names.starts_with(char::is_whitespace) {
+        return None;
+    }
+    Some(
+        names
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|name| !name.is_empty())
+            .collect(),
+    )
+}
+
+impl<'a> Visitor<'a> for SuppressionCollector {
+    fn visit_item(&mut self, item: &'a Item) {
+        self.targets.push(item.span);
+        for attr in &item.attrs {
+            self.push_attr(attr, item.span);
+        }
+        visit::walk_item(self, item);
+    }
+
+    fn visit_stmt(&mut self, stmt: &'a Stmt) {
+        self.targets.push(stmt.span);
+        visit::walk_stmt(self, stmt);
+    }
+}
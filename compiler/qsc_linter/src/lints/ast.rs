@@ -28,6 +28,7 @@ declare_ast_lints! {
     (NeedlessParens, LintLevel::Allow, "unnecessary parentheses", "remove the extra parentheses for clarity"),
     (RedundantSemicolons, LintLevel::Warn, "redundant semicolons", "remove the redundant semicolons"),
     (DeprecatedNewtype, LintLevel::Allow, "deprecated `newtype` declarations", "`newtype` declarations are deprecated, use `struct` instead"),
+    (UnusedSuppression, LintLevel::Warn, "unused lint suppression", "no lint was suppressed here, remove the suppression"),
 }
 
 impl AstLintPass for DivisionByZero {
@@ -172,3 +173,14 @@ impl AstLintPass for DeprecatedNewtype {
         }
     }
 }
+
+impl UnusedSuppression {
+    /// Creates a lint for an `@Allow` attribute or `// qsharp-lint-ignore` comment
+    /// that didn't suppress any lint. The suppressions themselves are applied after
+    /// all the lint passes run, so this lint doesn't have an [`AstLintPass`] check.
+    pub(crate) fn lint(&self, span: Span, code_action_edits: Vec<(String, Span)>) -> Lint {
+        lint!(self, span, code_action_edits)
+    }
+}
+
+impl AstLintPass for UnusedSuppression {}
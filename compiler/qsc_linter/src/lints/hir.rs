@@ -3,15 +3,20 @@
 
 use std::rc::Rc;
 
+use qsc_ast::{ast, visit as ast_visit, visit::Visitor as _};
 use qsc_data_structures::span::Span;
+use qsc_frontend::{compile::AstPackage, resolve};
 use qsc_hir::{
+    global,
     hir::{
-        CallableDecl, CallableKind, Expr, ExprKind, Field, ItemKind, Res, SpecBody, SpecDecl, Stmt,
-        StmtKind,
+        Attr, Block, CallableDecl, CallableKind, Expr, ExprKind, Field, Functor, Ident, Item,
+        ItemId, ItemKind, Lit, LocalItemId, NodeId, Package, Pat, PatKind, Res, SpecBody, SpecDecl,
+        Stmt, StmtKind, UnOp, Visibility,
     },
-    ty::Ty,
+    ty::{Prim, Ty},
     visit::{self, Visitor},
 };
+use rustc_hash::{FxHashMap, FxHashSet};
 
 use crate::linter::{hir::declare_hir_lints, Compilation};
 
@@ -39,6 +44,14 @@ declare_hir_lints! {
     (DeprecatedFunctionConstructor, LintLevel::Allow, "deprecated function constructors", "function constructors for struct types are deprecated, use `new` instead"),
     (DeprecatedWithOperator, LintLevel::Allow, "deprecated `w/` and `w/=` operators for structs", "`w/` and `w/=` operators for structs are deprecated, use `new` instead"),
     (DeprecatedDoubleColonOperator, LintLevel::Allow, "deprecated `::` for field access", "`::` operator is deprecated, use `.` instead"),
+    (UseAfterMeasure, LintLevel::Allow, "qubit used after measurement without a reset", "the qubit is left in a measured state by `M`, use `MResetZ` or `Reset` if later operations expect a fresh qubit"),
+    (QubitNotReset, LintLevel::Allow, "qubit may not be in the |0⟩ state when it is released", "reset the qubit with `Reset` or measure it with `MResetZ` before the end of its `use` scope"),
+    (QubitAliasing, LintLevel::Warn, "the same qubit is passed more than once to a gate or controlled operation", "gates and controlled operations require distinct qubits, this call will fail at runtime"),
+    (UnusedLocal, LintLevel::Allow, "unused local variable", "remove the variable, or prefix its name with an underscore if it is intentionally unused"),
+    (UnusedParameter, LintLevel::Allow, "unused parameter", "remove the parameter, or prefix its name with an underscore if it is intentionally unused"),
+    (UnusedCallable, LintLevel::Allow, "unused internal callable", "this callable is never called and is not exported, remove it"),
+    (UnusedOpen, LintLevel::Allow, "unused open", "no item is used from this namespace, remove the `open` statement"),
+    (UnusedImport, LintLevel::Allow, "unused import", "this item is never used, remove the import"),
 }
 
 /// Helper to check if an operation has desired operation characteristics
@@ -307,3 +320,1137 @@ impl HirLintPass for DeprecatedDoubleColonOperator {
         }
     }
 }
+
+/// A reference to a qubit, or to an array of qubits, that can be identified statically.
+/// Used by the quantum dataflow lints to track the qubits passed to operations.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum QubitRef {
+    /// A local variable holding a qubit or an array of qubits.
+    Local(NodeId),
+    /// An element of a local array of qubits, indexed by an integer literal.
+    Element(NodeId, i64),
+}
+
+impl QubitRef {
+    fn from_expr(expr: &Expr) -> Option<Self> {
+        match &expr.kind {
+            ExprKind::Var(Res::Local(id), _) if is_qubit_ty(&expr.ty) => Some(Self::Local(*id)),
+            ExprKind::Index(array, index) if expr.ty == Ty::Prim(Prim::Qubit) => {
+                match (&array.kind, &index.kind) {
+                    (ExprKind::Var(Res::Local(id), _), ExprKind::Lit(Lit::Int(i))) => {
+                        Some(Self::Element(*id, *i))
+                    }
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+
+    fn local(self) -> NodeId {
+        match self {
+            Self::Local(id) | Self::Element(id, _) => id,
+        }
+    }
+
+    /// Returns `true` if both references may denote the same qubit.
+    fn overlaps(self, other: Self) -> bool {
+        match (self, other) {
+            (Self::Element(a, i), Self::Element(b, j)) => a == b && i == j,
+            _ => self.local() == other.local(),
+        }
+    }
+}
+
+fn is_qubit_ty(ty: &Ty) -> bool {
+    match ty {
+        Ty::Prim(Prim::Qubit) => true,
+        Ty::Array(item) => **item == Ty::Prim(Prim::Qubit),
+        _ => false,
+    }
+}
+
+fn is_operation(callee: &Expr) -> bool {
+    matches!(&callee.ty, Ty::Arrow(arrow) if arrow.kind == CallableKind::Operation)
+}
+
+/// Collects the qubits passed in the arguments of a call, looking into tuple and array literals.
+fn qubit_args(args: &Expr, refs: &mut Vec<QubitRef>) {
+    match &args.kind {
+        ExprKind::Tuple(items) | ExprKind::Array(items) => {
+            for item in items {
+                qubit_args(item, refs);
+            }
+        }
+        _ => refs.extend(QubitRef::from_expr(args)),
+    }
+}
+
+/// Returns the name of a called callable, looking through functor applications.
+fn callee_name(callee: &Expr, compilation: Compilation) -> Option<Rc<str>> {
+    match &callee.kind {
+        ExprKind::UnOp(UnOp::Functor(_), inner) => callee_name(inner, compilation),
+        ExprKind::Var(Res::Item(item_id), _) => match &compilation.resolve_item_id(item_id).kind {
+            ItemKind::Callable(decl) => Some(decl.name.name.clone()),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum QuantumCall {
+    /// Measures the qubits, leaving them in a basis state.
+    Measure,
+    /// Returns the qubits to the |0⟩ state.
+    Reset,
+    /// Releases the qubits at the end of their `use` scope.
+    Release,
+    /// Any other operation.
+    Other,
+}
+
+const MEASUREMENTS: &[&str] = &["M", "Measure", "MeasureEachZ"];
+const RESETS: &[&str] = &[
+    "Reset",
+    "ResetAll",
+    "MResetZ",
+    "MResetX",
+    "MResetY",
+    "MResetEachZ",
+];
+
+// By the time the linter runs, `use` statements have been replaced by calls to these callables.
+const ALLOCATIONS: &[&str] = &["__quantum__rt__qubit_allocate", "AllocateQubitArray"];
+const RELEASES: &[&str] = &["__quantum__rt__qubit_release", "ReleaseQubitArray"];
+
+/// Classifies a call by its effect on the qubits passed to it. Returns `None` for function calls.
+fn classify_call(callee: &Expr, args: &Expr, compilation: Compilation) -> Option<QuantumCall> {
+    if !is_operation(callee) {
+        return None;
+    }
+
+    let name = callee_name(callee, compilation);
+    let is_reset = |name: &str| RESETS.contains(&name);
+    Some(match name.as_deref() {
+        Some(name) if MEASUREMENTS.contains(&name) => QuantumCall::Measure,
+        Some(name) if is_reset(name) => QuantumCall::Reset,
+        Some(name) if RELEASES.contains(&name) => QuantumCall::Release,
+        // `ApplyToEach(Reset, qs)` and its variants reset every qubit in the array.
+        Some(name) if name.starts_with("ApplyToEach") => match &args.kind {
+            ExprKind::Tuple(items)
+                if items
+                    .first()
+                    .and_then(|op| callee_name(op, compilation))
+                    .as_deref()
+                    .is_some_and(is_reset) =>
+            {
+                QuantumCall::Reset
+            }
+            _ => QuantumCall::Other,
+        },
+        _ => QuantumCall::Other,
+    })
+}
+
+/// Returns `true` if the expression allocates a qubit or an array of qubits.
+fn is_qubit_allocation(expr: &Expr, compilation: Compilation) -> bool {
+    match &expr.kind {
+        ExprKind::Call(callee, _) => callee_name(callee, compilation)
+            .is_some_and(|name| ALLOCATIONS.contains(&name.as_ref())),
+        _ => false,
+    }
+}
+
+/// Collects all the qubits referenced in a HIR node.
+#[derive(Default)]
+struct QubitUses {
+    refs: Vec<QubitRef>,
+}
+
+impl Visitor<'_> for QubitUses {
+    fn visit_expr(&mut self, expr: &Expr) {
+        match QubitRef::from_expr(expr) {
+            Some(qubit) => self.refs.push(qubit),
+            None => visit::walk_expr(self, expr),
+        }
+    }
+}
+
+#[derive(Default)]
+struct UseAfterMeasure {
+    level: LintLevel,
+}
+
+/// Creates a lint for operations applied to a qubit after it was measured with `M`.
+/// Only the calls at the top level of a block are tracked. Any other statement using a
+/// measured qubit, such as a correction conditioned on the measurement result, is
+/// assumed to handle the measured state on purpose.
+impl HirLintPass for UseAfterMeasure {
+    fn check_block(&mut self, block: &Block, buffer: &mut Vec<Lint>, compilation: Compilation) {
+        let mut measured: Vec<QubitRef> = Vec::new();
+
+        for stmt in &block.stmts {
+            let call = match &stmt.kind {
+                StmtKind::Expr(expr) | StmtKind::Semi(expr) | StmtKind::Local(_, _, expr) => {
+                    match &expr.kind {
+                        ExprKind::Call(callee, args) => {
+                            classify_call(callee, args, compilation).map(|kind| (expr, args, kind))
+                        }
+                        _ => None,
+                    }
+                }
+                _ => None,
+            };
+
+            let Some((expr, args, kind)) = call else {
+                let mut uses = QubitUses::default();
+                uses.visit_stmt(stmt);
+                measured.retain(|qubit| !uses.refs.iter().any(|used| used.overlaps(*qubit)));
+                continue;
+            };
+
+            let mut qubits = Vec::new();
+            qubit_args(args, &mut qubits);
+            let is_measured = |qubit: &QubitRef| qubits.iter().any(|arg| arg.overlaps(*qubit));
+            match kind {
+                QuantumCall::Measure => measured.extend(qubits.iter().copied()),
+                QuantumCall::Reset => measured.retain(|qubit| !is_measured(qubit)),
+                QuantumCall::Release => {}
+                QuantumCall::Other => {
+                    if measured.iter().any(is_measured) {
+                        buffer.push(lint!(self, expr.span));
+                        measured.retain(|qubit| !is_measured(qubit));
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Tracks whether the qubits allocated by a `use` statement may have left the |0⟩ state,
+/// following the statements in their scope in order.
+struct QubitStates<'a> {
+    compilation: Compilation<'a>,
+    /// The allocated qubits, and whether they may be in a state other than |0⟩.
+    qubits: Vec<(NodeId, Span, bool)>,
+    /// Variables bound to an allocated qubit or to an element of an allocated array,
+    /// with the allocated qubit or array they alias.
+    aliases: Vec<(NodeId, NodeId)>,
+}
+
+impl<'a> QubitStates<'a> {
+    fn new(pat: &Pat, compilation: Compilation<'a>) -> Self {
+        let mut states = Self {
+            compilation,
+            qubits: Vec::new(),
+            aliases: Vec::new(),
+        };
+        states.bind(pat);
+        states
+    }
+
+    fn bind(&mut self, pat: &Pat) {
+        match &pat.kind {
+            PatKind::Bind(Ident { id, span, .. }) => self.qubits.push((*id, *span, false)),
+            PatKind::Tuple(pats) => pats.iter().for_each(|pat| self.bind(pat)),
+            PatKind::Discard | PatKind::Err => {}
+        }
+    }
+
+    fn resolve(&self, id: NodeId) -> NodeId {
+        self.aliases
+            .iter()
+            .find_map(|(alias, qubit)| (*alias == id).then_some(*qubit))
+            .unwrap_or(id)
+    }
+
+    fn find(&mut self, id: NodeId) -> Option<&mut (NodeId, Span, bool)> {
+        let id = self.resolve(id);
+        self.qubits.iter_mut().find(|(qubit, _, _)| *qubit == id)
+    }
+
+    /// Records the variables of a `let` pattern that are bound to allocated qubits, as in the
+    /// `let (a, b) = (q0, q1);` statements generated for tuple `use` statements, or the
+    /// `let q = qs[index];` statements generated for `for q in qs` loops. Operations on an
+    /// element bound this way are assumed to apply to the whole array.
+    fn bind_aliases(&mut self, pat: &Pat, expr: &Expr) {
+        match (&pat.kind, &expr.kind) {
+            (PatKind::Bind(ident), ExprKind::Index(array, _)) => {
+                if let Some(QubitRef::Local(id)) = QubitRef::from_expr(array) {
+                    self.bind_alias(ident.id, id);
+                }
+            }
+            (PatKind::Bind(ident), _) => {
+                if let Some(QubitRef::Local(id)) = QubitRef::from_expr(expr) {
+                    self.bind_alias(ident.id, id);
+                }
+            }
+            (PatKind::Tuple(pats), ExprKind::Tuple(exprs)) => {
+                for (pat, expr) in pats.iter().zip(exprs) {
+                    self.bind_aliases(pat, expr);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn bind_alias(&mut self, alias: NodeId, id: NodeId) {
+        let id = self.resolve(id);
+        if self.qubits.iter().any(|(qubit, _, _)| *qubit == id) {
+            self.aliases.push((alias, id));
+        }
+    }
+
+    fn dirty_qubits(&self) -> impl Iterator<Item = Span> + '_ {
+        self.qubits
+            .iter()
+            .filter_map(|(_, span, dirty)| dirty.then_some(*span))
+    }
+}
+
+impl Visitor<'_> for QubitStates<'_> {
+    fn visit_expr(&mut self, expr: &Expr) {
+        match &expr.kind {
+            ExprKind::Call(callee, args) => {
+                // Calls in the arguments are evaluated first.
+                visit::walk_expr(self, expr);
+                let Some(kind) = classify_call(callee, args, self.compilation) else {
+                    return;
+                };
+                let mut qubits = Vec::new();
+                qubit_args(args, &mut qubits);
+                for qubit in qubits {
+                    if let Some((_, _, dirty)) = self.find(qubit.local()) {
+                        // Resetting a single element doesn't reset the whole array.
+                        match (kind, qubit) {
+                            (QuantumCall::Reset, QubitRef::Local(_)) => *dirty = false,
+                            (QuantumCall::Reset, QubitRef::Element(..))
+                            | (QuantumCall::Release, _) => {}
+                            (QuantumCall::Measure | QuantumCall::Other, _) => *dirty = true,
+                        }
+                    }
+                }
+            }
+            _ => visit::walk_expr(self, expr),
+        }
+    }
+
+    fn visit_stmt(&mut self, stmt: &Stmt) {
+        if let StmtKind::Local(_, pat, expr) = &stmt.kind {
+            self.bind_aliases(pat, expr);
+        }
+        visit::walk_stmt(self, stmt);
+    }
+}
+
+#[derive(Default)]
+struct QubitNotReset {
+    level: LintLevel,
+}
+
+/// Creates a lint for qubits that may not be returned to the |0⟩ state at the end of
+/// their `use` scope, because an operation was applied to them after their last reset.
+impl HirLintPass for QubitNotReset {
+    fn check_block(&mut self, block: &Block, buffer: &mut Vec<Lint>, compilation: Compilation) {
+        for (i, stmt) in block.stmts.iter().enumerate() {
+            // The qubits allocated in a block are released at the end of the same block.
+            if let StmtKind::Local(_, pat, expr) = &stmt.kind {
+                if !is_qubit_allocation(expr, compilation) {
+                    continue;
+                }
+                let mut states = QubitStates::new(pat, compilation);
+                for stmt in &block.stmts[i + 1..] {
+                    states.visit_stmt(stmt);
+                }
+                for span in states.dirty_qubits() {
+                    buffer.push(lint!(self, span));
+                }
+            }
+        }
+    }
+}
+
+#[derive(Default)]
+struct QubitAliasing {
+    level: LintLevel,
+}
+
+/// The gates whose implementation rejects being passed the same qubit more than once.
+/// Other operations may legitimately take the same qubit twice.
+const MULTI_QUBIT_GATES: &[&str] = &[
+    "CNOT", "CCNOT", "CX", "CY", "CZ", "SWAP", "Rxx", "Ryy", "Rzz",
+];
+
+/// Returns `true` if the call passes the same qubit more than once where the runtime rejects it:
+/// twice to a multi-qubit gate, or as a control and as another control or target of a
+/// controlled operation.
+fn has_aliased_qubits(callee: &Expr, args: &Expr, compilation: Compilation) -> bool {
+    match (&callee.kind, &args.kind) {
+        (ExprKind::UnOp(UnOp::Functor(Functor::Adj), inner), _) => {
+            has_aliased_qubits(inner, args, compilation)
+        }
+        (ExprKind::UnOp(UnOp::Functor(Functor::Ctl), inner), ExprKind::Tuple(items))
+            if items.len() == 2 =>
+        {
+            let mut controls = Vec::new();
+            qubit_args(&items[0], &mut controls);
+            let mut targets = Vec::new();
+            qubit_args(&items[1], &mut targets);
+            has_duplicates(&controls)
+                || controls
+                    .iter()
+                    .any(|control| targets.iter().any(|target| control.overlaps(*target)))
+                || has_aliased_qubits(inner, &items[1], compilation)
+        }
+        _ => {
+            let is_gate = callee_name(callee, compilation)
+                .is_some_and(|name| MULTI_QUBIT_GATES.contains(&name.as_ref()));
+            let mut qubits = Vec::new();
+            if is_gate {
+                qubit_args(args, &mut qubits);
+            }
+            has_duplicates(&qubits)
+        }
+    }
+}
+
+fn has_duplicates(qubits: &[QubitRef]) -> bool {
+    qubits
+        .iter()
+        .enumerate()
+        .any(|(i, a)| qubits[i + 1..].iter().any(|b| a.overlaps(*b)))
+}
+
+/// Creates a lint for calls passing the same qubit more than once to a gate, like `CNOT(q, q)`.
+impl HirLintPass for QubitAliasing {
+    fn check_expr(&mut self, expr: &Expr, buffer: &mut Vec<Lint>, compilation: Compilation) {
+        if let ExprKind::Call(callee, args) = &expr.kind {
+            if is_operation(callee) && has_aliased_qubits(callee, args, compilation) {
+                buffer.push(lint!(self, expr.span));
+            }
+        }
+    }
+}
+
+/// Collects the local variables referenced in a callable, including the ones
+/// captured by lambdas.
+#[derive(Default)]
+struct LocalUses {
+    used: FxHashSet<NodeId>,
+}
+
+impl Visitor<'_> for LocalUses {
+    fn visit_expr(&mut self, expr: &Expr) {
+        match &expr.kind {
+            ExprKind::Var(Res::Local(id), _) => {
+                self.used.insert(*id);
+            }
+            ExprKind::Closure(captures, _) => self.used.extend(captures.iter().copied()),
+            _ => {}
+        }
+        visit::walk_expr(self, expr);
+    }
+}
+
+/// Returns `true` if the expression contains a call, whose side effects would be lost
+/// by removing the expression.
+fn contains_call(expr: &Expr) -> bool {
+    #[derive(Default)]
+    struct ContainsCall(bool);
+
+    impl Visitor<'_> for ContainsCall {
+        fn visit_expr(&mut self, expr: &Expr) {
+            if matches!(expr.kind, ExprKind::Call(..)) {
+                self.0 = true;
+            } else if !self.0 {
+                visit::walk_expr(self, expr);
+            }
+        }
+    }
+
+    let mut finder = ContainsCall::default();
+    finder.visit_expr(expr);
+    finder.0
+}
+
+/// Returns the names bound by a pattern that are not marked as intentionally unused.
+/// Names introduced by the compiler, like the ones of desugared loops, are skipped too.
+fn bound_names(pat: &Pat, names: &mut Vec<Ident>) {
+    match &pat.kind {
+        PatKind::Bind(ident) => {
+            if ident.name.starts_with(char::is_alphabetic) && ident.span != Span::default() {
+                names.push(ident.clone());
+            }
+        }
+        PatKind::Tuple(items) => {
+            for item in items {
+                bound_names(item, names);
+            }
+        }
+        PatKind::Discard | PatKind::Err => {}
+    }
+}
+
+fn underscore_prefix(ident: &Ident) -> Vec<(String, Span)> {
+    vec![(format!("_{}", ident.name), ident.span)]
+}
+
+#[derive(Default)]
+struct UnusedLocal {
+    level: LintLevel,
+}
+
+/// Creates a lint for local variables that are never read. The `let` statement is removed
+/// if that doesn't discard any call, otherwise the variable is prefixed with an underscore.
+impl HirLintPass for UnusedLocal {
+    fn check_callable_decl(
+        &mut self,
+        decl: &CallableDecl,
+        buffer: &mut Vec<Lint>,
+        compilation: Compilation,
+    ) {
+        #[derive(Default)]
+        struct LocalDecls<'a> {
+            decls: Vec<&'a Stmt>,
+        }
+
+        impl<'a> Visitor<'a> for LocalDecls<'a> {
+            fn visit_stmt(&mut self, stmt: &'a Stmt) {
+                if let StmtKind::Local(..) = stmt.kind {
+                    self.decls.push(stmt);
+                }
+                visit::walk_stmt(self, stmt);
+            }
+        }
+
+        let mut uses = LocalUses::default();
+        uses.visit_callable_decl(decl);
+        let mut decls = LocalDecls::default();
+        decls.visit_callable_decl(decl);
+
+        // Generated specializations repeat the statements of the body, with the same spans.
+        let mut reported = FxHashSet::default();
+        for stmt in decls.decls {
+            let StmtKind::Local(_, pat, init) = &stmt.kind else {
+                continue;
+            };
+            let mut names = Vec::new();
+            bound_names(pat, &mut names);
+            for ident in names {
+                if uses.used.contains(&ident.id) || !reported.insert(ident.span) {
+                    continue;
+                }
+                // The arguments of partial applications are bound by generated statements
+                // that span only the argument, so there is no declaration to remove.
+                let is_declaration = stmt.span != init.span;
+                let code_action_edits = if matches!(pat.kind, PatKind::Bind(_))
+                    && is_declaration
+                    && !contains_call(init)
+                {
+                    vec![(String::new(), compilation.deletion_span(stmt.span))]
+                } else {
+                    underscore_prefix(&ident)
+                };
+                buffer.push(lint!(self, ident.span, code_action_edits));
+            }
+        }
+    }
+}
+
+#[derive(Default)]
+struct UnusedParameter {
+    level: LintLevel,
+}
+
+/// Creates a lint for parameters that are not used by any specialization of a callable.
+/// The parameter is removed together with the matching argument of every call in the package,
+/// unless the callable is also used in other ways, in which case the parameter is prefixed
+/// with an underscore instead.
+impl HirLintPass for UnusedParameter {
+    fn check_callable_decl(
+        &mut self,
+        decl: &CallableDecl,
+        buffer: &mut Vec<Lint>,
+        compilation: Compilation,
+    ) {
+        // Intrinsics and empty callables, like `I`, have no body to use their parameters.
+        if IsQuantumOperation::is_empty_op(decl) {
+            return;
+        }
+
+        let mut uses = LocalUses::default();
+        uses.visit_callable_decl(decl);
+        let mut params = Vec::new();
+        bound_names(&decl.input, &mut params);
+        let mut call_sites = None;
+        for ident in params {
+            if !uses.used.contains(&ident.id) {
+                let call_sites =
+                    call_sites.get_or_insert_with(|| CallSites::new(decl, compilation));
+                let code_action_edits = call_sites
+                    .as_ref()
+                    .and_then(|call_sites| call_sites.parameter_removal(&ident))
+                    .unwrap_or_else(|| underscore_prefix(&ident));
+                buffer.push(lint!(self, ident.span, code_action_edits));
+            }
+        }
+    }
+}
+
+/// The declaration of a callable and the calls to it, when those are the only uses of the
+/// callable in the package, so that a parameter can be removed from all of them.
+struct CallSites<'a> {
+    input: &'a ast::Pat,
+    /// The argument of each call, without the controls added by the `Controlled` functor.
+    args: Vec<&'a ast::Expr>,
+}
+
+impl<'a> CallSites<'a> {
+    fn new(decl: &CallableDecl, compilation: Compilation<'a>) -> Option<Self> {
+        struct Finder<'a> {
+            ast: &'a AstPackage,
+            item: LocalItemId,
+            name: Span,
+            input: Option<&'a ast::Pat>,
+            args: Vec<&'a ast::Expr>,
+            other_uses: bool,
+        }
+
+        impl<'a> ast_visit::Visitor<'a> for Finder<'a> {
+            fn visit_callable_decl(&mut self, decl: &'a ast::CallableDecl) {
+                if decl.name.span == self.name {
+                    self.input = Some(&decl.input);
+                }
+                ast_visit::walk_callable_decl(self, decl);
+            }
+
+            fn visit_expr(&mut self, expr: &'a ast::Expr) {
+                if let ast::ExprKind::Call(callee, arg) = &*expr.kind {
+                    if let Some(arg) = self.call_arg(callee, arg) {
+                        self.args.push(arg);
+                        self.visit_expr(arg);
+                        return;
+                    }
+                }
+                ast_visit::walk_expr(self, expr);
+            }
+
+            fn visit_path(&mut self, path: &'a ast::Path) {
+                if self.is_item(path) {
+                    self.other_uses = true;
+                }
+            }
+        }
+
+        impl<'a> Finder<'a> {
+            fn is_item(&self, path: &ast::Path) -> bool {
+                self.ast.names.get(path.id).and_then(resolve::Res::item_id)
+                    == Some(ItemId {
+                        package: None,
+                        item: self.item,
+                    })
+            }
+
+            /// Returns the argument of a call to the callable, with the controls of any
+            /// `Controlled` functors removed, or `None` if the callee is something else.
+            fn call_arg(&self, callee: &ast::Expr, arg: &'a ast::Expr) -> Option<&'a ast::Expr> {
+                match &*callee.kind {
+                    ast::ExprKind::Path(path) if self.is_item(path) => Some(arg),
+                    ast::ExprKind::Paren(inner)
+                    | ast::ExprKind::UnOp(ast::UnOp::Functor(ast::Functor::Adj), inner) => {
+                        self.call_arg(inner, arg)
+                    }
+                    ast::ExprKind::UnOp(ast::UnOp::Functor(ast::Functor::Ctl), inner) => {
+                        match &*self.call_arg(inner, arg)?.kind {
+                            ast::ExprKind::Tuple(items) if items.len() == 2 => Some(&items[1]),
+                            _ => None,
+                        }
+                    }
+                    _ => None,
+                }
+            }
+        }
+
+        let package = &compilation.compile_unit.package;
+        let (item, _) = package.items.iter().find(|(_, item)| {
+            matches!(&item.kind, ItemKind::Callable(callable) if std::ptr::eq(callable, decl))
+        })?;
+        // Callables that can be used by other packages can have callers that aren't updated.
+        if package.entry.is_none()
+            && package.items.get(item).map(|item| item.visibility) == Some(Visibility::Public)
+        {
+            return None;
+        }
+        let mut finder = Finder {
+            ast: &compilation.compile_unit.ast,
+            item,
+            name: decl.name.span,
+            input: None,
+            args: Vec::new(),
+            other_uses: false,
+        };
+        finder.visit_package(&compilation.compile_unit.ast.package);
+        // Lambdas have no declaration in the AST, and are only used as values.
+        match finder.input {
+            Some(input) if !finder.other_uses => Some(Self {
+                input,
+                args: finder.args,
+            }),
+            _ => None,
+        }
+    }
+
+    /// Returns the edits that remove the parameter from the declaration and the matching
+    /// argument from each call, or `None` if a call doesn't pass the arguments as a tuple
+    /// or leaves the argument as a hole of a partial application.
+    fn parameter_removal(&self, ident: &Ident) -> Option<Vec<(String, Span)>> {
+        let (index, len, span) = match &*self.input.kind {
+            ast::PatKind::Paren(inner) => (0, 1, inner.span),
+            ast::PatKind::Tuple(items) if items.len() > 1 => {
+                let spans: Vec<_> = items.iter().map(|item| item.span).collect();
+                let index = spans.iter().position(|span| span.contains(ident.span.lo))?;
+                (index, spans.len(), removal_span(&spans, index))
+            }
+            _ => return None,
+        };
+        let mut edits = vec![(String::new(), span)];
+        for arg in &self.args {
+            let (arg, span) = match &*arg.kind {
+                ast::ExprKind::Paren(inner) if len == 1 => (inner, inner.span),
+                ast::ExprKind::Tuple(items) if items.len() == len => {
+                    let spans: Vec<_> = items.iter().map(|item| item.span).collect();
+                    (&items[index], removal_span(&spans, index))
+                }
+                _ => return None,
+            };
+            if matches!(*arg.kind, ast::ExprKind::Hole) {
+                return None;
+            }
+            edits.push((String::new(), span));
+        }
+        Some(edits)
+    }
+}
+
+/// Returns the span that removes the item at `index` from a comma-separated list with the
+/// given item spans, together with the comma that separates it from its neighbor.
+fn removal_span(spans: &[Span], index: usize) -> Span {
+    match (index.checked_sub(1), spans.get(index + 1)) {
+        (_, Some(next)) => Span {
+            lo: spans[index].lo,
+            hi: next.lo,
+        },
+        (Some(prev), None) => Span {
+            lo: spans[prev].hi,
+            hi: spans[index].hi,
+        },
+        (None, None) => spans[index],
+    }
+}
+
+/// Collects the callables of the package that are referenced from outside their own body.
+#[derive(Default)]
+struct ItemUses {
+    current: Option<LocalItemId>,
+    used: FxHashSet<LocalItemId>,
+}
+
+impl ItemUses {
+    fn new(package: &Package) -> Self {
+        let mut uses = Self::default();
+        for (id, item) in &package.items {
+            if let ItemKind::Export(
+                _,
+                ItemId {
+                    package: None,
+                    item,
+                },
+            ) = &item.kind
+            {
+                uses.used.insert(*item);
+            }
+            uses.current = Some(id);
+            uses.visit_item(item);
+        }
+        uses.current = None;
+        for stmt in &package.stmts {
+            uses.visit_stmt(stmt);
+        }
+        if let Some(entry) = &package.entry {
+            uses.visit_expr(entry);
+        }
+        uses
+    }
+}
+
+impl Visitor<'_> for ItemUses {
+    fn visit_expr(&mut self, expr: &Expr) {
+        match &expr.kind {
+            ExprKind::Var(
+                Res::Item(ItemId {
+                    package: None,
+                    item,
+                }),
+                _,
+            ) if self.current != Some(*item) => {
+                self.used.insert(*item);
+            }
+            ExprKind::Closure(_, item) => {
+                self.used.insert(*item);
+            }
+            _ => {}
+        }
+        visit::walk_expr(self, expr);
+    }
+}
+
+#[derive(Default)]
+struct UnusedCallable {
+    level: LintLevel,
+    uses: Option<ItemUses>,
+}
+
+/// Creates a lint for internal callables that are not referenced anywhere in the package.
+/// Exported callables and entry points are used from outside the package, so they are never
+/// reported. A package with neither an entry point nor exports, like a library under
+/// development, has no known uses and is not linted.
+impl HirLintPass for UnusedCallable {
+    fn check_item(&mut self, item: &Item, buffer: &mut Vec<Lint>, compilation: Compilation) {
+        let ItemKind::Callable(decl) = &item.kind else {
+            return;
+        };
+        if item.visibility != Visibility::Internal || item.attrs.contains(&Attr::EntryPoint) {
+            return;
+        }
+        let package = &compilation.compile_unit.package;
+        if package.entry.is_none()
+            && !package.items.values().any(|item| {
+                item.visibility == Visibility::Public
+                    && !matches!(item.kind, ItemKind::Namespace(..))
+            })
+        {
+            return;
+        }
+        let uses = self.uses.get_or_insert_with(|| ItemUses::new(package));
+        if !uses.used.contains(&item.id) {
+            let code_action_edits = vec![(String::new(), compilation.deletion_span(item.span))];
+            buffer.push(lint!(self, decl.name.span, code_action_edits));
+        }
+    }
+}
+
+/// An `open` or `import` declaration, with the scope in which it makes names available.
+struct NameDecl<'a> {
+    kind: NameDeclKind<'a>,
+    scope: Span,
+    /// The span of the declaration, from the `open` or `import` keyword to the semicolon.
+    item_span: Span,
+}
+
+enum NameDeclKind<'a> {
+    /// An `open` statement, with an optional alias.
+    Open(Vec<Rc<str>>, Option<Rc<str>>),
+    /// An import declaration.
+    Import(&'a ast::ImportOrExportDecl),
+}
+
+/// A path written in the source code, which may rely on an `open` or an `import`.
+struct PathUse {
+    span: Span,
+    namespace: Vec<Rc<str>>,
+    name: Rc<str>,
+}
+
+/// Collects the `open` and `import` declarations of the package, and the paths that resolved
+/// to an item. Exported paths count as uses of the declarations they rely on.
+struct NameDecls<'a> {
+    ast: &'a AstPackage,
+    scopes: Vec<Span>,
+    decls: Vec<NameDecl<'a>>,
+    paths: Vec<PathUse>,
+}
+
+impl<'a> NameDecls<'a> {
+    fn new(ast: &'a AstPackage) -> Self {
+        let mut decls = Self {
+            ast,
+            scopes: Vec::new(),
+            decls: Vec::new(),
+            paths: Vec::new(),
+        };
+        // Top-level statements are only used in notebooks, where a declaration
+        // applies to the cells that follow it.
+        for node in &ast.package.nodes {
+            if let ast::TopLevelNode::Namespace(namespace) = node {
+                decls.visit_namespace(namespace);
+            }
+        }
+        decls
+    }
+
+    fn push_path(&mut self, path: &ast::Path) {
+        self.paths.push(PathUse {
+            span: path.span,
+            namespace: path
+                .segments
+                .as_ref()
+                .map(|segments| segments.iter().map(|i| i.name.clone()).collect())
+                .unwrap_or_default(),
+            name: path.name.name.clone(),
+        });
+    }
+
+    fn uses_in(&self, scope: Span) -> impl Iterator<Item = &PathUse> {
+        self.paths
+            .iter()
+            .filter(move |path| scope.lo <= path.span.lo && path.span.hi <= scope.hi)
+    }
+
+    /// Returns `true` if a path in `scope` refers to a member of `namespace`, opened
+    /// with an optional alias.
+    fn is_namespace_used(
+        &self,
+        scope: Span,
+        namespace: &[Rc<str>],
+        alias: Option<&Rc<str>>,
+        members: &NamespaceMembers,
+    ) -> bool {
+        self.uses_in(scope).any(|path| {
+            let segments = match alias {
+                Some(alias) if path.namespace.first() == Some(alias) => &path.namespace[1..],
+                Some(_) => return false,
+                None => &path.namespace[..],
+            };
+            members
+                .get(&[namespace, segments].concat())
+                .is_some_and(|names| names.contains(&path.name))
+        })
+    }
+}
+
+impl<'a> ast_visit::Visitor<'a> for NameDecls<'a> {
+    fn visit_namespace(&mut self, namespace: &'a ast::Namespace) {
+        self.scopes.push(namespace.span);
+        ast_visit::walk_namespace(self, namespace);
+        self.scopes.pop();
+    }
+
+    fn visit_block(&mut self, block: &'a ast::Block) {
+        self.scopes.push(block.span);
+        ast_visit::walk_block(self, block);
+        self.scopes.pop();
+    }
+
+    fn visit_item(&mut self, item: &'a ast::Item) {
+        let scope = *self.scopes.last().expect("item should be in a scope");
+        match &*item.kind {
+            ast::ItemKind::Open(name, alias) => self.decls.push(NameDecl {
+                kind: NameDeclKind::Open(
+                    name.iter().map(|i| i.name.clone()).collect(),
+                    alias.as_ref().map(|alias| alias.name.clone()),
+                ),
+                scope,
+                item_span: item.span,
+            }),
+            ast::ItemKind::ImportOrExport(decl) if decl.is_export() => {
+                for decl_item in decl.items() {
+                    self.push_path(&decl_item.path);
+                }
+            }
+            ast::ItemKind::ImportOrExport(decl) => self.decls.push(NameDecl {
+                kind: NameDeclKind::Import(decl),
+                scope,
+                item_span: item.span,
+            }),
+            _ => ast_visit::walk_item(self, item),
+        }
+    }
+
+    fn visit_path(&mut self, path: &'a ast::Path) {
+        if self
+            .ast
+            .names
+            .get(path.id)
+            .is_some_and(|res| res.item_id().is_some())
+        {
+            self.push_path(path);
+        }
+    }
+}
+
+type NamespaceMembers = FxHashMap<Vec<Rc<str>>, FxHashSet<Rc<str>>>;
+
+/// The names declared in each namespace of the package and of the packages in the store,
+/// including the exported ones. Exported namespaces share the members of the namespace
+/// they export.
+fn namespace_members(compilation: Compilation) -> NamespaceMembers {
+    let mut members = NamespaceMembers::default();
+    let mut exported_namespaces = Vec::new();
+    let packages = compilation
+        .package_store
+        .iter()
+        .map(|(id, unit)| (Some(id), &unit.package))
+        .chain(std::iter::once((None, &compilation.compile_unit.package)));
+    for (id, package) in packages {
+        // Namespaces exported from the package they are declared in, like `Std.Math`,
+        // are aliases in the namespace tree of the package.
+        for names in &package.namespaces {
+            if let Some((target, aliases)) = names.split_first() {
+                for alias in aliases {
+                    exported_namespaces.push((alias.clone(), target.clone()));
+                }
+            }
+        }
+        for global in global::iter_package(id, package) {
+            match global.kind {
+                global::Kind::Namespace => {
+                    members.entry(global.namespace).or_default();
+                }
+                global::Kind::Export(item_id) => {
+                    let target = match item_id.package {
+                        Some(package_id) => compilation
+                            .package_store
+                            .get(package_id)
+                            .and_then(|unit| unit.package.items.get(item_id.item)),
+                        None => package.items.get(item_id.item),
+                    };
+                    if let Some(Item {
+                        kind: ItemKind::Namespace(target, _),
+                        ..
+                    }) = target
+                    {
+                        let mut namespace = global.namespace;
+                        namespace.push(global.name);
+                        exported_namespaces.push((namespace, Vec::<Rc<str>>::from(target)));
+                    } else {
+                        members
+                            .entry(global.namespace)
+                            .or_default()
+                            .insert(global.name);
+                    }
+                }
+                global::Kind::Ty(_) | global::Kind::Term(_) => {
+                    members
+                        .entry(global.namespace)
+                        .or_default()
+                        .insert(global.name);
+                }
+            }
+        }
+    }
+    for (namespace, target) in exported_namespaces {
+        if let Some(names) = members.get(&target).cloned() {
+            members.entry(namespace).or_default().extend(names);
+        }
+    }
+    members
+}
+
+#[derive(Default)]
+struct UnusedOpen {
+    level: LintLevel,
+}
+
+/// Creates a lint for `open` statements from which no name is used. Namespaces that
+/// can't be found, like the ones of aliased dependencies, are not reported.
+impl HirLintPass for UnusedOpen {
+    fn check_package(
+        &mut self,
+        _package: &Package,
+        buffer: &mut Vec<Lint>,
+        compilation: Compilation,
+    ) {
+        let decls = NameDecls::new(&compilation.compile_unit.ast);
+        if !decls
+            .decls
+            .iter()
+            .any(|decl| matches!(decl.kind, NameDeclKind::Open(..)))
+        {
+            return;
+        }
+
+        let members = namespace_members(compilation);
+        for decl in &decls.decls {
+            if let NameDeclKind::Open(namespace, alias) = &decl.kind {
+                if members.contains_key(namespace)
+                    && !decls.is_namespace_used(decl.scope, namespace, alias.as_ref(), &members)
+                {
+                    let code_action_edits =
+                        vec![(String::new(), compilation.deletion_span(decl.item_span))];
+                    buffer.push(lint!(self, decl.item_span, code_action_edits));
+                }
+            }
+        }
+    }
+}
+
+#[derive(Default)]
+struct UnusedImport {
+    level: LintLevel,
+}
+
+/// Creates a lint for each imported item whose name is not used, and for glob imports from
+/// which no name is used. The whole declaration is removed if none of its items is used,
+/// otherwise only the unused item is.
+impl HirLintPass for UnusedImport {
+    fn check_package(
+        &mut self,
+        _package: &Package,
+        buffer: &mut Vec<Lint>,
+        compilation: Compilation,
+    ) {
+        let decls = NameDecls::new(&compilation.compile_unit.ast);
+        let mut members = None;
+        for decl in &decls.decls {
+            let NameDeclKind::Import(import) = decl.kind else {
+                continue;
+            };
+            let items: Vec<_> = import.items().collect();
+            let unused: Vec<bool> = items
+                .iter()
+                .map(|decl_item| {
+                    if decl_item.is_glob {
+                        let namespace: Vec<Rc<str>> = Vec::<ast::Ident>::from(&decl_item.path)
+                            .into_iter()
+                            .map(|i| i.name)
+                            .collect();
+                        let members = members.get_or_insert_with(|| namespace_members(compilation));
+                        members.contains_key(&namespace)
+                            && !decls.is_namespace_used(decl.scope, &namespace, None, members)
+                    } else {
+                        let name = &decl_item.name().name;
+                        !decls
+                            .uses_in(decl.scope)
+                            .any(|path| path.namespace.first().unwrap_or(&path.name) == name)
+                    }
+                })
+                .collect();
+
+            for (index, decl_item) in items.iter().enumerate() {
+                if !unused[index] {
+                    continue;
+                }
+                let deleted = if unused.iter().all(|unused| *unused) {
+                    compilation.deletion_span(decl.item_span)
+                } else if index + 1 < items.len() {
+                    // Remove the item along with the separator that follows it.
+                    Span {
+                        lo: decl_item.span().lo,
+                        hi: items[index + 1].span().lo,
+                    }
+                } else {
+                    Span {
+                        lo: items[index - 1].span().hi,
+                        hi: decl_item.span().hi,
+                    }
+                };
+                buffer.push(lint!(
+                    self,
+                    decl_item.span(),
+                    vec![(String::new(), deleted)]
+                ));
+            }
+        }
+    }
+}
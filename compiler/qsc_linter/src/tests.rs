@@ -2,8 +2,10 @@
 // Licensed under the MIT License.
 
 use crate::{
-    linter::{ast::run_ast_lints, hir::run_hir_lints, Compilation},
-    Lint, LintConfig, LintLevel,
+    linter::{
+        ast::run_ast_lints, hir::run_hir_lints, suppression::apply_suppressions, Compilation,
+    },
+    HirLint, Lint, LintConfig, LintKind, LintLevel,
 };
 use expect_test::{expect, Expect};
 use indoc::indoc;
@@ -590,6 +592,1457 @@ fn needless_operation_inside_function_call() {
         use q = Qubit();
         M(q);
     }
+    "},
+        &expect![[r#"
+            [
+                SrcLint {
+                    source: "use q = Qubit();",
+                    level: Allow,
+                    message: "qubit may not be in the |0⟩ state when it is released",
+                    help: "reset the qubit with `Reset` or measure it with `MResetZ` before the end of its `use` scope",
+                    code_action_edits: [],
+                },
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn use_after_measure() {
+    check(
+        indoc! {"
+    operation Foo() : Unit {
+        use q = Qubit();
+        H(q);
+        let r = M(q);
+        X(q);
+        Reset(q);
+    }
+    "},
+        &expect![[r#"
+            [
+                SrcLint {
+                    source: "X(q)",
+                    level: Allow,
+                    message: "qubit used after measurement without a reset",
+                    help: "the qubit is left in a measured state by `M`, use `MResetZ` or `Reset` if later operations expect a fresh qubit",
+                    code_action_edits: [],
+                },
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn use_after_measure_with_reset() {
+    check(
+        indoc! {"
+    operation Foo() : Unit {
+        use q = Qubit();
+        let r = M(q);
+        Reset(q);
+        X(q);
+        Reset(q);
+    }
+    "},
+        &expect![[r"
+            []
+        "]],
+    );
+}
+
+#[test]
+fn use_after_measure_conditional_correction() {
+    check(
+        indoc! {"
+    operation Foo() : Unit {
+        use q = Qubit();
+        let r = M(q);
+        if r == One {
+            X(q);
+        }
+        H(q);
+        Reset(q);
+    }
+    "},
+        &expect![[r"
+            []
+        "]],
+    );
+}
+
+#[test]
+fn use_after_measure_array_element() {
+    check(
+        indoc! {"
+    operation Foo() : Unit {
+        use qs = Qubit[2];
+        let r = M(qs[0]);
+        X(qs[1]);
+        CNOT(qs[0], qs[1]);
+        ResetAll(qs);
+    }
+    "},
+        &expect![[r#"
+            [
+                SrcLint {
+                    source: "CNOT(qs[0], qs[1])",
+                    level: Allow,
+                    message: "qubit used after measurement without a reset",
+                    help: "the qubit is left in a measured state by `M`, use `MResetZ` or `Reset` if later operations expect a fresh qubit",
+                    code_action_edits: [],
+                },
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn qubit_not_reset() {
+    check(
+        indoc! {"
+    operation Foo() : Result {
+        use q = Qubit();
+        H(q);
+        M(q)
+    }
+    "},
+        &expect![[r#"
+            [
+                SrcLint {
+                    source: "use q = Qubit();",
+                    level: Allow,
+                    message: "qubit may not be in the |0⟩ state when it is released",
+                    help: "reset the qubit with `Reset` or measure it with `MResetZ` before the end of its `use` scope",
+                    code_action_edits: [],
+                },
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn qubit_not_reset_with_mresetz() {
+    check(
+        indoc! {"
+    operation Foo() : Result {
+        use q = Qubit();
+        H(q);
+        MResetZ(q)
+    }
+    "},
+        &expect![[r"
+            []
+        "]],
+    );
+}
+
+#[test]
+fn qubit_not_reset_unused_qubit() {
+    check(
+        indoc! {"
+    operation Foo() : Unit {
+        use q = Qubit();
+    }
+    "},
+        &expect![[r"
+            []
+        "]],
+    );
+}
+
+#[test]
+fn qubit_not_reset_in_use_block() {
+    check(
+        indoc! {"
+    operation Foo() : Unit {
+        use (a, b) = (Qubit(), Qubit()) {
+            H(a);
+            X(b);
+            Reset(b);
+        }
+    }
+    "},
+        &expect![[r#"
+            [
+                SrcLint {
+                    source: "Qubit()",
+                    level: Allow,
+                    message: "qubit may not be in the |0⟩ state when it is released",
+                    help: "reset the qubit with `Reset` or measure it with `MResetZ` before the end of its `use` scope",
+                    code_action_edits: [],
+                },
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn qubit_not_reset_array() {
+    check(
+        indoc! {"
+    operation Foo() : Unit {
+        use qs = Qubit[3];
+        ApplyToEach(H, qs);
+        Reset(qs[0]);
+    }
+    "},
+        &expect![[r#"
+            [
+                SrcLint {
+                    source: "use qs = Qubit[3];",
+                    level: Allow,
+                    message: "qubit may not be in the |0⟩ state when it is released",
+                    help: "reset the qubit with `Reset` or measure it with `MResetZ` before the end of its `use` scope",
+                    code_action_edits: [],
+                },
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn qubit_not_reset_array_reset_in_loop() {
+    check(
+        indoc! {"
+    operation Foo() : Unit {
+        use qs = Qubit[3];
+        ApplyToEach(H, qs);
+        for q in qs {
+            Reset(q);
+        }
+    }
+    "},
+        &expect![[r"
+            []
+        "]],
+    );
+}
+
+#[test]
+fn qubit_not_reset_array_apply_to_each_reset() {
+    check(
+        indoc! {"
+    operation Foo() : Unit {
+        use qs = Qubit[3];
+        ApplyToEach(H, qs);
+        ApplyToEach(Reset, qs);
+    }
+    "},
+        &expect![[r"
+            []
+        "]],
+    );
+}
+
+#[test]
+fn qubit_aliasing() {
+    check(
+        indoc! {"
+    operation Foo() : Unit {
+        use q = Qubit();
+        CNOT(q, q);
+    }
+    "},
+        &expect![[r#"
+            [
+                SrcLint {
+                    source: "use q = Qubit();",
+                    level: Allow,
+                    message: "qubit may not be in the |0⟩ state when it is released",
+                    help: "reset the qubit with `Reset` or measure it with `MResetZ` before the end of its `use` scope",
+                    code_action_edits: [],
+                },
+                SrcLint {
+                    source: "CNOT(q, q)",
+                    level: Warn,
+                    message: "the same qubit is passed more than once to a gate or controlled operation",
+                    help: "gates and controlled operations require distinct qubits, this call will fail at runtime",
+                    code_action_edits: [],
+                },
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn qubit_aliasing_array_elements() {
+    check(
+        indoc! {"
+    operation Foo() : Unit {
+        use qs = Qubit[2];
+        CNOT(qs[0], qs[1]);
+        CNOT(qs[1], qs[1]);
+        Controlled X(qs, qs[0]);
+    }
+    "},
+        &expect![[r#"
+            [
+                SrcLint {
+                    source: "use qs = Qubit[2];",
+                    level: Allow,
+                    message: "qubit may not be in the |0⟩ state when it is released",
+                    help: "reset the qubit with `Reset` or measure it with `MResetZ` before the end of its `use` scope",
+                    code_action_edits: [],
+                },
+                SrcLint {
+                    source: "CNOT(qs[1], qs[1])",
+                    level: Warn,
+                    message: "the same qubit is passed more than once to a gate or controlled operation",
+                    help: "gates and controlled operations require distinct qubits, this call will fail at runtime",
+                    code_action_edits: [],
+                },
+                SrcLint {
+                    source: "Controlled X(qs, qs[0])",
+                    level: Warn,
+                    message: "the same qubit is passed more than once to a gate or controlled operation",
+                    help: "gates and controlled operations require distinct qubits, this call will fail at runtime",
+                    code_action_edits: [],
+                },
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn qubit_aliasing_user_operations_are_allowed() {
+    check(
+        indoc! {"
+    operation Foo() : Unit {
+        use qs = Qubit[2];
+        Bar(qs[0], qs[0]);
+        Baz(qs, qs[0]);
+        ResetAll(qs);
+    }
+    operation Bar(a : Qubit, b : Qubit) : Unit {
+        H(a);
+        H(b);
+    }
+    operation Baz(qs : Qubit[], q : Qubit) : Unit {
+        X(q);
+    }
+    "},
+        &expect![[r"
+            []
+        "]],
+    );
+}
+
+#[test]
+fn qubit_aliasing_distinct_qubits() {
+    check(
+        indoc! {"
+    operation Foo() : Unit {
+        use (a, b) = (Qubit(), Qubit());
+        CNOT(a, b);
+        Controlled X([a], b);
+    }
+    "},
+        &expect![[r#"
+            [
+                SrcLint {
+                    source: "Qubit()",
+                    level: Allow,
+                    message: "qubit may not be in the |0⟩ state when it is released",
+                    help: "reset the qubit with `Reset` or measure it with `MResetZ` before the end of its `use` scope",
+                    code_action_edits: [],
+                },
+                SrcLint {
+                    source: "Qubit()",
+                    level: Allow,
+                    message: "qubit may not be in the |0⟩ state when it is released",
+                    help: "reset the qubit with `Reset` or measure it with `MResetZ` before the end of its `use` scope",
+                    code_action_edits: [],
+                },
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn allow_attr_suppresses_ast_lint() {
+    check(
+        indoc! {r#"
+    @Allow("divisionByZero")
+    function Foo() : Int {
+        2 / 0
+    }
+    "#},
+        &expect![[r"
+            []
+        "]],
+    );
+}
+
+#[test]
+fn allow_attr_suppresses_hir_lint() {
+    check(
+        indoc! {r#"
+    @Allow("needlessOperation")
+    operation Foo() : Int {
+        let x = 2 / 0;
+        x
+    }
+    "#},
+        &expect![[r#"
+            [
+                SrcLint {
+                    source: "2 / 0",
+                    level: Error,
+                    message: "attempt to divide by zero",
+                    help: "division by zero will fail at runtime",
+                    code_action_edits: [],
+                },
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn allow_attr_with_multiple_lints() {
+    check(
+        indoc! {r#"
+    @Allow("needlessOperation", "divisionByZero")
+    operation Foo() : Int {
+        2 / 0
+    }
+    "#},
+        &expect![[r"
+            []
+        "]],
+    );
+}
+
+#[test]
+fn allow_attr_does_not_apply_to_other_items() {
+    check(
+        indoc! {r#"
+    @Allow("divisionByZero")
+    function Foo() : Int {
+        2 / 0
+    }
+
+    function Bar() : Int {
+        2 / 0
+    }
+    "#},
+        &expect![[r#"
+            [
+                SrcLint {
+                    source: "2 / 0",
+                    level: Error,
+                    message: "attempt to divide by zero",
+                    help: "division by zero will fail at runtime",
+                    code_action_edits: [],
+                },
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn ignore_comment_suppresses_next_statement() {
+    check(
+        indoc! {"
+    function Foo() : Unit {
+        // qsharp-lint-ignore divisionByZero
+        let x = 2 /
+            0;
+        let y = 2 / 0;
+    }
+    "},
+        &expect![[r#"
+            [
+                SrcLint {
+                    source: "2 / 0",
+                    level: Error,
+                    message: "attempt to divide by zero",
+                    help: "division by zero will fail at runtime",
+                    code_action_edits: [],
+                },
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn ignore_comment_at_end_of_line() {
+    check(
+        indoc! {"
+    function Foo() : Unit {
+        let x = 2 / 0; // qsharp-lint-ignore divisionByZero
+        let y = 2 / 0;
+    }
+    "},
+        &expect![[r#"
+            [
+                SrcLint {
+                    source: "2 / 0",
+                    level: Error,
+                    message: "attempt to divide by zero",
+                    help: "division by zero will fail at runtime",
+                    code_action_edits: [],
+                },
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn ignore_comment_in_string_literal_is_not_a_suppression() {
+    check(
+        indoc! {r#"
+    function Foo() : Unit {
+        let x = (2 / 0, "// qsharp-lint-ignore divisionByZero");
+    }
+    "#},
+        &expect![[r#"
+            [
+                SrcLint {
+                    source: "2 / 0",
+                    level: Error,
+                    message: "attempt to divide by zero",
+                    help: "division by zero will fail at runtime",
+                    code_action_edits: [],
+                },
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn ignore_comment_suppresses_next_item() {
+    check(
+        indoc! {"
+    // qsharp-lint-ignore needlessOperation, divisionByZero
+    operation Foo() : Unit {
+        let x = 2 / 0;
+    }
+    "},
+        &expect![[r"
+            []
+        "]],
+    );
+}
+
+#[test]
+fn unused_allow_attr() {
+    check(
+        indoc! {r#"
+    @Allow("divisionByZero")
+    function Foo() : Int {
+        2 / 1
+    }
+    "#},
+        &expect![[r#"
+            [
+                SrcLint {
+                    source: "@Allow(\"divisionByZero\")",
+                    level: Warn,
+                    message: "unused lint suppression",
+                    help: "no lint was suppressed here, remove the suppression",
+                    code_action_edits: [
+                        (
+                            "",
+                            Span {
+                                lo: 28,
+                                hi: 52,
+                            },
+                        ),
+                    ],
+                },
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn partially_unused_allow_attr_has_no_code_action() {
+    check(
+        indoc! {r#"
+    @Allow("needlessOperation", "divisionByZero")
+    operation Foo() : Int {
+        2 / 1
+    }
+    "#},
+        &expect![[r#"
+            [
+                SrcLint {
+                    source: "@Allow(\"needlessOperation\", \"divisionByZero\")",
+                    level: Warn,
+                    message: "unused lint suppression",
+                    help: "no lint was suppressed here, remove the suppression",
+                    code_action_edits: [],
+                },
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn unused_ignore_comment() {
+    check(
+        indoc! {"
+    function Foo() : Unit {
+        // qsharp-lint-ignore divisionByZero
+        let x = 2 / 1;
+    }
+    "},
+        &expect![[r#"
+            [
+                SrcLint {
+                    source: "// qsharp-lint-ignore divisionByZero",
+                    level: Warn,
+                    message: "unused lint suppression",
+                    help: "no lint was suppressed here, remove the suppression",
+                    code_action_edits: [
+                        (
+                            "",
+                            Span {
+                                lo: 56,
+                                hi: 92,
+                            },
+                        ),
+                    ],
+                },
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn ignore_comment_at_end_of_block_is_unused() {
+    check(
+        indoc! {"
+    function Foo() : Unit {
+        let x = 2 / 1;
+        // qsharp-lint-ignore divisionByZero
+    }
+    function Bar() : Unit {
+        let y = 2 / 0;
+    }
+    "},
+        &expect![[r#"
+            [
+                SrcLint {
+                    source: "2 / 0",
+                    level: Error,
+                    message: "attempt to divide by zero",
+                    help: "division by zero will fail at runtime",
+                    code_action_edits: [],
+                },
+                SrcLint {
+                    source: "// qsharp-lint-ignore divisionByZero",
+                    level: Warn,
+                    message: "unused lint suppression",
+                    help: "no lint was suppressed here, remove the suppression",
+                    code_action_edits: [
+                        (
+                            "",
+                            Span {
+                                lo: 75,
+                                hi: 111,
+                            },
+                        ),
+                    ],
+                },
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn ignore_comment_followed_by_blank_line_is_unused() {
+    check(
+        indoc! {"
+    function Foo() : Unit {
+        // qsharp-lint-ignore divisionByZero
+
+        let x = 2 / 0;
+    }
+    "},
+        &expect![[r#"
+            [
+                SrcLint {
+                    source: "2 / 0",
+                    level: Error,
+                    message: "attempt to divide by zero",
+                    help: "division by zero will fail at runtime",
+                    code_action_edits: [],
+                },
+                SrcLint {
+                    source: "// qsharp-lint-ignore divisionByZero",
+                    level: Warn,
+                    message: "unused lint suppression",
+                    help: "no lint was suppressed here, remove the suppression",
+                    code_action_edits: [
+                        (
+                            "",
+                            Span {
+                                lo: 56,
+                                hi: 92,
+                            },
+                        ),
+                    ],
+                },
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn ignore_comments_can_be_stacked() {
+    check(
+        indoc! {"
+    function Foo() : Unit {
+        // qsharp-lint-ignore divisionByZero
+        // qsharp-lint-ignore needlessParens
+        let x = (2 / 0);
+    }
+    "},
+        &expect![[r"
+            []
+        "]],
+    );
+}
+
+#[test]
+fn ignore_comment_with_unknown_lint_is_unused() {
+    check(
+        indoc! {"
+    function Foo() : Unit {
+        // qsharp-lint-ignore notALint
+        let x = 2 / 0;
+    }
+    "},
+        &expect![[r#"
+            [
+                SrcLint {
+                    source: "2 / 0",
+                    level: Error,
+                    message: "attempt to divide by zero",
+                    help: "division by zero will fail at runtime",
+                    code_action_edits: [],
+                },
+                SrcLint {
+                    source: "// qsharp-lint-ignore notALint",
+                    level: Warn,
+                    message: "unused lint suppression",
+                    help: "no lint was suppressed here, remove the suppression",
+                    code_action_edits: [
+                        (
+                            "",
+                            Span {
+                                lo: 56,
+                                hi: 86,
+                            },
+                        ),
+                    ],
+                },
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn unused_local_is_removed() {
+    check_unused(
+        &wrap_in_callable(
+            "let x = 1;
+            let y = 2;
+            Message($\"{y}\");",
+            CallableKind::Function,
+        ),
+        &expect![[r#"
+            [
+                SrcLint {
+                    source: "x",
+                    level: Allow,
+                    message: "unused local variable",
+                    help: "remove the variable, or prefix its name with an underscore if it is intentionally unused",
+                    code_action_edits: [
+                        (
+                            "",
+                            Span {
+                                lo: 59,
+                                hi: 82,
+                            },
+                        ),
+                    ],
+                },
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn unused_local_declared_across_lines_is_removed() {
+    check_unused(
+        &wrap_in_callable(
+            "let // the answer
+                x = 42;",
+            CallableKind::Function,
+        ),
+        &expect![[r#"
+            [
+                SrcLint {
+                    source: "x",
+                    level: Allow,
+                    message: "unused local variable",
+                    help: "remove the variable, or prefix its name with an underscore if it is intentionally unused",
+                    code_action_edits: [
+                        (
+                            "",
+                            Span {
+                                lo: 59,
+                                hi: 113,
+                            },
+                        ),
+                    ],
+                },
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn unused_local_with_call_is_prefixed() {
+    check_unused(
+        indoc! {"
+        operation Main() : Unit {
+            use q = Qubit();
+            let r = M(q);
+        }
+    "},
+        &expect![[r#"
+            [
+                SrcLint {
+                    source: "r",
+                    level: Allow,
+                    message: "unused local variable",
+                    help: "remove the variable, or prefix its name with an underscore if it is intentionally unused",
+                    code_action_edits: [
+                        (
+                            "_r",
+                            Span {
+                                lo: 83,
+                                hi: 84,
+                            },
+                        ),
+                    ],
+                },
+                SrcLint {
+                    source: "use q = Qubit();",
+                    level: Allow,
+                    message: "qubit may not be in the |0⟩ state when it is released",
+                    help: "reset the qubit with `Reset` or measure it with `MResetZ` before the end of its `use` scope",
+                    code_action_edits: [],
+                },
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn unused_local_in_tuple_pattern() {
+    check_unused(
+        &wrap_in_callable(
+            "let (a, b) = (1, 2);
+            Message($\"{b}\");",
+            CallableKind::Function,
+        ),
+        &expect![[r#"
+            [
+                SrcLint {
+                    source: "a",
+                    level: Allow,
+                    message: "unused local variable",
+                    help: "remove the variable, or prefix its name with an underscore if it is intentionally unused",
+                    code_action_edits: [
+                        (
+                            "_a",
+                            Span {
+                                lo: 76,
+                                hi: 77,
+                            },
+                        ),
+                    ],
+                },
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn unused_local_with_underscore_prefix() {
+    check_unused(
+        &wrap_in_callable(
+            "let _x = 1;
+            let (_, _y) = (1, 2);",
+            CallableKind::Function,
+        ),
+        &expect![[r"
+            []
+        "]],
+    );
+}
+
+#[test]
+fn local_captured_by_lambda_is_used() {
+    check_unused(
+        &wrap_in_callable(
+            "let x = 1;
+            let f = y -> x + y;
+            Message($\"{f(1)}\");",
+            CallableKind::Function,
+        ),
+        &expect![[r"
+            []
+        "]],
+    );
+}
+
+#[test]
+fn unused_parameter_of_library_callable_is_prefixed() {
+    check_unused(
+        indoc! {"
+        function Main(a : Int, b : Int, _c : Int) : Int {
+            a
+        }
+    "},
+        &expect![[r#"
+            [
+                SrcLint {
+                    source: "b",
+                    level: Allow,
+                    message: "unused parameter",
+                    help: "remove the parameter, or prefix its name with an underscore if it is intentionally unused",
+                    code_action_edits: [
+                        (
+                            "",
+                            Span {
+                                lo: 51,
+                                hi: 60,
+                            },
+                        ),
+                    ],
+                },
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn unused_parameter_is_removed_from_calls() {
+    check_unused(
+        indoc! {"
+        @EntryPoint()
+        operation Main() : Unit {
+            use (control, target) = (Qubit(), Qubit());
+            Flip(target, 1);
+            Controlled Flip([control], (target, 2));
+            ResetAll([control, target]);
+        }
+        operation Flip(q : Qubit, n : Int) : Unit is Ctl {
+            X(q);
+        }
+    "},
+        &expect![[r#"
+            [
+                SrcLint {
+                    source: "n",
+                    level: Allow,
+                    message: "unused parameter",
+                    help: "remove the parameter, or prefix its name with an underscore if it is intentionally unused",
+                    code_action_edits: [
+                        (
+                            "",
+                            Span {
+                                lo: 241,
+                                hi: 250,
+                            },
+                        ),
+                        (
+                            "",
+                            Span {
+                                lo: 131,
+                                hi: 134,
+                            },
+                        ),
+                        (
+                            "",
+                            Span {
+                                lo: 175,
+                                hi: 178,
+                            },
+                        ),
+                    ],
+                },
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn only_parameter_is_removed_from_calls() {
+    check_unused(
+        indoc! {"
+        @EntryPoint()
+        function Main() : Int {
+            Answer(1)
+        }
+        function Answer(n : Int) : Int {
+            42
+        }
+    "},
+        &expect![[r#"
+            [
+                SrcLint {
+                    source: "n",
+                    level: Allow,
+                    message: "unused parameter",
+                    help: "remove the parameter, or prefix its name with an underscore if it is intentionally unused",
+                    code_action_edits: [
+                        (
+                            "",
+                            Span {
+                                lo: 98,
+                                hi: 105,
+                            },
+                        ),
+                        (
+                            "",
+                            Span {
+                                lo: 77,
+                                hi: 78,
+                            },
+                        ),
+                    ],
+                },
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn unused_parameter_of_callable_used_as_value_is_prefixed() {
+    check_unused(
+        indoc! {"
+        @EntryPoint()
+        function Main() : Int {
+            let add = Add;
+            add(1, 2)
+        }
+        function Add(a : Int, b : Int) : Int {
+            a
+        }
+    "},
+        &expect![[r#"
+            [
+                SrcLint {
+                    source: "b",
+                    level: Allow,
+                    message: "unused parameter",
+                    help: "remove the parameter, or prefix its name with an underscore if it is intentionally unused",
+                    code_action_edits: [
+                        (
+                            "_b",
+                            Span {
+                                lo: 123,
+                                hi: 124,
+                            },
+                        ),
+                    ],
+                },
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn unused_parameter_left_as_hole_is_prefixed() {
+    check_unused(
+        indoc! {"
+        @EntryPoint()
+        function Main() : Int {
+            Add(1, _)(2)
+        }
+        function Add(a : Int, b : Int) : Int {
+            a
+        }
+    "},
+        &expect![[r#"
+            [
+                SrcLint {
+                    source: "b",
+                    level: Allow,
+                    message: "unused parameter",
+                    help: "remove the parameter, or prefix its name with an underscore if it is intentionally unused",
+                    code_action_edits: [
+                        (
+                            "_b",
+                            Span {
+                                lo: 107,
+                                hi: 108,
+                            },
+                        ),
+                    ],
+                },
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn unused_parameter_of_intrinsic() {
+    check_unused(
+        indoc! {"
+        operation Intrinsic(q : Qubit) : Unit {
+            body intrinsic;
+        }
+    "},
+        &expect![[r"
+            []
+        "]],
+    );
+}
+
+#[test]
+fn unused_callable() {
+    check_unused(
+        indoc! {"
+        @EntryPoint()
+        operation Main() : Unit {
+            Used();
+        }
+        function Used() : Unit {}
+        function Unused() : Unit {}
+        function Recursive() : Unit {
+            Recursive();
+        }
+    "},
+        &expect![[r#"
+            [
+                SrcLint {
+                    source: "Main",
+                    level: Allow,
+                    message: "operation does not contain any quantum operations",
+                    help: "this callable can be declared as a function instead",
+                    code_action_edits: [],
+                },
+                SrcLint {
+                    source: "Unused",
+                    level: Allow,
+                    message: "unused internal callable",
+                    help: "this callable is never called and is not exported, remove it",
+                    code_action_edits: [
+                        (
+                            "",
+                            Span {
+                                lo: 108,
+                                hi: 136,
+                            },
+                        ),
+                    ],
+                },
+                SrcLint {
+                    source: "Recursive",
+                    level: Allow,
+                    message: "unused internal callable",
+                    help: "this callable is never called and is not exported, remove it",
+                    code_action_edits: [
+                        (
+                            "",
+                            Span {
+                                lo: 136,
+                                hi: 185,
+                            },
+                        ),
+                    ],
+                },
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn exported_callable_is_used() {
+    check_unused(
+        indoc! {"
+        function Exported() : Unit {}
+        function Unused() : Unit {}
+        export Exported;
+    "},
+        &expect![[r#"
+            [
+                SrcLint {
+                    source: "Unused",
+                    level: Allow,
+                    message: "unused internal callable",
+                    help: "this callable is never called and is not exported, remove it",
+                    code_action_edits: [
+                        (
+                            "",
+                            Span {
+                                lo: 58,
+                                hi: 86,
+                            },
+                        ),
+                    ],
+                },
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn no_unused_callable_without_entry_point_or_exports() {
+    check_unused(
+        indoc! {"
+        function Unused() : Unit {}
+    "},
+        &expect![[r"
+            []
+        "]],
+    );
+}
+
+#[test]
+fn unused_open() {
+    check_unused(
+        indoc! {"
+        open Microsoft.Quantum.Arrays;
+        open Microsoft.Quantum.Math;
+        open Microsoft.Quantum.Convert as Convert;
+        open Microsoft.Quantum.Random as Random;
+        open Unknown.Namespace;
+        function Main() : Unit {
+            let x = PI() + Convert.IntAsDouble(1);
+            Message($\"{x}\");
+        }
+    "},
+        &expect![[r#"
+            [
+                SrcLint {
+                    source: "open Microsoft.Quantum.Arrays;",
+                    level: Allow,
+                    message: "unused open",
+                    help: "no item is used from this namespace, remove the `open` statement",
+                    code_action_edits: [
+                        (
+                            "",
+                            Span {
+                                lo: 16,
+                                hi: 59,
+                            },
+                        ),
+                    ],
+                },
+                SrcLint {
+                    source: "open Microsoft.Quantum.Random as Random;",
+                    level: Allow,
+                    message: "unused open",
+                    help: "no item is used from this namespace, remove the `open` statement",
+                    code_action_edits: [
+                        (
+                            "",
+                            Span {
+                                lo: 131,
+                                hi: 172,
+                            },
+                        ),
+                    ],
+                },
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn open_used_by_partially_qualified_path() {
+    check_unused(
+        indoc! {"
+        open Microsoft.Quantum;
+        function Main() : Unit {
+            let x = Math.PI();
+            Message($\"{x}\");
+        }
+    "},
+        &expect![[r"
+            []
+        "]],
+    );
+}
+
+#[test]
+fn open_in_callable() {
+    check_unused(
+        indoc! {"
+        function Main() : Unit {
+            open Microsoft.Quantum.Math;
+        }
+        function Other() : Unit {
+            let x = Microsoft.Quantum.Math.PI();
+            Message($\"{x}\");
+        }
+    "},
+        &expect![[r#"
+            [
+                SrcLint {
+                    source: "open Microsoft.Quantum.Math;",
+                    level: Allow,
+                    message: "unused open",
+                    help: "no item is used from this namespace, remove the `open` statement",
+                    code_action_edits: [
+                        (
+                            "",
+                            Span {
+                                lo: 53,
+                                hi: 86,
+                            },
+                        ),
+                    ],
+                },
+                SrcLint {
+                    source: "Other",
+                    level: Allow,
+                    message: "unused internal callable",
+                    help: "this callable is never called and is not exported, remove it",
+                    code_action_edits: [
+                        (
+                            "",
+                            Span {
+                                lo: 88,
+                                hi: 178,
+                            },
+                        ),
+                    ],
+                },
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn unused_import() {
+    check_unused(
+        indoc! {"
+        import Std.Math.PI;
+        import Std.Diagnostics.DumpMachine;
+        function Main() : Unit {
+            DumpMachine();
+        }
+    "},
+        &expect![[r#"
+            [
+                SrcLint {
+                    source: "Std.Math.PI",
+                    level: Allow,
+                    message: "unused import",
+                    help: "this item is never used, remove the import",
+                    code_action_edits: [
+                        (
+                            "",
+                            Span {
+                                lo: 16,
+                                hi: 48,
+                            },
+                        ),
+                    ],
+                },
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn partially_unused_import() {
+    check_unused(
+        indoc! {"
+        import Std.Math.PI, Std.Diagnostics.DumpMachine, Std.Math.E;
+        function Main() : Unit {
+            DumpMachine();
+        }
+    "},
+        &expect![[r#"
+            [
+                SrcLint {
+                    source: "Std.Math.PI",
+                    level: Allow,
+                    message: "unused import",
+                    help: "this item is never used, remove the import",
+                    code_action_edits: [
+                        (
+                            "",
+                            Span {
+                                lo: 35,
+                                hi: 48,
+                            },
+                        ),
+                    ],
+                },
+                SrcLint {
+                    source: "Std.Math.E",
+                    level: Allow,
+                    message: "unused import",
+                    help: "this item is never used, remove the import",
+                    code_action_edits: [
+                        (
+                            "",
+                            Span {
+                                lo: 75,
+                                hi: 87,
+                            },
+                        ),
+                    ],
+                },
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn unused_glob_import() {
+    check_unused(
+        indoc! {"
+        import Std.Math.*;
+        import Microsoft.Quantum.Diagnostics.*;
+        function Main() : Unit {
+            DumpMachine();
+        }
+    "},
+        &expect![[r#"
+            [
+                SrcLint {
+                    source: "Std.Math",
+                    level: Allow,
+                    message: "unused import",
+                    help: "this item is never used, remove the import",
+                    code_action_edits: [
+                        (
+                            "",
+                            Span {
+                                lo: 16,
+                                hi: 47,
+                            },
+                        ),
+                    ],
+                },
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn import_used_by_export() {
+    check_unused(
+        indoc! {"
+        import Std.Math.PI;
+        import Std.Math as M;
+        export PI;
+        function Main() : Double {
+            M.E()
+        }
     "},
         &expect![[r"
             []
@@ -597,7 +2050,31 @@ fn needless_operation_inside_function_call() {
     );
 }
 
+/// Checks the lints of the source, except for the unused-item lints, which would be
+/// reported for the variables of most snippets.
 fn check(source: &str, expected: &Expect) {
+    check_lints(source, expected, |lint| !is_unused_item_lint(lint));
+}
+
+/// Checks all the lints of the source, including the unused-item lints.
+fn check_unused(source: &str, expected: &Expect) {
+    check_lints(source, expected, |_| true);
+}
+
+fn is_unused_item_lint(lint: &Lint) -> bool {
+    matches!(
+        lint.kind,
+        LintKind::Hir(
+            HirLint::UnusedLocal
+                | HirLint::UnusedParameter
+                | HirLint::UnusedCallable
+                | HirLint::UnusedOpen
+                | HirLint::UnusedImport
+        )
+    )
+}
+
+fn check_lints(source: &str, expected: &Expect, filter: impl Fn(&Lint) -> bool) {
     let source = wrap_in_namespace(source);
     let mut store = PackageStore::new(compile::core());
     let std = store.insert(compile::std(&store, TargetCapabilityFlags::all()));
@@ -616,6 +2093,7 @@ fn check(source: &str, expected: &Expect) {
 
     let actual: Vec<SrcLint> = run_lints(&store, unit, None)
         .into_iter()
+        .filter(|lint| filter(lint))
         .map(|lint| SrcLint::from(&lint, &source))
         .collect();
 
@@ -682,5 +2160,5 @@ fn run_lints(
     let mut lints = Vec::new();
     lints.append(&mut ast_lints);
     lints.append(&mut hir_lints);
-    lints
+    apply_suppressions(lints, compile_unit, config)
 }
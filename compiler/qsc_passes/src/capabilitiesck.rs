@@ -10,6 +10,9 @@ mod tests_adaptive;
 #[cfg(test)]
 mod tests_adaptive_plus_integers;
 
+#[cfg(test)]
+mod tests_provenance;
+
 #[cfg(test)]
 pub mod tests_common;
 
@@ -18,8 +21,8 @@ use qsc_data_structures::{span::Span, target::TargetCapabilityFlags};
 use qsc_fir::{
     fir::{
         Block, BlockId, CallableImpl, Expr, ExprId, ExprKind, Global, Ident, Item, ItemKind,
-        LocalItemId, LocalVarId, Package, PackageLookup, Pat, PatId, PatKind, Res, SpecDecl,
-        SpecImpl, Stmt, StmtId, StmtKind,
+        LocalItemId, LocalVarId, Package, PackageId, PackageLookup, Pat, PatId, PatKind, Res,
+        SpecDecl, SpecImpl, Stmt, StmtId, StmtKind,
     },
     ty::FunctorSetValue,
     visit::Visitor,
@@ -27,8 +30,8 @@ use qsc_fir::{
 
 use qsc_lowerer::map_hir_package_to_fir;
 use qsc_rca::{
-    errors::{generate_errors_from_runtime_features, get_missing_runtime_features, Error},
-    Analyzer, ComputeKind, ItemComputeProperties, PackageComputeProperties,
+    errors::{generate_errors_from_runtime_features, get_missing_runtime_features, ExplainedError},
+    provenance, Analyzer, ComputeKind, ItemComputeProperties, PackageComputeProperties,
     PackageStoreComputeProperties, RuntimeFeatureFlags,
 };
 use rustc_hash::FxHashMap;
@@ -52,11 +55,10 @@ pub fn run_rca_pass(
 ) -> Result<PackageStoreComputeProperties, Vec<crate::Error>> {
     let analyzer = Analyzer::init(fir_store);
     let compute_properties = analyzer.analyze_all();
-    let fir_package = fir_store.get(package_id);
 
     let package_compute_properties = compute_properties.get(package_id);
     let mut errors = check_supported_capabilities(
-        fir_package,
+        package_id,
         package_compute_properties,
         capabilities,
         fir_store,
@@ -73,19 +75,22 @@ pub fn run_rca_pass(
     }
 }
 
+/// Checks that the package only uses the runtime features supported by the target capabilities.
+/// Each error is explained by the provenance of the dynamic value it is about, when there is one.
 #[must_use]
 pub fn check_supported_capabilities(
-    package: &Package,
+    package_id: PackageId,
     compute_properties: &PackageComputeProperties,
     capabilities: TargetCapabilityFlags,
     store: &qsc_fir::fir::PackageStore,
-) -> Vec<Error> {
+) -> Vec<ExplainedError> {
     let checker = Checker {
-        package,
+        package_id,
+        package: store.get(package_id),
         compute_properties,
         target_capabilities: capabilities,
         current_callable: None,
-        missing_features_map: FxHashMap::<Span, RuntimeFeatureFlags>::default(),
+        missing_features_map: FxHashMap::<Span, MissingFeatures>::default(),
         store,
     };
 
@@ -93,14 +98,22 @@ pub fn check_supported_capabilities(
 }
 
 struct Checker<'a> {
+    package_id: PackageId,
     package: &'a Package,
     compute_properties: &'a PackageComputeProperties,
     target_capabilities: TargetCapabilityFlags,
     current_callable: Option<LocalItemId>,
-    missing_features_map: FxHashMap<Span, RuntimeFeatureFlags>,
+    missing_features_map: FxHashMap<Span, MissingFeatures>,
     store: &'a qsc_fir::fir::PackageStore,
 }
 
+/// The runtime features missing at a span, along with the expression that uses them, which is
+/// `None` for features that affect a whole specialization or the output recording.
+struct MissingFeatures {
+    features: RuntimeFeatureFlags,
+    expr: Option<(Option<LocalItemId>, ExprId)>,
+}
+
 impl<'a> Visitor<'a> for Checker<'a> {
     fn get_block(&self, id: BlockId) -> &'a Block {
         self.package.get_block(id)
@@ -198,7 +211,7 @@ impl<'a> Visitor<'a> for Checker<'a> {
 }
 
 impl<'a> Checker<'a> {
-    pub fn check_all(mut self) -> Vec<Error> {
+    pub fn check_all(mut self) -> Vec<ExplainedError> {
         self.visit_package(self.package, self.store);
         self.generate_errors()
     }
@@ -225,10 +238,11 @@ impl<'a> Checker<'a> {
         );
         let expr = self.get_expr(expr_id);
         if !missing_features.is_empty() {
-            self.missing_features_map
-                .entry(expr.span)
-                .and_modify(|f| *f |= missing_features)
-                .or_insert(missing_features);
+            self.add_missing_features(
+                expr.span,
+                missing_features,
+                Some((self.current_callable, expr_id)),
+            );
         }
     }
 
@@ -322,10 +336,11 @@ impl<'a> Checker<'a> {
                     panic!("");
                 };
 
-                self.missing_features_map
-                    .entry(callable_decl.name.span)
-                    .and_modify(|f| *f |= missing_spec_level_runtime_features)
-                    .or_insert(missing_spec_level_runtime_features);
+                self.add_missing_features(
+                    callable_decl.name.span,
+                    missing_spec_level_runtime_features,
+                    None,
+                );
                 return;
             }
         }
@@ -356,13 +371,25 @@ impl<'a> Checker<'a> {
             self.target_capabilities,
         ) & RuntimeFeatureFlags::output_recording_flags();
         if !missing_features.is_empty() {
-            self.missing_features_map
-                .entry(output_reporting_span)
-                .and_modify(|f| *f |= missing_features)
-                .or_insert(missing_features);
+            self.add_missing_features(output_reporting_span, missing_features, None);
         }
     }
 
+    fn add_missing_features(
+        &mut self,
+        span: Span,
+        features: RuntimeFeatureFlags,
+        expr: Option<(Option<LocalItemId>, ExprId)>,
+    ) {
+        self.missing_features_map
+            .entry(span)
+            .and_modify(|missing| {
+                missing.features |= features;
+                missing.expr = missing.expr.or(expr);
+            })
+            .or_insert(MissingFeatures { features, expr });
+    }
+
     fn clear_current_callable(&mut self) -> LocalItemId {
         self.current_callable
             .take()
@@ -387,13 +414,31 @@ impl<'a> Checker<'a> {
             })
     }
 
-    fn generate_errors(&mut self) -> Vec<Error> {
+    fn generate_errors(&mut self) -> Vec<ExplainedError> {
         let mut errors = Vec::new();
         let mut missing_features_map = self.missing_features_map.drain().collect::<Vec<_>>();
-        missing_features_map.sort_unstable();
-        for (span, missing_features) in missing_features_map {
-            let mut span_errors = generate_errors_from_runtime_features(missing_features, span);
-            errors.append(&mut span_errors);
+        missing_features_map.sort_unstable_by_key(|(span, _)| *span);
+        for (span, missing) in missing_features_map {
+            let provenance = missing
+                .expr
+                .map(|(callable, expr_id)| {
+                    provenance::explain(
+                        self.store,
+                        self.package_id,
+                        self.compute_properties,
+                        callable,
+                        expr_id,
+                    )
+                })
+                .unwrap_or_default();
+            errors.extend(
+                generate_errors_from_runtime_features(missing.features, span)
+                    .into_iter()
+                    .map(|error| ExplainedError {
+                        error,
+                        provenance: provenance.clone(),
+                    }),
+            );
         }
         errors
     }
@@ -4,39 +4,65 @@
 #![allow(clippy::needless_raw_string_hashes)]
 
 use expect_test::Expect;
+use miette::Diagnostic;
+use std::{fmt::Write, ops::Range};
 
 use crate::capabilitiesck::check_supported_capabilities;
 use qsc::{incremental::Compiler, PackageType};
 use qsc_data_structures::{language_features::LanguageFeatures, target::TargetCapabilityFlags};
-use qsc_fir::fir::{Package, PackageId, PackageStore};
+use qsc_fir::fir::{PackageId, PackageStore};
 use qsc_frontend::compile::{PackageStore as HirPackageStore, SourceMap};
 use qsc_lowerer::{map_hir_package_to_fir, Lowerer};
-use qsc_rca::{Analyzer, PackageComputeProperties, PackageStoreComputeProperties};
+use qsc_rca::{
+    errors::{Error, ExplainedError},
+    Analyzer, PackageStoreComputeProperties,
+};
 
 pub fn check(source: &str, expect: &Expect, capabilities: TargetCapabilityFlags) {
     let compilation_context = CompilationContext::new(source);
-    let (package, compute_properties) = compilation_context.get_package_compute_properties_tuple();
-    let errors = check_supported_capabilities(
-        package,
-        compute_properties,
-        capabilities,
-        &compilation_context.fir_store,
-    );
+    let errors = compilation_context
+        .check_supported_capabilities(capabilities)
+        .into_iter()
+        .map(|error| error.error)
+        .collect::<Vec<_>>();
     expect.assert_debug_eq(&errors);
 }
 
 pub fn check_for_exe(source: &str, expect: &Expect, capabilities: TargetCapabilityFlags) {
     let compilation_context = CompilationContext::new_for_exe(source);
-    let (package, compute_properties) = compilation_context.get_package_compute_properties_tuple();
-    let errors = check_supported_capabilities(
-        package,
-        compute_properties,
-        capabilities,
-        &compilation_context.fir_store,
-    );
+    let errors = compilation_context
+        .check_supported_capabilities(capabilities)
+        .into_iter()
+        .map(|error| error.error)
+        .collect::<Vec<_>>();
     expect.assert_debug_eq(&errors);
 }
 
+/// Checks the errors along with their provenance, showing the source each span refers to.
+pub fn check_provenance(source: &str, expect: &Expect, capabilities: TargetCapabilityFlags) {
+    let compilation_context = CompilationContext::new(source);
+    let errors = compilation_context.check_supported_capabilities(capabilities);
+    let mut actual = String::new();
+    for explained in errors {
+        let code = explained.error.code().expect("error should have a code");
+        let span = error_span(&explained.error);
+        writeln!(actual, "{code}: `{}`", &source[span]).expect("writing should succeed");
+        for reason in explained.provenance {
+            writeln!(actual, "    {reason}: `{}`", &source[reason.span])
+                .expect("writing should succeed");
+        }
+    }
+    expect.assert_eq(&actual);
+}
+
+fn error_span(error: &Error) -> Range<usize> {
+    let label = error
+        .labels()
+        .and_then(|mut labels| labels.next())
+        .expect("error should have a label");
+    label.offset()..label.offset() + label.len()
+}
+
 fn lower_hir_package_store(
     lowerer: &mut Lowerer,
     hir_package_store: &HirPackageStore,
@@ -107,10 +133,15 @@ impl CompilationContext {
         }
     }
 
-    fn get_package_compute_properties_tuple(&self) -> (&Package, &PackageComputeProperties) {
-        (
-            self.fir_store.get(self.package_id),
+    fn check_supported_capabilities(
+        &self,
+        capabilities: TargetCapabilityFlags,
+    ) -> Vec<ExplainedError> {
+        check_supported_capabilities(
+            self.package_id,
             self.compute_properties.get(self.package_id),
+            capabilities,
+            &self.fir_store,
         )
     }
 }
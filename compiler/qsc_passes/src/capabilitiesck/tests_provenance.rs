@@ -0,0 +1,158 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+#![allow(clippy::needless_raw_string_hashes)]
+
+use super::tests_common::{check_provenance, USE_DYNAMIC_BOOLEAN, USE_DYNAMIC_DOUBLE};
+use expect_test::{expect, Expect};
+use qsc_data_structures::target::TargetCapabilityFlags;
+
+fn check_base(source: &str, expect: &Expect) {
+    check_provenance(source, expect, TargetCapabilityFlags::empty());
+}
+
+fn check_adaptive(source: &str, expect: &Expect) {
+    check_provenance(source, expect, TargetCapabilityFlags::Adaptive);
+}
+
+#[test]
+fn dynamic_bool_is_explained_by_measurement() {
+    check_base(
+        USE_DYNAMIC_BOOLEAN,
+        &expect![[r#"
+            Qsc.CapabilitiesCk.UseOfDynamicBool: `M(q) == Zero`
+                `M` returns a measurement result here: `M(q)`
+        "#]],
+    );
+}
+
+#[test]
+fn dynamic_double_is_explained_through_bindings() {
+    check_adaptive(
+        USE_DYNAMIC_DOUBLE,
+        &expect![[r#"
+            Qsc.CapabilitiesCk.UseOfDynamicInt: `IntAsDouble(ResultArrayAsInt(results))`
+                a dynamic value is passed to `IntAsDouble` here: `ResultArrayAsInt(results)`
+                a dynamic value is passed to `ResultArrayAsInt` here: `results`
+                `results` is bound to a dynamic value here: `results`
+                `MeasureEachZ` returns a measurement result here: `MeasureEachZ(register)`
+            Qsc.CapabilitiesCk.UseOfDynamicDouble: `IntAsDouble(ResultArrayAsInt(results))`
+                a dynamic value is passed to `IntAsDouble` here: `ResultArrayAsInt(results)`
+                a dynamic value is passed to `ResultArrayAsInt` here: `results`
+                `results` is bound to a dynamic value here: `results`
+                `MeasureEachZ` returns a measurement result here: `MeasureEachZ(register)`
+        "#]],
+    );
+}
+
+#[test]
+fn dynamic_value_is_explained_through_assignment_under_condition() {
+    check_adaptive(
+        r#"
+        namespace Test {
+            operation Foo() : Unit {
+                use q = Qubit();
+                mutable d = 0.0;
+                if M(q) == One {
+                    set d = 1.0;
+                }
+                let a = d * 2.0;
+            }
+        }"#,
+        &expect![[r#"
+            Qsc.CapabilitiesCk.UseOfDynamicDouble: `set d = 1.0`
+                this condition depends on a measurement result: `M(q) == One`
+                `M` returns a measurement result here: `M(q)`
+            Qsc.CapabilitiesCk.UseOfDynamicDouble: `d * 2.0`
+                `d` is assigned a dynamic value here: `set d = 1.0`
+                this condition depends on a measurement result: `M(q) == One`
+                `M` returns a measurement result here: `M(q)`
+        "#]],
+    );
+}
+
+#[test]
+fn dynamic_value_is_explained_through_callable() {
+    check_adaptive(
+        r#"
+        namespace Test {
+            operation MeasureAngle(q : Qubit) : Double {
+                let r = M(q);
+                r == One ? 1.0 | 0.0
+            }
+            operation Foo() : Unit {
+                use q = Qubit();
+                let angle = MeasureAngle(q);
+                Rx(angle, q);
+            }
+        }"#,
+        &expect![[r#"
+            Qsc.CapabilitiesCk.UseOfDynamicDouble: `r == One ? 1.0 | 0.0`
+                this condition depends on a measurement result: `r == One`
+                `r` is bound to a dynamic value here: `r`
+                `M` returns a measurement result here: `M(q)`
+            Qsc.CapabilitiesCk.UseOfDynamicDouble: `MeasureAngle(q)`
+                `MeasureAngle` returns a dynamic value here: `MeasureAngle(q)`
+                this condition depends on a measurement result: `r == One`
+                `r` is bound to a dynamic value here: `r`
+                `M` returns a measurement result here: `M(q)`
+            Qsc.CapabilitiesCk.UseOfDynamicDouble: `Rx(angle, q)`
+                a dynamic value is passed to `Rx` here: `angle`
+                `angle` is bound to a dynamic value here: `angle`
+                `MeasureAngle` returns a dynamic value here: `MeasureAngle(q)`
+                this condition depends on a measurement result: `r == One`
+                `r` is bound to a dynamic value here: `r`
+                `M` returns a measurement result here: `M(q)`
+        "#]],
+    );
+}
+
+#[test]
+fn dynamic_argument_is_explained_through_function() {
+    check_adaptive(
+        r#"
+        namespace Test {
+            open Microsoft.Quantum.Convert;
+            function Scale(x : Int) : Double {
+                2.0 * IntAsDouble(x)
+            }
+            operation Foo() : Unit {
+                use q = Qubit();
+                let i = M(q) == One ? 1 | 0;
+                let d = Scale(i);
+            }
+        }"#,
+        &expect![[r#"
+            Qsc.CapabilitiesCk.UseOfDynamicInt: `M(q) == One ? 1 | 0`
+                this condition depends on a measurement result: `M(q) == One`
+                `M` returns a measurement result here: `M(q)`
+            Qsc.CapabilitiesCk.UseOfDynamicInt: `Scale(i)`
+                a dynamic value is passed to `Scale` here: `i`
+                `i` is bound to a dynamic value here: `i`
+                this condition depends on a measurement result: `M(q) == One`
+                `M` returns a measurement result here: `M(q)`
+            Qsc.CapabilitiesCk.UseOfDynamicDouble: `Scale(i)`
+                a dynamic value is passed to `Scale` here: `i`
+                `i` is bound to a dynamic value here: `i`
+                this condition depends on a measurement result: `M(q) == One`
+                `M` returns a measurement result here: `M(q)`
+        "#]],
+    );
+}
+
+#[test]
+fn specialization_level_errors_have_no_provenance() {
+    check_base(
+        r#"
+        namespace Test {
+            operation Foo(q : Qubit) : Unit {
+                if M(q) == One {
+                    Foo(q);
+                }
+            }
+        }"#,
+        &expect![[r#"
+            Qsc.CapabilitiesCk.CyclicOperationSpec: `Foo`
+        "#]],
+    );
+}
@@ -44,7 +44,7 @@ pub(crate) static QIR_RUNTIME_NAMESPACE: &[&str] = &["QIR", "Runtime"];
 pub enum Error {
     BorrowCk(borrowck::Error),
     CallableLimits(callable_limits::Error),
-    CapabilitiesCk(qsc_rca::errors::Error),
+    CapabilitiesCk(qsc_rca::errors::ExplainedError),
     ConjInvert(conjugate_invert::Error),
     EntryPoint(entry_point::Error),
     SpecGen(spec_gen::Error),
@@ -162,13 +162,13 @@ pub fn run_core_passes(core: &mut CompileUnit) -> Vec<Error> {
 }
 
 pub fn run_fir_passes(
-    package: &fir::Package,
+    package_id: fir::PackageId,
     compute_properties: &PackageComputeProperties,
     capabilities: TargetCapabilityFlags,
     store: &fir::PackageStore,
 ) -> Vec<Error> {
     let capabilities_errors =
-        check_supported_capabilities(package, compute_properties, capabilities, store);
+        check_supported_capabilities(package_id, compute_properties, capabilities, store);
     capabilities_errors
         .into_iter()
         .map(Error::CapabilitiesCk)
@@ -14,14 +14,20 @@ mod fs;
 mod js;
 mod manifest;
 mod project;
+#[cfg(feature = "fs")]
+mod watch;
 
 pub use error::StdFsError;
 #[cfg(feature = "fs")]
 pub use fs::StdFs;
 pub use js::{JSFileEntry, JSProjectHost};
-pub use manifest::{Manifest, ManifestDescriptor, PackageRef, PackageType, MANIFEST_FILE_NAME};
+pub use manifest::{
+    FormatterConfig, Manifest, ManifestDescriptor, PackageRef, PackageType, MANIFEST_FILE_NAME,
+};
 pub use project::FileSystemAsync;
 pub use project::{
     key_for_package_ref, package_ref_from_key, DependencyCycle, DirEntry, EntryType, Error,
     FileSystem, PackageCache, PackageGraphSources, PackageInfo, Project, GITHUB_SCHEME,
 };
+#[cfg(feature = "fs")]
+pub use watch::Watcher;
@@ -9,6 +9,7 @@ use std::{
     fs::{self, DirEntry, FileType},
 };
 
+pub use qsc_formatter::formatter::FormatterConfig;
 pub use qsc_linter::LintConfig;
 use rustc_hash::FxHashMap;
 use serde::{Deserialize, Serialize};
@@ -27,14 +28,19 @@ pub struct Manifest {
     #[serde(default)]
     pub lints: Vec<LintConfig>,
     #[serde(default)]
+    pub format: FormatterConfig,
+    #[serde(default)]
     pub dependencies: FxHashMap<String, PackageRef>,
     #[serde(default)]
     pub files: Vec<String>,
     #[serde(default)]
     pub package_type: Option<PackageType>,
+    /// The path to the capability file that describes the target, relative to the manifest.
+    #[serde(default)]
+    pub target: Option<String>,
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone, Copy)]
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
 pub enum PackageType {
     #[serde(rename = "exe")]
     Exe,
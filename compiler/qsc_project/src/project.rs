@@ -8,7 +8,8 @@ use crate::{
 use async_trait::async_trait;
 use futures::FutureExt;
 use miette::Diagnostic;
-use qsc_data_structures::language_features::LanguageFeatures;
+use qsc_data_structures::{language_features::LanguageFeatures, target::TargetDescription};
+use qsc_formatter::formatter::FormatterConfig;
 use qsc_linter::LintConfig;
 use rustc_hash::FxHashMap;
 use std::{
@@ -34,6 +35,11 @@ pub struct Project {
     pub package_graph_sources: PackageGraphSources,
     /// Lint configuration for the project, typically comes from the root `qsharp.json`.
     pub lints: Vec<LintConfig>,
+    /// Formatter configuration for the project, from the `format` section of the root `qsharp.json`.
+    pub format: FormatterConfig,
+    /// The target the project is compiled for, from the capability file named by the `target`
+    /// field of the root `qsharp.json`.
+    pub target: Option<TargetDescription>,
     /// Any errors encountered while loading the project.
     pub errors: Vec<Error>,
 }
@@ -60,9 +66,42 @@ impl Project {
             path: name,
             name: display_name,
             lints: Vec::default(),
+            format: FormatterConfig::default(),
+            target: None,
             errors: Vec::default(),
         }
     }
+
+    /// Returns the paths to watch for changes that require loading the project again: the
+    /// manifests and source files of the project and its local dependencies, and the directories
+    /// that contain the source files, so that added and removed files are noticed too.
+    #[must_use]
+    pub fn watched_paths(&self) -> Vec<PathBuf> {
+        let manifests =
+            self.package_graph_sources.packages.keys().filter_map(
+                |key| match package_ref_from_key(key) {
+                    PackageRef::Path { path } => Some(Path::new(&path).join("qsharp.json")),
+                    PackageRef::GitHub { .. } => None,
+                },
+            );
+        let sources = self
+            .package_graph_sources
+            .packages
+            .values()
+            .chain([&self.package_graph_sources.root])
+            .flat_map(|package| &package.sources)
+            .filter(|(name, _)| !name.starts_with(GITHUB_SCHEME))
+            .map(|(name, _)| PathBuf::from(name.as_ref()));
+        let mut paths = vec![PathBuf::from(self.path.as_ref())];
+        paths.extend(manifests);
+        for source in sources {
+            paths.extend(source.parent().map(Path::to_path_buf));
+            paths.push(source);
+        }
+        paths.sort();
+        paths.dedup();
+        paths
+    }
 }
 
 /// This enum represents a filesystem object type. It is analogous to [`std::fs::FileType`].
@@ -126,6 +165,10 @@ pub enum Error {
     #[diagnostic(code("Qsc.Project.FileSystem"))]
     FileSystem { about_path: String, error: String },
 
+    #[error("Failed to parse target capability file: {error}")]
+    #[diagnostic(code("Qsc.Project.TargetParse"))]
+    TargetParse { path: String, error: String },
+
     #[error("Error fetching from GitHub: {0}")]
     #[diagnostic(code("Qsc.Project.GitHub"))]
     GitHub(String),
@@ -138,7 +181,8 @@ impl Error {
         match self {
             Error::GitHubManifestParse { path, .. }
             | Error::NoSrcDir { path }
-            | Error::ManifestParse { path, .. } => Some(path),
+            | Error::ManifestParse { path, .. }
+            | Error::TargetParse { path, .. } => Some(path),
             // Note we don't return the path for `FileSystem` errors,
             // since for most errors such as "file not found", it's more meaningful
             // to report the error for the manifest that was *referencing* the file,
@@ -266,6 +310,17 @@ pub trait FileSystemAsync {
         )
         .await;
 
+        let target = match &manifest.target {
+            Some(target_path) => match self.load_target(directory, Path::new(target_path)).await {
+                Ok(target) => Some(target),
+                Err(e) => {
+                    errors.push(e);
+                    None
+                }
+            },
+            None => None,
+        };
+
         let name = directory
             .file_name()
             .map(|f| f.to_string_lossy().into())
@@ -287,6 +342,8 @@ pub trait FileSystemAsync {
         Ok(Project {
             package_graph_sources: PackageGraphSources { root, packages },
             lints: manifest.lints,
+            format: manifest.format,
+            target,
             errors,
             name,
             path: manifest_path,
@@ -319,6 +376,28 @@ pub trait FileSystemAsync {
         Ok(manifest)
     }
 
+    /// Loads the target capability file at the given path, relative to the project directory.
+    async fn load_target(&self, directory: &Path, path: &Path) -> ProjectResult<TargetDescription> {
+        let target_path =
+            self.resolve_path(directory, path)
+                .await
+                .map_err(|e| Error::FileSystem {
+                    about_path: directory.to_string_lossy().to_string(),
+                    error: e.to_string(),
+                })?;
+        let (_, target_content) =
+            self.read_file(&target_path)
+                .await
+                .map_err(|e| Error::FileSystem {
+                    about_path: target_path.to_string_lossy().to_string(),
+                    error: e.to_string(),
+                })?;
+        serde_json::from_str::<TargetDescription>(&target_content).map_err(|e| Error::TargetParse {
+            path: target_path.to_string_lossy().to_string(),
+            error: e.to_string(),
+        })
+    }
+
     /// Load the sources for a single package at the given directory. Also load its
     /// dependency information but don't recurse into dependencies yet.
     async fn read_local_manifest_and_sources(
@@ -599,7 +678,7 @@ pub type PackageCache = FxHashMap<PackageKey, ProjectResult<PackageInfo>>;
 
 type Sources = Vec<(Arc<str>, Arc<str>)>;
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct PackageInfo {
     pub sources: Sources,
     pub language_features: LanguageFeatures,
@@ -607,7 +686,7 @@ pub struct PackageInfo {
     pub package_type: Option<PackageType>,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct PackageGraphSources {
     pub root: PackageInfo,
     pub packages: FxHashMap<PackageKey, PackageInfo>,
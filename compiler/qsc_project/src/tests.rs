@@ -8,7 +8,7 @@
 mod harness;
 
 use expect_test::expect;
-use harness::check;
+use harness::{check, check_watched_paths};
 
 #[test]
 fn basic_manifest() {
@@ -43,6 +43,12 @@ fn basic_manifest() {
                     packages: {},
                 },
                 lints: [],
+                format: FormatterConfig {
+                    max_line_width: None,
+                    indent_width: 4,
+                    trailing_comma: Preserve,
+                },
+                target: None,
                 errors: [],
             }"#]],
     );
@@ -81,6 +87,12 @@ fn circular_imports() {
                     packages: {},
                 },
                 lints: [],
+                format: FormatterConfig {
+                    max_line_width: None,
+                    indent_width: 4,
+                    trailing_comma: Preserve,
+                },
+                target: None,
                 errors: [],
             }"#]],
     );
@@ -119,6 +131,12 @@ fn different_files_same_manifest() {
                     packages: {},
                 },
                 lints: [],
+                format: FormatterConfig {
+                    max_line_width: None,
+                    indent_width: 4,
+                    trailing_comma: Preserve,
+                },
+                target: None,
                 errors: [],
             }"#]],
     );
@@ -149,6 +167,12 @@ fn empty_manifest() {
                     packages: {},
                 },
                 lints: [],
+                format: FormatterConfig {
+                    max_line_width: None,
+                    indent_width: 4,
+                    trailing_comma: Preserve,
+                },
+                target: None,
                 errors: [],
             }"#]],
     );
@@ -191,6 +215,12 @@ fn folder_structure() {
                     packages: {},
                 },
                 lints: [],
+                format: FormatterConfig {
+                    max_line_width: None,
+                    indent_width: 4,
+                    trailing_comma: Preserve,
+                },
+                target: None,
                 errors: [],
             }"#]],
     );
@@ -228,6 +258,12 @@ fn hidden_files() {
                     packages: {},
                 },
                 lints: [],
+                format: FormatterConfig {
+                    max_line_width: None,
+                    indent_width: 4,
+                    trailing_comma: Preserve,
+                },
+                target: None,
                 errors: [],
             }"#]],
     );
@@ -269,6 +305,12 @@ fn peer_file() {
                     packages: {},
                 },
                 lints: [],
+                format: FormatterConfig {
+                    max_line_width: None,
+                    indent_width: 4,
+                    trailing_comma: Preserve,
+                },
+                target: None,
                 errors: [],
             }"#]],
     );
@@ -299,6 +341,12 @@ fn language_feature() {
                     packages: {},
                 },
                 lints: [],
+                format: FormatterConfig {
+                    max_line_width: None,
+                    indent_width: 4,
+                    trailing_comma: Preserve,
+                },
+                target: None,
                 errors: [],
             }"#]],
     );
@@ -345,6 +393,12 @@ fn with_local_dep() {
                     },
                 },
                 lints: [],
+                format: FormatterConfig {
+                    max_line_width: None,
+                    indent_width: 4,
+                    trailing_comma: Preserve,
+                },
+                target: None,
                 errors: [],
             }"#]],
     );
@@ -406,6 +460,12 @@ fn transitive_dep() {
                     },
                 },
                 lints: [],
+                format: FormatterConfig {
+                    max_line_width: None,
+                    indent_width: 4,
+                    trailing_comma: Preserve,
+                },
+                target: None,
                 errors: [],
             }"#]],
     );
@@ -436,6 +496,12 @@ fn explicit_files_list() {
                     packages: {},
                 },
                 lints: [],
+                format: FormatterConfig {
+                    max_line_width: None,
+                    indent_width: 4,
+                    trailing_comma: Preserve,
+                },
+                target: None,
                 errors: [],
             }"#]],
     );
@@ -468,6 +534,12 @@ fn circular_dep() {
                     packages: {},
                 },
                 lints: [],
+                format: FormatterConfig {
+                    max_line_width: None,
+                    indent_width: 4,
+                    trailing_comma: Preserve,
+                },
+                target: None,
                 errors: [
                     Circular(
                         "REPLACED",
@@ -477,3 +549,115 @@ fn circular_dep() {
             }"#]],
     );
 }
+
+#[test]
+fn custom_target() {
+    check(
+        &"custom_target".into(),
+        &expect![[r#"
+        Project {
+            name: "custom_target",
+            path: "custom_target/qsharp.json",
+            package_graph_sources: PackageGraphSources {
+                root: PackageInfo {
+                    sources: [
+                        (
+                            "custom_target/src/Main.qs",
+                            "namespace Main {\n    @EntryPoint()\n    operation Main() : Result {\n        use q = Qubit();\n        H(q);\n        MResetZ(q)\n    }\n}\n",
+                        ),
+                    ],
+                    language_features: LanguageFeatures(
+                        0,
+                    ),
+                    dependencies: {},
+                    package_type: None,
+                },
+                packages: {},
+            },
+            lints: [],
+            format: FormatterConfig {
+                max_line_width: None,
+                indent_width: 4,
+                trailing_comma: Preserve,
+            },
+            target: Some(
+                TargetDescription {
+                    capabilities: TargetCapabilityFlags(
+                        Adaptive | IntegerComputations | QubitReset,
+                    ),
+                    gates: Some(
+                        [
+                            "h",
+                            "cx",
+                            "rz",
+                            "mresetz",
+                        ],
+                    ),
+                    max_qubits: Some(
+                        20,
+                    ),
+                    max_measurements: Some(
+                        100,
+                    ),
+                },
+            ),
+            errors: [],
+        }"#]],
+    );
+}
+
+#[test]
+fn invalid_target() {
+    check(
+        &"invalid_target".into(),
+        &expect![[r#"
+        Project {
+            name: "invalid_target",
+            path: "invalid_target/qsharp.json",
+            package_graph_sources: PackageGraphSources {
+                root: PackageInfo {
+                    sources: [
+                        (
+                            "invalid_target/src/Main.qs",
+                            "namespace Main {\n    @EntryPoint()\n    operation Main() : Result {\n        use q = Qubit();\n        H(q);\n        MResetZ(q)\n    }\n}\n",
+                        ),
+                    ],
+                    language_features: LanguageFeatures(
+                        0,
+                    ),
+                    dependencies: {},
+                    package_type: None,
+                },
+                packages: {},
+            },
+            lints: [],
+            format: FormatterConfig {
+                max_line_width: None,
+                indent_width: 4,
+                trailing_comma: Preserve,
+            },
+            target: None,
+            errors: [
+                TargetParse {
+                    path: "invalid_target/target.json",
+                    error: "unknown target capability `Teleportation` at line 3 column 1",
+                },
+            ],
+        }"#]],
+    );
+}
+
+#[test]
+fn watched_paths_include_local_dependencies() {
+    check_watched_paths(
+        &"with_local_dep".into(),
+        &expect![[r"
+            local_dep/qsharp.json
+            local_dep/src
+            local_dep/src/Dependency.qs
+            with_local_dep/qsharp.json
+            with_local_dep/src
+            with_local_dep/src/Main.qs
+        "]],
+    );
+}
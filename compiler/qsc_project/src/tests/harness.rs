@@ -2,6 +2,7 @@
 // Licensed under the MIT License.
 
 use std::{
+    fmt::Write,
     path::{Path, PathBuf},
     sync::Arc,
 };
@@ -33,6 +34,28 @@ pub fn check(project_path: &PathBuf, expect: &Expect) {
     expect.assert_eq(&format!("{project:#?}"));
 }
 
+/// Checks the paths that are watched for changes to the project, relative to the test projects
+/// directory.
+pub fn check_watched_paths(project_path: &PathBuf, expect: &Expect) {
+    let root_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("src")
+        .join("tests")
+        .join("projects");
+    let project = StdFs
+        .load_project(&root_path.join(project_path), None)
+        .expect("project should load");
+
+    let paths = project
+        .watched_paths()
+        .iter()
+        .fold(String::new(), |mut paths, path| {
+            let path = path.strip_prefix(&root_path).unwrap_or(path);
+            let _ = writeln!(paths, "{}", path.to_string_lossy().replace('\\', "/"));
+            paths
+        });
+    expect.assert_eq(&paths);
+}
+
 /// If the `Project` contains absolute paths, replace them with relative paths
 /// so that running the tests on different machines produce the same results.
 /// Some error messages may contain paths formatted into strings, in that case
@@ -59,7 +82,8 @@ fn normalize(project: &mut Project, root_path: &Path) {
         match err {
             Error::NoSrcDir { path }
             | Error::ManifestParse { path, .. }
-            | Error::GitHubManifestParse { path, .. } => {
+            | Error::GitHubManifestParse { path, .. }
+            | Error::TargetParse { path, .. } => {
                 let mut str = std::mem::take(path).into();
                 remove_absolute_path_prefix(&mut str, root_path);
                 *path = str.to_string();
@@ -0,0 +1,94 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+//! Watching files for changes, for the `--watch` modes of our binary targets. The watcher polls
+//! the modification times of the files, which needs no platform support and is cheap for the
+//! number of files in a Q# project.
+
+#[cfg(test)]
+mod tests;
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+/// The default time between two polls of the watched files.
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Watches a set of files and directories for changes.
+pub struct Watcher {
+    interval: Duration,
+    stamps: Vec<(PathBuf, Option<SystemTime>)>,
+}
+
+impl Watcher {
+    /// Creates a watcher for the given paths, which compares later changes against their
+    /// current state.
+    #[must_use]
+    pub fn new(paths: impl IntoIterator<Item = PathBuf>) -> Self {
+        let mut watcher = Self {
+            interval: POLL_INTERVAL,
+            stamps: Vec::new(),
+        };
+        watcher.watch(paths);
+        watcher
+    }
+
+    /// Sets the time between two polls of the watched files.
+    #[must_use]
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Replaces the watched paths, for example after the project was loaded again and its set
+    /// of files changed.
+    pub fn watch(&mut self, paths: impl IntoIterator<Item = PathBuf>) {
+        self.stamps = paths
+            .into_iter()
+            .map(|path| {
+                let stamp = modified(&path);
+                (path, stamp)
+            })
+            .collect();
+    }
+
+    /// Returns the watched paths that were modified, created or removed since the last poll.
+    pub fn poll(&mut self) -> Vec<PathBuf> {
+        let mut changed = Vec::new();
+        for (path, stamp) in &mut self.stamps {
+            let current = modified(path);
+            if current != *stamp {
+                *stamp = current;
+                changed.push(path.clone());
+            }
+        }
+        changed
+    }
+
+    /// Blocks until some of the watched paths change, and returns them. Editors often save a
+    /// file in several writes, so the watcher waits until a poll finds no further changes before
+    /// returning.
+    pub fn wait(&mut self) -> Vec<PathBuf> {
+        let mut changed = Vec::new();
+        loop {
+            thread::sleep(self.interval);
+            let more = self.poll();
+            if more.is_empty() && !changed.is_empty() {
+                changed.sort();
+                changed.dedup();
+                return changed;
+            }
+            changed.extend(more);
+        }
+    }
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
@@ -0,0 +1,68 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use super::Watcher;
+use std::{
+    fs::{self, File},
+    path::PathBuf,
+    time::{Duration, SystemTime},
+};
+
+/// Creates an empty directory for a test, named after the test.
+fn test_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("qsc-watch-{name}-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).expect("directory should be created");
+    dir
+}
+
+/// Writes the file and sets its modification time, so that the change is seen regardless of
+/// the resolution of the file system's timestamps.
+fn write(path: &PathBuf, contents: &str, seconds: u64) {
+    fs::write(path, contents).expect("file should be written");
+    File::options()
+        .write(true)
+        .open(path)
+        .and_then(|file| file.set_modified(SystemTime::UNIX_EPOCH + Duration::from_secs(seconds)))
+        .expect("modification time should be set");
+}
+
+#[test]
+fn poll_reports_modified_files() {
+    let dir = test_dir("modified");
+    let (a, b) = (dir.join("A.qs"), dir.join("B.qs"));
+    write(&a, "namespace A {}", 1);
+    write(&b, "namespace B {}", 1);
+    let mut watcher = Watcher::new([a.clone(), b.clone()]);
+    assert!(watcher.poll().is_empty());
+
+    write(&b, "namespace B { function F() : Unit {} }", 2);
+    assert_eq!(watcher.poll(), [b]);
+    assert!(watcher.poll().is_empty());
+    fs::remove_dir_all(&dir).expect("directory should be removed");
+}
+
+#[test]
+fn poll_reports_created_and_removed_files() {
+    let dir = test_dir("created");
+    let (a, b) = (dir.join("A.qs"), dir.join("B.qs"));
+    write(&a, "namespace A {}", 1);
+    let mut watcher = Watcher::new([a.clone(), b.clone()]);
+
+    write(&b, "namespace B {}", 1);
+    fs::remove_file(&a).expect("file should be removed");
+    assert_eq!(watcher.poll(), [a, b]);
+    fs::remove_dir_all(&dir).expect("directory should be removed");
+}
+
+#[test]
+fn wait_returns_the_changes() {
+    let dir = test_dir("wait");
+    let a = dir.join("A.qs");
+    write(&a, "namespace A {}", 1);
+    let mut watcher = Watcher::new([a.clone()]).with_interval(Duration::from_millis(1));
+
+    write(&a, "namespace A { function F() : Unit {} }", 2);
+    assert_eq!(watcher.wait(), [a]);
+    fs::remove_dir_all(&dir).expect("directory should be removed");
+}
@@ -1,11 +1,12 @@
 // Copyright (c) Microsoft Corporation.
 // Licensed under the MIT License.
 
-use miette::Diagnostic;
+use miette::{Diagnostic, LabeledSpan};
 use qsc_data_structures::{span::Span, target::TargetCapabilityFlags};
+use std::fmt::Display;
 use thiserror::Error;
 
-use crate::RuntimeFeatureFlags;
+use crate::{provenance::Reason, RuntimeFeatureFlags};
 
 #[derive(Clone, Debug, Diagnostic, Error)]
 pub enum Error {
@@ -212,6 +213,44 @@ pub enum Error {
     UseOfAdvancedOutput(#[label] Span),
 }
 
+/// An error together with the reasons why the value it is about is dynamic, which are shown as
+/// additional labels.
+#[derive(Clone, Debug, Error)]
+#[error("{error}")]
+pub struct ExplainedError {
+    pub error: Error,
+    /// The provenance of the dynamic value, from the error back to the measurement it depends on.
+    pub provenance: Vec<Reason>,
+}
+
+impl Diagnostic for ExplainedError {
+    fn code<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        self.error.code()
+    }
+
+    fn severity(&self) -> Option<miette::Severity> {
+        self.error.severity()
+    }
+
+    fn help<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        self.error.help()
+    }
+
+    fn url<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        self.error.url()
+    }
+
+    fn labels(&self) -> Option<Box<dyn Iterator<Item = LabeledSpan> + '_>> {
+        let provenance = self
+            .provenance
+            .iter()
+            .map(|reason| LabeledSpan::new_with_span(Some(reason.to_string()), reason.span));
+        Some(Box::new(
+            self.error.labels().into_iter().flatten().chain(provenance),
+        ))
+    }
+}
+
 #[must_use]
 pub fn generate_errors_from_runtime_features(
     runtime_features: RuntimeFeatureFlags,
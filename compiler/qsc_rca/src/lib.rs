@@ -17,6 +17,8 @@ mod cycle_detection;
 mod cyclic_callables;
 pub mod errors;
 mod overrider;
+pub mod provenance;
+pub mod report;
 mod scaffolding;
 
 use crate::common::set_indentation;
@@ -34,6 +36,7 @@ use qsc_fir::{
     ty::Ty,
 };
 use rustc_hash::FxHashSet;
+use serde::{Serialize, Serializer};
 
 use std::{
     cmp::Ord,
@@ -250,7 +253,7 @@ impl PackageComputeProperties {
 }
 
 /// The compute properties of an item.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
 pub enum ItemComputeProperties {
     /// The compute properties of a callable.
     Callable(CallableComputeProperties),
@@ -270,7 +273,7 @@ impl Display for ItemComputeProperties {
 }
 
 /// The compute properties of a callable.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
 pub struct CallableComputeProperties {
     /// The application generator set for the callable's body.
     pub body: ApplicationGeneratorSet,
@@ -306,7 +309,7 @@ impl Display for CallableComputeProperties {
 
 /// A set of compute properties associated to a callable or one of its elements, from which the properties of any
 /// particular call application can be derived.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
 pub struct ApplicationGeneratorSet {
     /// The inherent compute kind of a program element, which is determined by binding all the parameters it depends on
     /// to static values.
@@ -408,7 +411,7 @@ impl ApplicationGeneratorSet {
     }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
 pub enum ParamApplication {
     Element(ComputeKind),
     Array(ArrayParamApplication),
@@ -426,7 +429,7 @@ impl Display for ParamApplication {
     }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
 pub struct ArrayParamApplication {
     pub static_content_dynamic_size: ComputeKind,
     pub dynamic_content_static_size: ComputeKind,
@@ -457,7 +460,7 @@ impl Display for ArrayParamApplication {
     }
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, Serialize)]
 pub enum ComputeKind {
     Classical,
     Quantum(QuantumProperties),
@@ -576,7 +579,7 @@ impl ComputeKind {
 }
 
 /// The quantum properties of a program element.
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, Serialize)]
 pub struct QuantumProperties {
     /// The runtime features used by the program element.
     pub runtime_features: RuntimeFeatureFlags,
@@ -595,7 +598,7 @@ impl Display for QuantumProperties {
     }
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, Serialize)]
 pub enum ValueKind {
     /// The first runtime kind corresponds to the content of the array while the second corresponds to the size.
     Array(RuntimeKind, RuntimeKind),
@@ -703,7 +706,7 @@ impl ValueKind {
     }
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, Serialize)]
 pub enum RuntimeKind {
     Static,
     Dynamic,
@@ -906,3 +909,10 @@ impl RuntimeFeatureFlags {
             | RuntimeFeatureFlags::UseOfAdvancedOutput
     }
 }
+
+// Serialized as the names of the set flags, so that reports say which features are used.
+impl Serialize for RuntimeFeatureFlags {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter_names().map(|(name, _)| name))
+    }
+}
@@ -0,0 +1,511 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+//! Provenance explains why the value of an expression is dynamic, by following the data flow back
+//! from the expression to the measurement the value depends on. Each step of the way is a reason:
+//! a binding or assignment of a variable, a condition the value was computed under, an argument
+//! passed to a callable, or the result returned by one.
+
+use crate::{ComputeKind, PackageComputeProperties};
+use qsc_data_structures::span::Span;
+use qsc_fir::{
+    fir::{
+        BlockId, CallableDecl, CallableImpl, Expr, ExprId, ExprKind, Global, LocalItemId,
+        LocalVarId, Package, PackageId, PackageLookup, PackageStore, PackageStoreLookup, Pat,
+        PatId, PatKind, Res, Stmt, StmtId, StmtKind,
+    },
+    ty::{Prim, Ty},
+    visit::{walk_expr, Visitor},
+};
+use rustc_hash::{FxHashMap, FxHashSet};
+use std::{
+    fmt::{self, Display, Formatter},
+    rc::Rc,
+};
+
+/// A step in the explanation of why a value is dynamic.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Reason {
+    /// The span of the program element the reason is about.
+    pub span: Span,
+    pub kind: ReasonKind,
+}
+
+impl Display for Reason {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match &self.kind {
+            ReasonKind::Argument(callee) => {
+                write!(f, "a dynamic value is passed to `{callee}` here")
+            }
+            ReasonKind::Assignment(name) => write!(f, "`{name}` is assigned a dynamic value here"),
+            ReasonKind::Binding(name) => write!(f, "`{name}` is bound to a dynamic value here"),
+            ReasonKind::CallResult(callee) => write!(f, "`{callee}` returns a dynamic value here"),
+            ReasonKind::Condition => write!(f, "this condition depends on a measurement result"),
+            ReasonKind::Measurement(callee) => {
+                write!(f, "`{callee}` returns a measurement result here")
+            }
+        }
+    }
+}
+
+/// The kinds of reasons that make a value dynamic.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ReasonKind {
+    /// A dynamic value is passed as an argument to the callable.
+    Argument(String),
+    /// The variable is assigned a dynamic value.
+    Assignment(String),
+    /// The variable is bound to a dynamic value.
+    Binding(String),
+    /// The callable returns a dynamic value regardless of its arguments.
+    CallResult(String),
+    /// The value is computed under a dynamic condition.
+    Condition,
+    /// The callable returns measurement results and cannot be followed any further, because it is
+    /// an intrinsic or it is declared in another package.
+    Measurement(String),
+}
+
+/// Explains why the value of an expression is dynamic. The reasons are ordered from the expression
+/// back to the source of the dynamic value, and only refer to elements of the expression's package.
+/// The callable the expression belongs to is needed to resolve its local variables, and is `None`
+/// for the entry expression.
+#[must_use]
+pub fn explain(
+    package_store: &PackageStore,
+    package_id: PackageId,
+    compute_properties: &PackageComputeProperties,
+    callable: Option<LocalItemId>,
+    expr_id: ExprId,
+) -> Vec<Reason> {
+    let mut explainer = Explainer {
+        package_store,
+        package_id,
+        package: package_store.get(package_id),
+        compute_properties,
+        scopes: FxHashMap::default(),
+        visited: FxHashSet::default(),
+        reasons: Vec::new(),
+    };
+    explainer.explain(callable, expr_id);
+    explainer.reasons
+}
+
+struct Explainer<'a> {
+    package_store: &'a PackageStore,
+    package_id: PackageId,
+    package: &'a Package,
+    compute_properties: &'a PackageComputeProperties,
+    scopes: FxHashMap<Option<LocalItemId>, Scope>,
+    visited: FxHashSet<ExprId>,
+    reasons: Vec<Reason>,
+}
+
+impl<'a> Explainer<'a> {
+    fn explain(&mut self, scope: Option<LocalItemId>, expr_id: ExprId) {
+        if !self.visited.insert(expr_id) {
+            return;
+        }
+
+        let reasons_count = self.reasons.len();
+        let expr = self.package.get_expr(expr_id);
+        match &expr.kind {
+            ExprKind::Var(Res::Local(var), _) => self.explain_local(scope, *var),
+            ExprKind::Call(callee, arg) => self.explain_call(scope, expr, *callee, *arg),
+            ExprKind::If(condition, ..) | ExprKind::While(condition, _)
+                if self.is_dynamic(*condition) =>
+            {
+                self.explain_condition(scope, *condition);
+            }
+            _ => {
+                if let Some(sub_expr) = self.first_dynamic(sub_exprs(self.package, expr_id)) {
+                    self.explain(scope, sub_expr);
+                }
+            }
+        }
+
+        // An expression without dynamic parts can still be dynamic because of where it is, such as
+        // an assignment or a return under a dynamic condition.
+        if self.reasons.len() == reasons_count {
+            if let Some(condition) = self.dynamic_condition(scope, expr_id) {
+                self.explain_condition(scope, condition);
+            }
+        }
+    }
+
+    fn explain_call(
+        &mut self,
+        scope: Option<LocalItemId>,
+        expr: &Expr,
+        callee_id: ExprId,
+        arg_id: ExprId,
+    ) {
+        let dynamic_arg = self.first_dynamic(args(self.package, arg_id));
+        let Some((callee_item, decl)) = self.resolve_callable(callee_id) else {
+            // The callee is not a global callable, so it is either dynamic itself or the
+            // arguments are.
+            if let Some(expr_id) = self.first_dynamic([callee_id].into_iter().chain(dynamic_arg)) {
+                self.explain(scope, expr_id);
+            }
+            return;
+        };
+
+        let name = decl.name.name.to_string();
+        if let Some(arg) = dynamic_arg {
+            let arg_span = self.package.get_expr(arg).span;
+            self.push(arg_span, ReasonKind::Argument(name));
+            self.explain(scope, arg);
+        } else if has_result(&decl.output)
+            && (callee_item.is_none()
+                || matches!(
+                    decl.implementation,
+                    CallableImpl::Intrinsic | CallableImpl::SimulatableIntrinsic(_)
+                ))
+        {
+            // Measurements are intrinsics or come from other packages, such as the standard library,
+            // so this is where the data flow starts.
+            self.push(expr.span, ReasonKind::Measurement(name));
+        } else {
+            self.push(expr.span, ReasonKind::CallResult(name));
+            // The reasons inside of the callable can only be followed within this package.
+            if let (Some(item), CallableImpl::Spec(spec_impl)) = (callee_item, &decl.implementation)
+            {
+                if let Some(value) = self.block_value(spec_impl.body.block) {
+                    self.explain(Some(item), value);
+                } else {
+                    // The value comes from a return, which can be dynamic because of its value or
+                    // because of the condition it is under.
+                    let returns = self.scope(Some(item)).returns.clone();
+                    for return_expr in returns {
+                        let reasons_count = self.reasons.len();
+                        self.explain(Some(item), return_expr);
+                        if self.reasons.len() > reasons_count {
+                            break;
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn explain_condition(&mut self, scope: Option<LocalItemId>, condition: ExprId) {
+        let span = self.package.get_expr(condition).span;
+        self.push(span, ReasonKind::Condition);
+        self.explain(scope, condition);
+    }
+
+    fn explain_local(&mut self, scope: Option<LocalItemId>, var: LocalVarId) {
+        let scope_index = self.scope(scope);
+        let binding = scope_index.bindings.get(&var).cloned();
+        let assignments = scope_index
+            .assignments
+            .get(&var)
+            .cloned()
+            .unwrap_or_default();
+
+        // A variable is dynamic because of its initial value, or else because of a later assignment.
+        if let Some(binding) = binding.filter(|binding| self.is_dynamic(binding.value)) {
+            self.push(binding.span, ReasonKind::Binding(binding.name.to_string()));
+            self.explain(scope, binding.value);
+            return;
+        }
+
+        for assignment in assignments {
+            if self.is_dynamic(assignment.value) {
+                let span = self.package.get_expr(assignment.expr).span;
+                self.push(span, ReasonKind::Assignment(assignment.name.to_string()));
+                self.explain(scope, assignment.value);
+                return;
+            }
+            if let Some(condition) = self.dynamic_condition(scope, assignment.expr) {
+                let span = self.package.get_expr(assignment.expr).span;
+                self.push(span, ReasonKind::Assignment(assignment.name.to_string()));
+                self.explain_condition(scope, condition);
+                return;
+            }
+        }
+    }
+
+    /// The expression that the value of a block comes from, if it is dynamic.
+    fn block_value(&self, block: BlockId) -> Option<ExprId> {
+        let block = self.package.get_block(block);
+        let stmt = self.package.get_stmt(*block.stmts.last()?);
+        match stmt.kind {
+            StmtKind::Expr(expr) if self.is_dynamic(expr) => Some(expr),
+            _ => None,
+        }
+    }
+
+    /// The innermost dynamic condition that the expression is evaluated under.
+    fn dynamic_condition(&mut self, scope: Option<LocalItemId>, expr: ExprId) -> Option<ExprId> {
+        self.scope(scope);
+        let conditions = &self.scopes[&scope].conditions;
+        let mut condition = conditions.get(&expr).copied();
+        while let Some(id) = condition {
+            if self.is_dynamic(id) {
+                return Some(id);
+            }
+            condition = conditions.get(&id).copied();
+        }
+        None
+    }
+
+    fn first_dynamic(&self, exprs: impl IntoIterator<Item = ExprId>) -> Option<ExprId> {
+        exprs.into_iter().find(|expr| self.is_dynamic(*expr))
+    }
+
+    fn is_dynamic(&self, expr: ExprId) -> bool {
+        self.compute_properties.exprs.get(expr).is_some_and(|properties| {
+            matches!(
+                properties.inherent,
+                ComputeKind::Quantum(quantum_properties) if quantum_properties.value_kind.is_dynamic()
+            )
+        })
+    }
+
+    fn push(&mut self, span: Span, kind: ReasonKind) {
+        self.reasons.push(Reason { span, kind });
+    }
+
+    /// Resolves a callee expression to the callable it refers to, along with the item ID of the
+    /// callable if it is in the same package.
+    fn resolve_callable(&self, callee: ExprId) -> Option<(Option<LocalItemId>, &'a CallableDecl)> {
+        let ExprKind::Var(Res::Item(item_id), _) = &self.package.get_expr(callee).kind else {
+            return None;
+        };
+        let package_id = item_id.package.unwrap_or(self.package_id);
+        let Some(Global::Callable(decl)) = self
+            .package_store
+            .get_global((package_id, item_id.item).into())
+        else {
+            return None;
+        };
+        let local_item = (package_id == self.package_id).then_some(item_id.item);
+        Some((local_item, decl))
+    }
+
+    fn scope(&mut self, scope: Option<LocalItemId>) -> &Scope {
+        self.scopes
+            .entry(scope)
+            .or_insert_with(|| Scope::new(self.package, scope))
+    }
+}
+
+#[derive(Clone)]
+struct Binding {
+    name: Rc<str>,
+    span: Span,
+    value: ExprId,
+}
+
+#[derive(Clone)]
+struct Assignment {
+    name: Rc<str>,
+    expr: ExprId,
+    value: ExprId,
+}
+
+/// The data flow of the local variables in a callable or the entry expression.
+#[derive(Default)]
+struct Scope {
+    bindings: FxHashMap<LocalVarId, Binding>,
+    assignments: FxHashMap<LocalVarId, Vec<Assignment>>,
+    /// The innermost condition that each expression is evaluated under.
+    conditions: FxHashMap<ExprId, ExprId>,
+    /// The return expressions, in the order they appear.
+    returns: Vec<ExprId>,
+}
+
+impl Scope {
+    fn new(package: &Package, callable: Option<LocalItemId>) -> Self {
+        let mut builder = ScopeBuilder {
+            package,
+            scope: Scope::default(),
+            names: FxHashMap::default(),
+            conditions: Vec::new(),
+        };
+        match callable {
+            Some(item) => {
+                if let Some(Global::Callable(decl)) = package.get_global(item) {
+                    builder.visit_callable_decl(decl);
+                }
+            }
+            None => {
+                if let Some(entry) = package.entry {
+                    builder.visit_expr(entry);
+                }
+            }
+        }
+        builder.scope
+    }
+}
+
+struct ScopeBuilder<'a> {
+    package: &'a Package,
+    scope: Scope,
+    names: FxHashMap<LocalVarId, Rc<str>>,
+    conditions: Vec<ExprId>,
+}
+
+impl ScopeBuilder<'_> {
+    fn assign(&mut self, lhs: ExprId, expr: ExprId, value: ExprId) {
+        match &self.package.get_expr(lhs).kind {
+            ExprKind::Var(Res::Local(var), _) => {
+                let name = self.names.get(var).cloned().unwrap_or_else(|| "_".into());
+                self.scope
+                    .assignments
+                    .entry(*var)
+                    .or_default()
+                    .push(Assignment { name, expr, value });
+            }
+            ExprKind::Tuple(items) => {
+                for item in items {
+                    self.assign(*item, expr, value);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn bind(&mut self, pat: PatId, value: ExprId) {
+        match &self.package.get_pat(pat).kind {
+            PatKind::Bind(ident) => {
+                self.names.insert(ident.id, ident.name.clone());
+                self.scope.bindings.insert(
+                    ident.id,
+                    Binding {
+                        name: ident.name.clone(),
+                        span: ident.span,
+                        value,
+                    },
+                );
+            }
+            PatKind::Tuple(items) => {
+                for item in items {
+                    self.bind(*item, value);
+                }
+            }
+            PatKind::Discard => {}
+        }
+    }
+}
+
+impl<'a> Visitor<'a> for ScopeBuilder<'a> {
+    fn get_block(&self, id: BlockId) -> &'a qsc_fir::fir::Block {
+        self.package.get_block(id)
+    }
+
+    fn get_expr(&self, id: ExprId) -> &'a Expr {
+        self.package.get_expr(id)
+    }
+
+    fn get_pat(&self, id: PatId) -> &'a Pat {
+        self.package.get_pat(id)
+    }
+
+    fn get_stmt(&self, id: StmtId) -> &'a Stmt {
+        self.package.get_stmt(id)
+    }
+
+    fn visit_stmt(&mut self, id: StmtId) {
+        if let StmtKind::Local(_, pat, value) = self.get_stmt(id).kind {
+            self.bind(pat, value);
+        }
+        qsc_fir::visit::walk_stmt(self, id);
+    }
+
+    fn visit_ident(&mut self, ident: &'a qsc_fir::fir::Ident) {
+        self.names.insert(ident.id, ident.name.clone());
+    }
+
+    fn visit_expr(&mut self, id: ExprId) {
+        if let Some(condition) = self.conditions.last() {
+            self.scope.conditions.insert(id, *condition);
+        }
+
+        match &self.get_expr(id).kind {
+            ExprKind::If(condition, body, otherwise) => {
+                self.visit_expr(*condition);
+                self.conditions.push(*condition);
+                self.visit_expr(*body);
+                if let Some(otherwise) = otherwise {
+                    self.visit_expr(*otherwise);
+                }
+                self.conditions.pop();
+                return;
+            }
+            ExprKind::While(condition, block) => {
+                self.visit_expr(*condition);
+                self.conditions.push(*condition);
+                self.visit_block(*block);
+                self.conditions.pop();
+                return;
+            }
+            ExprKind::Assign(lhs, rhs)
+            | ExprKind::AssignOp(_, lhs, rhs)
+            | ExprKind::AssignField(lhs, _, rhs)
+            | ExprKind::AssignIndex(lhs, _, rhs) => self.assign(*lhs, id, *rhs),
+            ExprKind::Return(_) => self.scope.returns.push(id),
+            _ => {}
+        }
+        walk_expr(self, id);
+    }
+}
+
+/// The expressions of the arguments of a call.
+fn args(package: &Package, arg: ExprId) -> Vec<ExprId> {
+    match &package.get_expr(arg).kind {
+        ExprKind::Tuple(items) => items.clone(),
+        _ => vec![arg],
+    }
+}
+
+/// The direct sub-expressions of an expression, including the expressions of the statements of a
+/// block.
+fn sub_exprs(package: &Package, expr: ExprId) -> Vec<ExprId> {
+    struct SubExprs<'a> {
+        package: &'a Package,
+        exprs: Vec<ExprId>,
+    }
+
+    impl<'a> Visitor<'a> for SubExprs<'a> {
+        fn get_block(&self, id: BlockId) -> &'a qsc_fir::fir::Block {
+            self.package.get_block(id)
+        }
+
+        fn get_expr(&self, id: ExprId) -> &'a Expr {
+            self.package.get_expr(id)
+        }
+
+        fn get_pat(&self, id: PatId) -> &'a Pat {
+            self.package.get_pat(id)
+        }
+
+        fn get_stmt(&self, id: StmtId) -> &'a Stmt {
+            self.package.get_stmt(id)
+        }
+
+        fn visit_expr(&mut self, expr: ExprId) {
+            self.exprs.push(expr);
+        }
+    }
+
+    let mut sub_exprs = SubExprs {
+        package,
+        exprs: Vec::new(),
+    };
+    walk_expr(&mut sub_exprs, expr);
+    // The value of a block is its last expression, so it is the most likely to explain it.
+    sub_exprs.exprs.reverse();
+    sub_exprs.exprs
+}
+
+/// Whether values of the type contain measurement results.
+fn has_result(ty: &Ty) -> bool {
+    match ty {
+        Ty::Array(item) => has_result(item),
+        Ty::Prim(Prim::Result) => true,
+        Ty::Tuple(items) => items.iter().any(has_result),
+        _ => false,
+    }
+}
@@ -0,0 +1,92 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+//! A report of the compute properties of the callables in a package, which shows the compute kind,
+//! runtime features and value kinds the analysis inferred for each of them.
+
+use crate::{
+    common::set_indentation, CallableComputeProperties, ComputePropertiesLookup,
+    ItemComputeProperties, PackageStoreComputeProperties,
+};
+use indenter::indented;
+use qsc_data_structures::span::Span;
+use qsc_fir::fir::{ItemKind, PackageId, PackageLookup, PackageStore, StoreItemId};
+use serde::Serialize;
+use std::{
+    fmt::{self, Display, Formatter, Write},
+    rc::Rc,
+};
+
+/// The compute properties of the callables in a package.
+#[derive(Clone, Debug, Serialize)]
+pub struct PackageReport {
+    /// The callables, in the order they are declared in the package.
+    pub callables: Vec<CallableReport>,
+}
+
+impl PackageReport {
+    /// Creates the report for a package from the compute properties of its package store.
+    #[must_use]
+    pub fn new(
+        package_store: &PackageStore,
+        compute_properties: &PackageStoreComputeProperties,
+        package_id: PackageId,
+    ) -> Self {
+        let package = package_store.get(package_id);
+        let callables = package
+            .items
+            .iter()
+            .filter_map(|(item_id, item)| {
+                let ItemKind::Callable(decl) = &item.kind else {
+                    return None;
+                };
+                let ItemComputeProperties::Callable(properties) =
+                    compute_properties.get_item(StoreItemId::from((package_id, item_id)))
+                else {
+                    panic!("callable should have callable compute properties");
+                };
+                let name = match item.parent.map(|parent| &package.get_item(parent).kind) {
+                    Some(ItemKind::Namespace(namespace, _)) => {
+                        format!("{}.{}", namespace.name, decl.name.name).into()
+                    }
+                    _ => decl.name.name.clone(),
+                };
+                Some(CallableReport {
+                    name,
+                    span: decl.span,
+                    properties: properties.clone(),
+                })
+            })
+            .collect();
+        Self { callables }
+    }
+}
+
+impl Display for PackageReport {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        for callable in &self.callables {
+            writeln!(f, "{callable}")?;
+        }
+        Ok(())
+    }
+}
+
+/// The compute properties of a callable.
+#[derive(Clone, Debug, Serialize)]
+pub struct CallableReport {
+    /// The name of the callable, qualified by its namespace.
+    pub name: Rc<str>,
+    /// The span of the callable declaration.
+    pub span: Span,
+    /// The compute properties of each specialization of the callable.
+    pub properties: CallableComputeProperties,
+}
+
+impl Display for CallableReport {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let mut indent = set_indentation(indented(f), 0);
+        write!(indent, "{} {}:", self.name, self.span)?;
+        indent = set_indentation(indent, 1);
+        write!(indent, "\n{}", self.properties)
+    }
+}
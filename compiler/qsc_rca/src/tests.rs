@@ -13,7 +13,9 @@ mod intrinsics;
 mod loops;
 mod measurements;
 mod overrides;
+mod provenance;
 mod qubits;
+mod report;
 mod strings;
 mod structs;
 mod types;
@@ -0,0 +1,101 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+#![allow(clippy::needless_raw_string_hashes)]
+
+use super::{CompilationContext, PackageSearch};
+use crate::provenance::explain;
+use expect_test::{expect, Expect};
+use qsc_fir::fir::{CallableImpl, Global, PackageLookup, StmtKind};
+use qsc_lowerer::map_hir_package_to_fir;
+use std::fmt::Write;
+
+/// Checks the provenance of the value of the last binding in the body of the callable `Foo`.
+fn check_last_binding_provenance(source: &str, expect: &Expect) {
+    let mut compilation_context = CompilationContext::default();
+    compilation_context.update(source);
+    let package_id = map_hir_package_to_fir(compilation_context.compiler.package_id());
+    let package = compilation_context.fir_store.get(package_id);
+    let callable_id = package
+        .find_callable_id_by_name("Foo")
+        .expect("callable should exist");
+    let Some(Global::Callable(decl)) = package.get_global(callable_id) else {
+        panic!("item should be a callable");
+    };
+    let CallableImpl::Spec(spec_impl) = &decl.implementation else {
+        panic!("callable should have specializations");
+    };
+    let block = package.get_block(spec_impl.body.block);
+    let expr_id = block
+        .stmts
+        .iter()
+        .rev()
+        .find_map(|stmt_id| match package.get_stmt(*stmt_id).kind {
+            StmtKind::Local(_, _, expr_id) => Some(expr_id),
+            _ => None,
+        })
+        .expect("block should have a binding");
+
+    let reasons = explain(
+        &compilation_context.fir_store,
+        package_id,
+        compilation_context.compute_properties.get(package_id),
+        Some(callable_id),
+        expr_id,
+    );
+    let mut actual = String::new();
+    for reason in reasons {
+        writeln!(actual, "{reason} {}", reason.span).expect("writing should succeed");
+    }
+    expect.assert_eq(&actual);
+}
+
+#[test]
+fn provenance_of_classical_value_is_empty() {
+    check_last_binding_provenance(
+        r#"
+        operation Foo() : Unit {
+            let i = 1 + 2;
+        }"#,
+        &expect![[r#""#]],
+    );
+}
+
+#[test]
+fn provenance_follows_return_in_callee() {
+    check_last_binding_provenance(
+        r#"
+        operation Bar(q : Qubit) : Int {
+            if M(q) == One {
+                return 1;
+            }
+            0
+        }
+        operation Foo() : Unit {
+            use q = Qubit();
+            let i = Bar(q);
+        }"#,
+        &expect![[r#"
+            `Bar` returns a dynamic value here [217-223]
+            this condition depends on a measurement result [57-68]
+            `M` returns a measurement result here [57-61]
+        "#]],
+    );
+}
+
+#[test]
+fn provenance_follows_reassignment_of_mutable() {
+    check_last_binding_provenance(
+        r#"
+        operation Foo() : Unit {
+            use q = Qubit();
+            mutable r = Zero;
+            set r = M(q);
+            let b = r == One;
+        }"#,
+        &expect![[r#"
+            `r` is assigned a dynamic value here [105-117]
+            `M` returns a measurement result here [113-117]
+        "#]],
+    );
+}
@@ -0,0 +1,127 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+#![allow(clippy::needless_raw_string_hashes)]
+
+use super::CompilationContext;
+use crate::report::PackageReport;
+use expect_test::expect;
+use qsc_lowerer::map_hir_package_to_fir;
+
+fn report(source: &str) -> PackageReport {
+    let mut compilation_context = CompilationContext::default();
+    compilation_context.update(source);
+    let package_id = map_hir_package_to_fir(compilation_context.compiler.package_id());
+    PackageReport::new(
+        &compilation_context.fir_store,
+        compilation_context.get_compute_properties(),
+        package_id,
+    )
+}
+
+#[test]
+fn report_lists_callables_with_qualified_names() {
+    let report = report(
+        r#"
+        namespace Test {
+            function Add(a : Int, b : Int) : Int { a + b }
+            operation Measure(q : Qubit) : Result { M(q) }
+        }
+        operation Unqualified() : Unit {}"#,
+    );
+    expect![[r#"
+        Test.Add [38-84]:
+            CallableComputeProperties:
+                body: ApplicationsGeneratorSet:
+                    inherent: Classical
+                    dynamic_param_applications:
+                        [0]: [Parameter Type Element] Quantum: QuantumProperties:
+                            runtime_features: RuntimeFeatureFlags(UseOfDynamicInt)
+                            value_kind: Element(Dynamic)
+                        [1]: [Parameter Type Element] Quantum: QuantumProperties:
+                            runtime_features: RuntimeFeatureFlags(UseOfDynamicInt)
+                            value_kind: Element(Dynamic)
+                adj: <none>
+                ctl: <none>
+                ctl-adj: <none>
+        Test.Measure [97-143]:
+            CallableComputeProperties:
+                body: ApplicationsGeneratorSet:
+                    inherent: Quantum: QuantumProperties:
+                        runtime_features: RuntimeFeatureFlags(0x0)
+                        value_kind: Element(Dynamic)
+                    dynamic_param_applications:
+                        [0]: [Parameter Type Element] Quantum: QuantumProperties:
+                            runtime_features: RuntimeFeatureFlags(UseOfDynamicQubit)
+                            value_kind: Element(Dynamic)
+                adj: <none>
+                ctl: <none>
+                ctl-adj: <none>
+        Unqualified [162-195]:
+            CallableComputeProperties:
+                body: ApplicationsGeneratorSet:
+                    inherent: Classical
+                    dynamic_param_applications: <empty>
+                adj: <none>
+                ctl: <none>
+                ctl-adj: <none>
+    "#]]
+    .assert_eq(&report.to_string());
+}
+
+#[test]
+fn report_json_names_runtime_features() {
+    let report = report(
+        r#"
+        operation DynamicInt(q : Qubit) : Int {
+            M(q) == Zero ? 0 | 1
+        }"#,
+    );
+    expect![[r#"
+        {
+          "callables": [
+            {
+              "name": "DynamicInt",
+              "span": {
+                "lo": 9,
+                "hi": 91
+              },
+              "properties": {
+                "body": {
+                  "inherent": {
+                    "Quantum": {
+                      "runtime_features": [
+                        "UseOfDynamicBool",
+                        "UseOfDynamicInt"
+                      ],
+                      "value_kind": {
+                        "Element": "Dynamic"
+                      }
+                    }
+                  },
+                  "dynamic_param_applications": [
+                    {
+                      "Element": {
+                        "Quantum": {
+                          "runtime_features": [
+                            "UseOfDynamicBool",
+                            "UseOfDynamicInt",
+                            "UseOfDynamicQubit"
+                          ],
+                          "value_kind": {
+                            "Element": "Dynamic"
+                          }
+                        }
+                      }
+                    }
+                  ]
+                },
+                "adj": null,
+                "ctl": null,
+                "ctl_adj": null
+              }
+            }
+          ]
+        }"#]]
+    .assert_eq(&serde_json::to_string_pretty(&report).expect("report should serialize"));
+}
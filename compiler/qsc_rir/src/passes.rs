@@ -8,6 +8,7 @@ mod remap_block_ids;
 mod simplify_control_flow;
 mod ssa_check;
 mod ssa_transform;
+mod target_check;
 mod type_check;
 mod unreachable_code_check;
 
@@ -19,6 +20,7 @@ use remap_block_ids::remap_block_ids;
 use simplify_control_flow::simplify_control_flow;
 use ssa_check::check_ssa_form;
 use ssa_transform::transform_to_ssa;
+pub use target_check::{check_target, Error as TargetError};
 pub use type_check::check_types;
 pub use unreachable_code_check::check_unreachable_code;
 
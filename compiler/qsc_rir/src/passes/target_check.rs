@@ -0,0 +1,96 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+#[cfg(test)]
+mod tests;
+
+use miette::Diagnostic;
+use qsc_data_structures::target::TargetDescription;
+use rustc_hash::FxHashSet;
+use thiserror::Error;
+
+use crate::rir::{Callable, CallableType, Instruction, Program};
+
+const INTRINSIC_PREFIX: &str = "__quantum__qis__";
+
+#[derive(Clone, Debug, Diagnostic, Error, PartialEq, Eq)]
+pub enum Error {
+    #[error("the target does not support the `{0}` gate")]
+    #[diagnostic(help("the gates the target supports are listed in its capability file"))]
+    #[diagnostic(code("Qsc.TargetCheck.UnsupportedGate"))]
+    UnsupportedGate(String),
+
+    #[error("the program uses {0} qubits, but the target only has {1}")]
+    #[diagnostic(code("Qsc.TargetCheck.TooManyQubits"))]
+    TooManyQubits(u32, u32),
+
+    #[error("the program has {0} measurements, but the target only supports {1}")]
+    #[diagnostic(code("Qsc.TargetCheck.TooManyMeasurements"))]
+    TooManyMeasurements(u32, u32),
+}
+
+/// Checks that the program stays within the limits of the target: that it only calls the intrinsic
+/// gates the target supports, that it uses no more qubits than the target has, and that it has no
+/// more measurement instructions than the target supports. The program should have gone through the other passes, so that qubits are reindexed for
+/// targets without reset.
+#[must_use]
+pub fn check_target(program: &Program, target: &TargetDescription) -> Vec<Error> {
+    let mut errors = Vec::new();
+
+    if let Some(gates) = &target.gates {
+        let mut reported = FxHashSet::default();
+        for callable in calls(program) {
+            let Some(gate) = gate_name(callable) else {
+                continue;
+            };
+            if !gates.iter().any(|supported| supported == gate) && reported.insert(gate) {
+                errors.push(Error::UnsupportedGate(gate.to_string()));
+            }
+        }
+    }
+
+    if let Some(max_qubits) = target.max_qubits {
+        if program.num_qubits > max_qubits {
+            errors.push(Error::TooManyQubits(program.num_qubits, max_qubits));
+        }
+    }
+
+    if let Some(max_measurements) = target.max_measurements {
+        let measurements = calls(program)
+            .filter(|callable| callable.call_type == CallableType::Measurement)
+            .count();
+        let measurements = u32::try_from(measurements).unwrap_or(u32::MAX);
+        if measurements > max_measurements {
+            errors.push(Error::TooManyMeasurements(measurements, max_measurements));
+        }
+    }
+
+    errors
+}
+
+/// The callables of all the call instructions in the program.
+fn calls(program: &Program) -> impl Iterator<Item = &Callable> {
+    program
+        .blocks
+        .values()
+        .flat_map(|block| &block.0)
+        .filter_map(|instruction| match instruction {
+            Instruction::Call(callable_id, _, _) => Some(program.get_callable(*callable_id)),
+            _ => None,
+        })
+}
+
+/// The name of the gate an intrinsic callable implements, such as `h` for `__quantum__qis__h__body`
+/// and `s__adj` for `__quantum__qis__s__adj`, or `None` when the callable is not an intrinsic gate.
+/// Reading a result and recording output are done by the runtime rather than by the target, so
+/// they are not gates.
+fn gate_name(callable: &Callable) -> Option<&str> {
+    if matches!(
+        callable.call_type,
+        CallableType::Readout | CallableType::OutputRecording
+    ) {
+        return None;
+    }
+    let name = callable.name.strip_prefix(INTRINSIC_PREFIX)?;
+    Some(name.strip_suffix("__body").unwrap_or(name))
+}
@@ -0,0 +1,182 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+#![allow(clippy::needless_raw_string_hashes)]
+
+use expect_test::expect;
+use qsc_data_structures::target::{TargetCapabilityFlags, TargetDescription};
+
+use crate::{
+    builder::{cx_decl, h_decl, mresetz_decl, read_result_decl, result_record_decl},
+    rir::{
+        Block, BlockId, CallableId, Instruction, Literal, Operand, Program, Ty, Variable,
+        VariableId,
+    },
+};
+
+use super::check_target;
+
+fn bell_program() -> Program {
+    const H: CallableId = CallableId(0);
+    const CX: CallableId = CallableId(1);
+    const MRESETZ: CallableId = CallableId(2);
+    const RECORD: CallableId = CallableId(3);
+    let mut program = Program::new();
+    program.num_qubits = 2;
+    program.num_results = 2;
+    program.callables.insert(H, h_decl());
+    program.callables.insert(CX, cx_decl());
+    program.callables.insert(MRESETZ, mresetz_decl());
+    program.callables.insert(RECORD, result_record_decl());
+    program.blocks.insert(
+        BlockId(0),
+        Block(vec![
+            Instruction::Call(H, vec![Operand::Literal(Literal::Qubit(0))], None),
+            Instruction::Call(
+                CX,
+                vec![
+                    Operand::Literal(Literal::Qubit(0)),
+                    Operand::Literal(Literal::Qubit(1)),
+                ],
+                None,
+            ),
+            Instruction::Call(
+                MRESETZ,
+                vec![
+                    Operand::Literal(Literal::Qubit(0)),
+                    Operand::Literal(Literal::Result(0)),
+                ],
+                None,
+            ),
+            Instruction::Call(
+                MRESETZ,
+                vec![
+                    Operand::Literal(Literal::Qubit(1)),
+                    Operand::Literal(Literal::Result(1)),
+                ],
+                None,
+            ),
+            Instruction::Call(
+                RECORD,
+                vec![
+                    Operand::Literal(Literal::Result(0)),
+                    Operand::Literal(Literal::Pointer),
+                ],
+                None,
+            ),
+            Instruction::Return,
+        ]),
+    );
+    program
+}
+
+fn target(gates: &[&str], max_qubits: u32, max_measurements: u32) -> TargetDescription {
+    TargetDescription {
+        capabilities: TargetCapabilityFlags::Adaptive,
+        gates: Some(gates.iter().map(ToString::to_string).collect()),
+        max_qubits: Some(max_qubits),
+        max_measurements: Some(max_measurements),
+    }
+}
+
+#[test]
+fn program_within_target_limits_has_no_errors() {
+    let errors = check_target(&bell_program(), &target(&["h", "cx", "mresetz"], 2, 2));
+    assert!(errors.is_empty());
+}
+
+#[test]
+fn target_without_limits_accepts_any_program() {
+    let errors = check_target(
+        &bell_program(),
+        &TargetDescription::from(TargetCapabilityFlags::Adaptive),
+    );
+    assert!(errors.is_empty());
+}
+
+#[test]
+fn unsupported_gates_are_reported_once() {
+    let errors = check_target(&bell_program(), &target(&["h"], 2, 2));
+    expect![[r#"
+        [
+            UnsupportedGate(
+                "cx",
+            ),
+            UnsupportedGate(
+                "mresetz",
+            ),
+        ]
+    "#]]
+    .assert_debug_eq(&errors);
+}
+
+#[test]
+fn too_many_qubits_and_measurements_are_reported() {
+    let errors = check_target(&bell_program(), &target(&["h", "cx", "mresetz"], 1, 1));
+    expect![[r#"
+        [
+            TooManyQubits(
+                2,
+                1,
+            ),
+            TooManyMeasurements(
+                2,
+                1,
+            ),
+        ]
+    "#]]
+    .assert_debug_eq(&errors);
+}
+
+#[test]
+fn measurements_are_counted_from_measurement_calls() {
+    let mut program = bell_program();
+    program.num_results = 4;
+    let errors = check_target(&program, &target(&["h", "cx", "mresetz"], 2, 2));
+    assert!(errors.is_empty());
+}
+
+#[test]
+fn reading_results_and_recording_output_are_not_gates() {
+    const MRESETZ: CallableId = CallableId(0);
+    const READ_RESULT: CallableId = CallableId(1);
+    const RECORD: CallableId = CallableId(2);
+    let mut program = Program::new();
+    program.num_qubits = 1;
+    program.num_results = 1;
+    program.callables.insert(MRESETZ, mresetz_decl());
+    program.callables.insert(READ_RESULT, read_result_decl());
+    program.callables.insert(RECORD, result_record_decl());
+    program.blocks.insert(
+        BlockId(0),
+        Block(vec![
+            Instruction::Call(
+                MRESETZ,
+                vec![
+                    Operand::Literal(Literal::Qubit(0)),
+                    Operand::Literal(Literal::Result(0)),
+                ],
+                None,
+            ),
+            Instruction::Call(
+                READ_RESULT,
+                vec![Operand::Literal(Literal::Result(0))],
+                Some(Variable {
+                    variable_id: VariableId(0),
+                    ty: Ty::Boolean,
+                }),
+            ),
+            Instruction::Call(
+                RECORD,
+                vec![
+                    Operand::Literal(Literal::Result(0)),
+                    Operand::Literal(Literal::Pointer),
+                ],
+                None,
+            ),
+            Instruction::Return,
+        ]),
+    );
+    let errors = check_target(&program, &target(&["mresetz"], 1, 1));
+    assert!(errors.is_empty());
+}
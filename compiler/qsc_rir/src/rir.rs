@@ -3,10 +3,11 @@
 
 use indenter::{indented, Indented};
 use qsc_data_structures::{index_map::IndexMap, target::TargetCapabilityFlags};
+use serde::Serialize;
 use std::fmt::{self, Display, Formatter, Write};
 
 /// The root of the RIR.
-#[derive(Default, Clone)]
+#[derive(Default, Clone, Serialize)]
 pub struct Program {
     pub entry: CallableId,
     pub callables: IndexMap<CallableId, Callable>,
@@ -63,7 +64,7 @@ impl Program {
     }
 }
 
-#[derive(Default, Clone, Copy)]
+#[derive(Default, Clone, Copy, Serialize)]
 pub struct Config {
     pub capabilities: TargetCapabilityFlags,
 }
@@ -90,7 +91,7 @@ impl Config {
 }
 
 /// A unique identifier for a block in a RIR program.
-#[derive(Clone, Copy, Debug, Default, Hash, Eq, PartialEq, PartialOrd, Ord)]
+#[derive(Clone, Copy, Debug, Default, Hash, Eq, PartialEq, PartialOrd, Ord, Serialize)]
 pub struct BlockId(pub u32);
 
 impl From<BlockId> for usize {
@@ -129,11 +130,11 @@ impl BlockId {
 }
 
 /// A block is a collection of instructions.
-#[derive(Default, Clone)]
+#[derive(Default, Clone, Serialize)]
 pub struct Block(pub Vec<Instruction>);
 
 /// A unique identifier for a callable in a RIR program.
-#[derive(Clone, Copy, Debug, Default, Hash, Eq, PartialEq, PartialOrd, Ord)]
+#[derive(Clone, Copy, Debug, Default, Hash, Eq, PartialEq, PartialOrd, Ord, Serialize)]
 pub struct CallableId(pub u32);
 
 impl From<CallableId> for usize {
@@ -156,7 +157,7 @@ impl CallableId {
 }
 
 /// A callable.
-#[derive(Debug, Clone, Eq, PartialEq)]
+#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
 pub struct Callable {
     /// The name of the callable.
     pub name: String,
@@ -205,7 +206,7 @@ impl Display for Callable {
 }
 
 /// The type of callable.
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize)]
 pub enum CallableType {
     Measurement,
     Reset,
@@ -214,7 +215,7 @@ pub enum CallableType {
     Regular,
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
 pub enum ConditionCode {
     Eq,
     Ne,
@@ -251,7 +252,7 @@ impl Display for CallableType {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
 pub enum Instruction {
     Store(Operand, Variable),
     Call(CallableId, Vec<Operand>, Option<Variable>),
@@ -420,7 +421,7 @@ impl Display for Instruction {
     }
 }
 
-#[derive(Debug, Clone, Copy, Default, PartialEq, PartialOrd, Eq, Ord, Hash)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, PartialOrd, Eq, Ord, Hash, Serialize)]
 pub struct VariableId(pub u32);
 
 impl VariableId {
@@ -442,7 +443,7 @@ impl From<usize> for VariableId {
     }
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
 pub struct Variable {
     pub variable_id: VariableId,
     pub ty: Ty,
@@ -482,7 +483,7 @@ impl Variable {
     }
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
 pub enum Ty {
     Qubit,
     Result,
@@ -506,7 +507,7 @@ impl Display for Ty {
     }
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
 pub enum Operand {
     Literal(Literal),
     Variable(Variable),
@@ -538,7 +539,7 @@ impl Operand {
     }
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, Serialize)]
 pub enum Literal {
     Qubit(u32),
     Result(u32),
@@ -0,0 +1,392 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+//! The REPL session and its `:`-prefixed meta-commands, which inspect the session instead of
+//! evaluating Q#.
+
+#[cfg(test)]
+mod tests;
+
+use crate::completion::Completer;
+use miette::{Diagnostic, IntoDiagnostic, Report, WrapErr};
+use qsc::{
+    interpret::{self, CircuitEntryPoint, InterpretResult, Interpreter},
+    state_views,
+};
+use qsc_eval::{output::Receiver, state::format_state_id, val::Value};
+use serde_json::Value as Json;
+use std::{fs, io::Write, path::Path, process::ExitCode};
+use thiserror::Error;
+
+/// The meta-commands, with their arguments and descriptions.
+const COMMANDS: &[(&str, &str, &str)] = &[
+    ("type", "<expr>", "Show the type of an expression"),
+    ("circuit", "[<expr>]", "Show the circuit of an expression"),
+    ("qir", "<expr>", "Show the QIR of an expression"),
+    (
+        "estimate",
+        "<expr> [<params.json>]",
+        "Estimate the resources of an expression",
+    ),
+    ("state", "", "Show the quantum state"),
+    (
+        "probs",
+        "",
+        "Show the probability of measuring One for each qubit",
+    ),
+    ("bloch", "", "Show the Bloch vector of each qubit"),
+    (
+        "entropy",
+        "<qubit> [<qubit> ...]",
+        "Show the entanglement entropy of the given qubits",
+    ),
+    (
+        "expect",
+        "<paulis>",
+        "Show the expectation value of a Pauli string",
+    ),
+    ("load", "<file>", "Evaluate a Q# file"),
+    ("reset", "", "Start a new session with the initial sources"),
+    (
+        "seed",
+        "[<n>]",
+        "Seed the simulator, or unseed it without a seed",
+    ),
+    (
+        "doc",
+        "<name>",
+        "Show the documentation of a callable or type",
+    ),
+    ("help", "", "Show the commands"),
+];
+
+/// The commands whose argument is a Q# expression or name, which is completed as Q#.
+const EXPR_COMMANDS: &[&str] = &["type", "circuit", "qir", "estimate", "doc"];
+
+#[derive(Debug, Diagnostic, Error)]
+enum Error {
+    #[error("unknown command `:{0}`")]
+    #[diagnostic(help("expected one of {}", command_list()))]
+    UnknownCommand(String),
+
+    #[error("usage: :{0} {1}")]
+    Usage(&'static str, &'static str),
+
+    #[error("QIR generation needs a target profile")]
+    #[diagnostic(help(
+        "start qsi with `--profile base`, `--profile adaptive-ri` or `--target <FILE>`"
+    ))]
+    NoProfile,
+
+    #[error("resource estimation failed: {0}")]
+    Estimation(String),
+
+    #[error("could not start a new session")]
+    Reset,
+}
+
+fn command_list() -> String {
+    COMMANDS
+        .iter()
+        .map(|(name, _, _)| format!(":{name}"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// An interactive session, with the interpreter that evaluates its lines and the completer
+/// that completes them.
+pub(crate) struct Session {
+    interpreter: Interpreter,
+    /// Creates an interpreter for the initial sources of the session, when it is reset. It
+    /// reports its own errors.
+    new_interpreter: Box<dyn Fn() -> Result<Interpreter, ExitCode>>,
+    completer: Completer,
+}
+
+impl Session {
+    pub(crate) fn new(
+        new_interpreter: Box<dyn Fn() -> Result<Interpreter, ExitCode>>,
+        completer: Completer,
+    ) -> Result<Self, ExitCode> {
+        Ok(Self {
+            interpreter: new_interpreter()?,
+            new_interpreter,
+            completer,
+        })
+    }
+
+    /// Evaluates a line of Q#. Lines that evaluate without errors are kept for completion.
+    pub(crate) fn eval(&mut self, receiver: &mut impl Receiver, line: &str) -> InterpretResult {
+        let result = self.interpreter.eval_fragments(receiver, line);
+        if result.is_ok() {
+            self.completer.add_line(line);
+        }
+        result
+    }
+
+    /// Returns the completions for the line at the byte offset of the cursor. Command names are
+    /// completed after `:`, and Q# is completed in the arguments of the commands that take an
+    /// expression.
+    pub(crate) fn complete(&mut self, line: &str, cursor: usize) -> Vec<String> {
+        let Some(command) = line.trim_start().strip_prefix(':') else {
+            return self.completer.complete(line, cursor);
+        };
+        let command_start = line.len() - command.len();
+        let name_end = command
+            .find(char::is_whitespace)
+            .map_or(line.len(), |end| command_start + end);
+        if cursor <= name_end {
+            return COMMANDS
+                .iter()
+                .map(|(name, _, _)| (*name).to_string())
+                .collect();
+        }
+
+        if EXPR_COMMANDS.contains(&&line[command_start..name_end]) {
+            self.completer
+                .complete(&line[name_end..], cursor - name_end)
+        } else {
+            Vec::new()
+        }
+    }
+
+    /// Runs a meta-command, given without its `:` prefix, and writes its output.
+    #[allow(clippy::too_many_lines)]
+    pub(crate) fn run_command(
+        &mut self,
+        command: &str,
+        receiver: &mut impl Receiver,
+        out: &mut impl Write,
+    ) -> Result<(), Vec<Report>> {
+        let command = command.trim();
+        let (name, args) = command
+            .split_once(char::is_whitespace)
+            .unwrap_or((command, ""));
+        let args = args.trim();
+        match name {
+            "type" => {
+                let expr = required(name, args)?;
+                let ty = self.interpreter.type_of(expr).map_err(reports)?;
+                write_line(out, &ty.display())
+            }
+            "circuit" => {
+                let entry = if args.is_empty() {
+                    CircuitEntryPoint::EntryPoint
+                } else {
+                    CircuitEntryPoint::EntryExpr(args.to_string())
+                };
+                let circuit = self.interpreter.circuit(entry, false).map_err(reports)?;
+                write_line(out, &circuit.to_string())
+            }
+            "qir" => {
+                let expr = required(name, args)?;
+                let qir = self.interpreter.qirgen(expr).map_err(|errors| {
+                    if let [interpret::Error::UnsupportedRuntimeCapabilities] = errors.as_slice() {
+                        vec![Report::new(Error::NoProfile)]
+                    } else {
+                        reports(errors)
+                    }
+                })?;
+                write_line(out, qir.trim_end())
+            }
+            "estimate" => self.estimate(args, out),
+            "state" => {
+                let (state, qubit_count) = self.interpreter.get_quantum_state();
+                if qubit_count == 0 {
+                    return write_line(out, "No qubits allocated");
+                }
+                for (id, amplitude) in state {
+                    let id = format_state_id(&id, qubit_count);
+                    write_line(out, &format!("{id}: [{}, {}]", amplitude.re, amplitude.im))?;
+                }
+                Ok(())
+            }
+            "probs" => {
+                let (state, qubit_count) = self.interpreter.get_quantum_state();
+                for (qubit, view) in state_views::qubit_views(&state, qubit_count)
+                    .iter()
+                    .enumerate()
+                {
+                    write_line(
+                        out,
+                        &format!("q{qubit}: P(One) = {:.4}", view.probability_one),
+                    )?;
+                }
+                Ok(())
+            }
+            "bloch" => {
+                let (state, qubit_count) = self.interpreter.get_quantum_state();
+                for (qubit, view) in state_views::qubit_views(&state, qubit_count)
+                    .iter()
+                    .enumerate()
+                {
+                    let [x, y, z] = view.bloch_vector;
+                    write_line(out, &format!("q{qubit}: ({x:.4}, {y:.4}, {z:.4})"))?;
+                }
+                Ok(())
+            }
+            "entropy" => {
+                let qubits = args
+                    .split_whitespace()
+                    .map(str::parse)
+                    .collect::<Result<Vec<usize>, _>>()
+                    .ok()
+                    .filter(|qubits| !qubits.is_empty())
+                    .ok_or_else(|| usage(name))?;
+                let (state, qubit_count) = self.interpreter.get_quantum_state();
+                let entropy = state_views::entanglement_entropy(&state, qubit_count, &qubits)
+                    .map_err(|error| vec![Report::new(error)])?;
+                write_line(out, &format!("{entropy:.4}"))
+            }
+            "expect" => {
+                let (state, qubit_count) = self.interpreter.get_quantum_state();
+                let expectation = state_views::pauli_expectation(&state, qubit_count, args)
+                    .map_err(|error| vec![Report::new(error)])?;
+                write_line(out, &format!("{expectation:.4}"))
+            }
+            "load" => {
+                let path = required(name, args)?;
+                let contents = fs::read_to_string(path)
+                    .into_diagnostic()
+                    .with_context(|| format!("could not read source file `{path}`"))
+                    .map_err(|report| vec![report])?;
+                let value = self
+                    .interpreter
+                    .eval_source(receiver, path, &contents)
+                    .map_err(reports)?;
+                self.completer.add_line(&contents);
+                write_value(out, &value)
+            }
+            "reset" if args.is_empty() => {
+                self.interpreter =
+                    (self.new_interpreter)().map_err(|_| vec![Report::new(Error::Reset)])?;
+                self.completer.clear_lines();
+                Ok(())
+            }
+            "seed" => {
+                let seed = if args.is_empty() {
+                    None
+                } else {
+                    Some(args.parse::<u64>().map_err(|_| usage(name))?)
+                };
+                self.interpreter.set_quantum_seed(seed);
+                self.interpreter.set_classical_seed(seed);
+                Ok(())
+            }
+            "doc" => {
+                let name = required(name, args)?;
+                let doc = self.interpreter.doc(name).map_err(reports)?;
+                if doc.trim().is_empty() {
+                    write_line(out, &format!("no documentation for `{name}`"))
+                } else {
+                    write_line(out, doc.trim_end())
+                }
+            }
+            "help" => {
+                for (name, args, description) in COMMANDS {
+                    let usage = format!(":{name} {args}");
+                    write_line(out, &format!("{usage:<34}{description}"))?;
+                }
+                Ok(())
+            }
+            _ if COMMANDS.iter().any(|(command, _, _)| *command == name) => Err(usage(name)),
+            _ => Err(vec![Report::new(Error::UnknownCommand(name.to_string()))]),
+        }
+    }
+
+    /// Estimates the resources of an expression, with the parameters in the optional JSON file,
+    /// and writes a summary of each estimate.
+    fn estimate(&mut self, args: &str, out: &mut impl Write) -> Result<(), Vec<Report>> {
+        let (expr, params) = match args.rsplit_once(char::is_whitespace) {
+            Some((expr, file))
+                if Path::new(file)
+                    .extension()
+                    .is_some_and(|extension| extension.eq_ignore_ascii_case("json")) =>
+            {
+                let params = fs::read_to_string(file)
+                    .into_diagnostic()
+                    .with_context(|| format!("could not read parameters file `{file}`"))
+                    .map_err(|report| vec![report])?;
+                (expr.trim(), params)
+            }
+            _ => (args, "[{}]".to_string()),
+        };
+        let expr = required("estimate", expr)?;
+        let params = if params.trim_start().starts_with('{') {
+            format!("[{params}]")
+        } else {
+            params
+        };
+
+        let results = resource_estimator::estimate_expr(&mut self.interpreter, expr, &params)
+            .map_err(|errors| errors.into_iter().map(Report::new).collect::<Vec<_>>())?;
+        let results: Vec<Json> =
+            serde_json::from_str(&results).expect("resource estimates should be a JSON array");
+        let multiple = results.len() > 1;
+        for (index, result) in results.iter().enumerate() {
+            if multiple {
+                write_line(out, &format!("[{index}]"))?;
+            }
+            if let Some(message) = result.get("message").and_then(Json::as_str) {
+                return Err(vec![Report::new(Error::Estimation(message.to_string()))]);
+            }
+            let formatted = &result["physicalCountsFormatted"];
+            for (key, label) in [
+                ("physicalQubits", "Physical qubits"),
+                ("runtime", "Runtime"),
+                ("rqops", "rQOPS"),
+                ("algorithmicLogicalQubits", "Logical algorithmic qubits"),
+                ("algorithmicLogicalDepth", "Algorithmic depth"),
+                ("numTstates", "Number of T states"),
+                ("numTfactories", "Number of T factories"),
+            ] {
+                if let Some(value) = formatted.get(key).and_then(Json::as_str) {
+                    write_line(out, &format!("{label:<28}{value}"))?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn required<'a>(name: &str, args: &'a str) -> Result<&'a str, Vec<Report>> {
+    if args.is_empty() {
+        Err(usage(name))
+    } else {
+        Ok(args)
+    }
+}
+
+fn usage(name: &str) -> Vec<Report> {
+    let (name, args, _) = COMMANDS
+        .iter()
+        .find(|(command, _, _)| *command == name)
+        .expect("command should exist");
+    vec![Report::new(Error::Usage(name, args))]
+}
+
+/// Converts interpreter errors into reports, showing the stack traces of runtime errors first.
+fn reports(errors: Vec<interpret::Error>) -> Vec<Report> {
+    errors
+        .into_iter()
+        .map(|error| {
+            if let Some(stack_trace) = error.stack_trace() {
+                eprintln!("{stack_trace}");
+            }
+            Report::new(error)
+        })
+        .collect()
+}
+
+fn write_value(out: &mut impl Write, value: &Value) -> Result<(), Vec<Report>> {
+    match value {
+        Value::Tuple(items) if items.is_empty() => Ok(()),
+        value => write_line(out, &value.to_string()),
+    }
+}
+
+fn write_line(out: &mut impl Write, line: &str) -> Result<(), Vec<Report>> {
+    writeln!(out, "{line}")
+        .into_diagnostic()
+        .map_err(|report| vec![report])
+}
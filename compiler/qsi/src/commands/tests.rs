@@ -0,0 +1,246 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use super::Session;
+use crate::completion::Completer;
+use expect_test::{expect, Expect};
+use indoc::indoc;
+use qsc::{interpret::Interpreter, target::Profile, PackageStore};
+use qsc_data_structures::language_features::LanguageFeatures;
+use qsc_eval::output::GenericReceiver;
+use qsc_frontend::compile::SourceMap;
+use qsc_passes::PackageType;
+use std::{fs, str::from_utf8, sync::Arc};
+
+fn session_with_profile(source: &str, profile: Profile) -> Session {
+    let source: Arc<str> = source.into();
+    let sources = source.clone();
+    let new_interpreter = move || {
+        let (std_id, store): (_, PackageStore) =
+            qsc::compile::package_store_with_stdlib(profile.into());
+        Ok(Interpreter::new(
+            SourceMap::new([("test.qs".into(), sources.clone())], None),
+            PackageType::Lib,
+            profile.into(),
+            LanguageFeatures::default(),
+            store,
+            &[(std_id, None)],
+        )
+        .expect("interpreter should be created"))
+    };
+    let completer = Completer::new(vec![source], None, profile, LanguageFeatures::default());
+    Session::new(Box::new(new_interpreter), completer)
+        .unwrap_or_else(|_| panic!("session should be created"))
+}
+
+fn session(source: &str) -> Session {
+    session_with_profile(source, Profile::Unrestricted)
+}
+
+/// Runs the command and returns its output, or the messages and help of its errors.
+fn command(session: &mut Session, command: &str) -> String {
+    let mut output = Vec::new();
+    let mut out = Vec::new();
+    let result = session.run_command(command, &mut GenericReceiver::new(&mut output), &mut out);
+    let out = from_utf8(&out)
+        .expect("output should be valid UTF-8")
+        .to_string();
+    match result {
+        Ok(()) => out,
+        Err(reports) => reports
+            .iter()
+            .map(|report| match report.help() {
+                Some(help) => format!("error: {report}\nhelp: {help}\n"),
+                None => format!("error: {report}\n"),
+            })
+            .collect(),
+    }
+}
+
+fn check_command(session: &mut Session, input: &str, expect: &Expect) {
+    expect.assert_eq(&command(session, input));
+}
+
+fn eval(session: &mut Session, line: &str) {
+    let mut output = Vec::new();
+    session
+        .eval(&mut GenericReceiver::new(&mut output), line)
+        .expect("line should evaluate");
+}
+
+#[test]
+fn type_shows_the_type_of_an_expression() {
+    let mut session = session("");
+    eval(&mut session, "let xs = [1, 2];");
+    check_command(
+        &mut session,
+        "type (xs, 1.0)",
+        &expect!["(Int[], Double)\n"],
+    );
+    check_command(
+        &mut session,
+        "type",
+        &expect![[r"
+            error: usage: :type <expr>
+        "]],
+    );
+}
+
+#[test]
+fn unknown_command_lists_the_commands() {
+    check_command(
+        &mut session(""),
+        "tpye Zero",
+        &expect![[r"
+            error: unknown command `:tpye`
+            help: expected one of :type, :circuit, :qir, :estimate, :state, :probs, :bloch, :entropy, :expect, :load, :reset, :seed, :doc, :help
+        "]],
+    );
+}
+
+#[test]
+fn doc_shows_the_doc_comment() {
+    let mut session = session(indoc! {"
+        namespace Sample {
+            /// Says hello.
+            function Hello() : Unit {}
+            function Undocumented() : Unit {}
+        }
+    "});
+    check_command(&mut session, "doc Sample.Hello", &expect!["Says hello.\n"]);
+    check_command(
+        &mut session,
+        "doc Sample.Undocumented",
+        &expect!["no documentation for `Sample.Undocumented`\n"],
+    );
+}
+
+#[test]
+fn state_shows_the_amplitudes() {
+    let mut session = session("");
+    check_command(&mut session, "state", &expect!["No qubits allocated\n"]);
+    eval(&mut session, "use q = Qubit(); X(q);");
+    check_command(&mut session, "state", &expect!["|1⟩: [1, 0]\n"]);
+}
+
+#[test]
+fn seed_makes_random_results_repeatable() {
+    let mut session = session("");
+    let mut draw = || {
+        check_command(&mut session, "seed 42", &expect![""]);
+        let mut output = Vec::new();
+        session
+            .eval(
+                &mut GenericReceiver::new(&mut output),
+                "Microsoft.Quantum.Random.DrawRandomInt(0, 1000000)",
+            )
+            .expect("line should evaluate")
+            .to_string()
+    };
+    assert_eq!(draw(), draw());
+    check_command(
+        &mut session,
+        "seed many",
+        &expect![[r"
+            error: usage: :seed [<n>]
+        "]],
+    );
+}
+
+#[test]
+fn reset_forgets_evaluated_lines() {
+    let mut session = session("namespace Sample { function Hello() : Unit {} }");
+    eval(&mut session, "function Square(x : Int) : Int { x * x }");
+    assert!(session.complete("Squ", 3).contains(&"Square".to_string()));
+
+    check_command(&mut session, "reset", &expect![""]);
+    check_command(
+        &mut session,
+        "type Sample.Hello",
+        &expect!["(Unit -> Unit)\n"],
+    );
+    assert!(command(&mut session, "type Square").starts_with("error: "));
+    assert!(!session.complete("Squ", 3).contains(&"Square".to_string()));
+}
+
+#[test]
+fn load_evaluates_a_file() {
+    let path = std::env::temp_dir().join(format!("qsi-load-{}.qs", std::process::id()));
+    fs::write(&path, "function Square(x : Int) : Int { x * x }\nSquare(3)")
+        .expect("file should be written");
+    let mut session = session("");
+    check_command(
+        &mut session,
+        &format!("load {}", path.display()),
+        &expect!["9\n"],
+    );
+    check_command(&mut session, "type Square", &expect!["(Int -> Int)\n"]);
+    fs::remove_file(&path).expect("file should be removed");
+}
+
+#[test]
+fn qir_needs_a_profile() {
+    check_command(
+        &mut session(""),
+        "qir { use q = Qubit(); M(q) }",
+        &expect![[r"
+            error: QIR generation needs a target profile
+            help: start qsi with `--profile base`, `--profile adaptive-ri` or `--target <FILE>`
+        "]],
+    );
+
+    let qir = command(
+        &mut session_with_profile("", Profile::Base),
+        "qir { use q = Qubit(); M(q) }",
+    );
+    assert!(qir.contains("define void @ENTRYPOINT__main()"), "{qir}");
+}
+
+#[test]
+fn circuit_shows_the_circuit_of_an_expression() {
+    check_command(
+        &mut session(""),
+        "circuit { use q = Qubit(); H(q); M(q) }",
+        &expect![[r"
+            q_0    ── H ──── M ──
+                             ╘═══
+
+        "]],
+    );
+}
+
+#[test]
+fn estimate_summarizes_the_estimates() {
+    let output = command(
+        &mut session(""),
+        "estimate { use qs = Qubit[3]; T(qs[0]); CCNOT(qs[0], qs[1], qs[2]); }",
+    );
+    let labels = output
+        .lines()
+        .map(|line| line.split("  ").next().unwrap_or_default())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        labels,
+        [
+            "Physical qubits",
+            "Runtime",
+            "rQOPS",
+            "Logical algorithmic qubits",
+            "Algorithmic depth",
+            "Number of T states",
+            "Number of T factories",
+        ],
+        "{output}"
+    );
+}
+
+#[test]
+fn command_names_and_expressions_are_completed() {
+    let mut session = session("");
+    let completions = session.complete(":ty", 3);
+    assert!(completions.contains(&"type".to_string()));
+    assert!(session
+        .complete(":type Mes", 9)
+        .contains(&"Message".to_string()));
+    assert!(session.complete(":seed 4", 7).is_empty());
+}
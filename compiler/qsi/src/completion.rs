@@ -0,0 +1,153 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+//! Tab completion for the REPL, backed by the language service. The session is compiled by the
+//! language service as a notebook: the source files and the lines evaluated so far are its
+//! cells, and the line being edited is the last cell, where completions are requested.
+
+#[cfg(test)]
+mod tests;
+
+use async_trait::async_trait;
+use futures::{executor::LocalPool, task::LocalSpawnExt};
+use qsc::{
+    line_column::{Encoding, Position},
+    target::Profile,
+    LanguageFeatures,
+};
+use qsc_project::{DirEntry, EntryType, FileSystem, JSFileEntry, JSProjectHost, StdFs};
+use qsls::{protocol::NotebookMetadata, LanguageService};
+use std::{path::Path, sync::Arc};
+
+const NOTEBOOK_URI: &str = "qsi";
+
+pub(crate) struct Completer {
+    service: LanguageService,
+    /// Runs the language service's update worker whenever completions are requested.
+    pool: LocalPool,
+    metadata: NotebookMetadata,
+    /// The source files of the session, which are the first cells.
+    sources: Vec<Arc<str>>,
+    /// The lines evaluated so far.
+    lines: Vec<String>,
+    /// The version of the notebook, which changes with each request.
+    version: u32,
+}
+
+impl Completer {
+    /// Creates a completer for a session with the given source files, or for a project in the
+    /// given directory, whose sources the language service loads itself.
+    pub(crate) fn new(
+        sources: Vec<Arc<str>>,
+        project_root: Option<&Path>,
+        profile: Profile,
+        language_features: LanguageFeatures,
+    ) -> Self {
+        let mut service = LanguageService::new(Encoding::Utf8);
+        let mut worker = service.create_update_worker(|_| {}, ProjectHost);
+        let pool = LocalPool::new();
+        pool.spawner()
+            .spawn_local(async move { worker.run().await })
+            .expect("update worker should start");
+        Self {
+            service,
+            pool,
+            metadata: NotebookMetadata {
+                target_profile: Some(profile),
+                language_features,
+                manifest: None,
+                project_root: project_root.map(|dir| dir.to_string_lossy().to_string()),
+            },
+            sources,
+            lines: Vec::new(),
+            version: 0,
+        }
+    }
+
+    /// Adds a line that evaluated without errors to the session.
+    pub(crate) fn add_line(&mut self, line: &str) {
+        self.lines.push(line.to_string());
+    }
+
+    /// Forgets the lines evaluated so far, when the session is reset.
+    pub(crate) fn clear_lines(&mut self) {
+        self.lines.clear();
+    }
+
+    /// Returns the labels of the completions for the line at the byte offset of the cursor.
+    pub(crate) fn complete(&mut self, line: &str, cursor: usize) -> Vec<String> {
+        self.version += 1;
+        let cells = self
+            .sources
+            .iter()
+            .map(AsRef::as_ref)
+            .chain(self.lines.iter().map(String::as_str))
+            .chain([line])
+            .collect::<Vec<_>>();
+        let uris = (0..cells.len())
+            .map(|i| format!("{NOTEBOOK_URI}#cell{i}"))
+            .collect::<Vec<_>>();
+        self.service.update_notebook_document(
+            NOTEBOOK_URI,
+            self.metadata.clone(),
+            uris.iter()
+                .zip(&cells)
+                .map(|(uri, cell)| (uri.as_str(), self.version, *cell)),
+        );
+        self.pool.run_until_stalled();
+
+        let column = u32::try_from(cursor).expect("cursor offset should fit in u32");
+        self.service
+            .get_completions(
+                uris.last().expect("line should be a cell"),
+                Position { line: 0, column },
+            )
+            .items
+            .into_iter()
+            .map(|item| item.label)
+            .collect()
+    }
+}
+
+/// Gives the language service access to the file system, to load a project.
+struct ProjectHost;
+
+#[async_trait(?Send)]
+impl JSProjectHost for ProjectHost {
+    async fn read_file(&self, uri: &str) -> miette::Result<(Arc<str>, Arc<str>)> {
+        StdFs.read_file(Path::new(uri))
+    }
+
+    async fn list_directory(&self, dir_uri: &str) -> Vec<JSFileEntry> {
+        StdFs
+            .list_directory(Path::new(dir_uri))
+            .unwrap_or_default()
+            .into_iter()
+            .map(|entry| JSFileEntry {
+                name: entry.path().to_string_lossy().to_string(),
+                r#type: entry.entry_type().unwrap_or(EntryType::Unknown),
+            })
+            .collect()
+    }
+
+    async fn resolve_path(&self, base: &str, path: &str) -> Option<Arc<str>> {
+        StdFs
+            .resolve_path(Path::new(base), Path::new(path))
+            .ok()
+            .map(|path| path.to_string_lossy().into())
+    }
+
+    async fn fetch_github(
+        &self,
+        owner: &str,
+        repo: &str,
+        r#ref: &str,
+        path: &str,
+    ) -> miette::Result<Arc<str>> {
+        StdFs.fetch_github(owner, repo, r#ref, path)
+    }
+
+    async fn find_manifest_directory(&self, _doc_uri: &str) -> Option<Arc<str>> {
+        None
+    }
+}
@@ -0,0 +1,52 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use super::Completer;
+use qsc::{target::Profile, LanguageFeatures};
+use std::sync::Arc;
+
+fn completer(sources: &[&str]) -> Completer {
+    Completer::new(
+        sources.iter().map(|source| Arc::from(*source)).collect(),
+        None,
+        Profile::Unrestricted,
+        LanguageFeatures::default(),
+    )
+}
+
+fn contains(completions: &[String], label: &str) -> bool {
+    completions.iter().any(|completion| completion == label)
+}
+
+#[test]
+fn standard_library_callables_are_completed() {
+    let completions = completer(&[]).complete("Mes", 3);
+    assert!(contains(&completions, "Message"));
+}
+
+#[test]
+fn items_from_evaluated_lines_are_completed() {
+    let mut completer = completer(&[]);
+    assert!(!contains(&completer.complete("Squ", 3), "Square"));
+
+    completer.add_line("function Square(x : Int) : Int { x * x }");
+    assert!(contains(&completer.complete("Squ", 3), "Square"));
+
+    completer.clear_lines();
+    assert!(!contains(&completer.complete("Squ", 3), "Square"));
+}
+
+#[test]
+fn items_from_sources_are_completed() {
+    let mut completer = completer(&["namespace Sample { function Hello() : Unit {} }"]);
+    let completions = completer.complete("Sample.He", 9);
+    assert!(contains(&completions, "Hello"));
+}
+
+#[test]
+fn completions_are_for_the_cursor_position() {
+    let mut completer = completer(&[]);
+    completer.add_line("let register = 1;");
+    let completions = completer.complete("Message(); reg", 14);
+    assert!(contains(&completions, "register"));
+}
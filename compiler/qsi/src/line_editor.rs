@@ -0,0 +1,128 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+//! Line editing for the REPL, with persistent history and tab completion from the session.
+//! Editing is done by rustyline, which reads lines as they are when standard input isn't a
+//! terminal.
+
+#[cfg(test)]
+mod tests;
+
+use crate::commands::Session;
+use rustyline::{
+    completion::Completer, error::ReadlineError, highlight::Highlighter, hint::Hinter,
+    history::FileHistory, validate::Validator, CompletionType, Config, Context, Editor, Helper,
+};
+use std::{cell::RefCell, io, path::PathBuf};
+
+/// The most entries kept in the history.
+const HISTORY_LIMIT: usize = 1000;
+
+/// Completes the line being edited from the session.
+struct SessionHelper<'a> {
+    session: &'a RefCell<Session>,
+}
+
+impl Completer for SessionHelper<'_> {
+    type Candidate = String;
+
+    fn complete(
+        &self,
+        line: &str,
+        pos: usize,
+        _ctx: &Context<'_>,
+    ) -> rustyline::Result<(usize, Vec<String>)> {
+        let completions = self.session.borrow_mut().complete(line, pos);
+        Ok(candidates(line, pos, completions))
+    }
+}
+
+impl Hinter for SessionHelper<'_> {
+    type Hint = String;
+}
+
+impl Highlighter for SessionHelper<'_> {}
+
+impl Validator for SessionHelper<'_> {}
+
+impl Helper for SessionHelper<'_> {}
+
+/// Returns the start of the identifier that ends at the cursor, and the sorted, distinct
+/// completions that extend it.
+pub(crate) fn candidates(
+    line: &str,
+    cursor: usize,
+    completions: Vec<String>,
+) -> (usize, Vec<String>) {
+    let start = line[..cursor]
+        .char_indices()
+        .rev()
+        .find(|(_, c)| !(c.is_alphanumeric() || *c == '_'))
+        .map_or(0, |(i, c)| i + c.len_utf8());
+    let word = &line[start..cursor];
+    let mut candidates = completions
+        .into_iter()
+        .filter(|completion| completion.starts_with(word))
+        .collect::<Vec<_>>();
+    candidates.sort();
+    candidates.dedup();
+    (start, candidates)
+}
+
+/// Reads lines for the REPL, editing them in place in a terminal.
+pub(crate) struct LineEditor<'a> {
+    editor: Editor<SessionHelper<'a>, FileHistory>,
+    history_file: Option<PathBuf>,
+}
+
+impl<'a> LineEditor<'a> {
+    /// Creates an editor that completes lines from the session, with the history loaded from the
+    /// given file. A missing or unreadable file starts an empty history.
+    pub(crate) fn new(
+        session: &'a RefCell<Session>,
+        history_file: Option<PathBuf>,
+    ) -> io::Result<Self> {
+        let config = Config::builder()
+            .completion_type(CompletionType::List)
+            .max_history_size(HISTORY_LIMIT)
+            .map_err(io::Error::other)?
+            .history_ignore_dups(true)
+            .map_err(io::Error::other)?
+            .auto_add_history(false)
+            .build();
+        let mut editor = Editor::with_config(config).map_err(io::Error::other)?;
+        editor.set_helper(Some(SessionHelper { session }));
+        if let Some(path) = &history_file {
+            let _ = editor.load_history(path);
+        }
+        Ok(Self {
+            editor,
+            history_file,
+        })
+    }
+
+    /// Adds a line to the history, unless it is blank or repeats the last entry, and saves the
+    /// history to its file.
+    pub(crate) fn add_history(&mut self, line: &str) {
+        if line.trim().is_empty() {
+            return;
+        }
+        if let Ok(true) = self.editor.add_history_entry(line) {
+            if let Some(path) = &self.history_file {
+                let _ = self.editor.append_history(path);
+            }
+        }
+    }
+
+    /// Reads a line after showing the prompt, returning `None` when the input ends. An
+    /// interrupted line is returned as an empty line.
+    pub(crate) fn read_line(&mut self, prompt: &str) -> io::Result<Option<String>> {
+        match self.editor.readline(prompt) {
+            Ok(line) => Ok(Some(line)),
+            Err(ReadlineError::Interrupted) => Ok(Some(String::new())),
+            Err(ReadlineError::Eof) => Ok(None),
+            Err(ReadlineError::Io(e)) => Err(e),
+            Err(e) => Err(io::Error::other(e)),
+        }
+    }
+}
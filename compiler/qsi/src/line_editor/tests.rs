@@ -0,0 +1,38 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use super::candidates;
+
+fn completions(labels: &[&str]) -> Vec<String> {
+    labels.iter().map(ToString::to_string).collect()
+}
+
+#[test]
+fn candidates_complete_the_identifier_before_the_cursor() {
+    let line = "let x = Measure";
+    assert_eq!(
+        candidates(
+            line,
+            line.len(),
+            completions(&["MResetZ", "MeasureEachZ", "Measure", "MeasureEachZ"])
+        ),
+        (8, completions(&["Measure", "MeasureEachZ"]))
+    );
+}
+
+#[test]
+fn candidates_start_after_a_non_ascii_character() {
+    let line = "ψ⋅H";
+    assert_eq!(
+        candidates(line, line.len(), completions(&["H", "X"])),
+        (line.len() - 1, completions(&["H"]))
+    );
+}
+
+#[test]
+fn candidates_at_the_start_of_a_word_include_all_completions() {
+    assert_eq!(
+        candidates("H(", 2, completions(&["q", "a"])),
+        (2, completions(&["a", "q"]))
+    );
+}
@@ -0,0 +1,612 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+allocator::assign_global!();
+
+mod commands;
+mod completion;
+mod line_editor;
+
+use clap::{crate_version, Parser, ValueEnum};
+use commands::Session;
+use completion::Completer;
+use line_editor::LineEditor;
+use miette::{Context, IntoDiagnostic, Report, Result};
+use num_bigint::BigUint;
+use num_complex::Complex64;
+use qsc::{
+    cache::Cache,
+    hir::PackageId,
+    interpret::{self, InterpretResult, Interpreter},
+    packages::BuildableProgram,
+    target::{read_target_file, TargetDescription},
+    watch::{Input, WatchedProgram},
+    PackageStore,
+};
+use qsc_data_structures::{language_features::LanguageFeatures, target::TargetCapabilityFlags};
+use qsc_eval::{
+    output::{self, GenericReceiver, Receiver},
+    state::format_state_id,
+    val::Value,
+};
+use qsc_frontend::compile::{SourceContents, SourceMap, SourceName};
+use qsc_passes::PackageType;
+use qsc_project::{FileSystem, StdFs};
+use std::{
+    cell::RefCell,
+    env, fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+    process::ExitCode,
+    string::String,
+    sync::Arc,
+};
+
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq)]
+enum Profile {
+    /// This is the default profile, which allows all operations.
+    #[default]
+    Unrestricted,
+    /// This profile restricts the set of operations to those that are supported by the Base profile.
+    Base,
+    /// This profile restricts the set of operations to those that are supported by the `AdaptiveRI` profile.
+    AdaptiveRI,
+}
+
+impl From<Profile> for qsc::target::Profile {
+    fn from(profile: Profile) -> Self {
+        match profile {
+            Profile::Unrestricted => qsc::target::Profile::Unrestricted,
+            Profile::Base => qsc::target::Profile::Base,
+            Profile::AdaptiveRI => qsc::target::Profile::AdaptiveRI,
+        }
+    }
+}
+
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Debug, Parser)]
+#[command(name = "qsi", version = concat!(crate_version!(), " (", env!("QSHARP_GIT_HASH"), ")"))]
+#[command(author, about, next_line_help = true)]
+struct Cli {
+    /// Use the given file on startup as initial session input.
+    #[arg(long = "use")]
+    sources: Vec<PathBuf>,
+
+    /// Execute the given Q# expression on startup.
+    #[arg(long)]
+    entry: Option<String>,
+
+    /// Disable automatic inclusion of the standard library.
+    #[arg(long)]
+    nostdlib: bool,
+
+    /// Exit after loading the files or running the given file(s)/entry on the command line.
+    #[arg(long)]
+    exec: bool,
+
+    /// Path to a Q# manifest for a project
+    #[arg(short, long)]
+    qsharp_json: Option<PathBuf>,
+
+    /// Language features to compile with
+    #[arg(short, long)]
+    features: Vec<String>,
+
+    /// Compile the given files and interactive snippets in debug mode.
+    #[arg(long)]
+    debug: bool,
+
+    /// Run the code examples in the doc comments of the given files or project, then exit.
+    #[arg(long)]
+    doctest: bool,
+
+    /// Load the core and standard libraries and project dependencies from compiled packages
+    /// in the given directory, compiling and saving them there when they are missing.
+    #[arg(long, value_name = "DIR")]
+    cache_dir: Option<PathBuf>,
+
+    /// Target QIR profile for the session, which `:qir` generates code for.
+    #[arg(short, long)]
+    profile: Option<Profile>,
+
+    /// Target capability file that describes the capabilities, the intrinsic gates and the limits
+    /// of the hardware for the session, instead of a QIR profile.
+    #[arg(long, value_name = "FILE", conflicts_with = "profile")]
+    target: Option<PathBuf>,
+
+    /// Save the REPL history in the given file instead of `.qsi_history` in the home directory.
+    #[arg(long, value_name = "FILE")]
+    history_file: Option<PathBuf>,
+
+    /// With `--exec`, watch the source files for changes and run the entry point again whenever
+    /// they change.
+    #[arg(long, requires = "exec")]
+    watch: bool,
+}
+
+impl Cli {
+    fn profile(&self) -> qsc::target::Profile {
+        self.profile.unwrap_or_default().into()
+    }
+
+    /// The target given on the command line, as a capability file or a profile.
+    fn target_override(&self) -> miette::Result<Option<TargetDescription>> {
+        if let Some(path) = &self.target {
+            return read_target_file(path).map(Some);
+        }
+        Ok(self
+            .profile
+            .map(|_| TargetCapabilityFlags::from(self.profile()).into()))
+    }
+}
+
+/// The target for the session: the one given on the command line, or else the target named by
+/// the project manifest, or else the default profile.
+fn session_target(
+    target_override: Option<&TargetDescription>,
+    project_target: Option<TargetDescription>,
+) -> TargetDescription {
+    target_override
+        .cloned()
+        .or(project_target)
+        .unwrap_or_else(|| {
+            let profile: qsc::target::Profile = Profile::default().into();
+            TargetCapabilityFlags::from(profile).into()
+        })
+}
+
+struct TerminalReceiver;
+
+impl Receiver for TerminalReceiver {
+    fn state(
+        &mut self,
+        states: Vec<(BigUint, Complex64)>,
+        qubit_count: usize,
+    ) -> Result<(), output::Error> {
+        println!("DumpMachine:");
+        for (qubit, amplitude) in states {
+            let id = format_state_id(&qubit, qubit_count);
+            println!("{id}: [{}, {}]", amplitude.re, amplitude.im);
+        }
+
+        Ok(())
+    }
+
+    fn message(&mut self, msg: &str) -> Result<(), output::Error> {
+        println!("{msg}");
+        Ok(())
+    }
+}
+
+fn main() -> miette::Result<ExitCode> {
+    let cli = Cli::parse();
+    let features = LanguageFeatures::from_iter(cli.features.clone());
+    let target = cli.target_override()?;
+
+    if cli.doctest {
+        return Ok(run_doctests(&cli, features, target.as_ref()));
+    }
+
+    if cli.exec && cli.watch {
+        return Ok(watch(&cli, features, target.as_ref()));
+    }
+
+    if cli.exec {
+        let mut interpreter =
+            match new_interpreter(&cli, features, target.as_ref(), PackageType::Exe) {
+                Ok(interpreter) => interpreter,
+                Err(code) => return Ok(code),
+            };
+        return Ok(print_exec_result(
+            interpreter.eval_entry(&mut TerminalReceiver),
+        ));
+    }
+
+    let completer = Completer::new(
+        cli.sources
+            .iter()
+            .filter_map(|path| read_source(path).ok())
+            .map(|(_, contents)| contents)
+            .collect(),
+        cli.qsharp_json.as_deref().and_then(Path::parent),
+        cli.profile(),
+        features,
+    );
+    let history_file = cli.history_file.clone().or_else(|| {
+        env::var_os("HOME")
+            .or_else(|| env::var_os("USERPROFILE"))
+            .map(|home| PathBuf::from(home).join(".qsi_history"))
+    });
+    let entry = cli.entry.clone();
+    let mut session = match Session::new(
+        Box::new(move || new_interpreter(&cli, features, target.as_ref(), PackageType::Lib)),
+        completer,
+    ) {
+        Ok(session) => session,
+        Err(code) => return Ok(code),
+    };
+
+    if let Some(entry) = entry {
+        print_interpret_result(session.eval(&mut TerminalReceiver, &entry));
+    }
+
+    let session = RefCell::new(session);
+    let mut editor = LineEditor::new(&session, history_file).into_diagnostic()?;
+    repl(&session, &mut editor, &mut TerminalReceiver).into_diagnostic()?;
+
+    Ok(ExitCode::SUCCESS)
+}
+
+/// Creates an interpreter for the session sources, reporting any errors.
+fn new_interpreter(
+    cli: &Cli,
+    mut features: LanguageFeatures,
+    target_override: Option<&TargetDescription>,
+    package_type: PackageType,
+) -> Result<Interpreter, ExitCode> {
+    let (store, dependencies, source_map, target) = load(cli, &mut features, target_override)?;
+    let mut interpreter = (if cli.debug {
+        Interpreter::new_with_debug
+    } else {
+        Interpreter::new
+    })(
+        source_map,
+        package_type,
+        target.capabilities,
+        features,
+        store,
+        &dependencies,
+    )
+    .map_err(|errors| {
+        for error in errors {
+            eprintln!("error: {:?}", Report::new(error));
+        }
+        ExitCode::FAILURE
+    })?;
+    interpreter.set_target_limits(&target);
+    Ok(interpreter)
+}
+
+/// Loads the session sources, either from the project or from the given files, and returns the
+/// package store, the list of dependencies, the source map, and the target of the session.
+#[allow(clippy::type_complexity)]
+fn load(
+    cli: &Cli,
+    features: &mut LanguageFeatures,
+    target_override: Option<&TargetDescription>,
+) -> Result<
+    (
+        PackageStore,
+        Vec<(PackageId, Option<Arc<str>>)>,
+        SourceMap,
+        TargetDescription,
+    ),
+    ExitCode,
+> {
+    let cache = cli.cache_dir.as_ref().map(Cache::new);
+    if let Some(qsharp_json) = &cli.qsharp_json {
+        if let Some(dir) = qsharp_json.parent() {
+            load_project(dir, features, target_override, cache.as_ref())
+        } else {
+            eprintln!("{} must have a parent directory", qsharp_json.display());
+            Err(ExitCode::FAILURE)
+        }
+    } else {
+        let sources = match cli
+            .sources
+            .iter()
+            .map(read_source)
+            .collect::<miette::Result<Vec<_>>>()
+        {
+            Ok(sources) => sources,
+            Err(report) => {
+                eprintln!("error: {report:?}");
+                return Err(ExitCode::FAILURE);
+            }
+        };
+
+        let target = session_target(target_override, None);
+        let capabilities = target.capabilities;
+        let (store, dependencies) = match (&cache, cli.nostdlib) {
+            (Some(cache), true) => (PackageStore::new(cache.core()), vec![]),
+            (Some(cache), false) => {
+                let (std_id, store) = cache.package_store_with_stdlib(capabilities);
+                (store, vec![(std_id, None)])
+            }
+            (None, true) => (PackageStore::new(qsc::compile::core()), vec![]),
+            (None, false) => {
+                let (std_id, store) = qsc::compile::package_store_with_stdlib(capabilities);
+                (store, vec![(std_id, None)])
+            }
+        };
+        Ok((
+            store,
+            dependencies,
+            SourceMap::new(sources, cli.entry.clone().map(std::convert::Into::into)),
+            target,
+        ))
+    }
+}
+
+/// Runs the entry point of the session sources, then does it again whenever their files change.
+/// Only the sources are compiled again, unless the dependencies of the project changed.
+fn watch(
+    cli: &Cli,
+    features: LanguageFeatures,
+    target_override: Option<&TargetDescription>,
+) -> ExitCode {
+    let input = if let Some(qsharp_json) = &cli.qsharp_json {
+        let Some(dir) = qsharp_json.parent() else {
+            eprintln!("{} must have a parent directory", qsharp_json.display());
+            return ExitCode::FAILURE;
+        };
+        Input::Project(dir.to_path_buf())
+    } else {
+        Input::Files {
+            paths: cli.sources.clone(),
+            entry: cli.entry.clone().map(Into::into),
+            nostdlib: cli.nostdlib,
+        }
+    };
+    let mut program = WatchedProgram::new(
+        input,
+        target_override.map(|target| target.capabilities),
+        features,
+        cli.cache_dir.as_ref().map(Cache::new),
+    );
+
+    let mut interpreter = None;
+    loop {
+        match program.load() {
+            Ok((sources, dependencies)) => {
+                if let Some(dependencies) = dependencies {
+                    let target = session_target(target_override, dependencies.target);
+                    interpreter = Some(
+                        (if cli.debug {
+                            Interpreter::new_with_debug
+                        } else {
+                            Interpreter::new
+                        })(
+                            SourceMap::default(),
+                            PackageType::Lib,
+                            target.capabilities,
+                            dependencies.language_features,
+                            dependencies.store,
+                            &dependencies.dependencies,
+                        )
+                        .expect("empty sources should compile"),
+                    );
+                }
+                let interpreter = interpreter
+                    .as_mut()
+                    .expect("dependencies should be compiled on the first load");
+                print_exec_result(
+                    interpreter
+                        .update_sources(sources, PackageType::Exe)
+                        .and_then(|()| interpreter.eval_entry(&mut TerminalReceiver)),
+                );
+            }
+            Err(errors) => {
+                for error in errors {
+                    eprintln!("error: {:?}", Report::new(error));
+                }
+            }
+        }
+
+        eprintln!("Watching for changes...");
+        program.wait();
+        eprintln!();
+    }
+}
+
+/// Runs the code examples in the doc comments of the session sources and reports the ones
+/// that fail. Each example runs in a new interpreter, and its output is only shown if it fails.
+fn run_doctests(
+    cli: &Cli,
+    features: LanguageFeatures,
+    target_override: Option<&TargetDescription>,
+) -> ExitCode {
+    let new_interpreter = || new_interpreter(cli, features, target_override, PackageType::Lib);
+
+    let doctests = match new_interpreter() {
+        Ok(interpreter) => interpreter.doctests(),
+        Err(code) => return code,
+    };
+
+    let (mut passed, mut failed, mut ignored) = (0, 0, 0);
+    for doctest in &doctests {
+        let location = format!(
+            "{}:{}:{}",
+            doctest.source,
+            doctest.position.line + 1,
+            doctest.position.column + 1
+        );
+        if doctest.ignore {
+            println!("doctest {} ({location}) ... ignored", doctest.name);
+            ignored += 1;
+            continue;
+        }
+        let mut interpreter = match new_interpreter() {
+            Ok(interpreter) => interpreter,
+            Err(code) => return code,
+        };
+        let mut output = Vec::new();
+        let result = interpreter.run_doctest(&mut GenericReceiver::new(&mut output), doctest);
+        match result {
+            Ok(()) => {
+                println!("doctest {} ({location}) ... ok", doctest.name);
+                passed += 1;
+            }
+            Err(errors) => {
+                println!("doctest {} ({location}) ... FAILED", doctest.name);
+                io::stdout()
+                    .write_all(&output)
+                    .expect("standard out should be writable");
+                for error in errors {
+                    if let Some(stack_trace) = error.stack_trace() {
+                        eprintln!("{stack_trace}");
+                    }
+                    eprintln!("error: {:?}", Report::new(error));
+                }
+                failed += 1;
+            }
+        }
+    }
+
+    println!(
+        "\ndoctest result: {}. {passed} passed; {failed} failed; {ignored} ignored",
+        if failed == 0 { "ok" } else { "FAILED" }
+    );
+    if failed == 0 {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+fn repl(
+    session: &RefCell<Session>,
+    editor: &mut LineEditor,
+    receiver: &mut impl Receiver,
+) -> io::Result<()> {
+    while let Some(mut line) = editor.read_line("qsi$ ")? {
+        while line.ends_with('\\') {
+            if let Some(continuation) = editor.read_line("    > ")? {
+                line.pop(); // Remove backslash.
+                line.push_str(&continuation);
+            } else {
+                println!();
+                return Ok(());
+            }
+        }
+        editor.add_history(&line);
+
+        if let Some(command) = line.trim().strip_prefix(':') {
+            let result = session
+                .borrow_mut()
+                .run_command(command, receiver, &mut io::stdout());
+            if let Err(reports) = result {
+                for report in reports {
+                    eprintln!("error: {report:?}");
+                }
+            }
+        } else if !line.trim().is_empty() {
+            let result = session.borrow_mut().eval(receiver, &line);
+            print_interpret_result(result);
+        }
+    }
+
+    println!();
+    Ok(())
+}
+
+fn read_source(path: impl AsRef<Path>) -> miette::Result<(SourceName, SourceContents)> {
+    let path = path.as_ref();
+    let contents = fs::read_to_string(path)
+        .into_diagnostic()
+        .with_context(|| format!("could not read source file `{}`", path.display()))?;
+
+    Ok((path.to_string_lossy().into(), contents.into()))
+}
+
+fn print_interpret_result(result: InterpretResult) {
+    match result {
+        Ok(Value::Tuple(items)) if items.is_empty() => {}
+        Ok(value) => println!("{value}"),
+        Err(errors) => {
+            for error in errors {
+                if let Some(stack_trace) = error.stack_trace() {
+                    eprintln!("{stack_trace}");
+                }
+                let report = Report::new(error);
+                eprintln!("error: {report:?}");
+            }
+        }
+    }
+}
+
+fn print_exec_result(result: Result<Value, Vec<interpret::Error>>) -> ExitCode {
+    match result {
+        Ok(value) => {
+            println!("{value}");
+            ExitCode::SUCCESS
+        }
+        Err(errors) => {
+            for error in errors {
+                if let Some(stack_trace) = error.stack_trace() {
+                    eprintln!("{stack_trace}");
+                }
+                let report = Report::new(error);
+                eprintln!("error: {report:?}");
+            }
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Loads a project from the given directory and returns the package store, the list of
+/// dependencies, the source map, and the target of the session.
+/// Pre-populates the package store with all of the compiled dependencies.
+#[allow(clippy::type_complexity)]
+fn load_project(
+    dir: impl AsRef<Path>,
+    features: &mut LanguageFeatures,
+    target_override: Option<&TargetDescription>,
+    cache: Option<&Cache>,
+) -> Result<
+    (
+        PackageStore,
+        Vec<(PackageId, Option<Arc<str>>)>,
+        SourceMap,
+        TargetDescription,
+    ),
+    ExitCode,
+> {
+    let fs = StdFs;
+    let project = match fs.load_project(dir.as_ref(), None) {
+        Ok(project) => project,
+        Err(errs) => {
+            for e in errs {
+                eprintln!("{e:?}");
+            }
+            return Err(ExitCode::FAILURE);
+        }
+    };
+
+    if !project.errors.is_empty() {
+        for e in project.errors {
+            eprintln!("{e:?}");
+        }
+        return Err(ExitCode::FAILURE);
+    }
+
+    let target = session_target(target_override, project.target);
+
+    // This builds all the dependencies
+    let buildable_program = match cache {
+        Some(cache) => {
+            BuildableProgram::with_cache(target.capabilities, project.package_graph_sources, cache)
+        }
+        None => BuildableProgram::new(target.capabilities, project.package_graph_sources),
+    };
+
+    if !buildable_program.dependency_errors.is_empty() {
+        for e in buildable_program.dependency_errors {
+            eprintln!("{e:?}");
+        }
+        return Err(ExitCode::FAILURE);
+    }
+
+    let BuildableProgram {
+        store,
+        user_code,
+        user_code_dependencies,
+        ..
+    } = buildable_program;
+
+    let source_map = qsc::SourceMap::new(user_code.sources, None);
+
+    features.merge(LanguageFeatures::from_iter(user_code.language_features));
+
+    Ok((store, user_code_dependencies, source_map, target))
+}
@@ -0,0 +1,334 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+#[cfg(test)]
+mod tests;
+
+use crate::{
+    compilation::Compilation,
+    definition::get_definition,
+    protocol::{CallHierarchyCall, CallHierarchyItem},
+    qsc_utils::into_location,
+    symbols::{callable_symbol_kind, parent_namespace},
+};
+use qsc::{
+    ast::{self, visit::Visitor as AstVisitor},
+    hir::{
+        self,
+        visit::{walk_callable_decl, walk_expr, Visitor},
+        ItemKind, LocalItemId, PackageId,
+    },
+    line_column::{Encoding, Position},
+    location::Location,
+    resolve::Res,
+    CompileUnit, FunctorApp, PackageStore, Span,
+};
+use rustc_hash::FxHashSet;
+
+/// Identifies a callable across all the packages in a compilation.
+type CallableId = (PackageId, LocalItemId);
+
+/// A call to a global callable. The language service only keeps the HIR, so calls are
+/// resolved the same way as in `qsc_rca`, but without lowering to FIR.
+struct Call {
+    /// The named callable that contains the call. Calls made from lambdas are
+    /// attributed to the callable that declares the lambda.
+    caller: CallableId,
+    callee: CallableId,
+    functor_app: FunctorApp,
+    /// The span of the callee's name.
+    span: Span,
+}
+
+pub(crate) fn prepare_call_hierarchy(
+    compilation: &Compilation,
+    source_name: &str,
+    position: Position,
+    position_encoding: Encoding,
+) -> Option<CallHierarchyItem> {
+    let definition = get_definition(compilation, source_name, position, position_encoding)?;
+    let id = find_callable(compilation, &definition, position_encoding)?;
+    Some(call_hierarchy_item(compilation, id, position_encoding))
+}
+
+pub(crate) fn get_incoming_calls(
+    compilation: &Compilation,
+    item: &CallHierarchyItem,
+    position_encoding: Encoding,
+) -> Vec<CallHierarchyCall> {
+    let Some(target) = find_item(compilation, item, position_encoding) else {
+        return Vec::new();
+    };
+
+    let mut calls = Vec::new();
+    for (package_id, _) in &compilation.package_store {
+        calls.extend(
+            collect_calls(&compilation.package_store, package_id)
+                .into_iter()
+                .filter(|call| call.callee == target),
+        );
+    }
+    group_calls(compilation, calls, |call| call.caller, position_encoding)
+}
+
+pub(crate) fn get_outgoing_calls(
+    compilation: &Compilation,
+    item: &CallHierarchyItem,
+    position_encoding: Encoding,
+) -> Vec<CallHierarchyCall> {
+    let Some(target) = find_item(compilation, item, position_encoding) else {
+        return Vec::new();
+    };
+
+    let calls = collect_calls(&compilation.package_store, target.0)
+        .into_iter()
+        .filter(|call| call.caller == target)
+        .collect();
+    group_calls(compilation, calls, |call| call.callee, position_encoding)
+}
+
+/// Finds the callable whose name starts at `location`.
+fn find_callable(
+    compilation: &Compilation,
+    location: &Location,
+    position_encoding: Encoding,
+) -> Option<CallableId> {
+    compilation
+        .package_store
+        .iter()
+        .find_map(|(package_id, unit)| {
+            let source = unit.sources.find_by_name(&location.source)?;
+            let offset = source.offset
+                + location
+                    .range
+                    .start
+                    .to_utf8_byte_offset(position_encoding, &source.contents);
+            unit.package
+                .items
+                .values()
+                .find_map(|item| match &item.kind {
+                    ItemKind::Callable(decl) if decl.name.span.lo == offset => {
+                        Some((package_id, item.id))
+                    }
+                    _ => None,
+                })
+        })
+}
+
+fn find_item(
+    compilation: &Compilation,
+    item: &CallHierarchyItem,
+    position_encoding: Encoding,
+) -> Option<CallableId> {
+    let name_location = Location {
+        source: item.location.source.clone(),
+        range: item.selection_range,
+    };
+    find_callable(compilation, &name_location, position_encoding)
+}
+
+fn call_hierarchy_item(
+    compilation: &Compilation,
+    (package_id, item_id): CallableId,
+    position_encoding: Encoding,
+) -> CallHierarchyItem {
+    let package = &compilation
+        .package_store
+        .get(package_id)
+        .expect("package should exist")
+        .package;
+    let item = package.items.get(item_id).expect("item should exist");
+    let ItemKind::Callable(decl) = &item.kind else {
+        panic!("item should be a callable");
+    };
+    CallHierarchyItem {
+        name: decl.name.name.to_string(),
+        kind: callable_symbol_kind(decl.kind),
+        container_name: parent_namespace(package, item),
+        location: into_location(position_encoding, compilation, item.span, package_id),
+        selection_range: into_location(position_encoding, compilation, decl.name.span, package_id)
+            .range,
+    }
+}
+
+/// Groups calls by the callable returned by `key` and by the functors they apply,
+/// ordered by the location of that callable.
+fn group_calls(
+    compilation: &Compilation,
+    calls: Vec<Call>,
+    key: impl Fn(&Call) -> CallableId,
+    position_encoding: Encoding,
+) -> Vec<CallHierarchyCall> {
+    let mut groups: Vec<(CallableId, FunctorApp, PackageId, Vec<Span>)> = Vec::new();
+    for call in calls {
+        let id = key(&call);
+        // Call sites are always in the caller's package.
+        let package_id = call.caller.0;
+        match groups
+            .iter_mut()
+            .find(|(group_id, functor_app, ..)| *group_id == id && *functor_app == call.functor_app)
+        {
+            Some((.., spans)) => spans.push(call.span),
+            None => groups.push((id, call.functor_app, package_id, vec![call.span])),
+        }
+    }
+
+    let mut calls: Vec<CallHierarchyCall> = groups
+        .into_iter()
+        .map(|(id, functor_app, package_id, spans)| CallHierarchyCall {
+            item: call_hierarchy_item(compilation, id, position_encoding),
+            functor_app,
+            from_ranges: spans
+                .into_iter()
+                .map(|span| into_location(position_encoding, compilation, span, package_id).range)
+                .collect(),
+        })
+        .collect();
+    calls.sort_by(|a, b| {
+        let (a_start, b_start) = (a.item.location.range.start, b.item.location.range.start);
+        (&a.item.location.source, a_start.line, a_start.column)
+            .cmp(&(&b.item.location.source, b_start.line, b_start.column))
+            .then_with(|| {
+                (a.functor_app.controlled, a.functor_app.adjoint)
+                    .cmp(&(b.functor_app.controlled, b.functor_app.adjoint))
+            })
+    });
+    calls
+}
+
+/// Collects the calls to global callables made by the named callables in a package.
+fn collect_calls(package_store: &PackageStore, package_id: PackageId) -> Vec<Call> {
+    let unit = package_store.get(package_id).expect("package should exist");
+    let package = &unit.package;
+    let mut item_paths = ItemPathFinder {
+        unit,
+        spans: FxHashSet::default(),
+    };
+    item_paths.visit_package(&unit.ast.package);
+    let mut lambdas = LambdaFinder::default();
+    lambdas.visit_package(package);
+
+    let mut collector = CallCollector {
+        package_store,
+        package_id,
+        package,
+        item_paths: item_paths.spans,
+        caller: None,
+        calls: Vec::new(),
+    };
+    for item in package.items.values() {
+        if let ItemKind::Callable(decl) = &item.kind {
+            if !lambdas.lambdas.contains(&item.id) {
+                collector.caller = Some((package_id, item.id));
+                collector.visit_callable_decl(decl);
+            }
+        }
+    }
+    collector.calls
+}
+
+/// Finds the spans of the paths in the source that refer to global items.
+struct ItemPathFinder<'a> {
+    unit: &'a CompileUnit,
+    spans: FxHashSet<Span>,
+}
+
+impl AstVisitor<'_> for ItemPathFinder<'_> {
+    fn visit_path(&mut self, path: &ast::Path) {
+        if let Some(Res::Item(..) | Res::ExportedItem(..)) = self.unit.ast.names.get(path.id) {
+            self.spans.insert(path.span);
+        }
+    }
+}
+
+/// Finds the callables that were lifted from lambdas.
+#[derive(Default)]
+struct LambdaFinder {
+    lambdas: FxHashSet<LocalItemId>,
+}
+
+impl Visitor<'_> for LambdaFinder {
+    fn visit_expr(&mut self, expr: &hir::Expr) {
+        if let hir::ExprKind::Closure(_, item_id) = &expr.kind {
+            self.lambdas.insert(*item_id);
+        }
+        walk_expr(self, expr);
+    }
+}
+
+struct CallCollector<'a> {
+    package_store: &'a PackageStore,
+    package_id: PackageId,
+    package: &'a hir::Package,
+    /// Calls whose callee doesn't appear in the source, such as the qubit allocation
+    /// calls generated for `use` statements, are skipped.
+    item_paths: FxHashSet<Span>,
+    caller: Option<CallableId>,
+    calls: Vec<Call>,
+}
+
+impl<'a> Visitor<'a> for CallCollector<'a> {
+    fn visit_expr(&mut self, expr: &'a hir::Expr) {
+        match &expr.kind {
+            hir::ExprKind::Call(callee, _) => {
+                if let (Some(caller), Some((callee, functor_app, span))) =
+                    (self.caller, self.resolve_callee(callee))
+                {
+                    self.calls.push(Call {
+                        caller,
+                        callee,
+                        functor_app,
+                        span,
+                    });
+                }
+            }
+            hir::ExprKind::Closure(_, item_id) => {
+                let item = self.package.items.get(*item_id).expect("item should exist");
+                if let ItemKind::Callable(decl) = &item.kind {
+                    walk_callable_decl(self, decl);
+                }
+            }
+            _ => {}
+        }
+        walk_expr(self, expr);
+    }
+}
+
+impl CallCollector<'_> {
+    /// Resolves a callee expression that refers to a global callable, possibly with functors applied.
+    fn resolve_callee(&self, expr: &hir::Expr) -> Option<(CallableId, FunctorApp, Span)> {
+        let mut functor_app = FunctorApp::default();
+        let mut expr = expr;
+        loop {
+            match &expr.kind {
+                hir::ExprKind::UnOp(hir::UnOp::Functor(hir::Functor::Adj), operand) => {
+                    functor_app.adjoint = !functor_app.adjoint;
+                    expr = operand;
+                }
+                hir::ExprKind::UnOp(hir::UnOp::Functor(hir::Functor::Ctl), operand) => {
+                    functor_app.controlled += 1;
+                    expr = operand;
+                }
+                hir::ExprKind::Var(hir::Res::Item(item_id), _)
+                    if self.item_paths.contains(&expr.span) =>
+                {
+                    let callee = self.resolve_item(self.package_id, *item_id)?;
+                    return Some((callee, functor_app, expr.span));
+                }
+                _ => return None,
+            }
+        }
+    }
+
+    /// Follows exports to the callable that an item refers to. Returns `None` for other items,
+    /// such as the constructors of user-defined types.
+    fn resolve_item(&self, package_id: PackageId, item_id: hir::ItemId) -> Option<CallableId> {
+        let package_id = item_id.package.unwrap_or(package_id);
+        let package = &self.package_store.get(package_id)?.package;
+        match &package.items.get(item_id.item)?.kind {
+            ItemKind::Callable(_) => Some((package_id, item_id.item)),
+            ItemKind::Export(_, export) => self.resolve_item(package_id, *export),
+            _ => None,
+        }
+    }
+}
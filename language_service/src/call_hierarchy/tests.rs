@@ -0,0 +1,209 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+#![allow(clippy::needless_raw_string_hashes)]
+
+use super::{get_incoming_calls, get_outgoing_calls, prepare_call_hierarchy};
+use crate::{
+    protocol::{CallHierarchyCall, CallHierarchyItem},
+    test_utils::compile_with_markers,
+    Encoding,
+};
+use expect_test::{expect, Expect};
+use qsc::line_column::Range;
+use std::fmt::Write;
+
+fn display_range(range: Range) -> String {
+    format!(
+        "{}:{}-{}:{}",
+        range.start.line, range.start.column, range.end.line, range.end.column
+    )
+}
+
+fn display_item(item: &CallHierarchyItem) -> String {
+    format!(
+        "{:?} {} in {} at {} [{}]",
+        item.kind,
+        item.name,
+        item.container_name.as_deref().unwrap_or_default(),
+        item.location.source,
+        display_range(item.selection_range),
+    )
+}
+
+fn render_calls(calls: &[CallHierarchyCall]) -> String {
+    let mut actual = String::new();
+    for call in calls {
+        let from_ranges: Vec<String> = call
+            .from_ranges
+            .iter()
+            .map(|range| display_range(*range))
+            .collect();
+        writeln!(
+            actual,
+            "{} ({}) from [{}]",
+            display_item(&call.item),
+            call.functor_app,
+            from_ranges.join(", "),
+        )
+        .expect("writing to string should succeed");
+    }
+    actual
+}
+
+fn check(source: &str, expect: &Expect) {
+    let (compilation, cursor_position, _) = compile_with_markers(source, true);
+    let mut actual = String::new();
+    match prepare_call_hierarchy(&compilation, "<source>", cursor_position, Encoding::Utf8) {
+        Some(item) => {
+            let incoming = get_incoming_calls(&compilation, &item, Encoding::Utf8);
+            let outgoing = get_outgoing_calls(&compilation, &item, Encoding::Utf8);
+            writeln!(
+                actual,
+                "{}\nincoming:\n{}outgoing:\n{}",
+                display_item(&item),
+                render_calls(&incoming),
+                render_calls(&outgoing),
+            )
+            .expect("writing to string should succeed");
+        }
+        None => actual.push_str("none\n"),
+    }
+    expect.assert_eq(&actual);
+}
+
+#[test]
+fn incoming_and_outgoing_calls() {
+    check(
+        r#"
+namespace Test {
+    operation ↘Prepare(q : Qubit) : Unit {
+        Rotate(q);
+        Rotate(q);
+        Measure(q);
+    }
+    operation Rotate(q : Qubit) : Unit {}
+    operation Measure(q : Qubit) : Unit {}
+    operation Main() : Unit {
+        use q = Qubit();
+        Prepare(q);
+    }
+}"#,
+        &expect![[r#"
+            Operation Prepare in Test at <source> [2:14-2:21]
+            incoming:
+            Operation Main in Test at <source> [9:14-9:18] () from [11:8-11:15]
+            outgoing:
+            Operation Rotate in Test at <source> [7:14-7:20] () from [3:8-3:14, 4:8-4:14]
+            Operation Measure in Test at <source> [8:14-8:21] () from [5:8-5:15]
+
+        "#]],
+    );
+}
+
+#[test]
+fn functor_applications_are_distinguished() {
+    check(
+        r#"
+namespace Test {
+    operation Op(q : Qubit) : Unit is Adj + Ctl {}
+    operation ↘Main() : Unit {
+        use (c, q) = (Qubit(), Qubit());
+        Op(q);
+        Adjoint Op(q);
+        Controlled Op([c], q);
+        Controlled Adjoint Op([c], q);
+        Adjoint Adjoint Op(q);
+    }
+}"#,
+        &expect![[r#"
+            Operation Main in Test at <source> [3:14-3:18]
+            incoming:
+            outgoing:
+            Operation Op in Test at <source> [2:14-2:16] () from [5:8-5:10, 9:24-9:26]
+            Operation Op in Test at <source> [2:14-2:16] (Adjoint) from [6:16-6:18]
+            Operation Op in Test at <source> [2:14-2:16] (Controlled) from [7:19-7:21]
+            Operation Op in Test at <source> [2:14-2:16] (Controlled Adjoint) from [8:27-8:29]
+
+        "#]],
+    );
+}
+
+#[test]
+fn calls_from_lambdas_belong_to_enclosing_callable() {
+    check(
+        r#"
+namespace Test {
+    function ↘Double(x : Int) : Int { x * 2 }
+    function Main() : Int {
+        let f = y -> Double(y);
+        f(1)
+    }
+}"#,
+        &expect![[r#"
+            Function Double in Test at <source> [2:13-2:19]
+            incoming:
+            Function Main in Test at <source> [3:13-3:17] () from [4:21-4:27]
+            outgoing:
+
+        "#]],
+    );
+}
+
+#[test]
+fn dependency_callable() {
+    check(
+        r#"
+namespace Test {
+    open FakeStdLib;
+    operation Main() : Unit {
+        ↘Fake();
+    }
+}"#,
+        &expect![[r#"
+            Operation Fake in FakeStdLib at qsharp-library-source:<std> [1:26-1:30]
+            incoming:
+            Operation Main in Test at <source> [3:14-3:18] () from [4:8-4:12]
+            Operation RefFake in FakeStdLib at qsharp-library-source:<std> [11:26-11:33] () from [12:20-12:24]
+            outgoing:
+
+        "#]],
+    );
+}
+
+#[test]
+fn outgoing_calls_into_dependency() {
+    check(
+        r#"
+namespace Test {
+    open FakeStdLib;
+    newtype Pair = (Int, Int);
+    operation ↘Main() : Unit {
+        let p = Pair(1, 2);
+        FakeStdLib.RefFake();
+    }
+}"#,
+        &expect![[r#"
+            Operation Main in Test at <source> [4:14-4:18]
+            incoming:
+            outgoing:
+            Operation RefFake in FakeStdLib at qsharp-library-source:<std> [11:26-11:33] () from [6:8-6:26]
+
+        "#]],
+    );
+}
+
+#[test]
+fn not_a_callable() {
+    check(
+        r#"
+namespace Test {
+    operation Main() : Unit {
+        let ↘x = 1;
+    }
+}"#,
+        &expect![[r#"
+            none
+        "#]],
+    );
+}
@@ -1,17 +1,24 @@
 // Copyright (c) Microsoft Corporation.
 // Licensed under the MIT License.
 
+#[cfg(test)]
+mod tests;
+
 use miette::Diagnostic;
 use qsc::{
     compile::ErrorKind,
     error::WithSource,
+    hir::{ItemKind, Visibility},
     line_column::{Encoding, Range},
     Span,
 };
+use std::rc::Rc;
 
 use crate::{
     compilation::Compilation,
+    completion::{format_external_name, import_edit},
     protocol::{CodeAction, CodeActionKind, TextEdit, WorkspaceEdit},
+    refactor,
 };
 
 pub(crate) fn get_code_actions(
@@ -22,7 +29,20 @@ pub(crate) fn get_code_actions(
 ) -> Vec<CodeAction> {
     // Compute quick_fixes and other code_actions, and then merge them together
     let span = compilation.source_range_to_package_span(source_name, range, position_encoding);
-    quick_fixes(compilation, source_name, span, position_encoding)
+    let mut code_actions = quick_fixes(compilation, source_name, span, position_encoding);
+    code_actions.extend(refactor::extract_callable(
+        compilation,
+        source_name,
+        span,
+        position_encoding,
+    ));
+    code_actions.extend(refactor::inline_callable(
+        compilation,
+        source_name,
+        span,
+        position_encoding,
+    ));
+    code_actions
 }
 
 fn quick_fixes(
@@ -40,27 +60,37 @@ fn quick_fixes(
         .filter(|error| is_error_relevant(error, span));
 
     // For all diagnostics that are lints, we extract the code action edits from them.
+    // Names that couldn't be resolved get an action to import each matching item.
     for diagnostic in diagnostics {
-        if let ErrorKind::Lint(lint) = diagnostic.error() {
+        if is_unresolved_name(diagnostic) {
+            code_actions.extend(auto_imports(compilation, source_name, diagnostic, encoding));
+        } else if let ErrorKind::Lint(lint) = diagnostic.error() {
             if !lint.code_action_edits.is_empty() {
-                let source = compilation
-                    .user_unit()
-                    .sources
-                    .find_by_name(source_name)
-                    .expect("source should exist");
-                let text_edits: Vec<TextEdit> = lint
-                    .code_action_edits
-                    .iter()
-                    .map(|(new_text, span)| TextEdit {
+                // Edits can be in other sources than the diagnostic, like the calls
+                // updated when removing a parameter.
+                let mut changes: Vec<(String, Vec<TextEdit>)> = Vec::new();
+                for (new_text, span) in &lint.code_action_edits {
+                    let source = compilation
+                        .user_unit()
+                        .sources
+                        .find_by_offset(span.lo)
+                        .expect("source should exist");
+                    let text_edit = TextEdit {
                         new_text: new_text.clone(),
-                        range: qsc::line_column::Range::from_span(encoding, &source.contents, span),
-                    })
-                    .collect();
+                        range: qsc::line_column::Range::from_span(
+                            encoding,
+                            &source.contents,
+                            &(*span - source.offset),
+                        ),
+                    };
+                    match changes.iter_mut().find(|(name, _)| **name == *source.name) {
+                        Some((_, text_edits)) => text_edits.push(text_edit),
+                        None => changes.push((source.name.to_string(), vec![text_edit])),
+                    }
+                }
                 code_actions.push(CodeAction {
                     title: diagnostic.to_string(),
-                    edit: Some(WorkspaceEdit {
-                        changes: vec![(source_name.to_string(), text_edits)],
-                    }),
+                    edit: Some(WorkspaceEdit { changes }),
                     kind: Some(CodeActionKind::QuickFix),
                     is_preferred: None,
                 });
@@ -71,6 +101,93 @@ fn quick_fixes(
     code_actions
 }
 
+fn is_unresolved_name(error: &WithSource<ErrorKind>) -> bool {
+    matches!(error.error(), ErrorKind::Frontend(_))
+        && error
+            .code()
+            .is_some_and(|code| code.to_string() == "Qsc.Resolve.NotFound")
+}
+
+/// Offers to import the unresolved name from every namespace, in the user's
+/// package or its dependencies, that declares or exports an item with that name.
+fn auto_imports(
+    compilation: &Compilation,
+    source_name: &str,
+    error: &WithSource<ErrorKind>,
+    encoding: Encoding,
+) -> Vec<CodeAction> {
+    let Some(span) = resolve_span(error) else {
+        return Vec::new();
+    };
+    let Some(source) = compilation.user_unit().sources.find_by_name(source_name) else {
+        return Vec::new();
+    };
+    let Some(name) = source
+        .contents
+        .get((span.lo - source.offset) as usize..(span.hi - source.offset) as usize)
+    else {
+        return Vec::new();
+    };
+    // Qualified names refer to a namespace that doesn't exist, so an import won't help.
+    if name.contains('.') {
+        return Vec::new();
+    }
+
+    let mut paths = Vec::new();
+    for (package_id, unit) in &compilation.package_store {
+        let is_user_package = compilation.user_package_id == package_id;
+        let package_alias_from_manifest =
+            compilation.dependencies.get(&package_id).cloned().flatten();
+        for item in unit.package.items.values() {
+            let item_name = match &item.kind {
+                ItemKind::Callable(decl) => &decl.name,
+                ItemKind::Ty(name, _) | ItemKind::Export(name, _) => name,
+                ItemKind::Namespace(..) => continue,
+            };
+            if &*item_name.name != name
+                || (matches!(item.visibility, Visibility::Internal) && !is_user_package)
+            {
+                continue;
+            }
+            let Some(ItemKind::Namespace(namespace, _)) = item
+                .parent
+                .and_then(|parent| unit.package.items.get(parent))
+                .map(|parent| &parent.kind)
+            else {
+                continue;
+            };
+            let qualification = namespace
+                .str_iter()
+                .into_iter()
+                .map(Rc::from)
+                .collect::<Vec<_>>();
+            paths.push(format_external_name(
+                &package_alias_from_manifest,
+                &qualification,
+                Some(name),
+            ));
+        }
+    }
+    // Items that are exported from their own namespace would otherwise be offered twice.
+    paths.sort();
+    paths.dedup();
+
+    paths
+        .into_iter()
+        .filter_map(|path| {
+            let edit = import_edit(compilation, span.lo, &path, encoding)?;
+            Some(CodeAction {
+                title: format!("Import `{path}`"),
+                edit: Some(WorkspaceEdit {
+                    changes: vec![(source_name.to_string(), vec![edit])],
+                }),
+                kind: Some(CodeActionKind::QuickFix),
+                is_preferred: None,
+            })
+        })
+        .collect()
+}
+
 /// Returns true if the error has a `Range` and it overlaps
 /// with the code action's range.
 fn is_error_relevant(error: &WithSource<ErrorKind>, span: Span) -> bool {
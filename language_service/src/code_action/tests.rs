@@ -0,0 +1,524 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use super::get_code_actions;
+use crate::compilation::Compilation;
+use expect_test::{expect, Expect};
+use qsc::{
+    line_column::{Encoding, Position, Range},
+    target::Profile,
+    LanguageFeatures, PackageType,
+};
+use qsc_linter::{HirLint, LintConfig, LintKind, LintLevel};
+use qsc_project::{PackageGraphSources, PackageInfo};
+use rustc_hash::FxHashMap;
+use std::sync::Arc;
+
+fn check(sources: &[(&str, &str)], lints: &[HirLint], source_name: &str, expect: &Expect) {
+    let lints_config: Vec<LintConfig> = lints
+        .iter()
+        .map(|lint| LintConfig {
+            kind: LintKind::Hir(*lint),
+            level: LintLevel::Warn,
+        })
+        .collect();
+    let compilation = Compilation::new(
+        PackageType::Lib,
+        Profile::Unrestricted.into(),
+        LanguageFeatures::default(),
+        &lints_config,
+        PackageGraphSources {
+            root: PackageInfo {
+                sources: sources
+                    .iter()
+                    .map(|(name, contents)| (Arc::from(*name), Arc::from(*contents)))
+                    .collect(),
+                language_features: LanguageFeatures::default(),
+                dependencies: FxHashMap::default(),
+                package_type: None,
+            },
+            packages: FxHashMap::default(),
+        },
+        Vec::new(),
+    );
+
+    let range = Range {
+        start: Position { line: 0, column: 0 },
+        end: Position {
+            line: u32::MAX,
+            column: 0,
+        },
+    };
+    let actions = get_code_actions(&compilation, source_name, range, Encoding::Utf8);
+    expect.assert_debug_eq(&actions);
+}
+
+#[test]
+fn remove_unused_local() {
+    check(
+        &[
+            ("a.qs", "namespace A { function F() : Unit {} }"),
+            (
+                "b.qs",
+                "namespace B {\n    function G() : Unit {\n        let x = 1;\n    }\n}",
+            ),
+        ],
+        &[HirLint::UnusedLocal],
+        "b.qs",
+        &expect![[r#"
+            [
+                CodeAction {
+                    title: "unused local variable",
+                    edit: Some(
+                        WorkspaceEdit {
+                            changes: [
+                                (
+                                    "b.qs",
+                                    [
+                                        TextEdit {
+                                            new_text: "",
+                                            range: Range {
+                                                start: Position {
+                                                    line: 2,
+                                                    column: 0,
+                                                },
+                                                end: Position {
+                                                    line: 3,
+                                                    column: 0,
+                                                },
+                                            },
+                                        },
+                                    ],
+                                ),
+                            ],
+                        },
+                    ),
+                    kind: Some(
+                        QuickFix,
+                    ),
+                    is_preferred: None,
+                },
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn rename_unused_parameter() {
+    check(
+        &[(
+            "a.qs",
+            "namespace A {\n    function F(x : Int) : Int { 0 }\n    export F;\n}",
+        )],
+        &[HirLint::UnusedParameter],
+        "a.qs",
+        &expect![[r#"
+            [
+                CodeAction {
+                    title: "unused parameter",
+                    edit: Some(
+                        WorkspaceEdit {
+                            changes: [
+                                (
+                                    "a.qs",
+                                    [
+                                        TextEdit {
+                                            new_text: "_x",
+                                            range: Range {
+                                                start: Position {
+                                                    line: 1,
+                                                    column: 15,
+                                                },
+                                                end: Position {
+                                                    line: 1,
+                                                    column: 16,
+                                                },
+                                            },
+                                        },
+                                    ],
+                                ),
+                            ],
+                        },
+                    ),
+                    kind: Some(
+                        QuickFix,
+                    ),
+                    is_preferred: None,
+                },
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn remove_unused_parameter_from_calls_in_other_sources() {
+    check(
+        &[
+            (
+                "a.qs",
+                "namespace A {\n    internal function F(x : Int, y : Int) : Int { y }\n}",
+            ),
+            (
+                "b.qs",
+                "namespace B {\n    function G() : Int { A.F(1, 2) }\n    export G;\n}",
+            ),
+        ],
+        &[HirLint::UnusedParameter],
+        "a.qs",
+        &expect![[r#"
+            [
+                CodeAction {
+                    title: "unused parameter",
+                    edit: Some(
+                        WorkspaceEdit {
+                            changes: [
+                                (
+                                    "a.qs",
+                                    [
+                                        TextEdit {
+                                            new_text: "",
+                                            range: Range {
+                                                start: Position {
+                                                    line: 1,
+                                                    column: 24,
+                                                },
+                                                end: Position {
+                                                    line: 1,
+                                                    column: 33,
+                                                },
+                                            },
+                                        },
+                                    ],
+                                ),
+                                (
+                                    "b.qs",
+                                    [
+                                        TextEdit {
+                                            new_text: "",
+                                            range: Range {
+                                                start: Position {
+                                                    line: 1,
+                                                    column: 29,
+                                                },
+                                                end: Position {
+                                                    line: 1,
+                                                    column: 32,
+                                                },
+                                            },
+                                        },
+                                    ],
+                                ),
+                            ],
+                        },
+                    ),
+                    kind: Some(
+                        QuickFix,
+                    ),
+                    is_preferred: None,
+                },
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn remove_unused_callable() {
+    check(
+        &[(
+            "a.qs",
+            "namespace A {\n    function F() : Unit {}\n    function G() : Unit {}\n    export F;\n}",
+        )],
+        &[HirLint::UnusedCallable],
+        "a.qs",
+        &expect![[r#"
+            [
+                CodeAction {
+                    title: "unused internal callable",
+                    edit: Some(
+                        WorkspaceEdit {
+                            changes: [
+                                (
+                                    "a.qs",
+                                    [
+                                        TextEdit {
+                                            new_text: "",
+                                            range: Range {
+                                                start: Position {
+                                                    line: 2,
+                                                    column: 0,
+                                                },
+                                                end: Position {
+                                                    line: 3,
+                                                    column: 0,
+                                                },
+                                            },
+                                        },
+                                    ],
+                                ),
+                            ],
+                        },
+                    ),
+                    kind: Some(
+                        QuickFix,
+                    ),
+                    is_preferred: None,
+                },
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn remove_unused_open_and_import() {
+    check(
+        &[(
+            "a.qs",
+            "namespace A {\n    open Microsoft.Quantum.Math;\n    import Std.Arrays.Head;\n    function F() : Unit {}\n}",
+        )],
+        &[HirLint::UnusedOpen, HirLint::UnusedImport],
+        "a.qs",
+        &expect![[r#"
+            [
+                CodeAction {
+                    title: "unused open",
+                    edit: Some(
+                        WorkspaceEdit {
+                            changes: [
+                                (
+                                    "a.qs",
+                                    [
+                                        TextEdit {
+                                            new_text: "",
+                                            range: Range {
+                                                start: Position {
+                                                    line: 1,
+                                                    column: 0,
+                                                },
+                                                end: Position {
+                                                    line: 2,
+                                                    column: 0,
+                                                },
+                                            },
+                                        },
+                                    ],
+                                ),
+                            ],
+                        },
+                    ),
+                    kind: Some(
+                        QuickFix,
+                    ),
+                    is_preferred: None,
+                },
+                CodeAction {
+                    title: "unused import",
+                    edit: Some(
+                        WorkspaceEdit {
+                            changes: [
+                                (
+                                    "a.qs",
+                                    [
+                                        TextEdit {
+                                            new_text: "",
+                                            range: Range {
+                                                start: Position {
+                                                    line: 2,
+                                                    column: 0,
+                                                },
+                                                end: Position {
+                                                    line: 3,
+                                                    column: 0,
+                                                },
+                                            },
+                                        },
+                                    ],
+                                ),
+                            ],
+                        },
+                    ),
+                    kind: Some(
+                        QuickFix,
+                    ),
+                    is_preferred: None,
+                },
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn import_unresolved_callable() {
+    check(
+        &[
+            ("a.qs", "namespace A { function F() : Unit {} }"),
+            (
+                "b.qs",
+                "namespace B {\n    function G() : Unit {\n        F();\n    }\n}",
+            ),
+        ],
+        &[],
+        "b.qs",
+        &expect![[r#"
+            [
+                CodeAction {
+                    title: "Import `A.F`",
+                    edit: Some(
+                        WorkspaceEdit {
+                            changes: [
+                                (
+                                    "b.qs",
+                                    [
+                                        TextEdit {
+                                            new_text: "import A.F;\n    ",
+                                            range: Range {
+                                                start: Position {
+                                                    line: 1,
+                                                    column: 4,
+                                                },
+                                                end: Position {
+                                                    line: 1,
+                                                    column: 4,
+                                                },
+                                            },
+                                        },
+                                    ],
+                                ),
+                            ],
+                        },
+                    ),
+                    kind: Some(
+                        QuickFix,
+                    ),
+                    is_preferred: None,
+                },
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn import_unresolved_type() {
+    check(
+        &[
+            ("a.qs", "namespace A { struct Pair { X : Int, Y : Int } }"),
+            (
+                "b.qs",
+                "namespace B {\n    function G(pair : Pair) : Unit {}\n}",
+            ),
+        ],
+        &[],
+        "b.qs",
+        &expect![[r#"
+            [
+                CodeAction {
+                    title: "Import `A.Pair`",
+                    edit: Some(
+                        WorkspaceEdit {
+                            changes: [
+                                (
+                                    "b.qs",
+                                    [
+                                        TextEdit {
+                                            new_text: "import A.Pair;\n    ",
+                                            range: Range {
+                                                start: Position {
+                                                    line: 1,
+                                                    column: 4,
+                                                },
+                                                end: Position {
+                                                    line: 1,
+                                                    column: 4,
+                                                },
+                                            },
+                                        },
+                                    ],
+                                ),
+                            ],
+                        },
+                    ),
+                    kind: Some(
+                        QuickFix,
+                    ),
+                    is_preferred: None,
+                },
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn import_unresolved_std_callable() {
+    check(
+        &[(
+            "a.qs",
+            "namespace A {\n    function F() : Double {\n        PI()\n    }\n}",
+        )],
+        &[],
+        "a.qs",
+        &expect![[r#"
+            [
+                CodeAction {
+                    title: "Import `Microsoft.Quantum.Math.PI`",
+                    edit: Some(
+                        WorkspaceEdit {
+                            changes: [
+                                (
+                                    "a.qs",
+                                    [
+                                        TextEdit {
+                                            new_text: "import Microsoft.Quantum.Math.PI;\n    ",
+                                            range: Range {
+                                                start: Position {
+                                                    line: 1,
+                                                    column: 4,
+                                                },
+                                                end: Position {
+                                                    line: 1,
+                                                    column: 4,
+                                                },
+                                            },
+                                        },
+                                    ],
+                                ),
+                            ],
+                        },
+                    ),
+                    kind: Some(
+                        QuickFix,
+                    ),
+                    is_preferred: None,
+                },
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn no_import_for_unresolved_qualified_name() {
+    check(
+        &[(
+            "a.qs",
+            "namespace A {\n    function F() : Unit {\n        Missing.F();\n    }\n}",
+        )],
+        &[],
+        "a.qs",
+        &expect![[r"
+            []
+        "]],
+    );
+}
+
+#[test]
+fn no_import_for_unknown_name() {
+    check(
+        &[(
+            "a.qs",
+            "namespace A {\n    function F() : Unit {\n        Missing();\n    }\n}",
+        )],
+        &[],
+        "a.qs",
+        &expect![[r"
+            []
+        "]],
+    );
+}
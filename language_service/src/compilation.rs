@@ -10,9 +10,8 @@ use qsc::{
     incremental::Compiler,
     line_column::{Encoding, Position, Range},
     packages::{prepare_package_store, BuildableProgram},
-    project, resolve,
-    target::Profile,
-    CompileUnit, LanguageFeatures, PackageStore, PackageType, PassContext, SourceMap, Span,
+    project, resolve, CompileUnit, LanguageFeatures, PackageStore, PackageType, PassContext,
+    SourceMap, Span, TargetCapabilityFlags,
 };
 use qsc_linter::{LintConfig, LintLevel};
 use qsc_project::{PackageGraphSources, Project};
@@ -57,14 +56,14 @@ impl Compilation {
     /// Creates a new `Compilation` by compiling sources.
     pub(crate) fn new(
         package_type: PackageType,
-        target_profile: Profile,
+        capabilities: TargetCapabilityFlags,
         language_features: LanguageFeatures,
         lints_config: &[LintConfig],
         package_graph_sources: PackageGraphSources,
         project_errors: Vec<project::Error>,
     ) -> Self {
         let mut buildable_program =
-            prepare_package_store(target_profile.into(), package_graph_sources.clone());
+            prepare_package_store(capabilities, package_graph_sources.clone());
 
         let mut compile_errors = take(&mut buildable_program.dependency_errors);
 
@@ -81,7 +80,7 @@ impl Compilation {
             &user_code_dependencies,
             user_code,
             package_type,
-            target_profile.into(),
+            capabilities,
             language_features,
         );
 
@@ -94,7 +93,7 @@ impl Compilation {
 
         run_fir_passes(
             &mut compile_errors,
-            target_profile,
+            capabilities,
             &package_store,
             package_id,
             unit,
@@ -117,7 +116,7 @@ impl Compilation {
     /// Creates a new `Compilation` by compiling sources from notebook cells.
     pub(crate) fn new_notebook<I>(
         cells: I,
-        target_profile: Profile,
+        capabilities: TargetCapabilityFlags,
         language_features: LanguageFeatures,
         lints_config: &[LintConfig],
         project: Option<Project>,
@@ -131,7 +130,7 @@ impl Compilation {
             Some(p) if p.errors.is_empty() => {
                 trace!("using buildable program from project");
                 let buildable_program =
-                    prepare_package_store(target_profile.into(), p.package_graph_sources.clone());
+                    prepare_package_store(capabilities, p.package_graph_sources.clone());
 
                 (
                     SourceMap::new(buildable_program.user_code.sources, None),
@@ -144,8 +143,7 @@ impl Compilation {
                 // If no project is specified, or if the project has errors, compile stdlib only.
                 // Any project errors will be handled below.
                 trace!("compiling stdlib only");
-                let (std_id, store) =
-                    qsc::compile::package_store_with_stdlib(target_profile.into());
+                let (std_id, store) = qsc::compile::package_store_with_stdlib(capabilities);
                 (
                     SourceMap::default(),
                     vec![(std_id, None)],
@@ -159,7 +157,7 @@ impl Compilation {
         let mut compiler = match Compiler::new(
             sources,
             PackageType::Lib,
-            target_profile.into(),
+            capabilities,
             language_features,
             store,
             &dependencies,
@@ -170,13 +168,12 @@ impl Compilation {
                 // Because there were errors in the user code project, we need to create a new compiler with no sources
                 // to do a best effort compilation of the cells.
                 trace!("falling back stdlib only only after user code project errors");
-                let (std_id, store) =
-                    qsc::compile::package_store_with_stdlib(target_profile.into());
+                let (std_id, store) = qsc::compile::package_store_with_stdlib(capabilities);
 
                 Compiler::new(
                     SourceMap::default(),
                     PackageType::Lib,
-                    target_profile.into(),
+                    capabilities,
                     language_features,
                     store,
                     &[(std_id, None)],
@@ -202,13 +199,7 @@ impl Compilation {
             .get(package_id)
             .expect("expected to find user package");
 
-        run_fir_passes(
-            &mut errors,
-            target_profile,
-            &package_store,
-            package_id,
-            unit,
-        );
+        run_fir_passes(&mut errors, capabilities, &package_store, package_id, unit);
 
         run_linter_passes(&mut errors, &package_store, unit, lints_config);
 
@@ -302,7 +293,7 @@ impl Compilation {
     pub fn recompile(
         &mut self,
         package_type: PackageType,
-        target_profile: Profile,
+        capabilities: TargetCapabilityFlags,
         language_features: LanguageFeatures,
         lints_config: &[LintConfig],
     ) {
@@ -318,7 +309,7 @@ impl Compilation {
                 ref package_graph_sources,
             } => Self::new(
                 package_type,
-                target_profile,
+                capabilities,
                 language_features,
                 lints_config,
                 package_graph_sources.clone(),
@@ -326,7 +317,7 @@ impl Compilation {
             ),
             CompilationKind::Notebook { ref project } => Self::new_notebook(
                 sources.into_iter(),
-                target_profile,
+                capabilities,
                 language_features,
                 lints_config,
                 project.clone(),
@@ -346,7 +337,7 @@ impl Compilation {
 /// or `Unrestricted`.
 fn run_fir_passes(
     errors: &mut Vec<WithSource<compile::ErrorKind>>,
-    target_profile: Profile,
+    capabilities: TargetCapabilityFlags,
     package_store: &PackageStore,
     package_id: PackageId,
     unit: &CompileUnit,
@@ -356,14 +347,13 @@ fn run_fir_passes(
         return;
     }
 
-    if target_profile == Profile::Unrestricted {
+    if capabilities == TargetCapabilityFlags::all() {
         // no point in running passes on unrestricted profile
         return;
     }
 
     let (fir_store, fir_package_id) = qsc::lower_hir_to_fir(package_store, package_id);
-    let caps_results =
-        PassContext::run_fir_passes_on_fir(&fir_store, fir_package_id, target_profile.into());
+    let caps_results = PassContext::run_fir_passes_on_fir(&fir_store, fir_package_id, capabilities);
     if let Err(caps_errors) = caps_results {
         for err in caps_errors {
             let err = WithSource::from_map(&unit.sources, compile::ErrorKind::Pass(err));
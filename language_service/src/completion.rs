@@ -97,28 +97,12 @@ pub(crate) fn get_completions(
     };
     context_finder.visit_package(user_ast_package);
 
-    let insert_open_at = match compilation.kind {
-        CompilationKind::OpenProject { .. } => context_finder.start_of_namespace,
-        // Since notebooks don't typically contain namespace declarations,
-        // open statements should just get before the first non-whitespace
-        // character (i.e. at the top of the cell)
-        CompilationKind::Notebook { .. } => {
-            Some(get_first_non_whitespace_in_source(compilation, offset))
-        }
-    };
-
-    let insert_open_range = insert_open_at.map(|o| {
-        into_range(
-            position_encoding,
-            qsc::Span { lo: o, hi: o },
-            &compilation.user_unit().sources,
-        )
-    });
-
-    let indent = match insert_open_at {
-        Some(start) => get_indent(compilation, start),
-        None => String::new(),
-    };
+    let (insert_open_range, indent) = import_insertion_point(
+        compilation,
+        context_finder.start_of_namespace,
+        offset,
+        position_encoding,
+    );
 
     let mut prelude_ns_ids: Vec<ImportItem> = PRELUDE
         .into_iter()
@@ -210,6 +194,69 @@ pub(crate) fn get_completions(
     }
 }
 
+/// Returns the edit that adds `import {path};` to the namespace, or notebook cell,
+/// that contains `offset`.
+pub(crate) fn import_edit(
+    compilation: &Compilation,
+    offset: u32,
+    path: &str,
+    position_encoding: Encoding,
+) -> Option<TextEdit> {
+    let mut context_finder = ContextFinder {
+        offset,
+        context: Context::TopLevel,
+        start_of_namespace: None,
+        current_namespace_name: None,
+        imports: vec![],
+    };
+    context_finder.visit_package(&compilation.user_unit().ast.package);
+
+    let (range, indent) = import_insertion_point(
+        compilation,
+        context_finder.start_of_namespace,
+        offset,
+        position_encoding,
+    );
+    range.map(|range| TextEdit {
+        new_text: format!("import {path};{indent}"),
+        range,
+    })
+}
+
+/// Returns the range at which to insert an import statement, if there is one,
+/// and the indentation to follow it with.
+fn import_insertion_point(
+    compilation: &Compilation,
+    start_of_namespace: Option<u32>,
+    offset: u32,
+    position_encoding: Encoding,
+) -> (Option<Range>, String) {
+    let insert_open_at = match compilation.kind {
+        CompilationKind::OpenProject { .. } => start_of_namespace,
+        // Since notebooks don't typically contain namespace declarations,
+        // open statements should just get before the first non-whitespace
+        // character (i.e. at the top of the cell)
+        CompilationKind::Notebook { .. } => {
+            Some(get_first_non_whitespace_in_source(compilation, offset))
+        }
+    };
+
+    let insert_open_range = insert_open_at.map(|o| {
+        into_range(
+            position_encoding,
+            qsc::Span { lo: o, hi: o },
+            &compilation.user_unit().sources,
+        )
+    });
+
+    let indent = match insert_open_at {
+        Some(start) => get_indent(compilation, start),
+        None => String::new(),
+    };
+
+    (insert_open_range, indent)
+}
+
 fn get_first_non_whitespace_in_source(compilation: &Compilation, package_offset: u32) -> u32 {
     const QSHARP_MAGIC: &str = "//qsharp";
     let source = compilation
@@ -338,7 +385,7 @@ impl CompletionListBuilder {
         indent: &String,
     ) {
         for (package_id, _) in compilation.package_store.iter().rev() {
-            self.push_sorted_completions(Self::get_callables(
+            self.push_sorted_completions(Self::get_global_items(
                 compilation,
                 package_id,
                 imports,
@@ -437,8 +484,8 @@ impl CompletionListBuilder {
     }
 
     #[allow(clippy::too_many_lines)]
-    /// Get all callables in a package and return them as completion items, with a sort priority.
-    fn get_callables<'a>(
+    /// Get all callables and types in a package and return them as completion items, with a sort priority.
+    fn get_global_items<'a>(
         compilation: &'a Compilation,
         package_id: PackageId,
         // name and alias
@@ -469,7 +516,8 @@ impl CompletionListBuilder {
         package.items.values().filter_map(move |i| {
             package_item_to_completion_item(
                 i,
-                package,
+                compilation,
+                package_id,
                 is_user_package,
                 current_namespace_name,
                 &display,
@@ -502,7 +550,7 @@ impl CompletionListBuilder {
 
 /// Format an external fully qualified name
 /// This will prepend the package alias and remove `Main` if it is the first namespace
-fn format_external_name(
+pub(crate) fn format_external_name(
     package_alias_from_manifest: &Option<Arc<str>>,
     qualification: &[Rc<str>],
     name: Option<&str>,
@@ -670,7 +718,8 @@ impl Visitor<'_> for ContextFinder {
 #[allow(clippy::too_many_arguments)]
 fn package_item_to_completion_item(
     item: &qsc::hir::Item,
-    package: &qsc::hir::Package,
+    compilation: &Compilation,
+    package_id: PackageId,
     is_user_package: bool,
     current_namespace_name: Option<&[Rc<str>]>,
     display: &CodeDisplay,
@@ -679,55 +728,105 @@ fn package_item_to_completion_item(
     insert_open_at: Option<Range>,
     indent: &String,
 ) -> Option<(CompletionItem, SortPriority)> {
+    let package = &compilation
+        .package_store
+        .get(package_id)
+        .expect("package id should exist")
+        .package;
+
     // We only want items whose parents are namespaces
-    if let Some(item_id) = item.parent {
-        if let Some(parent) = package.items.get(item_id) {
-            if let ItemKind::Namespace(callable_namespace, _) = &parent.kind {
-                // filter out internal packages that are not from the user's
-                // compilation
-                if matches!(item.visibility, Visibility::Internal) && !is_user_package {
-                    return None; // ignore item if not in the user's package
-                }
+    let parent = package.items.get(item.parent?)?;
+    let ItemKind::Namespace(item_namespace, _) = &parent.kind else {
+        return None;
+    };
+    // filter out internal packages that are not from the user's
+    // compilation
+    if matches!(item.visibility, Visibility::Internal) && !is_user_package {
+        return None; // ignore item if not in the user's package
+    }
 
-                match &item.kind {
-                    ItemKind::Callable(callable_decl) => {
-                        return Some(callable_decl_to_completion_item(
-                            callable_decl,
-                            current_namespace_name,
-                            display,
-                            package_alias_from_manifest,
-                            callable_namespace,
-                            imports,
-                            insert_open_at,
-                            indent,
-                        ))
-                    }
-                    _ => return None,
-                }
+    let (name, kind, detail) = match &item.kind {
+        ItemKind::Callable(callable_decl) => (
+            callable_decl.name.name.as_ref(),
+            CompletionItemKind::Function,
+            display.hir_callable_decl(callable_decl).to_string(),
+        ),
+        ItemKind::Ty(name, udt) => (
+            name.name.as_ref(),
+            CompletionItemKind::Interface,
+            display.hir_udt(udt).to_string(),
+        ),
+        // An exported item can be imported from the exporting namespace,
+        // under the exported name.
+        ItemKind::Export(name, export) => {
+            let export_package_id = export.package.unwrap_or(package_id);
+            let exported = compilation
+                .package_store
+                .get(export_package_id)?
+                .package
+                .items
+                .get(export.item)?;
+            // Items exported from the namespace that declares them are already listed.
+            if export_package_id == package_id
+                && exported.parent == item.parent
+                && item_name(exported) == Some(&name.name)
+            {
+                return None;
+            }
+            match &exported.kind {
+                ItemKind::Callable(callable_decl) => (
+                    name.name.as_ref(),
+                    CompletionItemKind::Function,
+                    display.hir_callable_decl(callable_decl).to_string(),
+                ),
+                ItemKind::Ty(_, udt) => (
+                    name.name.as_ref(),
+                    CompletionItemKind::Interface,
+                    display.hir_udt(udt).to_string(),
+                ),
+                _ => return None,
             }
         }
+        ItemKind::Namespace(..) => return None,
+    };
+
+    Some(global_item_to_completion_item(
+        name,
+        kind,
+        detail,
+        current_namespace_name,
+        package_alias_from_manifest,
+        item_namespace,
+        imports,
+        insert_open_at,
+        indent,
+    ))
+}
+
+fn item_name(item: &qsc::hir::Item) -> Option<&Rc<str>> {
+    match &item.kind {
+        ItemKind::Callable(decl) => Some(&decl.name.name),
+        ItemKind::Ty(name, _) | ItemKind::Export(name, _) => Some(&name.name),
+        ItemKind::Namespace(..) => None,
     }
-    None
 }
 
 #[allow(clippy::too_many_arguments)]
-fn callable_decl_to_completion_item(
-    callable_decl: &qsc::hir::CallableDecl,
+fn global_item_to_completion_item(
+    name: &str,
+    kind: CompletionItemKind,
+    detail: String,
     current_namespace_name: Option<&[Rc<str>]>,
-    display: &CodeDisplay,
     package_alias_from_manifest: &Option<Arc<str>>,
-    callable_namespace: &qsc::hir::Idents,
+    item_namespace: &qsc::hir::Idents,
     imports: &[ImportItem],
     insert_import_at: Option<Range>,
     indent: &String,
 ) -> (CompletionItem, SortPriority) {
-    let name = callable_decl.name.name.as_ref();
-    // details used when rendering the completion item
-    let detail = Some(display.hir_callable_decl(callable_decl).to_string());
     // Everything that starts with a __ goes last in the list
     let sort_group = u32::from(name.starts_with("__"));
 
-    let namespace_as_strs = Into::<Vec<_>>::into(callable_namespace);
+    let namespace_as_strs = Into::<Vec<_>>::into(item_namespace);
 
     // Now, we calculate the qualification that goes before the import
     // item.
@@ -775,7 +874,7 @@ fn callable_decl_to_completion_item(
             if let Some(range) = insert_import_at {
                 let import_text = format_external_name(
                     package_alias_from_manifest,
-                    &Into::<Vec<_>>::into(callable_namespace),
+                    &Into::<Vec<_>>::into(item_namespace),
                     Some(name),
                 );
                 Some(TextEdit {
@@ -796,9 +895,9 @@ fn callable_decl_to_completion_item(
     (
         CompletionItem {
             label,
-            kind: CompletionItemKind::Function,
+            kind,
             sort_text: None, // This will get filled in during `push_sorted_completions`
-            detail,
+            detail: Some(detail),
             additional_text_edits: additional_text_edit.map(|x| vec![x]),
         },
         sort_group,
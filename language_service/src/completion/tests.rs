@@ -1611,3 +1611,143 @@ fn callable_from_same_file() {
         "#]],
     );
 }
+
+#[test]
+fn stdlib_struct_is_auto_imported() {
+    check(
+        indoc! {r#"
+        namespace Test {
+            operation Foo() : Unit {
+                ↘
+            }
+        "#},
+        &["FakeStruct"],
+        &expect![[r#"
+            [
+                Some(
+                    CompletionItem {
+                        label: "FakeStruct",
+                        kind: Interface,
+                        sort_text: Some(
+                            "0700FakeStruct",
+                        ),
+                        detail: Some(
+                            "struct FakeStruct { x : Int, y : Int }",
+                        ),
+                        additional_text_edits: Some(
+                            [
+                                TextEdit {
+                                    new_text: "import FakeStdLib.FakeStruct;\n    ",
+                                    range: Range {
+                                        start: Position {
+                                            line: 1,
+                                            column: 4,
+                                        },
+                                        end: Position {
+                                            line: 1,
+                                            column: 4,
+                                        },
+                                    },
+                                },
+                            ],
+                        ),
+                    },
+                ),
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn reexported_items_are_auto_imported_from_exporting_namespace() {
+    check_project(
+        &[
+            (
+                "lib.qs",
+                indoc! {r#"
+                namespace Lib {
+                    operation Prepare() : Unit {}
+                    struct Pair { First : Int, Second : Int }
+                    export Prepare, Pair;
+                }
+                namespace Api {
+                    export Lib.Prepare as PrepareState, Lib.Pair;
+                }
+                "#},
+            ),
+            (
+                "main.qs",
+                indoc! {r#"
+                namespace Main {
+                    operation Run() : Unit {
+                        ↘
+                    }
+                }
+                "#},
+            ),
+        ],
+        &["PrepareState", "Pair"],
+        &expect![[r#"
+            [
+                Some(
+                    CompletionItem {
+                        label: "PrepareState",
+                        kind: Function,
+                        sort_text: Some(
+                            "0600PrepareState",
+                        ),
+                        detail: Some(
+                            "operation Prepare() : Unit",
+                        ),
+                        additional_text_edits: Some(
+                            [
+                                TextEdit {
+                                    new_text: "import Api.PrepareState;\n    ",
+                                    range: Range {
+                                        start: Position {
+                                            line: 1,
+                                            column: 4,
+                                        },
+                                        end: Position {
+                                            line: 1,
+                                            column: 4,
+                                        },
+                                    },
+                                },
+                            ],
+                        ),
+                    },
+                ),
+                Some(
+                    CompletionItem {
+                        label: "Pair",
+                        kind: Interface,
+                        sort_text: Some(
+                            "0600Pair",
+                        ),
+                        detail: Some(
+                            "struct Pair { First : Int, Second : Int }",
+                        ),
+                        additional_text_edits: Some(
+                            [
+                                TextEdit {
+                                    new_text: "import Lib.Pair;\n    ",
+                                    range: Range {
+                                        start: Position {
+                                            line: 1,
+                                            column: 4,
+                                        },
+                                        end: Position {
+                                            line: 1,
+                                            column: 4,
+                                        },
+                                    },
+                                },
+                            ],
+                        ),
+                    },
+                ),
+            ]
+        "#]],
+    );
+}
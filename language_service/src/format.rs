@@ -3,12 +3,13 @@
 
 use crate::{compilation::Compilation, protocol::TextEdit};
 
-use qsc::formatter::calculate_format_edits;
+use qsc::formatter::{calculate_format_edits_with_config, FormatterConfig};
 use qsc::line_column::{Encoding, Range};
 
 pub(crate) fn get_format_changes(
     compilation: &Compilation,
     source_name: &str,
+    config: &FormatterConfig,
     encoding: Encoding,
 ) -> Vec<TextEdit> {
     let contents = &compilation
@@ -18,7 +19,7 @@ pub(crate) fn get_format_changes(
         .expect("can't find source by name")
         .contents;
 
-    calculate_format_edits(contents)
+    calculate_format_edits_with_config(contents, config)
         .into_iter()
         .map(|edit| TextEdit {
             new_text: edit.new_text,
@@ -8,11 +8,12 @@ use crate::compilation::Compilation;
 use crate::name_locator::{Handler, Locator, LocatorContext};
 use crate::protocol::Hover;
 use crate::qsc_utils::into_range;
+use miette::Diagnostic;
 use qsc::ast::visit::Visitor;
 use qsc::display::{parse_doc_for_param, parse_doc_for_summary, CodeDisplay, Lookup};
 use qsc::line_column::{Encoding, Position, Range};
-use qsc::{ast, hir, Span};
-use std::fmt::Display;
+use qsc::{ast, compile, hir, passes, Span};
+use std::fmt::{Display, Write};
 use std::rc::Rc;
 
 pub(crate) fn get_hover(
@@ -34,7 +35,71 @@ pub(crate) fn get_hover(
 
     let mut locator = Locator::new(&mut hover_visitor, offset, compilation);
     locator.visit_package(user_ast_package);
-    hover_visitor.hover
+    with_provenance(hover_visitor.hover, compilation, offset, position_encoding)
+}
+
+/// Adds the provenance of the capability errors at the offset to the hover, which explains why the
+/// value there depends on a measurement result. When there is no other hover at the offset, the
+/// hover covers the span of the errors.
+fn with_provenance(
+    hover: Option<Hover>,
+    compilation: &Compilation,
+    offset: u32,
+    position_encoding: Encoding,
+) -> Option<Hover> {
+    let explained_errors = compilation
+        .compile_errors
+        .iter()
+        .filter_map(|error| match error.error() {
+            compile::ErrorKind::Pass(passes::Error::CapabilitiesCk(explained))
+                if !explained.provenance.is_empty() =>
+            {
+                let label = explained.error.labels()?.next()?;
+                let span = Span {
+                    lo: u32::try_from(label.offset()).ok()?,
+                    hi: u32::try_from(label.offset() + label.len()).ok()?,
+                };
+                (span.lo <= offset && offset <= span.hi).then_some((span, explained))
+            }
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    // Errors can be nested, so only the innermost ones are about the value at the offset.
+    let Some(span) = explained_errors
+        .iter()
+        .map(|(span, _)| *span)
+        .min_by_key(|span| span.hi - span.lo)
+    else {
+        return hover;
+    };
+    let explained_errors = explained_errors
+        .into_iter()
+        .filter_map(|(error_span, explained)| (error_span == span).then_some(explained))
+        .collect::<Vec<_>>();
+
+    let sources = &compilation.user_unit().sources;
+    let mut messages = String::new();
+    for explained in &explained_errors {
+        let _ = write!(messages, "{}\n\n", explained.error);
+    }
+    let mut reasons = String::new();
+    for reason in &explained_errors[0].provenance {
+        let range = into_range(position_encoding, reason.span, sources);
+        let _ = writeln!(reasons, "- {reason} (line {})", range.start.line + 1);
+    }
+    let contents = format!("{messages}This value depends on a measurement result:\n{reasons}");
+
+    Some(match hover {
+        Some(hover) => Hover {
+            contents: format!("{}---\n{contents}", hover.contents),
+            span: hover.span,
+        },
+        None => Hover {
+            contents,
+            span: into_range(position_encoding, span, sources),
+        },
+    })
 }
 
 enum LocalKind {
@@ -4,10 +4,12 @@
 #![allow(clippy::needless_raw_string_hashes)]
 
 use super::get_hover;
-use crate::test_utils::{compile_notebook_with_markers, compile_with_markers};
+use crate::test_utils::{
+    compile_notebook_with_markers, compile_with_markers, compile_with_profile_and_markers,
+};
 use expect_test::{expect, Expect};
 use indoc::indoc;
-use qsc::line_column::Encoding;
+use qsc::{line_column::Encoding, target::Profile};
 
 /// Asserts that the hover text at the given cursor position matches the expected hover text.
 /// The cursor position is indicated by a `↘` marker in the source text.
@@ -21,6 +23,16 @@ fn check(source_with_markers: &str, expect: &Expect) {
     expect.assert_eq(&actual.contents);
 }
 
+/// Asserts the hover text like `check`, but compiles for the given target profile.
+fn check_with_profile(source_with_markers: &str, target_profile: Profile, expect: &Expect) {
+    let (compilation, cursor_position, target_spans) =
+        compile_with_profile_and_markers(source_with_markers, target_profile);
+    let actual = get_hover(&compilation, "<source>", cursor_position, Encoding::Utf8)
+        .expect("Expected a hover.");
+    assert_eq!(&actual.span, &target_spans[0]);
+    expect.assert_eq(&actual.contents);
+}
+
 /// Asserts that there is no hover for the given test case.
 fn check_none(source_with_markers: &str) {
     let (compilation, cursor_position, _) = compile_with_markers(source_with_markers, true);
@@ -1618,3 +1630,64 @@ fn notebook_local_reference() {
         "#]],
     );
 }
+
+#[test]
+fn capability_error_provenance_on_local() {
+    check_with_profile(
+        indoc! {r#"
+        namespace Test {
+            @EntryPoint()
+            operation Main() : Unit {
+                use q = Qubit();
+                let r = M(q);
+                let angle = r == One ? 1.0 | 0.0;
+                Rx(◉an↘gle◉, q);
+            }
+        }
+    "#},
+        Profile::AdaptiveRI,
+        &expect![[r#"
+            local
+            ```qsharp
+            angle : Double
+            ```
+            ---
+            cannot use a dynamic double value
+
+            This value depends on a measurement result:
+            - a dynamic value is passed to `Rx` here (line 7)
+            - `angle` is bound to a dynamic value here (line 6)
+            - this condition depends on a measurement result (line 6)
+            - `r` is bound to a dynamic value here (line 5)
+            - `M` returns a measurement result here (line 5)
+        "#]],
+    );
+}
+
+#[test]
+fn capability_error_provenance_without_other_hover() {
+    check_with_profile(
+        indoc! {r#"
+        namespace Test {
+            @EntryPoint()
+            operation Main() : Unit {
+                use q = Qubit();
+                mutable d = 0.0;
+                if M(q) == One {
+                    set d = 1.0;
+                }
+                let a = ◉d ↘* 2.0◉;
+            }
+        }
+    "#},
+        Profile::AdaptiveRI,
+        &expect![[r#"
+            cannot use a dynamic double value
+
+            This value depends on a measurement result:
+            - `d` is assigned a dynamic value here (line 7)
+            - this condition depends on a measurement result (line 6)
+            - `M` returns a measurement result here (line 6)
+        "#]],
+    );
+}
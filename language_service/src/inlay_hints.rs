@@ -0,0 +1,239 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+#[cfg(test)]
+mod tests;
+
+use crate::{
+    compilation::Compilation,
+    protocol::{InlayHint, InlayHintKind, InlayHintsConfig},
+};
+use qsc::{
+    ast::{
+        self,
+        visit::{walk_expr, walk_stmt, Visitor},
+    },
+    display::Lookup,
+    hir::{self, ty::Ty},
+    line_column::{Encoding, Position, Range},
+    resolve::Res,
+    Span,
+};
+
+pub(crate) fn get_inlay_hints(
+    compilation: &Compilation,
+    source_name: &str,
+    range: Range,
+    config: InlayHintsConfig,
+    position_encoding: Encoding,
+) -> Vec<InlayHint> {
+    let unit = compilation.user_unit();
+    let span = compilation.source_range_to_package_span(source_name, range, position_encoding);
+
+    let mut collector = HintCollector {
+        compilation,
+        config,
+        span,
+        hints: Vec::new(),
+    };
+    for node in &unit.ast.package.nodes {
+        match node {
+            ast::TopLevelNode::Namespace(namespace) if overlaps(namespace.span, span) => {
+                collector.visit_namespace(namespace);
+            }
+            ast::TopLevelNode::Stmt(stmt) if overlaps(stmt.span, span) => {
+                collector.visit_stmt(stmt);
+            }
+            _ => {}
+        }
+    }
+
+    let mut hints = collector.hints;
+    hints.sort_by_key(|(offset, ..)| *offset);
+    hints.dedup();
+    hints
+        .into_iter()
+        .map(|(offset, label, kind)| {
+            let source = unit
+                .sources
+                .find_by_offset(offset)
+                .expect("source should exist for offset");
+            InlayHint {
+                position: Position::from_utf8_byte_offset(
+                    position_encoding,
+                    &source.contents,
+                    offset - source.offset,
+                ),
+                label,
+                kind,
+            }
+        })
+        .collect()
+}
+
+fn overlaps(a: Span, b: Span) -> bool {
+    a.lo <= b.hi && b.lo <= a.hi
+}
+
+struct HintCollector<'a> {
+    compilation: &'a Compilation,
+    config: InlayHintsConfig,
+    /// The requested range.
+    span: Span,
+    hints: Vec<(u32, String, InlayHintKind)>,
+}
+
+impl HintCollector<'_> {
+    fn push(&mut self, offset: u32, label: String, kind: InlayHintKind) {
+        if self.span.lo <= offset && offset <= self.span.hi {
+            self.hints.push((offset, label, kind));
+        }
+    }
+
+    /// Adds the inferred types of the bindings in a pattern that don't have a type annotation.
+    fn push_binding_types(&mut self, pat: &ast::Pat) {
+        match &*pat.kind {
+            ast::PatKind::Bind(name, None) => {
+                if let Some(ty) = self.compilation.get_ty(pat.id) {
+                    if is_known(ty) {
+                        self.push(
+                            name.span.hi,
+                            format!(": {}", ty.display()),
+                            InlayHintKind::Type,
+                        );
+                    }
+                }
+            }
+            ast::PatKind::Paren(pat) => self.push_binding_types(pat),
+            ast::PatKind::Tuple(pats) => pats.iter().for_each(|pat| self.push_binding_types(pat)),
+            _ => {}
+        }
+    }
+
+    /// Adds the names of the callee's parameters before the arguments of a call.
+    fn push_parameter_names(&mut self, callee: &ast::Expr, arg: &ast::Expr) {
+        let Some(params) = self.callee_params(callee) else {
+            return;
+        };
+        let args: Vec<&ast::Expr> = match &*arg.kind {
+            ast::ExprKind::Paren(arg) => vec![arg],
+            ast::ExprKind::Tuple(args) => args.iter().map(AsRef::as_ref).collect(),
+            _ => return,
+        };
+        if params.len() != args.len() {
+            return;
+        }
+
+        for (param, arg) in params.into_iter().zip(args) {
+            let Some(param) = param else {
+                continue;
+            };
+            let is_same_name = matches!(
+                &*arg.kind,
+                ast::ExprKind::Path(path) if path.segments.is_none() && *path.name.name == *param
+            );
+            if param.starts_with('_') || is_same_name || matches!(*arg.kind, ast::ExprKind::Hole) {
+                continue;
+            }
+            self.push(arg.span.lo, format!("{param}:"), InlayHintKind::Parameter);
+        }
+    }
+
+    /// Returns the parameter names of a global callable, or `None` for
+    /// parameters that are not bound to a name.
+    fn callee_params(&self, callee: &ast::Expr) -> Option<Vec<Option<String>>> {
+        let path = match &*callee.kind {
+            ast::ExprKind::Path(path) => path,
+            ast::ExprKind::Paren(callee)
+            | ast::ExprKind::UnOp(ast::UnOp::Functor(ast::Functor::Adj), callee) => {
+                return self.callee_params(callee);
+            }
+            _ => return None,
+        };
+        let (Res::Item(item_id, _) | Res::ExportedItem(item_id, _)) =
+            self.compilation.get_res(path.id)?
+        else {
+            return None;
+        };
+        let (item, ..) = self
+            .compilation
+            .resolve_item_relative_to_user_package(item_id);
+        let hir::ItemKind::Callable(decl) = &item.kind else {
+            return None;
+        };
+
+        let param_name = |pat: &hir::Pat| match &pat.kind {
+            hir::PatKind::Bind(name) => Some(name.name.to_string()),
+            _ => None,
+        };
+        match &decl.input.kind {
+            hir::PatKind::Tuple(items) => Some(items.iter().map(param_name).collect()),
+            _ => Some(vec![param_name(&decl.input)]),
+        }
+    }
+}
+
+impl<'a> Visitor<'a> for HintCollector<'a> {
+    fn visit_stmt(&mut self, stmt: &'a ast::Stmt) {
+        if let ast::StmtKind::Local(_, pat, value) = &*stmt.kind {
+            // The type of a partial application is already shown after the call.
+            let shown_after_value =
+                self.config.partial_application_types && is_partial_application(value);
+            if self.config.variable_types && !shown_after_value {
+                self.push_binding_types(pat);
+            }
+        }
+        walk_stmt(self, stmt);
+    }
+
+    fn visit_expr(&mut self, expr: &'a ast::Expr) {
+        match &*expr.kind {
+            ast::ExprKind::Lambda(_, input, _) if self.config.lambda_parameter_types => {
+                self.push_binding_types(input);
+            }
+            ast::ExprKind::Call(callee, arg) => {
+                if self.config.partial_application_types && is_partial_application(expr) {
+                    if let Some(ty) = self.compilation.get_ty(expr.id) {
+                        if is_known(ty) {
+                            self.push(
+                                expr.span.hi,
+                                format!(": {}", ty.display()),
+                                InlayHintKind::Type,
+                            );
+                        }
+                    }
+                }
+                if self.config.parameter_names {
+                    self.push_parameter_names(callee, arg);
+                }
+            }
+            _ => {}
+        }
+        walk_expr(self, expr);
+    }
+}
+
+/// Returns `true` if the expression is a call with a hole, `_`, in its arguments.
+fn is_partial_application(expr: &ast::Expr) -> bool {
+    fn has_hole(arg: &ast::Expr) -> bool {
+        match &*arg.kind {
+            ast::ExprKind::Hole => true,
+            ast::ExprKind::Paren(arg) => has_hole(arg),
+            ast::ExprKind::Tuple(args) => args.iter().any(|arg| has_hole(arg)),
+            _ => false,
+        }
+    }
+
+    matches!(&*expr.kind, ast::ExprKind::Call(_, arg) if has_hole(arg))
+}
+
+/// Returns `false` if the type couldn't be fully inferred.
+fn is_known(ty: &Ty) -> bool {
+    match ty {
+        Ty::Infer(_) | Ty::Err => false,
+        Ty::Array(item) => is_known(item),
+        Ty::Arrow(arrow) => is_known(&arrow.input) && is_known(&arrow.output),
+        Ty::Tuple(items) => items.iter().all(is_known),
+        Ty::Param(..) | Ty::Prim(_) | Ty::Udt(..) => true,
+    }
+}
@@ -0,0 +1,175 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+#![allow(clippy::needless_raw_string_hashes)]
+
+use super::get_inlay_hints;
+use crate::{
+    protocol::{InlayHint, InlayHintsConfig},
+    test_utils::compile_with_fake_stdlib_and_markers_no_cursor,
+    Encoding,
+};
+use expect_test::{expect, Expect};
+use qsc::line_column::{Position, Range};
+use std::fmt::Write;
+
+fn render(hints: &[InlayHint]) -> String {
+    let mut actual = String::new();
+    for hint in hints {
+        writeln!(
+            actual,
+            "{}:{} {:?} {}",
+            hint.position.line, hint.position.column, hint.kind, hint.label
+        )
+        .expect("writing to string should succeed");
+    }
+    actual
+}
+
+fn check_with_config(source: &str, config: InlayHintsConfig, expect: &Expect) {
+    let (compilation, _) = compile_with_fake_stdlib_and_markers_no_cursor(source, true);
+    let range = Range {
+        start: Position { line: 0, column: 0 },
+        end: Position {
+            line: u32::MAX,
+            column: 0,
+        },
+    };
+    let hints = get_inlay_hints(&compilation, "<source>", range, config, Encoding::Utf8);
+    expect.assert_eq(&render(&hints));
+}
+
+fn check(source: &str, expect: &Expect) {
+    check_with_config(source, InlayHintsConfig::default(), expect);
+}
+
+#[test]
+fn binding_types() {
+    check(
+        r#"
+namespace Test {
+    function Main() : Unit {
+        let x = 1;
+        mutable y = [1.0, 2.0];
+        let (a, b) = (true, "s");
+        let z : Int = 2;
+        for i in 0..3 {}
+    }
+}"#,
+        &expect![[r#"
+            3:13 Type : Int
+            4:17 Type : Double[]
+            5:14 Type : Bool
+            5:17 Type : String
+        "#]],
+    );
+}
+
+#[test]
+fn lambda_parameter_types() {
+    check(
+        r#"
+namespace Test {
+    function Main() : Unit {
+        let f = (a, b) -> a + b + 1;
+    }
+}"#,
+        &expect![[r#"
+            3:13 Type : ((Int, Int) -> Int)
+            3:18 Type : Int
+            3:21 Type : Int
+        "#]],
+    );
+}
+
+#[test]
+fn parameter_names() {
+    check(
+        r#"
+namespace Test {
+    operation Apply(target : Qubit, count : Int, _unused : Bool) : Unit is Adj {}
+    operation Main() : Unit {
+        use target = Qubit();
+        Apply(target, 3, true);
+        Adjoint Apply(target, 1 + 1, false);
+    }
+}"#,
+        &expect![[r#"
+            5:22 Parameter count:
+            6:30 Parameter count:
+        "#]],
+    );
+}
+
+#[test]
+fn partial_application_types() {
+    check(
+        r#"
+namespace Test {
+    operation Apply(target : Qubit, count : Int) : Unit is Adj {}
+    operation Main() : Unit {
+        let f = Apply(_, 3);
+        let g = Adjoint Apply(_, 1);
+    }
+}"#,
+        &expect![[r#"
+            4:25 Parameter count:
+            4:27 Type : (Qubit => Unit is Adj)
+            5:33 Parameter count:
+            5:35 Type : (Qubit => Unit is Adj)
+        "#]],
+    );
+}
+
+#[test]
+fn disabled_kinds_are_omitted() {
+    check_with_config(
+        r#"
+namespace Test {
+    operation Apply(target : Qubit, count : Int) : Unit {}
+    operation Main() : Unit {
+        let f = Apply(_, 3);
+        let g = x -> x + 1;
+        let n = g(1);
+    }
+}"#,
+        InlayHintsConfig {
+            parameter_names: false,
+            partial_application_types: false,
+            ..InlayHintsConfig::default()
+        },
+        &expect![[r#"
+            4:13 Type : (Qubit => Unit)
+            5:13 Type : (Int -> Int)
+            5:17 Type : Int
+            6:13 Type : Int
+        "#]],
+    );
+}
+
+#[test]
+fn only_hints_in_range_are_returned() {
+    let source = r#"
+namespace Test {
+    function Main() : Unit {
+        let x = 1;
+        let y = 2.0;
+    }
+}"#;
+    let (compilation, _) = compile_with_fake_stdlib_and_markers_no_cursor(source, true);
+    let range = Range {
+        start: Position { line: 4, column: 0 },
+        end: Position { line: 5, column: 0 },
+    };
+    let hints = get_inlay_hints(
+        &compilation,
+        "<source>",
+        range,
+        InlayHintsConfig::default(),
+        Encoding::Utf8,
+    );
+    expect![[r#"
+        4:13 Type : Double
+    "#]]
+    .assert_eq(&render(&hints));
+}
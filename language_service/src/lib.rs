@@ -1,6 +1,7 @@
 // Copyright (c) Microsoft Corporation.
 // Licensed under the MIT License.
 
+pub mod call_hierarchy;
 pub mod code_action;
 pub mod code_lens;
 mod compilation;
@@ -8,13 +9,17 @@ pub mod completion;
 pub mod definition;
 pub mod format;
 pub mod hover;
+pub mod inlay_hints;
 mod name_locator;
 pub mod protocol;
 mod qsc_utils;
+mod refactor;
 pub mod references;
 pub mod rename;
+pub mod semantic_tokens;
 pub mod signature_help;
 mod state;
+pub mod symbols;
 #[cfg(test)]
 mod test_utils;
 #[cfg(test)]
@@ -25,8 +30,9 @@ use futures::channel::mpsc::{unbounded, UnboundedReceiver, UnboundedSender};
 use futures_util::StreamExt;
 use log::{trace, warn};
 use protocol::{
-    CodeAction, CodeLens, CompletionList, DiagnosticUpdate, Hover, NotebookMetadata, SignatureHelp,
-    TextEdit, WorkspaceConfigurationUpdate,
+    CallHierarchyCall, CallHierarchyItem, CodeAction, CodeLens, CompletionList, DiagnosticUpdate,
+    DocumentSymbol, Hover, InlayHint, InlayHintsConfig, NotebookMetadata, SemanticToken,
+    SignatureHelp, TextEdit, WorkspaceConfigurationUpdate, WorkspaceSymbol,
 };
 use qsc::{
     line_column::{Encoding, Position, Range},
@@ -40,6 +46,9 @@ pub struct LanguageService {
     /// All [`Position`]s and [`Range`]s will be mapped using this encoding.
     /// In LSP the equivalent would be the `positionEncoding` server capability.
     position_encoding: Encoding,
+    /// The kinds of inlay hints to show. Unlike the other configuration settings,
+    /// these don't affect compilation, so they are not sent to the update worker.
+    inlay_hints_config: InlayHintsConfig,
     /// The compilation state. This state is protected by a `RefCell` so that
     /// read and update operations can share it. Update operations should take
     /// care never leave `CompilationState` in an inconsistent state during an
@@ -54,6 +63,7 @@ impl LanguageService {
     pub fn new(position_encoding: Encoding) -> Self {
         Self {
             position_encoding,
+            inlay_hints_config: InlayHintsConfig::default(),
             state: Rc::default(),
             state_updater: Option::default(),
         }
@@ -99,6 +109,9 @@ impl LanguageService {
     /// LSP: workspace/didChangeConfiguration
     pub fn update_configuration(&mut self, configuration: WorkspaceConfigurationUpdate) {
         trace!("update_configuration: {configuration:?}");
+        if let Some(inlay_hints_config) = configuration.inlay_hints {
+            self.inlay_hints_config = inlay_hints_config;
+        }
         self.send_update(Update::Configuration {
             changed: configuration,
         });
@@ -151,7 +164,7 @@ impl LanguageService {
         trace!("update_notebook_document: {notebook_uri}");
         self.send_update(Update::NotebookDocument {
             notebook_uri: notebook_uri.into(),
-            notebook_metadata,
+            notebook_metadata: Box::new(notebook_metadata),
             cells: cells
                 .map(|(uri, version, contents)| (uri.into(), version, contents.into()))
                 .collect(),
@@ -227,13 +240,14 @@ impl LanguageService {
     /// LSP: textDocument/format
     #[must_use]
     pub fn get_format_changes(&self, uri: &str) -> Vec<TextEdit> {
+        let config = self.state.borrow().get_format_config(uri);
         self.document_op(
-            |compilation, uri, (), position_encoding| {
-                format::get_format_changes(compilation, uri, position_encoding)
+            |compilation, uri, config, position_encoding| {
+                format::get_format_changes(compilation, uri, &config, position_encoding)
             },
             "get_format_changes",
             uri,
-            (),
+            config,
         )
     }
 
@@ -279,6 +293,107 @@ impl LanguageService {
         )
     }
 
+    /// LSP: textDocument/inlayHint
+    #[must_use]
+    pub fn get_inlay_hints(&self, uri: &str, range: Range) -> Vec<InlayHint> {
+        self.document_op(
+            |compilation, uri, (range, config), position_encoding| {
+                inlay_hints::get_inlay_hints(compilation, uri, range, config, position_encoding)
+            },
+            "get_inlay_hints",
+            uri,
+            (range, self.inlay_hints_config),
+        )
+    }
+
+    /// LSP: textDocument/prepareCallHierarchy
+    #[must_use]
+    pub fn prepare_call_hierarchy(
+        &self,
+        uri: &str,
+        position: Position,
+    ) -> Option<CallHierarchyItem> {
+        self.document_op(
+            call_hierarchy::prepare_call_hierarchy,
+            "prepare_call_hierarchy",
+            uri,
+            position,
+        )
+    }
+
+    /// LSP: callHierarchy/incomingCalls
+    ///
+    /// `uri` is the document that the call hierarchy was prepared in. Its compilation
+    /// is used to find callers, which may be in other documents.
+    #[must_use]
+    pub fn get_incoming_calls(&self, uri: &str, item: CallHierarchyItem) -> Vec<CallHierarchyCall> {
+        self.document_op(
+            |compilation, _, item, position_encoding| {
+                call_hierarchy::get_incoming_calls(compilation, &item, position_encoding)
+            },
+            "get_incoming_calls",
+            uri,
+            item,
+        )
+    }
+
+    /// LSP: callHierarchy/outgoingCalls
+    ///
+    /// `uri` is the document that the call hierarchy was prepared in.
+    #[must_use]
+    pub fn get_outgoing_calls(&self, uri: &str, item: CallHierarchyItem) -> Vec<CallHierarchyCall> {
+        self.document_op(
+            |compilation, _, item, position_encoding| {
+                call_hierarchy::get_outgoing_calls(compilation, &item, position_encoding)
+            },
+            "get_outgoing_calls",
+            uri,
+            item,
+        )
+    }
+
+    /// LSP: textDocument/semanticTokens/full
+    #[must_use]
+    pub fn get_semantic_tokens(&self, uri: &str) -> Vec<SemanticToken> {
+        self.document_op(
+            |compilation, uri, (), position_encoding| {
+                semantic_tokens::get_semantic_tokens(compilation, uri, position_encoding)
+            },
+            "get_semantic_tokens",
+            uri,
+            (),
+        )
+    }
+
+    /// LSP: textDocument/documentSymbol
+    #[must_use]
+    pub fn get_document_symbols(&self, uri: &str) -> Vec<DocumentSymbol> {
+        self.document_op(
+            |compilation, uri, (), position_encoding| {
+                symbols::get_document_symbols(compilation, uri, position_encoding)
+            },
+            "get_document_symbols",
+            uri,
+            (),
+        )
+    }
+
+    /// LSP: workspace/symbol
+    ///
+    /// Unlike document operations, this searches the current state of all compilations.
+    #[must_use]
+    pub fn get_workspace_symbols(&self, query: &str) -> Vec<WorkspaceSymbol> {
+        trace!("get_workspace_symbols: query: {query}");
+        let compilation_state = self.state.borrow();
+        let res = symbols::get_workspace_symbols(
+            compilation_state.compilations(),
+            query,
+            self.position_encoding,
+        );
+        trace!("get_workspace_symbols result: {res:?}");
+        res
+    }
+
     /// Executes an operation that takes a document uri, using the current compilation for that document.
     /// All "read" operations should go through this method. This method will borrow the current
     /// compilation state to perform the request.
@@ -463,7 +578,7 @@ enum Update {
     },
     NotebookDocument {
         notebook_uri: String,
-        notebook_metadata: NotebookMetadata,
+        notebook_metadata: Box<NotebookMetadata>,
         cells: Vec<(String, u32, String)>,
     },
     CloseNotebookDocument {
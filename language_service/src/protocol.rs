@@ -2,9 +2,13 @@
 // Licensed under the MIT License.
 
 use miette::Diagnostic;
-use qsc::line_column::Range;
+use qsc::line_column::{Position, Range};
+use qsc::location::Location;
 use qsc::{compile, project};
-use qsc::{linter::LintConfig, project::Manifest, target::Profile, LanguageFeatures, PackageType};
+use qsc::{
+    linter::LintConfig, project::Manifest, target::Profile, FunctorApp, LanguageFeatures,
+    PackageType,
+};
 use thiserror::Error;
 
 /// A change to the workspace configuration
@@ -14,6 +18,32 @@ pub struct WorkspaceConfigurationUpdate {
     pub package_type: Option<PackageType>,
     pub language_features: Option<LanguageFeatures>,
     pub lints_config: Option<Vec<LintConfig>>,
+    pub inlay_hints: Option<InlayHintsConfig>,
+}
+
+/// The kinds of inlay hints to show. All of them are shown by default.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[allow(clippy::struct_excessive_bools)]
+pub struct InlayHintsConfig {
+    /// Inferred types of `let` and `mutable` bindings.
+    pub variable_types: bool,
+    /// Inferred types of lambda parameters.
+    pub lambda_parameter_types: bool,
+    /// Parameter names at call sites.
+    pub parameter_names: bool,
+    /// The callable type that results from a partial application.
+    pub partial_application_types: bool,
+}
+
+impl Default for InlayHintsConfig {
+    fn default() -> Self {
+        Self {
+            variable_types: true,
+            lambda_parameter_types: true,
+            parameter_names: true,
+            partial_application_types: true,
+        }
+    }
 }
 
 #[derive(Clone, Debug, Diagnostic, Error)]
@@ -127,6 +157,110 @@ pub struct Hover {
     pub span: Range,
 }
 
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SymbolKind {
+    Namespace,
+    Function,
+    Operation,
+    Struct,
+    Field,
+}
+
+/// A symbol declared in a document, along with the symbols nested inside it.
+#[derive(Debug, PartialEq)]
+pub struct DocumentSymbol {
+    pub name: String,
+    pub detail: Option<String>,
+    pub kind: SymbolKind,
+    /// The full extent of the declaration, including its body.
+    pub range: Range,
+    /// The range of the symbol's name.
+    pub selection_range: Range,
+    pub children: Vec<DocumentSymbol>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct WorkspaceSymbol {
+    pub name: String,
+    pub kind: SymbolKind,
+    pub container_name: Option<String>,
+    pub location: Location,
+}
+
+/// A callable in a call hierarchy.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CallHierarchyItem {
+    pub name: String,
+    pub kind: SymbolKind,
+    /// The namespace that contains the callable.
+    pub container_name: Option<String>,
+    /// The full extent of the declaration, including its body.
+    pub location: Location,
+    /// The range of the callable's name, in the same source as `location`.
+    pub selection_range: Range,
+}
+
+/// The calls from one callable to another that apply the same functors.
+#[derive(Debug, PartialEq)]
+pub struct CallHierarchyCall {
+    /// The caller for incoming calls, or the callee for outgoing calls.
+    pub item: CallHierarchyItem,
+    pub functor_app: FunctorApp,
+    /// The ranges of the callee names at each call site, in the caller's source.
+    pub from_ranges: Vec<Range>,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SemanticTokenType {
+    Namespace,
+    Struct,
+    TypeParameter,
+    Parameter,
+    Variable,
+    Property,
+    Function,
+    Operation,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SemanticTokenModifier {
+    /// The token is the name in a declaration.
+    Declaration,
+    /// An immutable binding.
+    Readonly,
+    /// A binding declared with `mutable`.
+    Mutable,
+    /// A local of type `Qubit` or `Qubit[]`.
+    Qubit,
+    /// An item marked `@Unimplemented()`, which can't be used.
+    Deprecated,
+    /// A callable that supports the `Adjoint` functor.
+    Adjointable,
+    /// A callable that supports the `Controlled` functor.
+    Controllable,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct SemanticToken {
+    pub range: Range,
+    pub token_type: SemanticTokenType,
+    pub modifiers: Vec<SemanticTokenModifier>,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum InlayHintKind {
+    Type,
+    Parameter,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct InlayHint {
+    pub position: Position,
+    /// The text of the hint, including its punctuation, e.g. `: Int` or `target:`.
+    pub label: String,
+    pub kind: InlayHintKind,
+}
+
 #[derive(Debug)]
 pub struct WorkspaceEdit {
     pub changes: Vec<(String, Vec<TextEdit>)>,
@@ -0,0 +1,861 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+#[cfg(test)]
+mod tests;
+
+use crate::{
+    compilation::Compilation,
+    protocol::{CodeAction, CodeActionKind, TextEdit, WorkspaceEdit},
+};
+use qsc::{
+    ast::{
+        self,
+        visit::{walk_block, walk_expr, walk_pat, walk_stmt, Visitor},
+        Mutability, NodeId,
+    },
+    display::Lookup,
+    hir::{
+        self,
+        ty::{FunctorSet, FunctorSetValue, Ty},
+        CallableKind, Functor,
+    },
+    line_column::{Encoding, Range},
+    resolve::{path_as_field_accessor, Res},
+    Source, Span,
+};
+use rustc_hash::FxHashMap;
+use std::rc::Rc;
+
+/// The indentation of each level of nesting in generated code.
+const INDENT: &str = "    ";
+
+/// Offers to move the statements covered by `span` into a new callable, declared after the
+/// callable that contains them, and to replace them with a call to it.
+/// Variables declared outside of the statements become parameters, and variables declared
+/// by the statements that are used after them are returned.
+#[allow(clippy::too_many_lines)]
+pub(crate) fn extract_callable(
+    compilation: &Compilation,
+    source_name: &str,
+    span: Span,
+    position_encoding: Encoding,
+) -> Option<CodeAction> {
+    let unit = compilation.user_unit();
+    let source = unit.sources.find_by_name(source_name)?;
+    let (namespace, item, decl) = enclosing_callable(&unit.ast.package, span)?;
+    let ast::CallableBody::Block(body) = &*decl.body else {
+        return None;
+    };
+
+    let mut selector = StmtSelector { span, found: None };
+    selector.visit_block(body);
+    let (block, stmts) = selector.found?;
+    if stmts
+        .iter()
+        .any(|stmt| matches!(&*stmt.kind, ast::StmtKind::Item(_)))
+    {
+        return None;
+    }
+    let selection = Span {
+        lo: stmts.first()?.span.lo,
+        hi: stmts.last()?.span.hi,
+    };
+
+    let mut inner = BindingFinder::default();
+    for stmt in stmts {
+        inner.visit_stmt(stmt);
+    }
+    let mut all = BindingFinder::default();
+    all.visit_pat(&decl.input);
+    all.visit_block(body);
+
+    let mut analyzer = SelectionAnalyzer {
+        compilation,
+        inner: &inner.bindings,
+        free: Vec::new(),
+        assigns_outer: false,
+        has_return: false,
+        has_loop: false,
+        has_assignment: false,
+        is_quantum: false,
+        adj: true,
+        ctl: true,
+        lambda_depth: 0,
+    };
+    for stmt in stmts {
+        analyzer.visit_stmt(stmt);
+    }
+    // Parameters can't be assigned to, and the statements can't return from the original callable.
+    if analyzer.assigns_outer || analyzer.has_return {
+        return None;
+    }
+
+    let mut used_after = UsesAfter {
+        compilation,
+        inner: &inner.bindings,
+        offset: selection.hi,
+        used: Vec::new(),
+    };
+    used_after.visit_block(body);
+    let outputs: Vec<&Binding> = inner
+        .order
+        .iter()
+        .filter(|id| used_after.used.contains(id))
+        .map(|id| &inner.bindings[id])
+        .collect();
+
+    // A trailing expression is the value of its block, so it becomes the value of the new callable.
+    let tail = match stmts.last().map(|stmt| &*stmt.kind) {
+        Some(ast::StmtKind::Expr(expr))
+            if block.stmts.last().map(|stmt| stmt.id) == stmts.last().map(|stmt| stmt.id) =>
+        {
+            Some(expr)
+        }
+        _ => None,
+    };
+    if tail.is_some() && !outputs.is_empty() {
+        return None;
+    }
+
+    let mut params = Vec::new();
+    let mut tys = Vec::new();
+    for id in &analyzer.free {
+        let binding = all.bindings.get(id)?;
+        let ty = compilation.get_ty(binding.pat_id)?;
+        params.push(format!("{} : {}", binding.name, ty.display()));
+        tys.push(ty);
+    }
+    let output = if let Some(expr) = tail {
+        let ty = compilation.get_ty(expr.id)?;
+        tys.push(ty);
+        ty.display()
+    } else {
+        let output_tys = outputs
+            .iter()
+            .map(|binding| compilation.get_ty(binding.pat_id))
+            .collect::<Option<Vec<_>>>()?;
+        tys.extend(output_tys.iter().copied());
+        match output_tys.as_slice() {
+            [] => "Unit".to_string(),
+            [ty] => ty.display(),
+            tys => format!(
+                "({})",
+                tys.iter()
+                    .map(|ty| ty.display())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        }
+    };
+    if !tys.iter().all(|ty| is_known(ty)) {
+        return None;
+    }
+    let generics = if tys.iter().any(|ty| has_param(ty)) {
+        format!(
+            "<{}>",
+            decl.generics
+                .iter()
+                .map(|param| param.name.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+    } else {
+        String::new()
+    };
+
+    let is_operation = decl.kind == ast::CallableKind::Operation && analyzer.is_quantum;
+    let kind = if is_operation {
+        "operation"
+    } else {
+        "function"
+    };
+    let functors = if is_operation && output == "Unit" {
+        let adj = analyzer.adj && !analyzer.has_loop && !analyzer.has_assignment;
+        match (adj, analyzer.ctl) {
+            (true, true) => " is Adj + Ctl",
+            (true, false) => " is Adj",
+            (false, true) => " is Ctl",
+            (false, false) => "",
+        }
+    } else {
+        ""
+    };
+    let name = unique_name(
+        &unit.ast.package,
+        namespace,
+        if is_operation {
+            "ExtractedOperation"
+        } else {
+            "ExtractedFunction"
+        },
+    );
+
+    let item_indent = line_indent(&source.contents, item.span.lo - source.offset);
+    let body_indent = format!("{item_indent}{INDENT}");
+    let stmts_indent = line_indent(&source.contents, selection.lo - source.offset);
+    let stmts_text = reindent(span_text(source, selection), stmts_indent, &body_indent);
+    let names = |bindings: &[&Binding]| {
+        bindings
+            .iter()
+            .map(|binding| binding.name.to_string())
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    let result = match outputs.as_slice() {
+        [] => String::new(),
+        [output] => format!("\n{body_indent}{}", output.name),
+        outputs => format!("\n{body_indent}({})", names(outputs)),
+    };
+    let new_callable = format!(
+        "\n\n{item_indent}{kind} {name}{generics}({}) : {output}{functors} {{\n{body_indent}{stmts_text}{result}\n{item_indent}}}",
+        params.join(", "),
+    );
+
+    let args = analyzer
+        .free
+        .iter()
+        .map(|id| all.bindings[id].name.to_string())
+        .collect::<Vec<_>>()
+        .join(", ");
+    let call = format!("{name}({args})");
+    let replacement = if tail.is_some() {
+        call
+    } else {
+        let binding = if outputs.iter().any(|binding| binding.mutable) {
+            "mutable"
+        } else {
+            "let"
+        };
+        match outputs.as_slice() {
+            [] => format!("{call};"),
+            [output] => format!("{binding} {} = {call};", output.name),
+            outputs => format!("{binding} ({}) = {call};", names(outputs)),
+        }
+    };
+
+    Some(CodeAction {
+        title: format!("Extract to {kind}"),
+        edit: Some(WorkspaceEdit {
+            changes: vec![(
+                source_name.to_string(),
+                vec![
+                    text_edit(source, selection, replacement, position_encoding),
+                    text_edit(
+                        source,
+                        Span {
+                            lo: item.span.hi,
+                            hi: item.span.hi,
+                        },
+                        new_callable,
+                        position_encoding,
+                    ),
+                ],
+            )],
+        }),
+        kind: Some(CodeActionKind::RefactorExtract),
+        is_preferred: None,
+    })
+}
+
+/// Offers to replace the call to a callable whose name is at `span` with the callable's body.
+/// Arguments that are variables or literals are substituted for the parameters, and any
+/// others are bound to the parameter names before the body.
+#[allow(clippy::too_many_lines)]
+pub(crate) fn inline_callable(
+    compilation: &Compilation,
+    source_name: &str,
+    span: Span,
+    position_encoding: Encoding,
+) -> Option<CodeAction> {
+    let unit = compilation.user_unit();
+    let source = unit.sources.find_by_name(source_name)?;
+    let mut finder = CallFinder {
+        offset: span.lo,
+        call: None,
+        stmt: None,
+    };
+    finder.visit_package(&unit.ast.package);
+    let (call, callee, args) = finder.call?;
+
+    let Some(Res::Item(item_id, _)) = unit.ast.names.get(callee.id) else {
+        return None;
+    };
+    if item_id.package.is_some() {
+        return None;
+    }
+    let hir::ItemKind::Callable(hir_decl) = &unit.package.items.get(item_id.item)?.kind else {
+        return None;
+    };
+    let (decl_namespace, decl) = find_callable_decl(&unit.ast.package, hir_decl.name.span)?;
+    let (call_namespace, ..) = enclosing_callable(&unit.ast.package, call.span)?;
+    // Names used by the body may not be in scope elsewhere.
+    if decl_namespace.name.name() != call_namespace.name.name() {
+        return None;
+    }
+    let ast::CallableBody::Block(body) = &*decl.body else {
+        return None;
+    };
+    let (Some(first), Some(last)) = (body.stmts.first(), body.stmts.last()) else {
+        return None;
+    };
+    let decl_source = unit.sources.find_by_offset(decl.span.lo)?;
+
+    let mut checker = BodyChecker {
+        compilation,
+        item_id: *item_id,
+        inlinable: true,
+        lambda_depth: 0,
+    };
+    checker.visit_block(body);
+    if !checker.inlinable {
+        return None;
+    }
+
+    let params = flatten_params(&decl.input)?;
+    let args: Vec<&ast::Expr> = match &*args.kind {
+        ast::ExprKind::Tuple(args) => args.iter().map(AsRef::as_ref).collect(),
+        ast::ExprKind::Paren(arg) => vec![arg],
+        _ => vec![args],
+    };
+    if params.len() != args.len() {
+        return None;
+    }
+
+    let mut locals = BindingFinder::default();
+    locals.visit_block(body);
+    let local_names: Vec<&Rc<str>> = locals.bindings.values().map(|b| &b.name).collect();
+    let mut substitutions = FxHashMap::default();
+    let mut bound = Vec::new();
+    for (param, arg) in params.iter().zip(&args) {
+        let text = span_text(source, arg.span);
+        let is_simple = match &*arg.kind {
+            ast::ExprKind::Lit(_) => true,
+            ast::ExprKind::Path(path) => {
+                path.segments.is_none()
+                    && local_of(compilation, path).is_some()
+                    && !local_names.iter().any(|name| ***name == *text)
+            }
+            _ => false,
+        };
+        if is_simple {
+            substitutions.insert(param.id, text.to_string());
+        } else {
+            bound.push((param.name.to_string(), text.to_string()));
+        }
+    }
+
+    let mut substituter = Substituter {
+        compilation,
+        substitutions: &substitutions,
+        edits: Vec::new(),
+    };
+    substituter.visit_block(body);
+    let body_span = Span {
+        lo: first.span.lo,
+        hi: last.span.hi,
+    };
+    let mut text = span_text(decl_source, body_span).to_string();
+    substituter
+        .edits
+        .sort_by_key(|(span, _)| std::cmp::Reverse(span.lo));
+    for (span, new_text) in substituter.edits {
+        text.replace_range(
+            (span.lo - body_span.lo) as usize..(span.hi - body_span.lo) as usize,
+            &new_text,
+        );
+    }
+    let body_indent = line_indent(&decl_source.contents, first.span.lo - decl_source.offset);
+
+    let (replaced, new_text) = match finder.stmt {
+        Some(stmt) if hir_decl.output == Ty::UNIT => {
+            let indent = line_indent(&source.contents, stmt.span.lo - source.offset);
+            if bound.is_empty() && locals.bindings.is_empty() {
+                let mut new_text = reindent(&text, body_indent, indent);
+                if let ast::StmtKind::Expr(expr) = &*last.kind {
+                    if !is_block_like(expr) {
+                        new_text.push(';');
+                    }
+                }
+                (stmt.span, new_text)
+            } else {
+                let inner_indent = format!("{indent}{INDENT}");
+                let bindings = match bound.as_slice() {
+                    [] => String::new(),
+                    [(name, arg)] => format!("let {name} = {arg};\n{inner_indent}"),
+                    bound => {
+                        let (names, args): (Vec<_>, Vec<_>) = bound.iter().cloned().unzip();
+                        format!(
+                            "let ({}) = ({});\n{inner_indent}",
+                            names.join(", "),
+                            args.join(", ")
+                        )
+                    }
+                };
+                let new_text = format!(
+                    "{{\n{inner_indent}{bindings}{}\n{indent}}}",
+                    reindent(&text, body_indent, &inner_indent)
+                );
+                (stmt.span, new_text)
+            }
+        }
+        _ => {
+            // In any other position, only a single expression can replace the call.
+            let ([], ast::StmtKind::Expr(expr)) = (bound.as_slice(), &*last.kind) else {
+                return None;
+            };
+            if body.stmts.len() != 1 {
+                return None;
+            }
+            if is_atomic(expr) {
+                (call.span, text)
+            } else {
+                (call.span, format!("({text})"))
+            }
+        }
+    };
+
+    Some(CodeAction {
+        title: format!("Inline `{}`", decl.name.name),
+        edit: Some(WorkspaceEdit {
+            changes: vec![(
+                source_name.to_string(),
+                vec![text_edit(source, replaced, new_text, position_encoding)],
+            )],
+        }),
+        kind: Some(CodeActionKind::RefactorInline),
+        is_preferred: None,
+    })
+}
+
+/// Finds the namespace-level callable that contains the span.
+fn enclosing_callable(
+    package: &ast::Package,
+    span: Span,
+) -> Option<(&ast::Namespace, &ast::Item, &ast::CallableDecl)> {
+    namespaces(package).find_map(|namespace| {
+        namespace.items.iter().find_map(|item| match &*item.kind {
+            ast::ItemKind::Callable(decl) if encloses(decl.span, span) => {
+                Some((namespace, item.as_ref(), decl.as_ref()))
+            }
+            _ => None,
+        })
+    })
+}
+
+/// Finds the namespace-level callable whose name is at `name_span`.
+fn find_callable_decl(
+    package: &ast::Package,
+    name_span: Span,
+) -> Option<(&ast::Namespace, &ast::CallableDecl)> {
+    namespaces(package).find_map(|namespace| {
+        namespace.items.iter().find_map(|item| match &*item.kind {
+            ast::ItemKind::Callable(decl) if decl.name.span == name_span => {
+                Some((namespace, decl.as_ref()))
+            }
+            _ => None,
+        })
+    })
+}
+
+fn namespaces(package: &ast::Package) -> impl Iterator<Item = &ast::Namespace> {
+    package.nodes.iter().filter_map(|node| match node {
+        ast::TopLevelNode::Namespace(namespace) => Some(namespace),
+        ast::TopLevelNode::Stmt(_) => None,
+    })
+}
+
+/// Returns `base`, followed by a number if needed to make it different from
+/// the names of the other items in the namespace.
+fn unique_name(package: &ast::Package, namespace: &ast::Namespace, base: &str) -> String {
+    let namespace_name = namespace.name.name();
+    let taken: Vec<Rc<str>> = namespaces(package)
+        .filter(|other| other.name.name() == namespace_name)
+        .flat_map(|other| other.items.iter())
+        .filter_map(|item| match &*item.kind {
+            ast::ItemKind::Callable(decl) => Some(decl.name.name.clone()),
+            ast::ItemKind::Ty(name, _) => Some(name.name.clone()),
+            ast::ItemKind::Struct(decl) => Some(decl.name.name.clone()),
+            _ => None,
+        })
+        .collect();
+    let mut name = base.to_string();
+    let mut suffix = 1;
+    while taken.iter().any(|taken| **taken == *name) {
+        suffix += 1;
+        name = format!("{base}{suffix}");
+    }
+    name
+}
+
+fn encloses(outer: Span, inner: Span) -> bool {
+    outer.lo <= inner.lo && inner.hi <= outer.hi
+}
+
+fn is_known(ty: &Ty) -> bool {
+    match ty {
+        Ty::Infer(_) | Ty::Err => false,
+        Ty::Array(item) => is_known(item),
+        Ty::Arrow(arrow) => is_known(&arrow.input) && is_known(&arrow.output),
+        Ty::Tuple(items) => items.iter().all(is_known),
+        Ty::Param(..) | Ty::Prim(_) | Ty::Udt(..) => true,
+    }
+}
+
+fn has_param(ty: &Ty) -> bool {
+    match ty {
+        Ty::Param(..) => true,
+        Ty::Array(item) => has_param(item),
+        Ty::Arrow(arrow) => has_param(&arrow.input) || has_param(&arrow.output),
+        Ty::Tuple(items) => items.iter().any(has_param),
+        Ty::Infer(_) | Ty::Err | Ty::Prim(_) | Ty::Udt(..) => false,
+    }
+}
+
+/// Returns the local variable that a path refers to, along with the span of its name.
+fn local_of(compilation: &Compilation, path: &ast::Path) -> Option<(NodeId, Span)> {
+    let names = &compilation.user_unit().ast.names;
+    if let Some(Res::Local(id)) = names.get(path.id) {
+        return Some((*id, path.span));
+    }
+    let (id, parts) = path_as_field_accessor(names, path)?;
+    Some((id, parts.first()?.span))
+}
+
+/// Collects the local variables assigned to by the left-hand side of an assignment.
+fn assigned_locals(compilation: &Compilation, expr: &ast::Expr, locals: &mut Vec<NodeId>) {
+    match &*expr.kind {
+        ast::ExprKind::Path(path) => locals.extend(local_of(compilation, path).map(|(id, _)| id)),
+        ast::ExprKind::Paren(expr) => assigned_locals(compilation, expr, locals),
+        ast::ExprKind::Tuple(exprs) => exprs
+            .iter()
+            .for_each(|expr| assigned_locals(compilation, expr, locals)),
+        _ => {}
+    }
+}
+
+fn flatten_params(input: &ast::Pat) -> Option<Vec<&ast::Ident>> {
+    match &*input.kind {
+        ast::PatKind::Bind(name, _) => Some(vec![name]),
+        ast::PatKind::Paren(pat) => flatten_params(pat),
+        ast::PatKind::Tuple(pats) => pats
+            .iter()
+            .map(|pat| match &*pat.kind {
+                ast::PatKind::Bind(name, _) => Some(name.as_ref()),
+                _ => None,
+            })
+            .collect(),
+        _ => None,
+    }
+}
+
+/// Returns true for expressions that don't need a semicolon to be followed by another statement.
+fn is_block_like(expr: &ast::Expr) -> bool {
+    matches!(
+        &*expr.kind,
+        ast::ExprKind::Block(_)
+            | ast::ExprKind::Conjugate(..)
+            | ast::ExprKind::For(..)
+            | ast::ExprKind::If(..)
+            | ast::ExprKind::Repeat(..)
+            | ast::ExprKind::While(..)
+    )
+}
+
+/// Returns true for expressions that don't need parentheses to be used as an operand.
+fn is_atomic(expr: &ast::Expr) -> bool {
+    matches!(
+        &*expr.kind,
+        ast::ExprKind::Array(_)
+            | ast::ExprKind::Call(..)
+            | ast::ExprKind::Index(..)
+            | ast::ExprKind::Interpolate(_)
+            | ast::ExprKind::Lit(_)
+            | ast::ExprKind::Paren(_)
+            | ast::ExprKind::Path(_)
+            | ast::ExprKind::Tuple(_)
+    )
+}
+
+fn span_text(source: &Source, span: Span) -> &str {
+    &source.contents[(span.lo - source.offset) as usize..(span.hi - source.offset) as usize]
+}
+
+/// Returns the whitespace at the start of the line containing `offset`.
+fn line_indent(contents: &str, offset: u32) -> &str {
+    let line_start = contents[..offset as usize].rfind('\n').map_or(0, |i| i + 1);
+    let line = &contents[line_start..];
+    &line[..line.len() - line.trim_start_matches([' ', '\t']).len()]
+}
+
+/// Replaces the indentation `from` at the start of every line after the first with `to`.
+fn reindent(text: &str, from: &str, to: &str) -> String {
+    let mut lines = text.split('\n');
+    let mut result = lines.next().unwrap_or_default().to_string();
+    for line in lines {
+        result.push('\n');
+        if !line.trim().is_empty() {
+            result.push_str(to);
+            result.push_str(line.strip_prefix(from).unwrap_or(line.trim_start()));
+        }
+    }
+    result
+}
+
+fn text_edit(
+    source: &Source,
+    span: Span,
+    new_text: String,
+    position_encoding: Encoding,
+) -> TextEdit {
+    TextEdit {
+        new_text,
+        range: Range::from_span(position_encoding, &source.contents, &(span - source.offset)),
+    }
+}
+
+/// Finds the innermost block in which `span` covers one or more whole statements,
+/// and no part of any other statement.
+struct StmtSelector<'a> {
+    span: Span,
+    found: Option<(&'a ast::Block, &'a [Box<ast::Stmt>])>,
+}
+
+impl<'a> Visitor<'a> for StmtSelector<'a> {
+    fn visit_block(&mut self, block: &'a ast::Block) {
+        if !encloses(block.span, self.span) {
+            return;
+        }
+        let overlapping =
+            |stmt: &ast::Stmt| stmt.span.lo < self.span.hi && self.span.lo < stmt.span.hi;
+        let first = block.stmts.iter().position(|stmt| overlapping(stmt));
+        let last = block.stmts.iter().rposition(|stmt| overlapping(stmt));
+        if let (Some(first), Some(last)) = (first, last) {
+            let stmts = &block.stmts[first..=last];
+            if stmts.iter().all(|stmt| encloses(self.span, stmt.span)) {
+                self.found = Some((block, stmts));
+            }
+        }
+        walk_block(self, block);
+    }
+}
+
+struct Binding {
+    name: Rc<str>,
+    pat_id: NodeId,
+    mutable: bool,
+}
+
+/// Finds the local variables declared in a part of a callable.
+#[derive(Default)]
+struct BindingFinder {
+    bindings: FxHashMap<NodeId, Binding>,
+    /// The declared variables, in the order they appear.
+    order: Vec<NodeId>,
+    mutable: bool,
+}
+
+impl<'a> Visitor<'a> for BindingFinder {
+    fn visit_stmt(&mut self, stmt: &'a ast::Stmt) {
+        if let ast::StmtKind::Local(mutability, pat, value) = &*stmt.kind {
+            self.mutable = *mutability == Mutability::Mutable;
+            self.visit_pat(pat);
+            self.mutable = false;
+            self.visit_expr(value);
+        } else {
+            walk_stmt(self, stmt);
+        }
+    }
+
+    fn visit_pat(&mut self, pat: &'a ast::Pat) {
+        if let ast::PatKind::Bind(name, _) = &*pat.kind {
+            self.bindings.insert(
+                name.id,
+                Binding {
+                    name: name.name.clone(),
+                    pat_id: pat.id,
+                    mutable: self.mutable,
+                },
+            );
+            self.order.push(name.id);
+        }
+        walk_pat(self, pat);
+    }
+}
+
+/// Works out what the selected statements need from, and do to, the rest of the callable.
+#[allow(clippy::struct_excessive_bools)]
+struct SelectionAnalyzer<'a> {
+    compilation: &'a Compilation,
+    /// The variables declared by the selected statements.
+    inner: &'a FxHashMap<NodeId, Binding>,
+    /// The variables declared outside of the selected statements that they use,
+    /// in the order they are first used.
+    free: Vec<NodeId>,
+    assigns_outer: bool,
+    has_return: bool,
+    has_loop: bool,
+    has_assignment: bool,
+    is_quantum: bool,
+    /// Whether every operation called supports the adjoint functor.
+    adj: bool,
+    /// Whether every operation called supports the controlled functor.
+    ctl: bool,
+    /// Lambdas aren't called where they are declared, so the calls in them are ignored.
+    lambda_depth: usize,
+}
+
+impl<'a> Visitor<'a> for SelectionAnalyzer<'a> {
+    fn visit_stmt(&mut self, stmt: &'a ast::Stmt) {
+        if matches!(&*stmt.kind, ast::StmtKind::Qubit(..)) && self.lambda_depth == 0 {
+            self.is_quantum = true;
+        }
+        walk_stmt(self, stmt);
+    }
+
+    fn visit_expr(&mut self, expr: &'a ast::Expr) {
+        match &*expr.kind {
+            ast::ExprKind::Lambda(..) => {
+                self.lambda_depth += 1;
+                walk_expr(self, expr);
+                self.lambda_depth -= 1;
+                return;
+            }
+            ast::ExprKind::Return(_) if self.lambda_depth == 0 => self.has_return = true,
+            ast::ExprKind::While(..) | ast::ExprKind::Repeat(..) => self.has_loop = true,
+            ast::ExprKind::Assign(lhs, _)
+            | ast::ExprKind::AssignOp(_, lhs, _)
+            | ast::ExprKind::AssignUpdate(lhs, ..) => {
+                self.has_assignment = true;
+                let mut assigned = Vec::new();
+                assigned_locals(self.compilation, lhs, &mut assigned);
+                if assigned.iter().any(|id| !self.inner.contains_key(id)) {
+                    self.assigns_outer = true;
+                }
+            }
+            ast::ExprKind::Call(callee, _) if self.lambda_depth == 0 => {
+                if let Some(Ty::Arrow(arrow)) = self.compilation.get_ty(callee.id) {
+                    if arrow.kind == CallableKind::Operation {
+                        self.is_quantum = true;
+                        let functors = match arrow.functors {
+                            FunctorSet::Value(functors) | FunctorSet::Param(_, functors) => {
+                                functors
+                            }
+                            FunctorSet::Infer(_) => FunctorSetValue::Empty,
+                        };
+                        self.adj &= functors.contains(&Functor::Adj) && *arrow.output == Ty::UNIT;
+                        self.ctl &= functors.contains(&Functor::Ctl);
+                    }
+                }
+            }
+            _ => {}
+        }
+        walk_expr(self, expr);
+    }
+
+    fn visit_path(&mut self, path: &'a ast::Path) {
+        if let Some((id, _)) = local_of(self.compilation, path) {
+            if !self.inner.contains_key(&id) && !self.free.contains(&id) {
+                self.free.push(id);
+            }
+        }
+    }
+}
+
+/// Finds the variables in `inner` that are used after `offset`.
+struct UsesAfter<'a> {
+    compilation: &'a Compilation,
+    inner: &'a FxHashMap<NodeId, Binding>,
+    offset: u32,
+    used: Vec<NodeId>,
+}
+
+impl<'a> Visitor<'a> for UsesAfter<'a> {
+    fn visit_path(&mut self, path: &'a ast::Path) {
+        if let Some((id, _)) = local_of(self.compilation, path) {
+            if path.span.lo >= self.offset && self.inner.contains_key(&id) {
+                self.used.push(id);
+            }
+        }
+    }
+}
+
+/// Finds the innermost call whose callee is a path at `offset`, along with the
+/// statement it makes up, if any.
+struct CallFinder<'a> {
+    offset: u32,
+    call: Option<(&'a ast::Expr, &'a ast::Path, &'a ast::Expr)>,
+    stmt: Option<&'a ast::Stmt>,
+}
+
+impl<'a> Visitor<'a> for CallFinder<'a> {
+    fn visit_stmt(&mut self, stmt: &'a ast::Stmt) {
+        walk_stmt(self, stmt);
+        if let ast::StmtKind::Semi(expr) = &*stmt.kind {
+            if self.call.is_some_and(|(call, ..)| call.id == expr.id) {
+                self.stmt = Some(stmt);
+            }
+        }
+    }
+
+    fn visit_expr(&mut self, expr: &'a ast::Expr) {
+        if let ast::ExprKind::Call(callee, args) = &*expr.kind {
+            if let ast::ExprKind::Path(path) = &*callee.kind {
+                if path.span.touches(self.offset) {
+                    self.call = Some((expr, path, args));
+                    self.stmt = None;
+                }
+            }
+        }
+        walk_expr(self, expr);
+    }
+}
+
+/// Checks that a callable's body can be inlined: it doesn't return early or call itself.
+struct BodyChecker<'a> {
+    compilation: &'a Compilation,
+    item_id: hir::ItemId,
+    inlinable: bool,
+    lambda_depth: usize,
+}
+
+impl<'a> Visitor<'a> for BodyChecker<'a> {
+    fn visit_expr(&mut self, expr: &'a ast::Expr) {
+        match &*expr.kind {
+            ast::ExprKind::Lambda(..) => {
+                self.lambda_depth += 1;
+                walk_expr(self, expr);
+                self.lambda_depth -= 1;
+                return;
+            }
+            ast::ExprKind::Return(_) if self.lambda_depth == 0 => self.inlinable = false,
+            _ => {}
+        }
+        walk_expr(self, expr);
+    }
+
+    fn visit_path(&mut self, path: &'a ast::Path) {
+        if let Some(Res::Item(item_id, _)) = self.compilation.get_res(path.id) {
+            if *item_id == self.item_id {
+                self.inlinable = false;
+            }
+        }
+    }
+}
+
+/// Collects the edits that replace parameters with their arguments.
+struct Substituter<'a> {
+    compilation: &'a Compilation,
+    substitutions: &'a FxHashMap<NodeId, String>,
+    edits: Vec<(Span, String)>,
+}
+
+impl<'a> Visitor<'a> for Substituter<'a> {
+    fn visit_path(&mut self, path: &'a ast::Path) {
+        if let Some((id, span)) = local_of(self.compilation, path) {
+            if let Some(arg) = self.substitutions.get(&id) {
+                self.edits.push((span, arg.clone()));
+            }
+        }
+    }
+}
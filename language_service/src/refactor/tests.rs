@@ -0,0 +1,493 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+#![allow(clippy::needless_raw_string_hashes)]
+
+use super::{extract_callable, inline_callable};
+use crate::{
+    protocol::{CodeAction, TextEdit},
+    test_utils::{compile_with_fake_stdlib_and_markers_no_cursor, compile_with_markers},
+    Encoding,
+};
+use expect_test::{expect, Expect};
+use indoc::indoc;
+
+/// Asserts that the source after applying the extract code action for the selection,
+/// given by a pair of `◉` markers in the source, matches the expected source.
+fn check_extract(source_with_markers: &str, expect: &Expect) {
+    let (compilation, targets) =
+        compile_with_fake_stdlib_and_markers_no_cursor(source_with_markers, false);
+    let selection = targets.first().expect("source should have a selection");
+    let span = compilation.source_range_to_package_span("<source>", *selection, Encoding::Utf8);
+    let action = extract_callable(&compilation, "<source>", span, Encoding::Utf8);
+    expect.assert_eq(&apply(&source_with_markers.replace('◉', ""), action));
+}
+
+/// Asserts that the source after applying the inline code action for the call at
+/// the cursor, given by a `↘` marker in the source, matches the expected source.
+fn check_inline(source_with_markers: &str, expect: &Expect) {
+    let (compilation, cursor, _) = compile_with_markers(source_with_markers, false);
+    let span = compilation.source_range_to_package_span(
+        "<source>",
+        qsc::line_column::Range {
+            start: cursor,
+            end: cursor,
+        },
+        Encoding::Utf8,
+    );
+    let action = inline_callable(&compilation, "<source>", span, Encoding::Utf8);
+    expect.assert_eq(&apply(&source_with_markers.replace('↘', ""), action));
+}
+
+fn apply(source: &str, action: Option<CodeAction>) -> String {
+    let Some(action) = action else {
+        return "no code action".to_string();
+    };
+    let (_, edits) = action
+        .edit
+        .expect("code action should have an edit")
+        .changes
+        .into_iter()
+        .next()
+        .expect("edit should change a source");
+    let offset = |position: qsc::line_column::Position| {
+        source
+            .split_inclusive('\n')
+            .take(position.line as usize)
+            .map(str::len)
+            .sum::<usize>()
+            + position.column as usize
+    };
+    let mut edits: Vec<&TextEdit> = edits.iter().collect();
+    edits.sort_by_key(|edit| std::cmp::Reverse(offset(edit.range.start)));
+    let mut result = source.to_string();
+    for edit in edits {
+        result.replace_range(
+            offset(edit.range.start)..offset(edit.range.end),
+            &edit.new_text,
+        );
+    }
+    format!("{}\n{result}", action.title)
+}
+
+#[test]
+fn extract_operation_with_functors() {
+    check_extract(
+        indoc! {r#"
+        namespace Test {
+            operation Main() : Unit {
+                use (a, b) = (Qubit(), Qubit());
+                ◉H(a);
+                CNOT(a, b);◉
+                ResetAll([a, b]);
+            }
+        }
+        "#},
+        &expect![[r#"
+            Extract to operation
+            namespace Test {
+                operation Main() : Unit {
+                    use (a, b) = (Qubit(), Qubit());
+                    ExtractedOperation(a, b);
+                    ResetAll([a, b]);
+                }
+
+                operation ExtractedOperation(a : Qubit, b : Qubit) : Unit is Adj + Ctl {
+                    H(a);
+                    CNOT(a, b);
+                }
+            }
+        "#]],
+    );
+}
+
+#[test]
+fn extract_operation_returning_measurement() {
+    check_extract(
+        indoc! {r#"
+        namespace Test {
+            operation Main() : Result {
+                use q = Qubit();
+                ◉H(q);
+                let r = M(q);◉
+                Reset(q);
+                r
+            }
+        }
+        "#},
+        &expect![[r#"
+            Extract to operation
+            namespace Test {
+                operation Main() : Result {
+                    use q = Qubit();
+                    let r = ExtractedOperation(q);
+                    Reset(q);
+                    r
+                }
+
+                operation ExtractedOperation(q : Qubit) : Result {
+                    H(q);
+                    let r = M(q);
+                    r
+                }
+            }
+        "#]],
+    );
+}
+
+#[test]
+fn extract_function_from_operation() {
+    check_extract(
+        indoc! {r#"
+        namespace Test {
+            operation Main() : Unit {
+                let n = 3;
+                ◉let angle = 2.0 * IntAsDouble(n);◉
+                use q = Qubit();
+                Rx(angle, q);
+            }
+        }
+        "#},
+        &expect![[r#"
+            Extract to function
+            namespace Test {
+                operation Main() : Unit {
+                    let n = 3;
+                    let angle = ExtractedFunction(n);
+                    use q = Qubit();
+                    Rx(angle, q);
+                }
+
+                function ExtractedFunction(n : Int) : Double {
+                    let angle = 2.0 * IntAsDouble(n);
+                    angle
+                }
+            }
+        "#]],
+    );
+}
+
+#[test]
+fn extract_returns_variables_used_later() {
+    check_extract(
+        indoc! {r#"
+        namespace Test {
+            function F(x : Int) : Int {
+                ◉let y = x * 2;
+                mutable z = y + 1;◉
+                set z += 1;
+                y + z
+            }
+        }
+        "#},
+        &expect![[r#"
+            Extract to function
+            namespace Test {
+                function F(x : Int) : Int {
+                    mutable (y, z) = ExtractedFunction(x);
+                    set z += 1;
+                    y + z
+                }
+
+                function ExtractedFunction(x : Int) : (Int, Int) {
+                    let y = x * 2;
+                    mutable z = y + 1;
+                    (y, z)
+                }
+            }
+        "#]],
+    );
+}
+
+#[test]
+fn extract_trailing_expression() {
+    check_extract(
+        indoc! {r#"
+        namespace Test {
+            function F(x : Int, y : Int) : Int {
+                let z = x * y;
+                ◉z + x◉
+            }
+        }
+        "#},
+        &expect![[r#"
+            Extract to function
+            namespace Test {
+                function F(x : Int, y : Int) : Int {
+                    let z = x * y;
+                    ExtractedFunction(z, x)
+                }
+
+                function ExtractedFunction(z : Int, x : Int) : Int {
+                    z + x
+                }
+            }
+        "#]],
+    );
+}
+
+#[test]
+fn extract_from_nested_block() {
+    check_extract(
+        indoc! {r#"
+        namespace Test {
+            operation ExtractedOperation() : Unit {}
+            operation Main(qs : Qubit[]) : Unit {
+                for q in qs {
+                    ◉X(q);
+                    Z(q);◉
+                }
+            }
+        }
+        "#},
+        &expect![[r#"
+            Extract to operation
+            namespace Test {
+                operation ExtractedOperation() : Unit {}
+                operation Main(qs : Qubit[]) : Unit {
+                    for q in qs {
+                        ExtractedOperation2(q);
+                    }
+                }
+
+                operation ExtractedOperation2(q : Qubit) : Unit is Adj + Ctl {
+                    X(q);
+                    Z(q);
+                }
+            }
+        "#]],
+    );
+}
+
+#[test]
+fn extract_generic_variables() {
+    check_extract(
+        indoc! {r#"
+        namespace Test {
+            function F<'T>(items : 'T[]) : 'T[] {
+                ◉let reversed = items[...-1...];◉
+                reversed
+            }
+        }
+        "#},
+        &expect![[r#"
+            Extract to function
+            namespace Test {
+                function F<'T>(items : 'T[]) : 'T[] {
+                    let reversed = ExtractedFunction(items);
+                    reversed
+                }
+
+                function ExtractedFunction<'T>(items : 'T[]) : 'T[] {
+                    let reversed = items[...-1...];
+                    reversed
+                }
+            }
+        "#]],
+    );
+}
+
+#[test]
+fn no_extract_assignment_to_outer_variable() {
+    check_extract(
+        indoc! {r#"
+        namespace Test {
+            function F() : Int {
+                mutable x = 0;
+                ◉set x = 1;◉
+                x
+            }
+        }
+        "#},
+        &expect!["no code action"],
+    );
+}
+
+#[test]
+fn no_extract_return() {
+    check_extract(
+        indoc! {r#"
+        namespace Test {
+            function F(x : Int) : Int {
+                ◉if x > 0 {
+                    return x;
+                }◉
+                0
+            }
+        }
+        "#},
+        &expect!["no code action"],
+    );
+}
+
+#[test]
+fn no_extract_part_of_statement() {
+    check_extract(
+        indoc! {r#"
+        namespace Test {
+            operation Main() : Unit {
+                use q = Qubit();
+                ◉H(q)◉;
+            }
+        }
+        "#},
+        &expect!["no code action"],
+    );
+}
+
+#[test]
+fn inline_call_statement() {
+    check_inline(
+        indoc! {r#"
+        namespace Test {
+            operation Prepare(q : Qubit) : Unit {
+                H(q);
+                S(q);
+            }
+            operation Main() : Unit {
+                use q = Qubit();
+                ↘Prepare(q);
+                Reset(q);
+            }
+        }
+        "#},
+        &expect![[r#"
+            Inline `Prepare`
+            namespace Test {
+                operation Prepare(q : Qubit) : Unit {
+                    H(q);
+                    S(q);
+                }
+                operation Main() : Unit {
+                    use q = Qubit();
+                    H(q);
+                    S(q);
+                    Reset(q);
+                }
+            }
+        "#]],
+    );
+}
+
+#[test]
+fn inline_call_statement_with_bound_arguments() {
+    check_inline(
+        indoc! {r#"
+        namespace Test {
+            operation Rotate(q : Qubit, theta : Double) : Unit {
+                let half = theta / 2.0;
+                Rx(half, q);
+            }
+            operation Main() : Unit {
+                use q = Qubit();
+                let theta = 1.0;
+                Rot↘ate(q, 2.0 * theta);
+            }
+        }
+        "#},
+        &expect![[r#"
+            Inline `Rotate`
+            namespace Test {
+                operation Rotate(q : Qubit, theta : Double) : Unit {
+                    let half = theta / 2.0;
+                    Rx(half, q);
+                }
+                operation Main() : Unit {
+                    use q = Qubit();
+                    let theta = 1.0;
+                    {
+                        let theta = 2.0 * theta;
+                        let half = theta / 2.0;
+                        Rx(half, q);
+                    }
+                }
+            }
+        "#]],
+    );
+}
+
+#[test]
+fn inline_call_expression() {
+    check_inline(
+        indoc! {r#"
+        namespace Test {
+            function Add(a : Int, b : Int) : Int {
+                a + b
+            }
+            function Main() : Int {
+                let x = 1;
+                ↘Add(x, 2) * 3
+            }
+        }
+        "#},
+        &expect![[r#"
+            Inline `Add`
+            namespace Test {
+                function Add(a : Int, b : Int) : Int {
+                    a + b
+                }
+                function Main() : Int {
+                    let x = 1;
+                    (x + 2) * 3
+                }
+            }
+        "#]],
+    );
+}
+
+#[test]
+fn no_inline_call_expression_with_statements() {
+    check_inline(
+        indoc! {r#"
+        namespace Test {
+            function Double(a : Int) : Int {
+                let b = a * 2;
+                b
+            }
+            function Main() : Int {
+                ↘Double(1) + 1
+            }
+        }
+        "#},
+        &expect!["no code action"],
+    );
+}
+
+#[test]
+fn no_inline_recursive_callable() {
+    check_inline(
+        indoc! {r#"
+        namespace Test {
+            function Count(n : Int) : Unit {
+                if n > 0 {
+                    Count(n - 1);
+                }
+            }
+            function Main() : Unit {
+                ↘Count(3);
+            }
+        }
+        "#},
+        &expect!["no code action"],
+    );
+}
+
+#[test]
+fn no_inline_early_return() {
+    check_inline(
+        indoc! {r#"
+        namespace Test {
+            function Check(n : Int) : Unit {
+                if n > 0 {
+                    return ();
+                }
+                fail "negative";
+            }
+            function Main() : Unit {
+                ↘Check(3);
+            }
+        }
+        "#},
+        &expect!["no code action"],
+    );
+}
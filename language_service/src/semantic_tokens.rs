@@ -0,0 +1,382 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+#[cfg(test)]
+mod tests;
+
+use crate::{
+    compilation::Compilation,
+    protocol::{SemanticToken, SemanticTokenModifier, SemanticTokenType},
+    qsc_utils::into_range,
+};
+use qsc::{
+    ast::{
+        self,
+        visit::{walk_expr, walk_item, walk_pat, walk_stmt, walk_ty, walk_ty_def, Visitor},
+    },
+    display::Lookup,
+    hir::{
+        self,
+        ty::{FunctorSetValue, Prim, Ty},
+        ItemStatus,
+    },
+    line_column::Encoding,
+    resolve::{path_as_field_accessor, Res},
+    Span,
+};
+use rustc_hash::FxHashMap;
+
+pub(crate) fn get_semantic_tokens(
+    compilation: &Compilation,
+    source_name: &str,
+    position_encoding: Encoding,
+) -> Vec<SemanticToken> {
+    let unit = compilation.user_unit();
+    let source_span = compilation.package_span_of_source(source_name);
+
+    let mut classifier = Classifier {
+        compilation,
+        source_span,
+        locals: FxHashMap::default(),
+        in_params: false,
+        in_mutable: false,
+        tokens: Vec::new(),
+    };
+    for node in &unit.ast.package.nodes {
+        match node {
+            ast::TopLevelNode::Namespace(namespace) if source_span.contains(namespace.span.lo) => {
+                classifier.visit_namespace(namespace);
+            }
+            ast::TopLevelNode::Stmt(stmt) if source_span.contains(stmt.span.lo) => {
+                classifier.visit_stmt(stmt);
+            }
+            _ => {}
+        }
+    }
+
+    let mut tokens = classifier.tokens;
+    tokens.sort_by_key(|(span, ..)| span.lo);
+    tokens.dedup_by_key(|(span, ..)| *span);
+    tokens
+        .into_iter()
+        .map(|(span, token_type, modifiers)| SemanticToken {
+            range: into_range(position_encoding, span, &unit.sources),
+            token_type,
+            modifiers,
+        })
+        .collect()
+}
+
+/// How a local variable was declared.
+#[derive(Clone, Copy)]
+struct LocalInfo {
+    token_type: SemanticTokenType,
+    mutable: bool,
+    qubit: bool,
+}
+
+struct Classifier<'a> {
+    compilation: &'a Compilation,
+    source_span: Span,
+    /// The locals declared so far, by the node ID of their name.
+    locals: FxHashMap<ast::NodeId, LocalInfo>,
+    in_params: bool,
+    in_mutable: bool,
+    tokens: Vec<(Span, SemanticTokenType, Vec<SemanticTokenModifier>)>,
+}
+
+impl Classifier<'_> {
+    fn push(
+        &mut self,
+        ident: &ast::Ident,
+        token_type: SemanticTokenType,
+        modifiers: Vec<SemanticTokenModifier>,
+    ) {
+        // Skip identifiers that don't match the source text, such as the
+        // names of implicit namespaces and nodes created during error recovery.
+        let span = ident.span;
+        if self.source_span.contains(span.lo)
+            && (span.hi - span.lo) as usize == ident.name.len()
+            && !ident.name.is_empty()
+        {
+            self.tokens.push((span, token_type, modifiers));
+        }
+    }
+
+    fn push_namespace(&mut self, idents: &ast::Idents) {
+        for ident in idents {
+            self.push(ident, SemanticTokenType::Namespace, Vec::new());
+        }
+    }
+
+    fn push_local(&mut self, ident: &ast::Ident, local: LocalInfo, is_declaration: bool) {
+        let mut modifiers = Vec::new();
+        if is_declaration {
+            modifiers.push(SemanticTokenModifier::Declaration);
+        }
+        modifiers.push(if local.mutable {
+            SemanticTokenModifier::Mutable
+        } else {
+            SemanticTokenModifier::Readonly
+        });
+        if local.qubit {
+            modifiers.push(SemanticTokenModifier::Qubit);
+        }
+        self.push(ident, local.token_type, modifiers);
+    }
+
+    /// Classifies a reference to, or the declaration of, the global item that `res_id` resolves to.
+    fn push_item(&mut self, ident: &ast::Ident, res_id: ast::NodeId, is_declaration: bool) {
+        let Some(item_id) = self.compilation.get_res(res_id).and_then(Res::item_id) else {
+            return;
+        };
+        let (item, ..) = self
+            .compilation
+            .resolve_item_relative_to_user_package(&item_id);
+
+        let mut modifiers = Vec::new();
+        if is_declaration {
+            modifiers.push(SemanticTokenModifier::Declaration);
+        }
+        if ItemStatus::from_attrs(&item.attrs) == ItemStatus::Unimplemented {
+            modifiers.push(SemanticTokenModifier::Deprecated);
+        }
+        let token_type = match &item.kind {
+            hir::ItemKind::Callable(decl) => {
+                if matches!(
+                    decl.functors,
+                    FunctorSetValue::Adj | FunctorSetValue::CtlAdj
+                ) {
+                    modifiers.push(SemanticTokenModifier::Adjointable);
+                }
+                if matches!(
+                    decl.functors,
+                    FunctorSetValue::Ctl | FunctorSetValue::CtlAdj
+                ) {
+                    modifiers.push(SemanticTokenModifier::Controllable);
+                }
+                match decl.kind {
+                    hir::CallableKind::Function => SemanticTokenType::Function,
+                    hir::CallableKind::Operation => SemanticTokenType::Operation,
+                }
+            }
+            hir::ItemKind::Ty(..) => SemanticTokenType::Struct,
+            hir::ItemKind::Namespace(..) => SemanticTokenType::Namespace,
+            hir::ItemKind::Export(..) => return,
+        };
+        self.push(ident, token_type, modifiers);
+    }
+
+    fn local_info(&self, local_id: ast::NodeId) -> LocalInfo {
+        self.locals.get(&local_id).copied().unwrap_or(LocalInfo {
+            token_type: SemanticTokenType::Variable,
+            mutable: false,
+            qubit: false,
+        })
+    }
+
+    fn push_field(&mut self, ident: &ast::Ident, is_declaration: bool) {
+        let modifiers = if is_declaration {
+            vec![SemanticTokenModifier::Declaration]
+        } else {
+            Vec::new()
+        };
+        self.push(ident, SemanticTokenType::Property, modifiers);
+    }
+}
+
+impl<'a> Visitor<'a> for Classifier<'a> {
+    fn visit_namespace(&mut self, namespace: &'a ast::Namespace) {
+        self.push_namespace(&namespace.name);
+        namespace
+            .items
+            .iter()
+            .for_each(|item| self.visit_item(item));
+    }
+
+    fn visit_item(&mut self, item: &'a ast::Item) {
+        match &*item.kind {
+            ast::ItemKind::Open(namespace, alias) => {
+                self.push_namespace(namespace);
+                if let Some(alias) = alias {
+                    self.push(
+                        alias,
+                        SemanticTokenType::Namespace,
+                        vec![SemanticTokenModifier::Declaration],
+                    );
+                }
+            }
+            ast::ItemKind::Ty(name, def) => {
+                self.push_item(name, name.id, true);
+                self.visit_ty_def(def);
+            }
+            ast::ItemKind::ImportOrExport(decl) => {
+                for item in &decl.items {
+                    if self.compilation.get_res(item.path.id).is_some() {
+                        self.visit_path(&item.path);
+                        if let Some(alias) = &item.alias {
+                            self.push_item(alias, item.path.id, true);
+                        }
+                    } else {
+                        // Namespaces, including the namespaces of glob imports, are not resolved.
+                        let idents: Vec<ast::Ident> = (&item.path).into();
+                        self.push_namespace(&idents.into());
+                        if let Some(alias) = &item.alias {
+                            self.push(
+                                alias,
+                                SemanticTokenType::Namespace,
+                                vec![SemanticTokenModifier::Declaration],
+                            );
+                        }
+                    }
+                }
+            }
+            _ => walk_item(self, item),
+        }
+    }
+
+    fn visit_callable_decl(&mut self, decl: &'a ast::CallableDecl) {
+        self.push_item(&decl.name, decl.name.id, true);
+        for param in &decl.generics {
+            self.push(
+                param,
+                SemanticTokenType::TypeParameter,
+                vec![SemanticTokenModifier::Declaration],
+            );
+        }
+        self.in_params = true;
+        self.visit_pat(&decl.input);
+        self.in_params = false;
+        self.visit_ty(&decl.output);
+        match &*decl.body {
+            ast::CallableBody::Block(block) => self.visit_block(block),
+            ast::CallableBody::Specs(specs) => {
+                specs.iter().for_each(|spec| self.visit_spec_decl(spec));
+            }
+        }
+    }
+
+    fn visit_spec_decl(&mut self, decl: &'a ast::SpecDecl) {
+        if let ast::SpecBody::Impl(input, block) = &decl.body {
+            self.in_params = true;
+            self.visit_pat(input);
+            self.in_params = false;
+            self.visit_block(block);
+        }
+    }
+
+    fn visit_struct_decl(&mut self, decl: &'a ast::StructDecl) {
+        self.push_item(&decl.name, decl.name.id, true);
+        for field in &decl.fields {
+            self.push_field(&field.name, true);
+            self.visit_ty(&field.ty);
+        }
+    }
+
+    fn visit_ty_def(&mut self, def: &'a ast::TyDef) {
+        if let ast::TyDefKind::Field(Some(name), ty) = &*def.kind {
+            self.push_field(name, true);
+            self.visit_ty(ty);
+        } else {
+            walk_ty_def(self, def);
+        }
+    }
+
+    fn visit_ty(&mut self, ty: &'a ast::Ty) {
+        if let ast::TyKind::Param(name) = &*ty.kind {
+            self.push(name, SemanticTokenType::TypeParameter, Vec::new());
+        } else {
+            walk_ty(self, ty);
+        }
+    }
+
+    fn visit_stmt(&mut self, stmt: &'a ast::Stmt) {
+        if let ast::StmtKind::Local(mutability, pat, value) = &*stmt.kind {
+            self.in_mutable = *mutability == ast::Mutability::Mutable;
+            self.visit_pat(pat);
+            self.in_mutable = false;
+            self.visit_expr(value);
+        } else {
+            walk_stmt(self, stmt);
+        }
+    }
+
+    fn visit_pat(&mut self, pat: &'a ast::Pat) {
+        if let ast::PatKind::Bind(name, ty) = &*pat.kind {
+            let local = LocalInfo {
+                token_type: if self.in_params {
+                    SemanticTokenType::Parameter
+                } else {
+                    SemanticTokenType::Variable
+                },
+                mutable: self.in_mutable,
+                qubit: self.compilation.get_ty(pat.id).is_some_and(is_qubit),
+            };
+            self.locals.insert(name.id, local);
+            self.push_local(name, local, true);
+            if let Some(ty) = ty {
+                self.visit_ty(ty);
+            }
+        } else {
+            walk_pat(self, pat);
+        }
+    }
+
+    fn visit_expr(&mut self, expr: &'a ast::Expr) {
+        match &*expr.kind {
+            ast::ExprKind::Field(record, name) => {
+                self.visit_expr(record);
+                self.push_field(name, false);
+            }
+            ast::ExprKind::Lambda(_, input, body) => {
+                self.in_params = true;
+                self.visit_pat(input);
+                self.in_params = false;
+                self.visit_expr(body);
+            }
+            _ => walk_expr(self, expr),
+        }
+    }
+
+    fn visit_field_assign(&mut self, assign: &'a ast::FieldAssign) {
+        self.push_field(&assign.field, false);
+        self.visit_expr(&assign.value);
+    }
+
+    fn visit_path(&mut self, path: &'a ast::Path) {
+        let names = &self.compilation.user_unit().ast.names;
+        if let Some((local_id, parts)) = path_as_field_accessor(names, path) {
+            let (local, fields) = parts.split_first().expect("path should have a first part");
+            self.push_local(local, self.local_info(local_id), false);
+            for field in fields {
+                self.push_field(field, false);
+            }
+            return;
+        }
+
+        if let Some(segments) = &path.segments {
+            self.push_namespace(segments);
+        }
+        match self.compilation.get_res(path.id) {
+            Some(Res::Local(local_id)) => {
+                let local = self.local_info(*local_id);
+                self.push_local(&path.name, local, false);
+            }
+            Some(Res::Item(..) | Res::ExportedItem(..)) => {
+                self.push_item(&path.name, path.id, false);
+            }
+            Some(Res::Param(_)) => {
+                self.push(&path.name, SemanticTokenType::TypeParameter, Vec::new());
+            }
+            _ => {}
+        }
+    }
+}
+
+fn is_qubit(ty: &Ty) -> bool {
+    match ty {
+        Ty::Prim(Prim::Qubit) => true,
+        Ty::Array(item) => is_qubit(item),
+        _ => false,
+    }
+}
@@ -0,0 +1,228 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+#![allow(clippy::needless_raw_string_hashes)]
+
+use super::get_semantic_tokens;
+use crate::{
+    protocol::SemanticToken,
+    test_utils::{
+        compile_notebook_with_fake_stdlib, compile_with_fake_stdlib_and_markers_no_cursor,
+    },
+    Encoding,
+};
+use expect_test::{expect, Expect};
+use std::fmt::Write;
+
+fn render(source: &str, tokens: &[SemanticToken]) -> String {
+    let lines: Vec<&str> = source.lines().collect();
+    let mut actual = String::new();
+    for token in tokens {
+        let start = token.range.start;
+        let text =
+            &lines[start.line as usize][start.column as usize..token.range.end.column as usize];
+        writeln!(
+            actual,
+            "{}:{} {text} {:?} {:?}",
+            start.line, start.column, token.token_type, token.modifiers
+        )
+        .expect("writing to string should succeed");
+    }
+    actual
+}
+
+fn check(source: &str, expect: &Expect) {
+    let (compilation, _) = compile_with_fake_stdlib_and_markers_no_cursor(source, true);
+    let tokens = get_semantic_tokens(&compilation, "<source>", Encoding::Utf8);
+    expect.assert_eq(&render(source, &tokens));
+}
+
+#[test]
+fn callables() {
+    check(
+        r#"
+namespace Test {
+    open FakeStdLib;
+    function Add(a : Int, b : Int) : Int { a + b }
+    operation Main() : Unit is Adj + Ctl {
+        Fake();
+        FakeCtlAdj();
+        let x = Add(1, 2);
+    }
+}"#,
+        &expect![[r#"
+            1:10 Test Namespace []
+            2:9 FakeStdLib Namespace []
+            3:13 Add Function [Declaration]
+            3:17 a Parameter [Declaration, Readonly]
+            3:26 b Parameter [Declaration, Readonly]
+            3:43 a Parameter [Readonly]
+            3:47 b Parameter [Readonly]
+            4:14 Main Operation [Declaration, Adjointable, Controllable]
+            5:8 Fake Operation []
+            6:8 FakeCtlAdj Operation [Adjointable, Controllable]
+            7:12 x Variable [Declaration, Readonly]
+            7:16 Add Function []
+        "#]],
+    );
+}
+
+#[test]
+fn locals() {
+    check(
+        r#"
+namespace Test {
+    operation Main() : Unit {
+        use q = Qubit();
+        use qs = Qubit[2];
+        mutable count = 0;
+        let (a, b) = (1, 2);
+        set count += a + b;
+        for i in 0..count {}
+        let f = (x, y) -> x + y;
+        H(q);
+    }
+    operation H(target : Qubit) : Unit {}
+}"#,
+        &expect![[r#"
+            1:10 Test Namespace []
+            2:14 Main Operation [Declaration]
+            3:12 q Variable [Declaration, Readonly, Qubit]
+            4:12 qs Variable [Declaration, Readonly, Qubit]
+            5:16 count Variable [Declaration, Mutable]
+            6:13 a Variable [Declaration, Readonly]
+            6:16 b Variable [Declaration, Readonly]
+            7:12 count Variable [Mutable]
+            7:21 a Variable [Readonly]
+            7:25 b Variable [Readonly]
+            8:12 i Variable [Declaration, Readonly]
+            8:20 count Variable [Mutable]
+            9:12 f Variable [Declaration, Readonly]
+            9:17 x Parameter [Declaration, Readonly]
+            9:20 y Parameter [Declaration, Readonly]
+            9:26 x Parameter [Readonly]
+            9:30 y Parameter [Readonly]
+            10:8 H Operation []
+            10:10 q Variable [Readonly, Qubit]
+            12:14 H Operation [Declaration]
+            12:16 target Parameter [Declaration, Readonly, Qubit]
+        "#]],
+    );
+}
+
+#[test]
+fn types_and_fields() {
+    check(
+        r#"
+namespace Test {
+    newtype Pair = (First : Int, Second : Int);
+    struct Point { X : Double, Y : Double }
+    function Identity<'T>(value : 'T) : 'T { value }
+    function Make() : Point {
+        let pair = Pair(1, 2);
+        let p = new Point { X = 1.0, Y = 2.0 };
+        let x = p.X;
+        let first = pair::First;
+        new Point { ...p, Y = p.Y + x }
+    }
+}"#,
+        &expect![[r#"
+            1:10 Test Namespace []
+            2:12 Pair Struct [Declaration]
+            2:20 First Property [Declaration]
+            2:33 Second Property [Declaration]
+            3:11 Point Struct [Declaration]
+            3:19 X Property [Declaration]
+            3:31 Y Property [Declaration]
+            4:13 Identity Function [Declaration]
+            4:22 'T TypeParameter [Declaration]
+            4:26 value Parameter [Declaration, Readonly]
+            4:34 'T TypeParameter []
+            4:40 'T TypeParameter []
+            4:45 value Parameter [Readonly]
+            5:13 Make Function [Declaration]
+            5:22 Point Struct []
+            6:12 pair Variable [Declaration, Readonly]
+            6:19 Pair Struct []
+            7:12 p Variable [Declaration, Readonly]
+            7:20 Point Struct []
+            7:28 X Property []
+            7:37 Y Property []
+            8:12 x Variable [Declaration, Readonly]
+            8:16 p Variable [Readonly]
+            8:18 X Property []
+            9:12 first Variable [Declaration, Readonly]
+            9:20 pair Variable [Readonly]
+            9:26 First Property []
+            10:12 Point Struct []
+            10:23 p Variable [Readonly]
+            10:26 Y Property []
+            10:30 p Variable [Readonly]
+            10:32 Y Property []
+            10:36 x Variable [Readonly]
+        "#]],
+    );
+}
+
+#[test]
+fn namespaces_and_imports() {
+    check(
+        r#"
+namespace Test {
+    import FakeStdLib.Fake as F;
+    import FakeStdLib.*;
+    open FakeStdLib as Std;
+    operation Main() : Unit {
+        FakeStdLib.FakeWithParam(1);
+        Std.Fake();
+        F();
+    }
+}"#,
+        &expect![[r#"
+            1:10 Test Namespace []
+            2:11 FakeStdLib Namespace []
+            2:22 Fake Operation []
+            2:30 F Operation [Declaration]
+            3:11 FakeStdLib Namespace []
+            4:9 FakeStdLib Namespace []
+            4:23 Std Namespace [Declaration]
+            5:14 Main Operation [Declaration]
+            6:8 FakeStdLib Namespace []
+            6:19 FakeWithParam Operation []
+            7:8 Std Namespace []
+            7:12 Fake Operation []
+            8:8 F Operation []
+        "#]],
+    );
+}
+
+#[test]
+fn unimplemented_is_deprecated() {
+    check(
+        r#"
+namespace Test {
+    @Unimplemented()
+    operation Old() : Unit {}
+    operation Main() : Unit {}
+}"#,
+        &expect![[r#"
+            1:10 Test Namespace []
+            3:14 Old Operation [Declaration, Deprecated]
+            4:14 Main Operation [Declaration]
+        "#]],
+    );
+}
+
+#[test]
+fn notebook_cells() {
+    let cell = "operation Bar() : Unit { Foo(); }";
+    let compilation = compile_notebook_with_fake_stdlib(
+        [("cell1", "operation Foo() : Unit {}"), ("cell2", cell)].into_iter(),
+    );
+    let tokens = get_semantic_tokens(&compilation, "cell2", Encoding::Utf8);
+    expect![[r#"
+        0:10 Bar Operation [Declaration]
+        0:25 Foo Operation []
+    "#]]
+    .assert_eq(&render(cell, &tokens));
+}
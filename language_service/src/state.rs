@@ -10,9 +10,9 @@ use crate::protocol::{ErrorKind, WorkspaceConfigurationUpdate};
 use log::{debug, trace};
 use miette::Diagnostic;
 use qsc::{compile, project};
-use qsc::{target::Profile, LanguageFeatures, PackageType};
+use qsc::{target::Profile, LanguageFeatures, PackageType, TargetCapabilityFlags};
 use qsc_linter::LintConfig;
-use qsc_project::{FileSystemAsync, JSProjectHost, PackageCache, Project};
+use qsc_project::{FileSystemAsync, FormatterConfig, JSProjectHost, PackageCache, Project};
 use rustc_hash::{FxHashMap, FxHashSet};
 use std::path::PathBuf;
 use std::{cell::RefCell, fmt::Debug, mem::take, rc::Rc, sync::Arc};
@@ -59,7 +59,7 @@ struct OpenDocument {
 
 #[derive(Debug, Clone)]
 struct Configuration {
-    pub target_profile: Profile,
+    pub capabilities: TargetCapabilityFlags,
     pub package_type: PackageType,
     pub language_features: LanguageFeatures,
     pub lints_config: Vec<LintConfig>,
@@ -68,7 +68,7 @@ struct Configuration {
 impl Default for Configuration {
     fn default() -> Self {
         Self {
-            target_profile: Profile::Unrestricted,
+            capabilities: Profile::Unrestricted.into(),
             package_type: PackageType::Lib,
             language_features: LanguageFeatures::default(),
             lints_config: Vec::default(),
@@ -78,10 +78,13 @@ impl Default for Configuration {
 
 #[derive(Default, Clone, Debug)]
 pub struct PartialConfiguration {
-    pub target_profile: Option<Profile>,
+    /// The capabilities of the target, from the target named by the project manifest or the
+    /// target profile.
+    pub capabilities: Option<TargetCapabilityFlags>,
     pub package_type: Option<PackageType>,
     pub language_features: Option<LanguageFeatures>,
     pub lints_config: Vec<LintConfig>,
+    pub format_config: FormatterConfig,
 }
 
 pub(super) struct CompilationStateUpdater<'a> {
@@ -231,10 +234,15 @@ impl<'a> CompilationStateUpdater<'a> {
             }
 
             let compilation_overrides = PartialConfiguration {
+                capabilities: loaded_project
+                    .target
+                    .as_ref()
+                    .map(|target| target.capabilities),
                 language_features: Some(
                     loaded_project.package_graph_sources.root.language_features,
                 ),
                 lints_config: loaded_project.lints,
+                format_config: loaded_project.format,
                 package_type: loaded_project.package_graph_sources.root.package_type.map(
                     |x| match x {
                         qsc_project::PackageType::Exe => qsc::PackageType::Exe,
@@ -248,7 +256,7 @@ impl<'a> CompilationStateUpdater<'a> {
 
             let compilation = Compilation::new(
                 configuration.package_type,
-                configuration.target_profile,
+                configuration.capabilities,
                 configuration.language_features,
                 &configuration.lints_config,
                 loaded_project.package_graph_sources,
@@ -336,14 +344,23 @@ impl<'a> CompilationStateUpdater<'a> {
                 .open_documents
                 .retain(|_, open_doc| notebook_uri != open_doc.compilation.as_ref());
 
+            let (lints_config, format_config) = notebook_metadata
+                .manifest
+                .map(|manifest| (manifest.lints, manifest.format))
+                .unwrap_or_default();
+            // The target named by the manifest of the notebook's project takes precedence over
+            // the target profile, as it does in the interpreter.
+            let capabilities = project
+                .as_ref()
+                .and_then(|project| project.target.as_ref())
+                .map(|target| target.capabilities)
+                .or(notebook_metadata.target_profile.map(Into::into));
             let notebook_configuration = PartialConfiguration {
-                target_profile: notebook_metadata.target_profile,
+                capabilities,
                 package_type: None,
                 language_features: Some(notebook_metadata.language_features),
-                lints_config: notebook_metadata
-                    .manifest
-                    .map(|manifest| manifest.lints)
-                    .unwrap_or_default(),
+                lints_config,
+                format_config,
             };
             let configuration = merge_configurations(&notebook_configuration, &configuration);
 
@@ -361,7 +378,7 @@ impl<'a> CompilationStateUpdater<'a> {
                     );
                     (Arc::from(cell_uri), Arc::from(cell_contents))
                 }),
-                configuration.target_profile,
+                configuration.capabilities,
                 configuration.language_features,
                 &configuration.lints_config,
                 project,
@@ -464,8 +481,9 @@ impl<'a> CompilationStateUpdater<'a> {
         }
 
         if let Some(target_profile) = configuration.target_profile {
-            need_recompile |= self.configuration.target_profile != target_profile;
-            self.configuration.target_profile = target_profile;
+            let capabilities = target_profile.into();
+            need_recompile |= self.configuration.capabilities != capabilities;
+            self.configuration.capabilities = capabilities;
         }
 
         if let Some(language_features) = configuration.language_features {
@@ -496,7 +514,7 @@ impl<'a> CompilationStateUpdater<'a> {
                 let lints_config = package_specific_configuration.lints_config.clone();
                 compilation.recompile(
                     configuration.package_type,
-                    configuration.target_profile,
+                    configuration.capabilities,
                     configuration.language_features,
                     &lints_config,
                 );
@@ -536,6 +554,15 @@ impl<'a> CompilationStateUpdater<'a> {
 }
 
 impl CompilationState {
+    /// Returns the formatter configuration of the compilation the document belongs to.
+    pub(crate) fn get_format_config(&self, uri: &str) -> FormatterConfig {
+        self.open_documents
+            .get(uri)
+            .and_then(|doc| self.compilations.get(&doc.compilation))
+            .map(|(_, overrides)| overrides.format_config)
+            .unwrap_or_default()
+    }
+
     pub(crate) fn get_compilation(&self, uri: &str) -> Option<&Compilation> {
         let compilation_uri = &self
             .open_documents
@@ -549,6 +576,12 @@ impl CompilationState {
             panic!("document associated with compilation that hasn't been initialized ({compilation_uri})" ,)
         }).0)
     }
+
+    pub(crate) fn compilations(&self) -> impl Iterator<Item = &Compilation> {
+        self.compilations
+            .values()
+            .map(|(compilation, _)| compilation)
+    }
 }
 
 fn map_errors_to_docs(
@@ -607,9 +640,9 @@ fn merge_configurations(
     merged_lints.extend(override_lints);
 
     Configuration {
-        target_profile: compilation_overrides
-            .target_profile
-            .unwrap_or(workspace_scope.target_profile),
+        capabilities: compilation_overrides
+            .capabilities
+            .unwrap_or(workspace_scope.capabilities),
         package_type: compilation_overrides
             .package_type
             .unwrap_or(workspace_scope.package_type),
@@ -375,22 +375,71 @@ async fn rca_errors_are_reported_when_compilation_succeeds() {
                     [
                         Pass(
                             CapabilitiesCk(
-                                UseOfDynamicDouble(
-                                    Span {
-                                        lo: 106,
-                                        hi: 117,
-                                    },
-                                ),
+                                ExplainedError {
+                                    error: UseOfDynamicDouble(
+                                        Span {
+                                            lo: 106,
+                                            hi: 117,
+                                        },
+                                    ),
+                                    provenance: [
+                                        Reason {
+                                            span: Span {
+                                                lo: 86,
+                                                hi: 103,
+                                            },
+                                            kind: Condition,
+                                        },
+                                        Reason {
+                                            span: Span {
+                                                lo: 86,
+                                                hi: 96,
+                                            },
+                                            kind: Measurement(
+                                                "MResetZ",
+                                            ),
+                                        },
+                                    ],
+                                },
                             ),
                         ),
                         Pass(
                             CapabilitiesCk(
-                                UseOfDynamicDouble(
-                                    Span {
-                                        lo: 121,
-                                        hi: 122,
-                                    },
-                                ),
+                                ExplainedError {
+                                    error: UseOfDynamicDouble(
+                                        Span {
+                                            lo: 121,
+                                            hi: 122,
+                                        },
+                                    ),
+                                    provenance: [
+                                        Reason {
+                                            span: Span {
+                                                lo: 106,
+                                                hi: 117,
+                                            },
+                                            kind: Assignment(
+                                                "x",
+                                            ),
+                                        },
+                                        Reason {
+                                            span: Span {
+                                                lo: 86,
+                                                hi: 103,
+                                            },
+                                            kind: Condition,
+                                        },
+                                        Reason {
+                                            span: Span {
+                                                lo: 86,
+                                                hi: 96,
+                                            },
+                                            kind: Measurement(
+                                                "MResetZ",
+                                            ),
+                                        },
+                                    ],
+                                },
                             ),
                         ),
                     ],
@@ -429,32 +478,94 @@ async fn base_profile_rca_errors_are_reported_when_compilation_succeeds() {
                     [
                         Pass(
                             CapabilitiesCk(
-                                UseOfDynamicBool(
-                                    Span {
-                                        lo: 86,
-                                        hi: 103,
-                                    },
-                                ),
+                                ExplainedError {
+                                    error: UseOfDynamicBool(
+                                        Span {
+                                            lo: 86,
+                                            hi: 103,
+                                        },
+                                    ),
+                                    provenance: [
+                                        Reason {
+                                            span: Span {
+                                                lo: 86,
+                                                hi: 96,
+                                            },
+                                            kind: Measurement(
+                                                "MResetZ",
+                                            ),
+                                        },
+                                    ],
+                                },
                             ),
                         ),
                         Pass(
                             CapabilitiesCk(
-                                UseOfDynamicDouble(
-                                    Span {
-                                        lo: 106,
-                                        hi: 117,
-                                    },
-                                ),
+                                ExplainedError {
+                                    error: UseOfDynamicDouble(
+                                        Span {
+                                            lo: 106,
+                                            hi: 117,
+                                        },
+                                    ),
+                                    provenance: [
+                                        Reason {
+                                            span: Span {
+                                                lo: 86,
+                                                hi: 103,
+                                            },
+                                            kind: Condition,
+                                        },
+                                        Reason {
+                                            span: Span {
+                                                lo: 86,
+                                                hi: 96,
+                                            },
+                                            kind: Measurement(
+                                                "MResetZ",
+                                            ),
+                                        },
+                                    ],
+                                },
                             ),
                         ),
                         Pass(
                             CapabilitiesCk(
-                                UseOfDynamicDouble(
-                                    Span {
-                                        lo: 121,
-                                        hi: 122,
-                                    },
-                                ),
+                                ExplainedError {
+                                    error: UseOfDynamicDouble(
+                                        Span {
+                                            lo: 121,
+                                            hi: 122,
+                                        },
+                                    ),
+                                    provenance: [
+                                        Reason {
+                                            span: Span {
+                                                lo: 106,
+                                                hi: 117,
+                                            },
+                                            kind: Assignment(
+                                                "x",
+                                            ),
+                                        },
+                                        Reason {
+                                            span: Span {
+                                                lo: 86,
+                                                hi: 103,
+                                            },
+                                            kind: Condition,
+                                        },
+                                        Reason {
+                                            span: Span {
+                                                lo: 86,
+                                                hi: 96,
+                                            },
+                                            kind: Measurement(
+                                                "MResetZ",
+                                            ),
+                                        },
+                                    ],
+                                },
                             ),
                         ),
                     ],
@@ -549,12 +660,25 @@ async fn target_profile_update_fixes_error() {
                     [
                         Pass(
                             CapabilitiesCk(
-                                UseOfDynamicBool(
-                                    Span {
-                                        lo: 62,
-                                        hi: 74,
-                                    },
-                                ),
+                                ExplainedError {
+                                    error: UseOfDynamicBool(
+                                        Span {
+                                            lo: 62,
+                                            hi: 74,
+                                        },
+                                    ),
+                                    provenance: [
+                                        Reason {
+                                            span: Span {
+                                                lo: 62,
+                                                hi: 66,
+                                            },
+                                            kind: Measurement(
+                                                "M",
+                                            ),
+                                        },
+                                    ],
+                                },
                             ),
                         ),
                     ],
@@ -621,12 +745,43 @@ async fn target_profile_update_causes_error_in_stdlib() {
                     [
                         Pass(
                             CapabilitiesCk(
-                                UseOfDynamicBool(
-                                    Span {
-                                        lo: 95,
-                                        hi: 136,
-                                    },
-                                ),
+                                ExplainedError {
+                                    error: UseOfDynamicBool(
+                                        Span {
+                                            lo: 95,
+                                            hi: 136,
+                                        },
+                                    ),
+                                    provenance: [
+                                        Reason {
+                                            span: Span {
+                                                lo: 134,
+                                                hi: 135,
+                                            },
+                                            kind: Argument(
+                                                "ResultAsBool",
+                                            ),
+                                        },
+                                        Reason {
+                                            span: Span {
+                                                lo: 77,
+                                                hi: 78,
+                                            },
+                                            kind: Binding(
+                                                "r",
+                                            ),
+                                        },
+                                        Reason {
+                                            span: Span {
+                                                lo: 81,
+                                                hi: 85,
+                                            },
+                                            kind: Measurement(
+                                                "M",
+                                            ),
+                                        },
+                                    ],
+                                },
                             ),
                         ),
                     ],
@@ -2079,6 +2234,100 @@ async fn lints_prefer_manifest_over_workspace() {
     assert_eq!(received_errors.borrow().len(), 0);
 }
 
+#[tokio::test]
+async fn target_from_manifest_is_preferred_over_workspace() {
+    let this_file_qs = "namespace Foo { @EntryPoint() operation Main() : Int { use q = Qubit(); if MResetZ(q) == One { 1 } else { 0 } } }";
+    let fs = FsNode::Dir(
+        [dir(
+            "project",
+            [
+                file("qsharp.json", r#"{ "target": "target.json" }"#),
+                file(
+                    "target.json",
+                    r#"{ "capabilities": ["Adaptive", "QubitReset"] }"#,
+                ),
+                dir("src", [file("this_file.qs", this_file_qs)]),
+            ],
+        )]
+        .into_iter()
+        .collect(),
+    );
+
+    let fs = Rc::new(RefCell::new(fs));
+    let received_errors = RefCell::new(Vec::new());
+    let mut updater = new_updater_with_file_system(&received_errors, &fs);
+    updater.update_configuration(WorkspaceConfigurationUpdate {
+        target_profile: Some(Profile::Unrestricted),
+        ..WorkspaceConfigurationUpdate::default()
+    });
+
+    // Trigger a document update.
+    updater
+        .update_document("project/src/this_file.qs", 1, this_file_qs)
+        .await;
+
+    // The target doesn't support integer computations, so the dynamic integer is an error.
+    expect_errors(
+        &received_errors,
+        &expect![[r#"
+        [
+            (
+                "project/src/this_file.qs",
+                Some(
+                    1,
+                ),
+                [
+                    Pass(
+                        CapabilitiesCk(
+                            ExplainedError {
+                                error: UseOfIntOutput(
+                                    Span {
+                                        lo: 40,
+                                        hi: 44,
+                                    },
+                                ),
+                                provenance: [],
+                            },
+                        ),
+                    ),
+                    Pass(
+                        CapabilitiesCk(
+                            ExplainedError {
+                                error: UseOfDynamicInt(
+                                    Span {
+                                        lo: 72,
+                                        hi: 109,
+                                    },
+                                ),
+                                provenance: [
+                                    Reason {
+                                        span: Span {
+                                            lo: 75,
+                                            hi: 92,
+                                        },
+                                        kind: Condition,
+                                    },
+                                    Reason {
+                                        span: Span {
+                                            lo: 75,
+                                            hi: 85,
+                                        },
+                                        kind: Measurement(
+                                            "MResetZ",
+                                        ),
+                                    },
+                                ],
+                            },
+                        ),
+                    ),
+                ],
+                [],
+            ),
+        ]
+    "#]],
+    );
+}
+
 #[tokio::test]
 async fn missing_dependency_reported() {
     let fs = FsNode::Dir(
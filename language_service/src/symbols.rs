@@ -0,0 +1,242 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+#[cfg(test)]
+mod tests;
+
+use crate::{
+    compilation::Compilation,
+    protocol::{DocumentSymbol, SymbolKind, WorkspaceSymbol},
+    qsc_utils::{into_location, into_range},
+};
+use qsc::{
+    display::CodeDisplay,
+    hir::{
+        ty::{UdtDef, UdtDefKind},
+        CallableKind, Item, ItemKind, Package, StmtKind, Visibility,
+    },
+    line_column::Encoding,
+    SourceMap,
+};
+
+pub(crate) fn get_document_symbols(
+    compilation: &Compilation,
+    source_name: &str,
+    position_encoding: Encoding,
+) -> Vec<DocumentSymbol> {
+    let user_unit = compilation.user_unit();
+    let package = &user_unit.package;
+    let source_span = compilation.package_span_of_source(source_name);
+    let display = CodeDisplay { compilation };
+    let in_source = |item: &&Item| source_span.contains(item.span.lo);
+
+    // Items declared outside of any namespace, as in notebooks, are top-level statements.
+    let top_level_items = package.stmts.iter().filter_map(|stmt| match stmt.kind {
+        StmtKind::Item(id) => package.items.get(id),
+        _ => None,
+    });
+    let namespaces = package
+        .items
+        .values()
+        .filter(|item| matches!(item.kind, ItemKind::Namespace(..)));
+    let mut items = top_level_items
+        .chain(namespaces)
+        .filter(in_source)
+        .collect::<Vec<_>>();
+    items.sort_by_key(|item| item.span.lo);
+
+    items
+        .into_iter()
+        .filter_map(|item| {
+            let ItemKind::Namespace(name, _) = &item.kind else {
+                return item_document_symbol(item, &display, &user_unit.sources, position_encoding);
+            };
+
+            let mut members = package
+                .items
+                .values()
+                .filter(|member| member.parent == Some(item.id))
+                .filter(in_source)
+                .collect::<Vec<_>>();
+            members.sort_by_key(|member| member.span.lo);
+
+            Some(DocumentSymbol {
+                name: name.name().to_string(),
+                detail: None,
+                kind: SymbolKind::Namespace,
+                range: into_range(position_encoding, item.span, &user_unit.sources),
+                selection_range: into_range(position_encoding, name.span(), &user_unit.sources),
+                children: members
+                    .into_iter()
+                    .filter_map(|member| {
+                        item_document_symbol(
+                            member,
+                            &display,
+                            &user_unit.sources,
+                            position_encoding,
+                        )
+                    })
+                    .collect(),
+            })
+        })
+        .collect()
+}
+
+fn item_document_symbol(
+    item: &Item,
+    display: &CodeDisplay,
+    sources: &SourceMap,
+    position_encoding: Encoding,
+) -> Option<DocumentSymbol> {
+    let (name, name_span, kind, detail, children) = match &item.kind {
+        ItemKind::Callable(decl) => (
+            &decl.name.name,
+            decl.name.span,
+            callable_symbol_kind(decl.kind),
+            display.hir_callable_decl(decl).to_string(),
+            Vec::new(),
+        ),
+        ItemKind::Ty(ident, udt) => {
+            let mut fields = Vec::new();
+            collect_fields(&udt.definition, sources, position_encoding, &mut fields);
+            (
+                &ident.name,
+                ident.span,
+                SymbolKind::Struct,
+                display.hir_udt(udt).to_string(),
+                fields,
+            )
+        }
+        _ => return None,
+    };
+    Some(DocumentSymbol {
+        name: name.to_string(),
+        detail: Some(detail),
+        kind,
+        range: into_range(position_encoding, item.span, sources),
+        selection_range: into_range(position_encoding, name_span, sources),
+        children,
+    })
+}
+
+/// Returns the callables and types of all open compilations, and of the packages they depend on,
+/// whose names fuzzy-match `query`. Only public items are included from dependency packages.
+pub(crate) fn get_workspace_symbols<'a>(
+    compilations: impl Iterator<Item = &'a Compilation>,
+    query: &str,
+    position_encoding: Encoding,
+) -> Vec<WorkspaceSymbol> {
+    let mut symbols = Vec::new();
+    for compilation in compilations {
+        for (package_id, unit) in &compilation.package_store {
+            let is_user_package = package_id == compilation.user_package_id;
+            for item in unit.package.items.values() {
+                if !is_user_package && item.visibility != Visibility::Public {
+                    continue;
+                }
+                let Some((name, name_span, kind)) = item_symbol(item) else {
+                    continue;
+                };
+                let container_name = match parent_namespace(&unit.package, item) {
+                    Some(namespace) => Some(namespace),
+                    None if is_top_level(&unit.package, item) => None,
+                    None => continue,
+                };
+                if !fuzzy_match(query, name) {
+                    continue;
+                }
+                symbols.push(WorkspaceSymbol {
+                    name: name.to_string(),
+                    kind,
+                    container_name,
+                    location: into_location(position_encoding, compilation, name_span, package_id),
+                });
+            }
+        }
+    }
+
+    // The same dependency packages, such as the standard library, are shared by most compilations.
+    symbols.sort_by(|a, b| {
+        (&a.name, &a.container_name, &a.location.source)
+            .cmp(&(&b.name, &b.container_name, &b.location.source))
+            .then_with(|| {
+                let (a, b) = (a.location.range.start, b.location.range.start);
+                (a.line, a.column).cmp(&(b.line, b.column))
+            })
+    });
+    symbols.dedup();
+    symbols
+}
+
+pub(crate) fn callable_symbol_kind(kind: CallableKind) -> SymbolKind {
+    match kind {
+        CallableKind::Function => SymbolKind::Function,
+        CallableKind::Operation => SymbolKind::Operation,
+    }
+}
+
+fn item_symbol(item: &Item) -> Option<(&str, qsc::Span, SymbolKind)> {
+    match &item.kind {
+        ItemKind::Callable(decl) => Some((
+            &decl.name.name,
+            decl.name.span,
+            callable_symbol_kind(decl.kind),
+        )),
+        ItemKind::Ty(ident, _) => Some((&ident.name, ident.span, SymbolKind::Struct)),
+        _ => None,
+    }
+}
+
+pub(crate) fn parent_namespace(package: &Package, item: &Item) -> Option<String> {
+    match &package.items.get(item.parent?)?.kind {
+        ItemKind::Namespace(name, _) => Some(name.name().to_string()),
+        _ => None,
+    }
+}
+
+/// Returns `true` for items declared at the top level, outside of any namespace.
+fn is_top_level(package: &Package, item: &Item) -> bool {
+    item.parent.is_none()
+        && package
+            .stmts
+            .iter()
+            .any(|stmt| matches!(stmt.kind, StmtKind::Item(id) if id == item.id))
+}
+
+/// Collects the named fields of a type definition, in declaration order.
+fn collect_fields(
+    def: &UdtDef,
+    sources: &SourceMap,
+    position_encoding: Encoding,
+    fields: &mut Vec<DocumentSymbol>,
+) {
+    match &def.kind {
+        UdtDefKind::Field(field) => {
+            if let (Some(name), Some(name_span)) = (&field.name, field.name_span) {
+                fields.push(DocumentSymbol {
+                    name: name.to_string(),
+                    detail: Some(field.ty.display()),
+                    kind: SymbolKind::Field,
+                    range: into_range(position_encoding, def.span, sources),
+                    selection_range: into_range(position_encoding, name_span, sources),
+                    children: Vec::new(),
+                });
+            }
+        }
+        UdtDefKind::Tuple(defs) => {
+            for def in defs {
+                collect_fields(def, sources, position_encoding, fields);
+            }
+        }
+    }
+}
+
+/// Returns `true` if the characters of `query` appear in `name` in the same order,
+/// ignoring case. An empty query matches every name.
+fn fuzzy_match(query: &str, name: &str) -> bool {
+    let mut name_chars = name.chars().flat_map(char::to_lowercase);
+    query
+        .chars()
+        .flat_map(char::to_lowercase)
+        .all(|q| name_chars.any(|n| n == q))
+}
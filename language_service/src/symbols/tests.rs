@@ -0,0 +1,228 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+#![allow(clippy::needless_raw_string_hashes)]
+
+use super::{get_document_symbols, get_workspace_symbols};
+use crate::{
+    protocol::DocumentSymbol,
+    test_utils::{
+        compile_notebook_with_fake_stdlib, compile_with_fake_stdlib_and_markers_no_cursor,
+    },
+    Encoding,
+};
+use expect_test::{expect, Expect};
+use qsc::line_column::Range;
+use std::fmt::Write;
+
+fn display_range(range: Range) -> String {
+    format!(
+        "{}:{}-{}:{}",
+        range.start.line, range.start.column, range.end.line, range.end.column
+    )
+}
+
+fn write_symbols(out: &mut String, symbols: &[DocumentSymbol], depth: usize) {
+    for symbol in symbols {
+        writeln!(
+            out,
+            "{:indent$}{:?} {} [{}] [{}]{}",
+            "",
+            symbol.kind,
+            symbol.name,
+            display_range(symbol.range),
+            display_range(symbol.selection_range),
+            symbol
+                .detail
+                .as_ref()
+                .map(|detail| format!(" {detail}"))
+                .unwrap_or_default(),
+            indent = depth * 4,
+        )
+        .expect("writing to string should succeed");
+        write_symbols(out, &symbol.children, depth + 1);
+    }
+}
+
+fn check_document_symbols(source: &str, expect: &Expect) {
+    let (compilation, _) = compile_with_fake_stdlib_and_markers_no_cursor(source, true);
+    let symbols = get_document_symbols(&compilation, "<source>", Encoding::Utf8);
+    let mut actual = String::new();
+    write_symbols(&mut actual, &symbols, 0);
+    expect.assert_eq(&actual);
+}
+
+fn check_workspace_symbols(source: &str, query: &str, expect: &Expect) {
+    let (compilation, _) = compile_with_fake_stdlib_and_markers_no_cursor(source, true);
+    let symbols = get_workspace_symbols(std::iter::once(&compilation), query, Encoding::Utf8);
+    let mut actual = String::new();
+    for symbol in symbols {
+        writeln!(
+            actual,
+            "{:?} {} in {} at {} [{}]",
+            symbol.kind,
+            symbol.name,
+            symbol.container_name.as_deref().unwrap_or_default(),
+            symbol.location.source,
+            display_range(symbol.location.range),
+        )
+        .expect("writing to string should succeed");
+    }
+    expect.assert_eq(&actual);
+}
+
+#[test]
+fn callables_and_types() {
+    check_document_symbols(
+        r#"
+namespace Test {
+    function Add(a : Int, b : Int) : Int { a + b }
+    operation Main() : Unit {
+        use q = Qubit();
+    }
+    newtype Pair = (First : Int, Second : Int);
+    struct Point { X : Double, Y : Double }
+}"#,
+        &expect![[r#"
+            Namespace Test [0:0-8:1] [1:10-1:14]
+                Function Add [2:4-2:50] [2:13-2:16] function Add(a : Int, b : Int) : Int
+                Operation Main [3:4-5:5] [3:14-3:18] operation Main() : Unit
+                Struct Pair [6:4-6:47] [6:12-6:16] struct Pair { First : Int, Second : Int }
+                    Field First [6:20-6:31] [6:20-6:25] Int
+                    Field Second [6:33-6:45] [6:33-6:39] Int
+                Struct Point [7:4-7:43] [7:11-7:16] struct Point { X : Double, Y : Double }
+                    Field X [7:19-7:29] [7:19-7:20] Double
+                    Field Y [7:31-7:41] [7:31-7:32] Double
+        "#]],
+    );
+}
+
+#[test]
+fn multiple_namespaces() {
+    check_document_symbols(
+        r#"
+namespace A {
+    operation Foo() : Unit {}
+}
+namespace B.C {
+    function Bar() : Unit {}
+}"#,
+        &expect![[r#"
+            Namespace A [0:0-3:1] [1:10-1:11]
+                Operation Foo [2:4-2:29] [2:14-2:17] operation Foo() : Unit
+            Namespace B.C [4:0-6:1] [4:10-4:13]
+                Function Bar [5:4-5:28] [5:13-5:16] function Bar() : Unit
+        "#]],
+    );
+}
+
+#[test]
+fn lambdas_are_excluded() {
+    check_document_symbols(
+        r#"
+namespace Test {
+    function Foo() : Int {
+        let f = x -> x + 1;
+        f(1)
+    }
+}"#,
+        &expect![[r#"
+            Namespace Test [0:0-6:1] [1:10-1:14]
+                Function Foo [2:4-5:5] [2:13-2:16] function Foo() : Int
+        "#]],
+    );
+}
+
+#[test]
+fn notebook_cell() {
+    let compilation = compile_notebook_with_fake_stdlib(
+        [
+            ("cell1", "operation Foo() : Unit {}"),
+            ("cell2", "function Bar() : Unit {}"),
+        ]
+        .into_iter(),
+    );
+    let symbols = get_document_symbols(&compilation, "cell2", Encoding::Utf8);
+    let mut actual = String::new();
+    write_symbols(&mut actual, &symbols, 0);
+    expect![[r#"
+        Function Bar [0:0-0:24] [0:9-0:12] function Bar() : Unit
+    "#]]
+    .assert_eq(&actual);
+}
+
+#[test]
+fn workspace_symbols_include_public_library_items() {
+    check_workspace_symbols(
+        r#"
+namespace Test {
+    operation FakeLocal() : Unit {}
+}"#,
+        "fake",
+        &expect![[r#"
+            Operation Fake in FakeStdLib at qsharp-library-source:<std> [1:26-1:30]
+            Operation FakeCtlAdj in FakeStdLib at qsharp-library-source:<std> [3:26-3:36]
+            Operation FakeLocal in Test at <source> [2:14-2:23]
+            Struct FakeStruct in FakeStdLib at qsharp-library-source:<std> [16:23-16:33]
+            Operation FakeWithParam in FakeStdLib at qsharp-library-source:<std> [2:26-2:39]
+            Operation FakeWithTypeParam in FakeStdLib at qsharp-library-source:<std> [14:26-14:43]
+            Operation RefFake in FakeStdLib at qsharp-library-source:<std> [11:26-11:33]
+        "#]],
+    );
+}
+
+#[test]
+fn workspace_symbols_fuzzy_match() {
+    check_workspace_symbols(
+        r#"
+namespace Test {
+    operation PrepareBellState() : Unit {}
+    function PrepBell() : Unit {}
+    struct BellPair { A : Int }
+}"#,
+        "pbs",
+        &expect![[r#"
+            Operation PrepareBellState in Test at <source> [2:14-2:30]
+        "#]],
+    );
+}
+
+#[test]
+fn workspace_symbols_exclude_internal_library_items() {
+    check_workspace_symbols(
+        r#"
+namespace Test {
+    operation Main() : Unit {}
+}"#,
+        "hidden",
+        &expect![[r#""#]],
+    );
+}
+
+#[test]
+fn workspace_symbols_in_notebook() {
+    let compilation = compile_notebook_with_fake_stdlib(
+        [
+            ("cell1", "operation FakeCell() : Unit { let f = () -> (); }"),
+            (
+                "cell2",
+                "namespace Cell { function FakeNamespaced() : Unit {} }",
+            ),
+        ]
+        .into_iter(),
+    );
+    let symbols = get_workspace_symbols(std::iter::once(&compilation), "fake", Encoding::Utf8);
+    let mut actual = String::new();
+    for symbol in symbols {
+        writeln!(actual, "{} in {:?}", symbol.name, symbol.container_name)
+            .expect("writing to string should succeed");
+    }
+    expect![[r#"
+        Fake in Some("FakeStdLib")
+        FakeCell in None
+        FakeCtlAdj in Some("FakeStdLib")
+        FakeNamespaced in Some("Cell")
+        FakeWithParam in Some("FakeStdLib")
+    "#]]
+    .assert_eq(&actual);
+}
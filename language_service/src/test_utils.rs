@@ -29,6 +29,38 @@ pub(crate) fn compile_with_markers(
     )
 }
 
+/// Compiles the source with the real standard library for the given target profile, so that the
+/// compilation includes the errors of the capabilities check.
+pub(crate) fn compile_with_profile_and_markers(
+    source_with_markers: &str,
+    target_profile: Profile,
+) -> (Compilation, Position, Vec<Range>) {
+    let (sources, cursor_location, target_spans) =
+        get_sources_and_markers(&[("<source>", source_with_markers)]);
+    let (_, cursor_position) = cursor_location.expect("input string should have a cursor marker");
+    let compilation = Compilation::new(
+        PackageType::Exe,
+        target_profile.into(),
+        LanguageFeatures::default(),
+        &[],
+        PackageGraphSources {
+            root: PackageInfo {
+                sources,
+                language_features: LanguageFeatures::default(),
+                dependencies: FxHashMap::default(),
+                package_type: None,
+            },
+            packages: FxHashMap::default(),
+        },
+        Vec::new(),
+    );
+    (
+        compilation,
+        cursor_position,
+        target_spans.iter().map(|l| l.range).collect(),
+    )
+}
+
 pub(crate) fn compile_with_fake_stdlib_and_markers_no_cursor(
     source_with_markers: &str,
     use_fake_stdlib: bool,
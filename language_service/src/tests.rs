@@ -225,6 +225,45 @@ async fn completions_requested_after_document_load() {
     );
 }
 
+#[tokio::test]
+async fn format_uses_project_format_config() {
+    let received_errors = RefCell::new(Vec::new());
+    let mut ls = LanguageService::new(Encoding::Utf8);
+    let mut worker = create_update_worker(&mut ls, &received_errors);
+
+    ls.update_document(
+        "formatted_project/src/main.qs",
+        1,
+        "function Foo() : Unit {\n    Bar(first, second, third);\n}\n",
+    );
+
+    worker.apply_pending().await;
+
+    let edits = ls
+        .get_format_changes("formatted_project/src/main.qs")
+        .into_iter()
+        .map(|edit| {
+            format!(
+                "{}:{}-{}:{} {:?}",
+                edit.range.start.line,
+                edit.range.start.column,
+                edit.range.end.line,
+                edit.range.end.column,
+                edit.new_text
+            )
+        })
+        .collect::<Vec<_>>();
+    expect![[r#"
+        [
+            "1:8-1:8 \"\\n        \"",
+            "1:14-1:15 \"\\n        \"",
+            "1:22-1:23 \"\\n        \"",
+            "1:28-1:28 \",\\n    \"",
+        ]
+    "#]]
+    .assert_debug_eq(&edits);
+}
+
 fn check_errors_and_compilation(
     ls: &LanguageService,
     received_errors: &mut Vec<ErrorInfo>,
@@ -300,22 +339,34 @@ thread_local! { static TEST_FS: Rc<RefCell<FsNode>> = Rc::new(RefCell::new(test_
 
 fn test_fs() -> FsNode {
     FsNode::Dir(
-        [dir(
-            "project",
-            [
-                file("qsharp.json", "{}"),
-                dir(
-                    "src",
-                    [
-                        file(
-                            "other_file.qs",
-                            "namespace OtherFile { operation Other() : Unit {} }",
-                        ),
-                        file("this_file.qs", "namespace Foo { }"),
-                    ],
-                ),
-            ],
-        )]
+        [
+            dir(
+                "project",
+                [
+                    file("qsharp.json", "{}"),
+                    dir(
+                        "src",
+                        [
+                            file(
+                                "other_file.qs",
+                                "namespace OtherFile { operation Other() : Unit {} }",
+                            ),
+                            file("this_file.qs", "namespace Foo { }"),
+                        ],
+                    ),
+                ],
+            ),
+            dir(
+                "formatted_project",
+                [
+                    file(
+                        "qsharp.json",
+                        r#"{ "format": { "maxLineWidth": 25, "trailingComma": "multiline" } }"#,
+                    ),
+                    dir("src", [file("main.qs", "")]),
+                ],
+            ),
+        ]
         .into_iter()
         .collect(),
     )
@@ -153,10 +153,16 @@ export function getLanguageServiceWorker(
 
 export { StepResultId } from "../lib/web/qsc_wasm.js";
 export type {
+  IBreakpointOptions,
   IBreakpointSpan,
+  ICallHierarchyCall,
+  ICallHierarchyItem,
   ICodeAction,
   ICodeLens,
   IDocFile,
+  IDocumentSymbol,
+  IFunctionBreakpoint,
+  IInlayHint,
   ILocation,
   IOperationInfo,
   IPosition,
@@ -164,9 +170,12 @@ export type {
   IProjectHost,
   IQSharpError,
   IRange,
+  ISemanticToken,
   IStackFrame,
   IStructStepResult,
+  ITargetDescription,
   IWorkspaceEdit,
+  IWorkspaceSymbol,
   ProjectLoader,
   VSDiagnostic,
 } from "../lib/web/qsc_wasm.js";
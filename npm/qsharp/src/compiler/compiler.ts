@@ -7,6 +7,7 @@ import {
   IOperationInfo,
   IPackageGraphSources,
   IProgramConfig as wasmIProgramConfig,
+  ITargetDescription,
   TargetProfile,
   type VSDiagnostic,
 } from "../../lib/web/qsc_wasm.js";
@@ -89,6 +90,8 @@ export type ProgramConfig = (
 ) & {
   /** Target compilation profile. */
   profile?: TargetProfile;
+  /** Target described by a capability file, which takes precedence over the profile. */
+  target?: ITargetDescription;
 };
 
 // WebWorker also support being explicitly terminated to tear down the worker thread
@@ -247,7 +250,11 @@ export function toWasmProgramConfig(
     packageGraphSources = program.packageGraphSources;
   }
 
-  return { packageGraphSources, profile: program.profile || defaultProfile };
+  return {
+    packageGraphSources,
+    profile: program.profile || defaultProfile,
+    target: program.target,
+  };
 }
 
 export function onCompilerEvent(msg: string, eventTarget: IQscEventTarget) {
@@ -4,7 +4,9 @@
 import { type Circuit as CircuitData } from "@microsoft/quantum-viz.js/lib/circuit.js";
 import type {
   DebugService,
+  IBreakpointOptions,
   IBreakpointSpan,
+  IFunctionBreakpoint,
   IQuantumState,
   IStackFrame,
   IStructStepResult,
@@ -32,7 +34,9 @@ export interface IDebugService {
     entry: string | undefined,
   ): Promise<string>;
   getBreakpoints(path: string): Promise<IBreakpointSpan[]>;
-  getLocalVariables(): Promise<Array<IVariable>>;
+  getFunctionBreakpoints(name: string): Promise<IFunctionBreakpoint[]>;
+  setBreakpointOptions(options: IBreakpointOptions[]): Promise<void>;
+  getLocalVariables(frameId?: number): Promise<Array<IVariable>>;
   captureQuantumState(): Promise<Array<IQuantumState>>;
   getCircuit(): Promise<CircuitData>;
   getStackFrames(): Promise<IStackFrame[]>;
@@ -52,6 +56,15 @@ export interface IDebugService {
     bps: number[],
     eventHandler: IQscEventTarget,
   ): Promise<IStructStepResult>;
+  evalStepBack(): Promise<IStructStepResult>;
+  evalReverseContinue(bps: number[]): Promise<IStructStepResult>;
+  setCheckpointInterval(interval: number): Promise<void>;
+  evaluate(
+    expr: string,
+    frameId: number,
+    allowQuantum: boolean,
+    eventHandler: IQscEventTarget,
+  ): Promise<string>;
   dispose(): Promise<void>;
 }
 
@@ -81,8 +94,19 @@ export class QSharpDebugService implements IDebugService {
     return this.debugService.get_breakpoints(path).spans;
   }
 
-  async getLocalVariables(): Promise<Array<IVariable>> {
-    const variable_list = this.debugService.get_locals();
+  async getFunctionBreakpoints(name: string): Promise<IFunctionBreakpoint[]> {
+    return this.debugService.get_function_breakpoints(name).breakpoints;
+  }
+
+  async setBreakpointOptions(options: IBreakpointOptions[]): Promise<void> {
+    this.debugService.set_breakpoint_options({ breakpoints: options });
+  }
+
+  async getLocalVariables(frameId?: number): Promise<Array<IVariable>> {
+    const variable_list =
+      frameId === undefined
+        ? this.debugService.get_locals()
+        : this.debugService.get_locals_in_frame(frameId);
     return variable_list.variables;
   }
 
@@ -135,6 +159,29 @@ export class QSharpDebugService implements IDebugService {
     return this.debugService.eval_step_out(event_cb, ids);
   }
 
+  async evalStepBack(): Promise<IStructStepResult> {
+    return this.debugService.eval_step_back();
+  }
+
+  async evalReverseContinue(bps: number[]): Promise<IStructStepResult> {
+    const ids = new Uint32Array(bps);
+    return this.debugService.eval_reverse_continue(ids);
+  }
+
+  async setCheckpointInterval(interval: number): Promise<void> {
+    this.debugService.set_checkpoint_interval(interval);
+  }
+
+  async evaluate(
+    expr: string,
+    frameId: number,
+    allowQuantum: boolean,
+    eventHandler: IQscEventTarget,
+  ): Promise<string> {
+    const event_cb = (msg: string) => onCompilerEvent(msg, eventHandler);
+    return this.debugService.evaluate(event_cb, expr, frameId, allowQuantum);
+  }
+
   async dispose() {
     this.debugService.free();
   }
@@ -180,6 +227,8 @@ export const debugServiceProtocol: ServiceProtocol<
   methods: {
     loadProgram: "request",
     getBreakpoints: "request",
+    getFunctionBreakpoints: "request",
+    setBreakpointOptions: "request",
     getLocalVariables: "request",
     captureQuantumState: "request",
     getCircuit: "request",
@@ -188,6 +237,10 @@ export const debugServiceProtocol: ServiceProtocol<
     evalNext: "requestWithProgress",
     evalStepIn: "requestWithProgress",
     evalStepOut: "requestWithProgress",
+    evalStepBack: "request",
+    evalReverseContinue: "request",
+    setCheckpointInterval: "request",
+    evaluate: "requestWithProgress",
     dispose: "request",
   },
   eventNames: ["DumpMachine", "Message", "Result"],
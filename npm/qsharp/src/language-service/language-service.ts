@@ -2,18 +2,24 @@
 // Licensed under the MIT License.
 
 import type {
+  ICallHierarchyCall,
+  ICallHierarchyItem,
   ICodeAction,
   ICodeLens,
   ICompletionList,
+  IDocumentSymbol,
   IHover,
+  IInlayHint,
   ILocation,
   INotebookMetadata,
   IPosition,
   IRange,
+  ISemanticToken,
   ISignatureHelp,
   ITextEdit,
   IWorkspaceConfiguration,
   IWorkspaceEdit,
+  IWorkspaceSymbol,
   LanguageService,
   VSDiagnostic,
 } from "../../lib/web/qsc_wasm.js";
@@ -86,6 +92,22 @@ export interface ILanguageService {
     position: IPosition,
   ): Promise<ITextEdit | undefined>;
   getCodeLenses(documentUri: string): Promise<ICodeLens[]>;
+  getSemanticTokens(documentUri: string): Promise<ISemanticToken[]>;
+  getInlayHints(documentUri: string, range: IRange): Promise<IInlayHint[]>;
+  prepareCallHierarchy(
+    documentUri: string,
+    position: IPosition,
+  ): Promise<ICallHierarchyItem | undefined>;
+  getIncomingCalls(
+    documentUri: string,
+    item: ICallHierarchyItem,
+  ): Promise<ICallHierarchyCall[]>;
+  getOutgoingCalls(
+    documentUri: string,
+    item: ICallHierarchyItem,
+  ): Promise<ICallHierarchyCall[]>;
+  getDocumentSymbols(documentUri: string): Promise<IDocumentSymbol[]>;
+  getWorkspaceSymbols(query: string): Promise<IWorkspaceSymbol[]>;
 
   dispose(): Promise<void>;
 
@@ -237,6 +259,46 @@ export class QSharpLanguageService implements ILanguageService {
     return this.languageService.get_code_lenses(documentUri);
   }
 
+  async getSemanticTokens(documentUri: string): Promise<ISemanticToken[]> {
+    return this.languageService.get_semantic_tokens(documentUri);
+  }
+
+  async getInlayHints(
+    documentUri: string,
+    range: IRange,
+  ): Promise<IInlayHint[]> {
+    return this.languageService.get_inlay_hints(documentUri, range);
+  }
+
+  async prepareCallHierarchy(
+    documentUri: string,
+    position: IPosition,
+  ): Promise<ICallHierarchyItem | undefined> {
+    return this.languageService.prepare_call_hierarchy(documentUri, position);
+  }
+
+  async getIncomingCalls(
+    documentUri: string,
+    item: ICallHierarchyItem,
+  ): Promise<ICallHierarchyCall[]> {
+    return this.languageService.get_incoming_calls(documentUri, item);
+  }
+
+  async getOutgoingCalls(
+    documentUri: string,
+    item: ICallHierarchyItem,
+  ): Promise<ICallHierarchyCall[]> {
+    return this.languageService.get_outgoing_calls(documentUri, item);
+  }
+
+  async getDocumentSymbols(documentUri: string): Promise<IDocumentSymbol[]> {
+    return this.languageService.get_document_symbols(documentUri);
+  }
+
+  async getWorkspaceSymbols(query: string): Promise<IWorkspaceSymbol[]> {
+    return this.languageService.get_workspace_symbols(query);
+  }
+
   async dispose() {
     this.languageService.stop_background_work();
     await this.backgroundWork;
@@ -302,6 +364,13 @@ export const languageServiceProtocol: ServiceProtocol<
     getRename: "request",
     prepareRename: "request",
     getCodeLenses: "request",
+    getSemanticTokens: "request",
+    getInlayHints: "request",
+    prepareCallHierarchy: "request",
+    getIncomingCalls: "request",
+    getOutgoingCalls: "request",
+    getDocumentSymbols: "request",
+    getWorkspaceSymbols: "request",
     dispose: "request",
     addEventListener: "addEventListener",
     removeEventListener: "removeEventListener",
@@ -10,6 +10,7 @@ from ._qsharp import (
     estimate,
     set_quantum_seed,
     set_classical_seed,
+    register_intrinsic,
     dump_machine,
     dump_circuit,
     StateDump,
@@ -35,6 +36,7 @@ __all__ = [
     "run",
     "set_quantum_seed",
     "set_classical_seed",
+    "register_intrinsic",
     "dump_machine",
     "dump_circuit",
     "compile",
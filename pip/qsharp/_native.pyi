@@ -2,7 +2,7 @@
 # Licensed under the MIT License.
 
 from enum import Enum
-from typing import Any, Callable, ClassVar, Optional, Dict, List
+from typing import Any, Callable, ClassVar, Optional, Dict, List, Tuple
 
 class TargetProfile:
     """
@@ -133,6 +133,19 @@ class Interpreter:
         """
         ...
 
+    def register_intrinsic(self, name: str, callable: Callable[..., Any]) -> None:
+        """
+        Registers a Python callable as the implementation of a Q# function declared
+        with `body intrinsic;`.
+
+        :param name: The name of the Q# function.
+        :param callable: The Python callable that implements the function.
+
+        :raises QSharpError: If the name is not an intrinsic function declared in Q#,
+            or if its types cannot be passed to and from Python.
+        """
+        ...
+
     def set_classical_seed(self, seed: Optional[int]) -> None:
         """
         Sets the seed for the classical random number generator.
@@ -204,6 +217,31 @@ class StateDumpData:
     complex amplitudes.
     """
     def get_dict(self) -> dict: ...
+
+    """
+    Get the probability of measuring each qubit as `One`, in allocation order.
+    """
+    def qubit_probabilities(self) -> List[float]: ...
+
+    """
+    Get the Bloch vector of each qubit as an (x, y, z) tuple, in allocation order.
+    """
+    def bloch_vectors(self) -> List[Tuple[float, float, float]]: ...
+
+    """
+    Get the 2x2 density matrix of the given qubit with all other qubits traced out.
+    """
+    def reduced_density_matrix(self, qubit: int) -> List[List[complex]]: ...
+
+    """
+    Get the von Neumann entropy, in bits, of the given subset of qubits.
+    """
+    def entanglement_entropy(self, qubits: List[int]) -> float: ...
+
+    """
+    Get the expectation value of a Pauli string such as "XZI", with one Pauli per qubit.
+    """
+    def pauli_expectation(self, paulis: str) -> float: ...
     def __repr__(self) -> str: ...
     def __str__(self) -> str: ...
     def _repr_html_(self) -> str: ...
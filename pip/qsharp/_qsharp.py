@@ -95,7 +95,8 @@ def init(
         target_profile setting.
 
     :param project_root: An optional path to a root directory with a Q# project to include.
-        It must contain a qsharp.json project manifest.
+        It must contain a qsharp.json project manifest. If the manifest names a target capability
+        file in its `target` field, that target is used instead of target_profile.
     """
     from ._fs import read_file, list_directory, exists, join, resolve
     from ._http import fetch_github
@@ -354,6 +355,22 @@ def set_classical_seed(seed: Optional[int]) -> None:
     get_interpreter().set_classical_seed(seed)
 
 
+def register_intrinsic(name: str, fn: Callable[..., Any]) -> None:
+    """
+    Registers a Python callable as the implementation of a Q# function declared
+    with `body intrinsic;`. Each call to the Q# function calls the callable with
+    the arguments of the call, and its return value is converted to the declared
+    return type of the function.
+
+    :param name: The name of the Q# function.
+    :param fn: The Python callable that implements the function.
+
+    :raises QSharpError: If the name is not an intrinsic function declared in Q#,
+        or if its types cannot be passed to and from Python.
+    """
+    get_interpreter().register_intrinsic(name, fn)
+
+
 class StateDump:
     """
     A state dump returned from the Q# interpreter.
@@ -432,6 +449,47 @@ class StateDump:
         """
         return [self.__inner.get(i, complex(0)) for i in range(2**self.qubit_count)]
 
+    def qubit_probabilities(self) -> List[float]:
+        """
+        Returns the probability of measuring each qubit as `One`, in allocation order.
+        """
+        return self.__data.qubit_probabilities()
+
+    def bloch_vectors(self) -> List[Tuple[float, float, float]]:
+        """
+        Returns the Bloch vector of each qubit as an (x, y, z) tuple, in allocation order.
+        Entangled qubits have vectors shorter than one.
+        """
+        return self.__data.bloch_vectors()
+
+    def reduced_density_matrix(self, qubit: int) -> List[List[complex]]:
+        """
+        Returns the 2x2 density matrix of the given qubit, with all other qubits traced out.
+
+        :param qubit: The index of the qubit, in allocation order.
+        :raises ValueError: If the qubit is out of range.
+        """
+        return self.__data.reduced_density_matrix(qubit)
+
+    def entanglement_entropy(self, qubits: List[int]) -> float:
+        """
+        Returns the von Neumann entropy, in bits, of the given subset of qubits.
+        For a pure state this measures the entanglement between the subset and the remaining qubits.
+
+        :param qubits: The indices of the qubits in the subset.
+        :raises ValueError: If a qubit is out of range or listed more than once.
+        """
+        return self.__data.entanglement_entropy(qubits)
+
+    def pauli_expectation(self, paulis: str) -> float:
+        """
+        Returns the expectation value of a Pauli string, such as "XZI", with one of
+        I, X, Y or Z for each qubit in allocation order.
+
+        :raises ValueError: If the Pauli string is invalid or its length doesn't match the number of qubits.
+        """
+        return self.__data.pauli_expectation(paulis)
+
 
 def dump_machine() -> StateDump:
     """
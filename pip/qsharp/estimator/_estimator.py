@@ -252,6 +252,7 @@ class DistillationUnitSpecification(AutoValidatingParams):
     num_output_ts: Optional[int] = None
     failure_probability_formula: Optional[str] = None
     output_error_rate_formula: Optional[str] = None
+    output_state_type: Optional[str] = None
     physical_qubit_specification: Optional[
         ProtocolSpecificDistillationUnitSpecification
     ] = None
@@ -269,6 +270,7 @@ class DistillationUnitSpecification(AutoValidatingParams):
             or self.num_output_ts is not None
             or self.failure_probability_formula is not None
             or self.output_error_rate_formula is not None
+            or self.output_state_type is not None
             or self.physical_qubit_specification is not None
             or self.logical_qubit_specification is not None
             or self.logical_qubit_specification_first_round_override is not None
@@ -306,6 +308,11 @@ class DistillationUnitSpecification(AutoValidatingParams):
         if self.output_error_rate_formula is None:
             raise LookupError("output_error_rate_formula must be set")
 
+        if self.output_state_type not in (None, "T", "CCZ", "rotation"):
+            raise ValueError(
+                "output_state_type must be one of 'T', 'CCZ', or 'rotation'"
+            )
+
         if self.physical_qubit_specification is not None:
             self.physical_qubit_specification.post_validation(result)
 
@@ -11,13 +11,14 @@ use num_bigint::BigUint;
 use num_complex::Complex64;
 use pyo3::{
     create_exception,
-    exceptions::PyException,
+    exceptions::{PyException, PyTypeError, PyValueError},
     prelude::*,
     pyclass::CompareOp,
-    types::{PyComplex, PyDict, PyList, PyTuple},
+    types::{PyBool, PyComplex, PyDict, PyList, PyTuple},
 };
 use qsc::{
     fir,
+    hir::ty::{Prim, Ty},
     interpret::{
         self,
         output::{Error, Receiver},
@@ -25,8 +26,9 @@ use qsc::{
     },
     packages::BuildableProgram,
     project::{FileSystem, PackageCache, PackageGraphSources},
+    state_views,
     target::Profile,
-    LanguageFeatures, PackageType, SourceMap,
+    LanguageFeatures, PackageType, SourceMap, TargetCapabilityFlags,
 };
 use resource_estimator::{self as re, estimate_expr};
 use std::{cell::RefCell, fmt::Write, path::PathBuf, rc::Rc};
@@ -95,7 +97,7 @@ impl Interpreter {
         resolve_path: Option<PyObject>,
         fetch_github: Option<PyObject>,
     ) -> PyResult<Self> {
-        let target = match target {
+        let profile = match target {
             TargetProfile::Adaptive_RI => Profile::AdaptiveRI,
             TargetProfile::Base => Profile::Base,
             TargetProfile::Unrestricted => Profile::Unrestricted,
@@ -105,7 +107,9 @@ impl Interpreter {
 
         let package_cache = PACKAGE_CACHE.with(Clone::clone);
 
-        let buildable_program = if let Some(project_root) = project_root {
+        // A project that names a capability file in its manifest is compiled for that target
+        // instead of the target profile.
+        let (buildable_program, target) = if let Some(project_root) = project_root {
             if let (Some(read_file), Some(list_directory), Some(resolve_path), Some(fetch_github)) =
                 (read_file, list_directory, resolve_path, fetch_github)
             {
@@ -118,7 +122,13 @@ impl Interpreter {
                     return Err(project.errors.into_py_err());
                 }
 
-                BuildableProgram::new(target.into(), project.package_graph_sources)
+                let target = project
+                    .target
+                    .unwrap_or_else(|| TargetCapabilityFlags::from(profile).into());
+                (
+                    BuildableProgram::new(target.capabilities, project.package_graph_sources),
+                    target,
+                )
             } else {
                 panic!("file system hooks should have been passed in with a manifest descriptor")
             }
@@ -128,18 +138,24 @@ impl Interpreter {
                 LanguageFeatures::from_iter(language_features),
                 None,
             );
-            BuildableProgram::new(target.into(), graph)
+            (
+                BuildableProgram::new(profile.into(), graph),
+                TargetCapabilityFlags::from(profile).into(),
+            )
         };
 
         match interpret::Interpreter::new(
             SourceMap::new(buildable_program.user_code.sources, None),
             PackageType::Lib,
-            target.into(),
+            target.capabilities,
             buildable_program.user_code.language_features,
             buildable_program.store,
             &buildable_program.user_code_dependencies,
         ) {
-            Ok(interpreter) => Ok(Self { interpreter }),
+            Ok(mut interpreter) => {
+                interpreter.set_target_limits(&target);
+                Ok(Self { interpreter })
+            }
             Err(errors) => Err(QSharpError::new_err(format_errors(errors))),
         }
     }
@@ -207,6 +223,48 @@ impl Interpreter {
         }
     }
 
+    /// Registers a Python callable as the implementation of a Q# function declared with
+    /// `body intrinsic;`. The callable is called with the arguments of each call to the function,
+    /// and its return value is converted to the declared return type of the function.
+    ///
+    /// :param name: The name of the Q# function.
+    /// :param callable: The Python callable that implements the function.
+    ///
+    /// :raises QSharpError: If the name is not an intrinsic function declared in Q#, or if its
+    ///     types cannot be passed to and from Python.
+    fn register_intrinsic(&mut self, py: Python, name: &str, callable: PyObject) -> PyResult<()> {
+        if !callable.as_ref(py).is_callable() {
+            return Err(PyTypeError::new_err(format!(
+                "the implementation of `{name}` must be callable"
+            )));
+        }
+        let (input, output) = self
+            .interpreter
+            .intrinsic_signature(name)
+            .map_err(|errors| QSharpError::new_err(format_errors(errors)))?;
+        let return_ty = output.clone();
+        let handler = move |arg: Value| {
+            Python::with_gil(|py| {
+                let args = match arg {
+                    Value::Tuple(items) => PyTuple::new(
+                        py,
+                        items
+                            .iter()
+                            .map(|item| ValueWrapper(item.clone()).into_py(py)),
+                    ),
+                    arg => PyTuple::new(py, [ValueWrapper(arg).into_py(py)]),
+                };
+                let result = callable
+                    .call1(py, args)
+                    .map_err(|error| error.to_string())?;
+                value_from_py(result.as_ref(py), &return_ty)
+            })
+        };
+        self.interpreter
+            .register_intrinsic(name, &input, output, Rc::new(handler))
+            .map_err(|errors| QSharpError::new_err(format_errors(errors)))
+    }
+
     fn qir(&mut self, _py: Python, entry_expr: &str) -> PyResult<String> {
         match self.interpreter.qirgen(entry_expr) {
             Ok(qir) => Ok(qir),
@@ -393,6 +451,40 @@ impl StateDumpData {
         self.0 .1
     }
 
+    fn qubit_probabilities(&self) -> Vec<f64> {
+        state_views::qubit_views(&self.0 .0, self.0 .1)
+            .into_iter()
+            .map(|view| view.probability_one)
+            .collect()
+    }
+
+    fn bloch_vectors(&self) -> Vec<(f64, f64, f64)> {
+        state_views::qubit_views(&self.0 .0, self.0 .1)
+            .into_iter()
+            .map(|view| {
+                let [x, y, z] = view.bloch_vector;
+                (x, y, z)
+            })
+            .collect()
+    }
+
+    fn reduced_density_matrix(&self, qubit: usize) -> PyResult<Vec<Vec<Complex64>>> {
+        state_views::reduced_density_matrix(&self.0 .0, self.0 .1, qubit)
+            .map(|matrix| matrix.iter().map(|row| row.to_vec()).collect())
+            .map_err(|e| PyValueError::new_err(e.to_string()))
+    }
+
+    #[allow(clippy::needless_pass_by_value)]
+    fn entanglement_entropy(&self, qubits: Vec<usize>) -> PyResult<f64> {
+        state_views::entanglement_entropy(&self.0 .0, self.0 .1, &qubits)
+            .map_err(|e| PyValueError::new_err(e.to_string()))
+    }
+
+    fn pauli_expectation(&self, paulis: &str) -> PyResult<f64> {
+        state_views::pauli_expectation(&self.0 .0, self.0 .1, paulis)
+            .map_err(|e| PyValueError::new_err(e.to_string()))
+    }
+
     fn __len__(&self) -> usize {
         self.0 .0.len()
     }
@@ -505,6 +597,68 @@ impl IntoPy<PyObject> for ValueWrapper {
     }
 }
 
+/// Converts a Python value to a Q# value of the given type.
+fn value_from_py(object: &PyAny, ty: &Ty) -> core::result::Result<Value, String> {
+    let mismatch = || {
+        format!(
+            "expected a value of type `{}`, found {}",
+            ty.display(),
+            object.get_type().name().unwrap_or("object")
+        )
+    };
+    match ty {
+        Ty::Tuple(items) if items.is_empty() && object.is_none() => Ok(Value::unit()),
+        Ty::Tuple(items) => {
+            let values = object.downcast::<PyTuple>().map_err(|_| mismatch())?;
+            if values.len() != items.len() {
+                return Err(mismatch());
+            }
+            values
+                .iter()
+                .zip(items)
+                .map(|(value, ty)| value_from_py(value, ty))
+                .collect::<core::result::Result<Vec<_>, _>>()
+                .map(|values| Value::Tuple(values.into()))
+        }
+        Ty::Array(item) => object
+            .iter()
+            .map_err(|_| mismatch())?
+            .map(|value| value_from_py(value.map_err(|error| error.to_string())?, item))
+            .collect::<core::result::Result<Vec<_>, _>>()
+            .map(|values| Value::Array(values.into())),
+        Ty::Prim(Prim::Bool) => object
+            .downcast::<PyBool>()
+            .map(|value| Value::Bool(value.is_true()))
+            .map_err(|_| mismatch()),
+        // Python booleans are integers, but they are not converted to Q# integers.
+        Ty::Prim(Prim::Int) if !object.is_instance_of::<PyBool>() => {
+            object.extract().map(Value::Int).map_err(|_| mismatch())
+        }
+        Ty::Prim(Prim::BigInt) => object.extract().map(Value::BigInt).map_err(|_| mismatch()),
+        Ty::Prim(Prim::Double) => object.extract().map(Value::Double).map_err(|_| mismatch()),
+        Ty::Prim(Prim::String) => object
+            .extract::<String>()
+            .map(|value| Value::String(value.into()))
+            .map_err(|_| mismatch()),
+        Ty::Prim(Prim::Result) => object
+            .extract::<PyRef<Result>>()
+            .map(|result| Value::Result((*result == Result::One).into()))
+            .map_err(|_| mismatch()),
+        Ty::Prim(Prim::Pauli) => object
+            .extract::<PyRef<Pauli>>()
+            .map(|pauli| {
+                Value::Pauli(match *pauli {
+                    Pauli::I => fir::Pauli::I,
+                    Pauli::X => fir::Pauli::X,
+                    Pauli::Y => fir::Pauli::Y,
+                    Pauli::Z => fir::Pauli::Z,
+                })
+            })
+            .map_err(|_| mismatch()),
+        _ => Err(mismatch()),
+    }
+}
+
 struct OptionalCallbackReceiver<'a> {
     callback: Option<PyObject>,
     py: Python<'a>,
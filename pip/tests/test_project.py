@@ -74,6 +74,14 @@ def test_github_dependency(qsharp) -> None:
     assert result == 12
 
 
+def test_project_target(qsharp) -> None:
+    qsharp.init(project_root="/with_target")
+    qsharp.compile("Test.MeasureOne()")
+    with pytest.raises(Exception) as excinfo:
+        qsharp.compile("Test.MeasureBell()")
+    assert str(excinfo.value).find("the target does not support the `cx` gate") != -1
+
+
 memfs = {
     "": {
         "good": {
@@ -124,6 +132,25 @@ memfs = {
                     }
                 }""",
         },
+        "with_target": {
+            "src": {
+                "test.qs": """namespace Test {
+                    operation MeasureOne() : Result { use q = Qubit(); H(q); MResetZ(q) }
+                    operation MeasureBell() : Result[] {
+                        use qs = Qubit[2];
+                        H(qs[0]);
+                        CNOT(qs[0], qs[1]);
+                        MResetEachZ(qs)
+                    }
+                }""",
+            },
+            "qsharp.json": """{ "target": "target.json" }""",
+            "target.json": """
+                {
+                    "capabilities": ["Adaptive", "QubitReset"],
+                    "gates": ["h", "mresetz"]
+                }""",
+        },
         "with_github_dep": {
             "src": {
                 "test.qs": "namespace Test { operation CallsDependency() : Int { return Foo.Test.ReturnsTwelve(); } }",
@@ -73,6 +73,36 @@ def test_classical_seed() -> None:
     assert value1 != value3
 
 
+def test_register_intrinsic() -> None:
+    qsharp.init(target_profile=qsharp.TargetProfile.Unrestricted)
+    qsharp.eval(
+        "function Scale(xs : Double[], factor : Int) : (Double[], Bool) { body intrinsic; }"
+    )
+    qsharp.register_intrinsic(
+        "Scale", lambda xs, factor: ([x * factor for x in xs], factor > 1)
+    )
+    assert qsharp.eval("Scale([0.5, 1.5], 2)") == ([1.0, 3.0], True)
+
+
+def test_register_intrinsic_errors() -> None:
+    qsharp.init(target_profile=qsharp.TargetProfile.Unrestricted)
+    qsharp.eval("function Parity(x : Int) : Result { body intrinsic; }")
+    qsharp.register_intrinsic("Parity", lambda x: "one" if x % 2 else "zero")
+    try:
+        qsharp.eval("Parity(3)")
+    except qsharp.QSharpError as e:
+        assert "expected a value of type `Result`, found str" in str(e)
+    else:
+        assert False
+
+    try:
+        qsharp.register_intrinsic("Microsoft.Quantum.Math.AbsI", abs)
+    except qsharp.QSharpError as e:
+        assert "is not an intrinsic function declared in this session" in str(e)
+    else:
+        assert False
+
+
 def test_dump_machine() -> None:
     qsharp.init(target_profile=qsharp.TargetProfile.Unrestricted)
     qsharp.eval(
@@ -136,6 +166,36 @@ def test_dump_machine() -> None:
     assert state_dump.check_eq([1.0], tolerance=1e-4)
 
 
+def test_dump_machine_views() -> None:
+    qsharp.init(target_profile=qsharp.TargetProfile.Unrestricted)
+    qsharp.eval(
+        """
+    use qs = Qubit[3];
+    H(qs[0]);
+    CNOT(qs[0], qs[1]);
+    X(qs[2]);
+    """
+    )
+    state_dump = qsharp.dump_machine()
+    assert [round(p, 4) for p in state_dump.qubit_probabilities()] == [0.5, 0.5, 1.0]
+    assert [tuple(round(c, 4) + 0.0 for c in v) for v in state_dump.bloch_vectors()] == [
+        (0.0, 0.0, 0.0),
+        (0.0, 0.0, 0.0),
+        (0.0, 0.0, -1.0),
+    ]
+    matrix = state_dump.reduced_density_matrix(2)
+    assert abs(matrix[1][1] - 1.0) < 1e-10
+    assert abs(state_dump.entanglement_entropy([0]) - 1.0) < 1e-10
+    assert abs(state_dump.entanglement_entropy([0, 1])) < 1e-10
+    assert abs(state_dump.pauli_expectation("ZZZ") + 1.0) < 1e-10
+    assert abs(state_dump.pauli_expectation("xxi") - 1.0) < 1e-10
+    try:
+        state_dump.pauli_expectation("XX")
+        assert False
+    except ValueError as e:
+        assert str(e) == "invalid Pauli string `XX`"
+
+
 def test_dump_operation() -> None:
     qsharp.init(target_profile=qsharp.TargetProfile.Unrestricted)
     res = qsharp.utils.dump_operation("qs => ()", 1)
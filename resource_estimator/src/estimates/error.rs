@@ -45,8 +45,9 @@ pub enum Error {
     CannotComputeMagicStates(f64),
     /// Constraint-based search only supports one magic state type.
     ///
-    /// ✅ This error cannot be triggered by the system, since only one magic
-    /// state type is supported.
+    /// ✅ This does not contain user data and can be logged
+    /// 🧑‍💻 This indicates a user error, e.g., when CCZ or rotation
+    /// distillation units are combined with duration or qubit constraints.
     #[error("Constraint-based search only supports one magic state type.")]
     #[diagnostic(code("Qsc.Estimates.MultipleMagicStatesNotSupported"))]
     MultipleMagicStatesNotSupported,
@@ -23,10 +23,15 @@ use crate::estimates::{Overhead, PhysicalResourceEstimation};
 use std::rc::Rc;
 
 pub use self::modeling::{
-    GateBasedPhysicalQubit, MajoranaQubit, PhysicalQubit, Protocol, TFactory,
+    GateBasedPhysicalQubit, MagicStateType, MajoranaQubit, PhysicalQubit, Protocol, TFactory,
+};
+pub use self::optimization::{
+    CczFactoryBuilder, MagicStateFactoryBuilder, RotationFactoryBuilder, TFactoryBuilder,
+};
+pub use self::{
+    data::{LogicalResourceCounts, MagicStateOverhead},
+    error::Error,
 };
-pub use self::optimization::TFactoryBuilder;
-pub use self::{data::LogicalResourceCounts, error::Error};
 use data::{EstimateType, JobParams};
 pub use data::{LayoutReportData, PartitioningOverhead};
 use serde::Serialize;
@@ -78,24 +83,19 @@ fn estimate_single<L: Overhead + LayoutReportData + PartitioningOverhead + Seria
     let qubit = job_params.qubit_params().clone();
 
     let ftp = Protocol::load_from_specification(job_params.qec_scheme_mut(), &qubit)?;
-    let distillation_unit_templates = job_params
-        .distillation_unit_specifications()
-        .as_templates()?;
+    let factory_builder = create_factory_builder(&job_params)?;
     // create error budget partitioning
     let partitioning = job_params
         .error_budget()
         .partitioning(logical_resources.as_ref())?;
 
-    let mut estimation = PhysicalResourceEstimation::new(
-        ftp,
-        qubit,
-        TFactoryBuilder::new(
-            distillation_unit_templates,
-            job_params.constraints().max_distillation_rounds,
-        ),
+    let layout_overhead = Rc::new(MagicStateOverhead::new(
         logical_resources,
-        partitioning,
-    );
+        factory_builder.magic_state_types().to_vec(),
+    ));
+
+    let mut estimation =
+        PhysicalResourceEstimation::new(ftp, qubit, factory_builder, layout_overhead, partitioning);
     if let Some(logical_depth_factor) = job_params.constraints().logical_depth_factor {
         estimation.set_logical_depth_factor(logical_depth_factor);
     }
@@ -133,6 +133,39 @@ fn estimate_single<L: Overhead + LayoutReportData + PartitioningOverhead + Seria
     }
 }
 
+/// Creates a factory builder for T states, and for CCZ and rotation states if
+/// the job parameters contain distillation units that produce them.
+fn create_factory_builder(job_params: &JobParams) -> Result<MagicStateFactoryBuilder> {
+    let specifications = job_params.distillation_unit_specifications();
+    let max_distillation_rounds = job_params.constraints().max_distillation_rounds;
+
+    let mut factory_builder = MagicStateFactoryBuilder::new(TFactoryBuilder::new(
+        specifications.as_templates(MagicStateType::T)?,
+        max_distillation_rounds,
+    ));
+
+    for magic_state_type in specifications.magic_state_types()? {
+        match magic_state_type {
+            MagicStateType::T => {}
+            MagicStateType::Ccz => {
+                factory_builder = factory_builder.with_ccz_factory_builder(CczFactoryBuilder::new(
+                    specifications.as_templates(magic_state_type)?,
+                    max_distillation_rounds,
+                )?);
+            }
+            MagicStateType::Rotation => {
+                factory_builder =
+                    factory_builder.with_rotation_factory_builder(RotationFactoryBuilder::new(
+                        specifications.as_templates(magic_state_type)?,
+                        max_distillation_rounds,
+                    )?);
+            }
+        }
+    }
+
+    Ok(factory_builder)
+}
+
 fn serialize_error(err: error::Error) -> String {
     serde_json::to_string(&data::Failure::new(err))
         .expect("serializing to json string should succeed")
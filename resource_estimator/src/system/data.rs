@@ -11,8 +11,10 @@ mod tfactory;
 
 pub use constraints::Constraints;
 pub use job_params::{EstimateType, JobParams, PartitioningOverhead};
-pub use logical_counts::{LayoutReportData, LogicalResourceCounts};
-pub use physical_counts::{PhysicalResourceCounts, PhysicalResourceCountsBreakdown};
+pub use logical_counts::{LayoutReportData, LogicalResourceCounts, MagicStateOverhead};
+pub use physical_counts::{
+    MagicStateFactoryCounts, PhysicalResourceCounts, PhysicalResourceCountsBreakdown,
+};
 pub use report::{FormattedPhysicalResourceCounts, Report};
 pub use result::{Failure, Success};
 
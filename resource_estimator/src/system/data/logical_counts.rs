@@ -1,11 +1,16 @@
 // Copyright (c) Microsoft Corporation.
 // Licensed under the MIT License.
 
+use std::rc::Rc;
+
 use crate::{
     estimates::{ErrorBudget, Overhead},
-    system::constants::{
-        NUM_MEASUREMENTS_PER_R, NUM_MEASUREMENTS_PER_TOF, NUM_TS_PER_ROTATION_A_COEFFICIENT,
-        NUM_TS_PER_ROTATION_B_COEFFICIENT,
+    system::{
+        constants::{
+            NUM_MEASUREMENTS_PER_R, NUM_MEASUREMENTS_PER_TOF, NUM_TS_PER_ROTATION_A_COEFFICIENT,
+            NUM_TS_PER_ROTATION_B_COEFFICIENT,
+        },
+        modeling::MagicStateType,
     },
 };
 use serde::{Deserialize, Serialize};
@@ -131,3 +136,122 @@ impl LayoutReportData for LogicalResourceCounts {
         }
     }
 }
+
+/// Assigns the magic states of some logical overhead to the magic state types
+/// for which factories are available
+///
+/// CCZ and `CCiX` gates consume one CCZ state each, if CCZ factories are
+/// available, and rotation gates consume one rotation state each, if rotation
+/// factories are available.  Otherwise, these operations are implemented using
+/// T states.  If T states are the only magic state type, all values are
+/// forwarded to the underlying overhead.
+pub struct MagicStateOverhead<L> {
+    logical_counts: Rc<L>,
+    magic_state_types: Vec<MagicStateType>,
+}
+
+impl<L> MagicStateOverhead<L> {
+    pub fn new(logical_counts: Rc<L>, magic_state_types: Vec<MagicStateType>) -> Self {
+        Self {
+            logical_counts,
+            magic_state_types,
+        }
+    }
+
+    #[must_use]
+    pub fn logical_counts(&self) -> &Rc<L> {
+        &self.logical_counts
+    }
+
+    /// The magic state types in the order of their magic state index
+    #[must_use]
+    pub fn magic_state_types(&self) -> &[MagicStateType] {
+        &self.magic_state_types
+    }
+
+    fn has_magic_state_type(&self, magic_state_type: MagicStateType) -> bool {
+        self.magic_state_types.contains(&magic_state_type)
+    }
+}
+
+impl<L: Overhead + LayoutReportData> Overhead for MagicStateOverhead<L> {
+    fn logical_qubits(&self) -> u64 {
+        self.logical_counts.logical_qubits()
+    }
+
+    fn logical_depth(&self, budget: &ErrorBudget) -> u64 {
+        if self.has_magic_state_type(MagicStateType::Rotation) {
+            // Each rotation is applied by consuming a single rotation state,
+            // there is no need for rotation synthesis
+            let counts = self.logical_counts.as_ref();
+            (counts.measurement_count() + counts.rotation_count() + counts.t_count())
+                * NUM_MEASUREMENTS_PER_R
+                + (counts.ccz_count() + counts.ccix_count()) * NUM_MEASUREMENTS_PER_TOF
+        } else {
+            self.logical_counts.logical_depth(budget)
+        }
+    }
+
+    fn num_magic_states(&self, budget: &ErrorBudget, index: usize) -> u64 {
+        if self.magic_state_types.len() == 1 {
+            return self.logical_counts.num_magic_states(budget, index);
+        }
+
+        let counts = self.logical_counts.as_ref();
+        match self.magic_state_types[index] {
+            MagicStateType::T => {
+                let mut num_tstates = counts.t_count();
+                if !self.has_magic_state_type(MagicStateType::Ccz) {
+                    num_tstates += 4 * (counts.ccz_count() + counts.ccix_count());
+                }
+                if !self.has_magic_state_type(MagicStateType::Rotation) {
+                    num_tstates += counts
+                        .num_ts_per_rotation(budget.rotations())
+                        .unwrap_or_default()
+                        * counts.rotation_count();
+                }
+                num_tstates
+            }
+            MagicStateType::Ccz => counts.ccz_count() + counts.ccix_count(),
+            MagicStateType::Rotation => counts.rotation_count(),
+        }
+    }
+}
+
+impl<L: LayoutReportData> LayoutReportData for MagicStateOverhead<L> {
+    fn num_qubits(&self) -> u64 {
+        self.logical_counts.num_qubits()
+    }
+
+    fn t_count(&self) -> u64 {
+        self.logical_counts.t_count()
+    }
+
+    fn rotation_count(&self) -> u64 {
+        self.logical_counts.rotation_count()
+    }
+
+    fn rotation_depth(&self) -> u64 {
+        self.logical_counts.rotation_depth()
+    }
+
+    fn ccz_count(&self) -> u64 {
+        self.logical_counts.ccz_count()
+    }
+
+    fn ccix_count(&self) -> u64 {
+        self.logical_counts.ccix_count()
+    }
+
+    fn measurement_count(&self) -> u64 {
+        self.logical_counts.measurement_count()
+    }
+
+    fn num_ts_per_rotation(&self, eps_synthesis: f64) -> Option<u64> {
+        if self.has_magic_state_type(MagicStateType::Rotation) {
+            None
+        } else {
+            self.logical_counts.num_ts_per_rotation(eps_synthesis)
+        }
+    }
+}
@@ -46,4 +46,25 @@ pub struct PhysicalResourceCountsBreakdown {
     pub(crate) num_ts_per_rotation: Option<u64>,
     /// The Clifford error rate based on the qubit parameters
     pub(crate) clifford_error_rate: f64,
+    /// CCZ state factories, if CCZ distillation units are specified
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) ccz_factories: Option<MagicStateFactoryCounts>,
+    /// Rotation state factories, if rotation distillation units are specified
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) rotation_factories: Option<MagicStateFactoryCounts>,
+}
+
+#[derive(Default, Debug, serde::Serialize)]
+#[serde(rename_all(serialize = "camelCase"))]
+pub struct MagicStateFactoryCounts {
+    /// The number of magic states of this type consumed by the algorithm
+    pub(crate) num_states: u64,
+    /// The number of factories for this magic state type
+    pub(crate) num_factories: u64,
+    /// The number of how often all parallel factories should run
+    pub(crate) num_factory_runs: u64,
+    /// The number of physical qubits for all factories of this magic state type
+    pub(crate) physical_qubits: u64,
+    /// The required logical error rate for this magic state type
+    pub(crate) required_logical_state_error_rate: Option<f64>,
 }
@@ -11,11 +11,11 @@ use serde::Serialize;
 use crate::estimates::{Factory, FactoryPart, Overhead, PhysicalResourceEstimationResult};
 use crate::system::modeling::Protocol;
 
-use super::LayoutReportData;
 use super::{
-    super::modeling::{PhysicalInstructionSet, TFactory},
+    super::modeling::{MagicStateType, PhysicalInstructionSet, TFactory},
     job_params::JobParams,
 };
+use super::{LayoutReportData, MagicStateOverhead};
 
 #[derive(Serialize)]
 #[serde(rename_all(serialize = "camelCase"))]
@@ -31,13 +31,13 @@ impl Report {
         result: &PhysicalResourceEstimationResult<
             Protocol,
             TFactory,
-            impl Overhead + LayoutReportData,
+            MagicStateOverhead<impl Overhead + LayoutReportData>,
         >,
         formatted_counts: &FormattedPhysicalResourceCounts,
     ) -> Self {
         let logical_counts = result.layout_overhead();
-        // In this system, we consider T as the only magic state type, therefore
-        // there is only one factory part in the result.
+        // T states are always the first magic state type; factories for
+        // other magic state types are reported in separate groups.
         let part = result.factory_parts()[0].as_ref();
         let factory = part.map(FactoryPart::factory);
 
@@ -126,6 +126,7 @@ impl Report {
                 entries,
             });
         }
+        groups.extend(magic_state_factory_groups(result));
         let mut entries = vec![];
         entries.push(ReportEntry::new("physicalCountsFormatted/logicalCountsNumQubits", "Logical qubits (pre-layout)", r#"Number of logical qubits in the input quantum program"#, &format!(r#"We determine {} algorithmic logical qubits from this number by assuming to align them in a 2D grid.  Auxiliary qubits are added to allow for sufficient space to execute multi-qubit Pauli measurements on all or a subset of the logical qubits."#, format_thousand_sep(&result.layout_overhead().logical_qubits()))));
         entries.push(ReportEntry::new("physicalCountsFormatted/logicalCountsTCount", "T gates", r#"Number of T gates in the input quantum program"#, r#"This includes all T gates and adjoint T gates, but not T gates used to implement rotation gates with arbitrary angle, CCZ gates, or CCiX gates."#));
@@ -233,6 +234,96 @@ impl Report {
     }
 }
 
+/// Creates one report group for each factory of a magic state type other than T
+fn magic_state_factory_groups(
+    result: &PhysicalResourceEstimationResult<
+        Protocol,
+        TFactory,
+        MagicStateOverhead<impl Overhead + LayoutReportData>,
+    >,
+) -> Vec<ReportEntryGroup> {
+    let mut groups = vec![];
+
+    for (index, magic_state_type) in result
+        .layout_overhead()
+        .magic_state_types()
+        .iter()
+        .enumerate()
+    {
+        let (title, factory_path, counts_path) = match magic_state_type {
+            MagicStateType::T => continue,
+            MagicStateType::Ccz => (
+                "CCZ factory parameters",
+                "cczFactory",
+                "physicalCounts/breakdown/cczFactories",
+            ),
+            MagicStateType::Rotation => (
+                "Rotation factory parameters",
+                "rotationFactory",
+                "physicalCounts/breakdown/rotationFactories",
+            ),
+        };
+
+        let Some(part) = result.factory_parts()[index].as_ref() else {
+            continue;
+        };
+        let factory = part.factory();
+
+        let entries = vec![
+            ReportEntry::new(
+                &format!("{counts_path}/numStates"),
+                &format!("Number of {magic_state_type} states"),
+                &format!("Number of {magic_state_type} states consumed by the algorithm"),
+                &format!("These {magic_state_type} states are produced by dedicated {magic_state_type} factories instead of being implemented with T states."),
+            ),
+            ReportEntry::new(
+                &format!("{counts_path}/numFactories"),
+                &format!("{magic_state_type} factories"),
+                &format!("Number of {magic_state_type} factories"),
+                &format!("Number of {magic_state_type} factories that run in parallel, each executed {} times.", format_thousand_sep(&part.runs())),
+            ),
+            ReportEntry::new(
+                &format!("{factory_path}/physicalQubits"),
+                "Physical qubits",
+                &format!("Number of physical qubits for a single {magic_state_type} factory"),
+                "This corresponds to the maximum number of physical qubits over all rounds of distillation units in the factory.",
+            ),
+            ReportEntry::new(
+                &format!("{factory_path}/runtime"),
+                "Runtime",
+                &format!("Runtime of a single {magic_state_type} factory"),
+                "The runtime of a single factory is the accumulated runtime of executing each round in the factory.",
+            ),
+            ReportEntry::new(
+                &format!("{factory_path}/numTstates"),
+                &format!("Number of output {magic_state_type} states per run"),
+                &format!("Number of output {magic_state_type} states produced in a single run of the factory"),
+                &format!("The factory takes as input {} noisy physical T states and produces {} {magic_state_type} states.", format_thousand_sep(&factory.num_input_states()), format_thousand_sep(&factory.num_output_states())),
+            ),
+            ReportEntry::new(
+                &format!("{factory_path}/unitNamePerRound"),
+                "Distillation units",
+                "The types of distillation units",
+                &format!("All rounds but the last one distill T states, the last round produces {magic_state_type} states."),
+            ),
+            ReportEntry::new(
+                &format!("{factory_path}/logicalErrorRate"),
+                &format!("Logical {magic_state_type} state error rate"),
+                &format!("Logical {magic_state_type} state error rate"),
+                &format!("This is the logical {magic_state_type} state error rate achieved by the factory which is equal or smaller than the required error rate {:.2e}.", part.required_output_error_rate()),
+            ),
+        ];
+
+        groups.push(ReportEntryGroup {
+            title: title.into(),
+            always_visible: false,
+            entries,
+        });
+    }
+
+    groups
+}
+
 #[derive(Serialize)]
 #[serde(rename_all(serialize = "camelCase"))]
 struct ReportEntryGroup {
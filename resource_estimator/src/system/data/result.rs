@@ -6,13 +6,13 @@ use std::rc::Rc;
 use crate::estimates::{
     ErrorBudget, FactoryPart, LogicalPatch, Overhead, PhysicalResourceEstimationResult,
 };
-use crate::system::modeling::{Protocol, TFactory};
+use crate::system::modeling::{MagicStateType, Protocol, TFactory};
 
-use super::LayoutReportData;
 use super::{
-    super::Error, FormattedPhysicalResourceCounts, JobParams, PhysicalResourceCounts,
-    PhysicalResourceCountsBreakdown, Report,
+    super::Error, FormattedPhysicalResourceCounts, JobParams, MagicStateFactoryCounts,
+    PhysicalResourceCounts, PhysicalResourceCountsBreakdown, Report,
 };
+use super::{LayoutReportData, MagicStateOverhead};
 use miette::Diagnostic;
 use serde::{ser::SerializeMap, Serialize, Serializer};
 
@@ -30,6 +30,10 @@ pub struct Success<L: Serialize> {
     #[serde(skip_serializing_if = "Option::is_none")]
     tfactory: Option<TFactory>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    ccz_factory: Option<TFactory>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rotation_factory: Option<TFactory>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     error_budget: Option<ErrorBudget>,
     logical_counts: Rc<L>,
     report_data: Report,
@@ -40,7 +44,7 @@ pub struct Success<L: Serialize> {
 impl<L: Overhead + LayoutReportData + Serialize> Success<L> {
     pub fn new(
         job_params: JobParams,
-        result: PhysicalResourceEstimationResult<Protocol, TFactory, L>,
+        result: PhysicalResourceEstimationResult<Protocol, TFactory, MagicStateOverhead<L>>,
     ) -> Self {
         let counts = create_physical_resource_counts(&result);
 
@@ -49,9 +53,21 @@ impl<L: Overhead + LayoutReportData + Serialize> Success<L> {
 
         let report_data = Report::new(&job_params, &result, &formatted_counts);
 
-        let logical_counts = result.layout_overhead().clone();
-        let (logical_qubit, mut parts, error_budget) = result.take();
-        let tfactory = parts.swap_remove(0).map(FactoryPart::into_factory);
+        let logical_counts = result.layout_overhead().logical_counts().clone();
+        let magic_state_types = result.layout_overhead().magic_state_types().to_vec();
+        let (logical_qubit, parts, error_budget) = result.take();
+
+        let mut tfactory = None;
+        let mut ccz_factory = None;
+        let mut rotation_factory = None;
+        for (magic_state_type, part) in magic_state_types.into_iter().zip(parts) {
+            let factory = part.map(FactoryPart::into_factory);
+            match magic_state_type {
+                MagicStateType::T => tfactory = factory,
+                MagicStateType::Ccz => ccz_factory = factory,
+                MagicStateType::Rotation => rotation_factory = factory,
+            }
+        }
 
         Self {
             status: "success",
@@ -60,6 +76,8 @@ impl<L: Overhead + LayoutReportData + Serialize> Success<L> {
             physical_counts_formatted: Some(formatted_counts),
             logical_qubit: Some(logical_qubit),
             tfactory,
+            ccz_factory,
+            rotation_factory,
             error_budget: Some(error_budget),
             logical_counts,
             report_data,
@@ -69,13 +87,15 @@ impl<L: Overhead + LayoutReportData + Serialize> Success<L> {
 
     pub fn new_from_multiple(
         job_params: JobParams,
-        mut results: Vec<PhysicalResourceEstimationResult<Protocol, TFactory, L>>,
+        mut results: Vec<
+            PhysicalResourceEstimationResult<Protocol, TFactory, MagicStateOverhead<L>>,
+        >,
     ) -> Self {
         let mut report_data: Option<Report> = None;
 
         let mut frontier_entries: Vec<FrontierEntry> = Vec::new();
 
-        let logical_counts = results[0].layout_overhead().clone();
+        let logical_counts = results[0].layout_overhead().logical_counts().clone();
 
         // we will pick the shortest runtime result as the first result.
         results.sort_by_key(PhysicalResourceEstimationResult::runtime);
@@ -97,6 +117,8 @@ impl<L: Overhead + LayoutReportData + Serialize> Success<L> {
             physical_counts_formatted: None,
             logical_qubit: None,
             tfactory: None,
+            ccz_factory: None,
+            rotation_factory: None,
             error_budget: None,
             logical_counts,
             report_data: report_data.expect("error should have report"), // Here we assume that at least a single solution was found.
@@ -117,7 +139,11 @@ pub struct FrontierEntry {
 
 fn create_frontier_entry(
     job_params: &JobParams,
-    result: PhysicalResourceEstimationResult<Protocol, TFactory, impl Overhead + LayoutReportData>,
+    result: PhysicalResourceEstimationResult<
+        Protocol,
+        TFactory,
+        MagicStateOverhead<impl Overhead + LayoutReportData>,
+    >,
     create_report: bool,
 ) -> (FrontierEntry, Option<Report>) {
     let physical_counts = create_physical_resource_counts(&result);
@@ -147,7 +173,11 @@ fn create_frontier_entry(
 }
 
 fn create_physical_resource_counts(
-    result: &PhysicalResourceEstimationResult<Protocol, TFactory, impl Overhead + LayoutReportData>,
+    result: &PhysicalResourceEstimationResult<
+        Protocol,
+        TFactory,
+        MagicStateOverhead<impl Overhead + LayoutReportData>,
+    >,
 ) -> PhysicalResourceCounts {
     let breakdown = create_physical_resource_counts_breakdown(result);
 
@@ -160,7 +190,11 @@ fn create_physical_resource_counts(
 }
 
 fn create_physical_resource_counts_breakdown(
-    result: &PhysicalResourceEstimationResult<Protocol, TFactory, impl Overhead + LayoutReportData>,
+    result: &PhysicalResourceEstimationResult<
+        Protocol,
+        TFactory,
+        MagicStateOverhead<impl Overhead + LayoutReportData>,
+    >,
 ) -> PhysicalResourceCountsBreakdown {
     let num_ts_per_rotation = result
         .layout_overhead()
@@ -189,9 +223,35 @@ fn create_physical_resource_counts_breakdown(
             .logical_patch()
             .physical_qubit()
             .clifford_error_rate(),
+        ccz_factories: create_magic_state_factory_counts(result, MagicStateType::Ccz),
+        rotation_factories: create_magic_state_factory_counts(result, MagicStateType::Rotation),
     }
 }
 
+fn create_magic_state_factory_counts(
+    result: &PhysicalResourceEstimationResult<
+        Protocol,
+        TFactory,
+        MagicStateOverhead<impl Overhead + LayoutReportData>,
+    >,
+    magic_state_type: MagicStateType,
+) -> Option<MagicStateFactoryCounts> {
+    let index = result
+        .layout_overhead()
+        .magic_state_types()
+        .iter()
+        .position(|&t| t == magic_state_type)?;
+    let part = result.factory_parts()[index].as_ref();
+
+    Some(MagicStateFactoryCounts {
+        num_states: result.num_magic_states(index),
+        num_factories: part.map_or(0, FactoryPart::copies),
+        num_factory_runs: part.map_or(0, FactoryPart::runs),
+        physical_qubits: part.map_or(0, FactoryPart::physical_qubits),
+        required_logical_state_error_rate: part.map(FactoryPart::required_output_error_rate),
+    })
+}
+
 pub struct Failure {
     error: Error,
     batch_index: Option<usize>,
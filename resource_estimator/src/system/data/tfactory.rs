@@ -8,7 +8,7 @@ use super::super::{
     compiled_expression::CompiledExpression,
     error::IO::{self, CannotParseJSON},
     modeling::{
-        TFactoryDistillationUnitResources, TFactoryDistillationUnitTemplate,
+        MagicStateType, TFactoryDistillationUnitResources, TFactoryDistillationUnitTemplate,
         TFactoryDistillationUnitType, TFactoryFormula,
     },
     Error, Result,
@@ -18,7 +18,57 @@ use super::super::{
 pub struct TFactoryDistillationUnitSpecifications(Vec<TFactoryDistillationUnitSpecification>);
 
 impl TFactoryDistillationUnitSpecifications {
-    pub fn as_templates(&self) -> Result<Vec<TFactoryDistillationUnitTemplate>> {
+    /// Returns all templates that can be used in a factory for the given magic
+    /// state type.
+    ///
+    /// These are all T state distillation units (or the default ones, if none
+    /// are specified), and for magic states other than T, additionally all
+    /// distillation units that produce this magic state type.
+    pub fn as_templates(
+        &self,
+        magic_state_type: MagicStateType,
+    ) -> Result<Vec<TFactoryDistillationUnitTemplate>> {
+        let (mut templates, others): (Vec<_>, Vec<_>) = self
+            .all_templates()?
+            .into_iter()
+            .partition(|template| template.output_state_type.is_t());
+
+        if templates.is_empty() {
+            templates = TFactoryDistillationUnitTemplate::default_distillation_unit_templates();
+        }
+
+        if !magic_state_type.is_t() {
+            templates.extend(
+                others
+                    .into_iter()
+                    .filter(|template| template.output_state_type == magic_state_type),
+            );
+        }
+
+        Ok(templates)
+    }
+
+    /// Returns the magic state types for which factories need to be built.
+    ///
+    /// T states are always the first magic state type.  CCZ and rotation
+    /// states follow, if there are distillation units that produce them.
+    pub fn magic_state_types(&self) -> Result<Vec<MagicStateType>> {
+        let templates = self.all_templates()?;
+
+        let mut types = vec![MagicStateType::T];
+        for magic_state_type in [MagicStateType::Ccz, MagicStateType::Rotation] {
+            if templates
+                .iter()
+                .any(|template| template.output_state_type == magic_state_type)
+            {
+                types.push(magic_state_type);
+            }
+        }
+
+        Ok(types)
+    }
+
+    fn all_templates(&self) -> Result<Vec<TFactoryDistillationUnitTemplate>> {
         if self.is_empty() {
             Ok(TFactoryDistillationUnitTemplate::default_distillation_unit_templates())
         } else {
@@ -51,6 +101,9 @@ pub enum TFactoryDistillationUnitSpecification {
         num_input_ts: u64,
         /// The number of output t states generated by the distillation unit.
         num_output_ts: u64,
+        /// The type of magic state generated by the distillation unit.
+        #[serde(default, skip_serializing_if = "MagicStateType::is_t")]
+        output_state_type: MagicStateType,
         /// The failure probability formula.
         failure_probability_formula: String,
         /// The output error rate formula.
@@ -80,6 +133,7 @@ impl TryFrom<&TFactoryDistillationUnitSpecification> for TFactoryDistillationUni
                 display_name,
                 num_input_ts,
                 num_output_ts,
+                output_state_type,
                 failure_probability_formula,
                 output_error_rate_formula,
                 physical_qubit_specification,
@@ -159,6 +213,7 @@ impl TryFrom<&TFactoryDistillationUnitSpecification> for TFactoryDistillationUni
                     name: display_name.clone(),
                     num_input_ts: *num_input_ts,
                     num_output_ts: *num_output_ts,
+                    output_state_type: *output_state_type,
                     failure_probability_function,
                     output_error_rate_function,
                     unit_type,
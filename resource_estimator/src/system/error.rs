@@ -4,6 +4,8 @@
 use miette::Diagnostic;
 use thiserror::Error;
 
+use super::modeling::MagicStateType;
+
 #[derive(Debug, Error, Diagnostic)]
 pub enum IO {
     /// Cannot open a filename that is passed by string
@@ -81,6 +83,15 @@ pub enum InvalidInput {
         "Qsc.Estimates.InvalidInputError.ConstraintsProvidedForFrontierEstimation"
     ))]
     ConstraintsProvidedForFrontierEstimation,
+    /// A factory builder for CCZ or rotation states is given no distillation
+    /// unit that produces them.  There is no predefined distillation unit for
+    /// rotation states, so one must always be specified.
+    ///
+    /// ✅ This does not contain user data and can be logged
+    /// 🧑‍💻 This indicates a user error
+    #[error("no distillation unit produces {0} states")]
+    #[diagnostic(code("Qsc.Estimates.InvalidInputError.MissingDistillationUnit"))]
+    MissingDistillationUnit(MagicStateType),
 }
 
 #[derive(Debug, Error, Diagnostic)]
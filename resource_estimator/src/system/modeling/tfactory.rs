@@ -7,7 +7,7 @@ mod tests;
 use core::fmt;
 use std::{collections::BTreeMap, fmt::Display, vec};
 
-use serde::{ser::SerializeMap, Serialize};
+use serde::{ser::SerializeMap, Deserialize, Serialize};
 
 use crate::estimates::{
     DistillationRound, DistillationUnit, Factory, LogicalPatch, RoundBasedFactory,
@@ -115,6 +115,39 @@ impl From<CompiledExpression> for TFactoryFormula {
     }
 }
 
+/// The type of magic state that is produced by a distillation unit.
+///
+/// Distillation units that produce CCZ or rotation states consume T states as
+/// inputs, and are therefore only used in the last round of a factory.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Deserialize, Serialize)]
+pub enum MagicStateType {
+    #[default]
+    T,
+    #[serde(rename = "CCZ")]
+    Ccz,
+    #[serde(rename = "rotation")]
+    Rotation,
+}
+
+impl MagicStateType {
+    // takes a reference, since it is used in `skip_serializing_if`
+    #[allow(clippy::trivially_copy_pass_by_ref)]
+    #[must_use]
+    pub fn is_t(&self) -> bool {
+        *self == Self::T
+    }
+}
+
+impl Display for MagicStateType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MagicStateType::T => f.write_str("T"),
+            MagicStateType::Ccz => f.write_str("CCZ"),
+            MagicStateType::Rotation => f.write_str("rotation"),
+        }
+    }
+}
+
 #[derive(Copy, Clone, Eq, PartialEq)]
 pub enum TFactoryDistillationUnitType {
     Logical,
@@ -136,6 +169,8 @@ pub struct TFactoryDistillationUnitTemplate {
     pub(crate) num_input_ts: u64,
     /// The number of output t states generated by the distillation unit.
     pub(crate) num_output_ts: u64,
+    /// The type of magic state that is generated by the distillation unit.
+    pub(crate) output_state_type: MagicStateType,
     /// The failure probability formula expression.
     pub(crate) failure_probability_function: TFactoryFormula,
     /// The output error rate formula expression.
@@ -163,6 +198,9 @@ impl TFactoryDistillationUnitTemplate {
             | "15-to-1 space efficient" => {
                 Ok(Self::create_distillation_unit_15_to_1_rm_space_efficient_template())
             }
+            "8-to-CCZ" | "8T-to-CCZ" | "8-T-to-CCZ" => {
+                Ok(Self::create_distillation_unit_8_t_to_ccz_template())
+            }
             _ => Err(CannotParseJSON(serde::de::Error::custom(format!(
                 "Invalid distillation unit specification name: {name}."
             )))),
@@ -174,6 +212,7 @@ impl TFactoryDistillationUnitTemplate {
             name: String::from("15-to-1 RM prep"),
             num_input_ts: 15,
             num_output_ts: 1,
+            output_state_type: MagicStateType::T,
             failure_probability_function: Box::new(Self::failure_probability),
             output_error_rate_function: Box::new(Self::output_error_rate),
             unit_type: TFactoryDistillationUnitType::Combined,
@@ -195,6 +234,7 @@ impl TFactoryDistillationUnitTemplate {
             name: String::from("15-to-1 space efficient"),
             num_input_ts: 15,
             num_output_ts: 1,
+            output_state_type: MagicStateType::T,
             failure_probability_function: Box::new(Self::failure_probability),
             output_error_rate_function: Box::new(Self::output_error_rate),
             unit_type: TFactoryDistillationUnitType::Combined,
@@ -210,11 +250,33 @@ impl TFactoryDistillationUnitTemplate {
         }
     }
 
+    /// CCZ factory that consumes 8 T states to produce one CCZ state, based on
+    /// [[arXiv:1812.01238](https://arxiv.org/abs/1812.01238)].  The unit only
+    /// operates on logical qubits.
+    pub fn create_distillation_unit_8_t_to_ccz_template() -> Self {
+        Self {
+            name: String::from("8-to-CCZ"),
+            num_input_ts: 8,
+            num_output_ts: 1,
+            output_state_type: MagicStateType::Ccz,
+            failure_probability_function: Box::new(Self::ccz_failure_probability),
+            output_error_rate_function: Box::new(Self::ccz_output_error_rate),
+            unit_type: TFactoryDistillationUnitType::Logical,
+            physical_qubit_specification: None,
+            logical_qubit_specification: Some(TFactoryDistillationUnitResources {
+                num_unit_qubits: 36,
+                duration_in_qubit_cycle_time: 6,
+            }),
+            logical_qubit_specification_first_round_override: None,
+        }
+    }
+
     pub fn create_trivial_distillation_unit_1_to_1() -> Self {
         Self {
             name: String::from("trivial 1-to-1"),
             num_input_ts: 1,
             num_output_ts: 1,
+            output_state_type: MagicStateType::T,
             failure_probability_function: Box::new(Self::trivial_failure_probability),
             output_error_rate_function: Box::new(Self::trivial_error_rate),
             unit_type: TFactoryDistillationUnitType::Logical,
@@ -243,6 +305,24 @@ impl TFactoryDistillationUnitTemplate {
         35.0 * input_error_rate.powi(3) + 7.1 * clifford_error_rate
     }
 
+    fn ccz_failure_probability(
+        input_error_rate: f64,
+        clifford_error_rate: f64,
+        _readout_error_rate: f64,
+    ) -> f64 {
+        // Any single faulty input T state is detected and leads to a failure.
+        8.0 * input_error_rate + 120.0 * clifford_error_rate
+    }
+
+    fn ccz_output_error_rate(
+        input_error_rate: f64,
+        clifford_error_rate: f64,
+        _readout_error_rate: f64,
+    ) -> f64 {
+        // Any pair of faulty input T states remains undetected.
+        28.0 * input_error_rate.powi(2) + 3.0 * clifford_error_rate
+    }
+
     fn trivial_failure_probability(
         _input_error_rate: f64,
         _clifford_error_rate: f64,
@@ -271,6 +351,7 @@ pub struct TFactoryDistillationUnit<'a> {
     unit_type: TFactoryDistillationUnitType,
     num_input_ts: u64,
     num_output_ts: u64,
+    output_state_type: MagicStateType,
     physical_qubits_at_first_round: u64,
     physical_qubits_at_subsequent_rounds: u64,
     duration_at_first_round: u64,
@@ -292,6 +373,7 @@ impl<'a> fmt::Debug for TFactoryDistillationUnit<'a> {
             .field("unit_type", &self.unit_type.to_string())
             .field("num_input_ts", &self.num_input_ts)
             .field("num_output_ts", &self.num_output_ts)
+            .field("output_state_type", &self.output_state_type)
             .field(
                 "physical_qubits_at_first_round",
                 &self.physical_qubits_at_first_round,
@@ -383,6 +465,7 @@ impl<'a> TFactoryDistillationUnit<'a> {
             unit_type: template.unit_type,
             num_input_ts,
             num_output_ts,
+            output_state_type: template.output_state_type,
             physical_qubits_at_first_round,
             physical_qubits_at_subsequent_rounds,
             duration_at_first_round,
@@ -405,6 +488,10 @@ impl<'a> TFactoryDistillationUnit<'a> {
         self.qubit_t_error_rate
     }
 
+    pub fn output_state_type(&self) -> MagicStateType {
+        self.output_state_type
+    }
+
     pub fn is_valid(&self) -> bool {
         self.clifford_error_rate() <= 0.1 * self.qubit_t_error_rate
     }
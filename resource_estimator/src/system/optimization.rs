@@ -3,6 +3,10 @@
 
 mod code_distance_iterators;
 mod distillation_units_map;
+mod magic_state_factories;
 mod tfactory_exhaustive;
 
+pub use magic_state_factories::{
+    CczFactoryBuilder, MagicStateFactoryBuilder, RotationFactoryBuilder,
+};
 pub use tfactory_exhaustive::TFactoryBuilder;
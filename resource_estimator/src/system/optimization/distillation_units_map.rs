@@ -10,8 +10,8 @@ use std::rc::Rc;
 
 use crate::estimates::LogicalPatch;
 use crate::system::modeling::{
-    PhysicalQubit, Protocol, TFactoryDistillationUnit, TFactoryDistillationUnitTemplate,
-    TFactoryDistillationUnitType, TFactoryQubit,
+    MagicStateType, PhysicalQubit, Protocol, TFactoryDistillationUnit,
+    TFactoryDistillationUnitTemplate, TFactoryDistillationUnitType, TFactoryQubit,
 };
 
 pub struct DistillationUnitsMap<'a> {
@@ -24,6 +24,8 @@ pub struct DistillationUnitsMap<'a> {
     min_valid_code_distance_indexes: Vec<usize>,
     num_code_distances: usize,
     distances: Vec<u64>,
+    /// Output magic state type per unit index (combined, purely logical, purely physical)
+    output_state_types: Vec<MagicStateType>,
 }
 
 impl<'a> DistillationUnitsMap<'a> {
@@ -76,6 +78,16 @@ impl<'a> DistillationUnitsMap<'a> {
         let mut logical_distillation_unit_templates = combined_distillation_unit_templates;
         logical_distillation_unit_templates.append(&mut purely_logical_distillation_unit_templates);
 
+        let output_state_types = logical_distillation_unit_templates
+            .iter()
+            .map(|template| template.output_state_type)
+            .chain(
+                physical_distillation_units[num_combined_distillation_units..]
+                    .iter()
+                    .map(TFactoryDistillationUnit::output_state_type),
+            )
+            .collect();
+
         let mut logical_distillation_units: Vec<Vec<TFactoryDistillationUnit>> = Vec::new();
 
         for qubit in qubits {
@@ -123,6 +135,7 @@ impl<'a> DistillationUnitsMap<'a> {
             min_valid_code_distance_indexes,
             num_code_distances,
             distances,
+            output_state_types,
         }
     }
 
@@ -221,6 +234,20 @@ impl<'a> DistillationUnitsMap<'a> {
             .collect()
     }
 
+    /// Checks whether a sequence of distillation units produces the given magic
+    /// state type.  All units but the last one must produce T states, which
+    /// are the inputs to the subsequent round.
+    pub fn produces_magic_state_type(
+        &self,
+        indexes: &[usize],
+        magic_state_type: MagicStateType,
+    ) -> bool {
+        indexes.split_last().is_some_and(|(&last, rest)| {
+            self.output_state_types[last] == magic_state_type
+                && rest.iter().all(|&idx| self.output_state_types[idx].is_t())
+        })
+    }
+
     pub fn iterate_for_all_distillation_units<F>(&self, num_rounds: usize, action: &mut F)
     where
         F: FnMut(&[usize]),
@@ -12,8 +12,8 @@ use super::super::super::{
         TFactoryProtocolSpecificDistillationUnitSpecification,
     },
     modeling::{
-        PhysicalQubit, Protocol, TFactoryDistillationUnit, TFactoryDistillationUnitTemplate,
-        TFactoryDistillationUnitType,
+        MagicStateType, PhysicalQubit, Protocol, TFactoryDistillationUnit,
+        TFactoryDistillationUnitTemplate, TFactoryDistillationUnitType,
     },
 };
 
@@ -109,6 +109,7 @@ fn create_distillation_unit_template(
         display_name: name.to_owned(),
         num_input_ts: 1,
         num_output_ts: 1,
+        output_state_type: MagicStateType::T,
         failure_probability_formula: "0.5 * inputErrorRate".to_owned(),
         output_error_rate_formula: "0.5 * inputErrorRate".to_owned(),
         physical_qubit_specification,
@@ -125,6 +126,7 @@ fn create_distillation_unit_template_with_override() -> TFactoryDistillationUnit
         display_name: "combined with override".to_owned(),
         num_input_ts: 1,
         num_output_ts: 1,
+        output_state_type: MagicStateType::T,
         failure_probability_formula: "0.5 * inputErrorRate".to_owned(),
         output_error_rate_formula: "0.5 * inputErrorRate".to_owned(),
         physical_qubit_specification: Some(TFactoryProtocolSpecificDistillationUnitSpecification {
@@ -152,6 +154,7 @@ fn create_distillation_unit_template_without_override() -> TFactoryDistillationU
         display_name: "combined without override".to_owned(),
         num_input_ts: 1,
         num_output_ts: 1,
+        output_state_type: MagicStateType::T,
         failure_probability_formula: "0.5 * inputErrorRate".to_owned(),
         output_error_rate_formula: "0.5 * inputErrorRate".to_owned(),
         physical_qubit_specification: Some(TFactoryProtocolSpecificDistillationUnitSpecification {
@@ -0,0 +1,224 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+#[cfg(test)]
+mod tests;
+
+use std::borrow::Cow;
+use std::rc::Rc;
+
+use crate::estimates::FactoryBuilder;
+use crate::system::error::InvalidInput;
+use crate::system::modeling::{
+    MagicStateType, PhysicalQubit, Protocol, TFactory, TFactoryDistillationUnitTemplate,
+};
+use crate::system::Error;
+
+use super::tfactory_exhaustive::find_nondominated_factories;
+use super::TFactoryBuilder;
+
+/// Builds factories that produce CCZ states
+///
+/// The distillation unit templates must contain T state distillation units and
+/// at least one unit that produces CCZ states.  The latter is only used in the
+/// last round of a factory.
+pub struct CczFactoryBuilder {
+    distillation_unit_templates: Vec<TFactoryDistillationUnitTemplate>,
+    max_distillation_rounds: u64,
+}
+
+impl CczFactoryBuilder {
+    /// Fails if none of the distillation unit templates produces CCZ states.
+    pub fn new(
+        distillation_unit_templates: Vec<TFactoryDistillationUnitTemplate>,
+        max_distillation_rounds: u64,
+    ) -> Result<Self, Error> {
+        check_produces(&distillation_unit_templates, MagicStateType::Ccz)?;
+        Ok(Self {
+            distillation_unit_templates,
+            max_distillation_rounds,
+        })
+    }
+}
+
+impl FactoryBuilder<Protocol> for CczFactoryBuilder {
+    type Factory = TFactory;
+
+    fn find_factories(
+        &self,
+        ftp: &Protocol,
+        qubit: &Rc<PhysicalQubit>,
+        _magic_state_type: usize,
+        output_error_rate: f64,
+        max_code_distance: &u64,
+    ) -> Option<Vec<Cow<Self::Factory>>> {
+        Some(find_nondominated_factories(
+            ftp,
+            qubit,
+            &self.distillation_unit_templates,
+            MagicStateType::Ccz,
+            output_error_rate,
+            *max_code_distance,
+            self.max_distillation_rounds,
+        ))
+    }
+}
+
+/// Builds factories that directly produce states for arbitrary angle rotations
+///
+/// The distillation unit templates must contain T state distillation units and
+/// at least one unit that produces rotation states.  The latter is only used in
+/// the last round of a factory.  There is no predefined distillation unit for
+/// rotation states, so it must be given as a custom distillation unit
+/// specification.
+pub struct RotationFactoryBuilder {
+    distillation_unit_templates: Vec<TFactoryDistillationUnitTemplate>,
+    max_distillation_rounds: u64,
+}
+
+impl RotationFactoryBuilder {
+    /// Fails if none of the distillation unit templates produces rotation states.
+    pub fn new(
+        distillation_unit_templates: Vec<TFactoryDistillationUnitTemplate>,
+        max_distillation_rounds: u64,
+    ) -> Result<Self, Error> {
+        check_produces(&distillation_unit_templates, MagicStateType::Rotation)?;
+        Ok(Self {
+            distillation_unit_templates,
+            max_distillation_rounds,
+        })
+    }
+}
+
+impl FactoryBuilder<Protocol> for RotationFactoryBuilder {
+    type Factory = TFactory;
+
+    fn find_factories(
+        &self,
+        ftp: &Protocol,
+        qubit: &Rc<PhysicalQubit>,
+        _magic_state_type: usize,
+        output_error_rate: f64,
+        max_code_distance: &u64,
+    ) -> Option<Vec<Cow<Self::Factory>>> {
+        Some(find_nondominated_factories(
+            ftp,
+            qubit,
+            &self.distillation_unit_templates,
+            MagicStateType::Rotation,
+            output_error_rate,
+            *max_code_distance,
+            self.max_distillation_rounds,
+        ))
+    }
+}
+
+fn check_produces(
+    distillation_unit_templates: &[TFactoryDistillationUnitTemplate],
+    magic_state_type: MagicStateType,
+) -> Result<(), Error> {
+    if distillation_unit_templates
+        .iter()
+        .any(|template| template.output_state_type == magic_state_type)
+    {
+        Ok(())
+    } else {
+        Err(InvalidInput::MissingDistillationUnit(magic_state_type).into())
+    }
+}
+
+/// Dispatches magic state indexes to the factory builders of the available
+/// magic state types
+///
+/// T states always have index 0, followed by CCZ states and rotation states,
+/// if builders for them are provided.  No factories are found for other
+/// indexes.
+pub struct MagicStateFactoryBuilder {
+    t_factory_builder: TFactoryBuilder,
+    ccz_factory_builder: Option<CczFactoryBuilder>,
+    rotation_factory_builder: Option<RotationFactoryBuilder>,
+    magic_state_types: Vec<MagicStateType>,
+}
+
+impl MagicStateFactoryBuilder {
+    #[must_use]
+    pub fn new(t_factory_builder: TFactoryBuilder) -> Self {
+        Self {
+            t_factory_builder,
+            ccz_factory_builder: None,
+            rotation_factory_builder: None,
+            magic_state_types: vec![MagicStateType::T],
+        }
+    }
+
+    #[must_use]
+    pub fn with_ccz_factory_builder(mut self, builder: CczFactoryBuilder) -> Self {
+        self.ccz_factory_builder = Some(builder);
+        self.update_magic_state_types();
+        self
+    }
+
+    #[must_use]
+    pub fn with_rotation_factory_builder(mut self, builder: RotationFactoryBuilder) -> Self {
+        self.rotation_factory_builder = Some(builder);
+        self.update_magic_state_types();
+        self
+    }
+
+    /// The magic state types in the order of their magic state index
+    #[must_use]
+    pub fn magic_state_types(&self) -> &[MagicStateType] {
+        &self.magic_state_types
+    }
+
+    fn update_magic_state_types(&mut self) {
+        self.magic_state_types = vec![MagicStateType::T];
+        if self.ccz_factory_builder.is_some() {
+            self.magic_state_types.push(MagicStateType::Ccz);
+        }
+        if self.rotation_factory_builder.is_some() {
+            self.magic_state_types.push(MagicStateType::Rotation);
+        }
+    }
+}
+
+impl FactoryBuilder<Protocol> for MagicStateFactoryBuilder {
+    type Factory = TFactory;
+
+    fn find_factories(
+        &self,
+        ftp: &Protocol,
+        qubit: &Rc<PhysicalQubit>,
+        magic_state_type: usize,
+        output_error_rate: f64,
+        max_code_distance: &u64,
+    ) -> Option<Vec<Cow<Self::Factory>>> {
+        match self.magic_state_types.get(magic_state_type)? {
+            MagicStateType::T => self.t_factory_builder.find_factories(
+                ftp,
+                qubit,
+                magic_state_type,
+                output_error_rate,
+                max_code_distance,
+            ),
+            MagicStateType::Ccz => self.ccz_factory_builder.as_ref()?.find_factories(
+                ftp,
+                qubit,
+                magic_state_type,
+                output_error_rate,
+                max_code_distance,
+            ),
+            MagicStateType::Rotation => self.rotation_factory_builder.as_ref()?.find_factories(
+                ftp,
+                qubit,
+                magic_state_type,
+                output_error_rate,
+                max_code_distance,
+            ),
+        }
+    }
+
+    fn num_magic_state_types(&self) -> usize {
+        self.magic_state_types.len()
+    }
+}
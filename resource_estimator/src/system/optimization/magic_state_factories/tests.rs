@@ -0,0 +1,66 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use std::rc::Rc;
+
+use crate::{
+    estimates::FactoryBuilder,
+    system::{
+        constants::MAX_DISTILLATION_ROUNDS,
+        modeling::{PhysicalQubit, Protocol, TFactoryDistillationUnitTemplate},
+        optimization::TFactoryBuilder,
+    },
+};
+
+use super::{CczFactoryBuilder, MagicStateFactoryBuilder, RotationFactoryBuilder};
+
+fn t_factory_builder() -> TFactoryBuilder {
+    TFactoryBuilder::new(
+        TFactoryDistillationUnitTemplate::default_distillation_unit_templates(),
+        MAX_DISTILLATION_ROUNDS,
+    )
+}
+
+#[test]
+fn unconfigured_magic_state_type_has_no_factories() {
+    let builder = MagicStateFactoryBuilder::new(t_factory_builder());
+
+    let factories = builder.find_factories(
+        &Protocol::default(),
+        &Rc::new(PhysicalQubit::default()),
+        1,
+        1e-10,
+        &35,
+    );
+
+    assert!(factories.is_none());
+}
+
+#[test]
+fn ccz_factory_builder_requires_ccz_distillation_unit() {
+    let mut templates = TFactoryDistillationUnitTemplate::default_distillation_unit_templates();
+    assert!(CczFactoryBuilder::new(
+        TFactoryDistillationUnitTemplate::default_distillation_unit_templates(),
+        MAX_DISTILLATION_ROUNDS
+    )
+    .is_err());
+
+    templates
+        .push(TFactoryDistillationUnitTemplate::create_distillation_unit_8_t_to_ccz_template());
+    assert!(CczFactoryBuilder::new(templates, MAX_DISTILLATION_ROUNDS).is_ok());
+}
+
+#[test]
+fn rotation_factory_builder_requires_rotation_distillation_unit() {
+    let error = RotationFactoryBuilder::new(
+        TFactoryDistillationUnitTemplate::default_distillation_unit_templates(),
+        MAX_DISTILLATION_ROUNDS,
+    )
+    .err()
+    .expect("builder should fail without rotation distillation unit");
+
+    assert_eq!(
+        error.to_string(),
+        "no distillation unit produces rotation states"
+    );
+}
@@ -6,7 +6,8 @@ use std::fmt::Display;
 use std::rc::Rc;
 
 use crate::system::modeling::{
-    PhysicalQubit, Protocol, TFactory, TFactoryDistillationUnit, TFactoryDistillationUnitTemplate,
+    MagicStateType, PhysicalQubit, Protocol, TFactory, TFactoryDistillationUnit,
+    TFactoryDistillationUnitTemplate,
 };
 use crate::{
     estimates::{
@@ -217,13 +218,36 @@ pub(crate) fn find_nondominated_tfactories<'a>(
     max_code_distance: u64,
     max_distillation_rounds: u64,
 ) -> Vec<Cow<'a, TFactory>> {
-    let points = find_nondominated_population::<Point2D<TFactory>>(
+    find_nondominated_factories(
         ftp,
         qubit,
         distillation_unit_templates,
+        MagicStateType::T,
         output_t_error_rate,
         max_code_distance,
         max_distillation_rounds,
+    )
+}
+
+/// Finds non-dominated factories whose last distillation round produces the
+/// given magic state type.
+pub(crate) fn find_nondominated_factories<'a>(
+    ftp: &Protocol,
+    qubit: &Rc<PhysicalQubit>,
+    distillation_unit_templates: &[TFactoryDistillationUnitTemplate],
+    magic_state_type: MagicStateType,
+    output_error_rate: f64,
+    max_code_distance: u64,
+    max_distillation_rounds: u64,
+) -> Vec<Cow<'a, TFactory>> {
+    let points = find_nondominated_population::<Point2D<TFactory>>(
+        ftp,
+        qubit,
+        distillation_unit_templates,
+        magic_state_type,
+        output_error_rate,
+        max_code_distance,
+        max_distillation_rounds,
     );
 
     points
@@ -237,6 +261,7 @@ fn find_nondominated_population<P>(
     ftp: &Protocol,
     qubit: &Rc<PhysicalQubit>,
     distillation_unit_templates: &[TFactoryDistillationUnitTemplate],
+    magic_state_type: MagicStateType,
     output_t_error_rate: f64,
     max_code_distance: u64,
     max_distillation_rounds: u64,
@@ -247,7 +272,9 @@ where
     let min_code_distance = 1;
     let distances: Vec<_> = (min_code_distance..=max_code_distance).step_by(2).collect();
 
-    if output_t_error_rate > qubit.t_gate_error_rate() {
+    // Physical T states are good enough, but other magic states always require
+    // at least one round to be produced from T states.
+    if magic_state_type.is_t() && output_t_error_rate > qubit.t_gate_error_rate() {
         let mut population = Population::<P>::new();
 
         if let Ok(logical_qubit) = LogicalPatch::new(ftp, max_code_distance, qubit.clone()) {
@@ -278,12 +305,22 @@ where
     let mut searcher = TFactoryExhaustiveSearch::<P>::new(output_t_error_rate);
 
     for num_rounds in 1..=max_distillation_rounds {
-        process_for_num_rounds(&mut searcher, &distillation_units_map, num_rounds as usize);
+        process_for_num_rounds(
+            &mut searcher,
+            &distillation_units_map,
+            magic_state_type,
+            num_rounds as usize,
+        );
     }
 
     if searcher.frontier_factories.items().is_empty() || P::ITERATE_MAX_NUM_ROUNDS {
         for num_rounds in max_distillation_rounds + 1..=MAX_EXTRA_DISTILLATION_ROUNDS {
-            process_for_num_rounds(&mut searcher, &distillation_units_map, num_rounds as usize);
+            process_for_num_rounds(
+                &mut searcher,
+                &distillation_units_map,
+                magic_state_type,
+                num_rounds as usize,
+            );
         }
     }
 
@@ -296,12 +333,15 @@ where
 fn process_for_num_rounds<P>(
     searcher: &mut TFactoryExhaustiveSearch<P>,
     distillation_units_map: &DistillationUnitsMap,
+    magic_state_type: MagicStateType,
     num_rounds: usize,
 ) where
     P: Point + Ord + From<TFactory> + TFactoryExhaustiveSearchOptions,
 {
     distillation_units_map.iterate_for_all_distillation_units(num_rounds, &mut |unit_indexes| {
-        process_for_specifications_combination(searcher, distillation_units_map, unit_indexes);
+        if distillation_units_map.produces_magic_state_type(unit_indexes, magic_state_type) {
+            process_for_specifications_combination(searcher, distillation_units_map, unit_indexes);
+        }
     });
 }
 
@@ -9,7 +9,7 @@ use crate::{
 use super::{
     super::super::{
         data,
-        modeling::{PhysicalQubit, Protocol, TFactoryDistillationUnitTemplate},
+        modeling::{MagicStateType, PhysicalQubit, Protocol, TFactoryDistillationUnitTemplate},
     },
     find_nondominated_population, find_nondominated_tfactories,
 };
@@ -142,6 +142,7 @@ fn required_logical_tstate_error_too_high() {
         &ftp,
         &qubit,
         &distillation_unit_templates,
+        MagicStateType::T,
         output_t_error_rate,
         max_code_distance,
         MAX_DISTILLATION_ROUNDS,
@@ -209,6 +210,7 @@ fn create_custom_distillation_unit_1_to_15_template(
         display_name: name.to_string(),
         num_input_ts: 15,
         num_output_ts: 1,
+        output_state_type: MagicStateType::T,
         failure_probability_formula: String::from(
             "15.0 * inputErrorRate + 356.0 * cliffordErrorRate",
         ),
@@ -101,6 +101,115 @@ fn physical_estimates_crash() {
         .contains(r#""status":"success"#));
 }
 
+#[test]
+fn estimate_with_ccz_factories() {
+    let logical_resources = LogicalResourceCounts {
+        num_qubits: 12,
+        t_count: 100,
+        rotation_count: 0,
+        rotation_depth: 0,
+        ccz_count: 1000,
+        ccix_count: 24,
+        measurement_count: 50,
+    };
+
+    let params: &str = r#"[{
+        "distillationUnitSpecifications": [{"name": "15-1 RM"}, {"name": "8-to-CCZ"}]
+    }]"#;
+    let result = estimate_physical_resources(logical_resources, params);
+
+    let json_value: Vec<Value> =
+        serde_json::from_str(&result.expect("result is err")).expect("Failed to parse JSON");
+    let map = json_value[0].as_object().expect("Failed build map");
+    assert_eq!(map["status"], json!("success"));
+    assert!(map.contains_key("tfactory"));
+    assert!(map.contains_key("cczFactory"));
+    assert!(!map.contains_key("rotationFactory"));
+
+    let breakdown = &map["physicalCounts"]["breakdown"];
+    assert_eq!(breakdown["numTstates"], json!(100));
+    assert_eq!(breakdown["cczFactories"]["numStates"], json!(1024));
+    assert!(breakdown.get("rotationFactories").is_none());
+
+    let unit_names = map["cczFactory"]["unitNamePerRound"]
+        .as_array()
+        .expect("unit names should be an array");
+    assert_eq!(unit_names.last(), Some(&json!("8-to-CCZ")));
+    assert!(unit_names[..unit_names.len() - 1]
+        .iter()
+        .all(|name| name == &json!("15-to-1 RM prep")));
+
+    let titles: Vec<_> = map["reportData"]["groups"]
+        .as_array()
+        .expect("groups should be an array")
+        .iter()
+        .map(|group| group["title"].clone())
+        .collect();
+    assert!(titles.contains(&json!("T factory parameters")));
+    assert!(titles.contains(&json!("CCZ factory parameters")));
+}
+
+#[test]
+fn estimate_with_rotation_factories() {
+    let logical_resources = LogicalResourceCounts {
+        num_qubits: 12,
+        t_count: 0,
+        rotation_count: 500,
+        rotation_depth: 100,
+        ccz_count: 0,
+        ccix_count: 0,
+        measurement_count: 50,
+    };
+
+    let params: &str = r#"[{
+        "distillationUnitSpecifications": [{
+            "displayName": "rotation 1-to-1",
+            "numInputTs": 1,
+            "numOutputTs": 1,
+            "outputStateType": "rotation",
+            "failureProbabilityFormula": "inputErrorRate + 10 * cliffordErrorRate",
+            "outputErrorRateFormula": "inputErrorRate / 10 + cliffordErrorRate",
+            "logicalQubitSpecification": {"numUnitQubits": 2, "durationInQubitCycleTime": 3}
+        }]
+    }]"#;
+    let result = estimate_physical_resources(logical_resources, params);
+
+    let json_value: Vec<Value> =
+        serde_json::from_str(&result.expect("result is err")).expect("Failed to parse JSON");
+    let map = json_value[0].as_object().expect("Failed build map");
+    assert_eq!(map["status"], json!("success"));
+    assert!(!map.contains_key("tfactory"));
+    assert!(map.contains_key("rotationFactory"));
+
+    let breakdown = &map["physicalCounts"]["breakdown"];
+    assert_eq!(breakdown["numTstates"], json!(0));
+    assert_eq!(breakdown["rotationFactories"]["numStates"], json!(500));
+    assert_eq!(breakdown["numTsPerRotation"], Value::Null);
+}
+
+#[test]
+fn ccz_factories_not_supported_with_constraints() {
+    let logical_resources = LogicalResourceCounts {
+        num_qubits: 12,
+        t_count: 100,
+        ccz_count: 1000,
+        ..LogicalResourceCounts::default()
+    };
+
+    let params: &str = r#"[{
+        "distillationUnitSpecifications": [{"name": "8-to-CCZ"}],
+        "constraints": {"maxPhysicalQubits": 100000}
+    }]"#;
+    let result = estimate_physical_resources(logical_resources, params);
+
+    let json_value: Vec<Value> =
+        serde_json::from_str(&result.expect("result is err")).expect("Failed to parse JSON");
+    assert_eq!(
+        json_value[0]["code"],
+        json!("Qsc.Estimates.MultipleMagicStatesNotSupported")
+    );
+}
+
 #[derive(Clone)]
 struct TestLayoutOverhead {
     num_qubits: u64,
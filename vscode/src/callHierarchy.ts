@@ -0,0 +1,107 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+import {
+  ICallHierarchyCall,
+  ICallHierarchyItem,
+  ILanguageService,
+} from "qsharp-lang";
+import * as vscode from "vscode";
+import { toVscodeRange } from "./common";
+
+export function createCallHierarchyProvider(
+  languageService: ILanguageService,
+) {
+  return new QSharpCallHierarchyProvider(languageService);
+}
+
+/**
+ * Remembers the document that the call hierarchy was prepared in, since
+ * callables in dependencies don't belong to any open compilation of their own.
+ */
+class QSharpCallHierarchyItem extends vscode.CallHierarchyItem {
+  constructor(
+    public documentUri: string,
+    public item: ICallHierarchyItem,
+  ) {
+    super(
+      item.kind === "function"
+        ? vscode.SymbolKind.Function
+        : vscode.SymbolKind.Method,
+      item.name,
+      item.containerName ?? "",
+      vscode.Uri.parse(item.location.source),
+      toVscodeRange(item.location.span),
+      toVscodeRange(item.selectionRange),
+    );
+  }
+}
+
+class QSharpCallHierarchyProvider implements vscode.CallHierarchyProvider {
+  constructor(public languageService: ILanguageService) {}
+
+  async prepareCallHierarchy(
+    document: vscode.TextDocument,
+    position: vscode.Position,
+  ): Promise<vscode.CallHierarchyItem | undefined> {
+    const documentUri = document.uri.toString();
+    const item = await this.languageService.prepareCallHierarchy(
+      documentUri,
+      position,
+    );
+    return item && new QSharpCallHierarchyItem(documentUri, item);
+  }
+
+  async provideCallHierarchyIncomingCalls(
+    item: QSharpCallHierarchyItem,
+  ): Promise<vscode.CallHierarchyIncomingCall[]> {
+    const calls = await this.languageService.getIncomingCalls(
+      item.documentUri,
+      item.item,
+    );
+    return calls.map(
+      (call) =>
+        new vscode.CallHierarchyIncomingCall(
+          toVscodeCallHierarchyItem(item.documentUri, call),
+          call.fromRanges.map(toVscodeRange),
+        ),
+    );
+  }
+
+  async provideCallHierarchyOutgoingCalls(
+    item: QSharpCallHierarchyItem,
+  ): Promise<vscode.CallHierarchyOutgoingCall[]> {
+    const calls = await this.languageService.getOutgoingCalls(
+      item.documentUri,
+      item.item,
+    );
+    return calls.map(
+      (call) =>
+        new vscode.CallHierarchyOutgoingCall(
+          toVscodeCallHierarchyItem(item.documentUri, call),
+          call.fromRanges.map(toVscodeRange),
+        ),
+    );
+  }
+}
+
+/**
+ * Calls that apply functors are shown separately, with the functors
+ * prepended to the detail, e.g. "Controlled Adjoint · Microsoft.Quantum.Intrinsic".
+ */
+function toVscodeCallHierarchyItem(
+  documentUri: string,
+  call: ICallHierarchyCall,
+): QSharpCallHierarchyItem {
+  const item = new QSharpCallHierarchyItem(documentUri, call.item);
+  const functors = [
+    ...Array(call.controlled).fill("Controlled"),
+    ...(call.adjoint ? ["Adjoint"] : []),
+  ];
+  if (functors.length > 0) {
+    item.detail = [functors.join(" "), item.detail]
+      .filter((part) => part !== "")
+      .join(" · ");
+  }
+  return item;
+}
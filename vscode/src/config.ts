@@ -41,3 +41,16 @@ export function getTargetFriendlyName(targetProfile?: string) {
       return "Q#: invalid";
   }
 }
+
+export function getInlayHintsConfig() {
+  const config = vscode.workspace.getConfiguration("Q#.inlayHints");
+  return {
+    variableTypes: config.get<boolean>("variableTypes", true),
+    lambdaParameterTypes: config.get<boolean>("lambdaParameterTypes", true),
+    parameterNames: config.get<boolean>("parameterNames", true),
+    partialApplicationTypes: config.get<boolean>(
+      "partialApplicationTypes",
+      true,
+    ),
+  };
+}
@@ -10,6 +10,7 @@ import {
   ExitedEvent,
   Handles,
   InitializedEvent,
+  InvalidatedEvent,
   Logger,
   LoggingDebugSession,
   OutputEvent,
@@ -23,6 +24,7 @@ import {
 } from "@vscode/debugadapter";
 import { DebugProtocol } from "@vscode/debugprotocol";
 import {
+  IBreakpointOptions,
   IDebugServiceWorker,
   IStructStepResult,
   QscEventTarget,
@@ -71,7 +73,13 @@ export class QscDebugSession extends LoggingDebugSession {
 
   private breakpointLocations: Map<string, IBreakpointLocationData[]>;
   private breakpoints: Map<string, DebugProtocol.Breakpoint[]>;
-  private variableHandles = new Handles<"locals" | "quantum" | "circuit">();
+  /** Conditions and log messages of the source breakpoints, by file */
+  private breakpointOptions = new Map<string, IBreakpointOptions[]>();
+  private functionBreakpoints: DebugProtocol.Breakpoint[] = [];
+  private functionBreakpointOptions: IBreakpointOptions[] = [];
+  private variableHandles = new Handles<
+    { frameId: number } | "quantum" | "circuit"
+  >();
   private failureMessage: string;
   private eventTarget: QscEventTarget;
   private supportsVariableType = false;
@@ -118,6 +126,11 @@ export class QscDebugSession extends LoggingDebugSession {
     );
 
     if (failureMessage == "") {
+      if (this.config.checkpointInterval !== undefined) {
+        await this.debugService.setCheckpointInterval(
+          this.config.checkpointInterval,
+        );
+      }
       for (const [path, _contents] of this.program.packageGraphSources.root
         .sources) {
         const locations = await this.debugService.getBreakpoints(path);
@@ -175,7 +188,7 @@ export class QscDebugSession extends LoggingDebugSession {
     response.body.supportsConfigurationDoneRequest = true;
 
     // make VS Code show a 'step back' button
-    response.body.supportsStepBack = false;
+    response.body.supportsStepBack = this.config.checkpointInterval !== 0;
 
     // make VS Code support data breakpoints
     response.body.supportsDataBreakpoints = false;
@@ -196,6 +209,9 @@ export class QscDebugSession extends LoggingDebugSession {
     response.body.supportSuspendDebuggee = false;
     response.body.supportTerminateDebuggee = true;
     response.body.supportsFunctionBreakpoints = true;
+    response.body.supportsConditionalBreakpoints = true;
+    response.body.supportsHitConditionalBreakpoints = true;
+    response.body.supportsLogPoints = true;
     response.body.supportsRestartRequest = false;
 
     // make VS Code send the breakpointLocations request
@@ -207,7 +223,7 @@ export class QscDebugSession extends LoggingDebugSession {
     response.body.supportsCancelRequest = false;
 
     // make VS Code use 'evaluate' when hovering over source
-    response.body.supportsEvaluateForHovers = false;
+    response.body.supportsEvaluateForHovers = true;
 
     response.body.supportsDelayedStackTraceLoading = false;
 
@@ -363,6 +379,17 @@ export class QscDebugSession extends LoggingDebugSession {
     );
   }
 
+  private async stepBack(): Promise<void> {
+    await this.eval_step(async () => await this.debugService.evalStepBack());
+  }
+
+  private async reverseContinue(): Promise<void> {
+    const bps = this.getBreakpointIds();
+    await this.eval_step(
+      async () => await this.debugService.evalReverseContinue(bps),
+    );
+  }
+
   private async stepOut(): Promise<void> {
     const bps = this.getBreakpointIds();
     await this.eval_step(
@@ -423,7 +450,10 @@ export class QscDebugSession extends LoggingDebugSession {
 
   private getBreakpointIds(): number[] {
     const bps: number[] = [];
-    for (const file_bps of this.breakpoints.values()) {
+    for (const file_bps of [
+      ...this.breakpoints.values(),
+      this.functionBreakpoints,
+    ]) {
       for (const bp of file_bps) {
         if (bp?.id != null) {
           bps.push(bp.id);
@@ -434,6 +464,14 @@ export class QscDebugSession extends LoggingDebugSession {
     return bps;
   }
 
+  private async updateBreakpointOptions(): Promise<void> {
+    const options = [
+      ...[...this.breakpointOptions.values()].flat(),
+      ...this.functionBreakpointOptions,
+    ];
+    await this.debugService.setBreakpointOptions(options);
+  }
+
   protected async continueRequest(
     response: DebugProtocol.ContinueResponse,
     args: DebugProtocol.ContinueArguments,
@@ -479,6 +517,28 @@ export class QscDebugSession extends LoggingDebugSession {
     await this.stepOut();
   }
 
+  protected async stepBackRequest(
+    response: DebugProtocol.StepBackResponse,
+    args: DebugProtocol.StepBackArguments,
+    request?: DebugProtocol.Request,
+  ): Promise<void> {
+    log.trace(`stepBackRequest: %O`, args);
+    this.sendResponse(response);
+
+    await this.stepBack();
+  }
+
+  protected async reverseContinueRequest(
+    response: DebugProtocol.ReverseContinueResponse,
+    args: DebugProtocol.ReverseContinueArguments,
+    request?: DebugProtocol.Request,
+  ): Promise<void> {
+    log.trace(`reverseContinueRequest: %O`, args);
+    this.sendResponse(response);
+
+    await this.reverseContinue();
+  }
+
   protected async breakpointLocationsRequest(
     response: DebugProtocol.BreakpointLocationsResponse,
     args: DebugProtocol.BreakpointLocationsArguments,
@@ -597,6 +657,7 @@ export class QscDebugSession extends LoggingDebugSession {
       range: vscode.Range;
       isLineBreakpoint: boolean;
       uiLine: number;
+      sourceBreakpoint: DebugProtocol.SourceBreakpoint;
     }[] = (args.breakpoints ?? [])
       .filter(
         (sourceBreakpoint) =>
@@ -616,12 +677,14 @@ export class QscDebugSession extends LoggingDebugSession {
           range: new vscode.Range(startPos, lineRange.end),
           isLineBreakpoint,
           uiLine: sourceBreakpoint.line,
+          sourceBreakpoint,
         };
       });
 
     // Now that we have the mapped breakpoint span, get the actual breakpoints
     // with corresponding ids from the debugger
     const bps = [];
+    const options: IBreakpointOptions[] = [];
 
     for (const bpOffset of desiredBpOffsets) {
       const lo = bpOffset.range.start;
@@ -636,19 +699,26 @@ export class QscDebugSession extends LoggingDebugSession {
       // matching location. Otherwise, we need to check if the desired column
       // is within the range of the location.
       for (const location of matchingLocations) {
-        if (isLineBreakpoint) {
-          bps.push(location.breakpoint);
-        } else {
+        if (
+          isLineBreakpoint ||
           // column bp just has end of selection or cursor location in lo
-          if (location.range.contains(lo)) {
-            bps.push(location.breakpoint);
-          }
+          location.range.contains(lo)
+        ) {
+          bps.push(location.breakpoint);
+          options.push(
+            this.createBreakpointOptions(
+              location.breakpoint.id,
+              bpOffset.sourceBreakpoint,
+            ),
+          );
         }
       }
     }
 
     // Update our breakpoint list for the given file
     this.breakpoints.set(doc.uri.toString(), bps);
+    this.breakpointOptions.set(doc.uri.toString(), options);
+    await this.updateBreakpointOptions();
 
     response.body = {
       breakpoints: bps,
@@ -658,6 +728,90 @@ export class QscDebugSession extends LoggingDebugSession {
     this.sendResponse(response);
   }
 
+  protected async setFunctionBreakPointsRequest(
+    response: DebugProtocol.SetFunctionBreakpointsResponse,
+    args: DebugProtocol.SetFunctionBreakpointsArguments,
+    request?: DebugProtocol.Request,
+  ): Promise<void> {
+    log.trace(`setFunctionBreakPointsRequest: %O`, args);
+
+    const bps: DebugProtocol.Breakpoint[] = [];
+    const specializationBps: DebugProtocol.Breakpoint[] = [];
+    const options: IBreakpointOptions[] = [];
+    for (const functionBreakpoint of args.breakpoints) {
+      const locations = await this.debugService.getFunctionBreakpoints(
+        functionBreakpoint.name,
+      );
+      if (locations.length === 0) {
+        const bp = new Breakpoint(false) as DebugProtocol.Breakpoint;
+        bp.message = `No callable named '${functionBreakpoint.name}' was found.`;
+        bps.push(bp);
+        continue;
+      }
+      // A callable with several specializations has a location for each one,
+      // but the client expects a single breakpoint per function breakpoint.
+      for (const [index, location] of locations.entries()) {
+        const bp = this.createBreakpoint(location.id, {
+          line: this.convertDebuggerLineToClient(
+            location.location.span.start.line,
+          ),
+          column: this.convertDebuggerColumnToClient(
+            location.location.span.start.character,
+          ),
+          endLine: this.convertDebuggerLineToClient(
+            location.location.span.end.line,
+          ),
+          endColumn: this.convertDebuggerColumnToClient(
+            location.location.span.end.character,
+          ),
+        });
+        const uri = location.location.source;
+        bp.source = new Source(
+          basename(vscode.Uri.parse(uri).path) ?? uri,
+          uri,
+          undefined,
+          undefined,
+          "qsharp-adapter-data",
+        );
+        if (index === 0) {
+          bps.push(bp);
+        } else {
+          specializationBps.push(bp);
+        }
+        options.push(
+          this.createBreakpointOptions(location.id, functionBreakpoint),
+        );
+      }
+    }
+
+    this.functionBreakpoints = [...bps, ...specializationBps];
+    this.functionBreakpointOptions = options;
+    await this.updateBreakpointOptions();
+
+    response.body = {
+      breakpoints: bps,
+    };
+
+    log.trace(`setFunctionBreakPointsResponse: %O`, response);
+    this.sendResponse(response);
+  }
+
+  private createBreakpointOptions(
+    id: number | undefined,
+    breakpoint: {
+      condition?: string;
+      hitCondition?: string;
+      logMessage?: string;
+    },
+  ): IBreakpointOptions {
+    return {
+      id: id ?? 0,
+      condition: breakpoint.condition || undefined,
+      hitCondition: breakpoint.hitCondition || undefined,
+      logMessage: breakpoint.logMessage || undefined,
+    };
+  }
+
   protected threadsRequest(response: DebugProtocol.ThreadsResponse): void {
     log.trace(`threadRequest`);
     response.body = {
@@ -736,7 +890,11 @@ export class QscDebugSession extends LoggingDebugSession {
     log.trace(`scopesRequest: %O`, args);
     response.body = {
       scopes: [
-        new Scope("Locals", this.variableHandles.create("locals"), false),
+        new Scope(
+          "Locals",
+          this.variableHandles.create({ frameId: args.frameId }),
+          false,
+        ),
         new Scope(
           "Quantum State",
           this.variableHandles.create("quantum"),
@@ -765,26 +923,24 @@ export class QscDebugSession extends LoggingDebugSession {
     };
 
     const handle = this.variableHandles.get(args.variablesReference);
-    switch (handle) {
-      case "locals":
-        {
-          const locals = await this.debugService.getLocalVariables();
-          const variables = locals.map((local) => {
-            const variable: DebugProtocol.Variable = {
-              name: local.name,
-              value: local.value,
-              variablesReference: 0,
-            };
-            if (this.supportsVariableType) {
-              variable.type = local.var_type;
-            }
-            return variable;
-          });
-          response.body = {
-            variables: variables,
-          };
+    if (typeof handle === "object") {
+      const locals = await this.debugService.getLocalVariables(handle.frameId);
+      const variables = locals.map((local) => {
+        const variable: DebugProtocol.Variable = {
+          name: local.name,
+          value: local.value,
+          variablesReference: 0,
+        };
+        if (this.supportsVariableType) {
+          variable.type = local.var_type;
         }
-        break;
+        return variable;
+      });
+      response.body = {
+        variables: variables,
+      };
+    }
+    switch (handle) {
       case "quantum":
         {
           const associationId = getRandomGuid();
@@ -844,6 +1000,39 @@ export class QscDebugSession extends LoggingDebugSession {
     this.sendResponse(response);
   }
 
+  protected async evaluateRequest(
+    response: DebugProtocol.EvaluateResponse,
+    args: DebugProtocol.EvaluateArguments,
+  ): Promise<void> {
+    log.trace(`evaluateRequest: %O`, args);
+    const frameId =
+      args.frameId ?? (await this.debugService.getStackFrames()).length - 1;
+    // Only expressions typed into the debug console may call operations or
+    // allocate qubits, since those change the state of the program.
+    const allowQuantum = args.context === "repl";
+    try {
+      const result = await this.debugService.evaluate(
+        args.expression,
+        frameId,
+        allowQuantum,
+        this.eventTarget,
+      );
+      response.body = { result, variablesReference: 0 };
+      this.sendResponse(response);
+      if (allowQuantum) {
+        this.sendEvent(new InvalidatedEvent(["variables"]));
+        await this.updateCircuit();
+      }
+    } catch (e) {
+      this.sendErrorResponse(response, {
+        id: -1,
+        format: `${e}`,
+        showUser: false,
+      });
+    }
+    log.trace(`evaluateResponse: %O`, response);
+  }
+
   private createBreakpoint(
     id: number,
     location: DebugProtocol.BreakpointLocation,
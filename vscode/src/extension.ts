@@ -12,6 +12,7 @@ import {
 } from "qsharp-lang";
 import * as vscode from "vscode";
 import { initAzureWorkspaces } from "./azure/commands.js";
+import { createCallHierarchyProvider } from "./callHierarchy.js";
 import { createCodeActionsProvider } from "./codeActions.js";
 import { createCodeLensProvider } from "./codeLens.js";
 import {
@@ -20,13 +21,14 @@ import {
   qsharpLanguageId,
 } from "./common.js";
 import { createCompletionItemProvider } from "./completion";
-import { getTarget } from "./config";
+import { getInlayHintsConfig, getTarget } from "./config";
 import { initProjectCreator } from "./createProject.js";
 import { activateDebugger } from "./debugger/activate";
 import { createDefinitionProvider } from "./definition";
 import { startCheckingQSharp } from "./diagnostics";
 import { createFormattingProvider } from "./format.js";
 import { createHoverProvider } from "./hover";
+import { createInlayHintsProvider } from "./inlayHints.js";
 import {
   Logging,
   initLogForwarder,
@@ -50,8 +52,16 @@ import {
 import { initCodegen } from "./qirGeneration.js";
 import { createReferenceProvider } from "./references.js";
 import { createRenameProvider } from "./rename.js";
+import {
+  createSemanticTokensProvider,
+  semanticTokensLegend,
+} from "./semanticTokens.js";
 import { createSignatureHelpProvider } from "./signature.js";
 import { activateTargetProfileStatusBarItem } from "./statusbar.js";
+import {
+  createDocumentSymbolProvider,
+  createWorkspaceSymbolProvider,
+} from "./symbols.js";
 import {
   EventType,
   QsharpDocumentType,
@@ -328,6 +338,47 @@ async function activateLanguageService(extensionUri: vscode.Uri) {
     ),
   );
 
+  // semantic tokens
+  subscriptions.push(
+    vscode.languages.registerDocumentSemanticTokensProvider(
+      qsharpLanguageId,
+      createSemanticTokensProvider(languageService),
+      semanticTokensLegend,
+    ),
+  );
+
+  // inlay hints
+  const inlayHintsProvider = createInlayHintsProvider(languageService);
+  subscriptions.push(
+    vscode.languages.registerInlayHintsProvider(
+      qsharpLanguageId,
+      inlayHintsProvider,
+    ),
+  );
+  subscriptions.push(inlayHintsProvider);
+
+  // call hierarchy
+  subscriptions.push(
+    vscode.languages.registerCallHierarchyProvider(
+      qsharpLanguageId,
+      createCallHierarchyProvider(languageService),
+    ),
+  );
+
+  // document and workspace symbols
+  subscriptions.push(
+    vscode.languages.registerDocumentSymbolProvider(
+      qsharpLanguageId,
+      createDocumentSymbolProvider(languageService),
+    ),
+  );
+
+  subscriptions.push(
+    vscode.languages.registerWorkspaceSymbolProvider(
+      createWorkspaceSymbolProvider(languageService),
+    ),
+  );
+
   // add the language service dispose handler as well
   subscriptions.push(languageService);
 
@@ -350,6 +401,7 @@ async function updateLanguageServiceProfile(languageService: ILanguageService) {
   languageService.updateConfiguration({
     targetProfile: targetProfile,
     lints: [{ lint: "needlessOperation", level: "warn" }],
+    inlayHints: getInlayHintsConfig(),
   });
 }
 
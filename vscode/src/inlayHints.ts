@@ -0,0 +1,61 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+import { IInlayHint, ILanguageService } from "qsharp-lang";
+import * as vscode from "vscode";
+import { getInlayHintsConfig } from "./config";
+
+export function createInlayHintsProvider(languageService: ILanguageService) {
+  return new QSharpInlayHintsProvider(languageService);
+}
+
+class QSharpInlayHintsProvider
+  implements vscode.InlayHintsProvider, vscode.Disposable
+{
+  private changeEmitter = new vscode.EventEmitter<void>();
+  private configListener: vscode.Disposable;
+  onDidChangeInlayHints = this.changeEmitter.event;
+
+  constructor(public languageService: ILanguageService) {
+    // Hints are computed by the language service, so send it the new
+    // settings before asking VS Code to request them again.
+    this.configListener = vscode.workspace.onDidChangeConfiguration(
+      async (event) => {
+        if (event.affectsConfiguration("Q#.inlayHints")) {
+          await this.languageService.updateConfiguration({
+            inlayHints: getInlayHintsConfig(),
+          });
+          this.changeEmitter.fire();
+        }
+      },
+    );
+  }
+
+  async provideInlayHints(
+    document: vscode.TextDocument,
+    range: vscode.Range,
+  ): Promise<vscode.InlayHint[]> {
+    const hints = await this.languageService.getInlayHints(
+      document.uri.toString(),
+      range,
+    );
+    return hints.map(toVscodeInlayHint);
+  }
+
+  dispose() {
+    this.configListener.dispose();
+    this.changeEmitter.dispose();
+  }
+}
+
+function toVscodeInlayHint(hint: IInlayHint): vscode.InlayHint {
+  const inlayHint = new vscode.InlayHint(
+    new vscode.Position(hint.position.line, hint.position.character),
+    hint.label,
+    hint.kind === "type"
+      ? vscode.InlayHintKind.Type
+      : vscode.InlayHintKind.Parameter,
+  );
+  inlayHint.paddingRight = hint.kind === "parameter";
+  return inlayHint;
+}
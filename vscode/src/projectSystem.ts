@@ -210,6 +210,7 @@ async function singleFileProject(
     },
     lints: [],
     errors: [],
+    target: undefined,
   };
 }
 
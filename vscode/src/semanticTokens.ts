@@ -0,0 +1,58 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+import { ILanguageService } from "qsharp-lang";
+import * as vscode from "vscode";
+import { toVscodeRange } from "./common";
+
+// The custom token types and modifiers are declared in package.json.
+export const semanticTokensLegend = new vscode.SemanticTokensLegend(
+  [
+    "namespace",
+    "struct",
+    "typeParameter",
+    "parameter",
+    "variable",
+    "property",
+    "function",
+    "operation",
+  ],
+  [
+    "declaration",
+    "readonly",
+    "mutable",
+    "qubit",
+    "deprecated",
+    "adjointable",
+    "controllable",
+  ],
+);
+
+export function createSemanticTokensProvider(
+  languageService: ILanguageService,
+) {
+  return new QSharpSemanticTokensProvider(languageService);
+}
+
+class QSharpSemanticTokensProvider
+  implements vscode.DocumentSemanticTokensProvider
+{
+  constructor(public languageService: ILanguageService) {}
+
+  async provideDocumentSemanticTokens(
+    document: vscode.TextDocument,
+  ): Promise<vscode.SemanticTokens> {
+    const tokens = await this.languageService.getSemanticTokens(
+      document.uri.toString(),
+    );
+    const builder = new vscode.SemanticTokensBuilder(semanticTokensLegend);
+    for (const token of tokens) {
+      builder.push(
+        toVscodeRange(token.range),
+        token.tokenType,
+        token.modifiers,
+      );
+    }
+    return builder.build();
+  }
+}
@@ -0,0 +1,86 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+import {
+  IDocumentSymbol,
+  ILanguageService,
+  IWorkspaceSymbol,
+} from "qsharp-lang";
+import * as vscode from "vscode";
+import { toVscodeLocation, toVscodeRange } from "./common";
+
+export function createDocumentSymbolProvider(
+  languageService: ILanguageService,
+) {
+  return new QSharpDocumentSymbolProvider(languageService);
+}
+
+export function createWorkspaceSymbolProvider(
+  languageService: ILanguageService,
+) {
+  return new QSharpWorkspaceSymbolProvider(languageService);
+}
+
+class QSharpDocumentSymbolProvider implements vscode.DocumentSymbolProvider {
+  constructor(public languageService: ILanguageService) {}
+
+  async provideDocumentSymbols(
+    document: vscode.TextDocument,
+  ): Promise<vscode.DocumentSymbol[]> {
+    const symbols = await this.languageService.getDocumentSymbols(
+      document.uri.toString(),
+    );
+    return symbols.map(toVscodeDocumentSymbol);
+  }
+}
+
+class QSharpWorkspaceSymbolProvider
+  implements vscode.WorkspaceSymbolProvider
+{
+  constructor(public languageService: ILanguageService) {}
+
+  async provideWorkspaceSymbols(
+    query: string,
+  ): Promise<vscode.SymbolInformation[]> {
+    const symbols = await this.languageService.getWorkspaceSymbols(query);
+    return symbols.map(
+      (symbol) =>
+        new vscode.SymbolInformation(
+          symbol.name,
+          toVscodeSymbolKind(symbol.kind),
+          symbol.containerName ?? "",
+          toVscodeLocation(symbol.location),
+        ),
+    );
+  }
+}
+
+function toVscodeDocumentSymbol(
+  symbol: IDocumentSymbol,
+): vscode.DocumentSymbol {
+  const documentSymbol = new vscode.DocumentSymbol(
+    symbol.name,
+    symbol.detail ?? "",
+    toVscodeSymbolKind(symbol.kind),
+    toVscodeRange(symbol.range),
+    toVscodeRange(symbol.selectionRange),
+  );
+  documentSymbol.children = symbol.children.map(toVscodeDocumentSymbol);
+  return documentSymbol;
+}
+
+function toVscodeSymbolKind(
+  kind: IDocumentSymbol["kind"] | IWorkspaceSymbol["kind"],
+): vscode.SymbolKind {
+  switch (kind) {
+    case "namespace":
+      return vscode.SymbolKind.Namespace;
+    case "function":
+    case "operation":
+      return vscode.SymbolKind.Function;
+    case "struct":
+      return vscode.SymbolKind.Struct;
+    case "field":
+      return vscode.SymbolKind.Field;
+  }
+}
@@ -6,8 +6,9 @@ use crate::project_system::{into_qsc_args, ProgramConfig};
 use crate::{serializable_type, CallbackReceiver};
 use qsc::fir::StmtId;
 use qsc::fmt_complex;
-use qsc::interpret::{Debugger, Error, StepAction, StepResult};
+use qsc::interpret::{Breakpoint, Debugger, Error, StepAction, StepResult};
 use qsc::line_column::Encoding;
+use rustc_hash::FxHashMap;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 use wasm_bindgen::prelude::*;
@@ -16,6 +17,8 @@ use wasm_bindgen::prelude::*;
 #[derive(Default)]
 pub struct DebugService {
     debugger: Option<Debugger>,
+    /// Conditions and log messages of breakpoints, by breakpoint id.
+    breakpoint_options: FxHashMap<u32, BreakpointOptions>,
 }
 
 #[wasm_bindgen]
@@ -111,7 +114,7 @@ impl DebugService {
         if !event_cb.is_function() {
             return Err(JsError::new("Events callback function must be provided").into());
         }
-        let bps: Vec<_> = ids.iter().map(|f| StmtId::from(*f)).collect();
+        let bps = self.breakpoints(ids);
 
         let event_cb = |msg: &str| {
             // See example at https://rustwasm.github.io/wasm-bindgen/reference/receiving-js-closures-in-rust.html
@@ -123,17 +126,53 @@ impl DebugService {
         }
     }
 
+    pub fn eval_step_back(&mut self) -> Result<IStructStepResult, JsValue> {
+        match self.debugger_mut().step_back() {
+            Ok(value) => Ok(StructStepResult::from(value).into()),
+            Err(e) => Err(JsError::from(&e[0]).into()),
+        }
+    }
+
+    pub fn eval_reverse_continue(&mut self, ids: &[u32]) -> Result<IStructStepResult, JsValue> {
+        let bps = self.breakpoints(ids);
+        match self.debugger_mut().reverse_continue(&bps) {
+            Ok(value) => Ok(StructStepResult::from(value).into()),
+            Err(e) => Err(JsError::from(&e[0]).into()),
+        }
+    }
+
+    pub fn set_checkpoint_interval(&mut self, interval: usize) {
+        self.debugger_mut().set_checkpoint_interval(interval);
+    }
+
+    /// Returns the breakpoints with the given ids, along with their options.
+    fn breakpoints(&self, ids: &[u32]) -> Vec<Breakpoint> {
+        ids.iter()
+            .map(|id| match self.breakpoint_options.get(id) {
+                Some(options) => Breakpoint {
+                    id: StmtId::from(*id),
+                    condition: options.condition.clone(),
+                    hit_condition: options.hit_condition.clone(),
+                    log_message: options.log_message.clone(),
+                },
+                None => StmtId::from(*id).into(),
+            })
+            .collect()
+    }
+
     fn run_internal<F>(
         &mut self,
         event_cb: F,
-        bps: &[StmtId],
+        bps: &[Breakpoint],
         step: StepAction,
     ) -> Result<StepResult, Vec<Error>>
     where
         F: Fn(&str),
     {
         let mut out = CallbackReceiver { event_cb };
-        let result = self.debugger_mut().eval_step(&mut out, bps, step);
+        let result = self
+            .debugger_mut()
+            .eval_step_with_breakpoints(&mut out, bps, step);
         let mut success = true;
 
         let msg: Option<serde_json::Value> = match &result {
@@ -180,6 +219,35 @@ impl DebugService {
         .into()
     }
 
+    /// Returns the breakpoint locations on entry to the callables with the given name,
+    /// which may be qualified with its namespace.
+    pub fn get_function_breakpoints(&self, name: &str) -> IFunctionBreakpointList {
+        let bps = self.debugger().get_function_breakpoints(name);
+
+        FunctionBreakpointList {
+            breakpoints: bps
+                .into_iter()
+                .map(|bp| FunctionBreakpoint {
+                    id: bp.id,
+                    location: bp.location.into(),
+                })
+                .collect(),
+        }
+        .into()
+    }
+
+    /// Replaces the conditions, hit conditions and log messages used for breakpoints
+    /// in subsequent evaluation. Breakpoints without options always stop.
+    #[allow(clippy::needless_pass_by_value)] // needed for wasm_bindgen
+    pub fn set_breakpoint_options(&mut self, options: IBreakpointOptionsList) {
+        let options: BreakpointOptionsList = options.into();
+        self.breakpoint_options = options
+            .breakpoints
+            .into_iter()
+            .map(|options| (options.id, options))
+            .collect();
+    }
+
     pub fn get_locals(&self) -> IVariableList {
         let locals = self.debugger().get_locals();
         let variables: Vec<_> = locals
@@ -193,6 +261,42 @@ impl DebugService {
         VariableList { variables }.into()
     }
 
+    pub fn get_locals_in_frame(&self, frame_id: usize) -> IVariableList {
+        let locals = self.debugger().get_locals_in_frame(frame_id);
+        let variables: Vec<_> = locals
+            .into_iter()
+            .map(|local| Variable {
+                name: (*local.name).to_string(),
+                value: local.value.to_string(),
+                var_type: local.type_name,
+            })
+            .collect();
+        VariableList { variables }.into()
+    }
+
+    pub fn evaluate(
+        &mut self,
+        event_cb: &js_sys::Function,
+        expr: &str,
+        frame_id: usize,
+        allow_quantum: bool,
+    ) -> Result<String, JsValue> {
+        if !event_cb.is_function() {
+            return Err(JsError::new("Events callback function must be provided").into());
+        }
+        let event_cb = |msg: &str| {
+            let _ = event_cb.call1(&JsValue::null(), &JsValue::from(msg));
+        };
+        let mut out = CallbackReceiver { event_cb };
+        match self
+            .debugger_mut()
+            .evaluate(&mut out, expr, frame_id, allow_quantum)
+        {
+            Ok(value) => Ok(value.to_string()),
+            Err(e) => Err(JsError::from(&e[0]).into()),
+        }
+    }
+
     fn debugger(&self) -> &Debugger {
         self.debugger
             .as_ref()
@@ -317,6 +421,58 @@ serializable_type! {
     }"#
 }
 
+serializable_type! {
+    FunctionBreakpointList,
+    {
+        pub breakpoints: Vec<FunctionBreakpoint>,
+    },
+    r#"export interface IFunctionBreakpointList {
+        breakpoints: Array<IFunctionBreakpoint>
+    }
+    "#,
+    IFunctionBreakpointList
+}
+
+serializable_type! {
+    FunctionBreakpoint,
+    {
+        pub id: u32,
+        pub location: Location
+    },
+    r#"export interface IFunctionBreakpoint {
+        id: number;
+        location: ILocation;
+    }"#
+}
+
+serializable_type! {
+    BreakpointOptionsList,
+    {
+        pub breakpoints: Vec<BreakpointOptions>,
+    },
+    r#"export interface IBreakpointOptionsList {
+        breakpoints: Array<IBreakpointOptions>
+    }
+    "#,
+    IBreakpointOptionsList
+}
+
+serializable_type! {
+    BreakpointOptions,
+    {
+        pub id: u32,
+        pub condition: Option<String>,
+        pub hit_condition: Option<String>,
+        pub log_message: Option<String>,
+    },
+    r#"export interface IBreakpointOptions {
+        id: number;
+        condition?: string;
+        hitCondition?: string;
+        logMessage?: string;
+    }"#
+}
+
 serializable_type! {
     StackFrameList,
     {
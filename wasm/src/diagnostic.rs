@@ -271,6 +271,15 @@ fn interpret_error_labels(err: &interpret::Error) -> Vec<Label> {
         interpret::Error::NoEntryPoint
         | interpret::Error::UnsupportedRuntimeCapabilities
         | interpret::Error::Circuit(_)
-        | interpret::Error::NotAnOperation => Vec::new(),
+        | interpret::Error::NotAnOperation
+        | interpret::Error::NotAnItem
+        | interpret::Error::NotAHostIntrinsic(_)
+        | interpret::Error::UnsupportedHostType(..)
+        | interpret::Error::HostTypeMismatch(..)
+        | interpret::Error::FrameNotFound(_)
+        | interpret::Error::UnknownVariableType(_)
+        | interpret::Error::QuantumSideEffects
+        | interpret::Error::NoCheckpoint
+        | interpret::Error::Target(_) => Vec::new(),
     }
 }
@@ -86,6 +86,14 @@ impl LanguageService {
                     .languageFeatures
                     .map(|features| features.iter().collect::<LanguageFeatures>()),
                 lints_config: config.lints,
+                inlay_hints: config.inlayHints.map(|inlay_hints| {
+                    qsls::protocol::InlayHintsConfig {
+                        variable_types: inlay_hints.variableTypes,
+                        lambda_parameter_types: inlay_hints.lambdaParameterTypes,
+                        parameter_names: inlay_hints.parameterNames,
+                        partial_application_types: inlay_hints.partialApplicationTypes,
+                    }
+                }),
             });
     }
 
@@ -292,6 +300,128 @@ impl LanguageService {
         })
     }
 
+    pub fn get_inlay_hints(&self, uri: &str, range: IRange) -> Vec<IInlayHint> {
+        let range: Range = range.into();
+        let hints = self.0.get_inlay_hints(uri, range.into());
+        hints
+            .into_iter()
+            .map(|hint| {
+                InlayHint {
+                    position: hint.position.into(),
+                    label: hint.label,
+                    kind: (match hint.kind {
+                        qsls::protocol::InlayHintKind::Type => "type",
+                        qsls::protocol::InlayHintKind::Parameter => "parameter",
+                    })
+                    .to_string(),
+                }
+                .into()
+            })
+            .collect()
+    }
+
+    pub fn get_semantic_tokens(&self, uri: &str) -> Vec<ISemanticToken> {
+        let tokens = self.0.get_semantic_tokens(uri);
+        tokens
+            .into_iter()
+            .map(|token| {
+                SemanticToken {
+                    range: token.range.into(),
+                    tokenType: (match token.token_type {
+                        qsls::protocol::SemanticTokenType::Namespace => "namespace",
+                        qsls::protocol::SemanticTokenType::Struct => "struct",
+                        qsls::protocol::SemanticTokenType::TypeParameter => "typeParameter",
+                        qsls::protocol::SemanticTokenType::Parameter => "parameter",
+                        qsls::protocol::SemanticTokenType::Variable => "variable",
+                        qsls::protocol::SemanticTokenType::Property => "property",
+                        qsls::protocol::SemanticTokenType::Function => "function",
+                        qsls::protocol::SemanticTokenType::Operation => "operation",
+                    })
+                    .to_string(),
+                    modifiers: token
+                        .modifiers
+                        .into_iter()
+                        .map(|modifier| {
+                            (match modifier {
+                                qsls::protocol::SemanticTokenModifier::Declaration => "declaration",
+                                qsls::protocol::SemanticTokenModifier::Readonly => "readonly",
+                                qsls::protocol::SemanticTokenModifier::Mutable => "mutable",
+                                qsls::protocol::SemanticTokenModifier::Qubit => "qubit",
+                                qsls::protocol::SemanticTokenModifier::Deprecated => "deprecated",
+                                qsls::protocol::SemanticTokenModifier::Adjointable => "adjointable",
+                                qsls::protocol::SemanticTokenModifier::Controllable => {
+                                    "controllable"
+                                }
+                            })
+                            .to_string()
+                        })
+                        .collect(),
+                }
+                .into()
+            })
+            .collect()
+    }
+
+    pub fn get_document_symbols(&self, uri: &str) -> Vec<IDocumentSymbol> {
+        let symbols = self.0.get_document_symbols(uri);
+        symbols
+            .into_iter()
+            .map(|symbol| DocumentSymbol::from(symbol).into())
+            .collect()
+    }
+
+    pub fn get_workspace_symbols(&self, query: &str) -> Vec<IWorkspaceSymbol> {
+        let symbols = self.0.get_workspace_symbols(query);
+        symbols
+            .into_iter()
+            .map(|symbol| {
+                WorkspaceSymbol {
+                    name: symbol.name,
+                    kind: symbol_kind(symbol.kind).to_string(),
+                    containerName: symbol.container_name,
+                    location: symbol.location.into(),
+                }
+                .into()
+            })
+            .collect()
+    }
+
+    pub fn prepare_call_hierarchy(
+        &self,
+        uri: &str,
+        position: IPosition,
+    ) -> Option<ICallHierarchyItem> {
+        let position: Position = position.into();
+        let item = self.0.prepare_call_hierarchy(uri, position.into());
+        item.map(|item| CallHierarchyItem::from(item).into())
+    }
+
+    pub fn get_incoming_calls(
+        &self,
+        uri: &str,
+        item: ICallHierarchyItem,
+    ) -> Vec<ICallHierarchyCall> {
+        let item: CallHierarchyItem = item.into();
+        let calls = self.0.get_incoming_calls(uri, item.into());
+        calls
+            .into_iter()
+            .map(|call| CallHierarchyCall::from(call).into())
+            .collect()
+    }
+
+    pub fn get_outgoing_calls(
+        &self,
+        uri: &str,
+        item: ICallHierarchyItem,
+    ) -> Vec<ICallHierarchyCall> {
+        let item: CallHierarchyItem = item.into();
+        let calls = self.0.get_outgoing_calls(uri, item.into());
+        calls
+            .into_iter()
+            .map(|call| CallHierarchyCall::from(call).into())
+            .collect()
+    }
+
     pub fn get_code_lenses(&self, uri: &str) -> Vec<ICodeLens> {
         let code_lenses = self.0.get_code_lenses(uri);
         code_lenses
@@ -328,17 +458,35 @@ serializable_type! {
         pub targetProfile: Option<String>,
         pub packageType: Option<String>,
         pub languageFeatures: Option<Vec<String>>,
-        pub lints: Option<Vec<LintConfig>>
+        pub lints: Option<Vec<LintConfig>>,
+        pub inlayHints: Option<InlayHintsConfiguration>
     },
     r#"export interface IWorkspaceConfiguration {
         targetProfile?: TargetProfile;
         packageType?: "exe" | "lib";
         languageFeatures?: LanguageFeatures[];
         lints?: { lint: string; level: string }[];
+        inlayHints?: IInlayHintsConfiguration;
     }"#,
     IWorkspaceConfiguration
 }
 
+serializable_type! {
+    InlayHintsConfiguration,
+    {
+        pub variableTypes: bool,
+        pub lambdaParameterTypes: bool,
+        pub parameterNames: bool,
+        pub partialApplicationTypes: bool,
+    },
+    r#"export interface IInlayHintsConfiguration {
+        variableTypes: boolean;
+        lambdaParameterTypes: boolean;
+        parameterNames: boolean;
+        partialApplicationTypes: boolean;
+    }"#
+}
+
 serializable_type! {
     CodeAction,
     {
@@ -509,6 +657,172 @@ serializable_type! {
     ICodeLens
 }
 
+serializable_type! {
+    SemanticToken,
+    {
+        pub range: Range,
+        pub tokenType: String,
+        pub modifiers: Vec<String>,
+    },
+    r#"export interface ISemanticToken {
+        range: IRange;
+        tokenType: "namespace" | "struct" | "typeParameter" | "parameter" | "variable" | "property" | "function" | "operation";
+        modifiers: ("declaration" | "readonly" | "mutable" | "qubit" | "deprecated" | "adjointable" | "controllable")[];
+    }"#,
+    ISemanticToken
+}
+
+serializable_type! {
+    InlayHint,
+    {
+        pub position: Position,
+        pub label: String,
+        pub kind: String,
+    },
+    r#"export interface IInlayHint {
+        position: IPosition;
+        label: string;
+        kind: "type" | "parameter";
+    }"#,
+    IInlayHint
+}
+
+serializable_type! {
+    DocumentSymbol,
+    {
+        pub name: String,
+        pub detail: Option<String>,
+        pub kind: String,
+        pub range: Range,
+        pub selectionRange: Range,
+        pub children: Vec<DocumentSymbol>,
+    },
+    r#"export interface IDocumentSymbol {
+        name: string;
+        detail?: string;
+        kind: "namespace" | "function" | "operation" | "struct" | "field";
+        range: IRange;
+        selectionRange: IRange;
+        children: IDocumentSymbol[];
+    }"#,
+    IDocumentSymbol
+}
+
+impl From<qsls::protocol::DocumentSymbol> for DocumentSymbol {
+    fn from(symbol: qsls::protocol::DocumentSymbol) -> Self {
+        Self {
+            name: symbol.name,
+            detail: symbol.detail,
+            kind: symbol_kind(symbol.kind).to_string(),
+            range: symbol.range.into(),
+            selectionRange: symbol.selection_range.into(),
+            children: symbol.children.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+serializable_type! {
+    WorkspaceSymbol,
+    {
+        pub name: String,
+        pub kind: String,
+        pub containerName: Option<String>,
+        pub location: Location,
+    },
+    r#"export interface IWorkspaceSymbol {
+        name: string;
+        kind: "namespace" | "function" | "operation" | "struct" | "field";
+        containerName?: string;
+        location: ILocation;
+    }"#,
+    IWorkspaceSymbol
+}
+
+serializable_type! {
+    CallHierarchyItem,
+    {
+        pub name: String,
+        pub kind: String,
+        pub containerName: Option<String>,
+        pub location: Location,
+        pub selectionRange: Range,
+    },
+    r#"export interface ICallHierarchyItem {
+        name: string;
+        kind: "function" | "operation";
+        containerName?: string;
+        location: ILocation;
+        selectionRange: IRange;
+    }"#,
+    ICallHierarchyItem
+}
+
+impl From<qsls::protocol::CallHierarchyItem> for CallHierarchyItem {
+    fn from(item: qsls::protocol::CallHierarchyItem) -> Self {
+        CallHierarchyItem {
+            name: item.name,
+            kind: symbol_kind(item.kind).to_string(),
+            containerName: item.container_name,
+            location: item.location.into(),
+            selectionRange: item.selection_range.into(),
+        }
+    }
+}
+
+impl From<CallHierarchyItem> for qsls::protocol::CallHierarchyItem {
+    fn from(item: CallHierarchyItem) -> Self {
+        qsls::protocol::CallHierarchyItem {
+            name: item.name,
+            kind: match item.kind.as_str() {
+                "function" => qsls::protocol::SymbolKind::Function,
+                "operation" => qsls::protocol::SymbolKind::Operation,
+                _ => panic!("invalid call hierarchy item kind"),
+            },
+            container_name: item.containerName,
+            location: item.location.into(),
+            selection_range: item.selectionRange.into(),
+        }
+    }
+}
+
+serializable_type! {
+    CallHierarchyCall,
+    {
+        pub item: CallHierarchyItem,
+        pub adjoint: bool,
+        pub controlled: u8,
+        pub fromRanges: Vec<Range>,
+    },
+    r#"export interface ICallHierarchyCall {
+        item: ICallHierarchyItem;
+        adjoint: boolean;
+        controlled: number;
+        fromRanges: IRange[];
+    }"#,
+    ICallHierarchyCall
+}
+
+impl From<qsls::protocol::CallHierarchyCall> for CallHierarchyCall {
+    fn from(call: qsls::protocol::CallHierarchyCall) -> Self {
+        CallHierarchyCall {
+            item: call.item.into(),
+            adjoint: call.functor_app.adjoint,
+            controlled: call.functor_app.controlled,
+            fromRanges: call.from_ranges.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+fn symbol_kind(kind: qsls::protocol::SymbolKind) -> &'static str {
+    match kind {
+        qsls::protocol::SymbolKind::Namespace => "namespace",
+        qsls::protocol::SymbolKind::Function => "function",
+        qsls::protocol::SymbolKind::Operation => "operation",
+        qsls::protocol::SymbolKind::Struct => "struct",
+        qsls::protocol::SymbolKind::Field => "field",
+    }
+}
+
 serializable_type! {
     OperationInfo,
     {
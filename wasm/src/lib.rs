@@ -9,7 +9,7 @@ use katas::check_solution;
 use language_service::IOperationInfo;
 use num_bigint::BigUint;
 use num_complex::Complex64;
-use project_system::{into_qsc_args, ProgramConfig};
+use project_system::{into_qsc_args, program_target, ProgramConfig};
 use qsc::{
     compile::{self, Dependencies},
     format_state_id, get_latex,
@@ -19,7 +19,7 @@ use qsc::{
         output::{self, Receiver},
         CircuitEntryPoint,
     },
-    target::Profile,
+    target::{Profile, TargetDescription},
     LanguageFeatures, PackageStore, PackageType, SourceContents, SourceMap, SourceName, SparseSim,
     TargetCapabilityFlags,
 };
@@ -57,26 +57,21 @@ pub fn git_hash() -> String {
 
 #[wasm_bindgen]
 pub fn get_qir(program: ProgramConfig) -> Result<String, String> {
-    let (source_map, capabilities, language_features, store, deps) =
+    let target = program_target(&program);
+    let (source_map, _, language_features, store, deps) =
         into_qsc_args(program, None).map_err(compile_errors_into_qsharp_errors_json)?;
 
-    _get_qir(
-        source_map,
-        language_features,
-        capabilities,
-        store,
-        &deps[..],
-    )
+    _get_qir(source_map, language_features, &target, store, &deps[..])
 }
 
 pub(crate) fn _get_qir(
     sources: SourceMap,
     language_features: LanguageFeatures,
-    capabilities: TargetCapabilityFlags,
+    target: &TargetDescription,
     store: PackageStore,
     deps: &qsc::compile::Dependencies,
 ) -> Result<String, String> {
-    qsc::codegen::get_qir(sources, language_features, capabilities, store, deps)
+    qsc::codegen::get_qir(sources, language_features, target, store, deps)
         .map_err(interpret_errors_into_qsharp_errors_json)
 }
 
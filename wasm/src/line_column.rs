@@ -89,3 +89,12 @@ impl From<qsc::location::Location> for Location {
         }
     }
 }
+
+impl From<Location> for qsc::location::Location {
+    fn from(location: Location) -> Self {
+        qsc::location::Location {
+            source: location.source.into(),
+            range: location.span.into(),
+        }
+    }
+}
@@ -4,7 +4,10 @@
 use crate::{diagnostic::project_errors_into_qsharp_errors, serializable_type};
 use async_trait::async_trait;
 use miette::Report;
-use qsc::{linter::LintConfig, packages::BuildableProgram, LanguageFeatures};
+use qsc::{
+    linter::LintConfig, packages::BuildableProgram, target::TargetDescription, LanguageFeatures,
+    TargetCapabilityFlags,
+};
 use qsc_project::{EntryType, FileSystemAsync, JSFileEntry, JSProjectHost, PackageCache};
 use rustc_hash::FxHashMap;
 use serde::{Deserialize, Serialize};
@@ -28,6 +31,20 @@ export interface IProjectHost {
 export interface IProgramConfig {
     packageGraphSources: IPackageGraphSources;
     profile: TargetProfile;
+    /**
+     * The target named by the project manifest, which takes precedence over the profile.
+     */
+    target: ITargetDescription | undefined;
+}
+
+/**
+ * A target described by a capability file.
+ */
+export interface ITargetDescription {
+    capabilities: string[];
+    gates?: string[];
+    maxQubits?: number;
+    maxMeasurements?: number;
 }
 "#;
 
@@ -71,6 +88,9 @@ extern "C" {
 
     #[wasm_bindgen(method, getter, structural)]
     fn profile(this: &ProgramConfig) -> String;
+
+    #[wasm_bindgen(method, getter, structural)]
+    fn target(this: &ProgramConfig) -> JsValue;
 }
 
 pub(crate) fn to_js_function(val: JsValue, help_text_panic: &'static str) -> js_sys::Function {
@@ -300,6 +320,7 @@ impl TryFrom<qsc_project::Project> for IProjectConfig {
             project_uri: value.path.to_string(),
             lints: value.lints,
             package_graph_sources: value.package_graph_sources.into(),
+            target: value.target,
         };
         Ok(project_config.into())
     }
@@ -312,6 +333,7 @@ serializable_type! {
         pub project_uri: String,
         pub package_graph_sources: PackageGraphSources,
         pub lints: Vec<LintConfig>,
+        pub target: Option<TargetDescription>,
     },
     r#"export interface IProjectConfig {
         /**
@@ -328,6 +350,10 @@ serializable_type! {
           level: string;
         }[];
         errors: string[];
+        /**
+         * The target named by the manifest, from its capability file
+         */
+        target: ITargetDescription | undefined;
     }"#,
     IProjectConfig
 }
@@ -373,6 +399,19 @@ impl From<PackageInfo> for qsc_project::PackageInfo {
     }
 }
 
+/// The target of the program: the one named by the project manifest, or else the target profile.
+pub(crate) fn program_target(program: &ProgramConfig) -> TargetDescription {
+    let target: Option<TargetDescription> = serde_wasm_bindgen::from_value(program.target())
+        .unwrap_or_else(|e| panic!("Invalid target description : {e}"));
+    target.unwrap_or_else(|| {
+        let capabilities: TargetCapabilityFlags =
+            qsc::target::Profile::from_str(&program.profile())
+                .unwrap_or_else(|()| panic!("Invalid target : {}", program.profile()))
+                .into();
+        capabilities.into()
+    })
+}
+
 /// This returns the common parameters that the compiler/interpreter uses
 #[allow(clippy::type_complexity)]
 #[allow(clippy::needless_pass_by_value)]
@@ -389,9 +428,7 @@ pub(crate) fn into_qsc_args(
     ),
     Vec<qsc::compile::Error>,
 > {
-    let capabilities = qsc::target::Profile::from_str(&program.profile())
-        .unwrap_or_else(|()| panic!("Invalid target : {}", program.profile()))
-        .into();
+    let capabilities = program_target(&program).capabilities;
 
     let pkg_graph: PackageGraphSources = program.packageGraphSources().into();
     let pkg_graph: qsc_project::PackageGraphSources = pkg_graph.into();
@@ -55,7 +55,7 @@ fn test_compile() {
     let result = qsc::codegen::get_qir(
         SourceMap::new([("test.qs".into(), code.into())], None),
         LanguageFeatures::default(),
-        TargetCapabilityFlags::empty(),
+        &TargetCapabilityFlags::empty().into(),
         store,
         &[(std_id, None)],
     );
@@ -500,5 +500,5 @@ fn code_with_errors_returns_errors() {
             "[{\"document\":\"test.qs\",\"diagnostic\":{\"range\":{\"start\":{\"line\":4,\"character\":16},\"end\":{\"line\":4,\"character\":19}},\"message\":\"syntax error: expected `;`, found keyword `let`\",\"severity\":\"error\",\"code\":\"Qsc.Parse.Token\"},\"stack\":null}]",
         )
     "#]]
-    .assert_debug_eq(&_get_qir(sources, language_features, capabilities, store, &[]));
+    .assert_debug_eq(&_get_qir(sources, language_features, &capabilities.into(), store, &[]));
 }